@@ -1,6 +1,8 @@
 //! CSS3 color names.
 
-use strum::{EnumIter, EnumString};
+use alloc::{boxed::Box, format, string::String};
+
+use strum::{EnumIter, EnumString, IntoEnumIterator};
 
 /// CSS3 colors as defined by [the W3C recommendation.](https://www.w3.org/TR/css-color-3/)
 #[derive(
@@ -158,3 +160,272 @@ pub enum Css3Color {
     YellowGreen,
 }
 
+impl Css3Color {
+    /// Returns an iterator over all CSS3 color names in declaration order.
+    ///
+    /// The British and American spellings of the gray colors (e.g. [`Css3Color::Gray`] and
+    /// [`Css3Color::Grey`]) are distinct variants, and so are both yielded.
+    pub fn iter() -> impl ExactSizeIterator<Item = Self> {
+        <Self as IntoEnumIterator>::iter()
+    }
+
+    /// Returns the `(red, green, blue)` components of this color.
+    pub const fn to_rgb(self) -> (u8, u8, u8) {
+        match self {
+            Self::AliceBlue => (0xf0, 0xf8, 0xff),
+            Self::AntiqueWhite => (0xfa, 0xeb, 0xd7),
+            Self::Aqua => (0x00, 0xff, 0xff),
+            Self::Aquamarine => (0x7f, 0xff, 0xd4),
+            Self::Azure => (0xf0, 0xff, 0xff),
+            Self::Beige => (0xf5, 0xf5, 0xdc),
+            Self::Bisque => (0xff, 0xe4, 0xc4),
+            Self::Black => (0x00, 0x00, 0x00),
+            Self::BlanchedAlmond => (0xff, 0xeb, 0xcd),
+            Self::Blue => (0x00, 0x00, 0xff),
+            Self::BlueViolet => (0x8a, 0x2b, 0xe2),
+            Self::Brown => (0xa5, 0x2a, 0x2a),
+            Self::BurlyWood => (0xde, 0xb8, 0x87),
+            Self::CadetBlue => (0x5f, 0x9e, 0xa0),
+            Self::Chartreuse => (0x7f, 0xff, 0x00),
+            Self::Chocolate => (0xd2, 0x69, 0x1e),
+            Self::Coral => (0xff, 0x7f, 0x50),
+            Self::CornflowerBlue => (0x64, 0x95, 0xed),
+            Self::Cornsilk => (0xff, 0xf8, 0xdc),
+            Self::Crimson => (0xdc, 0x14, 0x3c),
+            Self::Cyan => (0x00, 0xff, 0xff),
+            Self::DarkBlue => (0x00, 0x00, 0x8b),
+            Self::DarkCyan => (0x00, 0x8b, 0x8b),
+            Self::DarkGoldenRod => (0xb8, 0x86, 0x0b),
+            Self::DarkGray => (0xa9, 0xa9, 0xa9),
+            Self::DarkGrey => (0xa9, 0xa9, 0xa9),
+            Self::DarkGreen => (0x00, 0x64, 0x00),
+            Self::DarkKhaki => (0xbd, 0xb7, 0x6b),
+            Self::DarkMagenta => (0x8b, 0x00, 0x8b),
+            Self::DarkOliveGreen => (0x55, 0x6b, 0x2f),
+            Self::DarkOrange => (0xff, 0x8c, 0x00),
+            Self::DarkOrchid => (0x99, 0x32, 0xcc),
+            Self::DarkRed => (0x8b, 0x00, 0x00),
+            Self::DarkSalmon => (0xe9, 0x96, 0x7a),
+            Self::DarkSeaGreen => (0x8f, 0xbc, 0x8f),
+            Self::DarkSlateBlue => (0x48, 0x3d, 0x8b),
+            Self::DarkSlateGray => (0x2f, 0x4f, 0x4f),
+            Self::DarkSlateGrey => (0x2f, 0x4f, 0x4f),
+            Self::DarkTurquoise => (0x00, 0xce, 0xd1),
+            Self::DarkViolet => (0x94, 0x00, 0xd3),
+            Self::DeepPink => (0xff, 0x14, 0x93),
+            Self::DeepSkyBlue => (0x00, 0xbf, 0xff),
+            Self::DimGray => (0x69, 0x69, 0x69),
+            Self::DimGrey => (0x69, 0x69, 0x69),
+            Self::DodgerBlue => (0x1e, 0x90, 0xff),
+            Self::FireBrick => (0xb2, 0x22, 0x22),
+            Self::FloralWhite => (0xff, 0xfa, 0xf0),
+            Self::ForestGreen => (0x22, 0x8b, 0x22),
+            Self::Fuchsia => (0xff, 0x00, 0xff),
+            Self::Gainsboro => (0xdc, 0xdc, 0xdc),
+            Self::GhostWhite => (0xf8, 0xf8, 0xff),
+            Self::Gold => (0xff, 0xd7, 0x00),
+            Self::GoldenRod => (0xda, 0xa5, 0x20),
+            Self::Gray => (0x80, 0x80, 0x80),
+            Self::Grey => (0x80, 0x80, 0x80),
+            Self::Green => (0x00, 0x80, 0x00),
+            Self::GreenYellow => (0xad, 0xff, 0x2f),
+            Self::HoneyDew => (0xf0, 0xff, 0xf0),
+            Self::HotPink => (0xff, 0x69, 0xb4),
+            Self::IndianRed => (0xcd, 0x5c, 0x5c),
+            Self::Indigo => (0x4b, 0x00, 0x82),
+            Self::Ivory => (0xff, 0xff, 0xf0),
+            Self::Khaki => (0xf0, 0xe6, 0x8c),
+            Self::Lavender => (0xe6, 0xe6, 0xfa),
+            Self::LavenderBlush => (0xff, 0xf0, 0xf5),
+            Self::LawnGreen => (0x7c, 0xfc, 0x00),
+            Self::LemonChiffon => (0xff, 0xfa, 0xcd),
+            Self::LightBlue => (0xad, 0xd8, 0xe6),
+            Self::LightCoral => (0xf0, 0x80, 0x80),
+            Self::LightCyan => (0xe0, 0xff, 0xff),
+            Self::LightGoldenRodYellow => (0xfa, 0xfa, 0xd2),
+            Self::LightGray => (0xd3, 0xd3, 0xd3),
+            Self::LightGrey => (0xd3, 0xd3, 0xd3),
+            Self::LightGreen => (0x90, 0xee, 0x90),
+            Self::LightPink => (0xff, 0xb6, 0xc1),
+            Self::LightSalmon => (0xff, 0xa0, 0x7a),
+            Self::LightSeaGreen => (0x20, 0xb2, 0xaa),
+            Self::LightSkyBlue => (0x87, 0xce, 0xfa),
+            Self::LightSlateGray => (0x77, 0x88, 0x99),
+            Self::LightSlateGrey => (0x77, 0x88, 0x99),
+            Self::LightSteelBlue => (0xb0, 0xc4, 0xde),
+            Self::LightYellow => (0xff, 0xff, 0xe0),
+            Self::Lime => (0x00, 0xff, 0x00),
+            Self::LimeGreen => (0x32, 0xcd, 0x32),
+            Self::Linen => (0xfa, 0xf0, 0xe6),
+            Self::Magenta => (0xff, 0x00, 0xff),
+            Self::Maroon => (0x80, 0x00, 0x00),
+            Self::MediumAquaMarine => (0x66, 0xcd, 0xaa),
+            Self::MediumBlue => (0x00, 0x00, 0xcd),
+            Self::MediumOrchid => (0xba, 0x55, 0xd3),
+            Self::MediumPurple => (0x93, 0x70, 0xdb),
+            Self::MediumSeaGreen => (0x3c, 0xb3, 0x71),
+            Self::MediumSlateBlue => (0x7b, 0x68, 0xee),
+            Self::MediumSpringGreen => (0x00, 0xfa, 0x9a),
+            Self::MediumTurquoise => (0x48, 0xd1, 0xcc),
+            Self::MediumVioletRed => (0xc7, 0x15, 0x85),
+            Self::MidnightBlue => (0x19, 0x19, 0x70),
+            Self::MintCream => (0xf5, 0xff, 0xfa),
+            Self::MistyRose => (0xff, 0xe4, 0xe1),
+            Self::Moccasin => (0xff, 0xe4, 0xb5),
+            Self::NavajoWhite => (0xff, 0xde, 0xad),
+            Self::Navy => (0x00, 0x00, 0x80),
+            Self::OldLace => (0xfd, 0xf5, 0xe6),
+            Self::Olive => (0x80, 0x80, 0x00),
+            Self::OliveDrab => (0x6b, 0x8e, 0x23),
+            Self::Orange => (0xff, 0xa5, 0x00),
+            Self::OrangeRed => (0xff, 0x45, 0x00),
+            Self::Orchid => (0xda, 0x70, 0xd6),
+            Self::PaleGoldenRod => (0xee, 0xe8, 0xaa),
+            Self::PaleGreen => (0x98, 0xfb, 0x98),
+            Self::PaleTurquoise => (0xaf, 0xee, 0xee),
+            Self::PaleVioletRed => (0xdb, 0x70, 0x93),
+            Self::PapayaWhip => (0xff, 0xef, 0xd5),
+            Self::PeachPuff => (0xff, 0xda, 0xb9),
+            Self::Peru => (0xcd, 0x85, 0x3f),
+            Self::Pink => (0xff, 0xc0, 0xcb),
+            Self::Plum => (0xdd, 0xa0, 0xdd),
+            Self::PowderBlue => (0xb0, 0xe0, 0xe6),
+            Self::Purple => (0x80, 0x00, 0x80),
+            Self::Red => (0xff, 0x00, 0x00),
+            Self::RosyBrown => (0xbc, 0x8f, 0x8f),
+            Self::RoyalBlue => (0x41, 0x69, 0xe1),
+            Self::SaddleBrown => (0x8b, 0x45, 0x13),
+            Self::Salmon => (0xfa, 0x80, 0x72),
+            Self::SandyBrown => (0xf4, 0xa4, 0x60),
+            Self::SeaGreen => (0x2e, 0x8b, 0x57),
+            Self::SeaShell => (0xff, 0xf5, 0xee),
+            Self::Sienna => (0xa0, 0x52, 0x2d),
+            Self::Silver => (0xc0, 0xc0, 0xc0),
+            Self::SkyBlue => (0x87, 0xce, 0xeb),
+            Self::SlateBlue => (0x6a, 0x5a, 0xcd),
+            Self::SlateGray => (0x70, 0x80, 0x90),
+            Self::SlateGrey => (0x70, 0x80, 0x90),
+            Self::Snow => (0xff, 0xfa, 0xfa),
+            Self::SpringGreen => (0x00, 0xff, 0x7f),
+            Self::SteelBlue => (0x46, 0x82, 0xb4),
+            Self::Tan => (0xd2, 0xb4, 0x8c),
+            Self::Teal => (0x00, 0x80, 0x80),
+            Self::Thistle => (0xd8, 0xbf, 0xd8),
+            Self::Tomato => (0xff, 0x63, 0x47),
+            Self::Turquoise => (0x40, 0xe0, 0xd0),
+            Self::Violet => (0xee, 0x82, 0xee),
+            Self::Wheat => (0xf5, 0xde, 0xb3),
+            Self::White => (0xff, 0xff, 0xff),
+            Self::WhiteSmoke => (0xf5, 0xf5, 0xf5),
+            Self::Yellow => (0xff, 0xff, 0x00),
+            Self::YellowGreen => (0x9a, 0xcd, 0x32),
+        }
+    }
+
+    /// Returns this color as a lowercase `#rrggbb` hex string.
+    pub fn to_hex(self) -> String {
+        let (red, green, blue) = self.to_rgb();
+        format!("#{red:02x}{green:02x}{blue:02x}")
+    }
+
+    /// Returns the named color closest to the given `(red, green, blue)` components.
+    ///
+    /// Distance is measured as the squared Euclidean distance in RGB space. Ties (including the
+    /// exact matches shared by aliases such as [`Css3Color::Aqua`] and [`Css3Color::Cyan`]) are
+    /// broken in favour of the variant declared first.
+    pub fn from_rgb_nearest((red, green, blue): (u8, u8, u8)) -> Self {
+        let distance = |color: Self| {
+            let (r, g, b) = color.to_rgb();
+            let dr = i32::from(r) - i32::from(red);
+            let dg = i32::from(g) - i32::from(green);
+            let db = i32::from(b) - i32::from(blue);
+            dr * dr + dg * dg + db * db
+        };
+
+        // min_by_key returns the first minimum, which gives the documented tie-breaking
+        Self::iter().min_by_key(|&color| distance(color)).unwrap()
+    }
+
+    /// Parses a `#rrggbb` or `#rgb` hex string and returns the closest named color, as described
+    /// by [`Css3Color::from_rgb_nearest`]. The leading `#` is optional and hex digits are matched
+    /// case-insensitively.
+    pub fn from_hex_nearest(hex: &str) -> Result<Self, InvalidHexColorError> {
+        parse_hex_rgb(hex)
+            .map(Self::from_rgb_nearest)
+            .ok_or_else(|| InvalidHexColorError(hex.into()))
+    }
+}
+
+/// An error indicating that a string is not a valid `#rrggbb` or `#rgb` hex color.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("expected a hex color of the form #rrggbb or #rgb but received {0:?}")]
+pub struct InvalidHexColorError(Box<str>);
+
+fn parse_hex_rgb(hex: &str) -> Option<(u8, u8, u8)> {
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |i: usize, len: usize| u8::from_str_radix(&digits[i * len..(i + 1) * len], 16);
+
+    match digits.len() {
+        6 => Some((
+            channel(0, 2).ok()?,
+            channel(1, 2).ok()?,
+            channel(2, 2).ok()?,
+        )),
+        // each digit of the short form is repeated, so #abc is equivalent to #aabbcc
+        3 => Some((
+            channel(0, 1).ok()? * 0x11,
+            channel(1, 1).ok()? * 0x11,
+            channel(2, 1).ok()? * 0x11,
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iter_yields_every_variant() {
+        assert_eq!(Css3Color::iter().len(), 147);
+        assert_eq!(Css3Color::iter().next(), Some(Css3Color::AliceBlue));
+        assert_eq!(Css3Color::iter().last(), Some(Css3Color::YellowGreen));
+    }
+
+    #[test]
+    fn to_hex_matches_known_values() {
+        assert_eq!(Css3Color::Black.to_hex(), "#000000");
+        assert_eq!(Css3Color::White.to_hex(), "#ffffff");
+        assert_eq!(Css3Color::CornflowerBlue.to_hex(), "#6495ed");
+    }
+
+    #[test]
+    fn nearest_prefers_exact_match() {
+        for color in Css3Color::iter() {
+            let nearest = Css3Color::from_rgb_nearest(color.to_rgb());
+            assert_eq!(nearest.to_rgb(), color.to_rgb());
+        }
+
+        assert_eq!(Css3Color::from_hex_nearest("#00FFFF"), Ok(Css3Color::Aqua));
+        assert_eq!(Css3Color::from_hex_nearest("808080"), Ok(Css3Color::Gray));
+    }
+
+    #[test]
+    fn nearest_approximates_unnamed_colors() {
+        assert_eq!(Css3Color::from_hex_nearest("#fe0102"), Ok(Css3Color::Red));
+        assert_eq!(Css3Color::from_hex_nearest("#f00"), Ok(Css3Color::Red));
+        assert_eq!(Css3Color::from_hex_nearest("#010101"), Ok(Css3Color::Black));
+    }
+
+    #[test]
+    fn from_hex_nearest_rejects_malformed_input() {
+        for input in ["", "#", "#12345", "#1234567", "#gggggg", "#12 345", "#ÿÿÿ"] {
+            assert!(Css3Color::from_hex_nearest(input).is_err(), "{input:?}");
+        }
+    }
+}
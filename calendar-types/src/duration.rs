@@ -1,5 +1,11 @@
 //! RFC 5545 duration types.
 
+use alloc::format;
+use core::{
+    cmp::Ordering,
+    ops::{Add, Neg},
+};
+
 use thiserror::Error;
 
 use crate::{
@@ -22,6 +28,35 @@ pub enum InvalidDurationError {
     FractionalSecond(#[from] InvalidFractionalSecondError),
 }
 
+/// An error arising from arithmetic on [`Duration`] and [`SignedDuration`] values.
+#[derive(Debug, Clone, Copy, Error, PartialEq, Eq)]
+pub enum DurationArithmeticError {
+    /// The operation needs an exact length of time, but the duration has week or day components,
+    /// whose length depends on the time zone and the date to which the duration is applied.
+    #[error("nominal week and day components have no fixed length")]
+    Nominal,
+    /// The result is too large to be represented.
+    #[error("duration arithmetic overflowed")]
+    Overflow,
+}
+
+/// A policy for choosing the units in which a [`Duration`] is written.
+///
+/// Equal durations can be written in several ways, such as `PT90M` and `PT1H30M`; see
+/// [`Duration::reformat`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DurationFormat {
+    /// Keep the components as they are, which is how a parsed duration was written.
+    #[default]
+    PreserveInput,
+    /// Carry excess days into weeks, and excess seconds and minutes into larger units, so that
+    /// `PT90M` is written as `PT1H30M` and `P9D` as `P1W2D`. Hours are never carried into days.
+    LargestUnits,
+    /// Write the time components as whole and fractional seconds, so that `PT1H30M` is written
+    /// as `PT5400S`. Weeks and days are left unchanged.
+    SecondsOnly,
+}
+
 /// A [`Duration`] which may be positive or negative (RFC 8984 §1.4.7).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct SignedDuration {
@@ -65,8 +100,8 @@ pub struct ExactDuration {
     pub frac: Option<FractionalSecond>,
 }
 
-impl std::fmt::Display for ExactDuration {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for ExactDuration {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if self.hours > 0 {
             write!(f, "{}H", self.hours)?;
         }
@@ -92,8 +127,8 @@ impl std::fmt::Display for ExactDuration {
     }
 }
 
-impl std::fmt::Display for NominalDuration {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for NominalDuration {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         if self.weeks > 0 {
             write!(f, "{}W", self.weeks)?;
         }
@@ -111,8 +146,8 @@ impl std::fmt::Display for NominalDuration {
     }
 }
 
-impl std::fmt::Display for Duration {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Duration {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "P")?;
         match self {
             Duration::Nominal(n) => write!(f, "{n}"),
@@ -121,11 +156,537 @@ impl std::fmt::Display for Duration {
     }
 }
 
-impl std::fmt::Display for SignedDuration {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for SignedDuration {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self.sign {
             Sign::Neg => write!(f, "-{}", self.duration),
             Sign::Pos => write!(f, "{}", self.duration),
         }
     }
 }
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+impl Duration {
+    /// The zero-length duration, `PT0S`.
+    pub const ZERO: Self = Self::Exact(ExactDuration::ZERO);
+
+    /// Returns `true` iff this duration has no non-zero week or day components.
+    pub const fn is_exact(&self) -> bool {
+        match self {
+            Duration::Nominal(nominal) => nominal.weeks == 0 && nominal.days == 0,
+            Duration::Exact(_) => true,
+        }
+    }
+
+    /// Returns `true` iff this duration has zero length, such as `PT0S` or `P0D`.
+    pub const fn is_zero(&self) -> bool {
+        match self {
+            Duration::Nominal(NominalDuration {
+                weeks: 0,
+                days: 0,
+                exact,
+            }) => match exact {
+                Some(exact) => exact.is_zero(),
+                None => true,
+            },
+            Duration::Nominal(_) => false,
+            Duration::Exact(exact) => exact.is_zero(),
+        }
+    }
+
+    /// Returns the time-only part of this duration, or an error if it has week or day components.
+    pub fn to_exact(self) -> Result<ExactDuration, DurationArithmeticError> {
+        match self {
+            Duration::Exact(exact) => Ok(exact),
+            Duration::Nominal(NominalDuration {
+                weeks: 0,
+                days: 0,
+                exact,
+            }) => Ok(exact.unwrap_or_default()),
+            Duration::Nominal(_) => Err(DurationArithmeticError::Nominal),
+        }
+    }
+
+    /// Returns the number of whole seconds in this duration, discarding any fractional second.
+    ///
+    /// This is only defined for durations without week or day components.
+    pub fn to_seconds(self) -> Result<u64, DurationArithmeticError> {
+        self.to_exact().map(ExactDuration::to_seconds)
+    }
+
+    /// Normalizes the time components of this duration (see [`ExactDuration::normalize`]).
+    ///
+    /// Weeks and days are left unchanged, since hours cannot be carried into days without
+    /// knowing the time zone. Returns `None` if the number of hours overflows.
+    pub fn normalize(self) -> Option<Self> {
+        match self {
+            Duration::Exact(exact) => exact.normalize().map(Duration::Exact),
+            Duration::Nominal(nominal) => Some(Duration::Nominal(NominalDuration {
+                exact: match nominal.exact {
+                    Some(exact) => Some(exact.normalize()?),
+                    None => None,
+                },
+                ..nominal
+            })),
+        }
+    }
+
+    /// Returns an equal duration whose components are chosen according to `format`, or `None`
+    /// if a component overflows.
+    pub fn reformat(self, format: DurationFormat) -> Option<Self> {
+        match self {
+            Duration::Exact(exact) => exact.reformat(format).map(Duration::Exact),
+            Duration::Nominal(nominal) => {
+                let (weeks, days) = match format {
+                    DurationFormat::LargestUnits => (
+                        nominal.weeks.checked_add(nominal.days / 7)?,
+                        nominal.days % 7,
+                    ),
+                    _ => (nominal.weeks, nominal.days),
+                };
+                let exact = match nominal.exact {
+                    Some(exact) => Some(exact.reformat(format)?),
+                    None => None,
+                };
+                Some(Duration::Nominal(NominalDuration { weeks, days, exact }))
+            }
+        }
+    }
+
+    /// Adds two durations componentwise, returning `None` on overflow.
+    ///
+    /// The result is nominal if either operand is nominal.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        match (self, rhs) {
+            (Duration::Exact(lhs), Duration::Exact(rhs)) => {
+                lhs.checked_add(rhs).map(Duration::Exact)
+            }
+            (Duration::Nominal(lhs), Duration::Nominal(rhs)) => {
+                lhs.checked_add(rhs).map(Duration::Nominal)
+            }
+            (Duration::Nominal(nominal), Duration::Exact(exact))
+            | (Duration::Exact(exact), Duration::Nominal(nominal)) => nominal
+                .checked_add(NominalDuration {
+                    exact: Some(exact),
+                    ..Default::default()
+                })
+                .map(Duration::Nominal),
+        }
+    }
+
+    /// Compares the lengths of two durations.
+    ///
+    /// Unlike the derived [`Ord`] impl, this compares the actual lengths of time, so `PT90M` is
+    /// longer than `PT1H`. Durations with week or day components have no fixed length and
+    /// cannot be compared.
+    pub fn cmp_length(&self, other: &Self) -> Result<Ordering, DurationArithmeticError> {
+        Ok(self.to_exact()?.cmp_length(&other.to_exact()?))
+    }
+}
+
+impl Add for Duration {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("overflow when adding durations")
+    }
+}
+
+impl Neg for Duration {
+    type Output = SignedDuration;
+
+    fn neg(self) -> Self::Output {
+        SignedDuration {
+            sign: Sign::Neg,
+            duration: self,
+        }
+    }
+}
+
+impl TryFrom<Duration> for core::time::Duration {
+    type Error = DurationArithmeticError;
+
+    fn try_from(value: Duration) -> Result<Self, Self::Error> {
+        value.to_exact().map(Into::into)
+    }
+}
+
+impl TryFrom<core::time::Duration> for Duration {
+    type Error = DurationArithmeticError;
+
+    fn try_from(value: core::time::Duration) -> Result<Self, Self::Error> {
+        ExactDuration::try_from(value).map(Duration::Exact)
+    }
+}
+
+impl SignedDuration {
+    /// Adds two signed durations, returning an error on overflow.
+    ///
+    /// Durations with the same sign are added componentwise. Durations with opposite signs can
+    /// only be combined if neither has week or day components, in which case the result is
+    /// exact and normalized.
+    pub fn checked_add(self, rhs: Self) -> Result<Self, DurationArithmeticError> {
+        if self.sign == rhs.sign {
+            let duration = self
+                .duration
+                .checked_add(rhs.duration)
+                .ok_or(DurationArithmeticError::Overflow)?;
+            return Ok(Self {
+                sign: self.sign,
+                duration,
+            });
+        }
+
+        let lhs_nanos = self.duration.to_exact()?.as_nanos();
+        let rhs_nanos = rhs.duration.to_exact()?.as_nanos();
+        let (sign, nanos) = match lhs_nanos.cmp(&rhs_nanos) {
+            Ordering::Less => (rhs.sign, rhs_nanos - lhs_nanos),
+            Ordering::Equal => (Sign::Pos, 0),
+            Ordering::Greater => (self.sign, lhs_nanos - rhs_nanos),
+        };
+        let exact = ExactDuration::from_nanos(nanos).ok_or(DurationArithmeticError::Overflow)?;
+
+        Ok(Self {
+            sign,
+            duration: Duration::Exact(exact),
+        })
+    }
+
+    /// Returns an equal signed duration whose components are chosen according to `format`; see
+    /// [`Duration::reformat`].
+    pub fn reformat(self, format: DurationFormat) -> Option<Self> {
+        Some(Self {
+            sign: self.sign,
+            duration: self.duration.reformat(format)?,
+        })
+    }
+
+    /// Subtracts `rhs` from `self`; see [`SignedDuration::checked_add`].
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, DurationArithmeticError> {
+        self.checked_add(-rhs)
+    }
+
+    /// Compares the lengths of two signed durations, so that every negative duration is shorter
+    /// than every positive one (except for zero, which is equal to its negation).
+    ///
+    /// Durations with week or day components cannot be compared.
+    pub fn cmp_length(&self, other: &Self) -> Result<Ordering, DurationArithmeticError> {
+        let signed_nanos = |value: &Self| -> Result<i128, DurationArithmeticError> {
+            let nanos = value.duration.to_exact()?.as_nanos() as i128;
+            Ok(nanos * value.sign as i128)
+        };
+
+        Ok(signed_nanos(self)?.cmp(&signed_nanos(other)?))
+    }
+}
+
+impl Neg for SignedDuration {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            sign: -self.sign,
+            duration: self.duration,
+        }
+    }
+}
+
+impl NominalDuration {
+    /// Adds two nominal durations componentwise, returning `None` on overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let exact = match (self.exact, rhs.exact) {
+            (Some(lhs), Some(rhs)) => Some(lhs.checked_add(rhs)?),
+            (lhs, rhs) => lhs.or(rhs),
+        };
+
+        Some(Self {
+            weeks: self.weeks.checked_add(rhs.weeks)?,
+            days: self.days.checked_add(rhs.days)?,
+            exact,
+        })
+    }
+}
+
+impl ExactDuration {
+    /// The zero-length duration.
+    pub const ZERO: Self = Self {
+        hours: 0,
+        minutes: 0,
+        seconds: 0,
+        frac: None,
+    };
+
+    /// Returns the total length of this duration in nanoseconds.
+    pub const fn as_nanos(self) -> u128 {
+        let frac = match self.frac {
+            Some(frac) => frac.get().get() as u128,
+            None => 0,
+        };
+        self.to_seconds() as u128 * NANOS_PER_SECOND + frac
+    }
+
+    /// Returns `true` iff this duration has zero length.
+    pub const fn is_zero(&self) -> bool {
+        self.as_nanos() == 0
+    }
+
+    /// Returns the number of whole seconds in this duration, discarding any fractional second.
+    pub const fn to_seconds(self) -> u64 {
+        self.hours as u64 * 3600 + self.minutes as u64 * 60 + self.seconds as u64
+    }
+
+    /// Constructs a normalized duration from a number of nanoseconds, returning `None` if the
+    /// number of hours does not fit in a `u32`.
+    pub fn from_nanos(nanos: u128) -> Option<Self> {
+        let total_seconds = nanos / NANOS_PER_SECOND;
+
+        Some(Self {
+            hours: u32::try_from(total_seconds / 3600).ok()?,
+            minutes: ((total_seconds / 60) % 60) as u32,
+            seconds: (total_seconds % 60) as u32,
+            frac: FractionalSecond::new((nanos % NANOS_PER_SECOND) as u32).ok(),
+        })
+    }
+
+    /// Returns an equivalent duration with fewer than 60 minutes and 60 seconds, carrying the
+    /// excess into larger units (so `PT90M` becomes `PT1H30M`). Returns `None` if the number of
+    /// hours overflows.
+    pub fn normalize(self) -> Option<Self> {
+        Self::from_nanos(self.as_nanos())
+    }
+
+    /// Returns an equal duration whose components are chosen according to `format`; see
+    /// [`Duration::reformat`]. Returns `None` if a component overflows.
+    pub fn reformat(self, format: DurationFormat) -> Option<Self> {
+        match format {
+            DurationFormat::PreserveInput => Some(self),
+            DurationFormat::LargestUnits => self.normalize(),
+            DurationFormat::SecondsOnly => Some(Self {
+                hours: 0,
+                minutes: 0,
+                seconds: u32::try_from(self.to_seconds()).ok()?,
+                frac: self.frac,
+            }),
+        }
+    }
+
+    /// Adds two durations componentwise, carrying whole seconds out of the fractional part.
+    /// Returns `None` on overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        let frac_nanos = |frac: Option<FractionalSecond>| frac.map_or(0, |frac| frac.get().get());
+        let nanos = frac_nanos(self.frac) + frac_nanos(rhs.frac);
+        let carry = nanos / NANOS_PER_SECOND as u32;
+
+        Some(Self {
+            hours: self.hours.checked_add(rhs.hours)?,
+            minutes: self.minutes.checked_add(rhs.minutes)?,
+            seconds: self.seconds.checked_add(rhs.seconds)?.checked_add(carry)?,
+            frac: FractionalSecond::new(nanos % NANOS_PER_SECOND as u32).ok(),
+        })
+    }
+
+    /// Compares the lengths of two durations; see [`Duration::cmp_length`].
+    pub fn cmp_length(&self, other: &Self) -> Ordering {
+        self.as_nanos().cmp(&other.as_nanos())
+    }
+}
+
+impl Add for ExactDuration {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("overflow when adding durations")
+    }
+}
+
+impl From<ExactDuration> for core::time::Duration {
+    fn from(value: ExactDuration) -> Self {
+        let nanos = value.frac.map_or(0, |frac| frac.get().get());
+        core::time::Duration::new(value.to_seconds(), nanos)
+    }
+}
+
+impl TryFrom<core::time::Duration> for ExactDuration {
+    type Error = DurationArithmeticError;
+
+    fn try_from(value: core::time::Duration) -> Result<Self, Self::Error> {
+        Self::from_nanos(value.as_nanos()).ok_or(DurationArithmeticError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exact(hours: u32, minutes: u32, seconds: u32) -> ExactDuration {
+        ExactDuration {
+            hours,
+            minutes,
+            seconds,
+            frac: None,
+        }
+    }
+
+    fn days(days: u32) -> Duration {
+        Duration::Nominal(NominalDuration {
+            days,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn exact_duration_normalize() {
+        assert_eq!(exact(0, 90, 0).normalize(), Some(exact(1, 30, 0)));
+        assert_eq!(exact(1, 59, 61).normalize(), Some(exact(2, 0, 1)));
+        assert_eq!(exact(u32::MAX, 60, 0).normalize(), None);
+
+        let nominal = Duration::Nominal(NominalDuration {
+            weeks: 0,
+            days: 1,
+            exact: Some(exact(25, 0, 0)),
+        });
+        // hours are never carried into days
+        assert_eq!(nominal.normalize(), Some(nominal));
+        assert_eq!(nominal.to_string(), "P1DT25H");
+    }
+
+    #[test]
+    fn duration_is_zero() {
+        assert!(Duration::ZERO.is_zero());
+        assert!(days(0).is_zero());
+        assert!(Duration::Exact(exact(0, 0, 0)).is_zero());
+        assert!(!days(1).is_zero());
+        assert!(!Duration::Exact(exact(0, 0, 1)).is_zero());
+
+        let frac = ExactDuration {
+            frac: FractionalSecond::new(1).ok(),
+            ..ExactDuration::ZERO
+        };
+        assert!(!Duration::Exact(frac).is_zero());
+    }
+
+    #[test]
+    fn exact_duration_checked_add_carries_fraction() {
+        let half = ExactDuration {
+            frac: FractionalSecond::new(600_000_000).ok(),
+            ..exact(0, 0, 1)
+        };
+        let sum = half.checked_add(half).unwrap();
+        assert_eq!(sum.seconds, 3);
+        assert_eq!(sum.frac, FractionalSecond::new(200_000_000).ok());
+
+        let whole = ExactDuration {
+            frac: FractionalSecond::new(500_000_000).ok(),
+            ..ExactDuration::ZERO
+        };
+        assert_eq!(whole + whole, exact(0, 0, 1));
+        assert_eq!(exact(u32::MAX, 0, 0).checked_add(exact(1, 0, 0)), None);
+    }
+
+    #[test]
+    fn duration_add() {
+        let sum = days(1) + Duration::Exact(exact(0, 30, 0));
+        assert_eq!(sum.to_string(), "P1DT30M");
+        assert_eq!(
+            Duration::Exact(exact(0, 45, 0)) + Duration::Exact(exact(0, 45, 0)),
+            Duration::Exact(exact(0, 90, 0))
+        );
+        assert_eq!((days(2) + days(3)).to_string(), "P5D");
+    }
+
+    #[test]
+    fn duration_to_seconds() {
+        assert_eq!(Duration::Exact(exact(1, 1, 1)).to_seconds(), Ok(3661));
+        assert_eq!(days(1).to_seconds(), Err(DurationArithmeticError::Nominal));
+        assert_eq!(days(0).to_seconds(), Ok(0));
+    }
+
+    #[test]
+    fn duration_cmp_length() {
+        let long = Duration::Exact(exact(0, 90, 0));
+        let short = Duration::Exact(exact(1, 0, 0));
+        assert!(long < short, "the derived impl is structural");
+        assert_eq!(long.cmp_length(&short), Ok(Ordering::Greater));
+        assert_eq!(
+            days(1).cmp_length(&short),
+            Err(DurationArithmeticError::Nominal)
+        );
+
+        let neg = -long;
+        assert_eq!(neg.cmp_length(&short.into()), Ok(Ordering::Less));
+        assert_eq!(
+            (-Duration::ZERO).cmp_length(&Duration::ZERO.into()),
+            Ok(Ordering::Equal)
+        );
+    }
+
+    #[test]
+    fn duration_reformat() {
+        let mixed = |weeks, days, exact| Duration::Nominal(NominalDuration { weeks, days, exact });
+        let ninety = Duration::Exact(exact(0, 90, 0));
+
+        let format = |duration: Duration, format| duration.reformat(format).unwrap().to_string();
+        assert_eq!(format(ninety, DurationFormat::PreserveInput), "PT90M");
+        assert_eq!(format(ninety, DurationFormat::LargestUnits), "PT1H30M");
+        assert_eq!(format(ninety, DurationFormat::SecondsOnly), "PT5400S");
+
+        let nominal = mixed(0, 9, Some(exact(25, 0, 61)));
+        assert_eq!(
+            format(nominal, DurationFormat::LargestUnits),
+            "P1W2DT25H1M1S"
+        );
+        assert_eq!(format(nominal, DurationFormat::SecondsOnly), "P9DT90061S");
+        assert_eq!(format(Duration::ZERO, DurationFormat::SecondsOnly), "PT0S");
+        assert_eq!(
+            Duration::Exact(exact(u32::MAX, 0, 0)).reformat(DurationFormat::SecondsOnly),
+            None
+        );
+
+        let signed = -ninety;
+        assert_eq!(
+            signed
+                .reformat(DurationFormat::LargestUnits)
+                .unwrap()
+                .to_string(),
+            "-PT1H30M"
+        );
+    }
+
+    #[test]
+    fn signed_duration_add() {
+        let hour = SignedDuration::from(Duration::Exact(exact(1, 0, 0)));
+        let quarter = SignedDuration::from(Duration::Exact(exact(0, 15, 0)));
+
+        assert_eq!(hour.checked_sub(quarter).unwrap().to_string(), "PT45M");
+        assert_eq!(quarter.checked_sub(hour).unwrap().to_string(), "-PT45M");
+        assert_eq!(hour.checked_sub(hour).unwrap().to_string(), "PT0S");
+        assert_eq!(
+            (-hour).checked_add(-quarter).unwrap().to_string(),
+            "-PT1H15M"
+        );
+        assert_eq!(
+            SignedDuration::from(days(1)).checked_sub(hour),
+            Err(DurationArithmeticError::Nominal)
+        );
+        assert_eq!(-(-hour), hour);
+    }
+
+    #[test]
+    fn std_duration_conversion() {
+        let std = core::time::Duration::new(5400, 250_000_000);
+        let duration = Duration::try_from(std).unwrap();
+        assert_eq!(duration.to_string(), "PT1H30M0.25S");
+        assert_eq!(core::time::Duration::try_from(duration), Ok(std));
+        assert_eq!(
+            core::time::Duration::try_from(days(1)),
+            Err(DurationArithmeticError::Nominal)
+        );
+        assert_eq!(
+            Duration::try_from(core::time::Duration::MAX),
+            Err(DurationArithmeticError::Overflow)
+        );
+    }
+}
@@ -6,7 +6,8 @@
 //! - **Date and time types** ([`time`]): [`Year`](time::Year), [`Month`](time::Month),
 //!   [`Day`](time::Day), [`Hour`](time::Hour), [`Minute`](time::Minute),
 //!   [`Second`](time::Second), [`Date`](time::Date), [`Time`](time::Time), and
-//!   [`DateTime`](time::DateTime) with compile-time timezone markers.
+//!   [`DateTime`](time::DateTime) with compile-time timezone markers and checked calendar
+//!   arithmetic.
 //! - **Duration types** ([`duration`]): [`Duration`](duration::Duration) and
 //!   [`SignedDuration`](duration::SignedDuration) following RFC 8984 §1.4.6–7.
 //! - **String types** ([`string`]): validated [`Uid`](string::Uid) and [`Uri`](string::Uri)
@@ -16,6 +17,16 @@
 //! - **Token sets** ([`set`]): [`Token`](set::Token) for extensible enum values, and
 //!   IANA registry types ([`LinkRelation`](set::LinkRelation),
 //!   [`LocationType`](set::LocationType)).
+//!
+//! # Feature flags
+//!
+//! - **`std`** (enabled by default): implements the standard library traits and enables
+//!   [`LanguageTag`](string::LanguageTag). Without it, the crate is `no_std` and only requires
+//!   `alloc`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
 pub mod css;
 pub mod duration;
@@ -21,6 +21,17 @@ impl Sign {
     }
 }
 
+impl core::ops::Neg for Sign {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        match self {
+            Sign::Neg => Sign::Pos,
+            Sign::Pos => Sign::Neg,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,4 +40,10 @@ mod tests {
     fn sign_ord_impl() {
         assert!(Sign::Neg < Sign::Pos);
     }
+
+    #[test]
+    fn sign_neg_impl() {
+        assert_eq!(-Sign::Neg, Sign::Pos);
+        assert_eq!(-Sign::Pos, Sign::Neg);
+    }
 }
@@ -1,8 +1,11 @@
 //! Types for finite set values.
 
-use std::{convert::Infallible, fmt, str::FromStr};
+use core::{convert::Infallible, fmt, str::FromStr};
 
-use strum::{Display, EnumString};
+use strum::{Display, EnumString, IntoStaticStr, VariantArray};
+
+#[doc(hidden)]
+pub use strum as __strum;
 
 /// A token which may be a statically known value of type `T` or else an unknown value of type
 /// `S`.
@@ -60,6 +63,31 @@ impl<T, S> Token<T, S> {
     }
 }
 
+impl<T: TokenParse, S> Token<T, S> {
+    /// Parses a token, matching the names of the known values according to
+    /// [`T::CASE_FOLDING`](TokenParse::CASE_FOLDING).
+    pub fn parse<'a>(s: &'a str) -> Self
+    where
+        &'a str: Into<S>,
+    {
+        match T::parse_token(s) {
+            Some(value) => Token::Known(value),
+            None => Token::Unknown(s.into()),
+        }
+    }
+
+    /// Returns the canonical name of a known value, or else the unknown value unchanged.
+    pub fn canonical_str(&self) -> &str
+    where
+        S: AsRef<str>,
+    {
+        match self {
+            Token::Known(value) => value.canonical_str(),
+            Token::Unknown(value) => value.as_ref(),
+        }
+    }
+}
+
 impl<T: fmt::Display, S: fmt::Display> fmt::Display for Token<T, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -69,10 +97,90 @@ impl<T: fmt::Display, S: fmt::Display> fmt::Display for Token<T, S> {
     }
 }
 
+/// How the text of a token is matched against the names of the values of a [`TokenParse`] type.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaseFolding {
+    /// Names match regardless of the case of ASCII letters, as for the enumerated values of
+    /// iCalendar (RFC 5545 §2) and most enumerated values of JSCalendar.
+    #[default]
+    AsciiInsensitive,
+    /// Names only match exactly.
+    Sensitive,
+}
+
+/// A finite set of values with canonical names, which can be the known values of a [`Token`].
+///
+/// This trait is usually implemented with [`impl_token_parse!`](crate::impl_token_parse), which
+/// takes the names of the values from their [`strum`] serializations.
+pub trait TokenParse: Copy + 'static {
+    /// How text is matched against the names of the values.
+    const CASE_FOLDING: CaseFolding;
+
+    /// Every value of this type.
+    const VALUES: &'static [Self];
+
+    /// Returns the canonical name of this value, as used when serializing it.
+    fn canonical_str(&self) -> &'static str;
+
+    /// Returns the value named by `s`, or `None` if `s` does not name a value.
+    fn parse_token(s: &str) -> Option<Self> {
+        let matches = |name: &str| match Self::CASE_FOLDING {
+            CaseFolding::AsciiInsensitive => name.eq_ignore_ascii_case(s),
+            CaseFolding::Sensitive => name == s,
+        };
+        Self::VALUES
+            .iter()
+            .copied()
+            .find(|value| matches(value.canonical_str()))
+    }
+}
+
+/// Implements [`TokenParse`] for enums which derive [`strum::IntoStaticStr`] and
+/// [`strum::VariantArray`], with [`CaseFolding::AsciiInsensitive`] unless the list of types is
+/// preceded by `case_sensitive:`.
+#[macro_export]
+macro_rules! impl_token_parse {
+    (case_sensitive: $($ty:ty),+ $(,)?) => {
+        $($crate::impl_token_parse!(@impl $ty, Sensitive);)+
+    };
+    ($($ty:ty),+ $(,)?) => {
+        $($crate::impl_token_parse!(@impl $ty, AsciiInsensitive);)+
+    };
+    (@impl $ty:ty, $folding:ident) => {
+        impl $crate::set::TokenParse for $ty {
+            const CASE_FOLDING: $crate::set::CaseFolding = $crate::set::CaseFolding::$folding;
+            const VALUES: &'static [Self] =
+                <Self as $crate::set::__strum::VariantArray>::VARIANTS;
+
+            fn canonical_str(&self) -> &'static str {
+                self.into()
+            }
+        }
+    };
+}
+
+impl_token_parse!(LinkRelation, LocationType);
+
 /// A link relation from the [IANA Link Relations Registry].
 ///
+/// Values which are not (yet) in the registry can be held by a [`Token`], which parses them as
+/// [`Token::Unknown`] instead of failing.
+///
 /// [IANA Link Relations Registry]: https://www.iana.org/assignments/link-relations/
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum LinkRelation {
@@ -745,10 +853,38 @@ pub enum LinkRelation {
     WorkingCopyOf,
 }
 
+impl LinkRelation {
+    /// Returns the registered name of this link relation, in lowercase.
+    pub fn as_str(self) -> &'static str {
+        self.into()
+    }
+
+    /// Returns an iterator over every link relation in the registry, in declaration order.
+    pub fn iter() -> impl ExactSizeIterator<Item = Self> {
+        <Self as VariantArray>::VARIANTS.iter().copied()
+    }
+}
+
 /// A location type from the [IANA Location Types Registry].
 ///
+/// Values which are not (yet) in the registry can be held by a [`Token`], which parses them as
+/// [`Token::Unknown`] instead of failing.
+///
 /// [IANA Location Types Registry]: https://www.iana.org/assignments/location-type-registry/
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum LocationType {
@@ -1049,10 +1185,22 @@ pub enum LocationType {
     YouthCamp,
 }
 
+impl LocationType {
+    /// Returns the registered name of this location type, in lowercase.
+    pub fn as_str(self) -> &'static str {
+        self.into()
+    }
+
+    /// Returns an iterator over every location type in the registry, in declaration order.
+    pub fn iter() -> impl ExactSizeIterator<Item = Self> {
+        <Self as VariantArray>::VARIANTS.iter().copied()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::str::FromStr;
+    use core::str::FromStr;
 
     #[test]
     fn link_relation_from_str_lowercase() {
@@ -1199,4 +1347,71 @@ mod tests {
         // "utility-box" with hyphen is NOT valid (registry uses "utilitybox")
         assert!(LocationType::from_str("utility-box").is_err());
     }
+
+    #[test]
+    fn registry_tokens_preserve_unknown_values() {
+        let rel: Token<LinkRelation, String> = "api-catalog".parse().unwrap();
+        assert_eq!(rel, Token::Known(LinkRelation::ApiCatalog));
+        assert_eq!(rel.to_string(), "api-catalog");
+
+        let rel: Token<LinkRelation, String> = "x-vendor-rel".parse().unwrap();
+        assert_eq!(rel, Token::Unknown("x-vendor-rel".to_string()));
+        assert_eq!(rel.to_string(), "x-vendor-rel");
+
+        let kind: Token<LocationType, Box<str>> = "utilitybox".parse().unwrap();
+        assert_eq!(kind.to_string(), "utilitybox");
+
+        let kind: Token<LocationType, Box<str>> = "utility-box".parse().unwrap();
+        assert_eq!(kind, Token::Unknown("utility-box".into()));
+        assert_eq!(kind.to_string(), "utility-box");
+    }
+
+    #[test]
+    fn token_parse_folds_case() {
+        assert_eq!(
+            Token::<LinkRelation, Box<str>>::parse("Api-Catalog"),
+            Token::Known(LinkRelation::ApiCatalog)
+        );
+        assert_eq!(
+            Token::<LocationType, Box<str>>::parse("x-Spaceport"),
+            Token::Unknown("x-Spaceport".into())
+        );
+        assert_eq!(LinkRelation::parse_token(" about"), None);
+    }
+
+    #[test]
+    fn token_canonical_str() {
+        let known: Token<LocationType, Box<str>> = Token::parse("BUS-STATION");
+        assert_eq!(known.canonical_str(), "bus-station");
+        let unknown: Token<LocationType, Box<str>> = Token::parse("X-Dock");
+        assert_eq!(unknown.canonical_str(), "X-Dock");
+
+        for relation in LinkRelation::VALUES {
+            assert_eq!(relation.canonical_str(), relation.to_string());
+            assert_eq!(
+                LinkRelation::parse_token(&relation.to_string()),
+                Some(*relation)
+            );
+        }
+        for location in LocationType::VALUES {
+            assert_eq!(location.canonical_str(), location.to_string());
+        }
+    }
+
+    #[test]
+    fn registry_iteration() {
+        assert_eq!(LinkRelation::iter().len(), 133);
+        assert_eq!(LocationType::iter().len(), 59);
+        assert_eq!(LinkRelation::iter().next(), Some(LinkRelation::About));
+        assert_eq!(LinkRelation::OpenId2LocalId.as_str(), "openid2.local_id");
+        assert_eq!(LocationType::PlaceOfWorship.as_str(), "place-of-worship");
+
+        for relation in LinkRelation::iter() {
+            assert_eq!(relation.as_str().parse(), Ok(relation));
+            assert_eq!(relation.as_str(), relation.as_str().to_ascii_lowercase());
+        }
+        for location in LocationType::iter() {
+            assert_eq!(location.as_str().parse(), Ok(location));
+        }
+    }
 }
@@ -1,17 +1,22 @@
 //! String data model types.
 
-use std::str::FromStr;
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
 
 use dizzy::DstNewtype;
 use thiserror::Error;
 
 /// Re-export of the error type from the `language-tags` crate.
+#[cfg(feature = "std")]
 pub use language_tags::ParseError as LanguageTagParseError;
 
 /// A BCP 47 language tag (RFC 5646).
+///
+/// Requires the `std` feature, since the `language-tags` crate depends on the standard library.
+#[cfg(feature = "std")]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LanguageTag(language_tags::LanguageTag);
 
+#[cfg(feature = "std")]
 impl LanguageTag {
     /// Parses a language tag from a string.
     pub fn parse(s: &str) -> Result<Self, language_tags::ParseError> {
@@ -31,7 +36,8 @@ impl LanguageTag {
     }
 }
 
-impl FromStr for LanguageTag {
+#[cfg(feature = "std")]
+impl core::str::FromStr for LanguageTag {
     type Err = language_tags::ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -39,9 +45,10 @@ impl FromStr for LanguageTag {
     }
 }
 
-impl std::fmt::Display for LanguageTag {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Display::fmt(&self.0, f)
+#[cfg(feature = "std")]
+impl core::fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
     }
 }
 
@@ -67,8 +74,8 @@ pub enum InvalidUidError {
 #[repr(transparent)]
 pub struct Uid(str);
 
-impl std::fmt::Display for Uid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Uid {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str(self.as_str())
     }
 }
@@ -93,10 +100,22 @@ pub enum InvalidUriError {
     SchemeStartsWithNonLetter,
     #[error("invalid character in scheme: {c}")]
     InvalidSchemeChar { index: usize, c: char },
+    /// A character which RFC 3986 does not permit in a URI (see [`Uri::parse_strict`]).
+    #[error("invalid character in URI: {c:?}")]
+    InvalidChar { index: usize, c: char },
+    /// A `%` which is not followed by two hexadecimal digits (see [`Uri::parse_strict`]).
+    #[error("invalid percent-encoding at index {index}")]
+    InvalidPercentEncoding { index: usize },
 }
 
 /// A URI string (RFC 3986).
 ///
+/// The constructor only checks the scheme, since URIs in calendar data are often not strictly
+/// valid; [`Uri::parse_strict`] also checks the remaining characters. The components of the URI
+/// are available through [`scheme`](Uri::scheme), [`authority`](Uri::authority),
+/// [`path`](Uri::path), [`query`](Uri::query), and [`fragment`](Uri::fragment), and
+/// [`normalized`](Uri::normalized) returns an equivalent URI in a canonical form for comparison.
+///
 /// # Invariants
 /// 1. The underlying string is not empty.
 /// 2. The string contains a colon separating the scheme from the rest.
@@ -111,8 +130,8 @@ pub enum InvalidUriError {
 #[repr(transparent)]
 pub struct Uri(str);
 
-impl std::fmt::Display for Uri {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Uri {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str(self.as_str())
     }
 }
@@ -144,6 +163,31 @@ impl Uri {
         Ok(())
     }
 
+    /// Parses a URI whose characters are all permitted by RFC 3986 §2, with every `%` starting a
+    /// percent-encoding.
+    pub fn parse_strict(s: &str) -> Result<&Self, InvalidUriError> {
+        let uri = Self::new(s)?;
+        let bytes = s.as_bytes();
+        for (index, c) in s.char_indices() {
+            match c {
+                '%' => {
+                    let encoded = bytes.get(index + 1..index + 3);
+                    if !encoded.is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit)) {
+                        return Err(InvalidUriError::InvalidPercentEncoding { index });
+                    }
+                }
+                c if is_unreserved(c) || ":/?#[]@!$&'()*+,;=".contains(c) => {}
+                c => return Err(InvalidUriError::InvalidChar { index, c }),
+            }
+        }
+        Ok(uri)
+    }
+
+    /// Parses a URI after trimming surrounding whitespace, checking only its scheme.
+    pub fn parse_lenient(s: &str) -> Result<&Self, InvalidUriError> {
+        Self::new(s.trim())
+    }
+
     /// Returns the scheme portion of the URI (before the first colon).
     #[inline(always)]
     pub fn scheme(&self) -> &str {
@@ -152,4 +196,152 @@ impl Uri {
             .expect("a Uri must contain a colon")
             .0
     }
+
+    /// Returns the authority of the URI, which follows `//` after the scheme, or `None` if the URI
+    /// has no authority (as with `mailto:` and `tel:` URIs).
+    pub fn authority(&self) -> Option<&str> {
+        let rest = self.hier_part().strip_prefix("//")?;
+        Some(&rest[..rest.find('/').unwrap_or(rest.len())])
+    }
+
+    /// Returns the path of the URI, which may be empty.
+    pub fn path(&self) -> &str {
+        let hier_part = self.hier_part();
+        match self.authority() {
+            Some(authority) => &hier_part[2 + authority.len()..],
+            None => hier_part,
+        }
+    }
+
+    /// Returns the query of the URI, without the leading `?`.
+    pub fn query(&self) -> Option<&str> {
+        let rest = self.without_fragment().split_once(':')?.1;
+        rest.split_once('?').map(|(_, query)| query)
+    }
+
+    /// Returns the fragment of the URI, without the leading `#`.
+    pub fn fragment(&self) -> Option<&str> {
+        self.as_str().split_once('#').map(|(_, fragment)| fragment)
+    }
+
+    /// Returns an equivalent URI in the normal form of RFC 3986 §6.2.2, so that equivalent URIs
+    /// compare equal.
+    ///
+    /// The scheme and the host are converted to lowercase, percent-encoded unreserved characters
+    /// are decoded and other percent-encodings are converted to uppercase, and the `.` and `..`
+    /// segments of the path are removed if the URI has an authority. The address of a `mailto:`
+    /// URI is also converted to lowercase, since calendar user addresses are compared
+    /// case-insensitively in practice.
+    pub fn normalized(&self) -> Box<Self> {
+        let scheme = self.scheme().to_ascii_lowercase();
+        let mut normalized = scheme.clone();
+        normalized.push(':');
+
+        if let Some(authority) = self.authority() {
+            let (userinfo, host) = match authority.rsplit_once('@') {
+                Some((userinfo, host)) => (Some(userinfo), host),
+                None => (None, authority),
+            };
+            normalized.push_str("//");
+            if let Some(userinfo) = userinfo {
+                normalized.push_str(&normalize_percent_encoding(userinfo));
+                normalized.push('@');
+            }
+            normalized.push_str(&normalize_percent_encoding(host).to_ascii_lowercase());
+            normalized.push_str(&remove_dot_segments(&normalize_percent_encoding(self.path())));
+        } else if scheme == "mailto" {
+            normalized.push_str(&normalize_percent_encoding(self.path()).to_lowercase());
+        } else {
+            normalized.push_str(&normalize_percent_encoding(self.path()));
+        }
+
+        if let Some(query) = self.query() {
+            normalized.push('?');
+            normalized.push_str(&normalize_percent_encoding(query));
+        }
+        if let Some(fragment) = self.fragment() {
+            normalized.push('#');
+            normalized.push_str(&normalize_percent_encoding(fragment));
+        }
+
+        Uri::new(&normalized)
+            .expect("normalization preserves the scheme")
+            .into()
+    }
+
+    /// Returns the URI without its fragment.
+    fn without_fragment(&self) -> &str {
+        let s = self.as_str();
+        s.split_once('#').map_or(s, |(uri, _)| uri)
+    }
+
+    /// Returns the part of the URI between the scheme and the query.
+    fn hier_part(&self) -> &str {
+        let (_, rest) = self
+            .without_fragment()
+            .split_once(':')
+            .expect("a Uri must contain a colon");
+        rest.split_once('?').map_or(rest, |(hier_part, _)| hier_part)
+    }
+}
+
+/// Returns `true` if `c` is an unreserved character (RFC 3986 §2.3).
+fn is_unreserved(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~')
+}
+
+/// Decodes the percent-encoded unreserved characters of `s`, and converts the hexadecimal digits
+/// of other percent-encodings to uppercase (RFC 3986 §6.2.2.1–2).
+fn normalize_percent_encoding(s: &str) -> String {
+    let mut normalized = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(index) = rest.find('%') {
+        normalized.push_str(&rest[..index]);
+        let encoded = &rest[index..];
+        let decoded = encoded
+            .get(1..3)
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .filter(|_| encoded.as_bytes()[1..3].iter().all(u8::is_ascii_hexdigit));
+        match decoded {
+            Some(byte) if is_unreserved(byte as char) => {
+                normalized.push(byte as char);
+                rest = &encoded[3..];
+            }
+            Some(_) => {
+                normalized.push_str(&encoded[..3].to_ascii_uppercase());
+                rest = &encoded[3..];
+            }
+            None => {
+                normalized.push('%');
+                rest = &encoded[1..];
+            }
+        }
+    }
+    normalized.push_str(rest);
+    normalized
+}
+
+/// Removes the `.` and `..` segments of an absolute path (RFC 3986 §5.2.4).
+fn remove_dot_segments(path: &str) -> String {
+    let Some(path) = path.strip_prefix('/') else {
+        return path.to_owned();
+    };
+
+    let mut segments: Vec<&str> = Vec::new();
+    let mut input = path.split('/').peekable();
+    while let Some(segment) = input.next() {
+        match segment {
+            "." | ".." => {
+                if segment == ".." {
+                    segments.pop();
+                }
+                // a trailing dot segment leaves the path ending in a slash
+                if input.peek().is_none() {
+                    segments.push("");
+                }
+            }
+            segment => segments.push(segment),
+        }
+    }
+    segments.iter().flat_map(|segment| ["/", segment]).collect()
 }
\ No newline at end of file
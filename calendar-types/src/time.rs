@@ -1,9 +1,15 @@
 //! Date and time types, largely from RFC 3339.
 
-use std::{convert::Infallible, num::NonZero};
+use alloc::format;
+use core::{convert::Infallible, num::NonZero};
 
 use thiserror::Error;
 
+use crate::{
+    duration::{Duration, ExactDuration, SignedDuration},
+    primitive::Sign,
+};
+
 /// One of the seven weekdays.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u8)]
@@ -24,7 +30,7 @@ impl Weekday {
             0..=6 => {
                 // SAFETY: the valid discriminants of Self are exactly the
                 // values of the range 0..=6.
-                Some(unsafe { std::mem::transmute::<u8, Self>(repr) })
+                Some(unsafe { core::mem::transmute::<u8, Self>(repr) })
             }
             _ => None,
         }
@@ -115,7 +121,7 @@ impl IsoWeek {
     pub const fn from_index(index: u8) -> Option<Self> {
         match index {
             1..=53 => {
-                let week: Self = unsafe { std::mem::transmute(index) };
+                let week: Self = unsafe { core::mem::transmute(index) };
                 Some(week)
             }
             _ => None,
@@ -161,6 +167,92 @@ pub struct DateTime<M> {
     pub marker: M,
 }
 
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+const NANOS_PER_DAY: i128 = 86_400 * NANOS_PER_SECOND;
+
+impl<M> DateTime<M> {
+    /// Adds `duration` to `self`, returning `None` if the result is out of range.
+    ///
+    /// Since the marker carries no time zone information, every nominal day is treated as
+    /// exactly 24 hours long. A leap second in `self` is counted as a full second, and the result
+    /// is never a leap second.
+    pub fn checked_add(self, duration: Duration) -> Option<Self> {
+        self.checked_add_signed(duration.into())
+    }
+
+    /// Subtracts `duration` from `self`, returning `None` if the result is out of range.
+    ///
+    /// This has the same caveats as [`DateTime::checked_add`].
+    pub fn checked_sub(self, duration: Duration) -> Option<Self> {
+        self.checked_add_signed(SignedDuration {
+            sign: Sign::Neg,
+            duration,
+        })
+    }
+
+    /// Adds a signed `duration` to `self`, returning `None` if the result is out of range.
+    ///
+    /// This has the same caveats as [`DateTime::checked_add`].
+    pub fn checked_add_signed(self, duration: SignedDuration) -> Option<Self> {
+        let delta = duration_nanos(duration.duration) * duration.sign as i128;
+        let nanos = self.epoch_nanos().checked_add(delta)?;
+
+        let days = i64::try_from(nanos.div_euclid(NANOS_PER_DAY)).ok()?;
+        let date = Date::from_epoch_days(days)?;
+        let time = Time::from_nanos_since_midnight(nanos.rem_euclid(NANOS_PER_DAY) as u64);
+
+        Some(Self {
+            date,
+            time,
+            marker: self.marker,
+        })
+    }
+
+    /// Returns the signed, exact duration from `rhs` to `self`.
+    ///
+    /// The result is positive if `self` is later than `rhs`. The markers of both values are
+    /// ignored, so the caller is responsible for ensuring they refer to the same time scale.
+    pub fn signed_duration_since(&self, rhs: &Self) -> SignedDuration {
+        let delta = self.epoch_nanos() - rhs.epoch_nanos();
+        let sign = if delta < 0 { Sign::Neg } else { Sign::Pos };
+        let delta = delta.unsigned_abs();
+
+        let nanos = (delta % NANOS_PER_SECOND as u128) as u32;
+        let total_seconds = delta / NANOS_PER_SECOND as u128;
+
+        // the representable range of dates spans fewer than 2^32 hours
+        let exact = ExactDuration {
+            hours: (total_seconds / 3600) as u32,
+            minutes: ((total_seconds / 60) % 60) as u32,
+            seconds: (total_seconds % 60) as u32,
+            frac: FractionalSecond::new(nanos).ok(),
+        };
+
+        SignedDuration {
+            sign,
+            duration: Duration::Exact(exact),
+        }
+    }
+
+    /// Returns the number of nanoseconds from 1970-01-01T00:00:00 to `self`.
+    fn epoch_nanos(&self) -> i128 {
+        self.date.epoch_days() as i128 * NANOS_PER_DAY + self.time.nanos_since_midnight() as i128
+    }
+}
+
+/// Returns the length of `duration` in nanoseconds, treating each nominal day as 24 hours.
+fn duration_nanos(duration: Duration) -> i128 {
+    let exact_nanos = |exact: ExactDuration| exact.as_nanos() as i128;
+
+    match duration {
+        Duration::Nominal(nominal) => {
+            let days = nominal.weeks as i128 * 7 + nominal.days as i128;
+            days * NANOS_PER_DAY + nominal.exact.map_or(0, exact_nanos)
+        }
+        Duration::Exact(exact) => exact_nanos(exact),
+    }
+}
+
 /// An ISO 8601 date.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Date {
@@ -215,6 +307,218 @@ impl Date {
     }
 }
 
+impl Date {
+    /// The earliest representable date (0000-01-01).
+    pub const MIN: Self = Self {
+        year: Year::MIN,
+        month: Month::Jan,
+        day: Day::D01,
+    };
+
+    /// The latest representable date (9999-12-31).
+    pub const MAX: Self = Self {
+        year: Year::MAX,
+        month: Month::Dec,
+        day: Day::D31,
+    };
+
+    /// Returns the following date, or `None` if `self` is [`Date::MAX`].
+    pub const fn succ(self) -> Option<Self> {
+        if (self.day as u8) < (Date::maximum_day(self.year, self.month) as u8) {
+            let day = match Day::new(self.day as u8 + 1) {
+                Ok(day) => day,
+                Err(_) => unreachable!(),
+            };
+
+            return Some(Self { day, ..self });
+        }
+
+        match self.month {
+            Month::Dec => match Year::new(self.year.get() + 1) {
+                Ok(year) => Some(Self {
+                    year,
+                    month: Month::Jan,
+                    day: Day::D01,
+                }),
+                Err(_) => None,
+            },
+            month => {
+                let month = match Month::new(month as u8 + 1) {
+                    Ok(month) => month,
+                    Err(_) => unreachable!(),
+                };
+
+                Some(Self {
+                    month,
+                    day: Day::D01,
+                    ..self
+                })
+            }
+        }
+    }
+
+    /// Returns the preceding date, or `None` if `self` is [`Date::MIN`].
+    pub const fn pred(self) -> Option<Self> {
+        if (self.day as u8) > 1 {
+            let day = match Day::new(self.day as u8 - 1) {
+                Ok(day) => day,
+                Err(_) => unreachable!(),
+            };
+
+            return Some(Self { day, ..self });
+        }
+
+        let (year, month) = match self.month {
+            Month::Jan => match self.year.get().checked_sub(1) {
+                Some(year) => (Year(year), Month::Dec),
+                None => return None,
+            },
+            month => match Month::new(month as u8 - 1) {
+                Ok(month) => (self.year, month),
+                Err(_) => unreachable!(),
+            },
+        };
+
+        Some(Self {
+            year,
+            month,
+            day: Date::maximum_day(year, month),
+        })
+    }
+
+    /// Adds a signed number of days to `self`, returning `None` if the result lies outside the
+    /// range from [`Date::MIN`] through [`Date::MAX`].
+    pub fn checked_add_days(self, days: i64) -> Option<Self> {
+        Self::from_epoch_days(self.epoch_days().checked_add(days)?)
+    }
+
+    /// Returns the signed number of days from `rhs` to `self`.
+    pub fn days_since(self, rhs: Self) -> i64 {
+        self.epoch_days() - rhs.epoch_days()
+    }
+
+    /// Returns the day of the week on which this date falls.
+    pub fn weekday(self) -> Weekday {
+        // 1970-01-01 was a Thursday
+        let index = (self.epoch_days() + Weekday::Thursday as i64).rem_euclid(7);
+        Weekday::from_repr(index as u8).unwrap()
+    }
+
+    /// Returns the day of the year, ranging from 1 through 366.
+    pub fn ordinal(self) -> u16 {
+        let start = Self {
+            month: Month::Jan,
+            day: Day::D01,
+            ..self
+        };
+
+        (self.days_since(start) + 1) as u16
+    }
+
+    /// Returns the ISO 8601 week containing this date.
+    ///
+    /// Weeks start on Monday, and the first week of a year is the one containing its first
+    /// Thursday. Consequently, dates in late December may fall in week 1 of the following year,
+    /// and dates in early January may fall in week 52 or 53 of the preceding year.
+    pub fn iso_week(self) -> IsoWeek {
+        self.iso_week_date().1
+    }
+
+    /// Returns the ISO week-numbering year of `self`, which differs from [`Date::year`] for dates
+    /// that fall in the last week of the preceding year or the first week of the following year.
+    ///
+    /// The result is signed because 0000-01-01 belongs to the last week of year -1.
+    pub fn iso_week_year(self) -> i32 {
+        self.iso_week_date().0
+    }
+
+    /// Returns the date with the given 1-based day of the year, or `None` if `ordinal` is zero or
+    /// exceeds the number of days in `year`.
+    pub fn from_ordinal(year: Year, ordinal: u16) -> Option<Self> {
+        if ordinal == 0 || ordinal > year.days() {
+            return None;
+        }
+
+        let start = days_from_civil(year.get() as i64, 1, 1);
+        Self::from_epoch_days(start + ordinal as i64 - 1)
+    }
+
+    /// Returns the ISO week-numbering year and the ISO week of `self`. The year is signed because
+    /// 0000-01-01 belongs to the last week of year -1.
+    pub(crate) fn iso_week_date(self) -> (i32, IsoWeek) {
+        let year = self.year.get() as i32;
+        let weekday = self.weekday() as i32 + 1;
+        let week = (self.ordinal() as i32 - weekday + 10) / 7;
+
+        let (year, week) = if week < 1 {
+            (year - 1, iso_weeks_in_year(year - 1))
+        } else if week > iso_weeks_in_year(year) {
+            (year + 1, 1)
+        } else {
+            (year, week)
+        };
+
+        (year, IsoWeek::from_index(week as u8).unwrap())
+    }
+
+    /// Returns the number of days from 1970-01-01 to `self`.
+    fn epoch_days(self) -> i64 {
+        days_from_civil(self.year.get() as i64, self.month as i64, self.day as i64)
+    }
+
+    /// Inverse of [`Date::epoch_days`], returning `None` if the result is out of range.
+    fn from_epoch_days(days: i64) -> Option<Self> {
+        // shift the epoch to 0000-03-01, so that the leap day is the last day of each cycle
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        Some(Self {
+            year: Year::new(u16::try_from(year).ok()?).ok()?,
+            month: Month::new(month as u8).ok()?,
+            day: Day::new(day as u8).ok()?,
+        })
+    }
+}
+
+/// Returns the number of days from 1970-01-01 to the given proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // shift the epoch to 0000-03-01, so that the leap day is the last day of each cycle
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = (month + 9) % 12;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Returns the number of ISO weeks (52 or 53) in the given ISO week-numbering year.
+fn iso_weeks_in_year(year: i32) -> i32 {
+    let is_leap =
+        |y: i32| y.rem_euclid(4) == 0 && (y.rem_euclid(100) != 0 || y.rem_euclid(400) == 0);
+    // the weekday of December 31 as an index from 0 (Monday) through 6 (Sunday)
+    let dec_31 = (days_from_civil(year as i64, 12, 31) + Weekday::Thursday as i64).rem_euclid(7);
+
+    // a year has 53 weeks exactly when it starts or ends on a Thursday
+    if dec_31 == Weekday::Thursday as i64 || (is_leap(year) && dec_31 == Weekday::Friday as i64) {
+        53
+    } else {
+        52
+    }
+}
+
 /// An error arising from an invalid [`Date`] value.
 #[derive(Debug, Clone, Copy, Error, PartialEq, Eq)]
 pub enum InvalidDateError {
@@ -246,8 +550,8 @@ pub struct ImpossibleDateError {
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Year(u16);
 
-impl std::fmt::Debug for Year {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for Year {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         assert!(self.0 <= 9999);
         write!(f, "{:04} CE", self.0)
     }
@@ -260,12 +564,23 @@ impl Year {
     pub const MAX: Self = Self(9999);
 
     /// Returns `true` if this year is a leap year (RFC 3339 Appendix C).
-    pub const fn is_leap_year(self) -> bool {
+    pub const fn is_leap(self) -> bool {
         let year = self.0;
         // as given by RFC 3339, Appendix C
         year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
     }
 
+    /// Returns `true` if this year is a leap year; equivalent to [`Year::is_leap`].
+    #[inline(always)]
+    pub const fn is_leap_year(self) -> bool {
+        self.is_leap()
+    }
+
+    /// Returns the number of days in this year (either 365 or 366).
+    pub const fn days(self) -> u16 {
+        if self.is_leap() { 366 } else { 365 }
+    }
+
     /// Creates a `Year` from a raw `u16`, returning an error if greater than 9999.
     #[inline(always)]
     pub const fn new(value: u16) -> Result<Self, InvalidYearError> {
@@ -283,50 +598,50 @@ impl Year {
     }
 }
 
-impl std::fmt::Display for Year {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Year {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:04}", self.0)
     }
 }
 
-impl std::fmt::Display for Month {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Month {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:02}", *self as u8)
     }
 }
 
-impl std::fmt::Display for Day {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Day {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:02}", *self as u8)
     }
 }
 
-impl std::fmt::Display for Date {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Date {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}-{}-{}", self.year, self.month, self.day)
     }
 }
 
-impl std::fmt::Display for Hour {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Hour {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:02}", *self as u8)
     }
 }
 
-impl std::fmt::Display for Minute {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Minute {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:02}", *self as u8)
     }
 }
 
-impl std::fmt::Display for Second {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Second {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{:02}", *self as u8)
     }
 }
 
-impl std::fmt::Display for Time {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Time {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}:{}:{}", self.hour, self.minute, self.second)?;
         if let Some(frac) = self.frac {
             // Format as ".NNN..." with trailing zeros stripped
@@ -340,11 +655,11 @@ impl std::fmt::Display for Time {
     }
 }
 
-impl<M> std::fmt::Display for DateTime<M>
+impl<M> core::fmt::Display for DateTime<M>
 where
     M: DateTimeMarker,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}T{}{}", self.date, self.time, M::SUFFIX)
     }
 }
@@ -390,8 +705,8 @@ impl From<Local> for TimeFormat {
     }
 }
 
-impl std::fmt::Display for DateTime<TimeFormat> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for DateTime<TimeFormat> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let suffix = match self.marker {
             TimeFormat::Utc => "Z",
             TimeFormat::Local => "",
@@ -430,12 +745,17 @@ impl Month {
             1..=12 => Ok({
                 // SAFETY: Month is repr(u8) and takes the values in the range 1..=12, which are
                 // the only possible values in this branch
-                unsafe { std::mem::transmute::<u8, Month>(value) }
+                unsafe { core::mem::transmute::<u8, Month>(value) }
             }),
             _ => Err(InvalidMonthError(value)),
         }
     }
 
+    /// Returns the number of days in `self` during the given `year`.
+    pub const fn days_in(self, year: Year) -> u8 {
+        Date::maximum_day(year, self) as u8
+    }
+
     /// Returns the month number of `self`, which lies in the range `1..=12`.
     pub const fn number(self) -> NonZero<u8> {
         // SAFETY: the value of (self as u8) can never be zero
@@ -512,7 +832,7 @@ impl Day {
             1..=31 => Ok({
                 // SAFETY: Day is repr(u8) and takes the values in the range 1..=31, which are
                 // the only possible values in this branch
-                unsafe { std::mem::transmute::<u8, Self>(value) }
+                unsafe { core::mem::transmute::<u8, Self>(value) }
             }),
             _ => Err(InvalidDayError(value)),
         }
@@ -577,6 +897,27 @@ impl Time {
     }
 }
 
+impl Time {
+    /// Returns the number of nanoseconds since midnight. A leap second is counted as a full
+    /// second, so 23:59:60 yields a value of exactly one day.
+    fn nanos_since_midnight(&self) -> u64 {
+        let seconds = self.hour as u64 * 3600 + self.minute as u64 * 60 + self.second as u64;
+        seconds * 1_000_000_000 + self.frac.map_or(0, |frac| frac.get().get() as u64)
+    }
+
+    /// Inverse of [`Time::nanos_since_midnight`] for values less than one day.
+    fn from_nanos_since_midnight(nanos: u64) -> Self {
+        let seconds = nanos / 1_000_000_000;
+
+        Self {
+            hour: Hour::new((seconds / 3600) as u8).unwrap(),
+            minute: Minute::new(((seconds / 60) % 60) as u8).unwrap(),
+            second: Second::new((seconds % 60) as u8).unwrap(),
+            frac: FractionalSecond::new((nanos % 1_000_000_000) as u32).ok(),
+        }
+    }
+}
+
 /// An error arising from an invalid [`Time`] value.
 #[derive(Debug, Clone, Copy, Error, PartialEq, Eq)]
 pub enum InvalidTimeError {
@@ -641,7 +982,7 @@ impl Hour {
                 true => Ok({
                     // SAFETY: `value` must be less than 24 in this branch, so it is a valid hour,
                     // and Hour is repr(u8)
-                    unsafe { std::mem::transmute::<u8, Hour>(value.get()) }
+                    unsafe { core::mem::transmute::<u8, Hour>(value.get()) }
                 }),
             },
         }
@@ -730,7 +1071,7 @@ impl Minute {
                 true => Ok({
                     // SAFETY: `value` must be less than 59 in this branch, so it is a valid minute,
                     // and Minute is repr(u8)
-                    unsafe { std::mem::transmute::<u8, Minute>(value.get()) }
+                    unsafe { core::mem::transmute::<u8, Minute>(value.get()) }
                 }),
             },
         }
@@ -820,7 +1161,7 @@ impl Second {
                 true => Ok({
                     // SAFETY: `value` must be less than 60 in this branch, so it is a valid second,
                     // and Second is repr(u8)
-                    unsafe { std::mem::transmute::<u8, Second>(value.get()) }
+                    unsafe { core::mem::transmute::<u8, Second>(value.get()) }
                 }),
             },
         }
@@ -909,7 +1250,7 @@ impl NonLeapSecond {
                 true => Ok({
                     // SAFETY: `value` must be less than 60 in this branch, so it is a valid second
                     // and not a leap second, and NonLeapSecond is repr(u8)
-                    unsafe { std::mem::transmute::<u8, NonLeapSecond>(value.get()) }
+                    unsafe { core::mem::transmute::<u8, NonLeapSecond>(value.get()) }
                 }),
             },
         }
@@ -935,8 +1276,8 @@ pub struct InvalidNonLeapSecondError(NonZero<u8>);
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct FractionalSecond(NonZero<u32>);
 
-impl std::fmt::Debug for FractionalSecond {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for FractionalSecond {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}ns", self.0.get())
     }
 }
@@ -1003,4 +1344,208 @@ mod tests {
         assert_eq!(IsoWeek::from_index(254), None);
         assert_eq!(IsoWeek::from_index(255), None);
     }
+
+    fn date(y: u16, m: u8, d: u8) -> Date {
+        Date::new(
+            Year::new(y).unwrap(),
+            Month::new(m).unwrap(),
+            Day::new(d).unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn date_time(y: u16, m: u8, d: u8, h: u8, min: u8, s: u8) -> DateTime<Utc> {
+        DateTime {
+            date: date(y, m, d),
+            time: Time::new(
+                Hour::new(h).unwrap(),
+                Minute::new(min).unwrap(),
+                Second::new(s).unwrap(),
+                None,
+            )
+            .unwrap(),
+            marker: Utc,
+        }
+    }
+
+    #[test]
+    fn date_succ_pred() {
+        assert_eq!(date(2024, 2, 28).succ(), Some(date(2024, 2, 29)));
+        assert_eq!(date(2023, 2, 28).succ(), Some(date(2023, 3, 1)));
+        assert_eq!(date(2023, 12, 31).succ(), Some(date(2024, 1, 1)));
+        assert_eq!(date(2024, 3, 1).pred(), Some(date(2024, 2, 29)));
+        assert_eq!(date(2024, 1, 1).pred(), Some(date(2023, 12, 31)));
+        assert_eq!(Date::MAX.succ(), None);
+        assert_eq!(Date::MIN.pred(), None);
+    }
+
+    #[test]
+    fn date_epoch_days_roundtrip() {
+        let mut current = Date::MIN;
+        let mut days = current.epoch_days();
+
+        while let Some(next) = current.succ() {
+            days += 1;
+            assert_eq!(next.epoch_days(), days);
+            assert_eq!(Date::from_epoch_days(days), Some(next));
+            current = next;
+        }
+
+        assert_eq!(current, Date::MAX);
+        assert_eq!(date(1970, 1, 1).epoch_days(), 0);
+        assert_eq!(Date::from_epoch_days(Date::MAX.epoch_days() + 1), None);
+        assert_eq!(Date::from_epoch_days(Date::MIN.epoch_days() - 1), None);
+    }
+
+    #[test]
+    fn date_weekday_and_ordinal() {
+        assert_eq!(date(1970, 1, 1).weekday(), Weekday::Thursday);
+        assert_eq!(date(2000, 1, 1).weekday(), Weekday::Saturday);
+        assert_eq!(date(2024, 2, 29).weekday(), Weekday::Thursday);
+        assert_eq!(date(0, 1, 1).weekday(), Weekday::Saturday);
+        assert_eq!(date(2024, 1, 1).ordinal(), 1);
+        assert_eq!(date(2024, 12, 31).ordinal(), 366);
+        assert_eq!(date(2023, 12, 31).ordinal(), 365);
+    }
+
+    #[test]
+    fn date_iso_week() {
+        assert_eq!(date(2021, 1, 3).iso_week_date(), (2020, IsoWeek::W53));
+        assert_eq!(date(2021, 1, 4).iso_week_date(), (2021, IsoWeek::W1));
+        assert_eq!(date(2024, 12, 30).iso_week_date(), (2025, IsoWeek::W1));
+        assert_eq!(date(2026, 12, 31).iso_week_date(), (2026, IsoWeek::W53));
+        assert_eq!(date(2008, 12, 29).iso_week_date(), (2009, IsoWeek::W1));
+        assert_eq!(date(2010, 1, 3).iso_week_date(), (2009, IsoWeek::W53));
+        assert_eq!(date(0, 1, 1).iso_week_date(), (-1, IsoWeek::W52));
+        assert_eq!(date(2024, 12, 30).iso_week_year(), 2025);
+        assert_eq!(date(2024, 12, 29).iso_week_year(), 2024);
+    }
+
+    #[test]
+    fn year_and_month_lengths() {
+        assert!(Year::new(2000).unwrap().is_leap());
+        assert!(!Year::new(1900).unwrap().is_leap());
+        assert_eq!(Year::new(2024).unwrap().days(), 366);
+        assert_eq!(Year::new(2023).unwrap().days(), 365);
+
+        let leap = Year::new(2024).unwrap();
+        let common = Year::new(2023).unwrap();
+        assert_eq!(Month::Feb.days_in(leap), 29);
+        assert_eq!(Month::Feb.days_in(common), 28);
+        assert_eq!(Month::Apr.days_in(common), 30);
+        let total: u16 = Month::iter().map(|m| m.days_in(leap) as u16).sum();
+        assert_eq!(total, 366);
+    }
+
+    #[test]
+    fn date_from_ordinal() {
+        let year = Year::new(2024).unwrap();
+        assert_eq!(Date::from_ordinal(year, 1), Some(date(2024, 1, 1)));
+        assert_eq!(Date::from_ordinal(year, 60), Some(date(2024, 2, 29)));
+        assert_eq!(Date::from_ordinal(year, 366), Some(date(2024, 12, 31)));
+        assert_eq!(Date::from_ordinal(year, 367), None);
+        assert_eq!(Date::from_ordinal(year, 0), None);
+        assert_eq!(Date::from_ordinal(Year::new(2023).unwrap(), 366), None);
+        assert_eq!(Date::from_ordinal(Year::MAX, 365), Some(Date::MAX));
+
+        for ordinal in 1..=366 {
+            assert_eq!(
+                Date::from_ordinal(year, ordinal).unwrap().ordinal(),
+                ordinal
+            );
+        }
+    }
+
+    #[test]
+    fn date_time_checked_add() {
+        use crate::duration::NominalDuration;
+
+        let start = date_time(2024, 2, 28, 23, 30, 0);
+        let exact = Duration::Exact(ExactDuration {
+            hours: 1,
+            minutes: 0,
+            seconds: 0,
+            frac: None,
+        });
+        assert_eq!(
+            start.checked_add(exact),
+            Some(date_time(2024, 2, 29, 0, 30, 0))
+        );
+        assert_eq!(
+            start.checked_sub(exact),
+            Some(date_time(2024, 2, 28, 22, 30, 0))
+        );
+
+        let nominal = Duration::Nominal(NominalDuration {
+            weeks: 1,
+            days: 1,
+            exact: None,
+        });
+        assert_eq!(
+            start.checked_add(nominal),
+            Some(date_time(2024, 3, 7, 23, 30, 0))
+        );
+
+        let end = date_time(9999, 12, 31, 23, 59, 59);
+        let second = Duration::Exact(ExactDuration {
+            seconds: 1,
+            ..Default::default()
+        });
+        assert_eq!(end.checked_add(second), None);
+        assert_eq!(
+            DateTime {
+                date: Date::MIN,
+                ..date_time(0, 1, 1, 0, 0, 0)
+            }
+            .checked_sub(second),
+            None
+        );
+    }
+
+    #[test]
+    fn date_time_leap_second_carries() {
+        let leap = date_time(2016, 12, 31, 23, 59, 60);
+        let zero = Duration::Exact(ExactDuration::default());
+        assert_eq!(leap.checked_add(zero), Some(date_time(2017, 1, 1, 0, 0, 0)));
+    }
+
+    #[test]
+    fn date_time_signed_duration_since() {
+        let a = date_time(2024, 1, 1, 0, 0, 0);
+        let b = date_time(2024, 1, 2, 1, 2, 3);
+
+        let forward = b.signed_duration_since(&a);
+        assert_eq!(forward.sign, Sign::Pos);
+        assert_eq!(
+            forward.duration,
+            Duration::Exact(ExactDuration {
+                hours: 25,
+                minutes: 2,
+                seconds: 3,
+                frac: None,
+            })
+        );
+        assert_eq!(a.checked_add_signed(forward), Some(b));
+
+        let backward = a.signed_duration_since(&b);
+        assert_eq!(backward.sign, Sign::Neg);
+        assert_eq!(b.checked_add_signed(backward), Some(a));
+
+        let mut c = a;
+        c.time = Time::new(
+            Hour::H00,
+            Minute::M00,
+            Second::S00,
+            FractionalSecond::new(5).ok(),
+        )
+        .unwrap();
+        let frac = c.signed_duration_since(&a);
+        assert_eq!(
+            frac.duration,
+            Duration::Exact(ExactDuration {
+                frac: FractionalSecond::new(5).ok(),
+                ..Default::default()
+            })
+        );
+    }
 }
@@ -211,3 +211,79 @@ fn month_number_values() {
     assert_eq!(Month::Jan.number().get(), 1);
     assert_eq!(Month::Dec.number().get(), 12);
 }
+
+// ── Date arithmetic ──────────────────────────────────────────────────
+
+#[test]
+fn date_checked_add_days_across_years() {
+    let date = Date::new(Year::new(2023).unwrap(), Month::Dec, Day::D31).unwrap();
+    let next = Date::new(Year::new(2024).unwrap(), Month::Mar, Day::D01).unwrap();
+    assert_eq!(date.checked_add_days(61), Some(next));
+    assert_eq!(next.checked_add_days(-61), Some(date));
+    assert_eq!(next.days_since(date), 61);
+    assert_eq!(Date::MAX.checked_add_days(1), None);
+    assert_eq!(Date::MIN.checked_add_days(-1), None);
+}
+
+#[test]
+fn date_iso_week_year_boundaries() {
+    let dec_29_2025 = Date::new(Year::new(2025).unwrap(), Month::Dec, Day::D29).unwrap();
+    assert_eq!(dec_29_2025.weekday(), Weekday::Monday);
+    assert_eq!(dec_29_2025.iso_week(), IsoWeek::W1);
+
+    let jan_1_2027 = Date::new(Year::new(2027).unwrap(), Month::Jan, Day::D01).unwrap();
+    assert_eq!(jan_1_2027.iso_week(), IsoWeek::W53);
+}
+
+#[test]
+fn uri_components() {
+    let uri = Uri::new("https://ana@Example.COM:8443/a/b?c=d&e#frag").unwrap();
+    assert_eq!(uri.scheme(), "https");
+    assert_eq!(uri.authority(), Some("ana@Example.COM:8443"));
+    assert_eq!(uri.path(), "/a/b");
+    assert_eq!(uri.query(), Some("c=d&e"));
+    assert_eq!(uri.fragment(), Some("frag"));
+
+    let uri = Uri::new("mailto:ana@example.com?subject=hi").unwrap();
+    assert_eq!(uri.authority(), None);
+    assert_eq!(uri.path(), "ana@example.com");
+    assert_eq!(uri.query(), Some("subject=hi"));
+    assert_eq!(uri.fragment(), None);
+
+    let uri = Uri::new("file:///etc/hosts").unwrap();
+    assert_eq!(uri.authority(), Some(""));
+    assert_eq!(uri.path(), "/etc/hosts");
+}
+
+#[test]
+fn uri_strict_and_lenient_parsing() {
+    assert!(Uri::parse_strict("https://example.com/a%20b?q=[1]").is_ok());
+    assert_eq!(
+        Uri::parse_strict("https://example.com/a b"),
+        Err(InvalidUriError::InvalidChar { index: 21, c: ' ' })
+    );
+    assert_eq!(
+        Uri::parse_strict("https://example.com/%2"),
+        Err(InvalidUriError::InvalidPercentEncoding { index: 20 })
+    );
+    assert_eq!(Uri::parse_strict("1a:b"), Err(InvalidUriError::SchemeStartsWithNonLetter));
+
+    assert_eq!(Uri::parse_lenient("  mailto:ana@example.com\n").unwrap().as_str(), "mailto:ana@example.com");
+    assert!(Uri::parse_lenient("https://example.com/a b").is_ok());
+}
+
+#[test]
+fn uri_normalization() {
+    let normalized = |s| Uri::new(s).unwrap().normalized().as_str().to_owned();
+    assert_eq!(
+        normalized("HTTPS://Ana@Example.COM/a/./b/../c/%7euser%2f?Q=%3a#%7E"),
+        "https://Ana@example.com/a/c/~user%2F?Q=%3A#~"
+    );
+    assert_eq!(normalized("http://example.com/a/b/.."), "http://example.com/a/");
+    assert_eq!(normalized("http://example.com"), "http://example.com");
+    assert_eq!(
+        normalized("MAILTO:Ana.Lopez@Example.COM"),
+        normalized("mailto:ana.lopez@example.com")
+    );
+    assert_eq!(normalized("urn:ISBN:%zz"), "urn:ISBN:%zz");
+}
@@ -0,0 +1,6 @@
+//! Conversions between iCalendar data and the recurrence models of third-party calendar APIs.
+//!
+//! Conversions which do not depend on the iCalendar text format live in
+//! [`rfc5545_types::interop`].
+
+pub mod google;
@@ -0,0 +1,191 @@
+//! Google Calendar API recurrence conversions.
+//!
+//! The Google Calendar API represents the recurrence of an event as a `recurrence` array of
+//! iCalendar content lines, each of which is an `RRULE`, `EXRULE`, `RDATE`, or `EXDATE` property
+//! without a trailing line break. [`GoogleRecurrence`] parses such an array into typed properties
+//! and renders them back into the same shape.
+
+use crate::{
+    model::{
+        parameter::Params,
+        primitive::{DateTimeOrDate, ExDateSeq, RDateSeq},
+        property::Prop,
+        rrule::RRule,
+    },
+    parser::property::{PropValue, property},
+    serializer::{WriteIcal, write_content_line, write_exdate_prop, write_rdate_seq_prop},
+};
+
+use winnow::Parser;
+
+/// The recurrence properties of a Google Calendar event.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GoogleRecurrence {
+    /// The `RRULE` properties.
+    pub rrule: Vec<Prop<RRule, Params>>,
+    /// The `EXRULE` properties, which RFC 5545 deprecates but Google still accepts.
+    pub exrule: Vec<Prop<RRule, Params>>,
+    /// The `RDATE` properties.
+    pub rdate: Vec<Prop<RDateSeq, Params>>,
+    /// The `EXDATE` properties, with one entry per excluded date.
+    pub exdate: Vec<Prop<DateTimeOrDate, Params>>,
+}
+
+/// An error arising from an invalid Google Calendar `recurrence` array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoogleRecurrenceError {
+    /// The line at the given index is not a valid content line.
+    InvalidLine(usize),
+    /// The line at the given index is not a recurrence property.
+    UnexpectedProperty(usize),
+}
+
+impl std::fmt::Display for GoogleRecurrenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidLine(index) => {
+                write!(f, "line {index} is not a valid iCalendar content line")
+            }
+            Self::UnexpectedProperty(index) => {
+                write!(
+                    f,
+                    "line {index} is not an RRULE, EXRULE, RDATE, or EXDATE property"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GoogleRecurrenceError {}
+
+impl GoogleRecurrence {
+    /// Parses the lines of a Google Calendar `recurrence` array.
+    pub fn from_lines<S: AsRef<str>>(lines: &[S]) -> Result<Self, GoogleRecurrenceError> {
+        let mut recurrence = Self::default();
+
+        for (index, line) in lines.iter().enumerate() {
+            let prop = property::<_, ()>
+                .parse(line.as_ref())
+                .map_err(|_| GoogleRecurrenceError::InvalidLine(index))?
+                .try_into_known()
+                .map_err(|_| GoogleRecurrenceError::UnexpectedProperty(index))?;
+
+            match prop.value {
+                PropValue::RRule(prop) => recurrence.rrule.push(prop),
+                PropValue::ExRule(prop) => recurrence.exrule.push(prop),
+                PropValue::RDateSeq(prop) => recurrence.rdate.push(prop),
+                PropValue::ExDateSeq(ExDateSeq::DateTime(dates), params) => {
+                    recurrence
+                        .exdate
+                        .extend(dates.into_iter().map(|date_time| Prop {
+                            value: DateTimeOrDate::DateTime(date_time),
+                            params: params.clone(),
+                        }));
+                }
+                PropValue::ExDateSeq(ExDateSeq::Date(dates), params) => {
+                    recurrence.exdate.extend(dates.into_iter().map(|date| Prop {
+                        value: DateTimeOrDate::Date(date),
+                        params: params.clone(),
+                    }));
+                }
+                _ => return Err(GoogleRecurrenceError::UnexpectedProperty(index)),
+            }
+        }
+
+        Ok(recurrence)
+    }
+
+    /// Renders `self` as the lines of a Google Calendar `recurrence` array.
+    ///
+    /// Rules are written first, followed by the RDATE and EXDATE properties. Each EXDATE entry is
+    /// written on its own line.
+    pub fn to_lines(&self) -> Vec<String> {
+        let mut lines = Vec::with_capacity(
+            self.rrule.len() + self.exrule.len() + self.rdate.len() + self.exdate.len(),
+        );
+
+        // the writers below terminate each content line with CRLF, which Google does not expect
+        let mut push_line = |write: &dyn Fn(&mut String) -> std::fmt::Result| {
+            let mut line = String::new();
+            write(&mut line).expect("writing to String cannot fail");
+            line.truncate(line.trim_end_matches("\r\n").len());
+            lines.push(line);
+        };
+
+        for prop in &self.rrule {
+            push_line(&|w| write_content_line("RRULE", &prop.params, &prop.value, w));
+        }
+
+        for prop in &self.exrule {
+            push_line(&|w| write_content_line("EXRULE", &prop.params, &prop.value, w));
+        }
+
+        for prop in &self.rdate {
+            push_line(&|w| write_rdate_seq_prop("RDATE", prop, w));
+        }
+
+        for prop in &self.exdate {
+            push_line(&|w| write_exdate_prop(prop, w));
+        }
+
+        lines
+    }
+
+    /// Returns `true` if `self` contains no recurrence properties.
+    pub fn is_empty(&self) -> bool {
+        self.rrule.is_empty()
+            && self.exrule.is_empty()
+            && self.rdate.is_empty()
+            && self.exdate.is_empty()
+    }
+
+    /// Returns the value of the first `RRULE` property, rendered without its property name.
+    pub fn first_rrule_string(&self) -> Option<String> {
+        self.rrule.first().map(|prop| prop.value.to_ical_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn google_recurrence_roundtrip() {
+        let lines = [
+            "RRULE:FREQ=WEEKLY;UNTIL=20110701T170000Z;BYDAY=MO,FR",
+            "EXDATE;TZID=America/New_York:20110610T100000,20110617T100000",
+            "RDATE;VALUE=DATE:20110704",
+        ];
+
+        let recurrence = GoogleRecurrence::from_lines(&lines).unwrap();
+        assert_eq!(recurrence.rrule.len(), 1);
+        assert_eq!(recurrence.exdate.len(), 2);
+        assert_eq!(recurrence.rdate.len(), 1);
+
+        let rendered = recurrence.to_lines();
+        assert_eq!(rendered.len(), 4);
+        assert!(rendered[0].starts_with("RRULE:FREQ=WEEKLY"));
+        assert!(rendered.iter().all(|line| !line.ends_with('\n')));
+        assert_eq!(GoogleRecurrence::from_lines(&rendered).unwrap(), recurrence);
+    }
+
+    #[test]
+    fn google_recurrence_rejects_other_properties() {
+        assert_eq!(
+            GoogleRecurrence::from_lines(&["DTSTART:20110601T100000Z"]),
+            Err(GoogleRecurrenceError::UnexpectedProperty(0))
+        );
+        assert_eq!(
+            GoogleRecurrence::from_lines(&["RRULE:FREQ=WEEKLY", "RRULE:FREQ=NEVER"]),
+            Err(GoogleRecurrenceError::InvalidLine(1))
+        );
+    }
+
+    #[test]
+    fn google_recurrence_empty() {
+        let recurrence = GoogleRecurrence::from_lines::<&str>(&[]).unwrap();
+        assert!(recurrence.is_empty());
+        assert!(recurrence.to_lines().is_empty());
+        assert_eq!(recurrence.first_rrule_string(), None);
+    }
+}
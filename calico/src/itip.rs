@@ -0,0 +1,622 @@
+//! iTIP (RFC 5546) scheduling messages.
+//!
+//! An iTIP message is a VCALENDAR whose METHOD property names a scheduling operation, such as
+//! REQUEST or REPLY. RFC 5546 §3.2 restricts the components and properties which may occur in
+//! a message according to its method; [`validate`] checks a [`Calendar`] against these
+//! restrictions for VEVENT components, and [`request`], [`reply`], and [`cancel`] construct
+//! messages satisfying them from an existing [`Event`].
+//!
+//! Only the properties which the requirement tables of RFC 5546 mark as required or forbidden
+//! for some method are checked, and VTODO, VJOURNAL, and VFREEBUSY components are ignored.
+
+use crate::model::{
+    component::{Calendar, CalendarComponent, Event},
+    primitive::{DateTime, Method, ParticipationStatus, Status, Token, Utc, Version},
+    property::{Prop, StaticProp},
+    string::Uri,
+};
+
+use Presence::{Any, Forbidden, OneOrMore, Optional, Required};
+use StaticProp::{
+    Attendee, DtStamp, DtStart, Organizer, RecurId, RequestStatus, Sequence, Summary, Uid,
+};
+
+/// The number of times a property or component may occur in an iTIP message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Presence {
+    /// Must not occur.
+    Forbidden,
+    /// May occur at most once.
+    Optional,
+    /// Must occur exactly once.
+    Required,
+    /// Must occur at least once.
+    OneOrMore,
+    /// May occur any number of times.
+    Any,
+}
+
+impl Presence {
+    /// Returns `true` iff `count` occurrences are allowed.
+    pub fn allows(self, count: usize) -> bool {
+        match self {
+            Self::Forbidden => count == 0,
+            Self::Optional => count <= 1,
+            Self::Required => count == 1,
+            Self::OneOrMore => count >= 1,
+            Self::Any => true,
+        }
+    }
+}
+
+impl std::fmt::Display for Presence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Forbidden => write!(f, "0"),
+            Self::Optional => write!(f, "0 or 1"),
+            Self::Required => write!(f, "1"),
+            Self::OneOrMore => write!(f, "1+"),
+            Self::Any => write!(f, "0+"),
+        }
+    }
+}
+
+/// The restrictions of RFC 5546 §3.2 on the VEVENT components of a message.
+struct EventTable {
+    events: Presence,
+    alarms: Presence,
+    props: &'static [(StaticProp, Presence)],
+}
+
+const fn table(
+    events: Presence,
+    alarms: Presence,
+    props: &'static [(StaticProp, Presence)],
+) -> EventTable {
+    EventTable {
+        events,
+        alarms,
+        props,
+    }
+}
+
+const PUBLISH: EventTable = table(
+    OneOrMore,
+    Any,
+    &[
+        (Attendee, Forbidden),
+        (DtStamp, Required),
+        (DtStart, Required),
+        (Organizer, Required),
+        (RecurId, Optional),
+        (RequestStatus, Forbidden),
+        (Sequence, Optional),
+        (Summary, Required),
+        (Uid, Required),
+    ],
+);
+
+const REQUEST: EventTable = table(
+    OneOrMore,
+    Any,
+    &[
+        (Attendee, OneOrMore),
+        (DtStamp, Required),
+        (DtStart, Required),
+        (Organizer, Required),
+        (RecurId, Optional),
+        (RequestStatus, Forbidden),
+        (Sequence, Optional),
+        (Summary, Required),
+        (Uid, Required),
+    ],
+);
+
+const REPLY: EventTable = table(
+    OneOrMore,
+    Forbidden,
+    &[
+        (Attendee, Required),
+        (DtStamp, Required),
+        (Organizer, Required),
+        (RecurId, Optional),
+        (RequestStatus, Any),
+        (Sequence, Optional),
+        (Uid, Required),
+    ],
+);
+
+const ADD: EventTable = table(
+    OneOrMore,
+    Any,
+    &[
+        (Attendee, Any),
+        (DtStamp, Required),
+        (DtStart, Required),
+        (Organizer, Required),
+        (RecurId, Forbidden),
+        (RequestStatus, Forbidden),
+        (Sequence, Required),
+        (Summary, Required),
+        (Uid, Required),
+    ],
+);
+
+const CANCEL: EventTable = table(
+    OneOrMore,
+    Forbidden,
+    &[
+        (Attendee, Any),
+        (DtStamp, Required),
+        (Organizer, Required),
+        (RecurId, Optional),
+        (RequestStatus, Forbidden),
+        (Sequence, Required),
+        (Uid, Required),
+    ],
+);
+
+const REFRESH: EventTable = table(
+    Required,
+    Forbidden,
+    &[
+        (Attendee, Required),
+        (DtStamp, Required),
+        (DtStart, Forbidden),
+        (Organizer, Required),
+        (RecurId, Optional),
+        (RequestStatus, Forbidden),
+        (Sequence, Forbidden),
+        (Summary, Forbidden),
+        (Uid, Required),
+    ],
+);
+
+const COUNTER: EventTable = table(
+    Required,
+    Any,
+    &[
+        (Attendee, Any),
+        (DtStamp, Required),
+        (DtStart, Required),
+        (Organizer, Required),
+        (RecurId, Optional),
+        (RequestStatus, Any),
+        (Sequence, Optional),
+        (Summary, Required),
+        (Uid, Required),
+    ],
+);
+
+const DECLINE_COUNTER: EventTable = table(
+    Required,
+    Forbidden,
+    &[
+        (Attendee, Any),
+        (DtStamp, Required),
+        (Organizer, Required),
+        (RecurId, Optional),
+        (RequestStatus, Any),
+        (Sequence, Optional),
+        (Uid, Required),
+    ],
+);
+
+fn event_table(method: Method) -> Option<&'static EventTable> {
+    match method {
+        Method::Publish => Some(&PUBLISH),
+        Method::Request => Some(&REQUEST),
+        Method::Reply => Some(&REPLY),
+        Method::Add => Some(&ADD),
+        Method::Cancel => Some(&CANCEL),
+        Method::Refresh => Some(&REFRESH),
+        Method::Counter => Some(&COUNTER),
+        Method::DeclineCounter => Some(&DECLINE_COUNTER),
+        _ => None,
+    }
+}
+
+/// Returns the number of times the VEVENT components of a message with the given `method` may
+/// occur, or `None` if the method is not known.
+pub fn event_presence(method: Method) -> Option<Presence> {
+    event_table(method).map(|table| table.events)
+}
+
+/// Returns the restrictions on the properties of the VEVENT components of a message with the
+/// given `method`, or `None` if the method is not known.
+pub fn event_requirements(method: Method) -> Option<&'static [(StaticProp, Presence)]> {
+    event_table(method).map(|table| table.props)
+}
+
+/// A violation of the restrictions of RFC 5546 on an iTIP message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItipViolation {
+    /// The calendar has no METHOD property.
+    MissingMethod,
+    /// The METHOD property names a method which is not defined by RFC 5546.
+    UnknownMethod(String),
+    /// The calendar contains a number of VEVENT components which the method does not allow.
+    EventCount { expected: Presence, found: usize },
+    /// The VEVENT component at index `event` (among the VEVENT components of the calendar)
+    /// contains `prop` a number of times which the method does not allow.
+    PropertyCount {
+        event: usize,
+        prop: StaticProp,
+        expected: Presence,
+        found: usize,
+    },
+    /// The VEVENT component at index `event` contains VALARM components, which the method does
+    /// not allow.
+    UnexpectedAlarm { event: usize },
+    /// The VEVENT components do not all have the same UID, which every method except PUBLISH
+    /// requires.
+    MixedUids,
+}
+
+impl std::fmt::Display for ItipViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingMethod => write!(f, "the calendar has no METHOD property"),
+            Self::UnknownMethod(method) => write!(f, "unknown iTIP method {method:?}"),
+            Self::EventCount { expected, found } => {
+                write!(f, "expected {expected} VEVENT components, found {found}")
+            }
+            Self::PropertyCount {
+                event,
+                prop,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected {expected} {} properties in VEVENT {event}, found {found}",
+                prop_name(*prop)
+            ),
+            Self::UnexpectedAlarm { event } => {
+                write!(
+                    f,
+                    "VEVENT {event} contains a VALARM, which the method forbids"
+                )
+            }
+            Self::MixedUids => write!(f, "the VEVENT components have different UIDs"),
+        }
+    }
+}
+
+impl std::error::Error for ItipViolation {}
+
+/// Returns the name of a property which occurs in the requirement tables.
+fn prop_name(prop: StaticProp) -> &'static str {
+    match prop {
+        Attendee => "ATTENDEE",
+        DtStamp => "DTSTAMP",
+        DtStart => "DTSTART",
+        Organizer => "ORGANIZER",
+        RecurId => "RECURRENCE-ID",
+        RequestStatus => "REQUEST-STATUS",
+        Sequence => "SEQUENCE",
+        Summary => "SUMMARY",
+        Uid => "UID",
+        _ => "other",
+    }
+}
+
+/// Returns the number of occurrences of a property which occurs in the requirement tables.
+fn prop_count(event: &Event, prop: StaticProp) -> usize {
+    match prop {
+        Attendee => event.attendee().map_or(0, Vec::len),
+        DtStamp => event.dtstamp().is_some() as usize,
+        DtStart => event.dtstart().is_some() as usize,
+        Organizer => event.organizer().is_some() as usize,
+        RecurId => event.recurrence_id().is_some() as usize,
+        RequestStatus => event.request_status().map_or(0, Vec::len),
+        Sequence => event.sequence().is_some() as usize,
+        Summary => event.summary().is_some() as usize,
+        Uid => event.uid().is_some() as usize,
+        _ => 0,
+    }
+}
+
+/// Checks `calendar` against the restrictions of RFC 5546 §3.2 for its METHOD, returning the
+/// method if it satisfies them and every violation otherwise.
+pub fn validate(calendar: &Calendar) -> Result<Method, Vec<ItipViolation>> {
+    let method = match calendar.method().map(|prop| &prop.value) {
+        Some(Token::Known(method)) => *method,
+        Some(Token::Unknown(method)) => {
+            return Err(vec![ItipViolation::UnknownMethod(method.clone())]);
+        }
+        None => return Err(vec![ItipViolation::MissingMethod]),
+    };
+
+    let Some(table) = event_table(method) else {
+        return Err(vec![ItipViolation::UnknownMethod(method.to_string())]);
+    };
+
+    let events: Vec<&Event> = calendar
+        .components()
+        .iter()
+        .filter_map(|component| match component {
+            CalendarComponent::Event(event) => Some(event),
+            _ => None,
+        })
+        .collect();
+
+    let mut violations = Vec::new();
+    if !table.events.allows(events.len()) {
+        violations.push(ItipViolation::EventCount {
+            expected: table.events,
+            found: events.len(),
+        });
+    }
+
+    for (index, event) in events.iter().enumerate() {
+        for &(prop, expected) in table.props {
+            let found = prop_count(event, prop);
+            if !expected.allows(found) {
+                violations.push(ItipViolation::PropertyCount {
+                    event: index,
+                    prop,
+                    expected,
+                    found,
+                });
+            }
+        }
+
+        if !table.alarms.allows(event.alarms().len()) {
+            violations.push(ItipViolation::UnexpectedAlarm { event: index });
+        }
+    }
+
+    let mut uids = events
+        .iter()
+        .filter_map(|event| event.uid())
+        .map(|uid| &uid.value);
+    let mixed = uids
+        .next()
+        .is_some_and(|first| uids.any(|uid| uid != first));
+    if mixed && method != Method::Publish {
+        violations.push(ItipViolation::MixedUids);
+    }
+
+    if violations.is_empty() {
+        Ok(method)
+    } else {
+        Err(violations)
+    }
+}
+
+/// An error arising from the construction of an iTIP REPLY.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplyError {
+    /// The event has no ATTENDEE property for the replying calendar user.
+    UnknownAttendee,
+}
+
+impl std::fmt::Display for ReplyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownAttendee => write!(f, "the replying calendar user is not an attendee"),
+        }
+    }
+}
+
+impl std::error::Error for ReplyError {}
+
+/// Returns a REQUEST message inviting the attendees of `event`, with the given DTSTAMP.
+pub fn request(event: &Event, prod_id: &str, dtstamp: DateTime<Utc>) -> Calendar {
+    let mut event = event.clone();
+    event.set_dtstamp(Prop::from_value(dtstamp));
+    message(Method::Request, prod_id, event)
+}
+
+/// Returns a REPLY message from the calendar user `attendee` to the organizer of `event`, setting
+/// their participation status to `status`.
+///
+/// The reply carries the UID, ORGANIZER, SEQUENCE, and RECURRENCE-ID of `event`, together with
+/// the ATTENDEE property of `attendee`, whose PARTSTAT parameter is set to `status` and whose
+/// RSVP parameter is removed.
+pub fn reply(
+    event: &Event,
+    prod_id: &str,
+    dtstamp: DateTime<Utc>,
+    attendee: &Uri,
+    status: ParticipationStatus,
+) -> Result<Calendar, ReplyError> {
+    let mut prop = event
+        .attendee()
+        .into_iter()
+        .flatten()
+        .find(|prop| *prop.value == *attendee)
+        .cloned()
+        .ok_or(ReplyError::UnknownAttendee)?;
+    prop.params.set_participation_status(Token::Known(status));
+    prop.params.remove_rsvp_expectation();
+
+    let mut reply = reference(event, dtstamp);
+    reply.set_attendee(vec![prop]);
+    if let Some(sequence) = event.sequence() {
+        reply.set_sequence(sequence.clone());
+    }
+
+    Ok(message(Method::Reply, prod_id, reply))
+}
+
+/// Returns a CANCEL message cancelling `event` for all of its attendees.
+///
+/// The cancellation carries the UID, ORGANIZER, ATTENDEE, RECURRENCE-ID, and SUMMARY properties
+/// of `event`, a STATUS of CANCELLED, and a SEQUENCE one greater than that of `event`.
+pub fn cancel(event: &Event, prod_id: &str, dtstamp: DateTime<Utc>) -> Calendar {
+    let mut cancel = reference(event, dtstamp);
+    if let Some(attendee) = event.attendee() {
+        cancel.set_attendee(attendee.clone());
+    }
+    if let Some(summary) = event.summary() {
+        cancel.set_summary(summary.clone());
+    }
+
+    let sequence = event.sequence().map_or(0, |sequence| sequence.value);
+    cancel.set_sequence(Prop::from_value(sequence.saturating_add(1)));
+    cancel.set_status(Prop::from_value(Status::Cancelled));
+    message(Method::Cancel, prod_id, cancel)
+}
+
+/// Returns an event with the properties identifying `event` and the given DTSTAMP.
+fn reference(event: &Event, dtstamp: DateTime<Utc>) -> Event {
+    let mut reference = Event::new(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    reference.set_dtstamp(Prop::from_value(dtstamp));
+    if let Some(uid) = event.uid() {
+        reference.set_uid(uid.clone());
+    }
+    if let Some(organizer) = event.organizer() {
+        reference.set_organizer(organizer.clone());
+    }
+    if let Some(recurrence_id) = event.recurrence_id() {
+        reference.set_recurrence_id(recurrence_id.clone());
+    }
+    reference
+}
+
+/// Returns a VCALENDAR containing only `event`, with the given METHOD and PRODID.
+fn message(method: Method, prod_id: &str, event: Event) -> Calendar {
+    let mut calendar = Calendar::new(
+        Prop::from_value(Token::Known(Version::V2_0)),
+        Prop::from_value(prod_id.to_string()),
+        vec![CalendarComponent::Event(event)],
+    );
+    calendar.set_method(Prop::from_value(Token::Known(method)));
+    calendar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::primitive::{Date, Day, Hour, Minute, Month, Second, Time, Year},
+        serializer::WriteIcal,
+    };
+
+    const INVITATION: &str = "BEGIN:VCALENDAR\r\n\
+        VERSION:2.0\r\n\
+        PRODID:-//Example//EN\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:meeting-1@example.com\r\n\
+        DTSTAMP:20240101T090000Z\r\n\
+        DTSTART:20240115T140000Z\r\n\
+        SUMMARY:Planning\r\n\
+        SEQUENCE:2\r\n\
+        ORGANIZER:mailto:alice@example.com\r\n\
+        ATTENDEE;RSVP=TRUE:mailto:bob@example.com\r\n\
+        ATTENDEE;RSVP=TRUE:mailto:carol@example.com\r\n\
+        BEGIN:VALARM\r\n\
+        ACTION:DISPLAY\r\n\
+        DESCRIPTION:Reminder\r\n\
+        TRIGGER:-PT15M\r\n\
+        END:VALARM\r\n\
+        END:VEVENT\r\n\
+        END:VCALENDAR\r\n";
+
+    fn calendar(s: &str) -> Calendar {
+        Calendar::parse(s).unwrap().remove(0)
+    }
+
+    fn event(calendar: &Calendar) -> &Event {
+        match calendar.components().as_slice() {
+            [CalendarComponent::Event(event)] => event,
+            other => panic!("expected a single event, got {other:?}"),
+        }
+    }
+
+    fn dtstamp() -> DateTime<Utc> {
+        DateTime {
+            date: Date::new(Year::new(2024).unwrap(), Month::Jan, Day::D02).unwrap(),
+            time: Time::new(Hour::H08, Minute::M00, Second::S00, None).unwrap(),
+            marker: Utc,
+        }
+    }
+
+    #[test]
+    fn validate_requires_method() {
+        let mut invitation = calendar(INVITATION);
+        assert_eq!(
+            validate(&invitation),
+            Err(vec![ItipViolation::MissingMethod])
+        );
+
+        invitation.set_method(Prop::from_value(Token::Unknown("X-POLL".into())));
+        assert_eq!(
+            validate(&invitation),
+            Err(vec![ItipViolation::UnknownMethod("X-POLL".into())])
+        );
+
+        invitation.set_method(Prop::from_value(Token::Known(Method::Request)));
+        assert_eq!(validate(&invitation), Ok(Method::Request));
+    }
+
+    #[test]
+    fn validate_reports_violations() {
+        let mut invitation = calendar(INVITATION);
+        invitation.set_method(Prop::from_value(Token::Known(Method::Reply)));
+        assert_eq!(
+            validate(&invitation),
+            Err(vec![
+                ItipViolation::PropertyCount {
+                    event: 0,
+                    prop: StaticProp::Attendee,
+                    expected: Presence::Required,
+                    found: 2,
+                },
+                ItipViolation::UnexpectedAlarm { event: 0 },
+            ])
+        );
+
+        invitation.set_method(Prop::from_value(Token::Known(Method::Publish)));
+        let violations = validate(&invitation).unwrap_err();
+        assert_eq!(
+            violations[0].to_string(),
+            "expected 0 ATTENDEE properties in VEVENT 0, found 2"
+        );
+    }
+
+    #[test]
+    fn build_messages() {
+        let invitation = calendar(INVITATION);
+        let original = event(&invitation);
+
+        let invitation = request(original, "-//Example//EN", dtstamp());
+        assert_eq!(validate(&invitation), Ok(Method::Request));
+
+        let bob = Uri::new("mailto:bob@example.com").unwrap();
+        let response = reply(
+            original,
+            "-//Example//EN",
+            dtstamp(),
+            bob,
+            ParticipationStatus::Accepted,
+        )
+        .unwrap();
+        assert_eq!(validate(&response), Ok(Method::Reply));
+        let text = response.to_ical_string();
+        assert!(text.contains("ATTENDEE;PARTSTAT=ACCEPTED:mailto:bob@example.com\r\n"));
+        assert!(text.contains("SEQUENCE:2\r\n"));
+        assert!(!text.contains("carol"));
+
+        let dave = Uri::new("mailto:dave@example.com").unwrap();
+        assert_eq!(
+            reply(
+                original,
+                "-//Example//EN",
+                dtstamp(),
+                dave,
+                ParticipationStatus::Declined
+            ),
+            Err(ReplyError::UnknownAttendee)
+        );
+
+        let cancellation = cancel(original, "-//Example//EN", dtstamp());
+        assert_eq!(validate(&cancellation), Ok(Method::Cancel));
+        let cancelled = event(&cancellation);
+        assert_eq!(cancelled.sequence().unwrap().value, 3);
+        assert_eq!(cancelled.status().unwrap().value, Status::Cancelled);
+        assert_eq!(cancelled.attendee().unwrap().len(), 2);
+        assert!(cancelled.alarms().is_empty());
+    }
+}
@@ -1,5 +1,7 @@
 #![doc = include_str!("../README.md")]
 
+pub mod interop;
+pub mod itip;
 pub mod model;
 pub mod parser;
 pub mod serializer;
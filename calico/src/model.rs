@@ -12,6 +12,7 @@ pub mod parameter;
 pub mod primitive;
 pub mod property;
 pub mod string;
+pub mod tz;
 
 pub use rfc5545_types::rrule;
 
@@ -66,6 +66,25 @@ impl Calendar {
         icalendar_stream::<_, ParseError>(&mut input).map_err(|e| e.with_total_len(total))
     }
 
+    /// Parses an iCalendar stream from a string with the given
+    /// [`Config`](crate::parser::config::Config), returning zero or more [`Calendar`] objects.
+    ///
+    /// The line ending of the config is set from the input before parsing.
+    pub fn parse_with_config(
+        s: &str,
+        config: &mut impl crate::parser::config::Config,
+    ) -> Result<Vec<Calendar>, crate::parser::error::ParseError> {
+        use crate::parser::{
+            component::icalendar_stream_with_config, config::LineEnding, error::ParseError,
+            escaped::AsEscaped,
+        };
+        let total = s.len();
+        config.set_line_ending(LineEnding::detect(s.as_bytes()));
+        let mut input = s.as_escaped();
+        icalendar_stream_with_config::<_, ParseError>(&mut input, config)
+            .map_err(|e| e.with_total_len(total))
+    }
+
     /// Parses an iCalendar stream from a byte slice, returning zero or more [`Calendar`] objects.
     pub fn parse_bytes(b: &[u8]) -> Result<Vec<Calendar>, crate::parser::error::ParseError> {
         use crate::parser::{
@@ -0,0 +1,460 @@
+//! Evaluation of VTIMEZONE components to concrete UTC offsets.
+//!
+//! A [`TimeZone`] describes its UTC offsets by STANDARD and DAYLIGHT rules, each of which gives
+//! the onsets of an observance by a DTSTART together with RRULE and RDATE properties (RFC 5545
+//! §3.6.5). [`TimeZone::offset_at_utc`] and [`TimeZone::offset_at`] evaluate these rules, so that
+//! DATE-TIME values with a TZID parameter can be interpreted without a time zone database.
+//!
+//! The rules are expanded by [`vtimezone_to_transitions`], so only the recurrence rules it
+//! supports can be evaluated; other rules produce an
+//! [`UnsupportedRule`](TzEvalError::UnsupportedRule) error.
+
+use crate::model::{
+    component::{TimeZone, TzRule, TzRuleKind},
+    primitive::{
+        Date, DateTime, DateTimeOrDate, Hour, Local, Minute, Period, RDateSeq, Second, Time,
+        TimeFormat, Utc, UtcOffset, Year,
+    },
+};
+use rfc5545_types::tz::{Observance, TransitionError, vtimezone_to_transitions};
+
+/// A change of UTC offset in a [`TimeZone`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    /// The instant of the transition.
+    pub utc: DateTime<Utc>,
+    /// The local time of the transition, in terms of the offset before it.
+    pub local: DateTime<Local>,
+    /// The UTC offset before the transition.
+    pub offset_from: UtcOffset,
+    /// The UTC offset after the transition.
+    pub offset_to: UtcOffset,
+    /// The kind of the observance which begins at the transition.
+    pub kind: TzRuleKind,
+}
+
+/// The UTC offsets of a local date-time in a [`TimeZone`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalOffset {
+    /// The local date-time occurs exactly once.
+    Single(UtcOffset),
+    /// The local date-time occurs twice, because the clocks were turned back over it.
+    Ambiguous {
+        /// The offset of the first occurrence, which is in effect before the transition.
+        earlier: UtcOffset,
+        /// The offset of the second occurrence, which is in effect after the transition.
+        later: UtcOffset,
+    },
+    /// The local date-time never occurs, because the clocks were turned forward over it.
+    Gap {
+        /// The offset in effect before the gap.
+        before: UtcOffset,
+        /// The offset in effect after the gap.
+        after: UtcOffset,
+    },
+}
+
+impl LocalOffset {
+    /// Returns the offset which RFC 5545 §3.3.5 uses to interpret the local date-time: the offset
+    /// of its first occurrence if it is ambiguous, and the offset before the gap if it does not
+    /// occur.
+    pub fn resolve(self) -> UtcOffset {
+        match self {
+            Self::Single(offset) => offset,
+            Self::Ambiguous { earlier, .. } => earlier,
+            Self::Gap { before, .. } => before,
+        }
+    }
+}
+
+/// An error arising from the evaluation of a [`TimeZone`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TzEvalError {
+    /// The time zone has no STANDARD or DAYLIGHT rules.
+    NoRules,
+    /// The RRULE of the STANDARD or DAYLIGHT rule at the given index cannot be evaluated.
+    UnsupportedRule(usize),
+    /// A date-time lies outside the range of representable dates after applying an offset.
+    OutOfRange,
+}
+
+impl std::fmt::Display for TzEvalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoRules => write!(f, "the time zone has no STANDARD or DAYLIGHT rules"),
+            Self::UnsupportedRule(index) => {
+                write!(
+                    f,
+                    "the recurrence rule of time zone rule {index} is not supported"
+                )
+            }
+            Self::OutOfRange => write!(f, "the date-time is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for TzEvalError {}
+
+impl TimeZone {
+    /// Returns the transitions of this time zone whose local times fall in or before the year
+    /// `until`, in chronological order.
+    pub fn transitions(&self, until: Year) -> Result<Vec<Transition>, TzEvalError> {
+        if self.rules().is_empty() {
+            return Err(TzEvalError::NoRules);
+        }
+
+        // rules whose DTSTART cannot be represented have no onsets, so they are skipped and the
+        // indices of the remaining rules are kept to report errors and kinds
+        let (indices, observances): (Vec<usize>, Vec<Observance>) = self
+            .rules()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, rule)| Some((index, observance(rule)?)))
+            .unzip();
+
+        let transitions =
+            vtimezone_to_transitions(&observances, until).map_err(|error| match error {
+                TransitionError::UnsupportedRule(index) => {
+                    TzEvalError::UnsupportedRule(indices[index])
+                }
+                TransitionError::OutOfRange => TzEvalError::OutOfRange,
+            })?;
+
+        Ok(transitions
+            .into_iter()
+            .map(|transition| Transition {
+                utc: transition.utc,
+                local: transition.local,
+                offset_from: transition.offset_from,
+                offset_to: transition.offset_to,
+                kind: *self.rules()[indices[transition.observance]].kind(),
+            })
+            .collect())
+    }
+
+    /// Returns the UTC offset of this time zone at the instant `utc`. Before the first
+    /// transition, this is the offset from which the first transition changes.
+    pub fn offset_at_utc(&self, utc: DateTime<Utc>) -> Result<UtcOffset, TzEvalError> {
+        let transitions = self.transitions_near(utc.date.year())?;
+        let offset = match transitions.iter().rev().find(|t| t.utc <= utc) {
+            Some(transition) => transition.offset_to,
+            None => transitions[0].offset_from,
+        };
+
+        Ok(offset)
+    }
+
+    /// Returns the UTC offsets of the local date-time `local` in this time zone, which may be
+    /// ambiguous or nonexistent around a transition.
+    pub fn offset_at(&self, local: DateTime<Local>) -> Result<LocalOffset, TzEvalError> {
+        let transitions = self.transitions_near(local.date.year())?;
+
+        let mut offset = transitions[0].offset_from;
+        for transition in &transitions {
+            let (from, to) = (transition.offset_from, transition.offset_to);
+            let forward = from.as_seconds() < to.as_seconds();
+            let (low, high) = if forward { (from, to) } else { (to, from) };
+            let start = to_local(transition.utc, low)?;
+            let end = to_local(transition.utc, high)?;
+
+            if local < start {
+                break;
+            } else if local < end {
+                return Ok(if forward {
+                    LocalOffset::Gap {
+                        before: from,
+                        after: to,
+                    }
+                } else {
+                    LocalOffset::Ambiguous {
+                        earlier: from,
+                        later: to,
+                    }
+                });
+            }
+
+            offset = to;
+        }
+
+        Ok(LocalOffset::Single(offset))
+    }
+
+    /// Returns the transitions up to the year after `year`, which include at least the first
+    /// transition of this time zone.
+    fn transitions_near(&self, year: Year) -> Result<Vec<Transition>, TzEvalError> {
+        let first = self
+            .rules()
+            .iter()
+            .map(|rule| match rule.dtstart().value {
+                DateTimeOrDate::DateTime(dt) => dt.date.year(),
+                DateTimeOrDate::Date(date) => date.year(),
+            })
+            .min()
+            .ok_or(TzEvalError::NoRules)?;
+
+        self.transitions(next_year(year).max(first))
+    }
+
+    /// Converts the local date-time `local` to UTC, resolving ambiguous and nonexistent times as
+    /// described by [`LocalOffset::resolve`].
+    pub fn local_to_utc(&self, local: DateTime<Local>) -> Result<DateTime<Utc>, TzEvalError> {
+        to_utc(local, self.offset_at(local)?.resolve())
+    }
+}
+
+/// Returns `rule` as an [`Observance`], or `None` if its DTSTART cannot be represented as a
+/// local date-time.
+fn observance(rule: &TzRule) -> Option<Observance<'_>> {
+    let offset_from = rule.tz_offset_from().value;
+    let local = |value: DateTimeOrDate| match value {
+        DateTimeOrDate::DateTime(dt) => match dt.marker {
+            TimeFormat::Local => Some(with_marker(dt, Local)),
+            TimeFormat::Utc => offset_from.utc_to_local(with_marker(dt, Utc)),
+        },
+        DateTimeOrDate::Date(date) => Some(midnight(date)),
+    };
+
+    let mut rdates = Vec::new();
+    for rdate in rule.rdate().into_iter().flatten() {
+        let values: Vec<DateTimeOrDate> = match &rdate.value {
+            RDateSeq::DateTime(values) => values
+                .iter()
+                .copied()
+                .map(DateTimeOrDate::DateTime)
+                .collect(),
+            RDateSeq::Date(values) => values.iter().copied().map(DateTimeOrDate::Date).collect(),
+            RDateSeq::Period(values) => values
+                .iter()
+                .map(|period| match *period {
+                    Period::Explicit { start, .. } | Period::Start { start, .. } => {
+                        DateTimeOrDate::DateTime(start)
+                    }
+                })
+                .collect(),
+        };
+
+        rdates.extend(values.into_iter().filter_map(local));
+    }
+
+    Some(Observance {
+        start: local(rule.dtstart().value)?,
+        offset_from,
+        offset_to: rule.tz_offset_to().value,
+        rrules: rule
+            .rrule()
+            .into_iter()
+            .flatten()
+            .map(|p| &p.value)
+            .collect(),
+        rdates,
+        exdates: Vec::new(),
+    })
+}
+
+fn next_year(year: Year) -> Year {
+    Year::new(year.get() + 1).unwrap_or(Year::MAX)
+}
+
+fn midnight(date: Date) -> DateTime<Local> {
+    let time = Time::new(Hour::H00, Minute::M00, Second::S00, None).expect("midnight is valid");
+    DateTime {
+        date,
+        time,
+        marker: Local,
+    }
+}
+
+/// Converts a local date-time at the given UTC offset to UTC.
+fn to_utc(local: DateTime<Local>, offset: UtcOffset) -> Result<DateTime<Utc>, TzEvalError> {
+    offset.local_to_utc(local).ok_or(TzEvalError::OutOfRange)
+}
+
+/// Converts a UTC date-time to the local date-time at the given UTC offset.
+fn to_local(utc: DateTime<Utc>, offset: UtcOffset) -> Result<DateTime<Local>, TzEvalError> {
+    offset.utc_to_local(utc).ok_or(TzEvalError::OutOfRange)
+}
+
+fn with_marker<M, N>(dt: DateTime<M>, marker: N) -> DateTime<N> {
+    DateTime {
+        date: dt.date,
+        time: dt.time,
+        marker,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{
+        component::{Calendar, CalendarComponent},
+        primitive::{Day, Month, Sign},
+        rrule::FreqByRules,
+    };
+
+    const NEW_YORK: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+PRODID:-//test//EN\r
+BEGIN:VTIMEZONE\r
+TZID:America/New_York\r
+BEGIN:STANDARD\r
+DTSTART:19671029T020000\r
+RRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU;UNTIL=20061029T060000Z\r
+TZOFFSETFROM:-0400\r
+TZOFFSETTO:-0500\r
+TZNAME:EST\r
+END:STANDARD\r
+BEGIN:STANDARD\r
+DTSTART:20071104T020000\r
+RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU\r
+TZOFFSETFROM:-0400\r
+TZOFFSETTO:-0500\r
+TZNAME:EST\r
+END:STANDARD\r
+BEGIN:DAYLIGHT\r
+DTSTART:19870405T020000\r
+RRULE:FREQ=YEARLY;BYMONTH=4;BYDAY=1SU;UNTIL=20060402T070000Z\r
+TZOFFSETFROM:-0500\r
+TZOFFSETTO:-0400\r
+TZNAME:EDT\r
+END:DAYLIGHT\r
+BEGIN:DAYLIGHT\r
+DTSTART:20070311T020000\r
+RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU\r
+TZOFFSETFROM:-0500\r
+TZOFFSETTO:-0400\r
+TZNAME:EDT\r
+END:DAYLIGHT\r
+END:VTIMEZONE\r
+END:VCALENDAR\r
+";
+
+    fn new_york() -> TimeZone {
+        let calendar = Calendar::parse(NEW_YORK).unwrap().remove(0);
+        match calendar.components().as_slice() {
+            [CalendarComponent::TimeZone(tz)] => tz.clone(),
+            other => panic!("expected a single VTIMEZONE, got {other:?}"),
+        }
+    }
+
+    fn dt<M>(s: &str, marker: M) -> DateTime<M> {
+        let n = |range: std::ops::Range<usize>| s[range].parse::<u16>().unwrap();
+        let date = Date::new(
+            Year::new(n(0..4)).unwrap(),
+            Month::new(n(4..6) as u8).unwrap(),
+            Day::new(n(6..8) as u8).unwrap(),
+        )
+        .unwrap();
+        let time = Time::new(
+            Hour::new(n(9..11) as u8).unwrap(),
+            Minute::new(n(11..13) as u8).unwrap(),
+            Second::new(n(13..15) as u8).unwrap(),
+            None,
+        )
+        .unwrap();
+
+        DateTime { date, time, marker }
+    }
+
+    fn offset(hours: i8) -> UtcOffset {
+        UtcOffset {
+            sign: if hours < 0 { Sign::Neg } else { Sign::Pos },
+            hour: Hour::new(hours.unsigned_abs()).unwrap(),
+            minute: Minute::M00,
+            second: Default::default(),
+        }
+    }
+
+    #[test]
+    fn transitions_follow_rules() {
+        let transitions = new_york().transitions(Year::new(2024).unwrap()).unwrap();
+        let in_2024: Vec<_> = transitions
+            .iter()
+            .filter(|t| t.local.date.year().get() == 2024)
+            .map(|t| (t.local, t.kind))
+            .collect();
+
+        assert_eq!(
+            in_2024,
+            [
+                (dt("20240310T020000", Local), TzRuleKind::Daylight),
+                (dt("20241103T020000", Local), TzRuleKind::Standard),
+            ]
+        );
+
+        // the old rules end in 2006
+        let in_2006 = transitions
+            .iter()
+            .filter(|t| t.local.date.year().get() == 2006)
+            .count();
+        assert_eq!(in_2006, 2);
+        assert!(
+            transitions
+                .iter()
+                .all(|t| t.local.date.year().get() <= 2024)
+        );
+    }
+
+    #[test]
+    fn offset_at_utc() {
+        let tz = new_york();
+        let at = |s| tz.offset_at_utc(dt(s, Utc)).unwrap();
+
+        assert_eq!(at("20240115T120000"), offset(-5));
+        assert_eq!(at("20240310T065959"), offset(-5));
+        assert_eq!(at("20240310T070000"), offset(-4));
+        assert_eq!(at("20240701T000000"), offset(-4));
+        assert_eq!(at("20241103T060000"), offset(-5));
+        assert_eq!(at("20050501T000000"), offset(-4));
+        assert_eq!(at("19500101T000000"), offset(-4));
+    }
+
+    #[test]
+    fn offset_at_local() {
+        let tz = new_york();
+        let at = |s| tz.offset_at(dt(s, Local)).unwrap();
+
+        assert_eq!(at("20240101T000000"), LocalOffset::Single(offset(-5)));
+        assert_eq!(at("20240310T015959"), LocalOffset::Single(offset(-5)));
+        assert_eq!(
+            at("20240310T023000"),
+            LocalOffset::Gap {
+                before: offset(-5),
+                after: offset(-4),
+            }
+        );
+        assert_eq!(at("20240310T030000"), LocalOffset::Single(offset(-4)));
+        assert_eq!(
+            at("20241103T013000"),
+            LocalOffset::Ambiguous {
+                earlier: offset(-4),
+                later: offset(-5),
+            }
+        );
+        assert_eq!(at("20241103T020000"), LocalOffset::Single(offset(-5)));
+
+        let utc = tz.local_to_utc(dt("20241103T013000", Local)).unwrap();
+        assert_eq!(utc, dt("20241103T053000", Utc));
+        let utc = tz.local_to_utc(dt("20240310T023000", Local)).unwrap();
+        assert_eq!(utc, dt("20240310T073000", Utc));
+    }
+
+    #[test]
+    fn errors() {
+        let mut tz = new_york();
+        let mut rule = tz.rules()[3].clone();
+        let mut rrule = rule.rrule().unwrap()[0].clone();
+        rrule.value.freq = FreqByRules::Weekly;
+        rule.set_rrule(vec![rrule]);
+        tz.rules_mut()[3] = rule;
+        assert_eq!(
+            tz.offset_at_utc(dt("20240101T000000", Utc)),
+            Err(TzEvalError::UnsupportedRule(3))
+        );
+
+        tz.rules_mut().clear();
+        assert_eq!(
+            tz.transitions(Year::new(2024).unwrap()),
+            Err(TzEvalError::NoRules)
+        );
+    }
+}
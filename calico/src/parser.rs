@@ -22,6 +22,7 @@ pub mod parameter;
 pub mod primitive;
 pub mod property;
 pub mod rrule;
+pub mod stream;
 
 /// An input stream compatible with the parsers in [`calico::parser`](crate::parser).
 pub trait InputStream
@@ -31,7 +31,7 @@ use crate::{
         InputStream,
         config::{Config, DefaultConfig, LineEnding},
         error::{CalendarParseError, ComponentKind},
-        property::{ParsedProp, KnownProp, PropValue, UnknownProp, PropName, property},
+        property::{ParsedProp, KnownProp, PropValue, UnknownProp, PropName, optional_property_parser_from_config},
     },
 };
 
@@ -42,10 +42,11 @@ use crate::{
 /// Parses properties in a loop, breaking when BEGIN or END is encountered.
 /// The body of the match is provided by the caller.
 macro_rules! parse_props {
-    ($input:ident, $le:expr, $parsed:ident, $body:block) => {
+    ($input:ident, $config:ident, $parsed:ident, $body:block) => {
+        let le = $config.line_ending();
         loop {
             // Skip blank lines
-            let _: usize = repeat(0.., line_terminator($le)).parse_next($input)?;
+            let _: usize = repeat(0.., line_terminator(le)).parse_next($input)?;
 
             let checkpoint = $input.checkpoint();
             if alt((begin(empty::<I, E>), end(empty::<I, E>))).parse_next($input).is_ok() {
@@ -54,16 +55,27 @@ macro_rules! parse_props {
             }
             $input.reset(&checkpoint);
 
-            let $parsed: ParsedProp<I::Slice> = terminated(property, line_terminator($le)).parse_next($input)?;
+            let Some($parsed): Option<ParsedProp<I::Slice>> = terminated(
+                optional_property_parser_from_config($config),
+                line_terminator(le),
+            )
+            .parse_next($input)?
+            else {
+                continue;
+            };
             let result: Result<(), CalendarParseError<I::Slice>> = (|| $body)();
             result.map_err(|e| E::from_external_error($input, e))?;
         }
     };
 }
 
-/// Sets a once-only property, silently accepting duplicates (last value wins).
+/// Sets a once-only property. Duplicates are passed to [`Config::handle_duplicate_property`],
+/// and the last value wins if it accepts them.
 macro_rules! once {
-    ($opt:expr, $prop:expr, $component:expr, $val:expr) => {
+    ($config:ident, $opt:expr, $prop:expr, $component:expr, $val:expr) => {
+        if $opt.is_some() {
+            $config.handle_duplicate_property($prop, $component)?;
+        }
         $opt = Some($val);
     };
 }
@@ -111,11 +123,10 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
-    let le = config.line_ending();
-    calendar_impl(input, le)
+    calendar_impl(input, config)
 }
 
-fn calendar_impl<I, E>(input: &mut I, le: LineEnding) -> Result<Calendar, E>
+fn calendar_impl<I, E>(input: &mut I, config: &mut impl Config) -> Result<Calendar, E>
 where
     I: InputStream,
     I::Token: AsChar + Clone,
@@ -123,6 +134,7 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
+    let le = config.line_ending();
     // Strip UTF-8 BOM if present
     let _ = opt::<_, _, E, _>('\u{FEFF}').parse_next(input);
 
@@ -168,46 +180,53 @@ where
         let checkpoint = input.checkpoint();
         if begin(empty::<I, E>).parse_next(input).is_ok() {
             input.reset(&checkpoint);
-            components.push(calendar_component_lt(input, le)?);
+            components.push(calendar_component_lt(input, config)?);
             continue;
         }
         input.reset(&checkpoint);
 
         // Otherwise parse a property
-        let parsed: ParsedProp<I::Slice> = terminated(property, line_terminator(le)).parse_next(input)?;
+        let Some(parsed): Option<ParsedProp<I::Slice>> = terminated(
+            optional_property_parser_from_config(config),
+            line_terminator(le),
+        )
+        .parse_next(input)?
+        else {
+            continue;
+        };
         let result: Result<(), CalendarParseError<I::Slice>> = (|| {
             match parsed {
                 ParsedProp::Known(KnownProp { name: prop_name, value }) => {
                     match (prop_name, value) {
                         (StaticProp::ProdId, PropValue::Text(p)) => {
-                            once!(prod_id, StaticProp::ProdId, ComponentKind::Calendar, p);
+                            once!(config, prod_id, StaticProp::ProdId, ComponentKind::Calendar, p);
                         }
                         (StaticProp::Version, PropValue::Version(p)) => {
-                            once!(version, StaticProp::Version, ComponentKind::Calendar, p);
+                            once!(config, version, StaticProp::Version, ComponentKind::Calendar, p);
                         }
                         (StaticProp::CalScale, PropValue::Gregorian(p)) => {
-                            once!(cal_scale, StaticProp::CalScale, ComponentKind::Calendar, p);
+                            once!(config, cal_scale, StaticProp::CalScale, ComponentKind::Calendar, p);
                         }
                         (StaticProp::Method, PropValue::Method(p)) => {
-                            once!(method, StaticProp::Method, ComponentKind::Calendar, p);
+                            once!(config, method, StaticProp::Method, ComponentKind::Calendar, p);
                         }
                         (StaticProp::Uid, PropValue::Uid(p)) => {
-                            once!(uid, StaticProp::Uid, ComponentKind::Calendar, p);
+                            once!(config, uid, StaticProp::Uid, ComponentKind::Calendar, p);
                         }
                         (StaticProp::LastModified, PropValue::DateTimeUtc(p)) => {
-                            once!(last_modified, StaticProp::LastModified, ComponentKind::Calendar, p);
+                            once!(config, last_modified, StaticProp::LastModified, ComponentKind::Calendar, p);
                         }
                         (StaticProp::Url, PropValue::Uri(p)) => {
-                            once!(url, StaticProp::Url, ComponentKind::Calendar, p);
+                            once!(config, url, StaticProp::Url, ComponentKind::Calendar, p);
                         }
                         (StaticProp::RefreshInterval, PropValue::Duration(p)) => {
-                            once!(refresh_interval, StaticProp::RefreshInterval, ComponentKind::Calendar, p);
+                            once!(config, refresh_interval, StaticProp::RefreshInterval, ComponentKind::Calendar, p);
                         }
                         (StaticProp::Source, PropValue::Uri(p)) => {
-                            once!(source, StaticProp::Source, ComponentKind::Calendar, p);
+                            once!(config, source, StaticProp::Source, ComponentKind::Calendar, p);
                         }
                         (StaticProp::Color, PropValue::Color(p)) => {
-                            once!(color, StaticProp::Color, ComponentKind::Calendar, p);
+                            once!(config, color, StaticProp::Color, ComponentKind::Calendar, p);
                         }
                         (StaticProp::Name, PropValue::Text(p)) => {
                             name.push(p);
@@ -237,15 +256,18 @@ where
     terminated(end(Caseless("VCALENDAR")), alt((line_terminator(le), eof))).parse_next(input)?;
 
     // Check mandatory fields
-    let version = version.ok_or_else(|| {
-        E::from_external_error(
-            input,
-            CalendarParseError::MissingProp {
-                prop: PropName::Known(StaticProp::Version),
-                component: ComponentKind::Calendar,
-            },
-        )
-    })?;
+    let version = match version {
+        Some(version) => version,
+        None => {
+            config
+                .handle_missing_version()
+                .map_err(|e| E::from_external_error(input, e))?;
+            Prop {
+                value: Token::Known(Version::V2_0),
+                params: Params::new(),
+            }
+        }
+    };
 
     let prod_id = prod_id.ok_or_else(|| {
         E::from_external_error(
@@ -309,11 +331,10 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
-    let le = config.line_ending();
-    icalendar_stream_impl(input, le)
+    icalendar_stream_impl(input, config)
 }
 
-fn icalendar_stream_impl<I, E>(input: &mut I, le: LineEnding) -> Result<Vec<Calendar>, E>
+fn icalendar_stream_impl<I, E>(input: &mut I, config: &mut impl Config) -> Result<Vec<Calendar>, E>
 where
     I: InputStream,
     I::Token: AsChar + Clone,
@@ -321,6 +342,7 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
+    let le = config.line_ending();
     let mut calendars = Vec::new();
 
     loop {
@@ -332,7 +354,7 @@ where
             break;
         }
 
-        calendars.push(calendar_impl(input, le)?);
+        calendars.push(calendar_impl(input, config)?);
     }
 
     Ok(calendars)
@@ -351,10 +373,32 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
-    calendar_component_lt(input, LineEnding::Crlf)
+    let mut config = DefaultConfig::default();
+    calendar_component_lt(input, &mut config)
+}
+
+/// Parses a [`CalendarComponent`] using the provided [`Config`].
+///
+/// As with [`calendar_with_config`], the caller is responsible for setting
+/// [`Config::line_ending`] before calling this function.
+pub fn calendar_component_with_config<I, E>(
+    input: &mut I,
+    config: &mut impl Config,
+) -> Result<CalendarComponent, E>
+where
+    I: InputStream,
+    I::Token: AsChar + Clone,
+    I::Slice: AsBStr + Clone + PartialEq + Eq + SliceLen + Stream + Hash + AsRef<[u8]>,
+    <<I as Stream>::Slice as Stream>::Token: AsChar,
+    E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
+{
+    calendar_component_lt(input, config)
 }
 
-fn calendar_component_lt<I, E>(input: &mut I, le: LineEnding) -> Result<CalendarComponent, E>
+fn calendar_component_lt<I, E>(
+    input: &mut I,
+    config: &mut impl Config,
+) -> Result<CalendarComponent, E>
 where
     I: InputStream,
     I::Token: AsChar + Clone,
@@ -369,7 +413,7 @@ where
             let matched: Result<I::Slice, E> = begin(Caseless($name)).parse_next(input);
             input.reset(&checkpoint);
             if matched.is_ok() {
-                return $parser(input, le).map($variant);
+                return $parser(input, config).map($variant);
             }
         }};
     }
@@ -381,7 +425,7 @@ where
     try_component!("VTIMEZONE", timezone, CalendarComponent::TimeZone);
 
     // Anything else (including VALARM at calendar level) → other
-    other_with_name(input, le).map(CalendarComponent::Other)
+    other_with_name(input, config).map(CalendarComponent::Other)
 }
 
 // ============================================================================
@@ -389,7 +433,7 @@ where
 // ============================================================================
 
 /// Parses a [`Event`].
-fn event<I, E>(input: &mut I, le: LineEnding) -> Result<Event, E>
+fn event<I, E>(input: &mut I, config: &mut impl Config) -> Result<Event, E>
 where
     I: InputStream,
     I::Token: AsChar + Clone,
@@ -397,6 +441,7 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
+    let le = config.line_ending();
     terminated(begin(Caseless("VEVENT")), line_terminator(le)).parse_next(input)?;
 
     // Once-only properties
@@ -467,28 +512,28 @@ where
             let cp = input.checkpoint();
             if terminated(begin(Caseless("VALARM")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                 input.reset(&cp);
-                alarms.push(alarm(input, le)?);
+                alarms.push(alarm(input, config)?);
             } else {
                 input.reset(&cp);
                 let cp = input.checkpoint();
                 if terminated(begin(Caseless("PARTICIPANT")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                     input.reset(&cp);
-                    participants.push(participant(input, le)?);
+                    participants.push(participant(input, config)?);
                 } else {
                     input.reset(&cp);
                     let cp = input.checkpoint();
                     if terminated(begin(Caseless("VLOCATION")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                         input.reset(&cp);
-                        locations.push(location(input, le)?);
+                        locations.push(location(input, config)?);
                     } else {
                         input.reset(&cp);
                         let cp = input.checkpoint();
                         if terminated(begin(Caseless("VRESOURCE")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                             input.reset(&cp);
-                            resource_components.push(resource(input, le)?);
+                            resource_components.push(resource(input, config)?);
                         } else {
                             input.reset(&cp);
-                            let _ = other_with_name(input, le)?;
+                            let _ = other_with_name(input, config)?;
                         }
                     }
                 }
@@ -498,46 +543,53 @@ where
         input.reset(&checkpoint);
 
         // Otherwise parse a property
-        let parsed: ParsedProp<I::Slice> = terminated(property, line_terminator(le)).parse_next(input)?;
+        let Some(parsed): Option<ParsedProp<I::Slice>> = terminated(
+            optional_property_parser_from_config(config),
+            line_terminator(le),
+        )
+        .parse_next(input)?
+        else {
+            continue;
+        };
         let result: Result<(), CalendarParseError<I::Slice>> = (|| {
             match parsed {
                 ParsedProp::Known(KnownProp { name: prop_name, value }) => {
                     match (prop_name, value) {
                         (StaticProp::DtStamp, PropValue::DateTimeUtc(p)) => {
-                            once!(dtstamp, StaticProp::DtStamp, ComponentKind::Event, p);
+                            once!(config, dtstamp, StaticProp::DtStamp, ComponentKind::Event, p);
                         }
                         (StaticProp::Uid, PropValue::Uid(p)) => {
-                            once!(uid, StaticProp::Uid, ComponentKind::Event, p);
+                            once!(config, uid, StaticProp::Uid, ComponentKind::Event, p);
                         }
                         (StaticProp::DtStart, PropValue::DateTimeOrDate(p)) => {
-                            once!(dtstart, StaticProp::DtStart, ComponentKind::Event, p);
+                            once!(config, dtstart, StaticProp::DtStart, ComponentKind::Event, p);
                         }
                         (StaticProp::Class, PropValue::ClassValue(p)) => {
-                            once!(class, StaticProp::Class, ComponentKind::Event, p);
+                            once!(config, class, StaticProp::Class, ComponentKind::Event, p);
                         }
                         (StaticProp::Created, PropValue::DateTimeUtc(p)) => {
-                            once!(created, StaticProp::Created, ComponentKind::Event, p);
+                            once!(config, created, StaticProp::Created, ComponentKind::Event, p);
                         }
                         (StaticProp::Description, PropValue::Text(p)) => {
-                            once!(description, StaticProp::Description, ComponentKind::Event, p);
+                            once!(config, description, StaticProp::Description, ComponentKind::Event, p);
                         }
                         (StaticProp::Geo, PropValue::Geo(p)) => {
-                            once!(geo, StaticProp::Geo, ComponentKind::Event, p);
+                            once!(config, geo, StaticProp::Geo, ComponentKind::Event, p);
                         }
                         (StaticProp::LastModified, PropValue::DateTimeUtc(p)) => {
-                            once!(last_modified, StaticProp::LastModified, ComponentKind::Event, p);
+                            once!(config, last_modified, StaticProp::LastModified, ComponentKind::Event, p);
                         }
                         (StaticProp::Location, PropValue::Text(p)) => {
-                            once!(loc_prop, StaticProp::Location, ComponentKind::Event, p);
+                            once!(config, loc_prop, StaticProp::Location, ComponentKind::Event, p);
                         }
                         (StaticProp::Organizer, PropValue::Uri(p)) => {
-                            once!(organizer, StaticProp::Organizer, ComponentKind::Event, p);
+                            once!(config, organizer, StaticProp::Organizer, ComponentKind::Event, p);
                         }
                         (StaticProp::Priority, PropValue::Priority(p)) => {
-                            once!(priority, StaticProp::Priority, ComponentKind::Event, p);
+                            once!(config, priority, StaticProp::Priority, ComponentKind::Event, p);
                         }
                         (StaticProp::Sequence, PropValue::Integer(p)) => {
-                            once!(sequence, StaticProp::Sequence, ComponentKind::Event, p);
+                            once!(config, sequence, StaticProp::Sequence, ComponentKind::Event, p);
                         }
                         (StaticProp::Status, PropValue::Status(p)) => {
                             if status.is_some() {
@@ -553,31 +605,31 @@ where
                             status = Some(p);
                         }
                         (StaticProp::Summary, PropValue::Text(p)) => {
-                            once!(summary, StaticProp::Summary, ComponentKind::Event, p);
+                            once!(config, summary, StaticProp::Summary, ComponentKind::Event, p);
                         }
                         (StaticProp::Transp, PropValue::TimeTransparency(p)) => {
-                            once!(transp, StaticProp::Transp, ComponentKind::Event, p);
+                            once!(config, transp, StaticProp::Transp, ComponentKind::Event, p);
                         }
                         (StaticProp::Url, PropValue::Uri(p)) => {
-                            once!(url, StaticProp::Url, ComponentKind::Event, p);
+                            once!(config, url, StaticProp::Url, ComponentKind::Event, p);
                         }
                         (StaticProp::RecurId, PropValue::DateTimeOrDate(p)) => {
-                            once!(recurrence_id, StaticProp::RecurId, ComponentKind::Event, p);
+                            once!(config, recurrence_id, StaticProp::RecurId, ComponentKind::Event, p);
                         }
                         (StaticProp::DtEnd, PropValue::DateTimeOrDate(p)) => {
                             if duration.is_some() {
                                 return Err(CalendarParseError::EventTerminationCollision);
                             }
-                            once!(dtend, StaticProp::DtEnd, ComponentKind::Event, p);
+                            once!(config, dtend, StaticProp::DtEnd, ComponentKind::Event, p);
                         }
                         (StaticProp::Duration, PropValue::Duration(p)) => {
                             if dtend.is_some() {
                                 return Err(CalendarParseError::EventTerminationCollision);
                             }
-                            once!(duration, StaticProp::Duration, ComponentKind::Event, p);
+                            once!(config, duration, StaticProp::Duration, ComponentKind::Event, p);
                         }
                         (StaticProp::Color, PropValue::Color(p)) => {
-                            once!(color, StaticProp::Color, ComponentKind::Event, p);
+                            once!(config, color, StaticProp::Color, ComponentKind::Event, p);
                         }
                         // Multi-valued
                         (StaticProp::Attach, PropValue::Attachment(p)) => {
@@ -699,7 +751,7 @@ where
 // ============================================================================
 
 /// Parses a [`Todo`].
-fn todo_comp<I, E>(input: &mut I, le: LineEnding) -> Result<Todo, E>
+fn todo_comp<I, E>(input: &mut I, config: &mut impl Config) -> Result<Todo, E>
 where
     I: InputStream,
     I::Token: AsChar + Clone,
@@ -707,6 +759,7 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
+    let le = config.line_ending();
     terminated(begin(Caseless("VTODO")), line_terminator(le)).parse_next(input)?;
 
     // Once-only properties
@@ -775,28 +828,28 @@ where
             let cp = input.checkpoint();
             if terminated(begin(Caseless("VALARM")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                 input.reset(&cp);
-                alarms.push(alarm(input, le)?);
+                alarms.push(alarm(input, config)?);
             } else {
                 input.reset(&cp);
                 let cp = input.checkpoint();
                 if terminated(begin(Caseless("PARTICIPANT")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                     input.reset(&cp);
-                    participants.push(participant(input, le)?);
+                    participants.push(participant(input, config)?);
                 } else {
                     input.reset(&cp);
                     let cp = input.checkpoint();
                     if terminated(begin(Caseless("VLOCATION")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                         input.reset(&cp);
-                        locations.push(location(input, le)?);
+                        locations.push(location(input, config)?);
                     } else {
                         input.reset(&cp);
                         let cp = input.checkpoint();
                         if terminated(begin(Caseless("VRESOURCE")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                             input.reset(&cp);
-                            resource_components.push(resource(input, le)?);
+                            resource_components.push(resource(input, config)?);
                         } else {
                             input.reset(&cp);
-                            let _ = other_with_name(input, le)?;
+                            let _ = other_with_name(input, config)?;
                         }
                     }
                 }
@@ -805,55 +858,62 @@ where
         }
         input.reset(&checkpoint);
 
-        let parsed: ParsedProp<I::Slice> = terminated(property, line_terminator(le)).parse_next(input)?;
+        let Some(parsed): Option<ParsedProp<I::Slice>> = terminated(
+            optional_property_parser_from_config(config),
+            line_terminator(le),
+        )
+        .parse_next(input)?
+        else {
+            continue;
+        };
         let result: Result<(), CalendarParseError<I::Slice>> = (|| {
             match parsed {
                 ParsedProp::Known(KnownProp { name: prop_name, value }) => {
                     match (prop_name, value) {
                         (StaticProp::DtStamp, PropValue::DateTimeUtc(p)) => {
-                            once!(dtstamp, StaticProp::DtStamp, ComponentKind::Todo, p);
+                            once!(config, dtstamp, StaticProp::DtStamp, ComponentKind::Todo, p);
                         }
                         (StaticProp::Uid, PropValue::Uid(p)) => {
-                            once!(uid, StaticProp::Uid, ComponentKind::Todo, p);
+                            once!(config, uid, StaticProp::Uid, ComponentKind::Todo, p);
                         }
                         (StaticProp::DtStart, PropValue::DateTimeOrDate(p)) => {
-                            once!(dtstart, StaticProp::DtStart, ComponentKind::Todo, p);
+                            once!(config, dtstart, StaticProp::DtStart, ComponentKind::Todo, p);
                         }
                         (StaticProp::Class, PropValue::ClassValue(p)) => {
-                            once!(class, StaticProp::Class, ComponentKind::Todo, p);
+                            once!(config, class, StaticProp::Class, ComponentKind::Todo, p);
                         }
                         (StaticProp::DtCompleted, PropValue::DateTimeUtc(p)) => {
-                            once!(completed, StaticProp::DtCompleted, ComponentKind::Todo, p);
+                            once!(config, completed, StaticProp::DtCompleted, ComponentKind::Todo, p);
                         }
                         (StaticProp::Created, PropValue::DateTimeUtc(p)) => {
-                            once!(created, StaticProp::Created, ComponentKind::Todo, p);
+                            once!(config, created, StaticProp::Created, ComponentKind::Todo, p);
                         }
                         (StaticProp::Description, PropValue::Text(p)) => {
-                            once!(description, StaticProp::Description, ComponentKind::Todo, p);
+                            once!(config, description, StaticProp::Description, ComponentKind::Todo, p);
                         }
                         (StaticProp::Geo, PropValue::Geo(p)) => {
-                            once!(geo, StaticProp::Geo, ComponentKind::Todo, p);
+                            once!(config, geo, StaticProp::Geo, ComponentKind::Todo, p);
                         }
                         (StaticProp::LastModified, PropValue::DateTimeUtc(p)) => {
-                            once!(last_modified, StaticProp::LastModified, ComponentKind::Todo, p);
+                            once!(config, last_modified, StaticProp::LastModified, ComponentKind::Todo, p);
                         }
                         (StaticProp::Location, PropValue::Text(p)) => {
-                            once!(loc_prop, StaticProp::Location, ComponentKind::Todo, p);
+                            once!(config, loc_prop, StaticProp::Location, ComponentKind::Todo, p);
                         }
                         (StaticProp::Organizer, PropValue::Uri(p)) => {
-                            once!(organizer, StaticProp::Organizer, ComponentKind::Todo, p);
+                            once!(config, organizer, StaticProp::Organizer, ComponentKind::Todo, p);
                         }
                         (StaticProp::PercentComplete, PropValue::CompletionPercentage(p)) => {
-                            once!(percent_complete, StaticProp::PercentComplete, ComponentKind::Todo, p);
+                            once!(config, percent_complete, StaticProp::PercentComplete, ComponentKind::Todo, p);
                         }
                         (StaticProp::Priority, PropValue::Priority(p)) => {
-                            once!(priority, StaticProp::Priority, ComponentKind::Todo, p);
+                            once!(config, priority, StaticProp::Priority, ComponentKind::Todo, p);
                         }
                         (StaticProp::RecurId, PropValue::DateTimeOrDate(p)) => {
-                            once!(recurrence_id, StaticProp::RecurId, ComponentKind::Todo, p);
+                            once!(config, recurrence_id, StaticProp::RecurId, ComponentKind::Todo, p);
                         }
                         (StaticProp::Sequence, PropValue::Integer(p)) => {
-                            once!(sequence, StaticProp::Sequence, ComponentKind::Todo, p);
+                            once!(config, sequence, StaticProp::Sequence, ComponentKind::Todo, p);
                         }
                         (StaticProp::Status, PropValue::Status(p)) => {
                             if status.is_some() {
@@ -869,25 +929,25 @@ where
                             status = Some(p);
                         }
                         (StaticProp::Summary, PropValue::Text(p)) => {
-                            once!(summary, StaticProp::Summary, ComponentKind::Todo, p);
+                            once!(config, summary, StaticProp::Summary, ComponentKind::Todo, p);
                         }
                         (StaticProp::Url, PropValue::Uri(p)) => {
-                            once!(url, StaticProp::Url, ComponentKind::Todo, p);
+                            once!(config, url, StaticProp::Url, ComponentKind::Todo, p);
                         }
                         (StaticProp::DtDue, PropValue::DateTimeOrDate(p)) => {
                             if duration.is_some() {
                                 return Err(CalendarParseError::TodoTerminationCollision);
                             }
-                            once!(due, StaticProp::DtDue, ComponentKind::Todo, p);
+                            once!(config, due, StaticProp::DtDue, ComponentKind::Todo, p);
                         }
                         (StaticProp::Duration, PropValue::Duration(p)) => {
                             if due.is_some() {
                                 return Err(CalendarParseError::TodoTerminationCollision);
                             }
-                            once!(duration, StaticProp::Duration, ComponentKind::Todo, p);
+                            once!(config, duration, StaticProp::Duration, ComponentKind::Todo, p);
                         }
                         (StaticProp::Color, PropValue::Color(p)) => {
-                            once!(color, StaticProp::Color, ComponentKind::Todo, p);
+                            once!(config, color, StaticProp::Color, ComponentKind::Todo, p);
                         }
                         // Multi-valued
                         (StaticProp::Attach, PropValue::Attachment(p)) => { attach.push(p); }
@@ -982,7 +1042,7 @@ where
 // ============================================================================
 
 /// Parses a [`Journal`].
-fn journal<I, E>(input: &mut I, le: LineEnding) -> Result<Journal, E>
+fn journal<I, E>(input: &mut I, config: &mut impl Config) -> Result<Journal, E>
 where
     I: InputStream,
     I::Token: AsChar + Clone,
@@ -990,6 +1050,7 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
+    let le = config.line_ending();
     terminated(begin(Caseless("VJOURNAL")), line_terminator(le)).parse_next(input)?;
 
     let mut dtstamp: Option<Prop<DateTime<Utc>, Params>> = None;
@@ -1042,22 +1103,22 @@ where
             let cp = input.checkpoint();
             if terminated(begin(Caseless("PARTICIPANT")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                 input.reset(&cp);
-                participants.push(participant(input, le)?);
+                participants.push(participant(input, config)?);
             } else {
                 input.reset(&cp);
                 let cp = input.checkpoint();
                 if terminated(begin(Caseless("VLOCATION")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                     input.reset(&cp);
-                    locations.push(location(input, le)?);
+                    locations.push(location(input, config)?);
                 } else {
                     input.reset(&cp);
                     let cp = input.checkpoint();
                     if terminated(begin(Caseless("VRESOURCE")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                         input.reset(&cp);
-                        resource_components.push(resource(input, le)?);
+                        resource_components.push(resource(input, config)?);
                     } else {
                         input.reset(&cp);
-                        let _ = other_with_name(input, le)?;
+                        let _ = other_with_name(input, config)?;
                     }
                 }
             }
@@ -1065,37 +1126,44 @@ where
         }
         input.reset(&checkpoint);
 
-        let parsed: ParsedProp<I::Slice> = terminated(property, line_terminator(le)).parse_next(input)?;
+        let Some(parsed): Option<ParsedProp<I::Slice>> = terminated(
+            optional_property_parser_from_config(config),
+            line_terminator(le),
+        )
+        .parse_next(input)?
+        else {
+            continue;
+        };
         let result: Result<(), CalendarParseError<I::Slice>> = (|| {
             match parsed {
                 ParsedProp::Known(KnownProp { name: prop_name, value }) => {
                     match (prop_name, value) {
                         (StaticProp::DtStamp, PropValue::DateTimeUtc(p)) => {
-                            once!(dtstamp, StaticProp::DtStamp, ComponentKind::Journal, p);
+                            once!(config, dtstamp, StaticProp::DtStamp, ComponentKind::Journal, p);
                         }
                         (StaticProp::Uid, PropValue::Uid(p)) => {
-                            once!(uid, StaticProp::Uid, ComponentKind::Journal, p);
+                            once!(config, uid, StaticProp::Uid, ComponentKind::Journal, p);
                         }
                         (StaticProp::DtStart, PropValue::DateTimeOrDate(p)) => {
-                            once!(dtstart, StaticProp::DtStart, ComponentKind::Journal, p);
+                            once!(config, dtstart, StaticProp::DtStart, ComponentKind::Journal, p);
                         }
                         (StaticProp::Class, PropValue::ClassValue(p)) => {
-                            once!(class, StaticProp::Class, ComponentKind::Journal, p);
+                            once!(config, class, StaticProp::Class, ComponentKind::Journal, p);
                         }
                         (StaticProp::Created, PropValue::DateTimeUtc(p)) => {
-                            once!(created, StaticProp::Created, ComponentKind::Journal, p);
+                            once!(config, created, StaticProp::Created, ComponentKind::Journal, p);
                         }
                         (StaticProp::LastModified, PropValue::DateTimeUtc(p)) => {
-                            once!(last_modified, StaticProp::LastModified, ComponentKind::Journal, p);
+                            once!(config, last_modified, StaticProp::LastModified, ComponentKind::Journal, p);
                         }
                         (StaticProp::Organizer, PropValue::Uri(p)) => {
-                            once!(organizer, StaticProp::Organizer, ComponentKind::Journal, p);
+                            once!(config, organizer, StaticProp::Organizer, ComponentKind::Journal, p);
                         }
                         (StaticProp::RecurId, PropValue::DateTimeOrDate(p)) => {
-                            once!(recurrence_id, StaticProp::RecurId, ComponentKind::Journal, p);
+                            once!(config, recurrence_id, StaticProp::RecurId, ComponentKind::Journal, p);
                         }
                         (StaticProp::Sequence, PropValue::Integer(p)) => {
-                            once!(sequence, StaticProp::Sequence, ComponentKind::Journal, p);
+                            once!(config, sequence, StaticProp::Sequence, ComponentKind::Journal, p);
                         }
                         (StaticProp::Status, PropValue::Status(p)) => {
                             if status.is_some() {
@@ -1111,10 +1179,10 @@ where
                             status = Some(p);
                         }
                         (StaticProp::Summary, PropValue::Text(p)) => {
-                            once!(summary, StaticProp::Summary, ComponentKind::Journal, p);
+                            once!(config, summary, StaticProp::Summary, ComponentKind::Journal, p);
                         }
                         (StaticProp::Url, PropValue::Uri(p)) => {
-                            once!(url, StaticProp::Url, ComponentKind::Journal, p);
+                            once!(config, url, StaticProp::Url, ComponentKind::Journal, p);
                         }
                         // Multi-valued
                         (StaticProp::Attach, PropValue::Attachment(p)) => { attach.push(p); }
@@ -1203,7 +1271,7 @@ where
 // ============================================================================
 
 /// Parses a [`FreeBusy`].
-fn free_busy<I, E>(input: &mut I, le: LineEnding) -> Result<FreeBusy, E>
+fn free_busy<I, E>(input: &mut I, config: &mut impl Config) -> Result<FreeBusy, E>
 where
     I: InputStream,
     I::Token: AsChar + Clone,
@@ -1211,6 +1279,7 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
+    let le = config.line_ending();
     terminated(begin(Caseless("VFREEBUSY")), line_terminator(le)).parse_next(input)?;
 
     let mut dtstamp: Option<Prop<DateTime<Utc>, Params>> = None;
@@ -1251,22 +1320,22 @@ where
             let cp = input.checkpoint();
             if terminated(begin(Caseless("PARTICIPANT")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                 input.reset(&cp);
-                participants.push(participant(input, le)?);
+                participants.push(participant(input, config)?);
             } else {
                 input.reset(&cp);
                 let cp = input.checkpoint();
                 if terminated(begin(Caseless("VLOCATION")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                     input.reset(&cp);
-                    locations.push(location(input, le)?);
+                    locations.push(location(input, config)?);
                 } else {
                     input.reset(&cp);
                     let cp = input.checkpoint();
                     if terminated(begin(Caseless("VRESOURCE")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                         input.reset(&cp);
-                        resource_components.push(resource(input, le)?);
+                        resource_components.push(resource(input, config)?);
                     } else {
                         input.reset(&cp);
-                        let _ = other_with_name(input, le)?;
+                        let _ = other_with_name(input, config)?;
                     }
                 }
             }
@@ -1274,31 +1343,38 @@ where
         }
         input.reset(&checkpoint);
 
-        let parsed: ParsedProp<I::Slice> = terminated(property, line_terminator(le)).parse_next(input)?;
+        let Some(parsed): Option<ParsedProp<I::Slice>> = terminated(
+            optional_property_parser_from_config(config),
+            line_terminator(le),
+        )
+        .parse_next(input)?
+        else {
+            continue;
+        };
         let result: Result<(), CalendarParseError<I::Slice>> = (|| {
             match parsed {
                 ParsedProp::Known(KnownProp { name: prop_name, value }) => {
                     match (prop_name, value) {
                         (StaticProp::DtStamp, PropValue::DateTimeUtc(p)) => {
-                            once!(dtstamp, StaticProp::DtStamp, ComponentKind::FreeBusy, p);
+                            once!(config, dtstamp, StaticProp::DtStamp, ComponentKind::FreeBusy, p);
                         }
                         (StaticProp::Uid, PropValue::Uid(p)) => {
-                            once!(uid, StaticProp::Uid, ComponentKind::FreeBusy, p);
+                            once!(config, uid, StaticProp::Uid, ComponentKind::FreeBusy, p);
                         }
                         (StaticProp::Contact, PropValue::Text(p)) => {
-                            once!(contact, StaticProp::Contact, ComponentKind::FreeBusy, p);
+                            once!(config, contact, StaticProp::Contact, ComponentKind::FreeBusy, p);
                         }
                         (StaticProp::DtStart, PropValue::DateTimeOrDate(p)) => {
-                            once!(dtstart, StaticProp::DtStart, ComponentKind::FreeBusy, p);
+                            once!(config, dtstart, StaticProp::DtStart, ComponentKind::FreeBusy, p);
                         }
                         (StaticProp::DtEnd, PropValue::DateTimeOrDate(p)) => {
-                            once!(dtend, StaticProp::DtEnd, ComponentKind::FreeBusy, p);
+                            once!(config, dtend, StaticProp::DtEnd, ComponentKind::FreeBusy, p);
                         }
                         (StaticProp::Organizer, PropValue::Uri(p)) => {
-                            once!(organizer, StaticProp::Organizer, ComponentKind::FreeBusy, p);
+                            once!(config, organizer, StaticProp::Organizer, ComponentKind::FreeBusy, p);
                         }
                         (StaticProp::Url, PropValue::Uri(p)) => {
-                            once!(url, StaticProp::Url, ComponentKind::FreeBusy, p);
+                            once!(config, url, StaticProp::Url, ComponentKind::FreeBusy, p);
                         }
                         // Multi-valued
                         (StaticProp::Attendee, PropValue::Uri(p)) => { attendee.push(p); }
@@ -1355,7 +1431,7 @@ where
 // ============================================================================
 
 /// Parses a [`TimeZone`].
-fn timezone<I, E>(input: &mut I, le: LineEnding) -> Result<TimeZone, E>
+fn timezone<I, E>(input: &mut I, config: &mut impl Config) -> Result<TimeZone, E>
 where
     I: InputStream,
     I::Token: AsChar + Clone,
@@ -1363,6 +1439,7 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
+    let le = config.line_ending();
     terminated(begin(Caseless("VTIMEZONE")), line_terminator(le)).parse_next(input)?;
 
     let mut tz_id: Option<Prop<Box<TzId>, Params>> = None;
@@ -1390,25 +1467,32 @@ where
         let checkpoint = input.checkpoint();
         if begin(empty::<I, E>).parse_next(input).is_ok() {
             input.reset(&checkpoint);
-            rules.push(tz_rule(input, le)?);
+            rules.push(tz_rule(input, config)?);
             continue;
         }
         input.reset(&checkpoint);
 
         // Otherwise parse a property
-        let parsed: ParsedProp<I::Slice> = terminated(property, line_terminator(le)).parse_next(input)?;
+        let Some(parsed): Option<ParsedProp<I::Slice>> = terminated(
+            optional_property_parser_from_config(config),
+            line_terminator(le),
+        )
+        .parse_next(input)?
+        else {
+            continue;
+        };
         let result: Result<(), CalendarParseError<I::Slice>> = (|| {
             match parsed {
                 ParsedProp::Known(KnownProp { name: prop_name, value }) => {
                     match (prop_name, value) {
                         (StaticProp::TzId, PropValue::TzId(p)) => {
-                            once!(tz_id, StaticProp::TzId, ComponentKind::TimeZone, p);
+                            once!(config, tz_id, StaticProp::TzId, ComponentKind::TimeZone, p);
                         }
                         (StaticProp::LastModified, PropValue::DateTimeUtc(p)) => {
-                            once!(last_modified, StaticProp::LastModified, ComponentKind::TimeZone, p);
+                            once!(config, last_modified, StaticProp::LastModified, ComponentKind::TimeZone, p);
                         }
                         (StaticProp::TzUrl, PropValue::Uri(p)) => {
-                            once!(tz_url, StaticProp::TzUrl, ComponentKind::TimeZone, p);
+                            once!(config, tz_url, StaticProp::TzUrl, ComponentKind::TimeZone, p);
                         }
                         _ => { /* ignore - property parser guarantees correct variant */ }
                     }
@@ -1443,7 +1527,7 @@ where
 }
 
 /// Parses a STANDARD or DAYLIGHT subcomponent of a VTIMEZONE.
-fn tz_rule<I, E>(input: &mut I, le: LineEnding) -> Result<TzRule, E>
+fn tz_rule<I, E>(input: &mut I, config: &mut impl Config) -> Result<TzRule, E>
 where
     I: InputStream,
     I::Token: AsChar + Clone,
@@ -1451,6 +1535,7 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
+    let le = config.line_ending();
     let kind: TzRuleKind = terminated(
         begin(alt((
             Caseless("STANDARD").value(TzRuleKind::Standard),
@@ -1469,18 +1554,18 @@ where
     let mut tz_name: Vec<Prop<String, Params>> = Vec::new();
     let mut x_props: HashMap<Box<CaselessStr>, Vec<Prop<Value<String>, Params>>> = HashMap::new();
 
-    parse_props!(input, le, parsed, {
+    parse_props!(input, config, parsed, {
         match parsed {
             ParsedProp::Known(KnownProp { name: prop_name, value }) => {
                 match (prop_name, value) {
                     (StaticProp::DtStart, PropValue::DateTimeOrDate(p)) => {
-                        once!(dtstart, StaticProp::DtStart, ComponentKind::StandardOrDaylight, p);
+                        once!(config, dtstart, StaticProp::DtStart, ComponentKind::StandardOrDaylight, p);
                     }
                     (StaticProp::TzOffsetTo, PropValue::UtcOffset(p)) => {
-                        once!(tz_offset_to, StaticProp::TzOffsetTo, ComponentKind::StandardOrDaylight, p);
+                        once!(config, tz_offset_to, StaticProp::TzOffsetTo, ComponentKind::StandardOrDaylight, p);
                     }
                     (StaticProp::TzOffsetFrom, PropValue::UtcOffset(p)) => {
-                        once!(tz_offset_from, StaticProp::TzOffsetFrom, ComponentKind::StandardOrDaylight, p);
+                        once!(config, tz_offset_from, StaticProp::TzOffsetFrom, ComponentKind::StandardOrDaylight, p);
                     }
                     (StaticProp::Comment, PropValue::Text(p)) => { comment.push(p); }
                     (StaticProp::RDate, PropValue::RDateSeq(p)) => { rdate.push(p); }
@@ -1552,7 +1637,7 @@ where
 // Alarm parser (RFC 5545 §3.6.6)
 // ============================================================================
 
-fn alarm<I, E>(input: &mut I, le: LineEnding) -> Result<Alarm, E>
+fn alarm<I, E>(input: &mut I, config: &mut impl Config) -> Result<Alarm, E>
 where
     I: InputStream,
     I::Token: AsChar + Clone,
@@ -1560,6 +1645,7 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
+    let le = config.line_ending();
     use crate::model::primitive::AlarmAction;
 
     terminated(begin(Caseless("VALARM")), line_terminator(le)).parse_next(input)?;
@@ -1579,33 +1665,33 @@ where
     let mut related_to: Vec<Prop<Box<Uid>, Params>> = Vec::new();
     let mut x_props: HashMap<Box<CaselessStr>, Vec<Prop<Value<String>, Params>>> = HashMap::new();
 
-    parse_props!(input, le, parsed, {
+    parse_props!(input, config, parsed, {
         match parsed {
             ParsedProp::Known(KnownProp { name: prop_name, value }) => {
                 match (prop_name, value) {
                     (StaticProp::Action, PropValue::AlarmAction(p)) => {
-                        once!(action, StaticProp::Action, ComponentKind::Alarm, p);
+                        once!(config, action, StaticProp::Action, ComponentKind::Alarm, p);
                     }
                     (StaticProp::Trigger, PropValue::Trigger(p)) => {
-                        once!(trigger, StaticProp::Trigger, ComponentKind::Alarm, p);
+                        once!(config, trigger, StaticProp::Trigger, ComponentKind::Alarm, p);
                     }
                     (StaticProp::Duration, PropValue::Duration(p)) => {
-                        once!(duration, StaticProp::Duration, ComponentKind::Alarm, p);
+                        once!(config, duration, StaticProp::Duration, ComponentKind::Alarm, p);
                     }
                     (StaticProp::Repeat, PropValue::Integer(p)) => {
-                        once!(repeat, StaticProp::Repeat, ComponentKind::Alarm, p);
+                        once!(config, repeat, StaticProp::Repeat, ComponentKind::Alarm, p);
                     }
                     (StaticProp::Uid, PropValue::Uid(p)) => {
-                        once!(uid, StaticProp::Uid, ComponentKind::Alarm, p);
+                        once!(config, uid, StaticProp::Uid, ComponentKind::Alarm, p);
                     }
                     (StaticProp::Acknowledged, PropValue::DateTimeUtc(p)) => {
-                        once!(acknowledged, StaticProp::Acknowledged, ComponentKind::Alarm, p);
+                        once!(config, acknowledged, StaticProp::Acknowledged, ComponentKind::Alarm, p);
                     }
                     (StaticProp::Description, PropValue::Text(p)) => {
-                        once!(description, StaticProp::Description, ComponentKind::Alarm, p);
+                        once!(config, description, StaticProp::Description, ComponentKind::Alarm, p);
                     }
                     (StaticProp::Summary, PropValue::Text(p)) => {
-                        once!(summary, StaticProp::Summary, ComponentKind::Alarm, p);
+                        once!(config, summary, StaticProp::Summary, ComponentKind::Alarm, p);
                     }
                     (StaticProp::Attach, PropValue::Attachment(p)) => { attach.push(p); }
                     (StaticProp::Attendee, PropValue::Uri(p)) => { attendee.push(p); }
@@ -1722,7 +1808,7 @@ where
 // Participant parser (RFC 9073 §7.1)
 // ============================================================================
 
-fn participant<I, E>(input: &mut I, le: LineEnding) -> Result<Participant, E>
+fn participant<I, E>(input: &mut I, config: &mut impl Config) -> Result<Participant, E>
 where
     I: InputStream,
     I::Token: AsChar + Clone,
@@ -1730,6 +1816,7 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
+    let le = config.line_ending();
     terminated(begin(Caseless("PARTICIPANT")), line_terminator(le)).parse_next(input)?;
 
     let mut uid: Option<Prop<Box<Uid>, Params>> = None;
@@ -1782,65 +1869,72 @@ where
             let cp = input.checkpoint();
             if terminated(begin(Caseless("VLOCATION")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                 input.reset(&cp);
-                locations.push(location(input, le)?);
+                locations.push(location(input, config)?);
             } else {
                 input.reset(&cp);
                 let cp = input.checkpoint();
                 if terminated(begin(Caseless("VRESOURCE")), line_terminator::<I, E>(le)).parse_next(input).is_ok() {
                     input.reset(&cp);
-                    resource_components.push(resource(input, le)?);
+                    resource_components.push(resource(input, config)?);
                 } else {
                     input.reset(&cp);
-                    let _ = other_with_name(input, le)?;
+                    let _ = other_with_name(input, config)?;
                 }
             }
             continue;
         }
         input.reset(&checkpoint);
 
-        let parsed: ParsedProp<I::Slice> = terminated(property, line_terminator(le)).parse_next(input)?;
+        let Some(parsed): Option<ParsedProp<I::Slice>> = terminated(
+            optional_property_parser_from_config(config),
+            line_terminator(le),
+        )
+        .parse_next(input)?
+        else {
+            continue;
+        };
         let result: Result<(), CalendarParseError<I::Slice>> = (|| {
             match parsed {
                 ParsedProp::Known(KnownProp { name: prop_name, value }) => {
                     match (prop_name, value) {
                         (StaticProp::Uid, PropValue::Uid(p)) => {
-                            once!(uid, StaticProp::Uid, ComponentKind::Unknown, p);
+                            once!(config, uid, StaticProp::Uid, ComponentKind::Unknown, p);
                         }
                         (StaticProp::ParticipantType, PropValue::ParticipantType(p)) => {
-                            once!(participant_type, StaticProp::ParticipantType, ComponentKind::Unknown, p);
+                            once!(config, participant_type, StaticProp::ParticipantType, ComponentKind::Unknown, p);
                         }
                         (StaticProp::CalendarAddress, PropValue::Uri(p)) => {
-                            once!(calendar_address, StaticProp::CalendarAddress, ComponentKind::Unknown, p);
+                            once!(config, calendar_address, StaticProp::CalendarAddress, ComponentKind::Unknown, p);
                         }
                         (StaticProp::Created, PropValue::DateTimeUtc(p)) => {
-                            once!(created, StaticProp::Created, ComponentKind::Unknown, p);
+                            once!(config, created, StaticProp::Created, ComponentKind::Unknown, p);
                         }
                         (StaticProp::Description, PropValue::Text(p)) => {
-                            once!(description, StaticProp::Description, ComponentKind::Unknown, p);
+                            once!(config, description, StaticProp::Description, ComponentKind::Unknown, p);
                         }
                         (StaticProp::DtStamp, PropValue::DateTimeUtc(p)) => {
-                            once!(dtstamp, StaticProp::DtStamp, ComponentKind::Unknown, p);
+                            once!(config, dtstamp, StaticProp::DtStamp, ComponentKind::Unknown, p);
                         }
                         (StaticProp::Geo, PropValue::Geo(p)) => {
-                            once!(geo, StaticProp::Geo, ComponentKind::Unknown, p);
+                            once!(config, geo, StaticProp::Geo, ComponentKind::Unknown, p);
                         }
                         (StaticProp::LastModified, PropValue::DateTimeUtc(p)) => {
-                            once!(last_modified, StaticProp::LastModified, ComponentKind::Unknown, p);
+                            once!(config, last_modified, StaticProp::LastModified, ComponentKind::Unknown, p);
                         }
                         (StaticProp::Priority, PropValue::Priority(p)) => {
-                            once!(priority, StaticProp::Priority, ComponentKind::Unknown, p);
+                            once!(config, priority, StaticProp::Priority, ComponentKind::Unknown, p);
                         }
                         (StaticProp::Sequence, PropValue::Integer(p)) => {
-                            once!(sequence, StaticProp::Sequence, ComponentKind::Unknown, p);
+                            once!(config, sequence, StaticProp::Sequence, ComponentKind::Unknown, p);
                         }
                         (StaticProp::Status, PropValue::Status(p)) => {
-                            once!(status, StaticProp::Status, ComponentKind::Unknown, p);
+                            once!(config, status, StaticProp::Status, ComponentKind::Unknown, p);
                         }
                         (StaticProp::Summary, PropValue::Text(p)) => {
-                            once!(summary, StaticProp::Summary, ComponentKind::Unknown, p);
+                            once!(config, summary, StaticProp::Summary, ComponentKind::Unknown, p);
                         }
                         (StaticProp::Url, PropValue::Uri(p)) => {
-                            once!(url, StaticProp::Url, ComponentKind::Unknown, p);
+                            once!(config, url, StaticProp::Url, ComponentKind::Unknown, p);
                         }
                         // Multi-valued
                         (StaticProp::Attach, PropValue::Attachment(p)) => { attach.push(p); }
@@ -1914,7 +2008,7 @@ where
 // Location parser (RFC 9073 §7.2)
 // ============================================================================
 
-fn location<I, E>(input: &mut I, le: LineEnding) -> Result<LocationComponent, E>
+fn location<I, E>(input: &mut I, config: &mut impl Config) -> Result<LocationComponent, E>
 where
     I: InputStream,
     I::Token: AsChar + Clone,
@@ -1922,6 +2016,7 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
+    let le = config.line_ending();
     terminated(begin(Caseless("VLOCATION")), line_terminator(le)).parse_next(input)?;
 
     let mut uid: Option<Prop<Box<Uid>, Params>> = None;
@@ -1934,30 +2029,30 @@ where
     let mut x_props: HashMap<Box<CaselessStr>, Vec<Prop<Value<String>, Params>>> = HashMap::new();
 
 
-    parse_props!(input, le, parsed, {
+    parse_props!(input, config, parsed, {
         match parsed {
             ParsedProp::Known(KnownProp { name: prop_name, value }) => {
                 match (prop_name, value) {
                     (StaticProp::Uid, PropValue::Uid(p)) => {
-                        once!(uid, StaticProp::Uid, ComponentKind::Unknown, p);
+                        once!(config, uid, StaticProp::Uid, ComponentKind::Unknown, p);
                     }
                     (StaticProp::Description, PropValue::Text(p)) => {
-                        once!(description, StaticProp::Description, ComponentKind::Unknown, p);
+                        once!(config, description, StaticProp::Description, ComponentKind::Unknown, p);
                     }
                     (StaticProp::Geo, PropValue::Geo(p)) => {
-                        once!(geo, StaticProp::Geo, ComponentKind::Unknown, p);
+                        once!(config, geo, StaticProp::Geo, ComponentKind::Unknown, p);
                     }
                     (StaticProp::Name, PropValue::Text(p)) => {
-                        once!(name, StaticProp::Name, ComponentKind::Unknown, p);
+                        once!(config, name, StaticProp::Name, ComponentKind::Unknown, p);
                     }
                     (StaticProp::LocationType, PropValue::TextSeq(p)) => {
                         // LocationType is parsed as TextSeq but model stores as String
                         // Join back into a single comma-separated string
                         let joined = p.value.join(",");
-                        once!(location_type, StaticProp::LocationType, ComponentKind::Unknown, Prop { value: joined, params: p.params });
+                        once!(config, location_type, StaticProp::LocationType, ComponentKind::Unknown, Prop { value: joined, params: p.params });
                     }
                     (StaticProp::Url, PropValue::Uri(p)) => {
-                        once!(url, StaticProp::Url, ComponentKind::Unknown, p);
+                        once!(config, url, StaticProp::Url, ComponentKind::Unknown, p);
                     }
                     (StaticProp::StructuredData, PropValue::StructuredData(p)) => {
                         structured_data.push(p);
@@ -2001,7 +2096,7 @@ where
 // ============================================================================
 
 /// Parses a [`ResourceComponent`].
-fn resource<I, E>(input: &mut I, le: LineEnding) -> Result<ResourceComponent, E>
+fn resource<I, E>(input: &mut I, config: &mut impl Config) -> Result<ResourceComponent, E>
 where
     I: InputStream,
     I::Token: AsChar + Clone,
@@ -2009,6 +2104,7 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
+    let le = config.line_ending();
     terminated(begin(Caseless("VRESOURCE")), line_terminator(le)).parse_next(input)?;
 
     let mut uid: Option<Prop<Box<Uid>, Params>> = None;
@@ -2020,24 +2116,24 @@ where
     let mut x_props: HashMap<Box<CaselessStr>, Vec<Prop<Value<String>, Params>>> = HashMap::new();
 
 
-    parse_props!(input, le, parsed, {
+    parse_props!(input, config, parsed, {
         match parsed {
             ParsedProp::Known(KnownProp { name: prop_name, value }) => {
                 match (prop_name, value) {
                     (StaticProp::Uid, PropValue::Uid(p)) => {
-                        once!(uid, StaticProp::Uid, ComponentKind::Unknown, p);
+                        once!(config, uid, StaticProp::Uid, ComponentKind::Unknown, p);
                     }
                     (StaticProp::Description, PropValue::Text(p)) => {
-                        once!(description, StaticProp::Description, ComponentKind::Unknown, p);
+                        once!(config, description, StaticProp::Description, ComponentKind::Unknown, p);
                     }
                     (StaticProp::Geo, PropValue::Geo(p)) => {
-                        once!(geo, StaticProp::Geo, ComponentKind::Unknown, p);
+                        once!(config, geo, StaticProp::Geo, ComponentKind::Unknown, p);
                     }
                     (StaticProp::Name, PropValue::Text(p)) => {
-                        once!(name, StaticProp::Name, ComponentKind::Unknown, p);
+                        once!(config, name, StaticProp::Name, ComponentKind::Unknown, p);
                     }
                     (StaticProp::ResourceType, PropValue::ResourceType(p)) => {
-                        once!(resource_type, StaticProp::ResourceType, ComponentKind::Unknown, p);
+                        once!(config, resource_type, StaticProp::ResourceType, ComponentKind::Unknown, p);
                     }
                     (StaticProp::StructuredData, PropValue::StructuredData(p)) => {
                         structured_data.push(p);
@@ -2080,7 +2176,7 @@ where
 // ============================================================================
 
 /// Parses an arbitrary component with BEGIN and END lines.
-fn other_with_name<I, E>(input: &mut I, le: LineEnding) -> Result<OtherComponent, E>
+fn other_with_name<I, E>(input: &mut I, config: &mut impl Config) -> Result<OtherComponent, E>
 where
     I: InputStream,
     I::Token: AsChar + Clone,
@@ -2088,6 +2184,7 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
+    let le = config.line_ending();
     fn is_name_char<T: AsChar>(c: T) -> bool {
         let c = c.as_char();
         c.is_ascii_alphanumeric() || c == '-'
@@ -2116,11 +2213,11 @@ where
         input.reset(&checkpoint);
 
         // Consume the property line (we don't need to interpret it)
-        let _: ParsedProp<I::Slice> = terminated(property, line_terminator(le)).parse_next(input)?;
+        let _: Option<ParsedProp<I::Slice>> = terminated(optional_property_parser_from_config(config), line_terminator(le)).parse_next(input)?;
     }
 
     // Parse nested subcomponents recursively
-    let subcomponents: Vec<OtherComponent> = repeat(0.., |i: &mut I| other_with_name(i, le)).parse_next(input)?;
+    let subcomponents: Vec<OtherComponent> = repeat(0.., |i: &mut I| other_with_name(i, config)).parse_next(input)?;
 
     // Parse END:<name>
     let end_name_slice = terminated(
@@ -2661,7 +2758,7 @@ mod tests {
         );
 
         let mut esc = input.as_escaped();
-        let result: Result<Event, ()> = event(&mut esc, LineEnding::Lf);
+        let result: Result<Event, ()> = event(&mut esc, &mut DefaultConfig::new(LineEnding::Lf));
         assert!(result.is_ok(), "parse failed: {:?}", result.err());
         assert!(esc.is_empty(), "remaining input: {:?}", std::str::from_utf8(esc.0));
     }
@@ -2708,7 +2805,7 @@ mod tests {
         );
 
         let mut esc = input.as_escaped();
-        let result: Result<TimeZone, ()> = timezone(&mut esc, LineEnding::Lf);
+        let result: Result<TimeZone, ()> = timezone(&mut esc, &mut DefaultConfig::new(LineEnding::Lf));
         assert!(result.is_ok(), "parse failed: {:?}", result.err());
         let tz = result.unwrap();
         assert_eq!(tz.tz_id().value.as_str(), "America/New_York");
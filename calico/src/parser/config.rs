@@ -1,16 +1,32 @@
 //! Parser configurations
+//!
+//! A [`Config`] decides how the parser handles the quirks of real-world iCalendar data. The
+//! [`DefaultConfig`] follows RFC 5545 (with a few long-standing tolerances), while the
+//! [`LenientConfig`] recovers from common violations of the RFC and records a [`ParseWarning`]
+//! for each one, so that callers can audit what was fixed.
 
 use crate::{
-    model::string::ParamValue,
-    parser::error::{CalendarParseError, ParseFloatError},
+    model::{
+        primitive::{Date, Geo, Token},
+        property::StaticProp,
+        string::ParamValue,
+    },
+    parser::{
+        error::{
+            CalendarParseError, ComponentKind, InvalidDateError, InvalidGeoError,
+            InvalidRawTimeError, ParseFloatError,
+        },
+        property::PropName,
+    },
 };
 
 /// The line ending convention used in an iCalendar document.
 ///
 /// RFC 5545 mandates CRLF (`\r\n`), but many real-world `.ics` files use bare LF (`\n`).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub enum LineEnding {
     /// `\r\n`
+    #[default]
     Crlf,
     /// `\n`
     Lf,
@@ -72,6 +88,105 @@ pub trait Config {
     ) -> Result<f64, CalendarParseError<S>> {
         Err(CalendarParseError::FloatToF64Failure(error))
     }
+
+    /// Called when the value of a single-valued TEXT property (such as SUMMARY) contains a comma
+    /// which is not escaped, as RFC 5545 § 3.3.11 requires. The comma is kept as part of the value
+    /// unless this returns an error. The default behaviour is to accept the comma.
+    fn handle_unescaped_comma<S>(
+        &mut self,
+        _prop: StaticProp,
+    ) -> Result<(), CalendarParseError<S>> {
+        Ok(())
+    }
+
+    /// Called when a VCALENDAR has no VERSION property. If this returns `Ok(())`, the calendar is
+    /// given the version 2.0. The default behaviour is to return
+    /// [`CalendarParseError::MissingProp`].
+    fn handle_missing_version<S>(&mut self) -> Result<(), CalendarParseError<S>> {
+        Err(CalendarParseError::MissingProp {
+            prop: PropName::Known(StaticProp::Version),
+            component: ComponentKind::Calendar,
+        })
+    }
+
+    /// Called when a property which may occur at most once in a component (such as DTSTART in a
+    /// VEVENT) occurs again. If this returns `Ok(())`, the last value is kept. The default behaviour
+    /// is to return [`CalendarParseError::MoreThanOneProp`].
+    fn handle_duplicate_property<S>(
+        &mut self,
+        prop: StaticProp,
+        component: ComponentKind<S>,
+    ) -> Result<(), CalendarParseError<S>> {
+        Err(CalendarParseError::MoreThanOneProp {
+            prop: PropName::Known(prop),
+            component,
+        })
+    }
+
+    /// Called when the VALUE parameter of a known property names a value type which is not
+    /// defined by RFC 5545. If this returns `Ok(())`, the value is parsed as TEXT and the property
+    /// is returned as an unknown property, so that its value is preserved. The default behaviour is
+    /// to return [`CalendarParseError::InvalidValueType`].
+    fn handle_unknown_value_type<S>(
+        &mut self,
+        _prop: StaticProp,
+        value_type: &str,
+    ) -> Result<(), CalendarParseError<S>> {
+        Err(CalendarParseError::InvalidValueType(Token::Unknown(
+            value_type.to_string(),
+        )))
+    }
+
+    /// Called when a DATE-TIME on the given `date` has the time `240000`, which some producers use
+    /// for the end of the day. If this returns `Ok(())`, the value is moved to midnight of the
+    /// following day. The default behaviour is to return [`CalendarParseError::InvalidRawTime`].
+    fn handle_end_of_day_time<S>(&mut self, _date: Date) -> Result<(), CalendarParseError<S>> {
+        Err(CalendarParseError::InvalidRawTime(InvalidRawTimeError {
+            hours: 24,
+            minutes: 0,
+            seconds: 0,
+        }))
+    }
+
+    /// Called by [`geo_with_config`] if the latitude is outside the range `-90..=90` or the
+    /// longitude is outside the range `-180..=180`, and may either return an error or produce a
+    /// substitute [`Geo`] value. The default behaviour accepts coordinates which are out of range
+    /// by at most one degree, and returns [`CalendarParseError::InvalidGeo`] otherwise.
+    ///
+    /// [`geo_with_config`]: crate::parser::primitive::geo_with_config
+    fn handle_geo_out_of_range<S>(
+        &mut self,
+        lat: f64,
+        lon: f64,
+    ) -> Result<Geo, CalendarParseError<S>> {
+        if lat.abs() > 91.0 {
+            Err(CalendarParseError::InvalidGeo(
+                InvalidGeoError::LatOutOfBounds(lat),
+            ))
+        } else if lon.abs() > 181.0 {
+            Err(CalendarParseError::InvalidGeo(
+                InvalidGeoError::LonOutOfBounds(lon),
+            ))
+        } else {
+            Ok(Geo { lat, lon })
+        }
+    }
+
+    /// Called when a DATE or DATE-TIME value names a date which does not exist, such as
+    /// `20210229`. This may return an error, produce a substitute date, or return `Ok(None)` to
+    /// discard the property containing the date. The default behaviour is to return
+    /// [`CalendarParseError::InvalidDate`].
+    ///
+    /// Parsers of individual values (such as [`date_with_config`]) cannot discard a property, and
+    /// substitute [`InvalidDateError::clamped`] when this returns `Ok(None)`.
+    ///
+    /// [`date_with_config`]: crate::parser::primitive::date_with_config
+    fn handle_invalid_date<S>(
+        &mut self,
+        error: InvalidDateError,
+    ) -> Result<Option<Date>, CalendarParseError<S>> {
+        Err(CalendarParseError::InvalidDate(error))
+    }
 }
 
 /// A struct that implements [`Config`] with configurable line ending.
@@ -104,3 +219,322 @@ impl Config for DefaultConfig {
         self.line_ending = le;
     }
 }
+
+/// A violation of RFC 5545 which a [`LenientConfig`] recovered from.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum ParseWarning {
+    /// The TEXT value of `prop` contained an unescaped comma, which was kept in the value.
+    UnescapedComma { prop: StaticProp },
+    /// A VCALENDAR had no VERSION property, and was given the version 2.0.
+    MissingVersion,
+    /// A property which may occur at most once in a component occurred again, and the last
+    /// value was kept.
+    DuplicateProperty { prop: StaticProp },
+    /// The VALUE parameter of `prop` named the unknown `value_type`, and the value was kept as
+    /// TEXT in an unknown property.
+    UnknownValueType {
+        prop: StaticProp,
+        value_type: String,
+    },
+    /// A DATE-TIME on `date` had the time `240000`, and was moved to midnight of the next day.
+    EndOfDayTime { date: Date },
+    /// A GEO value was out of range, and was clamped to the valid range.
+    GeoClamped { original: Geo, clamped: Geo },
+    /// A date which does not exist was replaced with the nearest valid date.
+    DateClamped {
+        error: InvalidDateError,
+        clamped: Date,
+    },
+    /// A property containing a date which does not exist was discarded.
+    DateSkipped { error: InvalidDateError },
+}
+
+/// The handling of dates which do not exist, such as `20210229`, by a [`LenientConfig`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum InvalidDatePolicy {
+    /// Discard the property containing the date.
+    Skip,
+    /// Replace the date with [`InvalidDateError::clamped`].
+    #[default]
+    Clamp,
+}
+
+/// A [`Config`] which recovers from common violations of RFC 5545, recording a [`ParseWarning`]
+/// for each recovery.
+///
+/// ```
+/// # use calico::parser::config::{LenientConfig, ParseWarning};
+/// # use calico::model::component::Calendar;
+/// let input = "BEGIN:VCALENDAR\r\nPRODID:-//Example//EN\r\nEND:VCALENDAR\r\n";
+/// let mut config = LenientConfig::default();
+/// let calendars = Calendar::parse_with_config(input, &mut config).unwrap();
+/// assert_eq!(calendars.len(), 1);
+/// assert_eq!(config.warnings(), &[ParseWarning::MissingVersion]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LenientConfig {
+    line_ending: LineEnding,
+    invalid_dates: InvalidDatePolicy,
+    warnings: Vec<ParseWarning>,
+}
+
+impl LenientConfig {
+    /// Creates a new `LenientConfig` with the given line ending, which clamps invalid dates.
+    pub fn new(line_ending: LineEnding) -> Self {
+        Self {
+            line_ending,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the handling of dates which do not exist.
+    pub fn with_invalid_dates(mut self, policy: InvalidDatePolicy) -> Self {
+        self.invalid_dates = policy;
+        self
+    }
+
+    /// Returns the handling of dates which do not exist.
+    pub fn invalid_dates(&self) -> InvalidDatePolicy {
+        self.invalid_dates
+    }
+
+    /// Returns the warnings recorded so far, in the order of the recoveries.
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
+    /// Removes and returns the warnings recorded so far.
+    pub fn take_warnings(&mut self) -> Vec<ParseWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+}
+
+impl Config for LenientConfig {
+    fn line_ending(&self) -> LineEnding {
+        self.line_ending
+    }
+
+    fn set_line_ending(&mut self, le: LineEnding) {
+        self.line_ending = le;
+    }
+
+    fn handle_unescaped_comma<S>(&mut self, prop: StaticProp) -> Result<(), CalendarParseError<S>> {
+        self.warnings.push(ParseWarning::UnescapedComma { prop });
+        Ok(())
+    }
+
+    fn handle_missing_version<S>(&mut self) -> Result<(), CalendarParseError<S>> {
+        self.warnings.push(ParseWarning::MissingVersion);
+        Ok(())
+    }
+
+    fn handle_duplicate_property<S>(
+        &mut self,
+        prop: StaticProp,
+        _component: ComponentKind<S>,
+    ) -> Result<(), CalendarParseError<S>> {
+        self.warnings.push(ParseWarning::DuplicateProperty { prop });
+        Ok(())
+    }
+
+    fn handle_unknown_value_type<S>(
+        &mut self,
+        prop: StaticProp,
+        value_type: &str,
+    ) -> Result<(), CalendarParseError<S>> {
+        self.warnings.push(ParseWarning::UnknownValueType {
+            prop,
+            value_type: value_type.to_string(),
+        });
+        Ok(())
+    }
+
+    fn handle_end_of_day_time<S>(&mut self, date: Date) -> Result<(), CalendarParseError<S>> {
+        self.warnings.push(ParseWarning::EndOfDayTime { date });
+        Ok(())
+    }
+
+    fn handle_geo_out_of_range<S>(
+        &mut self,
+        lat: f64,
+        lon: f64,
+    ) -> Result<Geo, CalendarParseError<S>> {
+        let clamped = Geo {
+            lat: lat.clamp(-90.0, 90.0),
+            lon: lon.clamp(-180.0, 180.0),
+        };
+        self.warnings.push(ParseWarning::GeoClamped {
+            original: Geo { lat, lon },
+            clamped,
+        });
+        Ok(clamped)
+    }
+
+    fn handle_invalid_date<S>(
+        &mut self,
+        error: InvalidDateError,
+    ) -> Result<Option<Date>, CalendarParseError<S>> {
+        match self.invalid_dates {
+            InvalidDatePolicy::Skip => {
+                self.warnings.push(ParseWarning::DateSkipped { error });
+                Ok(None)
+            }
+            InvalidDatePolicy::Clamp => {
+                let clamped = error.clamped();
+                self.warnings
+                    .push(ParseWarning::DateClamped { error, clamped });
+                Ok(Some(clamped))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        date,
+        model::{
+            component::{Calendar, CalendarComponent, Event},
+            primitive::{DateTimeOrDate, Value},
+            string::CaselessStr,
+        },
+    };
+
+    const INPUT: &str = "BEGIN:VCALENDAR\r\n\
+        PRODID:-//Example//EN\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:1\r\n\
+        DTSTAMP:20210101T000000Z\r\n\
+        DTSTART:20210229T240000\r\n\
+        SUMMARY:Lunch, with friends\r\n\
+        DESCRIPTION;VALUE=X-HTML:<b>Hi</b>\r\n\
+        GEO:95.5;-200.25\r\n\
+        EXDATE:20210101T090000,20210431T090000\r\n\
+        END:VEVENT\r\n\
+        END:VCALENDAR\r\n";
+
+    fn event(calendars: &[Calendar]) -> &Event {
+        match calendars[0].components().as_slice() {
+            [CalendarComponent::Event(event)] => event,
+            other => panic!("expected a single event, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn default_config_rejects_quirks() {
+        assert!(Calendar::parse_with_config(INPUT, &mut DefaultConfig::default()).is_err());
+        assert!(Calendar::parse(INPUT).is_err());
+    }
+
+    #[test]
+    fn lenient_config_clamps_and_warns() {
+        let mut config = LenientConfig::default();
+        let calendars = Calendar::parse_with_config(INPUT, &mut config).unwrap();
+        let event = event(&calendars);
+
+        match &event.dtstart().unwrap().value {
+            DateTimeOrDate::DateTime(dt) => {
+                assert_eq!(dt.date, date!(2021; 3; 1));
+                assert_eq!(dt.time, crate::time!(0; 0; 0));
+            }
+            other => panic!("expected a datetime, got {other:?}"),
+        }
+        assert_eq!(event.summary().unwrap().value, "Lunch, with friends");
+        assert_eq!(
+            event.geo().unwrap().value,
+            Geo {
+                lat: 90.0,
+                lon: -180.0
+            }
+        );
+        assert!(event.description().is_none());
+        let description = event.x_property(CaselessStr::new("DESCRIPTION")).unwrap();
+        assert_eq!(description[0].value, Value::Text("<b>Hi</b>".into()));
+        match &event.exdate().unwrap()[1].value {
+            DateTimeOrDate::DateTime(dt) => assert_eq!(dt.date, date!(2021; 4; 30)),
+            other => panic!("expected a datetime, got {other:?}"),
+        }
+
+        let invalid = |year, month, day| InvalidDateError { year, month, day };
+        assert_eq!(
+            config.take_warnings(),
+            vec![
+                ParseWarning::DateClamped {
+                    error: invalid(2021, 2, 29),
+                    clamped: date!(2021; 2; 28),
+                },
+                ParseWarning::EndOfDayTime {
+                    date: date!(2021; 2; 28)
+                },
+                ParseWarning::UnescapedComma {
+                    prop: StaticProp::Summary
+                },
+                ParseWarning::UnknownValueType {
+                    prop: StaticProp::Description,
+                    value_type: "X-HTML".into(),
+                },
+                ParseWarning::GeoClamped {
+                    original: Geo {
+                        lat: 95.5,
+                        lon: -200.25
+                    },
+                    clamped: Geo {
+                        lat: 90.0,
+                        lon: -180.0
+                    },
+                },
+                ParseWarning::DateClamped {
+                    error: invalid(2021, 4, 31),
+                    clamped: date!(2021; 4; 30),
+                },
+                ParseWarning::MissingVersion,
+            ]
+        );
+        assert!(config.warnings().is_empty());
+    }
+
+    #[test]
+    fn lenient_config_skips_invalid_dates() {
+        let mut config = LenientConfig::default().with_invalid_dates(InvalidDatePolicy::Skip);
+        let calendars = Calendar::parse_with_config(INPUT, &mut config).unwrap();
+        let event = event(&calendars);
+
+        assert!(event.dtstart().is_none());
+        assert!(event.exdate().is_none());
+        assert!(event.summary().is_some());
+        assert!(config.warnings().contains(&ParseWarning::DateSkipped {
+            error: InvalidDateError {
+                year: 2021,
+                month: 4,
+                day: 31
+            }
+        }));
+    }
+
+    #[test]
+    fn duplicate_once_only_properties() {
+        let input = "BEGIN:VCALENDAR\r\n\
+            VERSION:2.0\r\n\
+            PRODID:-//Example//EN\r\n\
+            BEGIN:VEVENT\r\n\
+            UID:1\r\n\
+            DTSTAMP:20210101T000000Z\r\n\
+            SUMMARY:First\r\n\
+            SUMMARY:Second\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+        assert!(Calendar::parse(input).is_err());
+
+        let mut config = LenientConfig::default();
+        let calendars = Calendar::parse_with_config(input, &mut config).unwrap();
+        assert_eq!(event(&calendars).summary().unwrap().value, "Second");
+        assert_eq!(
+            config.warnings(),
+            &[ParseWarning::DuplicateProperty {
+                prop: StaticProp::Summary
+            }]
+        );
+    }
+}
@@ -13,7 +13,7 @@ use crate::{
     model::{
         component::TzRuleKind,
         parameter::{KnownParam, SDParamsFromParamsError, StaticParam},
-        primitive::{Integer, Sign, Status, Token, ValueType},
+        primitive::{Date, Day, Integer, Month, Sign, Status, Token, ValueType, Year},
         rrule,
     },
     parser::escaped::Escaped,
@@ -202,6 +202,33 @@ pub struct InvalidDateError {
     pub(crate) day: u8,
 }
 
+impl InvalidDateError {
+    /// Returns the year as written in the input.
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Returns the month as written in the input.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day as written in the input.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Returns the nearest valid date, by clamping the month to `1..=12` and then the day to the
+    /// length of that month. For example, `20210229` is clamped to `20210228`.
+    pub fn clamped(&self) -> Date {
+        let year = Year::new(self.year.min(9999)).expect("year is at most 9999");
+        let month = Month::new(self.month.clamp(1, 12)).expect("month is in range");
+        let max_day = Date::maximum_day(year, month);
+        let day = Day::new(self.day.clamp(1, max_day as u8)).expect("day is in range");
+        Date::new(year, month, day).expect("day is at most the length of the month")
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct InvalidIntegerError {
     pub(crate) sign: Option<Sign>,
@@ -251,6 +278,11 @@ impl ParseError {
             offset: total - self.offset,
         }
     }
+
+    /// Creates an error at the given byte offset.
+    pub(crate) fn at(offset: usize) -> Self {
+        Self { offset }
+    }
 }
 
 impl std::fmt::Display for ParseError {
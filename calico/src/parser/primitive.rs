@@ -38,8 +38,8 @@ use super::{
     InputStream,
     error::{
         CalendarParseError, InvalidCompletionPercentageError, InvalidDateError,
-        InvalidDurationTimeError, InvalidGeoError, InvalidIntegerError, InvalidPriorityError,
-        InvalidRawTimeError, InvalidUtcOffsetError,
+        InvalidDurationTimeError, InvalidIntegerError, InvalidPriorityError, InvalidRawTimeError,
+        InvalidUtcOffsetError,
     },
 };
 
@@ -63,9 +63,9 @@ macro_rules! match_iana_token {
     ($input:ident, $enum_name:ident) => {{
         let name = name.parse_next($input)?;
 
-        match name.as_str().parse::<$enum_name>() {
-            Ok(res) => Ok($crate::model::primitive::Token::Known(res)),
-            Err(_) => Ok($crate::model::primitive::Token::Unknown(name)),
+        match <$enum_name as ::calendar_types::set::TokenParse>::parse_token(name.as_str()) {
+            Some(res) => Ok($crate::model::primitive::Token::Known(res)),
+            None => Ok($crate::model::primitive::Token::Unknown(name)),
         }
     }};
 }
@@ -759,6 +759,27 @@ where
     .parse_next(input)
 }
 
+/// Parses a [`Period`] with the given [`Config`].
+pub fn period_with_config<I, E>(input: &mut I, config: &mut impl Config) -> Result<Period, E>
+where
+    I: InputStream,
+    <I as Stream>::Token: AsChar + Clone,
+    E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
+{
+    let start = datetime_with_config(input, config)?;
+    let _ = '/'.parse_next(input)?;
+
+    let checkpoint = input.checkpoint();
+    match datetime_with_config::<I, E>(input, config) {
+        Ok(end) => Ok(Period::Explicit { start, end }),
+        Err(_) => {
+            input.reset(&checkpoint);
+            let duration = duration.parse_next(input)?.duration;
+            Ok(Period::Start { start, duration })
+        }
+    }
+}
+
 /// Parses a [`SignedDuration`].
 pub fn duration<I, E>(input: &mut I) -> Result<SignedDuration, E>
 where
@@ -889,8 +910,120 @@ where
         .parse_next(input)
 }
 
+/// Parses a [`DateTimeOrDate<TimeFormat>`] with the given [`Config`].
+pub fn datetime_or_date_with_config<I, E>(
+    input: &mut I,
+    config: &mut impl Config,
+) -> Result<DateTimeOrDate<TimeFormat>, E>
+where
+    I: InputStream,
+    <I as Stream>::Token: AsChar + Clone,
+    E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
+{
+    let date = date_with_config(input, config)?;
+
+    if opt('T').parse_next(input)?.is_none() {
+        return Ok(DateTimeOrDate::Date(date));
+    }
+
+    let (date, time) = time_on_date(input, config, date)?;
+    let marker = time_format.parse_next(input)?;
+    Ok(DateTimeOrDate::DateTime(DateTime { date, time, marker }))
+}
+
+/// Parses a datetime of the form `YYYYMMDDThhmmss` with the given [`Config`], with an optional
+/// time format suffix.
+pub fn datetime_with_config<I, E>(
+    input: &mut I,
+    config: &mut impl Config,
+) -> Result<DateTime<TimeFormat>, E>
+where
+    I: InputStream,
+    <I as Stream>::Token: AsChar + Clone,
+    E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
+{
+    let date = date_with_config(input, config)?;
+    let _ = 'T'.parse_next(input)?;
+    let (date, time) = time_on_date(input, config, date)?;
+    let marker = time_format.parse_next(input)?;
+    Ok(DateTime { date, time, marker })
+}
+
+/// Parses a datetime of the form `YYYYMMDDThhmmssZ` with the given [`Config`], including the
+/// mandatory UTC marker suffix.
+pub fn datetime_utc_with_config<I, E>(
+    input: &mut I,
+    config: &mut impl Config,
+) -> Result<DateTime<Utc>, E>
+where
+    I: InputStream,
+    <I as Stream>::Token: AsChar + Clone,
+    E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
+{
+    let date = date_with_config(input, config)?;
+    let _ = 'T'.parse_next(input)?;
+    let (date, time) = time_on_date(input, config, date)?;
+    utc_marker.parse_next(input)?;
+    Ok(DateTime {
+        date,
+        time,
+        marker: Utc,
+    })
+}
+
+/// Parses the time of a datetime on the given `date`. If the time is `240000` and the config
+/// accepts it, the datetime is moved to midnight of the following day.
+fn time_on_date<I, E>(
+    input: &mut I,
+    config: &mut impl Config,
+    date: Date,
+) -> Result<(Date, Time), E>
+where
+    I: InputStream,
+    <I as Stream>::Token: AsChar + Clone,
+    E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
+{
+    let checkpoint = input.checkpoint();
+    if opt("240000").parse_next(input)?.is_none() {
+        return raw_time.map(|time| (date, time)).parse_next(input);
+    }
+
+    let next_day = config.handle_end_of_day_time(date).and_then(|()| {
+        date.succ()
+            .ok_or(CalendarParseError::InvalidRawTime(InvalidRawTimeError {
+                hours: 24,
+                minutes: 0,
+                seconds: 0,
+            }))
+    });
+
+    match next_day {
+        Ok(next_day) => {
+            let midnight = Time::new(Hour::H00, Minute::M00, Second::S00, None)
+                .expect("midnight is a valid time");
+            Ok((next_day, midnight))
+        }
+        Err(error) => {
+            input.reset(&checkpoint);
+            Err(E::from_external_error(input, error))
+        }
+    }
+}
+
 /// Parses a date of the form YYYYMMDD.
 pub fn date<I, E>(input: &mut I) -> Result<Date, E>
+where
+    I: StreamIsPartial + Stream,
+    <I as Stream>::Token: AsChar,
+    E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
+{
+    let mut config = DefaultConfig::default();
+    date_with_config(input, &mut config)
+}
+
+/// Parses a date of the form YYYYMMDD with the given [`Config`], which handles dates that do not
+/// exist.
+pub fn date_with_config<I, E>(input: &mut I, config: &mut impl Config) -> Result<Date, E>
 where
     I: StreamIsPartial + Stream,
     <I as Stream>::Token: AsChar,
@@ -919,23 +1052,18 @@ where
     let m = Month::new(month);
     let d = Day::new(day);
 
-    match (y, m, d) {
-        (Ok(y), Ok(m), Ok(d)) => match Date::new(y, m, d) {
-            Ok(date) => Ok(date),
-            Err(_) => {
-                input.reset(&checkpoint);
-                Err(E::from_external_error(
-                    input,
-                    CalendarParseError::InvalidDate(InvalidDateError { year, month, day }),
-                ))
-            }
-        },
-        _ => {
+    if let (Ok(y), Ok(m), Ok(d)) = (y, m, d)
+        && let Ok(date) = Date::new(y, m, d)
+    {
+        return Ok(date);
+    }
+
+    let error = InvalidDateError { year, month, day };
+    match config.handle_invalid_date(error) {
+        Ok(date) => Ok(date.unwrap_or_else(|| error.clamped())),
+        Err(error) => {
             input.reset(&checkpoint);
-            Err(E::from_external_error(
-                input,
-                CalendarParseError::InvalidDate(InvalidDateError { year, month, day }),
-            ))
+            Err(E::from_external_error(input, error))
         }
     }
 }
@@ -1185,16 +1313,10 @@ where
     let _ = ';'.parse_next(input)?;
     let lon = float_with_config(input, config)?;
 
-    if lat.abs() > 91.0 {
-        Err(E::from_external_error(
-            input,
-            CalendarParseError::InvalidGeo(InvalidGeoError::LatOutOfBounds(lat)),
-        ))
-    } else if lon.abs() > 181.0 {
-        Err(E::from_external_error(
-            input,
-            CalendarParseError::InvalidGeo(InvalidGeoError::LonOutOfBounds(lon)),
-        ))
+    if lat.abs() > 90.0 || lon.abs() > 180.0 {
+        config
+            .handle_geo_out_of_range(lat, lon)
+            .map_err(|e| E::from_external_error(input, e))
     } else {
         Ok(Geo { lat, lon })
     }
@@ -1372,8 +1494,6 @@ where
 
 #[cfg(test)]
 mod tests {
-    use strum::IntoEnumIterator;
-
     use crate::date;
     use crate::parser::escaped::{AsEscaped, Escaped};
 
@@ -22,19 +22,21 @@ use crate::{
         },
         property::{Prop, StaticProp, StructuredDataProp},
         rrule::RRule,
-        string::{CaselessStr, NameKind, TzId, Uid, Uri},
+        string::{CaselessStr, NameKind, ParamValue, TzId, Uid, Uri},
     },
     parser::{
         InputStream,
-        config::{Config, DefaultConfig},
-        error::CalendarParseError,
+        config::{Config, DefaultConfig, LineEnding},
+        error::{CalendarParseError, ComponentKind, InvalidDateError, ParseFloatError},
         parameter::parameter,
         primitive::{
             self, alarm_action, ascii_lower, binary, binary_with_config, bool_caseless,
-            class_value, color, completion_percentage, datetime, datetime_utc,
-            duration, geo_with_config, gregorian, integer, method, participant_type,
-            period, priority, proximity_value, request_status, resource_type, status, text,
-            text_seq, text_with_commas, time, time_transparency, tz_id, uid, uri, utc_offset, version,
+            class_value, color, completion_percentage, date_with_config, datetime,
+            datetime_or_date_with_config, datetime_utc_with_config, datetime_with_config, duration,
+            geo_with_config, gregorian, integer, method, participant_type, period,
+            period_with_config, priority, proximity_value, request_status, resource_type, status,
+            text, text_seq, text_with_commas, time, time_transparency, tz_id, uid, uri, utc_offset,
+            version,
         },
         rrule::rrule,
     },
@@ -251,6 +253,9 @@ where
 }
 
 /// Constructs a property parser from the given [`Config`].
+///
+/// If the config discards the property (see [`Config::handle_invalid_date`]), the parser returns
+/// [`CalendarParseError::InvalidDate`] after consuming the property.
 pub fn property_parser_from_config<I, E>(
     config: &mut impl Config,
 ) -> impl Parser<I, ParsedProp<I::Slice>, E> + '_
@@ -261,7 +266,144 @@ where
     <<I as Stream>::Slice as Stream>::Token: AsChar,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
-    move |input: &mut I| property_with_config(input, config)
+    move |input: &mut I| match tracked_property(input, config)? {
+        (prop, None) => Ok(prop),
+        (_, Some(error)) => Err(E::from_external_error(
+            input,
+            CalendarParseError::InvalidDate(error),
+        )),
+    }
+}
+
+/// Constructs a property parser from the given [`Config`], which returns `None` if the config
+/// discards the property.
+pub(crate) fn optional_property_parser_from_config<I, E>(
+    config: &mut impl Config,
+) -> impl Parser<I, Option<ParsedProp<I::Slice>>, E> + '_
+where
+    I: InputStream,
+    I::Token: AsChar + Clone,
+    I::Slice: AsBStr + Clone + PartialEq + SliceLen + Stream,
+    <<I as Stream>::Slice as Stream>::Token: AsChar,
+    E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
+{
+    move |input: &mut I| {
+        let (prop, skipped) = tracked_property(input, config)?;
+        Ok(skipped.is_none().then_some(prop))
+    }
+}
+
+/// Parses a property, and returns the invalid date for which the config discarded it (if any).
+fn tracked_property<I, E>(
+    input: &mut I,
+    config: &mut impl Config,
+) -> Result<(ParsedProp<I::Slice>, Option<InvalidDateError>), E>
+where
+    I: InputStream,
+    I::Token: AsChar + Clone,
+    I::Slice: AsBStr + Clone + PartialEq + SliceLen + Stream,
+    <<I as Stream>::Slice as Stream>::Token: AsChar,
+    E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
+{
+    let mut tracker = SkipTracker {
+        config,
+        skipped: None,
+    };
+    let prop = property_with_config(input, &mut tracker)?;
+    Ok((prop, tracker.skipped))
+}
+
+/// A [`Config`] which forwards to another, and records whether it discarded a property.
+struct SkipTracker<'a, C> {
+    config: &'a mut C,
+    skipped: Option<InvalidDateError>,
+}
+
+impl<C: Config> Config for SkipTracker<'_, C> {
+    fn line_ending(&self) -> LineEnding {
+        self.config.line_ending()
+    }
+
+    fn set_line_ending(&mut self, le: LineEnding) {
+        self.config.set_line_ending(le);
+    }
+
+    fn handle_duplicate_param<S>(
+        &mut self,
+        previous_value: &mut Vec<Box<ParamValue>>,
+        new_value: Vec<Box<ParamValue>>,
+    ) -> Result<(), CalendarParseError<S>> {
+        self.config
+            .handle_duplicate_param(previous_value, new_value)
+    }
+
+    fn handle_float_parse_failure<S>(
+        &mut self,
+        slice: &str,
+        error: ParseFloatError,
+    ) -> Result<f64, CalendarParseError<S>> {
+        self.config.handle_float_parse_failure(slice, error)
+    }
+
+    fn handle_unescaped_comma<S>(&mut self, prop: StaticProp) -> Result<(), CalendarParseError<S>> {
+        self.config.handle_unescaped_comma(prop)
+    }
+
+    fn handle_missing_version<S>(&mut self) -> Result<(), CalendarParseError<S>> {
+        self.config.handle_missing_version()
+    }
+
+    fn handle_duplicate_property<S>(
+        &mut self,
+        prop: StaticProp,
+        component: ComponentKind<S>,
+    ) -> Result<(), CalendarParseError<S>> {
+        self.config.handle_duplicate_property(prop, component)
+    }
+
+    fn handle_unknown_value_type<S>(
+        &mut self,
+        prop: StaticProp,
+        value_type: &str,
+    ) -> Result<(), CalendarParseError<S>> {
+        self.config.handle_unknown_value_type(prop, value_type)
+    }
+
+    fn handle_end_of_day_time<S>(&mut self, date: Date) -> Result<(), CalendarParseError<S>> {
+        self.config.handle_end_of_day_time(date)
+    }
+
+    fn handle_geo_out_of_range<S>(
+        &mut self,
+        lat: f64,
+        lon: f64,
+    ) -> Result<Geo, CalendarParseError<S>> {
+        self.config.handle_geo_out_of_range(lat, lon)
+    }
+
+    fn handle_invalid_date<S>(
+        &mut self,
+        error: InvalidDateError,
+    ) -> Result<Option<Date>, CalendarParseError<S>> {
+        let result = self.config.handle_invalid_date(error);
+        if let Ok(None) = result {
+            self.skipped.get_or_insert(error);
+        }
+        result
+    }
+}
+
+/// Returns `true` iff the TEXT value `text` contains a comma which is not escaped.
+fn has_unescaped_comma(text: &str) -> bool {
+    let mut escaped = false;
+    for c in text.chars() {
+        match c {
+            ',' if !escaped => return true,
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    false
 }
 
 /// Implements property parsing with a given config.
@@ -277,7 +419,7 @@ where
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
     // parse name
-    let prop_name = property_name.parse_next(input)?;
+    let (prop_name, name_slice) = property_name.with_taken().parse_next(input)?;
 
     // parse parameters
     let (params, value_type) = {
@@ -346,6 +488,21 @@ where
                 value,
             }))
         }
+        PropName::Known(name) if matches!(value_type, Some(Token::Unknown(_))) => {
+            let Some(Token::Unknown(vt)) = value_type else {
+                unreachable!()
+            };
+            config
+                .handle_unknown_value_type(name, &vt)
+                .map_err(|e| E::from_external_error(input, e))?;
+
+            Ok(ParsedProp::Unknown(UnknownProp {
+                name: name_slice,
+                kind: NameKind::Iana,
+                params,
+                value: Value::Text(text_with_commas.parse_next(input)?.into_string()),
+            }))
+        }
         PropName::Known(name) => {
             macro_rules! trivial {
                 ($parser:expr) => {{
@@ -440,12 +597,14 @@ where
                 StaticProp::ExDate => match &value_type {
                     None | Some(Token::Known(ValueType::DateTime)) => {
                         let dates: Vec<DateTime<_>> =
-                            separated(1.., datetime, ',').parse_next(input)?;
+                            separated(1.., |i: &mut I| datetime_with_config(i, config), ',')
+                                .parse_next(input)?;
                         PropValue::ExDateSeq(ExDateSeq::DateTime(dates), params)
                     }
                     Some(Token::Known(ValueType::Date)) => {
                         let dates: Vec<_> =
-                            separated(1.., primitive::date, ',').parse_next(input)?;
+                            separated(1.., |i: &mut I| date_with_config(i, config), ',')
+                                .parse_next(input)?;
                         PropValue::ExDateSeq(ExDateSeq::Date(dates), params)
                     }
                     Some(_) => {
@@ -458,7 +617,8 @@ where
                 StaticProp::RDate => match &value_type {
                     None | Some(Token::Known(ValueType::DateTime)) => {
                         let dates: Vec<DateTime<_>> =
-                            separated(1.., datetime, ',').parse_next(input)?;
+                            separated(1.., |i: &mut I| datetime_with_config(i, config), ',')
+                                .parse_next(input)?;
                         PropValue::RDateSeq(Prop {
                             value: RDateSeq::DateTime(dates),
                             params,
@@ -466,7 +626,8 @@ where
                     }
                     Some(Token::Known(ValueType::Date)) => {
                         let dates: Vec<_> =
-                            separated(1.., primitive::date, ',').parse_next(input)?;
+                            separated(1.., |i: &mut I| date_with_config(i, config), ',')
+                                .parse_next(input)?;
                         PropValue::RDateSeq(Prop {
                             value: RDateSeq::Date(dates),
                             params,
@@ -474,7 +635,8 @@ where
                     }
                     Some(Token::Known(ValueType::Period)) => {
                         let periods: Vec<Period> =
-                            separated(1.., period, ',').parse_next(input)?;
+                            separated(1.., |i: &mut I| period_with_config(i, config), ',')
+                                .parse_next(input)?;
                         PropValue::RDateSeq(Prop {
                             value: RDateSeq::Period(periods),
                             params,
@@ -496,7 +658,7 @@ where
                     }
                     Some(Token::Known(ValueType::DateTime)) => {
                         PropValue::Trigger(Prop {
-                            value: TriggerValue::DateTime(datetime_utc.parse_next(input)?),
+                            value: TriggerValue::DateTime(datetime_utc_with_config(input, config)?),
                             params,
                         })
                     }
@@ -651,8 +813,16 @@ where
                 | StaticProp::Contact
                 | StaticProp::Name => {
                     check_vt!(Text);
+                    let (value, raw) = text_with_commas.with_taken().parse_next(input)?;
+                    let raw =
+                        I::try_into_str(&raw).map_err(|e| E::from_external_error(input, e))?;
+                    if has_unescaped_comma(raw.as_ref()) {
+                        config
+                            .handle_unescaped_comma(name)
+                            .map_err(|e| E::from_external_error(input, e))?;
+                    }
                     PropValue::Text(Prop {
-                        value: text_with_commas.parse_next(input)?.into_string(),
+                        value: value.into_string(),
                         params,
                     })
                 }
@@ -719,14 +889,14 @@ where
                     match &value_type {
                         None | Some(Token::Known(ValueType::DateTime)) => {
                             PropValue::DateTimeUtc(Prop {
-                                value: datetime_utc.parse_next(input)?,
+                                value: datetime_utc_with_config(input, config)?,
                                 params,
                             })
                         }
                         Some(Token::Known(ValueType::Date)) => {
                             PropValue::DateTimeUtc(Prop {
                                 value: date_to_midnight_utc(
-                                    primitive::date.parse_next(input)?,
+                                    date_with_config(input, config)?,
                                 ),
                                 params,
                             })
@@ -746,14 +916,16 @@ where
                     match &value_type {
                         Some(Token::Known(ValueType::DateTime)) => {
                             PropValue::DateTimeOrDate(Prop {
-                                value: DateTimeOrDate::DateTime(datetime.parse_next(input)?),
+                                value: DateTimeOrDate::DateTime(datetime_with_config(
+                                    input, config,
+                                )?),
                                 params,
                             })
                         }
                         Some(Token::Known(ValueType::Date)) => {
                             PropValue::DateTimeOrDate(Prop {
                                 value: DateTimeOrDate::Date(
-                                    primitive::date.parse_next(input)?,
+                                    date_with_config(input, config)?,
                                 ),
                                 params,
                             })
@@ -762,7 +934,7 @@ where
                             // No VALUE parameter: try datetime first, fall back to date.
                             // Real-world calendars often omit VALUE=DATE even for date-only values.
                             PropValue::DateTimeOrDate(Prop {
-                                value: primitive::datetime_or_date.parse_next(input)?,
+                                value: datetime_or_date_with_config(input, config)?,
                                 params,
                             })
                         }
@@ -784,7 +956,8 @@ where
                 StaticProp::FreeBusy => {
                     check_vt!(Period);
                     let periods: Vec<Period> =
-                        separated(1.., period, ',').parse_next(input)?;
+                        separated(1.., |i: &mut I| period_with_config(i, config), ',')
+                            .parse_next(input)?;
                     PropValue::FreeBusyPeriods(Prop {
                         value: periods,
                         params,
@@ -917,14 +1090,14 @@ where
                     match &value_type {
                         None | Some(Token::Known(ValueType::DateTime)) => {
                             PropValue::DateTimeUtc(Prop {
-                                value: datetime_utc.parse_next(input)?,
+                                value: datetime_utc_with_config(input, config)?,
                                 params,
                             })
                         }
                         Some(Token::Known(ValueType::Date)) => {
                             PropValue::DateTimeUtc(Prop {
                                 value: date_to_midnight_utc(
-                                    primitive::date.parse_next(input)?,
+                                    date_with_config(input, config)?,
                                 ),
                                 params,
                             })
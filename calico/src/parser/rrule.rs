@@ -8,22 +8,23 @@ use winnow::{
     combinator::{alt, opt, preceded, separated, terminated},
     error::{FromExternalError, ParserError},
     stream::{AsBStr, Accumulate, AsChar, Compare, Stream, StreamIsPartial},
-    token::any,
+    token::{any, take_while},
 };
 
 use crate::{
     model::{
-        primitive::{Month, Weekday},
+        primitive::{Month, Token, Weekday},
         rrule::{
             ByMonthDayRule, ByPeriodDayRules, ByRuleName, CoreByRules, Freq, FreqByRules, Hour,
             HourSet, Interval, Minute, MinuteSet, MonthDay, MonthDaySet, MonthDaySetIndex,
-            MonthSet, Part, PartName, RRule, Second, SecondSet, Termination, WeekNoSet,
-            WeekNoSetIndex, WeekdayNum, YearDayNum, YearlyByRules, weekday_num_set::WeekdayNumSet,
+            MonthSet, Part, PartName, RRule, Rscale, Second, SecondSet, Skip, Termination,
+            WeekNoSet, WeekNoSetIndex, WeekdayNum, XPart, YearDayNum, YearlyByRules,
+            weekday_num_set::WeekdayNumSet,
         },
     },
     parser::{
         InputStream,
-        primitive::{digit, iso_week_index, lz_dec_uint, sign},
+        primitive::{digit, iso_week_index, lz_dec_uint, name as iana_token, sign},
     },
 };
 
@@ -79,6 +80,9 @@ where
         interval: Option<Interval>,
         termination: Option<Termination>,
         week_start: Option<Weekday>,
+        rscale: Option<Token<Rscale, Box<str>>>,
+        skip: Option<Skip>,
+        extras: Vec<XPart>,
     }
 
     impl State {
@@ -238,6 +242,30 @@ where
                         Ok(())
                     }
                 },
+                Part::RScale(rscale) => match self.rscale {
+                    Some(_) => Err(E::from_external_error(
+                        input,
+                        CalendarParseError::DuplicateRRulePart(part_name),
+                    )),
+                    None => {
+                        self.rscale = Some(rscale);
+                        Ok(())
+                    }
+                },
+                Part::Skip(skip) => match self.skip {
+                    Some(_) => Err(E::from_external_error(
+                        input,
+                        CalendarParseError::DuplicateRRulePart(part_name),
+                    )),
+                    None => {
+                        self.skip = Some(skip);
+                        Ok(())
+                    }
+                },
+                Part::Extension(part) => {
+                    self.extras.push(part);
+                    Ok(())
+                }
             }
         }
 
@@ -260,6 +288,9 @@ where
                 interval,
                 termination,
                 week_start,
+                rscale,
+                skip,
+                extras,
             } = self;
 
             // collect the BYxxx rules that are always admissible
@@ -389,6 +420,9 @@ where
                 interval,
                 termination,
                 week_start,
+                rscale,
+                skip,
+                extras,
             })
         }
     }
@@ -417,7 +451,10 @@ where
     I::Token: AsChar + Clone,
     E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
 {
-    let name = terminated(part_name, '=').parse_next(input)?;
+    let Some(name) = opt(terminated(part_name, '=')).parse_next(input)? else {
+        let part = x_part.parse_next(input)?;
+        return Ok(Part::Extension(part));
+    };
 
     Ok(match name {
         PartName::Freq => {
@@ -476,7 +513,37 @@ where
             let day = weekday.parse_next(input)?;
             Part::WkSt(day)
         }
+        PartName::RScale => {
+            let rscale = iana_token.parse_next(input)?;
+            Part::RScale(match rscale.as_str().parse() {
+                Ok(rscale) => Token::Known(rscale),
+                Err(_) => Token::Unknown(rscale.as_str().into()),
+            })
+        }
+        PartName::Skip => {
+            let skip = skip.parse_next(input)?;
+            Part::Skip(skip)
+        }
+        PartName::Extension => unreachable!("part_name only parses standard part names"),
+    })
+}
+
+/// Parses an [`XPart`], which is a part with a nonstandard name such as `X-EXAMPLE=1`.
+pub fn x_part<I, E>(input: &mut I) -> Result<XPart, E>
+where
+    I: InputStream,
+    I::Token: AsChar,
+    E: ParserError<I> + FromExternalError<I, CalendarParseError<I::Slice>>,
+{
+    let name = terminated(iana_token, '=').parse_next(input)?;
+    let value = take_while(0.., |t: I::Token| {
+        let c = t.as_char();
+        c != ';' && !c.is_control()
     })
+    .parse_next(input)?;
+
+    let value = I::try_into_string(&value).map_err(|e| E::from_external_error(input, e))?;
+    XPart::new(name, value).map_err(|_| E::from_input(input))
 }
 
 /// Parses a [`PartName`].
@@ -500,6 +567,22 @@ where
         Caseless("UNTIL").value(PartName::Until),
         Caseless("FREQ").value(PartName::Freq),
         Caseless("WKST").value(PartName::WkSt),
+        Caseless("RSCALE").value(PartName::RScale),
+        Caseless("SKIP").value(PartName::Skip),
+    ))
+    .parse_next(input)
+}
+
+/// Parses a [`Skip`].
+pub fn skip<I, E>(input: &mut I) -> Result<Skip, E>
+where
+    I: StreamIsPartial + Stream + Compare<Caseless<&'static str>>,
+    E: ParserError<I>,
+{
+    alt((
+        Caseless("BACKWARD").value(Skip::Backward),
+        Caseless("FORWARD").value(Skip::Forward),
+        Caseless("OMIT").value(Skip::Omit),
     ))
     .parse_next(input)
 }
@@ -757,6 +840,9 @@ mod tests {
             interval,
             termination,
             week_start,
+            rscale,
+            skip,
+            extras,
         } = rule;
 
         assert_eq!(
@@ -801,6 +887,9 @@ mod tests {
         assert!(interval.is_none());
         assert!(termination.is_none());
         assert!(week_start.is_none());
+        assert!(rscale.is_none());
+        assert!(skip.is_none());
+        assert!(extras.is_empty());
     }
 
     #[test]
@@ -816,6 +905,9 @@ mod tests {
             interval,
             termination,
             week_start,
+            rscale,
+            skip,
+            extras,
         } = rule;
 
         assert_eq!(freq, FreqByRules::Yearly(YearlyByRules::default()),);
@@ -850,6 +942,66 @@ mod tests {
         assert_eq!(interval, Some(Interval::new(2.try_into().unwrap())));
         assert!(termination.is_none());
         assert!(week_start.is_none());
+        assert!(rscale.is_none());
+        assert!(skip.is_none());
+        assert!(extras.is_empty());
+    }
+
+    #[test]
+    fn rrule_parser_rfc_7529() {
+        // input is from RFC 7529, section 4.3.1
+        let input = "RSCALE=GREGORIAN;FREQ=YEARLY;SKIP=FORWARD";
+        let (tail, rule) = rrule::<_, ()>.parse_peek(input).unwrap();
+        assert!(tail.is_empty());
+        assert_eq!(rule.freq, FreqByRules::Yearly(YearlyByRules::default()));
+        assert_eq!(rule.rscale, Some(Token::Known(Rscale::Gregorian)));
+        assert_eq!(rule.skip, Some(Skip::Forward));
+
+        let (_, rule) = rrule::<_, ()>
+            .parse_peek("FREQ=MONTHLY;rscale=x-lunar")
+            .unwrap();
+        assert_eq!(rule.rscale, Some(Token::Unknown("x-lunar".into())));
+        assert_eq!(rule.skip, None);
+
+        assert!(
+            rrule::<_, ()>
+                .parse_peek("FREQ=MONTHLY;SKIP=OMIT;SKIP=BACKWARD")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rrule_parser_extension_parts() {
+        use crate::serializer::WriteIcal;
+
+        let input = "FREQ=WEEKLY;X-EXAMPLE=1,2;COUNT=3;X-NAME=a:b;X-EXAMPLE=";
+        let (tail, rule) = rrule::<_, ()>.parse_peek(input).unwrap();
+        assert!(tail.is_empty());
+        assert_eq!(rule.freq, FreqByRules::Weekly);
+        assert_eq!(rule.termination, Some(Termination::Count(3)));
+
+        let extras: Vec<_> = rule
+            .extras
+            .iter()
+            .map(|part| (part.name().as_str(), part.value()))
+            .collect();
+        assert_eq!(
+            extras,
+            [("X-EXAMPLE", "1,2"), ("X-NAME", "a:b"), ("X-EXAMPLE", "")]
+        );
+
+        assert_eq!(
+            rule.to_ical_string(),
+            "FREQ=WEEKLY;COUNT=3;X-EXAMPLE=1,2;X-NAME=a:b;X-EXAMPLE="
+        );
+
+        // a standard name with an invalid value is not an extension part
+        let (tail, rule) = rrule::<_, ()>.parse_peek("FREQ=WEEKLY;COUNT=x").unwrap();
+        assert!(!tail.is_empty());
+        assert!(rule.extras.is_empty());
+
+        let (_, rule) = rrule::<_, ()>.parse_peek("FREQ=WEEKLY;BYMONTHX=1").unwrap();
+        assert_eq!(rule.extras[0].name().as_str(), "BYMONTHX");
     }
 
     #[test]
@@ -0,0 +1,447 @@
+//! Incremental parsing of iCalendar streams from a [`BufRead`].
+//!
+//! [`Calendar::parse`] requires the whole input in memory, which is impractical for very large
+//! exports. A [`StreamParser`] instead reads its input line by line, and yields each immediate
+//! subcomponent of a calendar as soon as its `END` line has been read, so that only one component
+//! is held in memory at a time.
+//!
+//! ```
+//! use calico::parser::stream::{StreamItem, StreamParser};
+//!
+//! let input = "BEGIN:VCALENDAR\r\n\
+//!              VERSION:2.0\r\n\
+//!              PRODID:-//Example//EN\r\n\
+//!              BEGIN:VEVENT\r\n\
+//!              UID:1@example.com\r\n\
+//!              DTSTAMP:20240101T000000Z\r\n\
+//!              DTSTART:20240101T090000Z\r\n\
+//!              END:VEVENT\r\n\
+//!              END:VCALENDAR\r\n";
+//!
+//! let mut components = 0;
+//! for item in StreamParser::new(input.as_bytes()) {
+//!     match item.unwrap() {
+//!         StreamItem::Component(_) => components += 1,
+//!         StreamItem::Calendar(calendar) => assert!(calendar.components().is_empty()),
+//!     }
+//! }
+//! assert_eq!(components, 1);
+//! ```
+
+use std::io::{self, BufRead};
+
+use crate::{
+    model::component::{Calendar, CalendarComponent},
+    parser::{
+        component::{calendar_component_with_config, calendar_with_config},
+        config::{DefaultConfig, LineEnding},
+        error::ParseError,
+        escaped::AsEscaped,
+    },
+};
+
+/// An item yielded by a [`StreamParser`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamItem {
+    /// An immediate subcomponent of the current calendar.
+    Component(CalendarComponent),
+    /// The properties of a calendar, yielded when its `END:VCALENDAR` line is read. The
+    /// components of the calendar have already been yielded, so it has none.
+    Calendar(Calendar),
+}
+
+/// An error returned by a [`StreamParser`].
+#[derive(Debug)]
+pub enum StreamError {
+    /// The underlying reader failed.
+    Io(io::Error),
+    /// The input is not a valid iCalendar stream. The offset of the error is relative to the
+    /// start of the input.
+    Parse(ParseError),
+}
+
+impl std::fmt::Display for StreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read iCalendar stream: {error}"),
+            Self::Parse(error) => error.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(error: ParseError) -> Self {
+        Self::Parse(error)
+    }
+}
+
+/// A pull-based parser over an iCalendar stream, yielding a [`StreamItem`] for each component
+/// and calendar as it completes.
+///
+/// Lines are read from the underlying reader one at a time, so content lines may be folded
+/// across the boundaries of its buffer, and memory use is bounded by the size of the largest
+/// component and of the properties of a calendar rather than by the size of the input. The
+/// line ending convention is detected from the first line. Iteration stops after the first
+/// error.
+pub struct StreamParser<R> {
+    reader: R,
+    /// The byte offset of the next line.
+    offset: usize,
+    /// The physical line following the last line read, which is empty at the end of the input.
+    lookahead: Option<Vec<u8>>,
+    line_ending: Option<LineEnding>,
+    calendar: Option<Header>,
+    done: bool,
+}
+
+/// The calendar-level lines of the current calendar.
+struct Header {
+    lines: Vec<u8>,
+    /// The position in `lines` and the offset in the input of the start of each line.
+    offsets: Vec<(usize, usize)>,
+}
+
+impl Header {
+    fn push(&mut self, line: Line) {
+        self.offsets.push((self.lines.len(), line.offset));
+        self.lines.extend(line.raw);
+    }
+
+    /// Converts a position in `lines` into an offset in the input.
+    fn input_offset(&self, position: usize) -> usize {
+        let index = self
+            .offsets
+            .partition_point(|(start, _)| *start <= position);
+        let (start, offset) = self.offsets[index.saturating_sub(1)];
+        offset + (position - start)
+    }
+}
+
+/// A logical content line, including any folds and its line terminator.
+struct Line {
+    offset: usize,
+    raw: Vec<u8>,
+}
+
+/// The meaning of a content line to the structure of the stream.
+enum LineKind {
+    Blank,
+    Begin(String),
+    End(String),
+    Other,
+}
+
+impl<R: BufRead> StreamParser<R> {
+    /// Creates a parser reading from `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            offset: 0,
+            lookahead: None,
+            line_ending: None,
+            calendar: None,
+            done: false,
+        }
+    }
+
+    /// Returns the number of bytes consumed so far.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Consumes the parser and returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn next_item(&mut self) -> Result<Option<StreamItem>, StreamError> {
+        loop {
+            let Some(line) = self.next_line()? else {
+                return match self.calendar {
+                    Some(_) => Err(ParseError::at(self.offset).into()),
+                    None => Ok(None),
+                };
+            };
+
+            match (line_kind(&line.raw), &mut self.calendar) {
+                (LineKind::Blank, _) => {}
+                (LineKind::Begin(name), None) if name == "VCALENDAR" => {
+                    let mut header = Header {
+                        lines: Vec::new(),
+                        offsets: Vec::new(),
+                    };
+                    header.push(line);
+                    self.calendar = Some(header);
+                }
+                (_, None) => return Err(ParseError::at(line.offset).into()),
+                (LineKind::Begin(_), Some(_)) => {
+                    let component = self.read_component(line)?;
+                    return Ok(Some(StreamItem::Component(component)));
+                }
+                (LineKind::End(name), Some(header)) if name == "VCALENDAR" => {
+                    header.push(line);
+                    let calendar = self.parse_header()?;
+                    return Ok(Some(StreamItem::Calendar(calendar)));
+                }
+                (_, Some(header)) => header.push(line),
+            }
+        }
+    }
+
+    /// Reads the rest of the component whose `BEGIN` line is `first`, and parses it.
+    fn read_component(&mut self, first: Line) -> Result<CalendarComponent, StreamError> {
+        let start = first.offset;
+        let mut lines = first.raw;
+        let mut depth = 1usize;
+        while depth > 0 {
+            let Some(line) = self.next_line()? else {
+                return Err(ParseError::at(self.offset).into());
+            };
+            match line_kind(&line.raw) {
+                LineKind::Begin(_) => depth += 1,
+                LineKind::End(_) => depth -= 1,
+                LineKind::Blank | LineKind::Other => {}
+            }
+            lines.extend(line.raw);
+        }
+
+        let mut config = DefaultConfig::new(self.line_ending());
+        let mut input = lines.as_escaped();
+        calendar_component_with_config::<_, ParseError>(&mut input, &mut config).map_err(|e| {
+            let position = e.with_total_len(lines.len()).offset();
+            ParseError::at(start + position).into()
+        })
+    }
+
+    /// Parses the lines of the current calendar, and ends it.
+    fn parse_header(&mut self) -> Result<Calendar, StreamError> {
+        let header = self.calendar.take().expect("a calendar is open");
+        let mut config = DefaultConfig::new(self.line_ending());
+        let mut input = header.lines.as_escaped();
+        calendar_with_config::<_, ParseError>(&mut input, &mut config).map_err(|e| {
+            let position = e.with_total_len(header.lines.len()).offset();
+            ParseError::at(header.input_offset(position)).into()
+        })
+    }
+
+    fn line_ending(&self) -> LineEnding {
+        self.line_ending.unwrap_or(LineEnding::Crlf)
+    }
+
+    /// Reads the next logical line, joining any continuation lines which follow it.
+    fn next_line(&mut self) -> io::Result<Option<Line>> {
+        let mut raw = match self.lookahead.take() {
+            Some(line) => line,
+            None => self.read_physical_line()?,
+        };
+        if raw.is_empty() {
+            self.lookahead = Some(raw);
+            return Ok(None);
+        }
+
+        if self.offset == 0 && raw.starts_with(BOM) {
+            raw.drain(..BOM.len());
+            self.offset = BOM.len();
+        }
+        if self.line_ending.is_none() {
+            self.line_ending = Some(LineEnding::detect(&raw));
+        }
+
+        loop {
+            let next = self.read_physical_line()?;
+            if next.first().is_some_and(|b| *b == b' ' || *b == b'\t') {
+                raw.extend(next);
+            } else {
+                self.lookahead = Some(next);
+                break;
+            }
+        }
+
+        let offset = self.offset;
+        self.offset += raw.len();
+        Ok(Some(Line { offset, raw }))
+    }
+
+    /// Reads a physical line, or returns an empty vector at the end of the input.
+    fn read_physical_line(&mut self) -> io::Result<Vec<u8>> {
+        let mut line = Vec::new();
+        self.reader.read_until(b'\n', &mut line)?;
+        Ok(line)
+    }
+}
+
+impl<R: BufRead> Iterator for StreamParser<R> {
+    type Item = Result<StreamItem, StreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let item = self.next_item().transpose();
+        if !matches!(item, Some(Ok(_))) {
+            self.done = true;
+        }
+        item
+    }
+}
+
+/// The UTF-8 byte order mark.
+const BOM: &[u8] = "\u{FEFF}".as_bytes();
+
+/// Classifies a logical line by its unfolded content.
+fn line_kind(raw: &[u8]) -> LineKind {
+    let mut line = Vec::with_capacity(raw.len());
+    let mut bytes = raw.iter().copied().peekable();
+    while let Some(byte) = bytes.next() {
+        match byte {
+            // folds are removed, and the line terminator is dropped
+            b'\r' | b'\n' => {
+                if byte == b'\r' {
+                    bytes.next_if_eq(&b'\n');
+                }
+                bytes.next_if(|b| *b == b' ' || *b == b'\t');
+            }
+            _ => line.push(byte),
+        }
+    }
+
+    let name = |prefix: &str| {
+        let (head, tail) = line.split_at_checked(prefix.len())?;
+        let name = str::from_utf8(tail).ok()?;
+        head.eq_ignore_ascii_case(prefix.as_bytes())
+            .then(|| name.to_ascii_uppercase())
+    };
+
+    if line.is_empty() {
+        LineKind::Blank
+    } else if let Some(name) = name("BEGIN:") {
+        LineKind::Begin(name)
+    } else if let Some(name) = name("END:") {
+        LineKind::End(name)
+    } else {
+        LineKind::Other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use super::*;
+
+    const INPUT: &str = "BEGIN:VCALENDAR\r\n\
+                         VERSION:2.0\r\n\
+                         PRODID:-//Test//Test//EN\r\n\
+                         BEGIN:VEVENT\r\n\
+                         UID:first@example.com\r\n\
+                         DTSTAMP:20070423T123432Z\r\n\
+                         DTSTART:20070628T090000Z\r\n\
+                         SUMMARY:A summary which is long enough that it has been folded\r\n  across two lines\r\n\
+                         BEGIN:VALARM\r\n\
+                         ACTION:DISPLAY\r\n\
+                         DESCRIPTION:Reminder\r\n\
+                         TRIGGER:-PT15M\r\n\
+                         END:VALARM\r\n\
+                         END:VEVENT\r\n\
+                         BEGIN:VTODO\r\n\
+                         UID:second@example.com\r\n\
+                         DTSTAMP:20070423T123432Z\r\n\
+                         END:VTODO\r\n\
+                         NAME:Streamed\r\n\
+                         END:VCALENDAR\r\n";
+
+    fn collect<R: BufRead>(reader: R) -> Result<Vec<StreamItem>, StreamError> {
+        StreamParser::new(reader).collect()
+    }
+
+    fn components(calendar: &Calendar) -> Vec<StreamItem> {
+        let mut items: Vec<_> = calendar
+            .components()
+            .iter()
+            .cloned()
+            .map(StreamItem::Component)
+            .collect();
+        let mut calendar = calendar.clone();
+        calendar.set_components(Vec::new());
+        items.push(StreamItem::Calendar(calendar));
+        items
+    }
+
+    #[test]
+    fn stream_matches_parse() {
+        let expected: Vec<_> = Calendar::parse(INPUT)
+            .unwrap()
+            .iter()
+            .flat_map(components)
+            .collect();
+        assert_eq!(expected.len(), 3);
+
+        assert_eq!(collect(INPUT.as_bytes()).unwrap(), expected);
+
+        // a one-byte buffer splits every line, including the fold, across buffer boundaries
+        let reader = BufReader::with_capacity(1, INPUT.as_bytes());
+        assert_eq!(collect(reader).unwrap(), expected);
+
+        let twice = format!("\u{FEFF}{INPUT}\r\n{INPUT}");
+        let items = collect(twice.as_bytes()).unwrap();
+        assert_eq!(items.len(), 6);
+        assert_eq!(items[3..], expected);
+
+        let lf = INPUT.replace("\r\n", "\n");
+        assert_eq!(collect(lf.as_bytes()).unwrap(), expected);
+    }
+
+    #[test]
+    fn stream_yields_components_as_they_complete() {
+        let end = INPUT.find("BEGIN:VTODO").unwrap();
+        let mut parser = StreamParser::new(INPUT.as_bytes());
+        let Some(Ok(StreamItem::Component(CalendarComponent::Event(event)))) = parser.next() else {
+            panic!("expected an event");
+        };
+        assert_eq!(event.uid().unwrap().value.as_str(), "first@example.com");
+        assert_eq!(parser.offset(), end);
+    }
+
+    #[test]
+    fn stream_errors() {
+        let offset = |input: &str| match collect(input.as_bytes()) {
+            Err(StreamError::Parse(error)) => error.offset(),
+            other => panic!("expected a parse error, got {other:?}"),
+        };
+
+        // truncated in a component and at the end of a calendar
+        let truncated = &INPUT[..INPUT.find("END:VALARM").unwrap()];
+        assert_eq!(offset(truncated), truncated.len());
+        let truncated = &INPUT[..INPUT.find("END:VCALENDAR").unwrap()];
+        assert_eq!(offset(truncated), truncated.len());
+
+        // an invalid property in a component, and in the calendar itself
+        let invalid = INPUT.replace("DTSTART:20070628T090000Z", "DTSTART:tomorrow");
+        let line = invalid.find("DTSTART").unwrap();
+        assert!((line..line + 16).contains(&offset(&invalid)));
+        let invalid = INPUT.replace("VERSION:2.0\r\n", "");
+        assert!(offset(&invalid) >= invalid.find("END:VCALENDAR").unwrap());
+
+        assert_eq!(offset("NAME:Stray\r\n"), 0);
+
+        let mut parser = StreamParser::new("BEGIN:VEVENT\r\n".as_bytes());
+        assert!(matches!(parser.next(), Some(Err(_))));
+        assert!(parser.next().is_none());
+    }
+}
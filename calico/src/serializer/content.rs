@@ -0,0 +1,411 @@
+//! Structured content lines shared by the jCal and xCal writers.
+//!
+//! RFC 7265 §3 and RFC 6321 §3 define jCal and xCal by a mapping from the iCalendar text format,
+//! so both writers start from the output of [`WriteIcal`]: each content line is split into its
+//! name, parameters, and value, and the value is converted according to its value type, which
+//! is given by the VALUE parameter or the default value type of the property.
+//!
+//! [`write_ical`] reverses the mapping, so that a component read from jCal can be written as
+//! iCalendar text.
+
+use std::fmt;
+
+use super::{WriteIcal, escape_text, parameter::write_maybe_quoted, write_crlf};
+use crate::model::component::Calendar;
+
+/// A component with its properties and subcomponents.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Component {
+    /// The lowercase name of the component.
+    pub name: String,
+    pub properties: Vec<Property>,
+    pub components: Vec<Component>,
+}
+
+/// A property with its parameters and values.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Property {
+    /// The lowercase name of the property.
+    pub name: String,
+    /// The lowercase names and the values of the parameters, excluding VALUE.
+    pub params: Vec<(String, Vec<String>)>,
+    /// The lowercase name of the value type, or `unknown` for an extension property whose type
+    /// is not known.
+    pub value_type: String,
+    pub values: Vec<Value>,
+}
+
+/// A property value, converted to the representation shared by jCal and xCal.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    /// A string, including dates, times, and durations in their extended format.
+    String(String),
+    /// An INTEGER or FLOAT literal.
+    Number(String),
+    Boolean(bool),
+    /// The latitude and longitude of a GEO value, as FLOAT literals.
+    Geo(String, String),
+    /// The code, description, and optional data of a REQUEST-STATUS value.
+    RequestStatus(Vec<String>),
+    /// The start of a PERIOD value, with its end date-time (`Ok`) or duration (`Err`).
+    Period(String, Result<String, String>),
+    /// The lowercase names and the values of the parts of a RECUR value.
+    Recur(Vec<(String, Vec<Value>)>),
+}
+
+/// Returns the structured form of `calendar`.
+pub(crate) fn calendar(calendar: &Calendar) -> Component {
+    let text = calendar.to_ical_string();
+    let mut stack = vec![Component {
+        name: String::new(),
+        properties: Vec::new(),
+        components: Vec::new(),
+    }];
+
+    for line in text.split("\r\n").filter(|line| !line.is_empty()) {
+        if let Some(name) = line.strip_prefix("BEGIN:") {
+            stack.push(Component {
+                name: name.to_ascii_lowercase(),
+                properties: Vec::new(),
+                components: Vec::new(),
+            });
+        } else if line.starts_with("END:") && stack.len() > 1 {
+            let component = stack.pop().expect("the stack has at least two entries");
+            let parent = stack.last_mut().expect("the stack has at least one entry");
+            parent.components.push(component);
+        } else {
+            let parent = stack.last_mut().expect("the stack has at least one entry");
+            parent.properties.push(property(line));
+        }
+    }
+
+    let root = stack.swap_remove(0);
+    root.components
+        .into_iter()
+        .next()
+        .expect("a calendar is written as a VCALENDAR component")
+}
+
+/// Splits a content line written by [`WriteIcal`] into a [`Property`].
+fn property(line: &str) -> Property {
+    let name_end = line.find([';', ':']).unwrap_or(line.len());
+    let name = line[..name_end].to_ascii_uppercase();
+    let mut rest = &line[name_end..];
+
+    let mut value_type = None;
+    let mut params = Vec::new();
+    while let Some(tail) = rest.strip_prefix(';') {
+        let eq = tail.find('=').unwrap_or(tail.len());
+        let param = &tail[..eq];
+        rest = tail.get(eq + 1..).unwrap_or("");
+
+        let mut values = Vec::new();
+        loop {
+            let value;
+            if let Some(quoted) = rest.strip_prefix('"') {
+                let close = quoted.find('"').unwrap_or(quoted.len());
+                value = &quoted[..close];
+                rest = quoted.get(close + 1..).unwrap_or("");
+            } else {
+                let end = rest.find([',', ';', ':']).unwrap_or(rest.len());
+                value = &rest[..end];
+                rest = &rest[end..];
+            }
+
+            values.push(value.to_string());
+            match rest.strip_prefix(',') {
+                Some(tail) => rest = tail,
+                None => break,
+            }
+        }
+
+        if param.eq_ignore_ascii_case("VALUE") {
+            value_type = values.pop().map(|value| value.to_ascii_lowercase());
+        } else {
+            params.push((param.to_ascii_lowercase(), values));
+        }
+    }
+
+    let raw = rest.strip_prefix(':').unwrap_or(rest);
+    let value_type = value_type.unwrap_or_else(|| default_value_type(&name).to_string());
+    let values = values(&name, &value_type, raw);
+
+    Property {
+        name: name.to_ascii_lowercase(),
+        params,
+        value_type,
+        values,
+    }
+}
+
+/// Returns the default value type of the property `name` (RFC 5545 §3.8, RFC 7986 §5, and
+/// RFC 9073 §6), or `unknown` if the property is not known.
+fn default_value_type(name: &str) -> &'static str {
+    match name {
+        "DTSTAMP" | "DTSTART" | "DTEND" | "DUE" | "RECURRENCE-ID" | "EXDATE" | "RDATE"
+        | "CREATED" | "LAST-MODIFIED" | "COMPLETED" | "ACKNOWLEDGED" => "date-time",
+        "DURATION" | "REFRESH-INTERVAL" | "TRIGGER" => "duration",
+        "PRIORITY" | "SEQUENCE" | "PERCENT-COMPLETE" | "REPEAT" => "integer",
+        "GEO" => "float",
+        "TZOFFSETFROM" | "TZOFFSETTO" => "utc-offset",
+        "RRULE" | "EXRULE" => "recur",
+        "URL" | "TZURL" | "SOURCE" | "CONFERENCE" | "ATTACH" | "IMAGE" | "STRUCTURED-DATA" => "uri",
+        "ATTENDEE" | "ORGANIZER" | "CALENDAR-ADDRESS" => "cal-address",
+        "FREEBUSY" => "period",
+        "CALSCALE" | "METHOD" | "PRODID" | "VERSION" | "CATEGORIES" | "CLASS" | "COMMENT"
+        | "DESCRIPTION" | "LOCATION" | "RESOURCES" | "STATUS" | "SUMMARY" | "TRANSP" | "TZID"
+        | "TZNAME" | "CONTACT" | "RELATED-TO" | "UID" | "ACTION" | "REQUEST-STATUS" | "NAME"
+        | "COLOR" | "STYLED-DESCRIPTION" | "LOCATION-TYPE" | "PARTICIPANT-TYPE"
+        | "RESOURCE-TYPE" => "text",
+        _ => "unknown",
+    }
+}
+
+/// Converts the raw value of the property `name` with the given value type.
+fn values(name: &str, value_type: &str, raw: &str) -> Vec<Value> {
+    match name {
+        "GEO" => {
+            let (lat, lon) = raw.split_once(';').unwrap_or((raw, ""));
+            return vec![Value::Geo(lat.to_string(), lon.to_string())];
+        }
+        "REQUEST-STATUS" => {
+            let parts = split_unescaped(raw, ';').map(unescape).collect();
+            return vec![Value::RequestStatus(parts)];
+        }
+        _ => {}
+    }
+
+    let multiple = matches!(
+        name,
+        "CATEGORIES" | "RESOURCES" | "EXDATE" | "RDATE" | "FREEBUSY"
+    );
+    let items: Vec<&str> = if multiple {
+        split_unescaped(raw, ',').collect()
+    } else {
+        vec![raw]
+    };
+
+    items
+        .into_iter()
+        .map(|item| match value_type {
+            "text" => Value::String(unescape(item)),
+            "date" => Value::String(date(item)),
+            "date-time" => Value::String(date_time(item)),
+            "time" => Value::String(time(item)),
+            "utc-offset" => Value::String(utc_offset(item)),
+            "integer" | "float" => Value::Number(item.to_string()),
+            "boolean" => Value::Boolean(item.eq_ignore_ascii_case("TRUE")),
+            "period" => period(item),
+            "recur" => recur(item),
+            _ => Value::String(item.to_string()),
+        })
+        .collect()
+}
+
+/// Splits `s` at each occurrence of `separator` which is not escaped by a backslash.
+fn split_unescaped(s: &str, separator: char) -> impl Iterator<Item = &str> {
+    let mut escaped = false;
+    s.split(move |c| {
+        let split = c == separator && !escaped;
+        escaped = c == '\\' && !escaped;
+        split
+    })
+}
+
+/// Removes the escapes of a TEXT value (RFC 5545 §3.3.11).
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Converts a DATE from `YYYYMMDD` to `YYYY-MM-DD`.
+fn date(s: &str) -> String {
+    match (s.get(0..4), s.get(4..6), s.get(6..8)) {
+        (Some(y), Some(m), Some(d)) if s.len() == 8 => format!("{y}-{m}-{d}"),
+        _ => s.to_string(),
+    }
+}
+
+/// Converts a TIME from `HHMMSS` to `HH:MM:SS`, keeping a trailing `Z`.
+fn time(s: &str) -> String {
+    let (digits, utc) = match s.strip_suffix('Z') {
+        Some(digits) => (digits, "Z"),
+        None => (s, ""),
+    };
+
+    match (digits.get(0..2), digits.get(2..4), digits.get(4..6)) {
+        (Some(h), Some(m), Some(sec)) if digits.len() == 6 => format!("{h}:{m}:{sec}{utc}"),
+        _ => s.to_string(),
+    }
+}
+
+/// Converts a DATE-TIME from `YYYYMMDDTHHMMSS` to `YYYY-MM-DDTHH:MM:SS`, keeping a trailing `Z`.
+fn date_time(s: &str) -> String {
+    match s.split_once('T') {
+        Some((d, t)) => format!("{}T{}", date(d), time(t)),
+        None => date(s),
+    }
+}
+
+/// Converts a UTC-OFFSET from `+HHMM[SS]` to `+HH:MM[:SS]`.
+fn utc_offset(s: &str) -> String {
+    match (s.get(0..3), s.get(3..5), s.get(5..)) {
+        (Some(h), Some(m), Some("")) => format!("{h}:{m}"),
+        (Some(h), Some(m), Some(sec)) if sec.len() == 2 => format!("{h}:{m}:{sec}"),
+        _ => s.to_string(),
+    }
+}
+
+/// Converts a PERIOD, whose end is either a DATE-TIME or a DURATION.
+fn period(s: &str) -> Value {
+    let (start, end) = s.split_once('/').unwrap_or((s, ""));
+    let end = if end.starts_with(['+', '-', 'P']) {
+        Err(end.to_string())
+    } else {
+        Ok(date_time(end))
+    };
+    Value::Period(date_time(start), end)
+}
+
+/// Converts a RECUR value into its parts, whose values are numbers where the part is numeric.
+fn recur(s: &str) -> Value {
+    let parts = s
+        .split(';')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let (name, value) = part.split_once('=').unwrap_or((part, ""));
+            let name = name.to_ascii_lowercase();
+            let values = value
+                .split(',')
+                .map(|item| match name.as_str() {
+                    "until" => Value::String(date_time(item)),
+                    "freq" | "wkst" | "byday" | "rscale" | "skip" => Value::String(item.into()),
+                    _ if item.parse::<i64>().is_ok() => Value::Number(item.to_string()),
+                    _ => Value::String(item.to_string()),
+                })
+                .collect();
+            (name, values)
+        })
+        .collect();
+    Value::Recur(parts)
+}
+
+/// Writes a component as unfolded iCalendar text, with a VALUE parameter on each property whose
+/// value type is not the default one.
+pub(crate) fn write_ical<W: fmt::Write>(component: &Component, w: &mut W) -> fmt::Result {
+    let name = component.name.to_ascii_uppercase();
+    write!(w, "BEGIN:{name}")?;
+    write_crlf(w)?;
+    for property in &component.properties {
+        write_property(property, w)?;
+    }
+    for component in &component.components {
+        write_ical(component, w)?;
+    }
+    write!(w, "END:{name}")?;
+    write_crlf(w)
+}
+
+/// Writes a property as an unfolded content line.
+fn write_property<W: fmt::Write>(property: &Property, w: &mut W) -> fmt::Result {
+    let name = property.name.to_ascii_uppercase();
+    w.write_str(&name)?;
+
+    let value_type = property.value_type.as_str();
+    match (default_value_type(&name), value_type) {
+        (_, "unknown") | ("unknown", "text") => {}
+        (default, value_type) if default == value_type => {}
+        (_, value_type) => write!(w, ";VALUE={}", value_type.to_ascii_uppercase())?,
+    }
+
+    for (param, values) in &property.params {
+        write!(w, ";{}=", param.to_ascii_uppercase())?;
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                w.write_char(',')?;
+            }
+            write_maybe_quoted(value, w)?;
+        }
+    }
+
+    w.write_char(':')?;
+    for (i, value) in property.values.iter().enumerate() {
+        if i > 0 {
+            w.write_char(',')?;
+        }
+        write_value(value_type, value, w)?;
+    }
+    write_crlf(w)
+}
+
+/// Writes a value in the iCalendar representation of the given value type.
+fn write_value<W: fmt::Write>(value_type: &str, value: &Value, w: &mut W) -> fmt::Result {
+    match value {
+        Value::String(s) if value_type == "text" => escape_text(s, w),
+        Value::String(s) => w.write_str(&compact(value_type, s)),
+        Value::Number(n) => w.write_str(n),
+        Value::Boolean(b) => w.write_str(if *b { "TRUE" } else { "FALSE" }),
+        Value::Geo(lat, lon) => write!(w, "{lat};{lon}"),
+        Value::RequestStatus(parts) => {
+            for (i, part) in parts.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(';')?;
+                }
+                escape_text(part, w)?;
+            }
+            Ok(())
+        }
+        Value::Period(start, end) => {
+            let end = match end {
+                Ok(end) => compact("date-time", end),
+                Err(duration) => duration.clone(),
+            };
+            write!(w, "{}/{end}", compact("date-time", start))
+        }
+        Value::Recur(parts) => {
+            // FREQ must come first for compatibility with RFC 2445 (RFC 5545 §3.3.10)
+            let freq = parts.iter().filter(|(name, _)| name == "freq");
+            let rest = parts.iter().filter(|(name, _)| name != "freq");
+            for (i, (name, values)) in freq.chain(rest).enumerate() {
+                if i > 0 {
+                    w.write_char(';')?;
+                }
+                write!(w, "{}=", name.to_ascii_uppercase())?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        w.write_char(',')?;
+                    }
+                    match value {
+                        Value::String(until) if name == "until" => {
+                            w.write_str(&compact("date-time", until))?
+                        }
+                        value => write_value("recur", value, w)?,
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Removes the separators which jCal adds to DATE, DATE-TIME, TIME, and UTC-OFFSET values.
+fn compact(value_type: &str, s: &str) -> String {
+    match value_type {
+        "date" | "date-time" => s.replace(['-', ':'], ""),
+        "time" | "utc-offset" => s.replace(':', ""),
+        _ => s.to_string(),
+    }
+}
@@ -0,0 +1,667 @@
+//! jCal serialization (RFC 7265).
+//!
+//! Besides writing calendars as jCal with [`write_jcal`], this module converts jCal text back
+//! into iCalendar text with [`write_ical_from_jcal`], which lets JSON-based tools such as
+//! JSCalendar converters produce `.ics` files without building a [`Calendar`].
+
+use std::fmt;
+
+use winnow::{
+    ModalResult, Parser,
+    ascii::multispace0,
+    combinator::{delimited, dispatch, eof, fail, peek, separated, separated_pair, terminated},
+    token::{any, take, take_while},
+};
+
+use super::{
+    FoldingWriter,
+    content::{self, Component, Property, Value},
+};
+use crate::model::component::Calendar;
+
+/// Writes a calendar in jCal format (RFC 7265) to the given writer.
+///
+/// The output is a single line of JSON text. Each component is a `[name, properties,
+/// components]` array and each property is a `[name, parameters, type, values...]` array,
+/// with names in lowercase and values in the representation of RFC 7265 §3.6.
+pub fn write_jcal<W: fmt::Write>(calendar: &Calendar, mut w: W) -> fmt::Result {
+    write_component(&content::calendar(calendar), &mut w)
+}
+
+impl Calendar {
+    /// Serializes this calendar to a jCal (RFC 7265) JSON string.
+    pub fn to_jcal(&self) -> String {
+        let mut s = String::new();
+        write_jcal(self, &mut s).expect("writing to String cannot fail");
+        s
+    }
+}
+
+fn write_component<W: fmt::Write>(component: &Component, w: &mut W) -> fmt::Result {
+    w.write_char('[')?;
+    write_string(&component.name, w)?;
+    w.write_str(",[")?;
+    for (i, property) in component.properties.iter().enumerate() {
+        if i > 0 {
+            w.write_char(',')?;
+        }
+        write_property(property, w)?;
+    }
+    w.write_str("],[")?;
+    for (i, component) in component.components.iter().enumerate() {
+        if i > 0 {
+            w.write_char(',')?;
+        }
+        write_component(component, w)?;
+    }
+    w.write_str("]]")
+}
+
+fn write_property<W: fmt::Write>(property: &Property, w: &mut W) -> fmt::Result {
+    w.write_char('[')?;
+    write_string(&property.name, w)?;
+    w.write_str(",{")?;
+    for (i, (name, values)) in property.params.iter().enumerate() {
+        if i > 0 {
+            w.write_char(',')?;
+        }
+        write_string(name, w)?;
+        w.write_char(':')?;
+        match values.as_slice() {
+            [value] => write_string(value, w)?,
+            values => write_array(values, w)?,
+        }
+    }
+    w.write_str("},")?;
+    write_string(&property.value_type, w)?;
+    for value in &property.values {
+        w.write_char(',')?;
+        write_value(value, w)?;
+    }
+    w.write_char(']')
+}
+
+fn write_value<W: fmt::Write>(value: &Value, w: &mut W) -> fmt::Result {
+    match value {
+        Value::String(s) => write_string(s, w),
+        Value::Number(n) => write_number(n, w),
+        Value::Boolean(b) => write!(w, "{b}"),
+        Value::Geo(lat, lon) => {
+            w.write_char('[')?;
+            write_number(lat, w)?;
+            w.write_char(',')?;
+            write_number(lon, w)?;
+            w.write_char(']')
+        }
+        Value::RequestStatus(parts) => write_array(parts, w),
+        Value::Period(start, end) => {
+            let end = match end {
+                Ok(end) | Err(end) => end,
+            };
+            write_string(&format!("{start}/{end}"), w)
+        }
+        Value::Recur(parts) => {
+            w.write_char('{')?;
+            for (i, (name, values)) in parts.iter().enumerate() {
+                if i > 0 {
+                    w.write_char(',')?;
+                }
+                write_string(name, w)?;
+                w.write_char(':')?;
+                match values.as_slice() {
+                    [value] => write_value(value, w)?,
+                    values => {
+                        w.write_char('[')?;
+                        for (i, value) in values.iter().enumerate() {
+                            if i > 0 {
+                                w.write_char(',')?;
+                            }
+                            write_value(value, w)?;
+                        }
+                        w.write_char(']')?;
+                    }
+                }
+            }
+            w.write_char('}')
+        }
+    }
+}
+
+/// Writes a JSON number, normalizing the leading `+` and redundant leading zeros which
+/// iCalendar allows but JSON does not.
+fn write_number<W: fmt::Write>(n: &str, w: &mut W) -> fmt::Result {
+    let (sign, digits) = match n.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", n.strip_prefix('+').unwrap_or(n)),
+    };
+
+    let trimmed = digits.trim_start_matches('0');
+    let digits = match trimmed {
+        "" => "0",
+        t if t.starts_with('.') => &digits[digits.len() - t.len() - 1..],
+        t => t,
+    };
+
+    if digits.starts_with(|c: char| c.is_ascii_digit()) {
+        write!(w, "{sign}{digits}")
+    } else {
+        write_string(n, w)
+    }
+}
+
+fn write_array<W: fmt::Write>(values: &[String], w: &mut W) -> fmt::Result {
+    w.write_char('[')?;
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            w.write_char(',')?;
+        }
+        write_string(value, w)?;
+    }
+    w.write_char(']')
+}
+
+/// Writes a JSON string literal (RFC 8259 §7).
+fn write_string<W: fmt::Write>(s: &str, w: &mut W) -> fmt::Result {
+    w.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => w.write_str("\\\"")?,
+            '\\' => w.write_str("\\\\")?,
+            '\n' => w.write_str("\\n")?,
+            '\r' => w.write_str("\\r")?,
+            '\t' => w.write_str("\\t")?,
+            c if c.is_control() => write!(w, "\\u{:04x}", c as u32)?,
+            c => w.write_char(c)?,
+        }
+    }
+    w.write_char('"')
+}
+
+// ============================================================================
+// jCal to iCalendar
+// ============================================================================
+
+/// An error returned by [`write_ical_from_jcal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JcalError {
+    /// The input is not JSON text.
+    InvalidJson,
+    /// The input does not have the structure of a jCal component.
+    Malformed(&'static str),
+    /// The writer returned an error.
+    Write(fmt::Error),
+}
+
+impl fmt::Display for JcalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidJson => f.write_str("the input is not valid JSON"),
+            Self::Malformed(reason) => write!(f, "malformed jCal: {reason}"),
+            Self::Write(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl std::error::Error for JcalError {}
+
+impl From<fmt::Error> for JcalError {
+    fn from(error: fmt::Error) -> Self {
+        Self::Write(error)
+    }
+}
+
+/// Writes a jCal component (RFC 7265), such as a VCALENDAR, as iCalendar text.
+///
+/// This reverses the mapping of [`write_jcal`]: names are uppercased, values are written in the
+/// representation of their value type, and a VALUE parameter is added to each property whose
+/// value type is not its default one. The output uses CRLF line endings and is folded at 75
+/// octets.
+///
+/// The input is not otherwise validated, so the output may not be a valid iCalendar stream.
+pub fn write_ical_from_jcal<W: fmt::Write>(jcal: &str, w: W) -> Result<(), JcalError> {
+    let json = terminated(delimited(multispace0, json, multispace0), eof)
+        .parse(jcal)
+        .map_err(|_| JcalError::InvalidJson)?;
+    let component = read_component(json)?;
+    content::write_ical(&component, &mut FoldingWriter::new(w))?;
+    Ok(())
+}
+
+/// A parsed JSON value, with numbers kept as their literal text.
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+/// Parses a JSON value (RFC 8259 §3) without surrounding whitespace.
+fn json(input: &mut &str) -> ModalResult<Json> {
+    let comma = (multispace0, ',', multispace0);
+    dispatch! {peek(any);
+        '{' => delimited(
+            ('{', multispace0),
+            separated(0.., separated_pair(string, (multispace0, ':', multispace0), json), comma),
+            (multispace0, '}'),
+        )
+        .map(Json::Object),
+        '[' => delimited(
+            ('[', multispace0),
+            separated(0.., json, comma),
+            (multispace0, ']'),
+        )
+        .map(Json::Array),
+        '"' => string.map(Json::String),
+        't' => "true".value(Json::Bool(true)),
+        'f' => "false".value(Json::Bool(false)),
+        'n' => "null".value(Json::Null),
+        _ => take_while(1.., |c: char| c.is_ascii_digit() || matches!(c, '+' | '-' | '.' | 'e' | 'E'))
+            .map(|n: &str| Json::Number(n.to_string())),
+    }
+    .parse_next(input)
+}
+
+/// Parses a JSON string literal (RFC 8259 §7).
+fn string(input: &mut &str) -> ModalResult<String> {
+    '"'.parse_next(input)?;
+    let mut out = String::new();
+    loop {
+        match any.parse_next(input)? {
+            '"' => return Ok(out),
+            '\\' => {
+                let c = match any.parse_next(input)? {
+                    '"' => '"',
+                    '\\' => '\\',
+                    '/' => '/',
+                    'b' => '\u{8}',
+                    'f' => '\u{c}',
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    'u' => unicode_escape(input)?,
+                    _ => return fail(input),
+                };
+                out.push(c);
+            }
+            c if c.is_control() => return fail(input),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Parses the hexadecimal digits of a `\u` escape, with the low surrogate of a surrogate pair.
+fn unicode_escape(input: &mut &str) -> ModalResult<char> {
+    let mut code_unit = take(4usize).try_map(|digits| u32::from_str_radix(digits, 16));
+    let high = code_unit.parse_next(input)?;
+    let code_point = if (0xD800..0xDC00).contains(&high) {
+        "\\u".parse_next(input)?;
+        let low = code_unit.parse_next(input)?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return fail(input);
+        }
+        0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+    } else {
+        high
+    };
+
+    match char::from_u32(code_point) {
+        Some(c) => Ok(c),
+        None => fail(input),
+    }
+}
+
+/// Converts a `[name, properties, components]` array into a [`Component`].
+fn read_component(json: Json) -> Result<Component, JcalError> {
+    let Json::Array(parts) = json else {
+        return Err(JcalError::Malformed("a component is not an array"));
+    };
+    let Ok(
+        [
+            Json::String(name),
+            Json::Array(properties),
+            Json::Array(components),
+        ],
+    ) = <[Json; 3]>::try_from(parts)
+    else {
+        return Err(JcalError::Malformed(
+            "a component is not a [name, properties, components] array",
+        ));
+    };
+
+    Ok(Component {
+        name: name.to_ascii_lowercase(),
+        properties: properties
+            .into_iter()
+            .map(read_property)
+            .collect::<Result<_, _>>()?,
+        components: components
+            .into_iter()
+            .map(read_component)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// Converts a `[name, parameters, type, values...]` array into a [`Property`].
+fn read_property(json: Json) -> Result<Property, JcalError> {
+    const MALFORMED: JcalError =
+        JcalError::Malformed("a property is not a [name, parameters, type, values...] array");
+
+    let Json::Array(parts) = json else {
+        return Err(MALFORMED);
+    };
+    let mut parts = parts.into_iter();
+    let (Some(Json::String(name)), Some(Json::Object(params)), Some(Json::String(value_type))) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(MALFORMED);
+    };
+
+    let name = name.to_ascii_lowercase();
+    let value_type = value_type.to_ascii_lowercase();
+    let params = params
+        .into_iter()
+        .map(|(param, value)| {
+            let values = match value {
+                Json::String(value) => vec![value],
+                Json::Array(values) => values
+                    .into_iter()
+                    .map(|value| match value {
+                        Json::String(value) => Ok(value),
+                        _ => Err(JcalError::Malformed("a parameter value is not a string")),
+                    })
+                    .collect::<Result<_, _>>()?,
+                _ => return Err(JcalError::Malformed("a parameter value is not a string")),
+            };
+            Ok((param.to_ascii_lowercase(), values))
+        })
+        .collect::<Result<_, _>>()?;
+    let values = parts
+        .map(|value| read_value(&name, &value_type, value))
+        .collect::<Result<_, _>>()?;
+
+    Ok(Property {
+        name,
+        params,
+        value_type,
+        values,
+    })
+}
+
+/// Converts a property value in the representation of RFC 7265 §3.6 into a [`Value`].
+fn read_value(name: &str, value_type: &str, json: Json) -> Result<Value, JcalError> {
+    let scalar = |json| match json {
+        Json::String(s) => Ok(Value::String(s)),
+        Json::Number(n) => Ok(Value::Number(n)),
+        Json::Bool(b) => Ok(Value::Boolean(b)),
+        _ => Err(JcalError::Malformed("a value is not a scalar")),
+    };
+    let string = |json| match json {
+        Json::String(s) => Ok(s),
+        _ => Err(JcalError::Malformed("a value is not a string")),
+    };
+    let period = |start, end: String| {
+        let end = match end.starts_with(['+', '-', 'P']) {
+            true => Err(end),
+            false => Ok(end),
+        };
+        Value::Period(start, end)
+    };
+
+    match (name, value_type, json) {
+        ("geo", _, Json::Array(parts)) => match <[Json; 2]>::try_from(parts) {
+            Ok([Json::Number(lat), Json::Number(lon)]) => Ok(Value::Geo(lat, lon)),
+            _ => Err(JcalError::Malformed("a GEO value is not two numbers")),
+        },
+        ("request-status", _, Json::Array(parts)) => parts
+            .into_iter()
+            .map(string)
+            .collect::<Result<_, _>>()
+            .map(Value::RequestStatus),
+        (_, "period", Json::Array(parts)) => match <[Json; 2]>::try_from(parts) {
+            Ok([Json::String(start), Json::String(end)]) => Ok(period(start, end)),
+            _ => Err(JcalError::Malformed("a PERIOD value is not two strings")),
+        },
+        (_, "period", Json::String(value)) => match value.split_once('/') {
+            Some((start, end)) => Ok(period(start.to_string(), end.to_string())),
+            None => Err(JcalError::Malformed("a PERIOD value has no end")),
+        },
+        (_, "recur", Json::Object(parts)) => parts
+            .into_iter()
+            .map(|(part, value)| {
+                let values = match value {
+                    Json::Array(values) => values.into_iter().map(scalar).collect(),
+                    value => scalar(value).map(|value| vec![value]),
+                };
+                Ok((part.to_ascii_lowercase(), values?))
+            })
+            .collect::<Result<_, _>>()
+            .map(Value::Recur),
+        (_, _, json) => scalar(json),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::serializer::WriteIcal;
+
+    /// Parses `ics` and returns its jCal form as a JSON value.
+    fn jcal(ics: &str) -> Value {
+        let calendar = Calendar::parse(ics).unwrap().remove(0);
+        serde_json::from_str(&calendar.to_jcal()).unwrap()
+    }
+
+    #[test]
+    fn event() {
+        let value = jcal(concat!(
+            "BEGIN:VCALENDAR\r\n",
+            "VERSION:2.0\r\n",
+            "PRODID:-//Example//EN\r\n",
+            "BEGIN:VEVENT\r\n",
+            "UID:1@example.com\r\n",
+            "DTSTAMP:20240101T000000Z\r\n",
+            "DTSTART;TZID=Europe/Berlin:20240115T130000\r\n",
+            "DURATION:PT1H\r\n",
+            "SUMMARY:Lunch\\, then \"coffee\"\r\n",
+            "CATEGORIES:FOOD,SOCIAL\r\n",
+            "GEO:52.5;13.4\r\n",
+            "PRIORITY:5\r\n",
+            "RRULE:FREQ=WEEKLY;COUNT=4;BYDAY=MO,WE\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        ));
+
+        let [name, properties, components] = value.as_array().unwrap().as_slice() else {
+            panic!("expected a component array");
+        };
+        assert_eq!(name, "vcalendar");
+        assert!(
+            properties
+                .as_array()
+                .unwrap()
+                .contains(&json!(["version", {}, "text", "2.0"]))
+        );
+
+        let event = &components[0];
+        assert_eq!(event[0], "vevent");
+        let properties = event[1].as_array().unwrap();
+        let find = |name: &str| properties.iter().find(|p| p[0] == name).unwrap().clone();
+        assert_eq!(
+            find("dtstamp"),
+            json!(["dtstamp", {}, "date-time", "2024-01-01T00:00:00Z"])
+        );
+        assert_eq!(
+            find("dtstart"),
+            json!(["dtstart", {"tzid": "Europe/Berlin"}, "date-time", "2024-01-15T13:00:00"])
+        );
+        assert_eq!(
+            find("duration"),
+            json!(["duration", {}, "duration", "PT1H"])
+        );
+        assert_eq!(
+            find("summary"),
+            json!(["summary", {}, "text", "Lunch, then \"coffee\""])
+        );
+        assert_eq!(
+            find("categories"),
+            json!(["categories", {}, "text", "FOOD", "SOCIAL"])
+        );
+        assert_eq!(find("geo"), json!(["geo", {}, "float", [52.5, 13.4]]));
+        assert_eq!(find("priority"), json!(["priority", {}, "integer", 5]));
+        assert_eq!(
+            find("rrule"),
+            json!(["rrule", {}, "recur", {"freq": "WEEKLY", "count": 4, "byday": ["MO", "WE"]}])
+        );
+    }
+
+    #[test]
+    fn all_day_and_multi_valued_parameters() {
+        let value = jcal(concat!(
+            "BEGIN:VCALENDAR\r\n",
+            "VERSION:2.0\r\n",
+            "PRODID:-//Example//EN\r\n",
+            "BEGIN:VEVENT\r\n",
+            "UID:2@example.com\r\n",
+            "DTSTAMP:20240101T000000Z\r\n",
+            "DTSTART;VALUE=DATE:20240115\r\n",
+            "ATTENDEE;MEMBER=\"mailto:a@example.com\",\"mailto:b@example.com\":mailto:c@example.com\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        ));
+
+        let properties = value[2][0][1].as_array().unwrap();
+        let find = |name: &str| properties.iter().find(|p| p[0] == name).unwrap().clone();
+        assert_eq!(
+            find("dtstart"),
+            json!(["dtstart", {}, "date", "2024-01-15"])
+        );
+        assert_eq!(
+            find("attendee"),
+            json!([
+                "attendee",
+                {"member": ["mailto:a@example.com", "mailto:b@example.com"]},
+                "cal-address",
+                "mailto:c@example.com"
+            ])
+        );
+    }
+
+    #[test]
+    fn timezone() {
+        let value = jcal(concat!(
+            "BEGIN:VCALENDAR\r\n",
+            "VERSION:2.0\r\n",
+            "PRODID:-//Example//EN\r\n",
+            "BEGIN:VTIMEZONE\r\n",
+            "TZID:America/New_York\r\n",
+            "BEGIN:STANDARD\r\n",
+            "DTSTART:20071104T020000\r\n",
+            "TZOFFSETFROM:-0400\r\n",
+            "TZOFFSETTO:-0500\r\n",
+            "RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU\r\n",
+            "END:STANDARD\r\n",
+            "END:VTIMEZONE\r\n",
+            "END:VCALENDAR\r\n",
+        ));
+
+        let standard = &value[2][0][2][0];
+        assert_eq!(standard[0], "standard");
+        let properties = standard[1].as_array().unwrap();
+        assert!(properties.contains(&json!(["tzoffsetfrom", {}, "utc-offset", "-04:00"])));
+        assert!(properties.contains(&json!([
+            "rrule",
+            {},
+            "recur",
+            {"freq": "YEARLY", "bymonth": 11, "byday": "1SU"}
+        ])));
+    }
+    /// Converts jCal text to iCalendar text.
+    fn ical(jcal: &str) -> Result<String, JcalError> {
+        let mut out = String::new();
+        write_ical_from_jcal(jcal, &mut out).map(|()| out)
+    }
+
+    #[test]
+    fn jcal_round_trip() {
+        let ics = concat!(
+            "BEGIN:VCALENDAR\r\n",
+            "VERSION:2.0\r\n",
+            "PRODID:-//Example//EN\r\n",
+            "BEGIN:VEVENT\r\n",
+            "UID:1@example.com\r\n",
+            "DTSTAMP:20240101T000000Z\r\n",
+            "DTSTART;VALUE=DATE:20240115\r\n",
+            "SUMMARY:Lunch\\, then \"coffee\"\r\n",
+            "CATEGORIES:FOOD,SOCIAL\r\n",
+            "GEO:52.5;13.4\r\n",
+            "ATTENDEE;MEMBER=\"mailto:a@example.com\":mailto:c@example.com\r\n",
+            "RRULE:FREQ=WEEKLY;UNTIL=20240301T000000Z;BYDAY=MO,WE\r\n",
+            "REQUEST-STATUS:2.0;Success\r\n",
+            "BEGIN:VALARM\r\n",
+            "ACTION:DISPLAY\r\n",
+            "TRIGGER;RELATED=END:-PT15M\r\n",
+            "DESCRIPTION:Reminder\r\n",
+            "END:VALARM\r\n",
+            "END:VEVENT\r\n",
+            "BEGIN:VTIMEZONE\r\n",
+            "TZID:America/New_York\r\n",
+            "BEGIN:STANDARD\r\n",
+            "DTSTART:20071104T020000\r\n",
+            "TZOFFSETFROM:-0400\r\n",
+            "TZOFFSETTO:-0500\r\n",
+            "END:STANDARD\r\n",
+            "END:VTIMEZONE\r\n",
+            "END:VCALENDAR\r\n",
+        );
+
+        let calendar = Calendar::parse(ics).unwrap().remove(0);
+        let written = ical(&calendar.to_jcal()).unwrap();
+        assert_eq!(written, calendar.to_ical_string());
+
+        let reparsed = Calendar::parse(&written).unwrap().remove(0);
+        assert_eq!(reparsed.to_jcal(), calendar.to_jcal());
+    }
+
+    #[test]
+    fn jcal_input_forms() {
+        let written = ical(concat!(
+            r#" ["vcalendar", [], [["vevent", ["#,
+            r#"["x-note", {}, "unknown", "a;b"],"#,
+            r#"["summary", {"language": "de"}, "text", "Gr\u00fc\u00dfe \ud83d\udcc5\n"],"#,
+            r#"["freebusy", {}, "period", ["2024-01-01T10:00:00Z", "PT1H"]],"#,
+            r#"["description", {}, "text", "This line is long enough that it has to be folded by the writer at 75 octets."]"#,
+            r#"], []]]] "#,
+        ))
+        .unwrap();
+        assert_eq!(
+            written,
+            concat!(
+                "BEGIN:VCALENDAR\r\n",
+                "BEGIN:VEVENT\r\n",
+                "X-NOTE:a;b\r\n",
+                "SUMMARY;LANGUAGE=de:Grüße 📅\\n\r\n",
+                "FREEBUSY:20240101T100000Z/PT1H\r\n",
+                "DESCRIPTION:This line is long enough that it has to be folded by the writer\r\n",
+                "  at 75 octets.\r\n",
+                "END:VEVENT\r\n",
+                "END:VCALENDAR\r\n",
+            )
+        );
+
+        assert_eq!(ical("[\"vcalendar\", [], []"), Err(JcalError::InvalidJson));
+        assert_eq!(ical("[\"a\\ud800\", [], []]"), Err(JcalError::InvalidJson));
+        assert!(matches!(
+            ical(r#"["vcalendar", [["version", {}]], []]"#),
+            Err(JcalError::Malformed(_))
+        ));
+        assert!(matches!(
+            ical(r#"["vevent", [["geo", {}, "float", [1]]], []]"#),
+            Err(JcalError::Malformed(_))
+        ));
+    }
+}
@@ -4,17 +4,39 @@
 //! which uses human-readable separators (`2003-12-25T12:00:00Z`, `+08:00`), `WriteIcal`
 //! produces compact representations required by RFC 5545 (`20031225T120000Z`, `+0800`).
 //!
-//! [`FoldingWriter`] handles RFC 5545 §3.1 line folding (75-octet limit).
+//! [`FoldingWriter`] handles RFC 5545 §3.1 line folding (75-octet limit), and
+//! [`write_calendar`] combines the two to produce a complete iCalendar stream.
+//!
+//! The same calendar can also be written in the jCal (RFC 7265) and xCal (RFC 6321) formats
+//! with [`write_jcal`] and [`write_xcal`].
 
 mod component;
+mod content;
+mod jcal;
 mod parameter;
 mod primitive;
 mod property;
+mod xcal;
 
+pub use self::jcal::{JcalError, write_ical_from_jcal, write_jcal};
 pub use self::property::{write_content_line, write_prop, write_opt_prop, write_vec_prop};
+pub(crate) use self::property::{write_exdate_prop, write_rdate_seq_prop};
+pub use self::xcal::write_xcal;
 
 use std::fmt;
 
+use crate::model::component::Calendar;
+
+/// Writes a calendar in iCalendar format to the given writer.
+///
+/// The output uses CRLF line endings and is folded at 75 octets per RFC 5545 §3.1,
+/// never splitting a multi-octet character. TEXT values are escaped, parameter values
+/// are quoted where necessary, and binary values are base64-encoded.
+pub fn write_calendar<W: fmt::Write>(calendar: &Calendar, w: W) -> fmt::Result {
+    let mut fw = FoldingWriter::new(w);
+    calendar.write_ical(&mut fw)
+}
+
 /// Writes a value in iCalendar text format.
 pub trait WriteIcal {
     /// Writes this value to the given writer in iCalendar wire format.
@@ -30,11 +52,19 @@ pub trait WriteIcal {
 
 /// A writer that folds content lines at 75 octets per RFC 5545 §3.1.
 ///
-/// Each time the accumulated line length would exceed 75 bytes, a CRLF + space
-/// fold sequence is inserted before continuing.
+/// Each time the accumulated line length would exceed the limit, a CRLF + space
+/// fold sequence is inserted before continuing. Folds never split a multi-octet
+/// UTF-8 sequence or a backslash escape pair such as `\n` or `\,`, although a
+/// character which does not fit on an empty continuation line is written anyway.
+///
+/// The limit can be changed with [`FoldingWriter::with_width`], and folding can be
+/// disabled entirely with [`FoldingWriter::unfolded`].
 pub struct FoldingWriter<W> {
     inner: W,
+    width: Option<usize>,
     line_len: usize,
+    folded: bool,
+    escaped: bool,
 }
 
 impl<W: fmt::Write> FoldingWriter<W> {
@@ -43,7 +73,40 @@ impl<W: fmt::Write> FoldingWriter<W> {
 
     /// Creates a new `FoldingWriter` wrapping the given writer.
     pub fn new(inner: W) -> Self {
-        Self { inner, line_len: 0 }
+        Self::with_width(inner, Self::MAX_LINE_OCTETS)
+    }
+
+    /// Creates a `FoldingWriter` which folds lines at `width` octets, excluding the
+    /// line break but including the leading space of continuation lines.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is less than 2, which leaves no room for content after a fold.
+    pub fn with_width(inner: W, width: usize) -> Self {
+        assert!(width >= 2, "folding width must be at least 2 octets");
+        Self {
+            inner,
+            width: Some(width),
+            line_len: 0,
+            folded: false,
+            escaped: false,
+        }
+    }
+
+    /// Creates a `FoldingWriter` which never folds, passing its input through unchanged.
+    pub fn unfolded(inner: W) -> Self {
+        Self {
+            inner,
+            width: None,
+            line_len: 0,
+            folded: false,
+            escaped: false,
+        }
+    }
+
+    /// Returns the folding width in octets, or `None` if folding is disabled.
+    pub fn width(&self) -> Option<usize> {
+        self.width
     }
 
     /// Consumes the `FoldingWriter` and returns the inner writer.
@@ -54,27 +117,40 @@ impl<W: fmt::Write> FoldingWriter<W> {
     /// Resets the line length counter (call after writing CRLF).
     pub fn reset_line(&mut self) {
         self.line_len = 0;
+        self.folded = false;
+        self.escaped = false;
     }
 }
 
 impl<W: fmt::Write> fmt::Write for FoldingWriter<W> {
     fn write_str(&mut self, s: &str) -> fmt::Result {
         for ch in s.chars() {
-            let ch_len = ch.len_utf8();
             if ch == '\r' || ch == '\n' {
                 self.inner.write_char(ch)?;
                 // We'll reset line_len on the newline char of a CRLF pair
                 if ch == '\n' {
-                    self.line_len = 0;
+                    self.reset_line();
                 }
                 continue;
             }
-            if self.line_len + ch_len > Self::MAX_LINE_OCTETS {
+
+            // An escape pair is kept together; its second character is always ASCII.
+            let starts_escape = ch == '\\' && !self.escaped;
+            let needed = if starts_escape { 2 } else { ch.len_utf8() };
+            let line_start = usize::from(self.folded);
+            if let Some(width) = self.width
+                && !self.escaped
+                && self.line_len + needed > width
+                && self.line_len > line_start
+            {
                 self.inner.write_str("\r\n ")?;
                 self.line_len = 1; // the space counts
+                self.folded = true;
             }
+
             self.inner.write_char(ch)?;
-            self.line_len += ch_len;
+            self.line_len += ch.len_utf8();
+            self.escaped = starts_escape;
         }
         Ok(())
     }
@@ -83,13 +159,20 @@ impl<W: fmt::Write> fmt::Write for FoldingWriter<W> {
 /// Escapes a TEXT value for iCalendar content lines.
 ///
 /// Backslash-escapes semicolons, commas, backslashes, and newlines per RFC 5545 §3.3.11.
+/// A CRLF pair or a lone CR is written as a single escaped newline, since TEXT values
+/// cannot contain control characters.
 pub fn escape_text<W: fmt::Write>(s: &str, w: &mut W) -> fmt::Result {
-    for ch in s.chars() {
+    let mut chars = s.chars().peekable();
+    while let Some(ch) = chars.next() {
         match ch {
             '\\' => w.write_str("\\\\")?,
             ';' => w.write_str("\\;")?,
             ',' => w.write_str("\\,")?,
             '\n' => w.write_str("\\n")?,
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                w.write_str("\\n")?
+            }
             _ => w.write_char(ch)?,
         }
     }
@@ -160,4 +243,134 @@ mod tests {
         escape_text("simple text", &mut buf).unwrap();
         assert_eq!(buf, "simple text");
     }
+
+    #[test]
+    fn escape_text_carriage_returns() {
+        let mut buf = String::new();
+        escape_text("one\r\ntwo\rthree", &mut buf).unwrap();
+        assert_eq!(buf, "one\\ntwo\\nthree");
+    }
+
+    #[test]
+    fn write_calendar_output() {
+        let description = "Ünïcödé ".repeat(12);
+        let input = format!(
+            "BEGIN:VCALENDAR\r\n\
+             VERSION:2.0\r\n\
+             PRODID:-//Test//Test//EN\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:write-calendar@example.com\r\n\
+             DTSTAMP:20070423T123432Z\r\n\
+             DTSTART:20070628T090000Z\r\n\
+             SUMMARY:Lunch\\, then review\r\n\
+             DESCRIPTION:{description}\r\n\
+             ORGANIZER;CN=\"Doe, Jane\":mailto:jane@example.com\r\n\
+             ATTACH;VALUE=BINARY;ENCODING=BASE64:SGVsbG8gV29ybGQ=\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR\r\n"
+        );
+        let calendar = Calendar::parse(&input).unwrap().remove(0);
+
+        let mut output = String::new();
+        write_calendar(&calendar, &mut output).unwrap();
+
+        assert!(output.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(output.ends_with("END:VCALENDAR\r\n"));
+        assert!(output.contains("SUMMARY:Lunch\\, then review\r\n"));
+        assert!(output.contains("ORGANIZER;CN=\"Doe, Jane\":mailto:jane@example.com\r\n"));
+        assert!(output.contains(":SGVsbG8gV29ybGQ=\r\n"));
+        assert_eq!(output.matches("ENCODING=BASE64").count(), 1);
+        for line in output.split("\r\n") {
+            assert!(line.len() <= 75, "unfolded line: {line:?}");
+            assert!(!line.contains('\n') && !line.contains('\r'));
+        }
+        assert_eq!(output, calendar.to_ical());
+
+        let reparsed = Calendar::parse(&output).unwrap();
+        assert_eq!(reparsed.len(), 1);
+        assert_eq!(reparsed[0].to_ical(), output);
+    }
+
+    /// Removes the folds from `s` as described in RFC 5545 §3.1.
+    fn unfold(s: &str) -> String {
+        s.replace("\r\n ", "").replace("\r\n\t", "")
+    }
+
+    #[test]
+    fn folding_writer_rfc_example() {
+        // RFC 5545 §3.1
+        let folded = "DESCRIPTION:This is a lo\r\n ng description\r\n  that exists on a long line.";
+        let line = "DESCRIPTION:This is a long description that exists on a long line.";
+        assert_eq!(unfold(folded), line);
+
+        for width in [75, 24, 10, 2] {
+            let mut fw = FoldingWriter::with_width(String::new(), width);
+            write!(fw, "{line}\r\n").unwrap();
+            let result = fw.into_inner();
+            assert_eq!(unfold(&result), format!("{line}\r\n"));
+            for segment in result.split("\r\n") {
+                assert!(segment.len() <= width, "{segment:?} exceeds {width} octets");
+            }
+        }
+
+        let mut fw = FoldingWriter::with_width(String::new(), 24);
+        write!(fw, "{line}").unwrap();
+        let result = fw.into_inner();
+        assert!(result.starts_with("DESCRIPTION:This is a lo\r\n ng descripti"));
+    }
+
+    #[test]
+    fn folding_writer_multi_octet() {
+        // 3 + 36 × 2 = 75 octets, so the 37th two-octet character cannot fit
+        let line = format!("ABC{}", "é".repeat(37));
+        let mut fw = FoldingWriter::new(String::new());
+        write!(fw, "{line}").unwrap();
+        let result = fw.into_inner();
+        assert_eq!(result, format!("ABC{}\r\n é", "é".repeat(36)));
+
+        // "€" is three octets, and never split across a fold
+        let mut fw = FoldingWriter::with_width(String::new(), 5);
+        write!(fw, "A€€€").unwrap();
+        assert_eq!(fw.into_inner(), "A€\r\n €\r\n €");
+    }
+
+    #[test]
+    fn folding_writer_escape_pairs() {
+        let mut buf = String::new();
+        escape_text("a,b;c\nd\\e", &mut buf).unwrap();
+        assert_eq!(buf, "a\\,b\\;c\\nd\\\\e");
+
+        for width in 2..12 {
+            let mut fw = FoldingWriter::with_width(String::new(), width);
+            write!(fw, "X:{buf}").unwrap();
+            let result = fw.into_inner();
+            assert_eq!(unfold(&result), format!("X:{buf}"));
+            for segment in result.split("\r\n") {
+                let escapes = segment.trim_start_matches(' ').replace("\\\\", "");
+                assert!(!escapes.ends_with('\\'), "escape split in {result:?}");
+            }
+        }
+
+        // an escape pair is also kept together across separate writes
+        let mut fw = FoldingWriter::with_width(String::new(), 4);
+        write!(fw, "ABC").unwrap();
+        write!(fw, "\\").unwrap();
+        write!(fw, "n").unwrap();
+        assert_eq!(fw.into_inner(), "ABC\r\n \\n");
+    }
+
+    #[test]
+    fn folding_writer_unfolded() {
+        let line = "D".repeat(200);
+        let mut fw = FoldingWriter::unfolded(String::new());
+        write!(fw, "{line}\r\n{line}").unwrap();
+        assert_eq!(fw.width(), None);
+        assert_eq!(fw.into_inner(), format!("{line}\r\n{line}"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn folding_writer_width_too_small() {
+        FoldingWriter::with_width(String::new(), 1);
+    }
 }
@@ -36,7 +36,7 @@ fn write_param_value<W: fmt::Write>(pv: &ParamValue, w: &mut W) -> fmt::Result {
 }
 
 /// Writes a string, quoting it if it contains `:`, `;`, `,`, or space.
-fn write_maybe_quoted<W: fmt::Write>(s: &str, w: &mut W) -> fmt::Result {
+pub(super) fn write_maybe_quoted<W: fmt::Write>(s: &str, w: &mut W) -> fmt::Result {
     let needs_quoting = s.contains(':') || s.contains(';') || s.contains(',') || s.contains(' ');
     if needs_quoting {
         w.write_char('"')?;
@@ -472,6 +472,13 @@ impl WriteIcal for RRule {
         let freq = Freq::from(&self.freq);
         write!(w, "FREQ={}", freq_str(freq))?;
 
+        if let Some(rscale) = &self.rscale {
+            match rscale {
+                calendar_types::set::Token::Known(rscale) => write!(w, ";RSCALE={rscale}")?,
+                calendar_types::set::Token::Unknown(rscale) => write!(w, ";RSCALE={rscale}")?,
+            }
+        }
+
         if let Some(interval) = &self.interval {
             write!(w, ";INTERVAL={}", interval.get())?;
         }
@@ -508,6 +515,14 @@ impl WriteIcal for RRule {
             write_weekday(*wkst, w)?;
         }
 
+        if let Some(skip) = &self.skip {
+            write!(w, ";SKIP={skip}")?;
+        }
+
+        for part in &self.extras {
+            write!(w, ";{part}")?;
+        }
+
         Ok(())
     }
 }
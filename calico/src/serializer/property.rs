@@ -67,6 +67,9 @@ pub fn write_structured_data_prop<W: fmt::Write>(
     match prop {
         StructuredDataProp::Binary(p) => {
             w.write_str("STRUCTURED-DATA;VALUE=BINARY")?;
+            if p.params.inline_encoding().is_none() {
+                w.write_str(";ENCODING=BASE64")?;
+            }
             p.params.write_ical(w)?;
             w.write_str(":")?;
             let encoded = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &p.value);
@@ -111,6 +114,9 @@ pub fn write_x_property_iter<'a, W: fmt::Write>(
     for (name, props) in iter {
         for prop in props {
             w.write_str(name.as_str())?;
+            if matches!(prop.value, Value::Binary(_)) && prop.params.inline_encoding().is_none() {
+                w.write_str(";ENCODING=BASE64")?;
+            }
             prop.params.write_ical(w)?;
             w.write_str(":")?;
             prop.value.write_ical(w)?;
@@ -182,6 +188,9 @@ pub fn write_exdate_prop<W: fmt::Write>(
 }
 
 /// Writes an Attachment property with correct VALUE/ENCODING params.
+///
+/// Binary attachments are base64-encoded, so `ENCODING=BASE64` is written if the
+/// parameters do not already carry an encoding.
 pub fn write_attach_prop<W: fmt::Write>(
     name: &str,
     prop: &Prop<rfc5545_types::value::Attachment, Params>,
@@ -191,6 +200,9 @@ pub fn write_attach_prop<W: fmt::Write>(
     match &prop.value {
         rfc5545_types::value::Attachment::Binary(_) => {
             w.write_str(";VALUE=BINARY")?;
+            if prop.params.inline_encoding().is_none() {
+                w.write_str(";ENCODING=BASE64")?;
+            }
         }
         rfc5545_types::value::Attachment::Uri(_) => {}
     }
@@ -267,4 +279,18 @@ mod tests {
             "DESCRIPTION:Meeting\\; with\\, team\\nSecond line\r\n"
         );
     }
+
+    #[test]
+    fn binary_attachment_gets_encoding() {
+        let mut buf = String::new();
+        let prop = Prop {
+            value: rfc5545_types::value::Attachment::Binary(b"Hello World".to_vec()),
+            params: Params::default(),
+        };
+        write_attach_prop("ATTACH", &prop, &mut buf).unwrap();
+        assert_eq!(
+            buf,
+            "ATTACH;VALUE=BINARY;ENCODING=BASE64:SGVsbG8gV29ybGQ=\r\n"
+        );
+    }
 }
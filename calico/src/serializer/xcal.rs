@@ -0,0 +1,210 @@
+//! xCal serialization (RFC 6321).
+
+use std::fmt;
+
+use super::content::{self, Component, Property, Value};
+use crate::model::component::Calendar;
+
+/// The XML namespace of iCalendar elements (RFC 6321 §3.1).
+const NAMESPACE: &str = "urn:ietf:params:xml:ns:icalendar-2.0";
+
+/// Writes a calendar in xCal format (RFC 6321) to the given writer.
+///
+/// The output is an XML document whose root `icalendar` element contains the calendar. Each
+/// property element contains its `parameters` and its values, each in an element named by its
+/// value type, with names in lowercase and values in the representation of RFC 6321 §3.6.
+pub fn write_xcal<W: fmt::Write>(calendar: &Calendar, mut w: W) -> fmt::Result {
+    w.write_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    write!(w, "<icalendar xmlns=\"{NAMESPACE}\">")?;
+    write_component(&content::calendar(calendar), &mut w)?;
+    w.write_str("</icalendar>")
+}
+
+impl Calendar {
+    /// Serializes this calendar to an xCal (RFC 6321) XML string.
+    pub fn to_xcal(&self) -> String {
+        let mut s = String::new();
+        write_xcal(self, &mut s).expect("writing to String cannot fail");
+        s
+    }
+}
+
+fn write_component<W: fmt::Write>(component: &Component, w: &mut W) -> fmt::Result {
+    write!(w, "<{}>", component.name)?;
+    if !component.properties.is_empty() {
+        w.write_str("<properties>")?;
+        for property in &component.properties {
+            write_property(property, w)?;
+        }
+        w.write_str("</properties>")?;
+    }
+    if !component.components.is_empty() {
+        w.write_str("<components>")?;
+        for component in &component.components {
+            write_component(component, w)?;
+        }
+        w.write_str("</components>")?;
+    }
+    write!(w, "</{}>", component.name)
+}
+
+fn write_property<W: fmt::Write>(property: &Property, w: &mut W) -> fmt::Result {
+    write!(w, "<{}>", property.name)?;
+    if !property.params.is_empty() {
+        w.write_str("<parameters>")?;
+        for (name, values) in &property.params {
+            write!(w, "<{name}>")?;
+            let value_type = param_type(name);
+            for value in values {
+                match value_type {
+                    "boolean" => write_element(value_type, &value.to_ascii_lowercase(), w)?,
+                    _ => write_element(value_type, value, w)?,
+                }
+            }
+            write!(w, "</{name}>")?;
+        }
+        w.write_str("</parameters>")?;
+    }
+    for value in &property.values {
+        write_value(&property.value_type, value, w)?;
+    }
+    write!(w, "</{}>", property.name)
+}
+
+/// Returns the value type of the parameter `name` (RFC 6321 §3.5).
+fn param_type(name: &str) -> &'static str {
+    match name {
+        "delegated-from" | "delegated-to" | "member" | "sent-by" => "cal-address",
+        "altrep" | "dir" => "uri",
+        "rsvp" => "boolean",
+        _ => "text",
+    }
+}
+
+fn write_value<W: fmt::Write>(value_type: &str, value: &Value, w: &mut W) -> fmt::Result {
+    match value {
+        Value::String(s) | Value::Number(s) => write_element(value_type, s, w),
+        Value::Boolean(b) => write_element(value_type, if *b { "true" } else { "false" }, w),
+        Value::Geo(lat, lon) => {
+            w.write_str("<latitude>")?;
+            write_escaped(lat, w)?;
+            w.write_str("</latitude><longitude>")?;
+            write_escaped(lon, w)?;
+            w.write_str("</longitude>")
+        }
+        Value::RequestStatus(parts) => {
+            for (name, part) in ["code", "description", "data"].iter().zip(parts) {
+                write_element(name, part, w)?;
+            }
+            Ok(())
+        }
+        Value::Period(start, end) => {
+            w.write_str("<period>")?;
+            write_element("start", start, w)?;
+            match end {
+                Ok(end) => write_element("end", end, w)?,
+                Err(duration) => write_element("duration", duration, w)?,
+            }
+            w.write_str("</period>")
+        }
+        Value::Recur(parts) => {
+            w.write_str("<recur>")?;
+            for (name, values) in parts {
+                for value in values {
+                    match value {
+                        Value::String(s) | Value::Number(s) => write_element(name, s, w)?,
+                        _ => {}
+                    }
+                }
+            }
+            w.write_str("</recur>")
+        }
+    }
+}
+
+/// Writes `<name>text</name>`, escaping `text`.
+fn write_element<W: fmt::Write>(name: &str, text: &str, w: &mut W) -> fmt::Result {
+    write!(w, "<{name}>")?;
+    write_escaped(text, w)?;
+    write!(w, "</{name}>")
+}
+
+/// Writes character data, escaping the characters which XML reserves.
+fn write_escaped<W: fmt::Write>(s: &str, w: &mut W) -> fmt::Result {
+    for c in s.chars() {
+        match c {
+            '<' => w.write_str("&lt;")?,
+            '>' => w.write_str("&gt;")?,
+            '&' => w.write_str("&amp;")?,
+            '"' => w.write_str("&quot;")?,
+            '\r' => w.write_str("&#13;")?,
+            c => w.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn xcal(ics: &str) -> String {
+        Calendar::parse(ics).unwrap().remove(0).to_xcal()
+    }
+
+    #[test]
+    fn event() {
+        let xml = xcal(concat!(
+            "BEGIN:VCALENDAR\r\n",
+            "VERSION:2.0\r\n",
+            "PRODID:-//Example//EN\r\n",
+            "BEGIN:VEVENT\r\n",
+            "UID:1@example.com\r\n",
+            "DTSTAMP:20240101T000000Z\r\n",
+            "DTSTART;TZID=Europe/Berlin:20240115T130000\r\n",
+            "SUMMARY:Fish & chips <3\r\n",
+            "GEO:52.5;13.4\r\n",
+            "RRULE:FREQ=WEEKLY;COUNT=4;BYDAY=MO,WE\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        ));
+
+        assert!(xml.starts_with(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+             <icalendar xmlns=\"urn:ietf:params:xml:ns:icalendar-2.0\"><vcalendar><properties>"
+        ));
+        assert!(xml.ends_with("</vevent></components></vcalendar></icalendar>"));
+        assert!(xml.contains("<version><text>2.0</text></version>"));
+        assert!(xml.contains(
+            "<dtstart><parameters><tzid><text>Europe/Berlin</text></tzid></parameters>\
+             <date-time>2024-01-15T13:00:00</date-time></dtstart>"
+        ));
+        assert!(xml.contains("<summary><text>Fish &amp; chips &lt;3</text></summary>"));
+        assert!(xml.contains("<geo><latitude>52.5</latitude><longitude>13.4</longitude></geo>"));
+        assert!(xml.contains(
+            "<rrule><recur><freq>WEEKLY</freq><count>4</count>\
+             <byday>MO</byday><byday>WE</byday></recur></rrule>"
+        ));
+    }
+
+    #[test]
+    fn parameter_types() {
+        let xml = xcal(concat!(
+            "BEGIN:VCALENDAR\r\n",
+            "VERSION:2.0\r\n",
+            "PRODID:-//Example//EN\r\n",
+            "BEGIN:VEVENT\r\n",
+            "UID:2@example.com\r\n",
+            "DTSTAMP:20240101T000000Z\r\n",
+            "ATTENDEE;RSVP=TRUE;DELEGATED-FROM=\"mailto:a@example.com\":mailto:b@example.com\r\n",
+            "END:VEVENT\r\n",
+            "END:VCALENDAR\r\n",
+        ));
+
+        assert!(xml.contains("<rsvp><boolean>true</boolean></rsvp>"));
+        assert!(xml.contains(
+            "<delegated-from><cal-address>mailto:a@example.com</cal-address></delegated-from>"
+        ));
+        assert!(xml.contains("<cal-address>mailto:b@example.com</cal-address></attendee>"));
+    }
+}
@@ -150,6 +150,28 @@ fn round_trip_simple_event() {
     assert_eq!(event.uid().unwrap().value.as_str(), "test-1@example.com");
 }
 
+/// RFC 7529 RSCALE and SKIP parts must survive a round-trip.
+#[test]
+fn round_trip_rscale_and_skip() {
+    let input = "BEGIN:VCALENDAR\r\n\
+                  VERSION:2.0\r\n\
+                  PRODID:-//Test//Test//EN\r\n\
+                  BEGIN:VEVENT\r\n\
+                  UID:test-rscale@example.com\r\n\
+                  DTSTAMP:20070423T123432Z\r\n\
+                  DTSTART;VALUE=DATE:20160229\r\n\
+                  RRULE:RSCALE=GREGORIAN;FREQ=YEARLY;SKIP=FORWARD\r\n\
+                  END:VEVENT\r\n\
+                  END:VCALENDAR\r\n";
+
+    let cals = Calendar::parse(input).expect("parse1");
+    let serialized = cals[0].to_ical();
+    assert!(serialized.contains("RRULE:FREQ=YEARLY;RSCALE=GREGORIAN;SKIP=FORWARD\r\n"));
+
+    let cals2 = Calendar::parse(&serialized).expect("parse2");
+    assert_eq!(cals, cals2);
+}
+
 /// Round-trip a calendar with a VTIMEZONE.
 #[test]
 fn round_trip_timezone() {
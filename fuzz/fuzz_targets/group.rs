@@ -0,0 +1,11 @@
+#![no_main]
+
+use jscalendar::{json::TryFromJson, model::object::Group};
+use libfuzzer_sys::fuzz_target;
+use serde_json::Value;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(json) = serde_json::from_slice::<Value>(data) {
+        let _ = Group::try_from_json(json);
+    }
+});
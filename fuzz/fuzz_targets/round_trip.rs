@@ -0,0 +1,43 @@
+#![no_main]
+
+//! Checks that every object which parses is serialized as JSON which parses again, into an
+//! object which is serialized in the same way.
+
+use std::fmt::Debug;
+
+use jscalendar::{
+    json::{IntoJson, TryFromJson},
+    model::{
+        object::{Event, Group, Task},
+        rrule::RRule,
+    },
+};
+use libfuzzer_sys::fuzz_target;
+use serde_json::Value;
+
+fn round_trip<T>(json: Value)
+where
+    T: TryFromJson<Value, Error: Debug> + IntoJson<Value>,
+{
+    let Ok(parsed) = T::try_from_json(json) else {
+        return;
+    };
+    let serialized: Value = parsed.into_json();
+    let reparsed = T::try_from_json(serialized.clone())
+        .unwrap_or_else(|error| panic!("{serialized} does not parse again: {error:?}"));
+    let reserialized: Value = reparsed.into_json();
+    assert_eq!(serialized, reserialized);
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(json) = serde_json::from_slice::<Value>(data) else {
+        return;
+    };
+    match json.get("@type").and_then(Value::as_str) {
+        Some("Event") => round_trip::<Event<Value>>(json),
+        Some("Task") => round_trip::<Task<Value>>(json),
+        Some("Group") => round_trip::<Group<Value>>(json),
+        Some("RecurrenceRule") => round_trip::<RRule>(json),
+        _ => {}
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use jscalendar::{json::TryFromJson, model::object::Task};
+use libfuzzer_sys::fuzz_target;
+use serde_json::Value;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(json) = serde_json::from_slice::<Value>(data) {
+        let _ = Task::try_from_json(json);
+    }
+});
@@ -0,0 +1,69 @@
+//! Differences between two objects.
+//!
+//! The differences are collected into a [`PatchObject`] which turns the old object into the new
+//! one, and described by [`describe_changes`]. Objects are compared property by property, and
+//! arrays as a whole.
+
+use std::fmt::Write;
+
+use jscalendar::{
+    json::TryFromJson,
+    model::{audit::describe_changes, object::PatchObject},
+};
+use serde_json::{Map, Value};
+
+use crate::{Args, Result, output, read_object};
+
+pub fn diff(args: Args) -> Result<bool> {
+    let [old, new] = args.operands.as_slice() else {
+        return Err("diff expects two files".into());
+    };
+    let old = read_object(old)?.into_json();
+    let new = read_object(new)?.into_json();
+
+    let mut patches = Map::new();
+    collect_patches(&old, &new, "", &mut patches);
+    let patch = PatchObject::try_from_json(Value::Object(patches))
+        .map_err(|error| format!("invalid patch: {error}"))?;
+
+    let changes = describe_changes(&old, &patch);
+    let mut report = String::new();
+    for change in &changes {
+        writeln!(report, "{}: {change}", change.pointer)?;
+    }
+    output(&report)?;
+    Ok(changes.is_empty())
+}
+
+/// Adds the patches which turn `old` into `new` to `patches`, where `pointer` is the location of
+/// both values.
+fn collect_patches(old: &Value, new: &Value, pointer: &str, patches: &mut Map<String, Value>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let pointer = child(pointer, key);
+                match new.get(key) {
+                    Some(new_value) => collect_patches(old_value, new_value, &pointer, patches),
+                    None => _ = patches.insert(pointer, Value::Null),
+                }
+            }
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    patches.insert(child(pointer, key), new_value.clone());
+                }
+            }
+        }
+        _ if old != new => _ = patches.insert(pointer.to_owned(), new.clone()),
+        _ => {}
+    }
+}
+
+/// Appends the segment `key` to `pointer`, escaping it as in RFC 6901 §3.
+fn child(pointer: &str, key: &str) -> String {
+    let key = key.replace('~', "~0").replace('/', "~1");
+    if pointer.is_empty() {
+        key
+    } else {
+        format!("{pointer}/{key}")
+    }
+}
@@ -0,0 +1,119 @@
+//! Expansion of recurring events into their occurrences.
+//!
+//! Time zones are not resolved, so the occurrences of an event are computed in its own time
+//! zone, in which the window is also given.
+
+use std::ops::Range;
+
+use jscalendar::{
+    json::{IntoJson, TryFromJson},
+    model::{
+        object::Event,
+        time::{DateTime, Duration, ExactDuration, Local, Utc},
+    },
+};
+use serde_json::Value;
+
+use crate::{Args, Object, Result, output, read_object, to_canonical};
+
+/// The number of occurrences materialized unless another limit is given with `--limit`.
+const DEFAULT_LIMIT: usize = 1000;
+
+/// The number of occurrences in the window after which expansion stops.
+const MAX_OCCURRENCES: usize = 100_000;
+
+pub fn expand(args: Args) -> Result<bool> {
+    let window = args
+        .option("window")
+        .ok_or("expand requires --window <start>/<end>")?;
+    let range = parse_window(window)?;
+    let limit = match args.option("limit") {
+        Some(limit) => limit
+            .parse()
+            .map_err(|_| format!("--limit expects a number, not {limit}"))?,
+        None => DEFAULT_LIMIT,
+    };
+
+    let Object::Event(event) = read_object(args.file()?)? else {
+        return Err("only events can be expanded".into());
+    };
+    let occurrences = occurrences(&event, &range)?;
+    let (group, report) = event.materialize(range, limit, |_, _| occurrences);
+
+    for recurrence_id in &report.invalid {
+        eprintln!("warning: omitted the invalid occurrence at {recurrence_id}");
+    }
+    if report.truncated > 0 {
+        eprintln!(
+            "warning: omitted {} occurrences after the limit of {limit}",
+            report.truncated
+        );
+    }
+
+    output(&format!("{}\n", to_canonical(&group.into_json())?))?;
+    Ok(true)
+}
+
+/// Parses a window of the form `start/end`, where both are local date-times.
+fn parse_window(window: &str) -> Result<Range<DateTime<Local>>> {
+    let (start, end) = window
+        .split_once('/')
+        .ok_or("--window expects the form <start>/<end>")?;
+    let [start, end] = [start, end].map(|time| {
+        DateTime::<Local>::try_from_json(Value::from(time))
+            .map_err(|_| format!("--window expects local date-times, not {time}"))
+    });
+    let (start, end) = (start?, end?);
+
+    if end <= start {
+        return Err("the window ends before it starts".into());
+    }
+    Ok(start..end)
+}
+
+/// Returns the recurrence IDs of the occurrences of `event` in `range`.
+fn occurrences(
+    event: &Event<Value>,
+    range: &Range<DateTime<Local>>,
+) -> Result<Vec<DateTime<Local>>> {
+    let mut occurrences = Vec::new();
+    let mut after = just_before(range.start).ok_or("the window is out of range")?;
+    while occurrences.len() < MAX_OCCURRENCES {
+        let Some(next) = event.next_occurrence(after, |_, _| None)? else {
+            break;
+        };
+        if next >= range.end {
+            break;
+        }
+        occurrences.push(next);
+        after = as_utc(next);
+    }
+    Ok(occurrences)
+}
+
+/// Returns `true` iff `event` has an occurrence at `recurrence_id`, or if its recurrence rules
+/// cannot be expanded.
+pub fn occurs(event: &Event<Value>, recurrence_id: DateTime<Local>) -> bool {
+    let Some(after) = just_before(recurrence_id) else {
+        return true;
+    };
+    match event.next_occurrence(after, |_, _| None) {
+        Ok(next) => next == Some(recurrence_id),
+        Err(_) => true,
+    }
+}
+
+/// Returns the instant one nanosecond before `time`, which [`Event::next_occurrence`] treats as
+/// being in the time zone of the event since no offsets are resolved.
+fn just_before(time: DateTime<Local>) -> Option<DateTime<Utc>> {
+    let nanosecond = Duration::Exact(ExactDuration::from_nanos(1)?);
+    time.checked_sub(nanosecond).map(as_utc)
+}
+
+fn as_utc(time: DateTime<Local>) -> DateTime<Utc> {
+    DateTime {
+        date: time.date,
+        time: time.time,
+        marker: Utc,
+    }
+}
@@ -0,0 +1,96 @@
+//! Conversion between JSCalendar objects and iCalendar text.
+//!
+//! Both directions go through jCal (RFC 7265): iCalendar text is parsed by `calico` and written
+//! as jCal, which [`jscalendar::jcal`] converts into events and tasks. In the other direction,
+//! the jCal produced by [`jscalendar::jcal`] is written as iCalendar text by `calico`.
+
+use calico::{
+    parser::{component::icalendar_stream, escaped::AsEscaped},
+    serializer::write_ical_from_jcal,
+};
+use jscalendar::{
+    jcal::{entries_from_jcal, entries_to_jcal},
+    model::{
+        object::{Group, TaskOrEvent},
+        string::Uid,
+    },
+};
+use serde_json::Value;
+use winnow::error::InputError;
+
+use crate::{
+    Args, Object, Result, display_name, now, output, read_input, read_object, to_canonical,
+    warn_dropped,
+};
+
+/// The PRODID of converted calendars, unless another is given with `--prod-id`.
+const PROD_ID: &str = concat!("-//jscalendar-cli//", env!("CARGO_PKG_VERSION"), "//EN");
+
+pub fn convert(args: Args) -> Result<bool> {
+    let path = args.file()?;
+    match (args.option("from"), args.option("to")) {
+        (Some("ics"), None) => from_ics(path, args.option("group-uid")),
+        (None, Some("ics")) => to_ics(path, args.option("prod-id").unwrap_or(PROD_ID)),
+        (Some(format), None) | (None, Some(format)) => {
+            Err(format!("unsupported format {format}; expected ics").into())
+        }
+        _ => Err("expected either --from ics or --to ics".into()),
+    }
+}
+
+/// Converts the events and tasks of the calendars in an iCalendar file into a single object.
+fn from_ics(path: &str, group_uid: Option<&str>) -> Result<bool> {
+    let text = read_input(path)?;
+    let name = display_name(path);
+    let mut input = text.as_escaped();
+    let calendars = icalendar_stream::<_, InputError<_>>(&mut input)
+        .map_err(|_| format!("{name}: not a valid iCalendar stream"))?;
+    if !input.is_empty() {
+        return Err(format!("{name}: unexpected input after the last calendar").into());
+    }
+
+    let mut entries = Vec::new();
+    for calendar in calendars {
+        let jcal: Value = serde_json::from_str(&calendar.to_jcal())?;
+        let (converted, dropped) = entries_from_jcal(jcal)?;
+        warn_dropped(&dropped);
+        entries.extend(converted);
+    }
+
+    let object = match (group_uid, entries.pop()) {
+        (Some(uid), last) => {
+            entries.extend(last);
+            Object::Group(Group::new(entries, Uid::new(uid)?.into()))
+        }
+        (None, Some(TaskOrEvent::Event(event))) if entries.is_empty() => Object::Event(event),
+        (None, Some(TaskOrEvent::Task(task))) if entries.is_empty() => Object::Task(task),
+        (None, None) => return Err(format!("{name}: no events or tasks to convert").into()),
+        (None, Some(_)) => {
+            let count = entries.len() + 1;
+            return Err(format!(
+                "{name}: found {count} events and tasks; pass --group-uid to convert them into a group"
+            )
+            .into());
+        }
+    };
+
+    output(&format!("{}\n", to_canonical(&object.into_json())?))?;
+    Ok(true)
+}
+
+/// Converts an object into an iCalendar file with a single calendar.
+fn to_ics(path: &str, prod_id: &str) -> Result<bool> {
+    let entries = match read_object(path)? {
+        Object::Event(event) => vec![TaskOrEvent::Event(event)],
+        Object::Task(task) => vec![TaskOrEvent::Task(task)],
+        Object::Group(group) => group.entries().clone(),
+    };
+
+    let (jcal, dropped): (Value, _) = entries_to_jcal(entries, prod_id, now()?);
+    warn_dropped(&dropped);
+
+    let mut ics = String::new();
+    write_ical_from_jcal(&jcal.to_string(), &mut ics)?;
+    output(&ics)?;
+    Ok(true)
+}
@@ -0,0 +1,354 @@
+//! A command-line tool for validating and converting JSCalendar (RFC 8984) objects.
+//!
+//! Each subcommand is built on the public API of the `jscalendar` and `calico` crates. Files
+//! which are named `-` or omitted are read from standard input. The exit status is 0 on success,
+//! 1 if a check failed (an invalid or non-canonical object, or a difference between two
+//! objects), and 2 if the command could not be run.
+
+mod diff;
+mod expand;
+mod ics;
+
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::Write as _,
+    io::{ErrorKind, Read, Write as _},
+    process::ExitCode,
+    time::SystemTime,
+};
+
+use jscalendar::{
+    json::{IntoJson, TryFromJson},
+    model::{
+        object::{Event, Group, Task, TaskOrEvent},
+        time::{
+            Date, DateTime, Day, Duration, ExactDuration, Hour, Minute, Month, Second, Time, Utc,
+            Year,
+        },
+    },
+};
+use serde_json::Value;
+
+const USAGE: &str = "\
+usage: jscalendar-cli <command> [options] [file...]
+
+commands:
+  validate [--strict] [file...]
+      Check objects against RFC 8984 and report sanity violations as warnings,
+      which --strict treats as errors.
+  fmt [--check] [file]
+      Print an object in canonical form, or with --check, fail if it is not.
+  convert --to ics [--prod-id <id>] [file]
+      Convert an event, task, or group into iCalendar.
+  convert --from ics [--group-uid <uid>] [file]
+      Convert iCalendar into an event or task, or into a group with the given
+      UID if there is more than one.
+  expand --window <start>/<end> [--limit <n>] [file]
+      Materialize the occurrences of an event whose recurrence IDs lie in the
+      window, which is given as local date-times in the time zone of the event.
+  diff <old> <new>
+      Describe the changes between two objects, failing if there are any.
+
+Files which are named `-` or omitted are read from standard input.
+";
+
+type Result<T, E = Box<dyn Error>> = std::result::Result<T, E>;
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let Some(command) = args.next() else {
+        eprint!("{USAGE}");
+        return ExitCode::from(2);
+    };
+
+    let result = match command.as_str() {
+        "validate" => Args::parse(args, &["strict"], &[]).and_then(validate),
+        "fmt" => Args::parse(args, &["check"], &[]).and_then(fmt),
+        "convert" => {
+            Args::parse(args, &[], &["from", "to", "prod-id", "group-uid"]).and_then(ics::convert)
+        }
+        "expand" => Args::parse(args, &[], &["window", "limit"]).and_then(expand::expand),
+        "diff" => Args::parse(args, &[], &[]).and_then(diff::diff),
+        "help" | "-h" | "--help" => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        _ => Err(format!("unknown command {command}; see `jscalendar-cli help`").into()),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// The operands, flags, and options of a subcommand.
+struct Args {
+    operands: Vec<String>,
+    flags: Vec<&'static str>,
+    options: HashMap<&'static str, String>,
+}
+
+impl Args {
+    /// Parses the arguments of a subcommand which accepts the given flags, and the given options
+    /// which take a value as either `--name value` or `--name=value`.
+    fn parse(
+        args: impl IntoIterator<Item = String>,
+        flags: &[&'static str],
+        options: &[&'static str],
+    ) -> Result<Self> {
+        let mut parsed = Args {
+            operands: Vec::new(),
+            flags: Vec::new(),
+            options: HashMap::new(),
+        };
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                parsed.operands.extend(args);
+                break;
+            }
+            let Some(name) = arg.strip_prefix("--") else {
+                parsed.operands.push(arg);
+                continue;
+            };
+
+            let (name, value) = match name.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_owned())),
+                None => (name, None),
+            };
+            if let Some(&flag) = flags.iter().find(|&&flag| flag == name)
+                && value.is_none()
+            {
+                parsed.flags.push(flag);
+            } else if let Some(&option) = options.iter().find(|&&option| option == name) {
+                let value = match value {
+                    Some(value) => value,
+                    None => args
+                        .next()
+                        .ok_or_else(|| format!("--{name} requires a value"))?,
+                };
+                parsed.options.insert(option, value);
+            } else {
+                return Err(format!("unknown option --{name}").into());
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.flags.contains(&name)
+    }
+
+    fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(String::as_str)
+    }
+
+    /// Returns the only operand, or `-` if there are none.
+    fn file(&self) -> Result<&str> {
+        match self.operands.as_slice() {
+            [] => Ok("-"),
+            [file] => Ok(file),
+            _ => Err("expected at most one file".into()),
+        }
+    }
+}
+
+/// A JSCalendar object of any type.
+enum Object {
+    Event(Event<Value>),
+    Task(Task<Value>),
+    Group(Group<Value>),
+}
+
+impl Object {
+    /// Parses JSON text as the object type named by its `@type` property.
+    fn parse(text: &str) -> Result<Self, String> {
+        let json: Value = serde_json::from_str(text).map_err(|error| error.to_string())?;
+        match json.get("@type").and_then(Value::as_str) {
+            Some("Event") => Event::try_from_json(json).map(Object::Event),
+            Some("Task") => Task::try_from_json(json).map(Object::Task),
+            Some("Group") => Group::try_from_json(json).map(Object::Group),
+            _ => return Err("@type: expected \"Event\", \"Task\", or \"Group\"".into()),
+        }
+        .map_err(|error| error.to_string())
+    }
+
+    fn into_json(self) -> Value {
+        match self {
+            Object::Event(event) => event.into_json(),
+            Object::Task(task) => task.into_json(),
+            Object::Group(group) => group.into_json(),
+        }
+    }
+
+    /// Returns the sanity violations of this object, where `now` is used to decide whether time
+    /// zones have expired.
+    fn violations(&self, now: DateTime<Utc>) -> Vec<String> {
+        let violations = match self {
+            Object::Event(event) => event.temporal_violations(now),
+            Object::Task(task) => task.temporal_violations(now),
+            Object::Group(group) => {
+                let integrity = group.check_integrity(|master, recurrence_id| match master {
+                    TaskOrEvent::Event(event) => expand::occurs(event, recurrence_id),
+                    _ => true,
+                });
+                let entries = group
+                    .entries()
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(index, entry)| {
+                        let violations = match entry {
+                            TaskOrEvent::Event(event) => event.temporal_violations(now),
+                            TaskOrEvent::Task(task) => task.temporal_violations(now),
+                            _ => Vec::new(),
+                        };
+                        violations.into_iter().map(move |violation| {
+                            format!(
+                                "{} at entries/{index}/{}",
+                                violation.code, violation.pointer
+                            )
+                        })
+                    });
+                let integrity = integrity.iter().map(ToString::to_string);
+                return integrity.chain(entries).collect();
+            }
+        };
+        violations.iter().map(ToString::to_string).collect()
+    }
+}
+
+/// Reads the file at `path`, or standard input if `path` is `-`.
+fn read_input(path: &str) -> Result<String> {
+    if path == "-" {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text)?;
+        Ok(text)
+    } else {
+        std::fs::read_to_string(path).map_err(|error| format!("{path}: {error}").into())
+    }
+}
+
+/// Reads and parses the object in the file at `path`.
+fn read_object(path: &str) -> Result<Object> {
+    let text = read_input(path)?;
+    Object::parse(&text).map_err(|error| format!("{}: {error}", display_name(path)).into())
+}
+
+/// Returns the name of the file at `path` for use in messages.
+fn display_name(path: &str) -> &str {
+    if path == "-" { "<stdin>" } else { path }
+}
+
+/// Serializes JSON in canonical form, with sorted keys and two-space indentation.
+fn to_canonical(json: &Value) -> Result<String> {
+    Ok(serde_json::to_string_pretty(&sort_keys(json.clone()))?)
+}
+
+/// Sorts the members of every object in `json` by key, which a `Map` does not do by itself if
+/// serde_json is built with its `preserve_order` feature.
+fn sort_keys(json: Value) -> Value {
+    match json {
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        Value::Object(object) => {
+            let mut members: Vec<_> = object.into_iter().collect();
+            members.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+            let members = members
+                .into_iter()
+                .map(|(key, value)| (key, sort_keys(value)));
+            Value::Object(members.collect())
+        }
+        json => json,
+    }
+}
+
+/// Writes `text` to standard output, stopping quietly if the reader has closed it.
+fn output(text: &str) -> Result<()> {
+    match std::io::stdout().lock().write_all(text.as_bytes()) {
+        Err(error) if error.kind() == ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+/// Reports the names of the properties and components which were dropped by a conversion.
+fn warn_dropped(dropped: &[String]) {
+    let mut dropped = dropped.to_vec();
+    dropped.dedup();
+    for name in dropped {
+        eprintln!("warning: dropped {name}");
+    }
+}
+
+/// Returns the current time in UTC.
+fn now() -> Result<DateTime<Utc>> {
+    let elapsed = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?;
+    let epoch = DateTime {
+        date: Date::new(Year::new(1970)?, Month::Jan, Day::D01)?,
+        time: Time::new(Hour::H00, Minute::M00, Second::S00, None)?,
+        marker: Utc,
+    };
+    ExactDuration::from_nanos(elapsed.as_nanos())
+        .and_then(|elapsed| epoch.checked_add(Duration::Exact(elapsed)))
+        .ok_or_else(|| "the system time is out of range".into())
+}
+
+fn validate(args: Args) -> Result<bool> {
+    let strict = args.flag("strict");
+    let now = now()?;
+    let files = match args.operands.as_slice() {
+        [] => &["-".to_owned()][..],
+        files => files,
+    };
+
+    let mut valid = true;
+    let mut report = String::new();
+    for path in files {
+        let name = display_name(path);
+        let object = match Object::parse(&read_input(path)?) {
+            Ok(object) => object,
+            Err(error) => {
+                writeln!(report, "{name}: invalid: {error}")?;
+                valid = false;
+                continue;
+            }
+        };
+
+        let violations = object.violations(now);
+        if violations.is_empty() {
+            writeln!(report, "{name}: valid")?;
+        }
+        for violation in &violations {
+            let severity = if strict { "error" } else { "warning" };
+            writeln!(report, "{name}: {severity}: {violation}")?;
+        }
+        valid &= !strict || violations.is_empty();
+    }
+
+    output(&report)?;
+    Ok(valid)
+}
+
+fn fmt(args: Args) -> Result<bool> {
+    let path = args.file()?;
+    let text = read_input(path)?;
+    let name = display_name(path);
+    let object = Object::parse(&text).map_err(|error| format!("{name}: {error}"))?;
+    let canonical = to_canonical(&object.into_json())?;
+
+    if !args.flag("check") {
+        output(&format!("{canonical}\n"))?;
+        Ok(true)
+    } else if text.trim_end() == canonical {
+        Ok(true)
+    } else {
+        eprintln!("{name}: not in canonical form");
+        Ok(false)
+    }
+}
@@ -0,0 +1,185 @@
+use std::{
+    io::Write,
+    path::PathBuf,
+    process::{Command, Output, Stdio},
+};
+
+const ICS: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example Corp.//Example Client//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:a8df6573-0474-496d-8496-033ad45d7fea\r\n\
+DTSTAMP:20200102T182304Z\r\n\
+SUMMARY:Team meeting\\, weekly\r\n\
+DTSTART;TZID=America/New_York:20200115T130000\r\n\
+DURATION:PT1H30M\r\n\
+RRULE:FREQ=WEEKLY;COUNT=10\r\n\
+EXDATE;TZID=America/New_York:20200122T130000\r\n\
+X-EXAMPLE:dropped\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+/// Runs the CLI with the given arguments and standard input.
+fn run(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_jscalendar-cli"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+fn stderr(output: &Output) -> &str {
+    std::str::from_utf8(&output.stderr).unwrap()
+}
+
+/// Writes `contents` to a file in the test directory and returns its path.
+fn write_file(name: &str, contents: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+fn event_json() -> String {
+    let output = run(&["convert", "--from", "ics"], ICS);
+    assert!(output.status.success(), "{}", stderr(&output));
+    stdout(&output).to_owned()
+}
+
+#[test]
+fn convert_round_trip() {
+    let output = run(&["convert", "--from", "ics"], ICS);
+    assert!(output.status.success());
+    assert_eq!(stderr(&output), "warning: dropped x-example\n");
+
+    let json: serde_json::Value = serde_json::from_str(stdout(&output)).unwrap();
+    assert_eq!(json["title"], "Team meeting, weekly");
+    assert_eq!(json["timeZone"], "America/New_York");
+    assert_eq!(json["recurrenceRules"][0]["count"], 10);
+
+    let output = run(
+        &["convert", "--to", "ics", "--prod-id", "-//Test//EN"],
+        &event_json(),
+    );
+    assert!(output.status.success());
+    let ics = stdout(&output);
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Test//EN\r\n"));
+    assert!(ics.contains("\r\nSUMMARY:Team meeting\\, weekly\r\n"));
+    assert!(ics.contains("\r\nDTSTART;TZID=America/New_York:20200115T130000\r\n"));
+    assert!(ics.contains("\r\nRRULE:FREQ=WEEKLY;COUNT=10\r\n"));
+
+    let output = run(&["convert", "--from", "ics"], ics);
+    assert_eq!(stdout(&output), event_json());
+
+    let output = run(&["convert", "--from", "ics"], &ICS.repeat(2));
+    assert_eq!(output.status.code(), Some(2));
+    let output = run(
+        &["convert", "--from", "ics", "--group-uid", "g"],
+        &ICS.repeat(2),
+    );
+    let json: serde_json::Value = serde_json::from_str(stdout(&output)).unwrap();
+    assert_eq!(json["entries"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn validate_and_fmt() {
+    let output = run(&["validate"], &event_json());
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "<stdin>: valid\n");
+
+    let invalid =
+        r#"{"@type": "Event", "uid": "a", "start": "2020-01-15T13:00:00", "sequence": 1.5}"#;
+    let output = run(&["validate"], invalid);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).starts_with("<stdin>: invalid: sequence: "));
+
+    let zero =
+        r#"{"@type": "Event", "uid": "a", "start": "2020-01-15T13:00:00", "duration": "PT0S"}"#;
+    let output = run(&["validate"], zero);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        "<stdin>: warning: zero-duration at duration\n"
+    );
+    let output = run(&["validate", "--strict"], zero);
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = run(&["fmt"], zero);
+    assert_eq!(
+        stdout(&output),
+        "{\n  \"@type\": \"Event\",\n  \"duration\": \"PT0S\",\n  \"start\": \"2020-01-15T13:00:00\",\n  \"uid\": \"a\"\n}\n"
+    );
+    assert!(run(&["fmt", "--check"], stdout(&output)).status.success());
+    assert_eq!(run(&["fmt", "--check"], zero).status.code(), Some(1));
+}
+
+#[test]
+fn expand_window() {
+    let args = [
+        "expand",
+        "--window",
+        "2020-01-01T00:00:00/2020-02-20T00:00:00",
+        "--limit",
+        "3",
+    ];
+    let output = run(&args, &event_json());
+    assert!(output.status.success());
+    assert_eq!(
+        stderr(&output),
+        "warning: omitted 2 occurrences after the limit of 3\n"
+    );
+
+    let json: serde_json::Value = serde_json::from_str(stdout(&output)).unwrap();
+    let recurrence_ids: Vec<_> = json["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["recurrenceId"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        recurrence_ids,
+        [
+            "2020-01-15T13:00:00",
+            "2020-01-29T13:00:00",
+            "2020-02-05T13:00:00"
+        ]
+    );
+}
+
+#[test]
+fn diff_objects() {
+    let old = event_json();
+    let mut new: serde_json::Value = serde_json::from_str(&old).unwrap();
+    new["title"] = "Standup".into();
+    new["recurrenceRules"][0]["count"] = 5.into();
+    new.as_object_mut().unwrap().remove("duration");
+
+    let old = write_file("diff-old.json", &old);
+    let new = write_file("diff-new.json", &new.to_string());
+    let output = run(&["diff", old.to_str().unwrap(), new.to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output).lines().collect::<Vec<_>>(),
+        [
+            "duration: Duration was removed (previously PT1H30M)",
+            "recurrenceRules: Recurrence rules changed from […] to […]",
+            "title: Title changed from Team meeting, weekly to Standup",
+        ]
+    );
+
+    let output = run(&["diff", old.to_str().unwrap(), old.to_str().unwrap()], "");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+}
@@ -0,0 +1,87 @@
+//! Compares the JSON backends at parsing a large group of events, from text to the object model.
+//!
+//! Run with `cargo bench -p jscalendar --features ijson,serde_json`.
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use ijson::IValue;
+use jscalendar::{json::TryFromJson, model::object::Group};
+use serde_json::{Value, json};
+
+/// Returns the text of a group of `n` events, each with a few locations and participants.
+fn group(n: usize) -> String {
+    let entries: Vec<Value> = (0..n)
+        .map(|i| {
+            json!({
+                "@type": "Event",
+                "uid": format!("bench-{i}"),
+                "updated": "2024-01-10T09:00:00Z",
+                "start": "2024-01-15T13:00:00",
+                "timeZone": "Europe/Berlin",
+                "duration": "PT1H30M",
+                "title": format!("Meeting {i}"),
+                "description": "Quarterly planning with the whole team.",
+                "keywords": { "work": true, "planning": true },
+                "locations": {
+                    "l1": { "@type": "Location", "name": "Room 4", "coordinates": "geo:52.5,13.4" },
+                    "l2": { "@type": "VirtualLocation", "uri": "https://example.com/call" }
+                },
+                "participants": {
+                    "p1": {
+                        "@type": "Participant",
+                        "name": "Alice",
+                        "sendTo": { "imip": "mailto:alice@example.com" },
+                        "roles": { "owner": true, "attendee": true },
+                        "participationStatus": "accepted"
+                    },
+                    "p2": {
+                        "@type": "Participant",
+                        "name": "Bob",
+                        "sendTo": { "imip": "mailto:bob@example.com" },
+                        "roles": { "attendee": true },
+                        "participationStatus": "needs-action"
+                    }
+                },
+                "alerts": {
+                    "a1": {
+                        "@type": "Alert",
+                        "trigger": { "@type": "OffsetTrigger", "offset": "-PT15M" }
+                    }
+                },
+                "recurrenceRules": [{ "@type": "RecurrenceRule", "frequency": "weekly", "count": 10 }]
+            })
+        })
+        .collect();
+
+    json!({
+        "@type": "Group",
+        "uid": "bench-group",
+        "title": "A large group",
+        "entries": entries
+    })
+    .to_string()
+}
+
+fn parse(c: &mut Criterion) {
+    let mut bench = c.benchmark_group("parse_group");
+    for n in [100, 1000] {
+        let text = group(n);
+        bench.throughput(Throughput::Bytes(text.len() as u64));
+
+        bench.bench_with_input(BenchmarkId::new("serde_json", n), &text, |b, text| {
+            b.iter(|| {
+                let value: Value = serde_json::from_str(text).unwrap();
+                Group::<Value>::try_from_json(value).unwrap()
+            })
+        });
+        bench.bench_with_input(BenchmarkId::new("ijson", n), &text, |b, text| {
+            b.iter(|| {
+                let value: IValue = serde_json::from_str(text).unwrap();
+                Group::<IValue>::try_from_json(value).unwrap()
+            })
+        });
+    }
+    bench.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -0,0 +1,563 @@
+//! Conversions between the iCalendar data model of `calico` and JSCalendar objects.
+//!
+//! # Events
+//!
+//! [`Event::from_ics_str`] and [`Event::to_ics_string`] convert an event to and from iCalendar
+//! text. Both go through jCal (RFC 7265): the text is parsed by `calico` and converted by the
+//! [`jcal`](crate::jcal) module, so the properties are mapped as described there, and whatever
+//! cannot be mapped is reported by name.
+//!
+//! # Alarms
+//!
+//! RFC 8984 §4.5.2 defines a much smaller alert model than RFC 5545 §3.6.6: an [`Alert`] has a
+//! trigger, an acknowledgement time, and an action, but no text, attachments, or repetition. The
+//! conversions in this module map the shared parts exactly and report everything else as an
+//! [`AlarmLoss`], so that callers can decide whether a lossy conversion is acceptable.
+//!
+//! | VALARM                   | [`Alert`]                                  |
+//! |--------------------------|--------------------------------------------|
+//! | `TRIGGER` (duration)     | [`OffsetTrigger::offset`]                  |
+//! | `RELATED` parameter      | [`OffsetTrigger::relative_to`]             |
+//! | `TRIGGER` (date-time)    | [`AbsoluteTrigger::when`]                  |
+//! | `ACKNOWLEDGED`           | [`Alert::acknowledged`]                    |
+//! | `ACTION:DISPLAY`         | `action: "display"`                        |
+//! | `ACTION:EMAIL`           | `action: "email"`                          |
+//! | `ACTION:AUDIO` and other | `action` with the lowercase action name    |
+
+use std::fmt;
+
+use calico::{
+    model::{
+        component::{Alarm, AudioAlarm, Calendar, DisplayAlarm, EmailAlarm, OtherAlarm},
+        parameter::Params,
+        primitive::{DateTime, TriggerValue, Utc},
+        property::Prop,
+    },
+    parser::error::ParseError,
+    serializer::write_ical_from_jcal,
+};
+use rfc5545_types::set::TriggerRelation;
+use thiserror::Error;
+
+use crate::{
+    jcal::{JcalError, entries_from_jcal, entries_to_jcal},
+    json::{
+        ConstructibleJsonValue, DestructibleJsonValue, JsonArray, JsonObject, JsonValue, ValueType,
+    },
+    model::object::{Alert, Event, TaskOrEvent},
+    valarm::{Valarm, ValarmTrigger},
+};
+
+#[cfg(doc)]
+use crate::model::object::{AbsoluteTrigger, OffsetTrigger};
+
+/// The PRODID of the calendars written by [`Event::to_ics_string`].
+const PROD_ID: &str = concat!("-//jscalendar//", env!("CARGO_PKG_VERSION"), "//EN");
+
+/// A part of an alarm which could not be represented after a conversion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlarmLoss {
+    /// The UID property of the VALARM.
+    Uid,
+    /// The DESCRIPTION property of the VALARM.
+    Description,
+    /// The SUMMARY property of the VALARM.
+    Summary,
+    /// The REPEAT and DURATION properties of the VALARM, which have no JSCalendar equivalent.
+    Repeat,
+    /// The ATTENDEE properties of the VALARM.
+    Attendees,
+    /// The ATTACH properties of the VALARM.
+    Attachments,
+    /// The non-standard properties of the VALARM.
+    XProperties,
+    /// The `relatedTo` property of the [`Alert`].
+    RelatedTo,
+    /// The vendor-specific properties of the [`Alert`] or its trigger.
+    VendorProperties,
+}
+
+impl fmt::Display for AlarmLoss {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Uid => "UID",
+            Self::Description => "DESCRIPTION",
+            Self::Summary => "SUMMARY",
+            Self::Repeat => "REPEAT and DURATION",
+            Self::Attendees => "ATTENDEE",
+            Self::Attachments => "ATTACH",
+            Self::XProperties => "non-standard properties",
+            Self::RelatedTo => "relatedTo",
+            Self::VendorProperties => "vendor-specific properties",
+        })
+    }
+}
+
+/// An error indicating that an [`Alert`] has a trigger with an unrecognized `@type`, which cannot
+/// be represented as a TRIGGER property.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownTriggerError;
+
+impl fmt::Display for UnknownTriggerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the alert trigger has an unknown type")
+    }
+}
+
+impl std::error::Error for UnknownTriggerError {}
+
+/// Converts a VALARM into an [`Alert`], returning the parts of `alarm` which were lost.
+///
+/// The AUDIO action and any other unregistered action are preserved as unknown action tokens, so
+/// that [`alert_to_alarm`] can restore them.
+pub fn alarm_to_alert<V: JsonValue>(alarm: &Alarm) -> (Alert<V>, Vec<AlarmLoss>) {
+    let parts = AlarmParts::of(alarm);
+    let mut losses = Vec::new();
+
+    let trigger = match parts.trigger.value {
+        TriggerValue::Duration(offset) => ValarmTrigger::Offset {
+            offset,
+            end: parts.trigger.params.trigger_relationship() == Some(&TriggerRelation::End),
+        },
+        TriggerValue::DateTime(when) => ValarmTrigger::Absolute(when),
+    };
+
+    let alert = Valarm {
+        action: parts.action,
+        trigger,
+        acknowledged: parts.acknowledged.map(|acknowledged| acknowledged.value),
+    }
+    .into_alert();
+
+    let mut report = |lost: bool, loss| {
+        if lost {
+            losses.push(loss);
+        }
+    };
+
+    report(parts.uid, AlarmLoss::Uid);
+    report(parts.description, AlarmLoss::Description);
+    report(parts.summary, AlarmLoss::Summary);
+    report(parts.repeat, AlarmLoss::Repeat);
+    report(parts.attendees, AlarmLoss::Attendees);
+    report(parts.attachments, AlarmLoss::Attachments);
+    report(parts.x_properties, AlarmLoss::XProperties);
+
+    (alert, losses)
+}
+
+/// Converts an [`Alert`] into a VALARM, returning the parts of `alert` which were lost.
+///
+/// JSCalendar alerts have no text, so `description` is used for the DESCRIPTION property of
+/// every alarm but an AUDIO alarm, and the SUMMARY property of EMAIL alarms; the title of the
+/// calendar object is usually a good choice. An alert without an action becomes a DISPLAY alarm
+/// (RFC 8984 §4.5.2).
+pub fn alert_to_alarm<V: JsonValue>(
+    alert: &Alert<V>,
+    description: &str,
+) -> Result<(Alarm, Vec<AlarmLoss>), UnknownTriggerError> {
+    let (valarm, alert_losses) = Valarm::from_alert(alert).ok_or(UnknownTriggerError)?;
+
+    let trigger = match valarm.trigger {
+        ValarmTrigger::Offset { offset, end } => {
+            let mut params = Params::default();
+            if end {
+                params.set_trigger_relationship(TriggerRelation::End);
+            }
+            Prop {
+                value: TriggerValue::Duration(offset),
+                params,
+            }
+        }
+        ValarmTrigger::Absolute(when) => Prop::from_value(TriggerValue::DateTime(when)),
+    };
+
+    let text = || Prop::from_value(description.to_string());
+    let acknowledged = valarm.acknowledged.map(Prop::from_value);
+
+    let alarm = match valarm.action.as_str() {
+        "DISPLAY" => {
+            let mut alarm = DisplayAlarm::new(trigger, text());
+            if let Some(acknowledged) = acknowledged {
+                alarm.set_acknowledged(acknowledged);
+            }
+            Alarm::Display(alarm)
+        }
+        "EMAIL" => {
+            let mut alarm = EmailAlarm::new(trigger, text(), text());
+            if let Some(acknowledged) = acknowledged {
+                alarm.set_acknowledged(acknowledged);
+            }
+            Alarm::Email(alarm)
+        }
+        "AUDIO" => {
+            let mut alarm = AudioAlarm::new(trigger);
+            if let Some(acknowledged) = acknowledged {
+                alarm.set_acknowledged(acknowledged);
+            }
+            Alarm::Audio(alarm)
+        }
+        _ => {
+            let mut alarm = OtherAlarm::new(trigger, Prop::from_value(valarm.action));
+            alarm.set_description(text());
+            if let Some(acknowledged) = acknowledged {
+                alarm.set_acknowledged(acknowledged);
+            }
+            Alarm::Other(alarm)
+        }
+    };
+
+    let mut losses = Vec::new();
+    if alert_losses.related_to {
+        losses.push(AlarmLoss::RelatedTo);
+    }
+
+    if !alert_losses.vendor_properties.is_empty() {
+        losses.push(AlarmLoss::VendorProperties);
+    }
+
+    Ok((alarm, losses))
+}
+
+/// An error returned by [`Event::from_ics_str`].
+#[derive(Debug, Clone, PartialEq, Error)]
+#[non_exhaustive]
+pub enum IcsError {
+    /// The input is not a valid iCalendar stream.
+    #[error("invalid iCalendar stream: {0}")]
+    Parse(#[from] ParseError),
+    /// The input does not contain exactly one VEVENT component.
+    #[error("expected a single event but found {0}")]
+    EventCount(usize),
+    /// The VEVENT component could not be converted into an [`Event`].
+    #[error(transparent)]
+    Jcal(#[from] JcalError),
+}
+
+impl<V> Event<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    /// Parses an iCalendar stream with a single VEVENT component into an [`Event`], returning the
+    /// names of the properties and components which were dropped.
+    ///
+    /// The stream is converted as by [`entries_from_jcal`], and any VTODO components are reported
+    /// as dropped. An event with overridden occurrences is written in iCalendar as several
+    /// VEVENT components, and so is rejected.
+    pub fn from_ics_str(ics: &str) -> Result<(Self, Vec<String>), IcsError> {
+        let mut events = Vec::new();
+        let mut dropped = Vec::new();
+        for calendar in Calendar::parse(ics)? {
+            let jcal: serde_json::Value =
+                serde_json::from_str(&calendar.to_jcal()).expect("calico writes valid JSON");
+            let (entries, lost) = entries_from_jcal(convert(jcal))?;
+            dropped.extend(lost);
+            for entry in entries {
+                match entry {
+                    TaskOrEvent::Event(event) => events.push(event),
+                    TaskOrEvent::Task(_) => dropped.push("vtodo".into()),
+                }
+            }
+        }
+
+        dropped.sort();
+        dropped.dedup();
+        match <[_; 1]>::try_from(events) {
+            Ok([event]) => Ok((event, dropped)),
+            Err(events) => Err(IcsError::EventCount(events.len())),
+        }
+    }
+
+    /// Writes this event as an iCalendar stream with a single VCALENDAR, returning the names of
+    /// the JSCalendar properties which were dropped.
+    ///
+    /// The event is converted as by [`event_to_jcal`](crate::jcal::event_to_jcal), so `now` is
+    /// its DTSTAMP if it has neither an `updated` nor a `created` time.
+    pub fn to_ics_string(&self, now: DateTime<Utc>) -> (String, Vec<String>)
+    where
+        V::Object: Clone,
+    {
+        let entries = vec![TaskOrEvent::Event(self.clone())];
+        let (jcal, dropped) = entries_to_jcal(entries, PROD_ID, now);
+        let jcal: serde_json::Value = convert(jcal);
+
+        let mut ics = String::new();
+        write_ical_from_jcal(&jcal.to_string(), &mut ics)
+            .expect("entries_to_jcal writes well-formed jCal");
+        (ics, dropped)
+    }
+}
+
+/// Converts a JSON value from one representation into another.
+fn convert<A, B>(value: A) -> B
+where
+    A: DestructibleJsonValue,
+    B: ConstructibleJsonValue,
+{
+    let converted = match value.value_type() {
+        ValueType::Null => Ok(B::null()),
+        ValueType::Bool => value.try_as_bool().map(B::bool),
+        ValueType::Number => value.try_as_number().map(B::number),
+        ValueType::String => value.try_into_string().map(|s| B::string(s.into())),
+        ValueType::Array => value.try_into_array().map(|items| {
+            let mut array = B::Array::with_capacity(items.len());
+            for item in items.into_iter() {
+                array.push(convert(item));
+            }
+            B::array(array)
+        }),
+        ValueType::Object => value.try_into_object().map(|members| {
+            let mut object = B::Object::with_capacity(members.len());
+            for (key, value) in members.into_iter() {
+                object.insert(A::Object::key_into_string(key).into(), convert(value));
+            }
+            B::object(object)
+        }),
+    };
+
+    // only a NaN or infinite number fails to convert, and neither is a JSON value
+    converted.unwrap_or_else(|_| B::null())
+}
+
+/// The parts of an [`Alarm`] which are relevant to [`alarm_to_alert`].
+struct AlarmParts<'a> {
+    trigger: &'a Prop<TriggerValue, Params>,
+    action: String,
+    acknowledged: Option<&'a Prop<DateTime<Utc>, Params>>,
+    uid: bool,
+    description: bool,
+    summary: bool,
+    repeat: bool,
+    attendees: bool,
+    attachments: bool,
+    x_properties: bool,
+}
+
+impl<'a> AlarmParts<'a> {
+    fn of(alarm: &'a Alarm) -> Self {
+        match alarm {
+            Alarm::Audio(alarm) => Self {
+                trigger: alarm.trigger(),
+                action: "AUDIO".into(),
+                acknowledged: alarm.acknowledged(),
+                uid: alarm.uid().is_some(),
+                description: false,
+                summary: false,
+                repeat: alarm.repeat().is_some() || alarm.duration().is_some(),
+                attendees: false,
+                attachments: alarm.attach().is_some(),
+                x_properties: alarm.x_property_iter().next().is_some(),
+            },
+            Alarm::Display(alarm) => Self {
+                trigger: alarm.trigger(),
+                action: "DISPLAY".into(),
+                acknowledged: alarm.acknowledged(),
+                uid: alarm.uid().is_some(),
+                description: true,
+                summary: false,
+                repeat: alarm.repeat().is_some() || alarm.duration().is_some(),
+                attendees: false,
+                attachments: false,
+                x_properties: alarm.x_property_iter().next().is_some(),
+            },
+            Alarm::Email(alarm) => Self {
+                trigger: alarm.trigger(),
+                action: "EMAIL".into(),
+                acknowledged: alarm.acknowledged(),
+                uid: alarm.uid().is_some(),
+                description: true,
+                summary: true,
+                repeat: alarm.repeat().is_some() || alarm.duration().is_some(),
+                attendees: alarm.attendee().is_some_and(|v| !v.is_empty()),
+                attachments: alarm.attach().is_some_and(|v| !v.is_empty()),
+                x_properties: alarm.x_property_iter().next().is_some(),
+            },
+            Alarm::Other(alarm) => Self {
+                trigger: alarm.trigger(),
+                action: alarm.action().value.to_ascii_uppercase(),
+                acknowledged: alarm.acknowledged(),
+                uid: alarm.uid().is_some(),
+                description: alarm.description().is_some(),
+                summary: alarm.summary().is_some(),
+                repeat: alarm.repeat().is_some() || alarm.duration().is_some(),
+                attendees: alarm.attendee().is_some_and(|v| !v.is_empty()),
+                attachments: alarm.attach().is_some_and(|v| !v.is_empty()),
+                x_properties: alarm.x_property_iter().next().is_some(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use calico::model::component::CalendarComponent;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::{
+        json::TryFromJson,
+        model::{
+            object::Trigger,
+            set::{AlertAction, AlertRelativeTo, Token},
+        },
+    };
+
+    fn parse_alarms(valarms: &str) -> Vec<Alarm> {
+        let source = format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//test//EN\r\nBEGIN:VEVENT\r\n\
+             UID:1\r\nDTSTAMP:20240101T000000Z\r\nDTSTART:20240115T130000Z\r\n\
+             {valarms}END:VEVENT\r\nEND:VCALENDAR\r\n"
+        );
+        let calendars = Calendar::parse(&source).unwrap();
+        match &calendars[0].components()[0] {
+            CalendarComponent::Event(event) => event.alarms().clone(),
+            other => panic!("expected an event, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn display_alarm_round_trip() {
+        let alarms = parse_alarms(
+            "BEGIN:VALARM\r\nACTION:DISPLAY\r\nTRIGGER;RELATED=END:-PT15M\r\n\
+             DESCRIPTION:Reminder\r\nREPEAT:2\r\nDURATION:PT5M\r\n\
+             ACKNOWLEDGED:20240115T124500Z\r\nEND:VALARM\r\n",
+        );
+
+        let (alert, losses) = alarm_to_alert::<Value>(&alarms[0]);
+        assert_eq!(losses, [AlarmLoss::Description, AlarmLoss::Repeat]);
+        assert_eq!(alert.action(), Some(&Token::Known(AlertAction::Display)));
+        assert!(alert.acknowledged().is_some());
+        let Trigger::Offset(trigger) = alert.trigger() else {
+            panic!("expected an offset trigger");
+        };
+        assert_eq!(
+            trigger.relative_to(),
+            Some(&Token::Known(AlertRelativeTo::End))
+        );
+
+        let (alarm, losses) = alert_to_alarm(&alert, "Reminder").unwrap();
+        assert!(losses.is_empty());
+        let Alarm::Display(alarm) = alarm else {
+            panic!("expected a display alarm");
+        };
+        assert_eq!(alarm.trigger(), AlarmParts::of(&alarms[0]).trigger);
+        assert_eq!(alarm.description().value, "Reminder");
+        assert_eq!(
+            alarm.acknowledged().map(|prop| prop.value),
+            alert.acknowledged().copied()
+        );
+    }
+
+    #[test]
+    fn email_audio_and_other_actions() {
+        let alarms = parse_alarms(
+            "BEGIN:VALARM\r\nACTION:EMAIL\r\nTRIGGER;VALUE=DATE-TIME:20240115T120000Z\r\n\
+             DESCRIPTION:Body\r\nSUMMARY:Subject\r\nATTENDEE:mailto:a@example.com\r\n\
+             END:VALARM\r\n\
+             BEGIN:VALARM\r\nACTION:AUDIO\r\nTRIGGER:-PT1H\r\nEND:VALARM\r\n\
+             BEGIN:VALARM\r\nACTION:X-PROCEDURE\r\nTRIGGER:PT0S\r\nX-FOO:bar\r\nEND:VALARM\r\n",
+        );
+
+        let converted: Vec<_> = alarms.iter().map(alarm_to_alert::<Value>).collect();
+        assert_eq!(
+            converted[0].1,
+            [
+                AlarmLoss::Description,
+                AlarmLoss::Summary,
+                AlarmLoss::Attendees
+            ]
+        );
+        assert!(matches!(converted[0].0.trigger(), Trigger::Absolute(_)));
+        assert_eq!(converted[1].1, []);
+        assert_eq!(
+            converted[1].0.action(),
+            Some(&Token::Unknown("audio".into()))
+        );
+        assert_eq!(converted[2].1, [AlarmLoss::XProperties]);
+        assert_eq!(
+            converted[2].0.action(),
+            Some(&Token::Unknown("x-procedure".into()))
+        );
+
+        let restored: Vec<_> = converted
+            .iter()
+            .map(|(alert, _)| alert_to_alarm(alert, "Title").unwrap().0)
+            .collect();
+        let Alarm::Email(email) = &restored[0] else {
+            panic!("expected an email alarm");
+        };
+        assert_eq!(email.summary().value, "Title");
+        assert_eq!(email.trigger(), AlarmParts::of(&alarms[0]).trigger);
+        assert!(matches!(&restored[1], Alarm::Audio(_)));
+        let Alarm::Other(other) = &restored[2] else {
+            panic!("expected an alarm with another action");
+        };
+        assert_eq!(other.action().value, "X-PROCEDURE");
+    }
+
+    #[test]
+    fn alert_losses_and_unknown_triggers() {
+        let alert = Alert::<Value>::new(Trigger::Unknown(Default::default()));
+        assert_eq!(alert_to_alarm(&alert, ""), Err(UnknownTriggerError));
+
+        let alarms =
+            parse_alarms("BEGIN:VALARM\r\nACTION:AUDIO\r\nTRIGGER:-PT1H\r\nEND:VALARM\r\n");
+        let (mut alert, _) = alarm_to_alert::<Value>(&alarms[0]);
+        alert.insert_vendor_property("example.com:sound".into(), Value::from("chime"));
+        let (_, losses) = alert_to_alarm(&alert, "").unwrap();
+        assert_eq!(losses, [AlarmLoss::VendorProperties]);
+    }
+
+    #[test]
+    fn event_ics_round_trip() {
+        let ics = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//test//EN\r\nBEGIN:VEVENT\r\n\
+                   UID:standup@example.com\r\nDTSTAMP:20240101T000000Z\r\n\
+                   DTSTART;TZID=Europe/Berlin:20240115T093000\r\nDURATION:PT15M\r\n\
+                   SUMMARY:Standup\r\nLOCATION:Room 1\r\nRRULE:FREQ=WEEKLY;BYDAY=MO,WE\r\n\
+                   X-EXAMPLE:dropped\r\nBEGIN:VALARM\r\nACTION:DISPLAY\r\n\
+                   TRIGGER:-PT5M\r\nDESCRIPTION:Standup\r\nEND:VALARM\r\n\
+                   END:VEVENT\r\nEND:VCALENDAR\r\n";
+
+        let (event, dropped) = Event::<Value>::from_ics_str(ics).unwrap();
+        assert_eq!(dropped, ["valarm/description", "x-example"]);
+        assert_eq!(event.uid().as_str(), "standup@example.com");
+        assert_eq!(event.title().map(String::as_str), Some("Standup"));
+        assert_eq!(event.recurrence_rules().map(Vec::len), Some(1));
+        assert_eq!(event.alerts().map(|alerts| alerts.len()), Some(1));
+
+        let now = DateTime::<Utc>::try_from_json(Value::from("2024-06-01T00:00:00Z")).unwrap();
+        let (text, dropped) = event.to_ics_string(now);
+        assert!(dropped.is_empty());
+        assert!(text.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(text.contains(PROD_ID));
+
+        let (restored, dropped) = Event::<Value>::from_ics_str(&text).unwrap();
+        assert_eq!(dropped, ["valarm/description"]);
+        assert_eq!(restored, event);
+    }
+
+    #[test]
+    fn ics_without_a_single_event() {
+        let calendar = |components: &str| {
+            format!(
+                "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//test//EN\r\n{components}\
+                 END:VCALENDAR\r\n"
+            )
+        };
+        let event = "BEGIN:VEVENT\r\nUID:1\r\nDTSTAMP:20240101T000000Z\r\n\
+                     DTSTART:20240115T130000Z\r\nEND:VEVENT\r\n";
+
+        assert_eq!(
+            Event::<Value>::from_ics_str(&calendar("")),
+            Err(IcsError::EventCount(0))
+        );
+        assert_eq!(
+            Event::<Value>::from_ics_str(&calendar(&event.repeat(2))),
+            Err(IcsError::EventCount(2))
+        );
+        assert!(matches!(
+            Event::<Value>::from_ics_str("BEGIN:VEVENT\r\n"),
+            Err(IcsError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,400 @@
+//! Memory instrumentation for parsing (requires the `instrument` feature).
+//!
+//! The `try_from_json_instrumented` methods of [`Event`], [`Task`], and [`Group`] parse an object
+//! like [`TryFromJson::try_from_json`], and also return the [`ParseStats`] of the parse: the number
+//! of allocations it made, and the sizes of the largest maps in the input. These make it possible
+//! to measure the effect of tuning on real data.
+//!
+//! The `size_hint` methods of the same types return a [`SizeHint`] of an object after parsing.
+//! The properties of an object are stored in a map which holds only those present, so an absent
+//! property costs no memory, and the inline size of an object does not grow with the number of
+//! properties it could have. Large objects are instead dominated by their collection-valued
+//! properties, such as `participants`, whose sizes the hint reports.
+//!
+//! Repeated map keys, such as the participant IDs and language tags of a large group, are
+//! deliberately not interned, and there is no interning variant of [`TryFromJson`]. Every map of
+//! the object types owns its keys as values like `Box<Id>`, which cannot share an allocation, so
+//! an interner threaded through parsing would save nothing unless those keys became `Arc<Id>`.
+//! That would change the public type of every such map for all users, to the benefit of large
+//! groups only. The allocation counts of [`ParseStats`] measure what interning could save on a
+//! given input.
+//!
+//! Allocations are counted through the [`Instrumentation`] trait, since a library cannot observe
+//! them by itself. [`CountingAllocator`] implements it as a wrapper around a global allocator; any
+//! other allocation counter can be plugged in by implementing the trait.
+//!
+//! ```ignore
+//! use jscalendar::instrument::CountingAllocator;
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: CountingAllocator = CountingAllocator::system();
+//!
+//! let (event, stats) = Event::try_from_json_instrumented(value, &ALLOCATOR)?;
+//! println!("{:?} allocations", stats.allocations);
+//! ```
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    borrow::Borrow,
+    collections::{BTreeMap, HashMap, HashSet},
+    mem::size_of,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::{
+    json::{
+        ConstructibleJsonValue, DestructibleJsonValue, DocumentError, JsonArray, JsonObject,
+        JsonValue, TryFromJson, TypeErrorOr,
+    },
+    model::{
+        object::{Event, Group, ObjectFromJsonError, Task},
+        props,
+    },
+};
+
+/// A source of allocation counts.
+pub trait Instrumentation {
+    /// Returns the number of allocations made so far, or `None` if they are not counted.
+    ///
+    /// Only the difference between two calls is used, so the count may start at any value.
+    fn allocation_count(&self) -> Option<u64>;
+}
+
+/// An [`Instrumentation`] which does not count allocations.
+impl Instrumentation for () {
+    fn allocation_count(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// A global allocator which counts the allocations made through it.
+///
+/// The count is shared by all threads, so the allocations of a parse will include those made
+/// concurrently by other threads.
+#[derive(Debug, Default)]
+pub struct CountingAllocator<A = System> {
+    inner: A,
+    count: AtomicU64,
+}
+
+impl CountingAllocator {
+    /// Returns a counting wrapper around the [`System`] allocator.
+    pub const fn system() -> Self {
+        Self::new(System)
+    }
+}
+
+impl<A> CountingAllocator<A> {
+    /// Returns a counting wrapper around `inner`.
+    pub const fn new(inner: A) -> Self {
+        Self {
+            inner,
+            count: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the number of allocations made so far, including reallocations.
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+}
+
+// SAFETY: every method forwards to the inner allocator with the same arguments.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: upheld by the caller.
+        unsafe { self.inner.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: upheld by the caller.
+        unsafe { self.inner.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: upheld by the caller.
+        unsafe { self.inner.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        self.count.fetch_add(1, Ordering::Relaxed);
+        // SAFETY: upheld by the caller.
+        unsafe { self.inner.realloc(ptr, layout, new_size) }
+    }
+}
+
+impl<A> Instrumentation for CountingAllocator<A> {
+    fn allocation_count(&self) -> Option<u64> {
+        Some(self.count())
+    }
+}
+
+/// Measurements of a single parse.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    /// The number of allocations made while parsing, or `None` if the [`Instrumentation`] does
+    /// not count them. Measuring the input is excluded.
+    pub allocations: Option<u64>,
+    /// The number of JSON objects in the input.
+    pub maps: usize,
+    /// The number of members of the largest JSON object held by each property in the input.
+    /// Objects in arrays are counted under the name of the array, and the input itself under the
+    /// empty string.
+    pub peak_map_sizes: BTreeMap<Box<str>, usize>,
+}
+
+impl ParseStats {
+    /// Returns the number of members of the largest JSON object in the input.
+    pub fn peak_map_size(&self) -> usize {
+        self.peak_map_sizes.values().copied().max().unwrap_or(0)
+    }
+
+    fn measure<V: DestructibleJsonValue>(&mut self, name: &str, value: &V) {
+        if let Ok(array) = value.try_as_array() {
+            for elem in array.iter() {
+                self.measure(name, elem);
+            }
+        } else if let Ok(object) = value.try_as_object() {
+            self.maps += 1;
+            let peak = self.peak_map_sizes.entry(name.into()).or_default();
+            *peak = (*peak).max(object.len());
+            for (key, member) in object.iter() {
+                self.measure(key.borrow(), member);
+            }
+        }
+    }
+}
+
+/// Measures `value`, and then parses it while counting allocations with `instrumentation`.
+fn parse_instrumented<T, V, I>(value: V, instrumentation: &I) -> Result<(T, ParseStats), T::Error>
+where
+    T: TryFromJson<V>,
+    V: DestructibleJsonValue,
+    I: Instrumentation + ?Sized,
+{
+    let mut stats = ParseStats::default();
+    stats.measure("", &value);
+
+    let before = instrumentation.allocation_count();
+    let result = T::try_from_json(value);
+    let after = instrumentation.allocation_count();
+    stats.allocations = before
+        .zip(after)
+        .map(|(before, after)| after.saturating_sub(before));
+
+    result.map(|object| (object, stats))
+}
+
+type ObjErr = DocumentError<TypeErrorOr<ObjectFromJsonError>>;
+
+macro_rules! impl_try_from_json_instrumented {
+    ($($name:ident: $noun:literal),*) => {
+        $(
+            impl<V> $name<V>
+            where
+                V: DestructibleJsonValue + ConstructibleJsonValue,
+            {
+                #[doc = concat!("Parses ", $noun, " like [`TryFromJson::try_from_json`], and returns the [`ParseStats`] of the parse, counting allocations with `instrumentation`.")]
+                pub fn try_from_json_instrumented<I: Instrumentation + ?Sized>(
+                    value: V,
+                    instrumentation: &I,
+                ) -> Result<(Self, ParseStats), ObjErr> {
+                    parse_instrumented(value, instrumentation)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_json_instrumented!(Event: "an event", Task: "a task", Group: "a group");
+
+/// An estimate of the memory held by an object, returned by the `size_hint` methods of
+/// [`Event`], [`Task`], and [`Group`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SizeHint {
+    /// The size in bytes of the object itself, excluding the memory it allocates.
+    pub inline: usize,
+    /// The number of properties present, including vendor properties.
+    pub properties: usize,
+    /// The number of entries of each collection-valued property which is present.
+    pub collection_sizes: BTreeMap<&'static str, usize>,
+}
+
+impl SizeHint {
+    /// Returns the number of entries of the largest collection-valued property.
+    pub fn largest_collection(&self) -> usize {
+        self.collection_sizes.values().copied().max().unwrap_or(0)
+    }
+}
+
+macro_rules! impl_size_hint {
+    ($($name:ident: $noun:literal { $($prop:ident => $field:ident),* $(,)? }),*) => {
+        $(
+            impl<V: JsonValue> $name<V> {
+                #[doc = concat!("Returns the [`SizeHint`] of ", $noun, ".")]
+                pub fn size_hint(&self) -> SizeHint {
+                    let mut collection_sizes = BTreeMap::new();
+                    $(
+                        if let Some(collection) = self.$field() {
+                            collection_sizes.insert(props::$prop, collection.len());
+                        }
+                    )*
+
+                    SizeHint {
+                        inline: size_of::<Self>(),
+                        properties: self.len(),
+                        collection_sizes,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_size_hint!(
+    Event: "this event" {
+        RELATED_TO => related_to,
+        LOCATIONS => locations,
+        VIRTUAL_LOCATIONS => virtual_locations,
+        LINKS => links,
+        KEYWORDS => keywords,
+        CATEGORIES => categories,
+        RECURRENCE_RULES => recurrence_rules,
+        EXCLUDED_RECURRENCE_RULES => excluded_recurrence_rules,
+        RECURRENCE_OVERRIDES => recurrence_overrides,
+        PARTICIPANTS => participants,
+        ALERTS => alerts,
+        LOCALIZATIONS => localizations,
+        TIME_ZONES => time_zones,
+    },
+    Task: "this task" {
+        RELATED_TO => related_to,
+        LOCATIONS => locations,
+        VIRTUAL_LOCATIONS => virtual_locations,
+        LINKS => links,
+        KEYWORDS => keywords,
+        CATEGORIES => categories,
+        RECURRENCE_RULES => recurrence_rules,
+        EXCLUDED_RECURRENCE_RULES => excluded_recurrence_rules,
+        RECURRENCE_OVERRIDES => recurrence_overrides,
+        PARTICIPANTS => participants,
+        ALERTS => alerts,
+        LOCALIZATIONS => localizations,
+        TIME_ZONES => time_zones,
+    }
+);
+
+impl<V: JsonValue> Group<V> {
+    /// Returns the [`SizeHint`] of this group, whose collection sizes include the number of its
+    /// entries but not the collections of the entries themselves.
+    pub fn size_hint(&self) -> SizeHint {
+        let mut collection_sizes = BTreeMap::from([(props::ENTRIES, self.entries().len())]);
+        let collections = [
+            (props::LINKS, self.links().map(HashMap::len)),
+            (props::KEYWORDS, self.keywords().map(HashSet::len)),
+            (props::CATEGORIES, self.categories().map(HashSet::len)),
+            (props::TIME_ZONES, self.time_zones().map(HashMap::len)),
+        ];
+        for (name, len) in collections {
+            if let Some(len) = len {
+                collection_sizes.insert(name, len);
+            }
+        }
+
+        SizeHint {
+            inline: size_of::<Self>(),
+            properties: self.len(),
+            collection_sizes,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use std::cell::Cell;
+
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::model::object::TaskOrEvent;
+
+    /// Counts one allocation per query.
+    struct Ticker(Cell<u64>);
+
+    impl Instrumentation for Ticker {
+        fn allocation_count(&self) -> Option<u64> {
+            self.0.set(self.0.get() + 1);
+            Some(self.0.get())
+        }
+    }
+
+    fn input() -> Value {
+        json!({
+            "@type": "Group",
+            "uid": "group",
+            "entries": [
+                {
+                    "@type": "Event",
+                    "uid": "a",
+                    "start": "2024-01-01T09:00:00",
+                    "keywords": { "x": true, "y": true, "z": true }
+                },
+                {
+                    "@type": "Task",
+                    "uid": "b",
+                    "keywords": { "x": true }
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn stats() {
+        let (group, stats) =
+            Group::<Value>::try_from_json_instrumented(input(), &Ticker(Cell::new(0))).unwrap();
+        assert_eq!(group.entries().len(), 2);
+        assert_eq!(stats.allocations, Some(1));
+        assert_eq!(stats.maps, 5);
+        assert_eq!(stats.peak_map_sizes[""], 3);
+        assert_eq!(stats.peak_map_sizes["entries"], 4);
+        assert_eq!(stats.peak_map_sizes["keywords"], 3);
+        assert_eq!(stats.peak_map_size(), 4);
+
+        let (_, stats) = Group::<Value>::try_from_json_instrumented(input(), &()).unwrap();
+        assert_eq!(stats.allocations, None);
+
+        assert!(Event::<Value>::try_from_json_instrumented(json!({}), &()).is_err());
+    }
+
+    #[test]
+    fn size_hint() {
+        let group = Group::<Value>::try_from_json(input()).unwrap();
+        let hint = group.size_hint();
+        assert_eq!(hint.inline, size_of::<Group<Value>>());
+        assert_eq!(hint.properties, 2);
+        assert_eq!(hint.collection_sizes, BTreeMap::from([(props::ENTRIES, 2)]));
+
+        let TaskOrEvent::Event(event) = &group.entries()[0] else {
+            panic!("expected an event");
+        };
+        let hint = event.size_hint();
+        assert_eq!(hint.properties, 3);
+        assert_eq!(hint.collection_sizes[props::KEYWORDS], 3);
+        assert_eq!(hint.largest_collection(), 3);
+    }
+
+    #[test]
+    fn counting_allocator() {
+        let allocator = CountingAllocator::system();
+        let layout = Layout::new::<u64>();
+        // SAFETY: the layout has a non-zero size, and the pointers come from this allocator.
+        unsafe {
+            let ptr = allocator.alloc(layout);
+            let ptr = allocator.realloc(ptr, layout, 16);
+            allocator.dealloc(ptr, Layout::from_size_align(16, layout.align()).unwrap());
+        }
+        assert_eq!(allocator.allocation_count(), Some(2));
+    }
+}
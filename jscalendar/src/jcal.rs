@@ -0,0 +1,1453 @@
+//! Conversion between jCal (RFC 7265) and JSCalendar objects.
+//!
+//! jCal is the JSON encoding of iCalendar, so servers which speak it can be migrated to
+//! JSCalendar without writing and re-parsing iCalendar text. VEVENT and VTODO components are
+//! converted to and from [`Event`]s and [`Task`]s, with the properties which both models share
+//! mapped as follows:
+//!
+//! | jCal                            | JSCalendar                                    |
+//! |---------------------------------|-----------------------------------------------|
+//! | `uid`, `summary`, `description` | `uid`, `title`, `description`                 |
+//! | `created`, `sequence`, `color`  | `created`, `sequence`, `color`                |
+//! | `last-modified` or `dtstamp`    | `updated`                                     |
+//! | `dtstart` and its `tzid`        | `start`, `timeZone`, and `showWithoutTime`    |
+//! | `dtend` or `duration`           | `duration` (`estimatedDuration` of a task)    |
+//! | `due`, `percent-complete`       | `due`, `percentComplete`                      |
+//! | `status`                        | `status` (`progress` of a task)               |
+//! | `completed`                     | `progressUpdated` of a completed task         |
+//! | `transp`, `class`, `priority`   | `freeBusyStatus`, `privacy`, `priority`       |
+//! | `categories`, `location`        | `keywords`, the name of a `locations` entry   |
+//! | `rrule`, `exrule`               | `recurrenceRules`, `excludedRecurrenceRules`  |
+//! | `rdate`, `exdate`               | `recurrenceOverrides` without a patch         |
+//! | `recurrence-id` and its `tzid`  | `recurrenceId`, `recurrenceIdTimeZone`        |
+//! | VALARM components               | `alerts`                                      |
+//!
+//! Every other property, parameter, and component is dropped, and the conversion functions
+//! return the names of what they dropped, so that callers can decide whether the loss is
+//! acceptable. Dropped jCal properties of subcomponents are named by their component and
+//! property, as in `valarm/description`.
+//!
+//! VALARM components are mapped to alerts in the same way as by the `ical` module, whose
+//! documentation describes the mapping.
+//!
+//! A UTC date-time is given the time zone `Etc/UTC`. The `until` part of a recurrence rule is
+//! always written as a local date-time in JSCalendar, so a UTC `until` is read as if it were in
+//! the time zone of the object.
+
+use std::{borrow::Borrow, collections::BTreeMap};
+
+use thiserror::Error;
+
+use crate::{
+    json::{
+        self, ConstructibleJsonValue, DestructibleJsonValue, DocumentError, Int, IntoJson,
+        JsonObject, TryFromJson, TypeErrorOr,
+    },
+    model::{
+        object::{Alert, Event, ObjectFromJsonError, Task, TaskOrEvent},
+        props,
+        time::{DateTime, Duration, Local, NominalDuration, Sign, Utc},
+    },
+    parser::{local_date_time, parse_full, signed_duration, utc_date_time},
+    valarm::{Valarm, ValarmTrigger},
+};
+
+/// The time zone given to UTC date-times.
+const UTC: &str = "Etc/UTC";
+
+/// The jCal names of the recurrence rule parts whose values are lists of numbers, with the
+/// names of the corresponding JSCalendar properties.
+const NUMBER_LIST_PARTS: [(&str, &str); 8] = [
+    ("bymonth", props::BY_MONTH),
+    ("bymonthday", props::BY_MONTH_DAY),
+    ("byyearday", props::BY_YEAR_DAY),
+    ("byweekno", props::BY_WEEK_NO),
+    ("byhour", props::BY_HOUR),
+    ("byminute", props::BY_MINUTE),
+    ("bysecond", props::BY_SECOND),
+    ("bysetpos", props::BY_SET_POSITION),
+];
+
+/// An error returned when converting jCal to JSCalendar.
+#[derive(Debug, Clone, PartialEq, Error)]
+#[non_exhaustive]
+pub enum JcalError {
+    /// The input does not have the structure of a jCal component.
+    #[error("malformed jCal: {0}")]
+    Malformed(&'static str),
+    /// A component has a different name than expected.
+    #[error("expected a {expected} component but found {found}")]
+    UnexpectedComponent {
+        /// The expected component name.
+        expected: &'static str,
+        /// The name of the component which was found.
+        found: String,
+    },
+    /// A property has a value which cannot be converted.
+    #[error("invalid value for the {0} property")]
+    InvalidValue(String),
+    /// The converted object is not a valid JSCalendar object.
+    #[error("invalid JSCalendar object: {0}")]
+    Object(#[from] DocumentError<TypeErrorOr<ObjectFromJsonError>>),
+}
+
+/// Converts a jCal VEVENT component into an [`Event`], returning the names of the properties and
+/// components which were dropped.
+pub fn event_from_jcal<V>(vevent: V) -> Result<(Event<V>, Vec<String>), JcalError>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    let component = Component::parse(vevent)?;
+    component.expect("vevent")?;
+
+    let mut dropped = Vec::new();
+    let object = object_from_component(component, Kind::Event, &mut dropped)?;
+    Ok((Event::try_from_json(object)?, sorted(dropped)))
+}
+
+/// Converts a jCal VTODO component into a [`Task`], returning the names of the properties and
+/// components which were dropped.
+pub fn task_from_jcal<V>(vtodo: V) -> Result<(Task<V>, Vec<String>), JcalError>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    let component = Component::parse(vtodo)?;
+    component.expect("vtodo")?;
+
+    let mut dropped = Vec::new();
+    let object = object_from_component(component, Kind::Task, &mut dropped)?;
+    Ok((Task::try_from_json(object)?, sorted(dropped)))
+}
+
+/// Converts the VEVENT and VTODO components of a jCal VCALENDAR into events and tasks, returning
+/// the names of the properties and components which were dropped.
+///
+/// The VERSION, PRODID, and CALSCALE properties of the calendar are ignored rather than
+/// reported as dropped.
+pub fn entries_from_jcal<V>(vcalendar: V) -> Result<(Vec<TaskOrEvent<V>>, Vec<String>), JcalError>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    let calendar = Component::parse(vcalendar)?;
+    calendar.expect("vcalendar")?;
+
+    let mut dropped = Vec::new();
+    for property in &calendar.properties {
+        if !matches!(property.name.as_str(), "version" | "prodid" | "calscale") {
+            dropped.push(property.name.clone());
+        }
+    }
+
+    let mut entries = Vec::new();
+    for component in calendar.components {
+        match component.name.as_str() {
+            "vevent" => {
+                let object = object_from_component(component, Kind::Event, &mut dropped)?;
+                entries.push(TaskOrEvent::Event(Event::try_from_json(object)?));
+            }
+            "vtodo" => {
+                let object = object_from_component(component, Kind::Task, &mut dropped)?;
+                entries.push(TaskOrEvent::Task(Task::try_from_json(object)?));
+            }
+            _ => dropped.push(component.name),
+        }
+    }
+
+    Ok((entries, sorted(dropped)))
+}
+
+/// Converts an [`Event`] into a jCal VEVENT component, returning the names of the JSCalendar
+/// properties which were dropped.
+///
+/// The required DTSTAMP is taken from the `updated` or else the `created` time of the event, or
+/// is `now` if it has neither.
+pub fn event_to_jcal<V>(event: Event<V>, now: DateTime<Utc>) -> (V, Vec<String>)
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    let mut dropped = Vec::new();
+    let vevent = component_from_object(event.into_json(), Kind::Event, now, &mut dropped);
+    (vevent, sorted(dropped))
+}
+
+/// Converts a [`Task`] into a jCal VTODO component, returning the names of the JSCalendar
+/// properties which were dropped.
+///
+/// The DTSTAMP is chosen as by [`event_to_jcal`].
+pub fn task_to_jcal<V>(task: Task<V>, now: DateTime<Utc>) -> (V, Vec<String>)
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    let mut dropped = Vec::new();
+    let vtodo = component_from_object(task.into_json(), Kind::Task, now, &mut dropped);
+    (vtodo, sorted(dropped))
+}
+
+/// Converts events and tasks into a jCal VCALENDAR with the given PRODID, returning the names of
+/// the JSCalendar properties which were dropped.
+///
+/// The DTSTAMP of each component is chosen as by [`event_to_jcal`].
+pub fn entries_to_jcal<V>(
+    entries: Vec<TaskOrEvent<V>>,
+    prod_id: &str,
+    now: DateTime<Utc>,
+) -> (V, Vec<String>)
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    let mut dropped = Vec::new();
+    let components = entries
+        .into_iter()
+        .map(|entry| match entry {
+            TaskOrEvent::Event(event) => {
+                component_from_object(event.into_json(), Kind::Event, now, &mut dropped)
+            }
+            TaskOrEvent::Task(task) => {
+                component_from_object(task.into_json(), Kind::Task, now, &mut dropped)
+            }
+        })
+        .collect();
+
+    let properties = vec![
+        property("version", &[], "text", [V::str("2.0")]),
+        property("prodid", &[], "text", [V::str(prod_id)]),
+    ];
+    (
+        component("vcalendar", properties, components),
+        sorted(dropped),
+    )
+}
+
+// ============================================================================
+// jCal to JSCalendar
+// ============================================================================
+
+/// The kind of object to which a component is converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Event,
+    Task,
+}
+
+/// A jCal component, split into its parts.
+struct Component<V> {
+    name: String,
+    properties: Vec<Property<V>>,
+    components: Vec<Component<V>>,
+}
+
+/// A jCal property, split into its parts.
+struct Property<V> {
+    name: String,
+    params: BTreeMap<String, V>,
+    value_type: String,
+    values: Vec<V>,
+}
+
+impl<V: DestructibleJsonValue> Component<V> {
+    fn parse(value: V) -> Result<Self, JcalError> {
+        let mut parts = elements(value, "a component must be an array")?.into_iter();
+        let (Some(name), Some(properties), Some(components), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(JcalError::Malformed(
+                "a component must have a name, properties, and subcomponents",
+            ));
+        };
+
+        let name = string(name).ok_or(JcalError::Malformed("a component name must be a string"))?;
+        let properties = elements(properties, "the properties of a component must be an array")?
+            .into_iter()
+            .map(Property::parse)
+            .collect::<Result<_, _>>()?;
+        let components = elements(
+            components,
+            "the subcomponents of a component must be an array",
+        )?
+        .into_iter()
+        .map(Component::parse)
+        .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            name: name.to_ascii_lowercase(),
+            properties,
+            components,
+        })
+    }
+
+    fn expect(&self, expected: &'static str) -> Result<(), JcalError> {
+        if self.name == expected {
+            Ok(())
+        } else {
+            Err(JcalError::UnexpectedComponent {
+                expected,
+                found: self.name.clone(),
+            })
+        }
+    }
+}
+
+impl<V: DestructibleJsonValue> Property<V> {
+    fn parse(value: V) -> Result<Self, JcalError> {
+        let mut parts = elements(value, "a property must be an array")?.into_iter();
+        let (Some(name), Some(params), Some(value_type)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(JcalError::Malformed(
+                "a property must have a name, parameters, a type, and values",
+            ));
+        };
+
+        let name = string(name).ok_or(JcalError::Malformed("a property name must be a string"))?;
+        let params = params
+            .try_into_object()
+            .map_err(|_| JcalError::Malformed("the parameters of a property must be an object"))?
+            .into_iter()
+            .map(|(key, value)| {
+                let key = <V::Object as JsonObject>::key_into_string(key);
+                (key.to_ascii_lowercase(), value)
+            })
+            .collect();
+        let value_type =
+            string(value_type).ok_or(JcalError::Malformed("a property type must be a string"))?;
+
+        Ok(Self {
+            name: name.to_ascii_lowercase(),
+            params,
+            value_type: value_type.to_ascii_lowercase(),
+            values: parts.collect(),
+        })
+    }
+
+    fn invalid(&self) -> JcalError {
+        JcalError::InvalidValue(self.name.clone())
+    }
+
+    /// Returns the first value of this property.
+    fn value(&self) -> Result<&V, JcalError> {
+        self.values.first().ok_or_else(|| self.invalid())
+    }
+
+    /// Returns the first value of this property, which must be a string.
+    fn text(&self) -> Result<&str, JcalError> {
+        self.value()
+            .and_then(|value| as_str(value).ok_or_else(|| self.invalid()))
+    }
+
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).and_then(as_str)
+    }
+
+    /// Returns each value of this property as a date or date-time.
+    fn instants(&self) -> Result<Vec<Instant>, JcalError> {
+        self.values
+            .iter()
+            .map(|value| {
+                let value = as_str(value).ok_or_else(|| self.invalid())?;
+                Instant::parse(value, self.value_type == "date", self.param("tzid"))
+                    .ok_or_else(|| self.invalid())
+            })
+            .collect()
+    }
+
+    fn instant(&self) -> Result<Instant, JcalError> {
+        self.instants()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| self.invalid())
+    }
+}
+
+/// A DATE or DATE-TIME value with its time zone.
+#[derive(Debug, Clone, PartialEq)]
+struct Instant {
+    local: DateTime<Local>,
+    date_only: bool,
+    time_zone: Option<String>,
+}
+
+impl Instant {
+    fn parse(value: &str, date_only: bool, tzid: Option<&str>) -> Option<Self> {
+        let (value, utc) = match value.strip_suffix('Z') {
+            Some(value) => (value, true),
+            None => (value, false),
+        };
+        let text = match date_only {
+            true => format!("{value}T00:00:00"),
+            false => value.to_string(),
+        };
+
+        Some(Self {
+            local: parse_full(local_date_time)(&text).ok()?,
+            date_only,
+            time_zone: match utc {
+                true => Some(UTC.into()),
+                false => tzid.map(Into::into),
+            },
+        })
+    }
+
+    /// Returns the duration from this instant to `end`, or `None` if they are in different time
+    /// zones or `end` is earlier.
+    fn duration_until(&self, end: &Instant) -> Option<Duration> {
+        if self.time_zone != end.time_zone {
+            return None;
+        }
+
+        if self.date_only && end.date_only {
+            let days = u32::try_from(end.local.date.days_since(self.local.date)).ok()?;
+            return Some(Duration::Nominal(NominalDuration {
+                days,
+                ..Default::default()
+            }));
+        }
+
+        let delta = end.local.signed_duration_since(&self.local);
+        (delta.sign == Sign::Pos).then_some(delta.duration)
+    }
+}
+
+/// Converts a VEVENT or VTODO component into the JSON form of an event or task.
+fn object_from_component<V>(
+    component: Component<V>,
+    kind: Kind,
+    dropped: &mut Vec<String>,
+) -> Result<V, JcalError>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    let mut members: BTreeMap<&str, V> = BTreeMap::new();
+    let object_type = match kind {
+        Kind::Event => "Event",
+        Kind::Task => "Task",
+    };
+    members.insert(props::TYPE, V::str(object_type));
+
+    let mut start = None;
+    let mut end = None;
+    let mut due = None;
+    let mut dtstamp = None;
+    let mut keywords = Vec::new();
+    let mut locations = Vec::new();
+    let mut rules = Vec::new();
+    let mut excluded_rules = Vec::new();
+    let mut overrides = BTreeMap::new();
+
+    for property in &component.properties {
+        let text = || property.text().map(V::str);
+        match (property.name.as_str(), kind) {
+            ("uid", _) => _ = members.insert(props::UID, text()?),
+            ("summary", _) => _ = members.insert(props::TITLE, text()?),
+            ("description", _) => _ = members.insert(props::DESCRIPTION, text()?),
+            ("color", _) => _ = members.insert(props::COLOR, text()?),
+            ("created", _) => _ = members.insert(props::CREATED, text()?),
+            ("last-modified", _) => _ = members.insert(props::UPDATED, text()?),
+            ("dtstamp", _) => dtstamp = Some(text()?),
+            ("sequence", _) => _ = members.insert(props::SEQUENCE, property.value()?.clone()),
+            ("priority", _) => _ = members.insert(props::PRIORITY, property.value()?.clone()),
+            ("dtstart", _) => start = Some(property.instant()?),
+            ("dtend", Kind::Event) => end = Some(property.instant()?),
+            ("due", Kind::Task) => due = Some(property.instant()?),
+            ("duration", Kind::Event) => _ = members.insert(props::DURATION, text()?),
+            ("duration", Kind::Task) => _ = members.insert(props::ESTIMATED_DURATION, text()?),
+            ("percent-complete", Kind::Task) => {
+                members.insert(props::PERCENT_COMPLETE, property.value()?.clone());
+            }
+            ("completed", Kind::Task) => _ = members.insert(props::PROGRESS_UPDATED, text()?),
+            ("status", _) => {
+                let status = property.text()?.to_ascii_lowercase();
+                match (kind, status.as_str()) {
+                    (Kind::Event, "tentative" | "confirmed" | "cancelled") => {
+                        members.insert(props::STATUS, V::string(status));
+                    }
+                    (Kind::Task, "needs-action" | "in-process" | "completed" | "cancelled") => {
+                        members.insert(props::PROGRESS, V::string(status));
+                    }
+                    _ => dropped.push(property.name.clone()),
+                }
+            }
+            ("transp", _) => match property.text()?.to_ascii_uppercase().as_str() {
+                "OPAQUE" => _ = members.insert(props::FREE_BUSY_STATUS, V::str("busy")),
+                "TRANSPARENT" => _ = members.insert(props::FREE_BUSY_STATUS, V::str("free")),
+                _ => dropped.push(property.name.clone()),
+            },
+            ("class", _) => match property.text()?.to_ascii_uppercase().as_str() {
+                "PUBLIC" => _ = members.insert(props::PRIVACY, V::str("public")),
+                "PRIVATE" => _ = members.insert(props::PRIVACY, V::str("private")),
+                "CONFIDENTIAL" => _ = members.insert(props::PRIVACY, V::str("secret")),
+                _ => dropped.push(property.name.clone()),
+            },
+            ("categories", _) => {
+                for value in &property.values {
+                    keywords.push(as_str(value).ok_or_else(|| property.invalid())?.to_string());
+                }
+            }
+            ("location", _) => locations.push(property.text()?.to_string()),
+            ("rrule", _) => rules.push(recur_from_jcal(property)?),
+            ("exrule", _) => excluded_rules.push(recur_from_jcal(property)?),
+            ("rdate", _) | ("exdate", _) if property.value_type != "period" => {
+                for instant in property.instants()? {
+                    overrides.insert(instant.local.to_string(), property.name == "exdate");
+                }
+            }
+            ("recurrence-id", _) => {
+                let instant = property.instant()?;
+                members.insert(props::RECURRENCE_ID, V::string(instant.local.to_string()));
+                if let Some(time_zone) = instant.time_zone {
+                    members.insert(props::RECURRENCE_ID_TIME_ZONE, V::string(time_zone));
+                }
+            }
+            _ => dropped.push(property.name.clone()),
+        }
+    }
+
+    let mut time_zone = None;
+    for (key, instant) in [(props::START, &start), (props::DUE, &due)] {
+        if let Some(instant) = instant {
+            members.insert(key, V::string(instant.local.to_string()));
+            time_zone = time_zone.or(instant.time_zone.clone());
+            if instant.date_only {
+                members.insert(props::SHOW_WITHOUT_TIME, V::bool(true));
+            }
+        }
+    }
+    if let Some(time_zone) = time_zone {
+        members.insert(props::TIME_ZONE, V::string(time_zone));
+    }
+
+    if let (Some(start), Some(end)) = (&start, &end)
+        && !members.contains_key(props::DURATION)
+    {
+        match start.duration_until(end) {
+            Some(duration) => _ = members.insert(props::DURATION, V::string(duration.to_string())),
+            None => dropped.push("dtend".into()),
+        }
+    }
+
+    if let Some(dtstamp) = dtstamp {
+        members.entry(props::UPDATED).or_insert(dtstamp);
+    }
+
+    if !keywords.is_empty() {
+        let keywords = keywords.into_iter().map(|keyword| (keyword, V::bool(true)));
+        members.insert(props::KEYWORDS, object(keywords));
+    }
+
+    if !locations.is_empty() {
+        let locations = locations.into_iter().enumerate().map(|(index, name)| {
+            let location = object([
+                (props::TYPE.into(), V::str("Location")),
+                (props::NAME.into(), V::string(name)),
+            ]);
+            ((index + 1).to_string(), location)
+        });
+        members.insert(props::LOCATIONS, object(locations));
+    }
+
+    if !rules.is_empty() {
+        members.insert(props::RECURRENCE_RULES, array(rules));
+    }
+    if !excluded_rules.is_empty() {
+        members.insert(props::EXCLUDED_RECURRENCE_RULES, array(excluded_rules));
+    }
+
+    if !overrides.is_empty() {
+        let overrides = overrides.into_iter().map(|(key, excluded)| {
+            let patch = match excluded {
+                true => object([(props::EXCLUDED.into(), V::bool(true))]),
+                false => object::<V>([]),
+            };
+            (key, patch)
+        });
+        members.insert(props::RECURRENCE_OVERRIDES, object(overrides));
+    }
+
+    let mut alerts = Vec::new();
+    for subcomponent in component.components {
+        match subcomponent.name.as_str() {
+            "valarm" => alerts.push(alert_from_component(subcomponent, dropped)?),
+            _ => dropped.push(subcomponent.name),
+        }
+    }
+    if !alerts.is_empty() {
+        let alerts = alerts
+            .into_iter()
+            .enumerate()
+            .map(|(index, alert)| ((index + 1).to_string(), alert));
+        members.insert(props::ALERTS, object(alerts));
+    }
+
+    Ok(object(
+        members
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value)),
+    ))
+}
+
+/// Converts a VALARM component into the JSON form of an alert.
+fn alert_from_component<V>(
+    component: Component<V>,
+    dropped: &mut Vec<String>,
+) -> Result<V, JcalError>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    let (mut action, mut trigger, mut acknowledged) = (None, None, None);
+
+    for property in &component.properties {
+        match property.name.as_str() {
+            "trigger" => {
+                let text = property.text()?;
+                trigger = Some(match property.value_type.as_str() {
+                    "duration" => ValarmTrigger::Offset {
+                        offset: parse_full(signed_duration)(text)
+                            .map_err(|_| property.invalid())?,
+                        end: property
+                            .param("related")
+                            .is_some_and(|related| related.eq_ignore_ascii_case("END")),
+                    },
+                    "date-time" => ValarmTrigger::Absolute(
+                        parse_full(utc_date_time)(text).map_err(|_| property.invalid())?,
+                    ),
+                    _ => return Err(property.invalid()),
+                });
+            }
+            "action" => action = Some(property.text()?.to_ascii_uppercase()),
+            "acknowledged" => {
+                let text = property.text()?;
+                acknowledged =
+                    Some(parse_full(utc_date_time)(text).map_err(|_| property.invalid())?);
+            }
+            _ => dropped.push(format!("{}/{}", component.name, property.name)),
+        }
+    }
+
+    for subcomponent in component.components {
+        dropped.push(format!("{}/{}", component.name, subcomponent.name));
+    }
+
+    let valarm = Valarm {
+        action: action.unwrap_or_else(|| "DISPLAY".into()),
+        trigger: trigger.ok_or(JcalError::Malformed(
+            "a VALARM must have a TRIGGER property",
+        ))?,
+        acknowledged,
+    };
+    Ok(valarm.into_alert::<V>().into_json())
+}
+
+/// Converts a jCal RECUR value into the JSON form of a recurrence rule.
+fn recur_from_jcal<V>(property: &Property<V>) -> Result<V, JcalError>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    let recur = property
+        .value()?
+        .try_as_object()
+        .map_err(|_| property.invalid())?;
+    let text = |value: &V| {
+        as_str(value)
+            .map(str::to_string)
+            .ok_or_else(|| property.invalid())
+    };
+
+    let mut members = vec![(props::TYPE.to_string(), V::str("RecurrenceRule"))];
+    for (key, value) in recur.iter() {
+        let key = Borrow::<str>::borrow(key).to_ascii_lowercase();
+        let (name, value) = match key.as_str() {
+            "freq" => (
+                props::FREQUENCY,
+                V::string(text(value)?.to_ascii_lowercase()),
+            ),
+            "wkst" => (
+                props::FIRST_DAY_OF_WEEK,
+                V::string(text(value)?.to_ascii_lowercase()),
+            ),
+            "rscale" => (props::RSCALE, V::string(text(value)?.to_ascii_lowercase())),
+            "skip" => (props::SKIP, V::string(text(value)?.to_ascii_lowercase())),
+            "until" => (props::UNTIL, V::str(text(value)?.trim_end_matches('Z'))),
+            "count" => (props::COUNT, value.clone()),
+            "interval" => (props::INTERVAL, value.clone()),
+            "byday" => {
+                let days = list(value)
+                    .map(|day| nday_from_jcal(&text(&day)?).ok_or_else(|| property.invalid()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                (props::BY_DAY, array(days))
+            }
+            key => {
+                let (_, name) = NUMBER_LIST_PARTS
+                    .iter()
+                    .find(|(part, _)| *part == key)
+                    .ok_or_else(|| property.invalid())?;
+                (*name, array(list(value)))
+            }
+        };
+        members.push((name.to_string(), value));
+    }
+
+    Ok(object(members))
+}
+
+/// Converts a BYDAY value such as `-1SU` into the JSON form of an `NDay`.
+fn nday_from_jcal<V: ConstructibleJsonValue>(value: &str) -> Option<V> {
+    let split = value.len().checked_sub(2)?;
+    let (nth, day) = (value.get(..split)?, value.get(split..)?);
+
+    let mut members = vec![
+        (props::TYPE.to_string(), V::str("NDay")),
+        (props::DAY.to_string(), V::string(day.to_ascii_lowercase())),
+    ];
+    if !nth.is_empty() {
+        let nth = Int::new(nth.trim_start_matches('+').parse().ok()?)?;
+        members.push((props::NTH_OF_PERIOD.to_string(), V::int(nth)));
+    }
+    Some(object(members))
+}
+
+// ============================================================================
+// JSCalendar to jCal
+// ============================================================================
+
+/// Converts the JSON form of an event or task into a VEVENT or VTODO component.
+fn component_from_object<V>(
+    value: V,
+    kind: Kind,
+    now: DateTime<Utc>,
+    dropped: &mut Vec<String>,
+) -> V
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    let mut members: BTreeMap<String, V> = value
+        .try_into_object()
+        .expect("objects serialize to JSON objects")
+        .into_iter()
+        .map(|(key, value)| (<V::Object as JsonObject>::key_into_string(key), value))
+        .collect();
+    let mut take = |key: &str| members.remove(key);
+    let take_string = |value: Option<V>| value.and_then(string);
+
+    let mut properties = Vec::new();
+    let text = |name: &str, value: String| property(name, &[], "text", [V::string(value)]);
+
+    let time_zone = take_string(take(props::TIME_ZONE));
+    let all_day =
+        take(props::SHOW_WITHOUT_TIME).is_some_and(|value| value.try_as_bool() == Ok(true));
+
+    if let Some(uid) = take_string(take(props::UID)) {
+        properties.push(text("uid", uid));
+    }
+
+    // DTSTAMP is required in both VEVENT and VTODO components (RFC 5545 §3.6.1, §3.6.2)
+    let updated = take(props::UPDATED).map(without_fraction);
+    let created = take(props::CREATED).map(without_fraction);
+    let dtstamp = updated
+        .clone()
+        .or_else(|| created.clone())
+        .unwrap_or_else(|| without_fraction(now.into_json()));
+    properties.push(property("dtstamp", &[], "date-time", [dtstamp]));
+    if let Some(updated) = updated {
+        properties.push(property("last-modified", &[], "date-time", [updated]));
+    }
+    if let Some(created) = created {
+        properties.push(property("created", &[], "date-time", [created]));
+    }
+    if let Some(sequence) = take(props::SEQUENCE) {
+        properties.push(property("sequence", &[], "integer", [sequence]));
+    }
+
+    let title = take_string(take(props::TITLE));
+    if let Some(title) = &title {
+        properties.push(text("summary", title.clone()));
+    }
+    if let Some(description) = take_string(take(props::DESCRIPTION)) {
+        properties.push(text("description", description));
+    }
+
+    for (key, name) in [(props::START, "dtstart"), (props::DUE, "due")] {
+        if let Some(local) = take_string(take(key)) {
+            properties.push(date_time_property(
+                name,
+                &local,
+                time_zone.as_deref(),
+                all_day,
+            ));
+        }
+    }
+    for key in [props::DURATION, props::ESTIMATED_DURATION] {
+        if let Some(duration) = take(key) {
+            properties.push(property("duration", &[], "duration", [duration]));
+        }
+    }
+
+    if let Some(status) = take_string(take(props::STATUS)) {
+        properties.push(text("status", status.to_ascii_uppercase()));
+    }
+    let progress = take_string(take(props::PROGRESS));
+    match progress.as_deref() {
+        Some(progress @ ("needs-action" | "in-process" | "completed" | "cancelled")) => {
+            properties.push(text("status", progress.to_ascii_uppercase()));
+        }
+        Some(_) => dropped.push(props::PROGRESS.into()),
+        None => {}
+    }
+    if let Some(updated) = take(props::PROGRESS_UPDATED) {
+        match progress.as_deref() {
+            Some("completed") => {
+                properties.push(property("completed", &[], "date-time", [updated]));
+            }
+            _ => dropped.push(props::PROGRESS_UPDATED.into()),
+        }
+    }
+    if let Some(percent) = take(props::PERCENT_COMPLETE) {
+        properties.push(property("percent-complete", &[], "integer", [percent]));
+    }
+
+    match take_string(take(props::FREE_BUSY_STATUS)).as_deref() {
+        Some("busy") => properties.push(text("transp", "OPAQUE".into())),
+        Some("free") => properties.push(text("transp", "TRANSPARENT".into())),
+        Some(_) => dropped.push(props::FREE_BUSY_STATUS.into()),
+        None => {}
+    }
+    match take_string(take(props::PRIVACY)).as_deref() {
+        Some("public") => properties.push(text("class", "PUBLIC".into())),
+        Some("private") => properties.push(text("class", "PRIVATE".into())),
+        Some("secret") => properties.push(text("class", "CONFIDENTIAL".into())),
+        Some(_) => dropped.push(props::PRIVACY.into()),
+        None => {}
+    }
+    if let Some(priority) = take(props::PRIORITY) {
+        properties.push(property("priority", &[], "integer", [priority]));
+    }
+    if let Some(color) = take_string(take(props::COLOR)) {
+        properties.push(text("color", color));
+    }
+
+    if let Some(keywords) = take(props::KEYWORDS).and_then(|value| value.try_into_object().ok()) {
+        let mut keywords: Vec<_> = keywords
+            .into_iter()
+            .map(|(keyword, _)| <V::Object as JsonObject>::key_into_string(keyword))
+            .collect();
+        keywords.sort();
+        properties.push(property(
+            "categories",
+            &[],
+            "text",
+            keywords.into_iter().map(V::string),
+        ));
+    }
+
+    if let Some(locations) = take(props::LOCATIONS).and_then(|value| value.try_into_object().ok()) {
+        let mut names: Vec<_> = locations
+            .into_iter()
+            .filter_map(|(id, location)| {
+                let id = <V::Object as JsonObject>::key_into_string(id);
+                let location = location.try_into_object().ok()?;
+                Some((id, location.get(props::NAME).and_then(as_str)?.to_string()))
+            })
+            .collect();
+        names.sort();
+        if names.len() > 1 {
+            dropped.push(props::LOCATIONS.into());
+        }
+        if let Some((_, name)) = names.into_iter().next() {
+            properties.push(text("location", name));
+        }
+    }
+
+    for (key, name) in [
+        (props::RECURRENCE_RULES, "rrule"),
+        (props::EXCLUDED_RECURRENCE_RULES, "exrule"),
+    ] {
+        let rules = take(key).and_then(|value| value.try_into_array().ok());
+        for rule in rules.into_iter().flat_map(json::JsonArray::into_iter) {
+            properties.push(property(name, &[], "recur", [recur_to_jcal(rule)]));
+        }
+    }
+
+    if let Some(overrides) =
+        take(props::RECURRENCE_OVERRIDES).and_then(|value| value.try_into_object().ok())
+    {
+        let mut overrides: Vec<_> = overrides
+            .into_iter()
+            .map(|(key, patch)| (<V::Object as JsonObject>::key_into_string(key), patch))
+            .collect();
+        overrides.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (local, patch) in overrides {
+            let patch = patch.try_into_object().ok();
+            let excluded = patch.as_ref().is_some_and(|patch| {
+                patch.len() == 1
+                    && patch
+                        .get(props::EXCLUDED)
+                        .is_some_and(|excluded| excluded.try_as_bool() == Ok(true))
+            });
+            let name = match (excluded, patch.map(|patch| patch.len())) {
+                (true, _) => "exdate",
+                (false, Some(0)) => "rdate",
+                _ => {
+                    dropped.push(props::RECURRENCE_OVERRIDES.into());
+                    continue;
+                }
+            };
+            properties.push(date_time_property(
+                name,
+                &local,
+                time_zone.as_deref(),
+                all_day,
+            ));
+        }
+    }
+
+    if let Some(recurrence_id) = take_string(take(props::RECURRENCE_ID)) {
+        let zone = take_string(take(props::RECURRENCE_ID_TIME_ZONE)).or(time_zone.clone());
+        properties.push(date_time_property(
+            "recurrence-id",
+            &recurrence_id,
+            zone.as_deref(),
+            all_day,
+        ));
+    }
+
+    let mut components = Vec::new();
+    if let Some(alerts) = take(props::ALERTS).and_then(|value| value.try_into_object().ok()) {
+        let mut alerts: Vec<_> = alerts
+            .into_iter()
+            .map(|(id, alert)| (<V::Object as JsonObject>::key_into_string(id), alert))
+            .collect();
+        alerts.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        for (_, alert) in alerts {
+            match alert_to_component(alert, title.as_deref().unwrap_or_default(), dropped) {
+                Some(valarm) => components.push(valarm),
+                None => dropped.push(props::ALERTS.into()),
+            }
+        }
+    }
+
+    members.remove(props::TYPE);
+    dropped.extend(members.into_keys());
+
+    let name = match kind {
+        Kind::Event => "vevent",
+        Kind::Task => "vtodo",
+    };
+    component(name, properties, components)
+}
+
+/// Converts the JSON form of an alert into a VALARM component, whose DESCRIPTION and SUMMARY
+/// are `description`, or returns `None` if its trigger has an unknown type.
+fn alert_to_component<V>(alert: V, description: &str, dropped: &mut Vec<String>) -> Option<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    let alert = Alert::<V>::try_from_json(alert).ok()?;
+    let (valarm, losses) = Valarm::from_alert(&alert)?;
+
+    if losses.related_to {
+        dropped.push(format!("{}/{}", props::ALERTS, props::RELATED_TO));
+    }
+    for name in losses.vendor_properties {
+        dropped.push(format!("{}/{name}", props::ALERTS));
+    }
+
+    let trigger = match valarm.trigger {
+        ValarmTrigger::Offset { offset, end } => {
+            let params: &[_] = if end { &[("related", "END")] } else { &[] };
+            property("trigger", params, "duration", [offset.into_json()])
+        }
+        ValarmTrigger::Absolute(when) => property("trigger", &[], "date-time", [when.into_json()]),
+    };
+
+    let action = property("action", &[], "text", [V::str(&valarm.action)]);
+    let mut properties = vec![action, trigger];
+    if valarm.has_description() {
+        properties.push(property("description", &[], "text", [V::str(description)]));
+    }
+    if valarm.has_summary() {
+        properties.push(property("summary", &[], "text", [V::str(description)]));
+    }
+    if let Some(acknowledged) = valarm.acknowledged {
+        properties.push(property(
+            "acknowledged",
+            &[],
+            "date-time",
+            [acknowledged.into_json()],
+        ));
+    }
+
+    Some(component("valarm", properties, Vec::new()))
+}
+
+/// Converts the JSON form of a recurrence rule into a jCal RECUR value.
+fn recur_to_jcal<V>(rule: V) -> V
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    let Ok(rule) = rule.try_into_object() else {
+        return object::<V>([]);
+    };
+
+    let upper = |value: V| V::string(string(value).unwrap_or_default().to_ascii_uppercase());
+    let mut parts = Vec::new();
+    for (key, value) in rule.into_iter() {
+        let key = <V::Object as JsonObject>::key_into_string(key);
+        let (name, value) = match key.as_str() {
+            props::FREQUENCY => ("freq", upper(value)),
+            props::FIRST_DAY_OF_WEEK => ("wkst", upper(value)),
+            props::RSCALE => ("rscale", upper(value)),
+            props::SKIP => ("skip", upper(value)),
+            props::UNTIL => ("until", value),
+            props::COUNT => ("count", value),
+            props::INTERVAL => ("interval", value),
+            props::BY_DAY => {
+                let days = elements(value, "").unwrap_or_default();
+                (
+                    "byday",
+                    scalar_or_array(days.into_iter().map(nday_to_jcal).collect()),
+                )
+            }
+            key => match NUMBER_LIST_PARTS.iter().find(|(_, name)| *name == key) {
+                Some((part, _)) => (
+                    *part,
+                    scalar_or_array(elements(value, "").unwrap_or_default()),
+                ),
+                None => continue,
+            },
+        };
+        parts.push((name.to_string(), value));
+    }
+
+    object(parts)
+}
+
+/// Converts the JSON form of an `NDay` into a BYDAY value such as `-1SU`.
+fn nday_to_jcal<V>(nday: V) -> V
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue,
+{
+    let Ok(nday) = nday.try_into_object() else {
+        return V::str("");
+    };
+    let day = nday.get(props::DAY).and_then(as_str).unwrap_or_default();
+    let nth = nday
+        .get(props::NTH_OF_PERIOD)
+        .and_then(|nth| nth.try_as_int().ok())
+        .map(|nth| nth.get().to_string())
+        .unwrap_or_default();
+    V::string(format!("{nth}{}", day.to_ascii_uppercase()))
+}
+
+/// Returns a DATE or DATE-TIME property for a local date-time in the given time zone.
+fn date_time_property<V: ConstructibleJsonValue>(
+    name: &str,
+    local: &str,
+    time_zone: Option<&str>,
+    all_day: bool,
+) -> V {
+    if all_day && let Some(date) = local.strip_suffix("T00:00:00") {
+        return property(name, &[], "date", [V::str(date)]);
+    }
+
+    match time_zone {
+        Some(UTC | "UTC") => property(name, &[], "date-time", [V::string(format!("{local}Z"))]),
+        Some(tzid) => property(name, &[("tzid", tzid)], "date-time", [V::str(local)]),
+        None => property(name, &[], "date-time", [V::str(local)]),
+    }
+}
+
+// ============================================================================
+// JSON helpers
+// ============================================================================
+
+fn component<V: ConstructibleJsonValue>(name: &str, properties: Vec<V>, components: Vec<V>) -> V {
+    array([V::str(name), array(properties), array(components)])
+}
+
+fn property<V: ConstructibleJsonValue>(
+    name: &str,
+    params: &[(&str, &str)],
+    value_type: &str,
+    values: impl IntoIterator<Item = V>,
+) -> V {
+    let params = params
+        .iter()
+        .map(|(name, value)| (name.to_string(), V::str(value)));
+    let head = [V::str(name), object(params), V::str(value_type)];
+    array(head.into_iter().chain(values))
+}
+
+fn array<V: ConstructibleJsonValue>(items: impl IntoIterator<Item = V>) -> V {
+    let mut array = <V::Array as json::JsonArray>::new();
+    for item in items {
+        json::JsonArray::push(&mut array, item);
+    }
+    V::array(array)
+}
+
+fn object<V: ConstructibleJsonValue>(members: impl IntoIterator<Item = (String, V)>) -> V {
+    let mut object = V::Object::new();
+    for (key, value) in members {
+        object.insert(key.into(), value);
+    }
+    V::object(object)
+}
+
+/// Returns the only element of `items` if there is exactly one, and an array otherwise.
+fn scalar_or_array<V: ConstructibleJsonValue>(mut items: Vec<V>) -> V {
+    match items.len() {
+        1 => items.remove(0),
+        _ => array(items),
+    }
+}
+
+/// Returns the elements of `value` if it is an array, and `value` itself otherwise.
+fn list<V: DestructibleJsonValue + Clone>(value: &V) -> impl Iterator<Item = V> {
+    let items: Vec<V> = match value.try_as_array() {
+        Ok(items) => json::JsonArray::iter(items).cloned().collect(),
+        Err(_) => vec![value.clone()],
+    };
+    items.into_iter()
+}
+
+fn elements<V: DestructibleJsonValue>(value: V, error: &'static str) -> Result<Vec<V>, JcalError> {
+    value
+        .try_into_array()
+        .map(|array| json::JsonArray::into_iter(array).collect())
+        .map_err(|_| JcalError::Malformed(error))
+}
+
+/// Removes the fractional seconds of a UTC date-time, which iCalendar cannot represent
+/// (RFC 5545 §3.3.5).
+fn without_fraction<V>(value: V) -> V
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue,
+{
+    match as_str(&value).and_then(|s| s.split_once('.')) {
+        Some((whole, _)) => V::string(format!("{whole}Z")),
+        None => value,
+    }
+}
+
+fn as_str<V: DestructibleJsonValue>(value: &V) -> Option<&str> {
+    value.try_as_string().ok().map(AsRef::as_ref)
+}
+
+fn string<V: DestructibleJsonValue>(value: V) -> Option<String> {
+    value.try_into_string().ok().map(Into::into)
+}
+
+fn sorted(mut names: Vec<String>) -> Vec<String> {
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::try_from_json(Value::from("2024-06-01T00:00:00Z")).unwrap()
+    }
+
+    fn vevent() -> Value {
+        json!(["vevent",
+            [
+                ["uid", {}, "text", "a8df6573-0474-496d-8496-033ad45d7fea"],
+                ["dtstamp", {}, "date-time", "2020-01-02T18:23:04Z"],
+                ["summary", {}, "text", "Team meeting"],
+                ["dtstart", {"tzid": "America/New_York"}, "date-time", "2020-01-15T13:00:00"],
+                ["dtend", {"tzid": "America/New_York"}, "date-time", "2020-01-15T14:30:00"],
+                ["transp", {}, "text", "TRANSPARENT"],
+                ["categories", {}, "text", "work", "weekly"],
+                ["location", {}, "text", "Room 101"],
+                ["rrule", {}, "recur", {"freq": "WEEKLY", "byday": "WE", "count": 10}],
+                ["exdate", {"tzid": "America/New_York"}, "date-time", "2020-01-22T13:00:00"],
+                ["attendee", {}, "cal-address", "mailto:a@example.com"]
+            ],
+            [
+                ["valarm",
+                    [
+                        ["action", {}, "text", "DISPLAY"],
+                        ["trigger", {"related": "END"}, "duration", "-PT15M"],
+                        ["description", {}, "text", "Reminder"]
+                    ],
+                    []
+                ]
+            ]
+        ])
+    }
+
+    #[test]
+    fn event_from_jcal_maps_shared_properties() {
+        let (event, dropped) = event_from_jcal(vevent()).unwrap();
+        assert_eq!(dropped, ["attendee", "valarm/description"]);
+
+        let json = event.into_json();
+        assert_eq!(json["updated"], "2020-01-02T18:23:04Z");
+        assert_eq!(json["title"], "Team meeting");
+        assert_eq!(json["start"], "2020-01-15T13:00:00");
+        assert_eq!(json["timeZone"], "America/New_York");
+        assert_eq!(json["duration"], "PT1H30M");
+        assert_eq!(json["freeBusyStatus"], "free");
+        assert_eq!(json["keywords"], json!({"work": true, "weekly": true}));
+        assert_eq!(json["locations"]["1"]["name"], "Room 101");
+        assert_eq!(
+            json["recurrenceRules"][0],
+            json!({
+                "@type": "RecurrenceRule",
+                "frequency": "weekly",
+                "byDay": [{"@type": "NDay", "day": "we"}],
+                "count": 10
+            })
+        );
+        assert_eq!(
+            json["recurrenceOverrides"],
+            json!({"2020-01-22T13:00:00": {"excluded": true}})
+        );
+        assert_eq!(json["alerts"]["1"]["trigger"]["offset"], "-PT15M");
+        assert_eq!(json["alerts"]["1"]["trigger"]["relativeTo"], "end");
+    }
+
+    #[test]
+    fn event_round_trip() {
+        let (event, _) = event_from_jcal(vevent()).unwrap();
+        let (vevent, dropped) = event_to_jcal(event, now());
+        assert!(dropped.is_empty(), "{dropped:?}");
+
+        let (event, dropped) = event_from_jcal(vevent.clone()).unwrap();
+        assert_eq!(dropped, ["valarm/description"]);
+        assert_eq!(event_to_jcal(event, now()).0, vevent);
+
+        let properties = vevent[1].as_array().unwrap();
+        let get = |name: &str| properties.iter().find(|p| p[0] == name).unwrap().clone();
+        assert_eq!(
+            get("dtstart"),
+            json!(["dtstart", {"tzid": "America/New_York"}, "date-time", "2020-01-15T13:00:00"])
+        );
+        assert_eq!(
+            get("rrule")[3],
+            json!({"freq": "WEEKLY", "byday": "WE", "count": 10})
+        );
+        assert_eq!(
+            get("categories"),
+            json!(["categories", {}, "text", "weekly", "work"])
+        );
+    }
+
+    #[test]
+    fn task_dates_and_progress() {
+        let vtodo = json!(["vtodo",
+            [
+                ["uid", {}, "text", "task-1"],
+                ["due", {}, "date", "2024-05-01"],
+                ["status", {}, "text", "COMPLETED"],
+                ["completed", {}, "date-time", "2024-04-30T09:00:00Z"],
+                ["rrule", {}, "recur", {"freq": "MONTHLY", "byday": ["1MO", "-1FR"], "until": "2024-12-31T00:00:00Z"}]
+            ],
+            []
+        ]);
+
+        let (task, dropped) = task_from_jcal(vtodo).unwrap();
+        assert!(dropped.is_empty(), "{dropped:?}");
+        let json = task.clone().into_json();
+        assert_eq!(json["due"], "2024-05-01T00:00:00");
+        assert_eq!(json["showWithoutTime"], true);
+        assert_eq!(json["progress"], "completed");
+        assert_eq!(json["recurrenceRules"][0]["until"], "2024-12-31T00:00:00");
+        assert_eq!(
+            json["recurrenceRules"][0]["byDay"],
+            json!([
+                {"@type": "NDay", "day": "fr", "nthOfPeriod": -1},
+                {"@type": "NDay", "day": "mo", "nthOfPeriod": 1}
+            ])
+        );
+
+        let (vtodo, _) = task_to_jcal(task, now());
+        let properties = vtodo[1].as_array().unwrap();
+        assert!(properties.contains(&json!(["due", {}, "date", "2024-05-01"])));
+        assert!(properties.contains(&json!(["status", {}, "text", "COMPLETED"])));
+        let rrule = properties.iter().find(|p| p[0] == "rrule").unwrap();
+        assert_eq!(rrule[3]["byday"], json!(["-1FR", "1MO"]));
+    }
+
+    #[test]
+    fn calendar_entries() {
+        let vcalendar = json!([
+            "vcalendar",
+            [
+                ["version", {}, "text", "2.0"],
+                ["method", {}, "text", "PUBLISH"]
+            ],
+            [
+                vevent(),
+                ["vtimezone", [["tzid", {}, "text", "America/New_York"]], []],
+                ["vtodo", [["uid", {}, "text", "task-1"]], []]
+            ]
+        ]);
+
+        let (entries, dropped) = entries_from_jcal(vcalendar).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            dropped,
+            ["attendee", "method", "valarm/description", "vtimezone"]
+        );
+
+        let (vcalendar, dropped) = entries_to_jcal(entries, "-//Example//EN", now());
+        assert!(dropped.is_empty(), "{dropped:?}");
+        assert_eq!(vcalendar[0], "vcalendar");
+        assert_eq!(
+            vcalendar[1][1],
+            json!(["prodid", {}, "text", "-//Example//EN"])
+        );
+        assert_eq!(vcalendar[2][0][0], "vevent");
+        assert_eq!(vcalendar[2][1][0], "vtodo");
+    }
+
+    #[test]
+    fn unmapped_members_are_reported() {
+        let event = Event::try_from_json(json!({
+            "@type": "Event",
+            "uid": "e",
+            "start": "2024-01-01T10:00:00",
+            "timeZone": "Etc/UTC",
+            "locale": "en",
+            "locations": {
+                "a": {"@type": "Location", "name": "A"},
+                "b": {"@type": "Location", "name": "B"}
+            },
+            "recurrenceOverrides": {"2024-01-02T10:00:00": {"title": "Moved"}}
+        }))
+        .unwrap();
+
+        let (vevent, dropped) = event_to_jcal::<Value>(event, now());
+        assert_eq!(dropped, ["locale", "locations", "recurrenceOverrides"]);
+        let properties = vevent[1].as_array().unwrap();
+        assert!(properties.contains(&json!(["dtstart", {}, "date-time", "2024-01-01T10:00:00Z"])));
+        assert!(properties.contains(&json!(["location", {}, "text", "A"])));
+    }
+
+    #[test]
+    fn alerts_follow_the_valarm_mapping() {
+        let event = Event::try_from_json(json!({
+            "@type": "Event",
+            "uid": "e",
+            "title": "Launch",
+            "start": "2024-01-01T10:00:00",
+            "alerts": {
+                "a": {
+                    "@type": "Alert",
+                    "action": "email",
+                    "trigger": {"@type": "AbsoluteTrigger", "when": "2024-01-01T09:00:00Z"},
+                    "relatedTo": {"b": {"@type": "Relation"}}
+                },
+                "b": {
+                    "@type": "Alert",
+                    "action": "audio",
+                    "trigger": {"@type": "OffsetTrigger", "offset": "-PT5M", "relativeTo": "end"},
+                    "example.com:sound": "chime"
+                }
+            }
+        }))
+        .unwrap();
+
+        let (vevent, dropped) = event_to_jcal::<Value>(event, now());
+        assert_eq!(dropped, ["alerts/example.com:sound", "alerts/relatedTo"]);
+        assert_eq!(
+            vevent[2],
+            json!([
+                ["valarm", [
+                    ["action", {}, "text", "EMAIL"],
+                    ["trigger", {}, "date-time", "2024-01-01T09:00:00Z"],
+                    ["description", {}, "text", "Launch"],
+                    ["summary", {}, "text", "Launch"]
+                ], []],
+                ["valarm", [
+                    ["action", {}, "text", "AUDIO"],
+                    ["trigger", {"related": "END"}, "duration", "-PT5M"]
+                ], []]
+            ])
+        );
+
+        let (event, dropped) = event_from_jcal::<Value>(vevent).unwrap();
+        assert_eq!(dropped, ["valarm/description", "valarm/summary"]);
+        let alerts = event.into_json()["alerts"].clone();
+        let actions: Vec<_> = alerts
+            .as_object()
+            .unwrap()
+            .values()
+            .map(|a| &a["action"])
+            .collect();
+        assert_eq!(actions, ["email", "audio"]);
+    }
+
+    #[test]
+    fn dtstamp_is_always_written() {
+        let dtstamp = |extra: Value| {
+            let mut task = json!({"@type": "Task", "uid": "t"});
+            task.as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            let (vtodo, _) = task_to_jcal(Task::<Value>::try_from_json(task).unwrap(), now());
+            let properties = vtodo[1].as_array().unwrap().clone();
+            let find = |name: &str| {
+                properties
+                    .iter()
+                    .find(|p| p[0] == name)
+                    .map(|p| p[3].clone())
+            };
+            (find("dtstamp").unwrap(), find("last-modified"))
+        };
+
+        assert_eq!(
+            dtstamp(json!({"updated": "2024-02-01T00:00:00Z", "created": "2024-01-01T00:00:00Z"})),
+            (
+                json!("2024-02-01T00:00:00Z"),
+                Some(json!("2024-02-01T00:00:00Z"))
+            )
+        );
+        assert_eq!(
+            dtstamp(json!({"created": "2024-01-01T00:00:00Z"})),
+            (json!("2024-01-01T00:00:00Z"), None)
+        );
+        assert_eq!(dtstamp(json!({})), (json!("2024-06-01T00:00:00Z"), None));
+        assert_eq!(
+            dtstamp(json!({"updated": "2024-02-01T00:00:00.25Z"})),
+            (
+                json!("2024-02-01T00:00:00Z"),
+                Some(json!("2024-02-01T00:00:00Z"))
+            )
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            event_from_jcal(json!(["vtodo", [], []])).unwrap_err(),
+            JcalError::UnexpectedComponent {
+                expected: "vevent",
+                found: "vtodo".into()
+            }
+        );
+        assert!(matches!(
+            event_from_jcal(json!(["vevent", []])),
+            Err(JcalError::Malformed(_))
+        ));
+        assert_eq!(
+            event_from_jcal(json!([
+                "vevent",
+                [["dtstart", {}, "date-time", "soon"]],
+                []
+            ]))
+            .unwrap_err(),
+            JcalError::InvalidValue("dtstart".into())
+        );
+        assert!(matches!(
+            event_from_jcal(json!(["vevent", [["uid", {}, "text", "e"]], []])),
+            Err(JcalError::Object(_))
+        ));
+    }
+}
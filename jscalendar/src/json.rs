@@ -11,7 +11,7 @@ use std::{
 
 use calendar_types::{
     duration::{Duration, SignedDuration},
-    set::Token,
+    set::{Token, TokenParse},
     time::{DateTime, Local, Utc},
 };
 use crate::model::set::{Percent, Priority};
@@ -21,6 +21,13 @@ use crate::parser::{
     OwnedParseError, duration, local_date_time, parse_full, signed_duration, utc_date_time,
 };
 
+pub mod error;
+#[cfg(feature = "ijson")]
+mod ijson_impl;
+
+#[cfg(feature = "ijson")]
+pub use ijson_impl::IJsonString;
+
 /// Fallible conversion from a JSON value into a Rust type.
 pub trait TryFromJson<V>
 where
@@ -92,15 +99,14 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for SignedDuration {
 
 impl<T, V> TryFromJson<V> for Token<T, Box<str>>
 where
-    T: FromStr,
+    T: TokenParse,
     V: DestructibleJsonValue,
 {
     type Error = TypeError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let s = value.try_into_string()?;
-        // Token::from_str is infallible when S = Box<str> (since &str: Into<Box<str>>)
-        Ok(Token::from_str(s.as_ref()).unwrap())
+        Ok(Token::parse(s.as_ref()))
     }
 }
 
@@ -394,6 +400,34 @@ impl<E> DocumentError<E> {
     pub fn into_parts(self) -> (VecDeque<PathSegment<Box<str>>>, E) {
         (self.path, self.error)
     }
+
+    /// Prepends a statically-known object key to the path, as when this error occurred in the
+    /// value of that key.
+    pub fn prepend_static(mut self, key: &'static str) -> Self {
+        self.path.push_front(PathSegment::Static(key));
+        self
+    }
+
+    /// Prepends an object key to the path, as when this error occurred in the value of that key.
+    pub fn prepend_string(mut self, key: impl Into<Box<str>>) -> Self {
+        self.path.push_front(PathSegment::String(key.into()));
+        self
+    }
+
+    /// Prepends an array index to the path, as when this error occurred in the element at that
+    /// index.
+    pub fn prepend_index(mut self, index: usize) -> Self {
+        self.path.push_front(PathSegment::Index(index));
+        self
+    }
+
+    /// Maps the underlying error, keeping the path.
+    pub fn map_error<F>(self, f: impl FnOnce(E) -> F) -> DocumentError<F> {
+        DocumentError {
+            path: self.path,
+            error: f(self.error),
+        }
+    }
 }
 
 impl<E: std::fmt::Display> std::fmt::Display for DocumentError<E> {
@@ -489,6 +523,21 @@ impl Int {
     }
 }
 
+/// Implements lossless `From` conversions into an integer type from narrower primitive integers.
+macro_rules! impl_from_narrower {
+    ($target:ident: $($ty:ty),*) => {
+        $(
+            impl From<$ty> for $target {
+                fn from(value: $ty) -> Self {
+                    Self(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_narrower!(Int: i8, i16, i32, u8, u16, u32);
+
 impl<V: ConstructibleJsonValue> IntoJson<V> for Int {
     fn into_json(self) -> V {
         V::int(self)
@@ -531,21 +580,21 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for SignedDuration {
     }
 }
 
-impl<T: fmt::Display, S: fmt::Display, V: ConstructibleJsonValue> IntoJson<V> for Token<T, S> {
+impl<T: TokenParse, S: AsRef<str>, V: ConstructibleJsonValue> IntoJson<V> for Token<T, S> {
     fn into_json(self) -> V {
-        V::string(self.to_string())
+        V::string(self.canonical_str().to_owned())
     }
 }
 
 impl<V: ConstructibleJsonValue> IntoJson<V> for Priority {
     fn into_json(self) -> V {
-        V::unsigned_int(UnsignedInt::new(self as u64).unwrap())
+        V::unsigned_int(UnsignedInt::from(self as u8))
     }
 }
 
 impl<V: ConstructibleJsonValue> IntoJson<V> for Percent {
     fn into_json(self) -> V {
-        V::unsigned_int(UnsignedInt::new(self.get() as u64).unwrap())
+        V::unsigned_int(UnsignedInt::from(self.get()))
     }
 }
 
@@ -562,9 +611,10 @@ impl<T: IntoJson<V>, V: ConstructibleJsonValue> IntoJson<V> for Vec<T> {
 impl<K: fmt::Display, T: IntoJson<V>, V: ConstructibleJsonValue> IntoJson<V> for HashMap<K, T> {
     fn into_json(self) -> V {
         let mut obj = V::Object::with_capacity(self.len());
-        for (key, value) in self {
-            obj.insert(key.to_string().into(), value.into_json());
-        }
+        obj.insert_unordered(
+            self.into_iter()
+                .map(|(key, value)| (key.to_string().into(), value.into_json())),
+        );
         V::object(obj)
     }
 }
@@ -572,9 +622,10 @@ impl<K: fmt::Display, T: IntoJson<V>, V: ConstructibleJsonValue> IntoJson<V> for
 impl<T: fmt::Display + Eq + Hash, V: ConstructibleJsonValue> IntoJson<V> for HashSet<T> {
     fn into_json(self) -> V {
         let mut obj = V::Object::with_capacity(self.len());
-        for item in self {
-            obj.insert(item.to_string().into(), V::bool(true));
-        }
+        obj.insert_unordered(
+            self.into_iter()
+                .map(|item| (item.to_string().into(), V::bool(true))),
+        );
         V::object(obj)
     }
 }
@@ -606,6 +657,16 @@ impl UnsignedInt {
         }
     }
 
+    /// Creates an `UnsignedInt` from a raw `u64`, clamping it to [`UnsignedInt::MAX`] if it is
+    /// out of range.
+    #[inline(always)]
+    pub const fn saturating_new(value: u64) -> Self {
+        match Self::new(value) {
+            Some(value) => value,
+            None => Self::MAX,
+        }
+    }
+
     /// Returns the numeric value.
     #[inline(always)]
     pub const fn get(self) -> u64 {
@@ -613,6 +674,8 @@ impl UnsignedInt {
     }
 }
 
+impl_from_narrower!(UnsignedInt: u8, u16, u32);
+
 impl<V: ConstructibleJsonValue> IntoJson<V> for UnsignedInt {
     fn into_json(self) -> V {
         V::unsigned_int(self)
@@ -627,6 +690,192 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for UnsignedInt {
     }
 }
 
+/// A JSON number, held without loss of precision.
+///
+/// A `Number` is an integer in the range of `i64` or `u64`, a finite `f64`, or a decimal which
+/// neither can hold exactly, kept as its JSON text. Unlike [`Int`] and [`UnsignedInt`], it is not
+/// bounded to the range of I-JSON, and so is suited to vendor properties and other values whose
+/// precision should survive a round trip. A backend whose numbers are not `f64`s, such as one with
+/// arbitrary precision, builds and reads values through [`ConstructibleJsonValue::number`] and
+/// [`DestructibleJsonValue::try_as_number`].
+///
+/// Numbers are compared by their representation, so an `f64` and a decimal with the same value
+/// are not equal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Number(NumberRepr);
+
+#[derive(Debug, Clone, PartialEq)]
+enum NumberRepr {
+    /// A negative integer.
+    NegInt(i64),
+    /// A non-negative integer.
+    PosInt(u64),
+    /// A finite float.
+    Float(f64),
+    /// A number in the grammar of RFC 8259 §6 which is not an integer in range.
+    Decimal(Box<str>),
+}
+
+impl Number {
+    /// Creates a `Number` from an `f64`, failing if it is NaN or infinite.
+    pub fn from_f64(value: f64) -> Result<Self, NonFiniteNumberError> {
+        match value.is_finite() {
+            true => Ok(Self(NumberRepr::Float(value))),
+            false => Err(NonFiniteNumberError(value)),
+        }
+    }
+
+    /// Returns the value as an `i64`, if it is an integer in its range.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.0 {
+            NumberRepr::NegInt(n) => Some(n),
+            NumberRepr::PosInt(n) => i64::try_from(n).ok(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a `u64`, if it is an integer in its range.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.0 {
+            NumberRepr::PosInt(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Returns the nearest `f64` to the value, saturating at [`f64::MIN`] and [`f64::MAX`].
+    pub fn as_f64(&self) -> f64 {
+        match &self.0 {
+            NumberRepr::NegInt(n) => *n as f64,
+            NumberRepr::PosInt(n) => *n as f64,
+            NumberRepr::Float(n) => *n,
+            NumberRepr::Decimal(text) => text
+                .parse::<f64>()
+                .expect("decimals are valid JSON numbers")
+                .clamp(f64::MIN, f64::MAX),
+        }
+    }
+
+    /// Returns `true` if the value is held as an integer.
+    pub fn is_integer(&self) -> bool {
+        matches!(self.0, NumberRepr::NegInt(_) | NumberRepr::PosInt(_))
+    }
+
+    /// Returns the value as [`Int`], if it is an integer in its range.
+    pub fn as_int(&self) -> Option<Int> {
+        self.as_i64().and_then(Int::new)
+    }
+
+    /// Returns the value as [`UnsignedInt`], if it is an integer in its range.
+    pub fn as_unsigned_int(&self) -> Option<UnsignedInt> {
+        self.as_u64().and_then(UnsignedInt::new)
+    }
+}
+
+impl From<i64> for Number {
+    fn from(value: i64) -> Self {
+        match u64::try_from(value) {
+            Ok(value) => Self(NumberRepr::PosInt(value)),
+            Err(_) => Self(NumberRepr::NegInt(value)),
+        }
+    }
+}
+
+impl From<u64> for Number {
+    fn from(value: u64) -> Self {
+        Self(NumberRepr::PosInt(value))
+    }
+}
+
+impl From<Int> for Number {
+    fn from(value: Int) -> Self {
+        value.get().into()
+    }
+}
+
+impl From<UnsignedInt> for Number {
+    fn from(value: UnsignedInt) -> Self {
+        value.get().into()
+    }
+}
+
+impl FromStr for Number {
+    type Err = InvalidNumberError;
+
+    /// Parses a number in the grammar of RFC 8259 §6, keeping its exact value.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !is_json_number(s) {
+            return Err(InvalidNumberError(s.into()));
+        }
+
+        let integer = !s.contains(['.', 'e', 'E']);
+        let repr = match s.strip_prefix('-') {
+            Some(_) if integer => s.parse::<i64>().ok().map(Number::from),
+            None if integer => s.parse::<u64>().ok().map(Number::from),
+            _ => None,
+        };
+        Ok(repr.unwrap_or_else(|| Self(NumberRepr::Decimal(s.into()))))
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            NumberRepr::NegInt(n) => write!(f, "{n}"),
+            NumberRepr::PosInt(n) => write!(f, "{n}"),
+            NumberRepr::Float(n) => write!(f, "{n}"),
+            NumberRepr::Decimal(text) => f.write_str(text),
+        }
+    }
+}
+
+impl<V: ConstructibleJsonValue> IntoJson<V> for Number {
+    fn into_json(self) -> V {
+        V::number(self)
+    }
+}
+
+impl<V: DestructibleJsonValue> TryFromJson<V> for Number {
+    type Error = TypeError;
+
+    fn try_from_json(value: V) -> Result<Self, Self::Error> {
+        value.try_as_number()
+    }
+}
+
+/// Returns `true` if `s` is a number in the grammar of RFC 8259 §6.
+fn is_json_number(s: &str) -> bool {
+    fn digits(s: &[u8]) -> usize {
+        s.iter().take_while(|b| b.is_ascii_digit()).count()
+    }
+
+    let s = s.as_bytes();
+    let mut i = usize::from(s.first() == Some(&b'-'));
+    match s.get(i) {
+        Some(b'0') => i += 1,
+        Some(b'1'..=b'9') => i += digits(&s[i..]),
+        _ => return false,
+    }
+    if s.get(i) == Some(&b'.') {
+        let n = digits(&s[i + 1..]);
+        if n == 0 {
+            return false;
+        }
+        i += 1 + n;
+    }
+    if matches!(s.get(i), Some(b'e' | b'E')) {
+        i += 1;
+        if matches!(s.get(i), Some(b'+' | b'-')) {
+            i += 1;
+        }
+        let n = digits(&s[i..]);
+        if n == 0 {
+            return false;
+        }
+        i += n;
+    }
+    i == s.len()
+}
+
 /// The type of a JSON value.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ValueType {
@@ -707,6 +956,17 @@ pub enum IntoUnsignedIntError {
     OutsideRange(u64),
 }
 
+/// Error returned when a float which is NaN or infinite is converted to a JSON number, which
+/// cannot represent it.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Error)]
+#[error("{0} is not a finite number")]
+pub struct NonFiniteNumberError(pub f64);
+
+/// Error returned when a string is not a JSON number.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Error)]
+#[error("{0:?} is not a JSON number")]
+pub struct InvalidNumberError(pub Box<str>);
+
 /// A type representing a JSON value.
 pub trait JsonValue {
     /// The string representation used by this JSON value.
@@ -782,6 +1042,26 @@ pub trait DestructibleJsonValue: Sized + JsonValue {
     fn try_as_int(&self) -> Result<Int, TypeErrorOr<IntoIntError>>;
     /// Tries to extract an unsigned integer.
     fn try_as_unsigned_int(&self) -> Result<UnsignedInt, TypeErrorOr<IntoUnsignedIntError>>;
+    /// Tries to get the number value without loss of precision.
+    ///
+    /// The default implementation reads any number outside the range of [`Int`] and
+    /// [`UnsignedInt`] through [`try_as_f64`], and so should be overridden by a backend whose numbers are more precise than an `f64`.
+    ///
+    /// [`try_as_f64`]: DestructibleJsonValue::try_as_f64
+    fn try_as_number(&self) -> Result<Number, TypeError> {
+        if let Ok(n) = self.try_as_unsigned_int() {
+            return Ok(n.into());
+        } else if let Ok(n) = self.try_as_int() {
+            return Ok(n.into());
+        }
+
+        // a NaN or infinity is not a JSON number, even if the backend can hold one
+        let value = self.try_as_f64()?;
+        Number::from_f64(value).map_err(|_| TypeError {
+            expected: ValueType::Number,
+            received: self.value_type(),
+        })
+    }
     /// Tries to borrow the string value.
     fn try_as_string(&self) -> Result<&Self::String, TypeError>;
     /// Tries to borrow the array value.
@@ -816,12 +1096,37 @@ pub trait ConstructibleJsonValue: Sized + JsonValue {
     fn cow_str(value: Cow<'_, str>) -> Self;
 
     /// Creates a JSON number from an `f64`.
+    ///
+    /// A NaN or infinite `value` has no JSON representation, and what this returns for it is up
+    /// to the implementation; [`float`] rejects such values instead.
+    ///
+    /// [`float`]: ConstructibleJsonValue::float
     fn f64(value: f64) -> Self;
     /// Creates a JSON number from an [`Int`].
     fn int(value: Int) -> Self;
     /// Creates a JSON number from an [`UnsignedInt`].
     fn unsigned_int(value: UnsignedInt) -> Self;
 
+    /// Creates a JSON number from an `f64`, failing if it is NaN or infinite.
+    fn float(value: f64) -> Result<Self, NonFiniteNumberError> {
+        Number::from_f64(value).map(Self::number)
+    }
+
+    /// Creates a JSON number from a [`Number`].
+    ///
+    /// The default implementation builds integers in the range of [`Int`] or [`UnsignedInt`]
+    /// exactly, and any other number from its nearest `f64`, and so should be overridden by a
+    /// backend whose numbers are more precise than an `f64`.
+    fn number(value: Number) -> Self {
+        if let Some(n) = value.as_unsigned_int() {
+            Self::unsigned_int(n)
+        } else if let Some(n) = value.as_int() {
+            Self::int(n)
+        } else {
+            Self::f64(value.as_f64())
+        }
+    }
+
     /// Creates a JSON array value.
     fn array(value: Self::Array) -> Self;
     /// Creates a JSON object value.
@@ -853,6 +1158,13 @@ pub trait JsonObject: Sized {
     /// Converts an owned key into a `String`.
     fn key_into_string(key: Self::Key) -> String;
 
+    /// Borrows a key as a string slice, which unlike [`key_into_string`](Self::key_into_string)
+    /// never allocates.
+    #[inline(always)]
+    fn key_as_str(key: &Self::Key) -> &str {
+        key.borrow()
+    }
+
     /// Inserts a key-value pair into the object.
     fn insert(&mut self, key: Self::Key, value: Self::Value);
 
@@ -886,6 +1198,32 @@ pub trait JsonObject: Sized {
     fn values(&self) -> impl Iterator<Item = &Self::Value> {
         self.iter().map(|(_, value)| value)
     }
+
+    /// Returns `true` if the object keeps its entries in the order in which they were inserted,
+    /// rather than in an order of its own.
+    #[inline(always)]
+    fn preserves_order() -> bool {
+        false
+    }
+
+    /// Inserts entries which come in no particular order, such as those of a `HashMap`.
+    ///
+    /// If the object [preserves the order](Self::preserves_order) of its entries, they are
+    /// inserted in the order of their keys, so that the same entries are always written in the
+    /// same order.
+    fn insert_unordered(&mut self, entries: impl IntoIterator<Item = (Self::Key, Self::Value)>) {
+        if Self::preserves_order() {
+            let mut entries: Vec<_> = entries.into_iter().collect();
+            entries.sort_unstable_by(|(a, _), (b, _)| Self::key_as_str(a).cmp(Self::key_as_str(b)));
+            for (key, value) in entries {
+                self.insert(key, value);
+            }
+        } else {
+            for (key, value) in entries {
+                self.insert(key, value);
+            }
+        }
+    }
 }
 
 /// A type which represents a JSON array.
@@ -1010,13 +1348,13 @@ impl<T> JsonArray for Vec<T> {
 
 #[cfg(feature = "serde_json")]
 mod serde_json_impl {
-    use std::{borrow::Cow, hash::Hash};
+    use std::{borrow::Cow, hash::Hash, sync::OnceLock};
 
     use serde_json::{Map, Value};
 
     use super::{
         ConstructibleJsonValue, DestructibleJsonValue, Int, IntoIntError, IntoUnsignedIntError,
-        JsonObject, JsonValue, TypeError, TypeErrorOr, UnsignedInt, ValueType,
+        JsonObject, JsonValue, Number, TypeError, TypeErrorOr, UnsignedInt, ValueType,
     };
 
     impl JsonValue for Value {
@@ -1056,6 +1394,25 @@ mod serde_json_impl {
                 })
         }
 
+        fn try_as_number(&self) -> Result<Number, TypeError> {
+            let number = self.as_number().ok_or_else(|| TypeError {
+                expected: ValueType::Number,
+                received: self.value_type(),
+            })?;
+
+            if let Some(n) = number.as_u64() {
+                Ok(n.into())
+            } else if let Some(n) = number.as_i64() {
+                Ok(n.into())
+            } else {
+                // the text of a number is exact, even with the arbitrary_precision feature
+                Ok(number
+                    .to_string()
+                    .parse()
+                    .expect("serde_json writes valid numbers"))
+            }
+        }
+
         #[inline(always)]
         fn try_as_string(&self) -> Result<&<Self as JsonValue>::String, TypeError> {
             match self {
@@ -1205,6 +1562,20 @@ mod serde_json_impl {
             value.get().into()
         }
 
+        fn number(value: Number) -> Self {
+            if let Some(n) = value.as_u64() {
+                n.into()
+            } else if let Some(n) = value.as_i64() {
+                n.into()
+            } else {
+                // a decimal is parsed as an f64 unless the arbitrary_precision feature is enabled
+                value
+                    .to_string()
+                    .parse::<serde_json::Number>()
+                    .map_or_else(|_| value.as_f64().into(), Value::Number)
+            }
+        }
+
         #[inline(always)]
         fn array(value: <Self as JsonValue>::Array) -> Self {
             Value::Array(value)
@@ -1267,6 +1638,18 @@ mod serde_json_impl {
         fn into_iter(self) -> impl Iterator<Item = (Self::Key, Self::Value)> {
             IntoIterator::into_iter(self)
         }
+
+        fn preserves_order() -> bool {
+            // a Map is sorted by key unless serde_json is built with its preserve_order feature,
+            // which another crate in the build may enable even if this one does not
+            static PRESERVES_ORDER: OnceLock<bool> = OnceLock::new();
+            *PRESERVES_ORDER.get_or_init(|| {
+                let mut map = Map::new();
+                map.insert("b".into(), Value::Null);
+                map.insert("a".into(), Value::Null);
+                map.keys().next().is_some_and(|key| key == "b")
+            })
+        }
     }
 }
 
@@ -1550,4 +1933,120 @@ mod tests {
             .into())
         );
     }
+
+    #[test]
+    fn number_from_str() {
+        let parse = |s: &str| s.parse::<Number>();
+
+        assert_eq!(parse("0"), Ok(Number::from(0u64)));
+        assert_eq!(parse("-0"), Ok(Number::from(0u64)));
+        assert_eq!(parse("-42"), Ok(Number::from(-42i64)));
+        assert_eq!(parse("18446744073709551615"), Ok(Number::from(u64::MAX)));
+        assert_eq!(parse("-9223372036854775808"), Ok(Number::from(i64::MIN)));
+
+        // numbers which are not integers in range are kept as written
+        for s in [
+            "1.5",
+            "1e3",
+            "-2.50E-7",
+            "18446744073709551616",
+            "0.1000000000000000055511",
+        ] {
+            let number = parse(s).unwrap();
+            assert!(!number.is_integer(), "{s}");
+            assert_eq!(number.to_string(), s);
+        }
+        assert_eq!(parse("1e400").unwrap().as_f64(), f64::MAX);
+
+        for s in [
+            "", "-", "01", "+1", ".5", "1.", "1e", "1e+", "0x10", "1 ", "NaN", "Infinity",
+        ] {
+            assert_eq!(parse(s), Err(InvalidNumberError(s.into())), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn number_from_f64() {
+        assert_eq!(Number::from_f64(0.5).unwrap().as_f64(), 0.5);
+        assert_eq!(Number::from_f64(2.0).unwrap().as_i64(), None);
+        assert!(Number::from_f64(f64::NAN).is_err());
+        assert_eq!(
+            Number::from_f64(f64::NEG_INFINITY),
+            Err(NonFiniteNumberError(f64::NEG_INFINITY))
+        );
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn number_serde_json() {
+        use serde_json::{Value, json};
+
+        assert!(Value::float(f64::INFINITY).is_err());
+        assert!(Value::float(f64::NAN).is_err());
+        assert_eq!(Value::float(-1.25), Ok(json!(-1.25)));
+
+        // integers beyond the range of Int and UnsignedInt are exact
+        for value in [
+            json!(u64::MAX),
+            json!(i64::MIN),
+            json!(1u64 << 60),
+            json!(0.1),
+        ] {
+            let number = Number::try_from_json(value.clone()).unwrap();
+            let json: Value = number.into_json();
+            assert_eq!(json, value);
+        }
+        assert_eq!(
+            Number::try_from_json(json!(u64::MAX)).unwrap().as_u64(),
+            Some(u64::MAX)
+        );
+        assert_eq!(
+            Number::try_from_json(json!("1")),
+            Err(TypeError {
+                expected: ValueType::Number,
+                received: ValueType::String
+            })
+        );
+
+        let decimal: Number = "2.5e-3".parse().unwrap();
+        assert_eq!(Value::number(decimal), json!(0.0025));
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn unordered_members_are_sorted() {
+        use serde_json::{Map, Value, json};
+
+        use crate::model::object::Event;
+
+        let input = json!({
+            "uid": "ordered",
+            "start": "2024-01-15T13:00:00",
+            "keywords": { "c": true, "a": true, "b": true, "d": true },
+            "example.com:z": 1,
+            "example.com:y": 2
+        });
+        let output: Value = Event::<Value>::try_from_json(input).unwrap().into_json();
+        let keywords: Vec<_> = output["keywords"].as_object().unwrap().keys().collect();
+        assert_eq!(keywords, ["a", "b", "c", "d"]);
+
+        // the properties of the event are written in a fixed order, followed by the sorted vendor
+        // properties
+        let keys: Vec<_> = output.as_object().unwrap().keys().collect();
+        if Map::<String, Value>::preserves_order() {
+            assert_eq!(
+                keys,
+                [
+                    "@type",
+                    "uid",
+                    "start",
+                    "keywords",
+                    "example.com:y",
+                    "example.com:z"
+                ]
+            );
+        } else {
+            assert!(keys.is_sorted());
+        }
+    }
 }
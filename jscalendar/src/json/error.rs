@@ -0,0 +1,154 @@
+//! Helpers for reporting errors from [`TryFromJson`] implementations.
+//!
+//! The object types of this crate report an invalid property as an [`ObjectError`]: a
+//! [`DocumentError`] whose path leads from the object to the property, and whose error is either
+//! a [`TypeError`] or an [`ObjectFromJsonError`]. An extension object defined in another crate can
+//! report its errors in the same shape, so that they compose with those of the objects which
+//! contain it:
+//!
+//! - [`missing`] reports an absent required property;
+//! - [`field_error`] reports a property whose value failed to convert;
+//! - [`nested_field_error`] and [`prepend`] report an error in the value of a property, where the
+//!   value is itself an object with a path of its own.
+//!
+//! Paths of any other shape are built with [`DocumentError::prepend_static`],
+//! [`DocumentError::prepend_string`], and [`DocumentError::prepend_index`].
+//!
+//! ```
+//! use jscalendar::json::{
+//!     DestructibleJsonValue, DocumentError, JsonObject, TryFromJson, UnsignedInt,
+//!     error::{ObjectError, field_error, missing},
+//! };
+//!
+//! /// A reserved seat, as in `{"row": "F", "number": 12}`.
+//! struct Seat {
+//!     row: String,
+//!     number: UnsignedInt,
+//! }
+//!
+//! impl<V: DestructibleJsonValue> TryFromJson<V> for Seat {
+//!     type Error = ObjectError;
+//!
+//!     fn try_from_json(value: V) -> Result<Self, Self::Error> {
+//!         let object = value
+//!             .try_into_object()
+//!             .map_err(|error| DocumentError::root(error.into()))?;
+//!
+//!         let (mut row, mut number) = (None, None);
+//!         for (key, value) in object.into_iter() {
+//!             match V::Object::key_as_str(&key) {
+//!                 "row" => row = Some(String::try_from_json(value).map_err(|e| field_error("row", e))?),
+//!                 "number" => {
+//!                     number = Some(UnsignedInt::try_from_json(value).map_err(|e| field_error("number", e))?)
+//!                 }
+//!                 _ => {}
+//!             }
+//!         }
+//!
+//!         Ok(Seat {
+//!             row: row.ok_or_else(|| missing("row"))?,
+//!             number: number.ok_or_else(|| missing("number"))?,
+//!         })
+//!     }
+//! }
+//! ```
+
+use crate::{
+    json::{DocumentError, LiftTypeError, TypeErrorOr},
+    model::object::{FieldValueError, ObjectFromJsonError},
+};
+
+#[cfg(doc)]
+use crate::json::{TryFromJson, TypeError};
+
+/// The error type of the [`TryFromJson`] implementations of the object types.
+pub type ObjectError = DocumentError<TypeErrorOr<ObjectFromJsonError>>;
+
+/// Reports that the value of the property `field` failed to convert with `error`.
+///
+/// A [`TypeError`] is kept as it is, and any other error is converted into a [`FieldValueError`]
+/// held by an [`ObjectFromJsonError::InvalidFieldValue`].
+pub fn field_error<E>(field: &'static str, error: E) -> ObjectError
+where
+    E: LiftTypeError,
+    E::Residual: Into<FieldValueError>,
+{
+    DocumentError::root(invalid_value(error.lift_type_error())).prepend_static(field)
+}
+
+/// Reports an error in the value of the property `field`, where `error` carries the path within
+/// that value.
+///
+/// This is [`field_error`] for values whose own conversion reports a [`DocumentError`].
+pub fn nested_field_error<E>(field: &'static str, error: DocumentError<E>) -> ObjectError
+where
+    E: LiftTypeError,
+    E::Residual: Into<FieldValueError>,
+{
+    error
+        .map_error(|error| invalid_value(error.lift_type_error()))
+        .prepend_static(field)
+}
+
+/// Prepends the property `field` to the path of `error`.
+pub fn prepend(field: &'static str, error: ObjectError) -> ObjectError {
+    error.prepend_static(field)
+}
+
+/// Reports that the required property `field` is absent.
+pub fn missing(field: &'static str) -> ObjectError {
+    DocumentError::root(TypeErrorOr::Other(ObjectFromJsonError::MissingField(field)))
+}
+
+fn invalid_value<E>(error: TypeErrorOr<E>) -> TypeErrorOr<ObjectFromJsonError>
+where
+    E: Into<FieldValueError>,
+{
+    match error {
+        TypeErrorOr::TypeError(error) => TypeErrorOr::TypeError(error),
+        TypeErrorOr::Other(error) => TypeErrorOr::Other(ObjectFromJsonError::InvalidFieldValue(
+            error.into(),
+        )),
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        json::{PathSegment, TryFromJson},
+        model::object::{Event, Location},
+    };
+
+    #[test]
+    fn paths() {
+        let error = String::try_from_json(json!(1)).unwrap_err();
+        let error = prepend("locations", field_error("name", error).prepend_string("l1"));
+        assert_eq!(
+            error.path(),
+            &[
+                PathSegment::Static("locations"),
+                PathSegment::String("l1".into()),
+                PathSegment::Static("name"),
+            ]
+        );
+        assert!(matches!(error.error(), TypeErrorOr::TypeError(_)));
+
+        let error = Location::<serde_json::Value>::try_from_json(json!({
+            "@type": "Location",
+            "coordinates": 5
+        }))
+        .unwrap_err();
+        let error = prepend("location", error).prepend_index(2);
+        assert_eq!(
+            error.to_string(),
+            "[2]/location/coordinates: expected a value of type string but received type number instead"
+        );
+
+        let error =
+            Event::<serde_json::Value>::try_from_json(json!({"@type": "Event"})).unwrap_err();
+        assert_eq!(error, missing("start"));
+    }
+}
@@ -0,0 +1,456 @@
+//! Implementations of the JSON value traits for [`ijson::IValue`] (requires the `ijson` feature).
+//!
+//! An [`IValue`] is a pointer-sized value whose strings are interned and whose arrays and objects
+//! are single allocations, so that a large document takes much less memory than it would as a
+//! `serde_json::Value`. Its strings are lent out as [`IJsonString`], since the string type of a
+//! [`JsonValue`] must be viewable as a `&str` through [`AsRef`], which [`IString`] is not.
+
+use std::{
+    borrow::{Borrow, Cow},
+    fmt,
+    hash::Hash,
+    ops::Deref,
+};
+
+use ijson::{Destructured, IArray, INumber, IObject, IString, IValue};
+
+use super::{
+    ConstructibleJsonValue, DestructibleJsonValue, Int, IntoIntError, IntoUnsignedIntError,
+    JsonArray, JsonObject, JsonValue, Number, TypeError, TypeErrorOr, UnsignedInt, ValueType,
+};
+
+/// The string of an [`IValue`], which is an [`IString`] viewable as a `&str`.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct IJsonString(IString);
+
+impl IJsonString {
+    /// Returns the string as a string slice.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Returns the interned string.
+    pub fn into_inner(self) -> IString {
+        self.0
+    }
+
+    fn from_ref(value: &IString) -> &Self {
+        // SAFETY: IJsonString is a repr(transparent) wrapper around IString
+        unsafe { &*(value as *const IString as *const IJsonString) }
+    }
+}
+
+impl Deref for IJsonString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for IJsonString {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for IJsonString {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<IString> for IJsonString {
+    fn from(value: IString) -> Self {
+        Self(value)
+    }
+}
+
+impl From<IJsonString> for String {
+    fn from(value: IJsonString) -> Self {
+        value.0.into()
+    }
+}
+
+impl fmt::Debug for IJsonString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for IJsonString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl JsonValue for IValue {
+    type String = IJsonString;
+    type Array = IArray;
+    type Object = IObject;
+}
+
+fn type_error(value: &IValue, expected: ValueType) -> TypeError {
+    TypeError {
+        expected,
+        received: value.value_type(),
+    }
+}
+
+fn try_as_inumber(value: &IValue) -> Result<&INumber, TypeError> {
+    value
+        .as_number()
+        .ok_or_else(|| type_error(value, ValueType::Number))
+}
+
+impl DestructibleJsonValue for IValue {
+    #[inline(always)]
+    fn value_type(&self) -> ValueType {
+        match self.type_() {
+            ijson::ValueType::Null => ValueType::Null,
+            ijson::ValueType::Bool => ValueType::Bool,
+            ijson::ValueType::Number => ValueType::Number,
+            ijson::ValueType::String => ValueType::String,
+            ijson::ValueType::Array => ValueType::Array,
+            ijson::ValueType::Object => ValueType::Object,
+        }
+    }
+
+    #[inline(always)]
+    fn try_as_bool(&self) -> Result<bool, TypeError> {
+        self.to_bool()
+            .ok_or_else(|| type_error(self, ValueType::Bool))
+    }
+
+    #[inline(always)]
+    fn try_as_f64(&self) -> Result<f64, TypeError> {
+        try_as_inumber(self).map(INumber::to_f64_lossy)
+    }
+
+    #[inline(always)]
+    fn try_as_int(&self) -> Result<Int, TypeErrorOr<IntoIntError>> {
+        let number = try_as_inumber(self)?;
+
+        if let Some(n) = number.to_i64() {
+            Int::new(n).ok_or(IntoIntError::OutsideRangeSigned(n))
+        } else if let Some(n) = number.to_u64() {
+            Err(IntoIntError::OutsideRangeUnsigned(n))
+        } else {
+            Err(IntoIntError::NotAnInteger(number.to_f64_lossy()))
+        }
+        .map_err(TypeErrorOr::Other)
+    }
+
+    #[inline(always)]
+    fn try_as_unsigned_int(&self) -> Result<UnsignedInt, TypeErrorOr<IntoUnsignedIntError>> {
+        let number = try_as_inumber(self)?;
+
+        if let Some(n) = number.to_u64() {
+            UnsignedInt::new(n).ok_or(IntoUnsignedIntError::OutsideRange(n))
+        } else if let Some(n) = number.to_i64() {
+            Err(IntoUnsignedIntError::NegativeInteger(n))
+        } else {
+            Err(IntoUnsignedIntError::NotAnInteger(number.to_f64_lossy()))
+        }
+        .map_err(TypeErrorOr::Other)
+    }
+
+    fn try_as_number(&self) -> Result<Number, TypeError> {
+        let number = try_as_inumber(self)?;
+
+        if let Some(n) = number.to_u64() {
+            Ok(n.into())
+        } else if let Some(n) = number.to_i64() {
+            Ok(n.into())
+        } else {
+            // an INumber holds no float which is not finite
+            Number::from_f64(number.to_f64_lossy()).map_err(|_| type_error(self, ValueType::Number))
+        }
+    }
+
+    #[inline(always)]
+    fn try_as_string(&self) -> Result<&<Self as JsonValue>::String, TypeError> {
+        self.as_string()
+            .map(IJsonString::from_ref)
+            .ok_or_else(|| type_error(self, ValueType::String))
+    }
+
+    #[inline(always)]
+    fn try_as_array(&self) -> Result<&<Self as JsonValue>::Array, TypeError> {
+        self.as_array()
+            .ok_or_else(|| type_error(self, ValueType::Array))
+    }
+
+    #[inline(always)]
+    fn try_as_object(&self) -> Result<&<Self as JsonValue>::Object, TypeError> {
+        self.as_object()
+            .ok_or_else(|| type_error(self, ValueType::Object))
+    }
+
+    // the owned downcasts go through destructure, since IValue::into_object checks that the value
+    // is a number rather than an object
+
+    #[inline(always)]
+    fn try_into_string(self) -> Result<<Self as JsonValue>::String, TypeError> {
+        let error = type_error(&self, ValueType::String);
+        match self.destructure() {
+            Destructured::String(s) => Ok(IJsonString(s)),
+            _ => Err(error),
+        }
+    }
+
+    #[inline(always)]
+    fn try_into_array(self) -> Result<<Self as JsonValue>::Array, TypeError> {
+        let error = type_error(&self, ValueType::Array);
+        match self.destructure() {
+            Destructured::Array(array) => Ok(array),
+            _ => Err(error),
+        }
+    }
+
+    #[inline(always)]
+    fn try_into_object(self) -> Result<<Self as JsonValue>::Object, TypeError> {
+        let error = type_error(&self, ValueType::Object);
+        match self.destructure() {
+            Destructured::Object(object) => Ok(object),
+            _ => Err(error),
+        }
+    }
+}
+
+impl ConstructibleJsonValue for IValue {
+    #[inline(always)]
+    fn null() -> Self {
+        IValue::NULL
+    }
+
+    #[inline(always)]
+    fn bool(value: bool) -> Self {
+        value.into()
+    }
+
+    #[inline(always)]
+    fn string(value: String) -> Self {
+        value.into()
+    }
+
+    #[inline(always)]
+    fn str(value: &str) -> Self {
+        value.into()
+    }
+
+    #[inline(always)]
+    fn cow_str(value: Cow<'_, str>) -> Self {
+        IString::from(&*value).into()
+    }
+
+    #[inline(always)]
+    fn f64(value: f64) -> Self {
+        value.into()
+    }
+
+    #[inline(always)]
+    fn int(value: Int) -> Self {
+        value.get().into()
+    }
+
+    #[inline(always)]
+    fn unsigned_int(value: UnsignedInt) -> Self {
+        value.get().into()
+    }
+
+    fn number(value: Number) -> Self {
+        if let Some(n) = value.as_u64() {
+            n.into()
+        } else if let Some(n) = value.as_i64() {
+            n.into()
+        } else {
+            value.as_f64().into()
+        }
+    }
+
+    #[inline(always)]
+    fn array(value: <Self as JsonValue>::Array) -> Self {
+        value.into()
+    }
+
+    #[inline(always)]
+    fn object(value: <Self as JsonValue>::Object) -> Self {
+        value.into()
+    }
+}
+
+impl JsonArray for IArray {
+    type Elem = IValue;
+
+    #[inline(always)]
+    fn with_capacity(capacity: usize) -> Self {
+        IArray::with_capacity(capacity)
+    }
+
+    #[inline(always)]
+    fn push(&mut self, elem: Self::Elem) {
+        IArray::push(self, elem);
+    }
+
+    #[inline(always)]
+    fn get(&self, index: usize) -> Option<&Self::Elem> {
+        self.as_slice().get(index)
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        IArray::len(self)
+    }
+
+    #[inline(always)]
+    fn iter(&self) -> impl Iterator<Item = &Self::Elem> {
+        self.as_slice().iter()
+    }
+
+    #[inline(always)]
+    fn into_iter(self) -> impl Iterator<Item = Self::Elem> {
+        IntoIterator::into_iter(self)
+    }
+}
+
+impl JsonObject for IObject {
+    type Key = IString;
+    type Value = IValue;
+
+    #[inline(always)]
+    fn with_capacity(capacity: usize) -> Self {
+        IObject::with_capacity(capacity)
+    }
+
+    // an IObject is indexed by interned strings, and interning the key of every lookup would take
+    // a lock on the global string cache, so the keys are compared directly instead; the members
+    // of a JSCalendar object are few enough that this is no slower
+    #[inline(always)]
+    fn get<Q>(&self, key: &Q) -> Option<&Self::Value>
+    where
+        Self::Key: Borrow<Q>,
+        Q: ?Sized + Hash + Eq + Ord,
+    {
+        IObject::iter(self)
+            .find(|(k, _)| (*k).borrow() == key)
+            .map(|(_, value)| value)
+    }
+
+    #[inline(always)]
+    fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        Self::Key: Borrow<Q>,
+        Q: ?Sized + Hash + Eq + Ord,
+    {
+        JsonObject::get(self, key).is_some()
+    }
+
+    #[inline(always)]
+    fn key_into_string(key: Self::Key) -> String {
+        key.into()
+    }
+
+    #[inline(always)]
+    fn insert(&mut self, key: Self::Key, value: Self::Value) {
+        IObject::insert(self, key, value);
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        IObject::len(self)
+    }
+
+    #[inline(always)]
+    fn iter(&self) -> impl Iterator<Item = (&Self::Key, &Self::Value)> {
+        IObject::iter(self)
+    }
+
+    #[inline(always)]
+    fn into_iter(self) -> impl Iterator<Item = (Self::Key, Self::Value)> {
+        IntoIterator::into_iter(self)
+    }
+
+    #[inline(always)]
+    fn preserves_order() -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ijson::ijson;
+
+    use super::*;
+    use crate::{
+        json::{IntoJson, TryFromJson},
+        model::object::Group,
+    };
+
+    #[test]
+    fn values() {
+        let value = ijson!({ "name": "x", "n": -3, "big": 18446744073709551615u64, "f": 0.5 });
+        let object = value.try_as_object().unwrap();
+        let name = JsonObject::get(object, "name").unwrap();
+        assert_eq!(name.try_as_string().unwrap().as_str(), "x");
+        assert_eq!(String::from(name.clone().try_into_string().unwrap()), "x");
+        assert!(JsonObject::get(object, "missing").is_none());
+
+        let n = JsonObject::get(object, "n").unwrap();
+        assert_eq!(n.try_as_int(), Ok(Int::new(-3).unwrap()));
+        assert_eq!(
+            n.try_as_unsigned_int(),
+            Err(TypeErrorOr::Other(IntoUnsignedIntError::NegativeInteger(
+                -3
+            )))
+        );
+        let big = JsonObject::get(object, "big").unwrap();
+        assert_eq!(big.try_as_number(), Ok(Number::from(u64::MAX)));
+        assert_eq!(IValue::number(Number::from(u64::MAX)), *big);
+        let f = JsonObject::get(object, "f").unwrap();
+        assert_eq!(f.try_as_number().ok(), Number::from_f64(0.5).ok());
+        assert_eq!(
+            f.try_as_array(),
+            Err(TypeError {
+                expected: ValueType::Array,
+                received: ValueType::Number
+            })
+        );
+    }
+
+    #[test]
+    fn group_round_trip() {
+        let input: IValue = ijson!({
+            "@type": "Group",
+            "uid": "bf0ac22b-4989-4caf-9ebd-54301b4ee51a",
+            "title": "A simple group",
+            "entries": [{
+                "@type": "Event",
+                "uid": "a8df6573-0474-496d-8496-033ad45d7fea",
+                "start": "2020-01-15T13:00:00",
+                "duration": "PT1H",
+                "title": "Some event",
+                "keywords": { "work": true }
+            }, {
+                "@type": "Task",
+                "uid": "2a358cee-6489-4f14-a57f-c104db4dc2f2",
+                "title": "Do something",
+                "percentComplete": 50
+            }]
+        });
+        let group = Group::<IValue>::try_from_json(input.clone()).unwrap();
+        assert_eq!(group.entries().len(), 2);
+        let output: IValue = group.into_json();
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn unordered_members_are_sorted() {
+        let mut object = IObject::new();
+        let entries = [("c", 1), ("a", 2), ("b", 3)];
+        object.insert_unordered(entries.map(|(key, n)| (key.into(), n.into())));
+        let keys: Vec<_> = JsonObject::keys(&object).map(IString::as_str).collect();
+        assert_eq!(keys, ["a", "b", "c"]);
+    }
+}
@@ -20,7 +20,22 @@
 //!
 //! | Flag | Default | Description |
 //! |------|---------|-------------|
+//! | `calico` | off | Enables the [`ical`] module, which converts between JSCalendar objects and the iCalendar data model of `calico`, including `Event::from_ics_str` and `Event::to_ics_string` (implies `serde_json`) |
+//! | `conferencing` | off | Enables [`model::conferencing`], which extracts meeting codes and dial-in numbers from the URIs of virtual locations |
+//! | `ijson` | off | Implements `JsonValue`, `DestructibleJsonValue`, and `ConstructibleJsonValue` for `ijson::IValue`, a more compact alternative to `serde_json::Value` |
+//! | `instrument` | off | Enables the [`instrument`] module, which measures allocations and map sizes while parsing, and the sizes of parsed objects |
+//! | `shared-refs` | off | Enables the experimental `$ref` emission of [`model::sharing`] for sub-objects repeated across the entries of a group |
+//! | `preserve_order` | off | Enables the `preserve_order` feature of `serde_json`, so that objects are written with their properties in a fixed order rather than sorted by name (implies `serde_json`) |
+//! | `pyo3` | off | Enables the [`python`] module, which exports `Event`, `Task`, and `Group` classes to Python through PyO3 (implies `serde_json`) |
+//! | `schema` | off | Enables the [`schema`] module, which generates a JSON Schema for events, tasks, and groups (implies `serde_json`) |
 //! | `serde_json` | off | Implements `JsonValue`, `DestructibleJsonValue`, and `ConstructibleJsonValue` for `serde_json::Value` |
+//! | `wasm` | off | Enables the [`wasm`] module, which exports `Event`, `Task`, and `Group` classes to JavaScript through `wasm-bindgen` |
+//!
+//! This crate requires the standard library, and has no `no_std` mode. Its object types are
+//! generated by `structible`, which uses the collections of `std` whichever map backs a type,
+//! and `rfc5545-types` requires `std` as well. The `wasm32-unknown-unknown` target provides
+//! `std`, which the `wasm` feature relies on. The primitive types of `calendar-types` are usable
+//! in `no_std` + `alloc` environments, by disabling its default `std` feature.
 //!
 //! # Example
 //!
@@ -54,15 +69,50 @@
 //! # Scope
 //!
 //! This crate covers the JSCalendar **data model** and **JSON conversion** only.
-//! It does not provide recurrence expansion, IANA time zone resolution, or
-//! iCalendar (RFC 5545) conversion.
+//! It does not provide recurrence expansion or IANA time zone resolution.
+//!
+//! The [`jcal`] module converts jCal (RFC 7265) components to and from JSCalendar objects, and
+//! the [`ical`] module, behind the `calico` feature, converts between JSCalendar objects and the
+//! iCalendar (RFC 5545) data model of the `calico` crate.
 //!
 //! # Modules
 //!
+//! - [`ical`] — Conversion between iCalendar and JSCalendar objects (`calico` feature)
+//! - [`instrument`] — Memory instrumentation for parsing (`instrument` feature)
+//! - [`jcal`] — Conversion between jCal components and JSCalendar objects
 //! - [`json`] — JSON value traits and conversion infrastructure
 //! - [`model`] — JSCalendar object types, enumerations, and string newtypes
 //! - [`parser`] — Incremental parsers for date/time and duration strings
+//! - [`prelude`] — Commonly used traits and types, for glob import
+//! - [`python`] — Python bindings for the object model (`pyo3` feature)
+//! - [`render`] — Plain text and Markdown agendas of events
+//! - [`schema`] — JSON Schema for the object model (`schema` feature)
+//! - [`wasm`] — JavaScript bindings for the object model (`wasm` feature)
 
+#[cfg(feature = "calico")]
+pub mod ical;
+#[cfg(feature = "instrument")]
+pub mod instrument;
+pub mod jcal;
 pub mod json;
 pub mod model;
 pub mod parser;
+pub mod prelude;
+#[cfg(feature = "pyo3")]
+pub mod python;
+pub mod render;
+#[cfg(feature = "schema")]
+pub mod schema;
+mod valarm;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// The `calendar-types` crate, which defines the date, time, and duration types of this crate.
+///
+/// Depending on it through this re-export keeps its version in step with this crate.
+pub use calendar_types;
+
+/// The `rfc5545-types` crate, which defines the recurrence rule types of this crate.
+///
+/// Depending on it through this re-export keeps its version in step with this crate.
+pub use rfc5545_types;
@@ -1,8 +1,39 @@
 //! Types in the JSCalendar data model.
 
+pub mod alert;
+pub mod audit;
+pub mod compact;
+#[cfg(feature = "conferencing")]
+pub mod conferencing;
+pub mod conflict;
+pub mod convert;
+pub mod counter;
+pub mod delegation;
+pub mod extension;
+pub mod jmap;
+pub mod location;
+pub mod lossless;
+pub mod materialize;
+pub mod notify;
 pub mod object;
+pub mod order;
+pub mod props;
+pub mod reflect;
+pub mod refresh;
+pub mod relations;
+pub mod resolve;
+pub mod rsvp;
 pub mod set;
+pub mod sharing;
 pub mod string;
+pub mod tracked;
+pub mod tz;
+pub mod tzif;
+pub mod upcoming;
+pub mod validate;
+pub mod vendor;
+pub mod views;
+pub mod window;
 
 pub use rfc5545_types::request_status;
 
@@ -0,0 +1,205 @@
+//! Structured payloads for the actions of alerts.
+//!
+//! The `action` of an [`Alert`] only says whether it is displayed or sent by email (RFC 8984
+//! §4.5.2), but a dispatcher needs to know what to display or send, and to whom. The
+//! [`AlertPayload`] of an alert provides this in the same terms as the DESCRIPTION, SUMMARY, and
+//! ATTENDEE properties of an iCalendar VALARM (RFC 5545 §3.6.6), stored on the alert as the
+//! vendor properties named by the constants in this module.
+//!
+//! ```
+//! # #[cfg(feature = "serde_json")]
+//! # {
+//! use jscalendar::{
+//!     model::{alert::AlertPayload, string::CalAddress},
+//!     prelude::*,
+//! };
+//! use serde_json::{Value, json};
+//!
+//! let mut alert = Alert::<Value>::try_from_json(json!({
+//!     "@type": "Alert",
+//!     "trigger": { "@type": "OffsetTrigger", "offset": "-PT15M" }
+//! }))
+//! .unwrap();
+//! alert.set_payload(AlertPayload::Email {
+//!     summary: Some("Standup".into()),
+//!     description: None,
+//!     attendees: vec![CalAddress::new("mailto:ana@example.com").unwrap().into()],
+//! });
+//!
+//! let json: Value = alert.into_json();
+//! assert_eq!(json["action"], "email");
+//! assert_eq!(json["eikopf.github.io:attendees"], json!(["mailto:ana@example.com"]));
+//! # }
+//! ```
+
+use crate::{
+    json::{ConstructibleJsonValue, DestructibleJsonValue, IntoJson, JsonArray, TryFromJson},
+    model::{
+        object::Alert,
+        set::{AlertAction, Token},
+        string::CalAddress,
+    },
+};
+
+/// The vendor property holding the summary of an email alert.
+pub const SUMMARY: &str = "eikopf.github.io:summary";
+/// The vendor property holding the description of an alert.
+pub const DESCRIPTION: &str = "eikopf.github.io:description";
+/// The vendor property holding the recipients of an email alert.
+pub const ATTENDEES: &str = "eikopf.github.io:attendees";
+
+/// What an alert displays or sends, depending on its `action`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AlertPayload {
+    /// The payload of an alert which is displayed on screen.
+    Display {
+        /// The text to display.
+        description: Option<String>,
+    },
+    /// The payload of an alert which is sent by email.
+    Email {
+        /// The subject of the email.
+        summary: Option<String>,
+        /// The body of the email.
+        description: Option<String>,
+        /// The addresses to which the email is sent.
+        attendees: Vec<Box<CalAddress>>,
+    },
+}
+
+impl AlertPayload {
+    /// Returns the action of alerts with this payload.
+    pub fn action(&self) -> AlertAction {
+        match self {
+            Self::Display { .. } => AlertAction::Display,
+            Self::Email { .. } => AlertAction::Email,
+        }
+    }
+}
+
+impl<V> Alert<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    /// Returns the payload of this alert, or `None` if its `action` is not one this crate knows.
+    /// An alert without an `action` is displayed, and values of the wrong type are ignored.
+    pub fn payload(&self) -> Option<AlertPayload> {
+        let property = |name| {
+            let value = self.vendor_property(name)?.clone();
+            String::try_from_json(value).ok()
+        };
+
+        match self.action() {
+            None | Some(Token::Known(AlertAction::Display)) => Some(AlertPayload::Display {
+                description: property(DESCRIPTION),
+            }),
+            Some(Token::Known(AlertAction::Email)) => Some(AlertPayload::Email {
+                summary: property(SUMMARY),
+                description: property(DESCRIPTION),
+                attendees: self.attendees().unwrap_or_default(),
+            }),
+            Some(Token::Unknown(_)) => None,
+        }
+    }
+
+    /// Sets the `action` of this alert and stores `payload` on it, removing the properties of
+    /// absent values.
+    pub fn set_payload(&mut self, payload: AlertPayload) {
+        self.set_action(Token::Known(payload.action()));
+        match payload {
+            AlertPayload::Display { description } => {
+                self.set_payload_property(SUMMARY, None::<String>);
+                self.set_payload_property(DESCRIPTION, description);
+                self.set_payload_property(ATTENDEES, None::<Vec<Box<CalAddress>>>);
+            }
+            AlertPayload::Email {
+                summary,
+                description,
+                attendees,
+            } => {
+                let attendees = Some(attendees).filter(|attendees| !attendees.is_empty());
+                self.set_payload_property(SUMMARY, summary);
+                self.set_payload_property(DESCRIPTION, description);
+                self.set_payload_property(ATTENDEES, attendees);
+            }
+        }
+    }
+
+    /// Returns the recipients stored on this alert, or `None` if they are absent or any of them is
+    /// not a calendar user address.
+    fn attendees(&self) -> Option<Vec<Box<CalAddress>>> {
+        let attendees = self.vendor_property(ATTENDEES)?.try_as_array().ok()?;
+        JsonArray::iter(attendees)
+            .map(|attendee| Box::<CalAddress>::try_from_json(attendee.clone()).ok())
+            .collect()
+    }
+
+    fn set_payload_property<T: IntoJson<V>>(&mut self, name: &str, value: Option<T>) {
+        let name = Box::<str>::from(name);
+        match value {
+            Some(value) => self.insert_vendor_property(name, value.into_json()),
+            None => self.remove_vendor_property(&name),
+        };
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    fn alert_with(action: Option<&str>) -> Alert<Value> {
+        let mut json = json!({
+            "@type": "Alert",
+            "trigger": { "@type": "OffsetTrigger", "offset": "-PT5M" }
+        });
+        if let Some(action) = action {
+            json["action"] = action.into();
+        }
+        Alert::try_from_json(json).unwrap()
+    }
+
+    #[test]
+    fn payload_round_trip() {
+        let payload = AlertPayload::Email {
+            summary: Some("Standup".into()),
+            description: Some("In five minutes".into()),
+            attendees: vec![CalAddress::new("mailto:ana@example.com").unwrap().into()],
+        };
+        let mut alert = alert_with(None);
+        alert.set_payload(payload.clone());
+
+        let alert = Alert::<Value>::try_from_json(alert.into_json()).unwrap();
+        assert_eq!(alert.payload(), Some(payload));
+    }
+
+    #[test]
+    fn payload_follows_action() {
+        let mut alert = alert_with(Some("email"));
+        alert.set_payload(AlertPayload::Email {
+            summary: Some("Standup".into()),
+            description: None,
+            attendees: Vec::new(),
+        });
+        alert.set_payload(AlertPayload::Display {
+            description: Some("Standup".into()),
+        });
+        assert_eq!(
+            alert.into_json(),
+            json!({
+                "@type": "Alert",
+                "trigger": { "@type": "OffsetTrigger", "offset": "-PT5M" },
+                "action": "display",
+                "eikopf.github.io:description": "Standup"
+            })
+        );
+
+        assert_eq!(
+            alert_with(None).payload(),
+            Some(AlertPayload::Display { description: None })
+        );
+        assert_eq!(alert_with(Some("audio")).payload(), None);
+    }
+}
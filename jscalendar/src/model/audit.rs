@@ -0,0 +1,328 @@
+//! Human-readable descriptions of the changes made by a [`PatchObject`].
+//!
+//! Collaborative calendar backends usually keep an audit log of edits, with entries such as
+//! "Start time changed from 2024-01-15T13:00:00 to 2024-01-15T14:00:00" or "Alice was added as a
+//! participant". [`describe_changes`] derives such entries from the original object and the patch
+//! applied to it.
+//!
+//! Each [`ChangeDescription`] is structured, so that callers can render it in any language; its
+//! [`Display`](std::fmt::Display) impl produces English text.
+
+use std::fmt;
+
+use crate::{
+    json::{DestructibleJsonValue, JsonArray, JsonObject},
+    model::{object::PatchObject, string::ImplicitJsonPointer},
+};
+
+/// A single change made by a patch, as returned by [`describe_changes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeDescription {
+    /// The patched property.
+    pub pointer: Box<ImplicitJsonPointer>,
+    /// The kind of change, with the relevant values rendered as text.
+    pub change: Change,
+}
+
+/// The kind of a [`ChangeDescription`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// A participant was added to the `participants` map.
+    ParticipantAdded {
+        /// The name of the participant, or else their ID.
+        participant: String,
+    },
+    /// A participant was removed from the `participants` map.
+    ParticipantRemoved {
+        /// The name of the participant, or else their ID.
+        participant: String,
+    },
+    /// A property was set to a new value.
+    Set {
+        /// A label for the property.
+        property: String,
+        /// The previous value, if the property was present in the original object.
+        old: Option<String>,
+        /// The new value.
+        new: String,
+    },
+    /// A property was removed.
+    Removed {
+        /// A label for the property.
+        property: String,
+        /// The previous value, if the property was present in the original object.
+        old: Option<String>,
+    },
+}
+
+impl fmt::Display for ChangeDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.change, f)
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::ParticipantAdded { participant } => {
+                write!(f, "{participant} was added as a participant")
+            }
+            Change::ParticipantRemoved { participant } => {
+                write!(f, "{participant} was removed as a participant")
+            }
+            Change::Set {
+                property,
+                old: Some(old),
+                new,
+            } => write!(f, "{property} changed from {old} to {new}"),
+            Change::Set {
+                property,
+                old: None,
+                new,
+            } => write!(f, "{property} set to {new}"),
+            Change::Removed {
+                property,
+                old: Some(old),
+            } => write!(f, "{property} was removed (previously {old})"),
+            Change::Removed {
+                property,
+                old: None,
+            } => write!(f, "{property} was removed"),
+        }
+    }
+}
+
+/// Describes each patch in `diff` as applied to the JSON object `original`, ordered by pointer.
+///
+/// The `original` value is used to look up the previous values of changed properties and the
+/// names of removed participants. A patch whose value is `null` removes a property (RFC 8984
+/// §1.4.9).
+pub fn describe_changes<V: DestructibleJsonValue>(
+    original: &V,
+    diff: &PatchObject<V>,
+) -> Vec<ChangeDescription> {
+    let mut patches: Vec<_> = diff.iter().collect();
+    patches.sort_by_key(|(pointer, _)| pointer.to_string());
+
+    patches
+        .iter()
+        .map(|&(pointer, value)| {
+            let previous = lookup(original, pointer);
+            let segments: Vec<_> = pointer.segments().collect();
+
+            let change = match (segments.as_slice(), value.is_null()) {
+                // a participant which already exists is replaced rather than added
+                ([participants, id], false)
+                    if participants == "participants" && previous.is_some() =>
+                {
+                    Change::Set {
+                        property: format!("Participant {id}"),
+                        old: previous.map(render),
+                        new: render(value),
+                    }
+                }
+                ([participants, id], false) if participants == "participants" => {
+                    Change::ParticipantAdded {
+                        participant: participant_name(value).unwrap_or_else(|| id.to_string()),
+                    }
+                }
+                ([participants, id], true) if participants == "participants" => {
+                    Change::ParticipantRemoved {
+                        participant: previous
+                            .and_then(participant_name)
+                            .unwrap_or_else(|| id.to_string()),
+                    }
+                }
+                (_, false) => Change::Set {
+                    property: property_label(original, &segments),
+                    old: previous.map(render),
+                    new: render(value),
+                },
+                (_, true) => Change::Removed {
+                    property: property_label(original, &segments),
+                    old: previous.map(render),
+                },
+            };
+
+            ChangeDescription {
+                pointer: pointer.into(),
+                change,
+            }
+        })
+        .collect()
+}
+
+/// Returns the value at `pointer` in `root`, if any.
+fn lookup<'a, V: DestructibleJsonValue>(
+    root: &'a V,
+    pointer: &ImplicitJsonPointer,
+) -> Option<&'a V> {
+    pointer.segments().try_fold(root, |value, segment| {
+        if let Ok(object) = value.try_as_object() {
+            object.get(segment.as_ref())
+        } else {
+            let index = segment.parse().ok()?;
+            value.try_as_array().ok()?.get(index)
+        }
+    })
+}
+
+/// Returns a human-readable label for the property at the given path.
+fn property_label<V: DestructibleJsonValue>(original: &V, segments: &[impl AsRef<str>]) -> String {
+    match segments {
+        [property] => top_level_label(property.as_ref()),
+        [participants, id, rest @ ..] if participants.as_ref() == "participants" => {
+            let pointer = format!("participants/{}", id.as_ref());
+            let name = ImplicitJsonPointer::new(&pointer)
+                .ok()
+                .and_then(|pointer| lookup(original, pointer))
+                .and_then(participant_name)
+                .unwrap_or_else(|| id.as_ref().to_string());
+            let rest: Vec<_> = rest.iter().map(AsRef::as_ref).collect();
+            format!("{name}'s {}", rest.join("/"))
+        }
+        _ => segments
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>()
+            .join("/"),
+    }
+}
+
+/// Returns a label for a top-level property of an event or task.
+fn top_level_label(property: &str) -> String {
+    let label = match property {
+        "start" => "Start time",
+        "due" => "Due time",
+        "duration" => "Duration",
+        "estimatedDuration" => "Estimated duration",
+        "timeZone" => "Time zone",
+        "title" => "Title",
+        "description" => "Description",
+        "status" => "Status",
+        "progress" => "Progress",
+        "priority" => "Priority",
+        "privacy" => "Privacy",
+        "freeBusyStatus" => "Free/busy status",
+        "locations" => "Locations",
+        "virtualLocations" => "Virtual locations",
+        "recurrenceRules" => "Recurrence rules",
+        "alerts" => "Alerts",
+        "color" => "Color",
+        "keywords" => "Keywords",
+        "categories" => "Categories",
+        "showWithoutTime" => "All-day flag",
+        other => other,
+    };
+    label.to_string()
+}
+
+/// Returns the `name` of a participant object, if it has one.
+fn participant_name<V: DestructibleJsonValue>(participant: &V) -> Option<String> {
+    let name = participant.try_as_object().ok()?.get("name")?;
+    Some(name.try_as_string().ok()?.as_ref().to_string())
+}
+
+/// Renders a JSON value as short, human-readable text.
+fn render<V: DestructibleJsonValue>(value: &V) -> String {
+    if let Ok(s) = value.try_as_string() {
+        s.as_ref().to_string()
+    } else if let Ok(b) = value.try_as_bool() {
+        b.to_string()
+    } else if let Ok(n) = value.try_as_int() {
+        n.get().to_string()
+    } else if let Ok(n) = value.try_as_f64() {
+        n.to_string()
+    } else if let Ok(object) = value.try_as_object() {
+        // prefer a descriptive field of the object over its full contents
+        ["name", "title"]
+            .into_iter()
+            .find_map(|key| object.get(key)?.try_as_string().ok())
+            .map_or_else(|| "{…}".to_string(), |s| s.as_ref().to_string())
+    } else if value.is_array() {
+        "[…]".to_string()
+    } else {
+        "null".to_string()
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::json::TryFromJson;
+
+    fn describe(original: Value, patch: Value) -> Vec<String> {
+        let patch = PatchObject::<Value>::try_from_json(patch).unwrap();
+        describe_changes(&original, &patch)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn describe_property_changes() {
+        let original = json!({
+            "@type": "Event",
+            "start": "2024-01-15T13:00:00",
+            "title": "Standup",
+            "locations": { "l1": { "@type": "Location", "name": "Room 1" } }
+        });
+        let patch = json!({
+            "start": "2024-01-15T14:00:00",
+            "duration": "PT30M",
+            "title": null,
+            "locations/l1/name": "Room 2"
+        });
+
+        assert_eq!(
+            describe(original, patch),
+            [
+                "Duration set to PT30M",
+                "locations/l1/name changed from Room 1 to Room 2",
+                "Start time changed from 2024-01-15T13:00:00 to 2024-01-15T14:00:00",
+                "Title was removed (previously Standup)",
+            ]
+        );
+    }
+
+    #[test]
+    fn describe_participant_changes() {
+        let original = json!({
+            "participants": {
+                "p1": {
+                    "@type": "Participant",
+                    "name": "Bob",
+                    "participationStatus": "needs-action"
+                }
+            }
+        });
+        let patch = json!({
+            "participants/p1": null,
+            "participants/p2": { "@type": "Participant", "name": "Alice" },
+            "participants/p3": { "@type": "Participant" },
+        });
+
+        assert_eq!(
+            describe(original.clone(), patch),
+            [
+                "Bob was removed as a participant",
+                "Alice was added as a participant",
+                "p3 was added as a participant",
+            ]
+        );
+
+        let patch = json!({ "participants/p1": { "@type": "Participant", "name": "Robert" } });
+        assert_eq!(
+            describe(original.clone(), patch),
+            ["Participant p1 changed from Bob to Robert"]
+        );
+
+        let patch = json!({ "participants/p1/participationStatus": "accepted" });
+        assert_eq!(
+            describe(original, patch),
+            ["Bob's participationStatus changed from needs-action to accepted"]
+        );
+    }
+}
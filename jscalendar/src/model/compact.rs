@@ -0,0 +1,173 @@
+//! Compact storage of events as differences from a template event.
+//!
+//! Stores holding many similar events (such as those generated from the same booking page or
+//! calendar defaults) can save space by recording each event only as its differences from a
+//! shared template. A [`CompactTemplate`] encodes an [`Event`] as a JSON object containing only
+//! the top-level members whose values differ from those of the template, and decodes such objects
+//! back into events.
+//!
+//! In an encoded object, a member of the template which the event lacks is recorded as `null`,
+//! much like in a [`PatchObject`](super::object::PatchObject). Consequently, a vendor property
+//! whose value is `null` cannot be distinguished from an absent one if the template also has that
+//! property.
+//!
+//! This module is experimental, and the encoding may change between releases; encoded objects
+//! should only be decoded by the same version of this crate.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    json::{
+        ConstructibleJsonValue, DestructibleJsonValue, DocumentError, IntoJson, JsonObject,
+        TryFromJson, TypeErrorOr,
+    },
+    model::object::{Event, ObjectFromJsonError},
+};
+
+/// A template event against which other events are encoded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompactTemplate<V: DestructibleJsonValue> {
+    members: BTreeMap<String, V>,
+}
+
+impl<V> CompactTemplate<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone + PartialEq,
+{
+    /// Creates a template from the given event.
+    pub fn new(template: Event<V>) -> Self {
+        Self {
+            members: members(template.into_json()),
+        }
+    }
+
+    /// Returns the members of `event` which differ from those of the template, with `null` for
+    /// each member of the template which `event` lacks.
+    pub fn encode(&self, event: Event<V>) -> V::Object {
+        let event = members(event.into_json());
+        let mut delta = V::Object::new();
+
+        for (key, value) in &event {
+            if self.members.get(key) != Some(value) {
+                delta.insert(key.as_str().into(), value.clone());
+            }
+        }
+
+        for key in self.members.keys() {
+            if !event.contains_key(key) {
+                delta.insert(key.as_str().into(), V::null());
+            }
+        }
+
+        delta
+    }
+
+    /// Reconstructs an event from an object produced by [`CompactTemplate::encode`].
+    pub fn decode(
+        &self,
+        delta: V::Object,
+    ) -> Result<Event<V>, DocumentError<TypeErrorOr<ObjectFromJsonError>>> {
+        let mut event = self.members.clone();
+
+        for (key, value) in delta.into_iter() {
+            let key = <V::Object as JsonObject>::key_into_string(key);
+            if value.is_null() && event.contains_key(&key) {
+                event.remove(&key);
+            } else {
+                event.insert(key, value);
+            }
+        }
+
+        let mut object = V::Object::with_capacity(event.len());
+        for (key, value) in event {
+            object.insert(key.into(), value);
+        }
+        Event::try_from_json(V::object(object))
+    }
+}
+
+/// Returns the members of a serialized object.
+fn members<V: DestructibleJsonValue>(value: V) -> BTreeMap<String, V> {
+    value
+        .try_into_object()
+        .expect("objects serialize to JSON objects")
+        .into_iter()
+        .map(|(key, value)| (<V::Object as JsonObject>::key_into_string(key), value))
+        .collect()
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    fn event(value: Value) -> Event<Value> {
+        Event::try_from_json(value).unwrap()
+    }
+
+    fn template() -> CompactTemplate<Value> {
+        CompactTemplate::new(event(json!({
+            "@type": "Event",
+            "uid": "template",
+            "start": "2024-01-01T09:00:00",
+            "duration": "PT30M",
+            "timeZone": "Europe/Berlin",
+            "title": "Consultation",
+            "freeBusyStatus": "busy",
+            "example.com:tenant": "acme"
+        })))
+    }
+
+    #[test]
+    fn encode_only_differing_members() {
+        let template = template();
+        let input = json!({
+            "@type": "Event",
+            "uid": "booking-1",
+            "start": "2024-03-05T14:00:00",
+            "duration": "PT30M",
+            "timeZone": "Europe/Berlin",
+            "title": "Consultation",
+            "description": "Follow-up",
+            "example.com:tenant": "acme"
+        });
+
+        let delta = template.encode(event(input.clone()));
+        assert_eq!(
+            Value::Object(delta.clone()),
+            json!({
+                "uid": "booking-1",
+                "start": "2024-03-05T14:00:00",
+                "description": "Follow-up",
+                "freeBusyStatus": null
+            })
+        );
+
+        assert_eq!(template.decode(delta).unwrap(), event(input));
+    }
+
+    #[test]
+    fn decode_preserves_null_vendor_properties() {
+        let template = template();
+        let input = json!({
+            "@type": "Event",
+            "uid": "booking-2",
+            "start": "2024-01-01T09:00:00",
+            "example.com:note": null
+        });
+
+        let delta = template.encode(event(input.clone()));
+        assert_eq!(template.decode(delta).unwrap(), event(input));
+    }
+
+    #[test]
+    fn decode_rejects_invalid_members() {
+        let template = template();
+        let delta = json!({ "uid": "booking-3", "start": "tomorrow" });
+        let Value::Object(delta) = delta else {
+            unreachable!()
+        };
+        assert!(template.decode(delta).is_err());
+    }
+}
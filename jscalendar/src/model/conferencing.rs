@@ -0,0 +1,283 @@
+//! Conferencing metadata of virtual locations (requires the `conferencing` feature).
+//!
+//! The `uri` of a [`VirtualLocation`] is usually a link to a video conference or a telephone
+//! number (RFC 8984 §4.2.6), but a calendar UI which shows a "Join" button or a dial-in number
+//! needs to know which. [`VirtualLocation::conference`] classifies the URI by its
+//! [`ConferenceProvider`], and extracts the meeting code and dial-in details from the common URI
+//! patterns of each provider.
+//!
+//! The URI patterns are recognized heuristically, since none of the providers documents them as
+//! stable, and percent-encoded characters are not decoded.
+//!
+//! ```
+//! use jscalendar::model::{
+//!     conferencing::{ConferenceProvider, DialIn},
+//!     object::VirtualLocation,
+//!     string::Uri,
+//! };
+//!
+//! let uri = Uri::new("https://example.zoom.us/j/85512345678?pwd=s3cret").unwrap();
+//! let location = VirtualLocation::<()>::new(uri.into());
+//! let conference = location.conference().unwrap();
+//! assert_eq!(conference.provider, ConferenceProvider::Zoom);
+//! assert_eq!(conference.meeting_code.as_deref(), Some("85512345678"));
+//! assert_eq!(conference.passcode.as_deref(), Some("s3cret"));
+//!
+//! let uri = Uri::new("tel:+1-555-0100,,85512345678#").unwrap();
+//! let location = VirtualLocation::<()>::new(uri.into());
+//! let dial_in = location.conference().unwrap().dial_in.unwrap();
+//! assert_eq!(dial_in, DialIn {
+//!     number: "+1-555-0100".into(),
+//!     pin: Some("85512345678".into()),
+//! });
+//! ```
+
+use crate::model::{object::VirtualLocation, string::Uri};
+
+/// The provider of a conference, as determined by the URI used to join it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ConferenceProvider {
+    /// A Zoom meeting, joined by an `https` link to `zoom.us` or a `zoommtg` URI.
+    Zoom,
+    /// A Google Meet meeting, joined by an `https` link to `meet.google.com`.
+    GoogleMeet,
+    /// A Microsoft Teams meeting, joined by an `https` link to `teams.microsoft.com` or
+    /// `teams.live.com`.
+    MicrosoftTeams,
+    /// A call to a SIP address (RFC 3261 §19.1).
+    Sip,
+    /// A call to a telephone number (RFC 3966).
+    Telephone,
+}
+
+/// The conferencing metadata extracted from the URI of a virtual location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conference {
+    /// The provider of the conference.
+    pub provider: ConferenceProvider,
+    /// The code identifying the meeting, such as the meeting ID of a Zoom meeting or the user part
+    /// of a SIP address.
+    pub meeting_code: Option<String>,
+    /// The passcode needed to join the meeting, if the URI includes it.
+    pub passcode: Option<String>,
+    /// The telephone number to dial, for a [`Telephone`](ConferenceProvider::Telephone) URI.
+    pub dial_in: Option<DialIn>,
+}
+
+/// A telephone number to dial into a conference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DialIn {
+    /// The telephone number, including any visual separators.
+    pub number: String,
+    /// The digits to dial once connected, such as a meeting ID or an extension, without pauses
+    /// or the terminating `#`.
+    pub pin: Option<String>,
+}
+
+impl<V> VirtualLocation<V> {
+    /// Returns the conferencing metadata of the `uri` of this virtual location, or `None` if it
+    /// does not belong to a known [`ConferenceProvider`].
+    pub fn conference(&self) -> Option<Conference> {
+        Conference::from_uri(self.uri())
+    }
+}
+
+impl Conference {
+    /// Classifies `uri` and extracts its conferencing metadata, or returns `None` if it does not
+    /// belong to a known [`ConferenceProvider`].
+    pub fn from_uri(uri: &Uri) -> Option<Self> {
+        let (scheme, rest) = uri.as_str().split_once(':')?;
+        match scheme.to_ascii_lowercase().as_str() {
+            "http" | "https" => from_web_link(rest.strip_prefix("//")?),
+            "zoommtg" => {
+                let (_, query) = rest.split_once('?')?;
+                Some(Self::new(
+                    ConferenceProvider::Zoom,
+                    query_param(query, "confno"),
+                    query_param(query, "pwd"),
+                ))
+            }
+            "sip" | "sips" => {
+                let (user, _) = rest.split_once('@')?;
+                let user = user.split_once(':').map_or(user, |(user, _)| user);
+                let code = Some(user).filter(|user| !user.is_empty());
+                Some(Self::new(ConferenceProvider::Sip, code, None))
+            }
+            "tel" => {
+                let dial_in = dial_in(rest)?;
+                Some(Self {
+                    dial_in: Some(dial_in),
+                    ..Self::new(ConferenceProvider::Telephone, None, None)
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn new(provider: ConferenceProvider, code: Option<&str>, passcode: Option<&str>) -> Self {
+        Self {
+            provider,
+            meeting_code: code.map(str::to_owned),
+            passcode: passcode.map(str::to_owned),
+            dial_in: None,
+        }
+    }
+}
+
+/// Extracts the conferencing metadata of an `http` or `https` URI, from its hierarchical part
+/// after the leading `//`.
+fn from_web_link(link: &str) -> Option<Conference> {
+    let (link, _) = link.split_once('#').unwrap_or((link, ""));
+    let (link, query) = link.split_once('?').unwrap_or((link, ""));
+    let (authority, path) = link.split_at(link.find('/').unwrap_or(link.len()));
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = host.split_once(':').map_or(host, |(host, _)| host);
+    let host = host.to_ascii_lowercase();
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+
+    if host == "zoom.us" || host.ends_with(".zoom.us") {
+        let code = match (segments.next(), segments.next()) {
+            (Some("j" | "w" | "s"), Some(code)) if is_digits(code) => Some(code),
+            _ => None,
+        };
+        Some(Conference::new(
+            ConferenceProvider::Zoom,
+            code,
+            query_param(query, "pwd"),
+        ))
+    } else if host == "meet.google.com" {
+        // meeting codes have the form `abc-defg-hij`
+        let code = segments.next().filter(|code| {
+            let groups: Vec<_> = code.split('-').map(str::len).collect();
+            groups == [3, 4, 3] && code.chars().all(|c| c.is_ascii_lowercase() || c == '-')
+        });
+        Some(Conference::new(ConferenceProvider::GoogleMeet, code, None))
+    } else if host == "teams.microsoft.com" || host == "teams.live.com" {
+        let code = match (segments.next(), segments.next()) {
+            (Some("meet"), Some(code)) if is_digits(code) => Some(code),
+            _ => None,
+        };
+        Some(Conference::new(
+            ConferenceProvider::MicrosoftTeams,
+            code,
+            query_param(query, "p"),
+        ))
+    } else {
+        None
+    }
+}
+
+/// Extracts the dial-in details of a `tel` URI, from the part after its scheme.
+///
+/// The digits to dial after connecting are taken from the `postd` parameter (RFC 4904 §5), or
+/// else from the part after the first pause (`,`) of the number, as written by most providers.
+fn dial_in(tel: &str) -> Option<DialIn> {
+    let mut parts = tel.split(';');
+    let number = parts.next()?;
+    let postd = parts.find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.eq_ignore_ascii_case("postd").then_some(value)
+    });
+    let (number, pauses) = number.split_once(',').unwrap_or((number, ""));
+    if number.is_empty() {
+        return None;
+    }
+
+    let pin: String = postd
+        .unwrap_or(pauses)
+        .chars()
+        .filter(|c| !matches!(c, ',' | 'p' | 'w' | '#'))
+        .collect();
+    Some(DialIn {
+        number: number.to_owned(),
+        pin: Some(pin).filter(|pin| !pin.is_empty()),
+    })
+}
+
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|param| param.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+        .filter(|value| !value.is_empty())
+}
+
+fn is_digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conference(uri: &str) -> Option<Conference> {
+        Conference::from_uri(Uri::new(uri).unwrap())
+    }
+
+    fn code(uri: &str) -> Option<(ConferenceProvider, Option<String>)> {
+        conference(uri).map(|conference| (conference.provider, conference.meeting_code))
+    }
+
+    #[test]
+    fn classify_web_links() {
+        use ConferenceProvider::*;
+
+        let some = |provider, code: &str| Some((provider, Some(code.to_owned())));
+        assert_eq!(
+            code("https://us02web.zoom.us/j/85512345678"),
+            some(Zoom, "85512345678")
+        );
+        assert_eq!(
+            code("zoommtg://zoom.us/join?action=join&confno=85512345678"),
+            some(Zoom, "85512345678")
+        );
+        assert_eq!(
+            code("https://meet.google.com/abc-defg-hij?authuser=0"),
+            some(GoogleMeet, "abc-defg-hij")
+        );
+        assert_eq!(
+            code("https://meet.google.com/lookup/xyz"),
+            Some((GoogleMeet, None))
+        );
+        assert_eq!(
+            code("https://teams.microsoft.com/l/meetup-join/19%3ameeting/0?context=%7b%7d"),
+            Some((MicrosoftTeams, None))
+        );
+        assert_eq!(
+            code("https://teams.live.com/meet/9876543210?p=abc"),
+            some(MicrosoftTeams, "9876543210")
+        );
+        assert_eq!(
+            code("sip:85512345678@zoomcrc.com"),
+            some(Sip, "85512345678")
+        );
+        assert_eq!(code("https://notzoom.us/j/1"), None);
+        assert_eq!(code("mailto:ana@example.com"), None);
+    }
+
+    #[test]
+    fn extract_dial_in() {
+        let dial_in = |uri| conference(uri).and_then(|conference| conference.dial_in);
+        let expected = |number: &str, pin: Option<&str>| {
+            Some(DialIn {
+                number: number.into(),
+                pin: pin.map(Into::into),
+            })
+        };
+
+        assert_eq!(dial_in("tel:+1-555-0100"), expected("+1-555-0100", None));
+        assert_eq!(
+            dial_in("tel:+1-555-0100,,85512345678#,,,,*123456#"),
+            expected("+1-555-0100", Some("85512345678*123456"))
+        );
+        assert_eq!(
+            dial_in("tel:+1-555-0100;postd=pp123#"),
+            expected("+1-555-0100", Some("123"))
+        );
+        assert_eq!(dial_in("tel:,123"), None);
+    }
+}
@@ -0,0 +1,356 @@
+//! Detection of overlapping events.
+//!
+//! [`Event::overlaps`] compares the time spans of two events directly, while [`conflicts`] finds
+//! every pair of clashing occurrences among a set of events within a window, taking their
+//! recurrences, overrides, and time zones into account.
+//!
+//! Each occurrence spans from its `start` for its `duration`, as a half-open interval; an
+//! occurrence without a duration is instantaneous. Two occurrences which start at the same time
+//! always overlap, so an instantaneous occurrence clashes with another that is in progress or
+//! starting at the same moment, unless [`conflicts_with`] is told to ignore such occurrences by
+//! its [`ZeroDurationPolicy`].
+
+use std::ops::Range;
+
+use crate::{
+    json::{ConstructibleJsonValue, DestructibleJsonValue, JsonValue},
+    model::{
+        object::{Event, TaskOrEvent, local_to_utc},
+        set::{EventStatus, FreeBusyStatus, Token},
+        time::{DateTime, Local, Utc, UtcOffset},
+    },
+};
+
+/// A pair of overlapping occurrences found by [`conflicts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// The indices of the two events in the input, in ascending order.
+    pub events: (usize, usize),
+    /// The start times of the two occurrences in the local time of their events, in the same
+    /// order as [`Conflict::events`].
+    pub occurrences: (DateTime<Local>, DateTime<Local>),
+    /// The time during which both occurrences take place, which is empty if either of them is
+    /// instantaneous.
+    pub interval: Range<DateTime<Utc>>,
+}
+
+/// How [`conflicts_with`] treats occurrences whose duration is absent or zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ZeroDurationPolicy {
+    /// Zero-length occurrences are instants, which conflict with any occurrence that is in
+    /// progress or starting at the same moment.
+    #[default]
+    Instant,
+    /// Zero-length occurrences never conflict, as though they took no time on the calendar.
+    Ignore,
+}
+
+impl<V: JsonValue> Event<V> {
+    /// Returns `true` iff the time spans of this event and `other` overlap.
+    ///
+    /// Both events are compared in local time, so the result is only meaningful if they have the
+    /// same `timeZone` or are both floating, and recurrences are ignored; use [`conflicts`] to
+    /// take these into account.
+    pub fn overlaps(&self, other: &Event<V>) -> bool {
+        let span = |event: &Event<V>| {
+            let start = *event.start();
+            start..event.end().unwrap_or(start)
+        };
+        spans_overlap(&span(self), &span(other))
+    }
+}
+
+/// Returns the pairs of occurrences of different `events` which overlap, ordered by the start of
+/// the overlap and then by the indices of the events.
+///
+/// The occurrences of each event are those produced by [`Event::materialize`] for `window`, so
+/// `expand` returns the occurrences generated by the recurrence rules of an event, as for the
+/// resolver of that method, and overrides are applied. Occurrences which are cancelled or whose
+/// `freeBusyStatus` is `free` never conflict.
+///
+/// Occurrences are compared in UTC: `offset` returns the offset from UTC of the given `timeZone`
+/// at the given local time. Floating occurrences, and those whose time zone cannot be resolved,
+/// are treated as though they were in UTC.
+///
+/// Zero-length occurrences are treated as instants; use [`conflicts_with`] to ignore them instead.
+pub fn conflicts<V, R, I, Z>(
+    events: &[Event<V>],
+    window: Range<DateTime<Local>>,
+    expand: R,
+    offset: Z,
+) -> Vec<Conflict>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+    V::Object: Clone,
+    R: FnMut(&Event<V>, &Range<DateTime<Local>>) -> I,
+    I: IntoIterator<Item = DateTime<Local>>,
+    Z: FnMut(&str, DateTime<Local>) -> Option<UtcOffset>,
+{
+    conflicts_with(
+        events,
+        window,
+        expand,
+        offset,
+        ZeroDurationPolicy::default(),
+    )
+}
+
+/// Returns the pairs of occurrences of different `events` which overlap like [`conflicts`], but
+/// treats occurrences whose duration is absent or zero according to `policy`.
+pub fn conflicts_with<V, R, I, Z>(
+    events: &[Event<V>],
+    window: Range<DateTime<Local>>,
+    mut expand: R,
+    mut offset: Z,
+    policy: ZeroDurationPolicy,
+) -> Vec<Conflict>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+    V::Object: Clone,
+    R: FnMut(&Event<V>, &Range<DateTime<Local>>) -> I,
+    I: IntoIterator<Item = DateTime<Local>>,
+    Z: FnMut(&str, DateTime<Local>) -> Option<UtcOffset>,
+{
+    let mut occurrences = Vec::new();
+    for (index, event) in events.iter().enumerate() {
+        let (group, _) = event.materialize(window.clone(), usize::MAX, &mut expand);
+        for entry in group.entries() {
+            let TaskOrEvent::Event(occurrence) = entry else {
+                continue;
+            };
+            if occurrence.status() == Some(&Token::Known(EventStatus::Cancelled))
+                || occurrence.free_busy_status() == Some(&Token::Known(FreeBusyStatus::Free))
+                || (policy == ZeroDurationPolicy::Ignore && occurrence.is_instantaneous())
+            {
+                continue;
+            }
+
+            let local = *occurrence.start();
+            let zone = occurrence
+                .time_zone()
+                .and_then(|time_zone| offset(time_zone, local));
+            let Some(start) = to_utc(local, zone) else {
+                continue;
+            };
+            let end = occurrence
+                .duration()
+                .and_then(|duration| start.checked_add(*duration))
+                .unwrap_or(start);
+            occurrences.push((start..end, index, local));
+        }
+    }
+    occurrences.sort_by_key(|(span, index, _)| (span.start, *index));
+
+    let mut conflicts = Vec::new();
+    for (i, (span, index, local)) in occurrences.iter().enumerate() {
+        for (other_span, other_index, other_local) in &occurrences[i + 1..] {
+            if !spans_overlap(span, other_span) {
+                break;
+            }
+            if index == other_index {
+                continue;
+            }
+
+            let start = span.start.max(other_span.start);
+            let end = span.end.min(other_span.end).max(start);
+            let (events, occurrences) = if index < other_index {
+                ((*index, *other_index), (*local, *other_local))
+            } else {
+                ((*other_index, *index), (*other_local, *local))
+            };
+            conflicts.push(Conflict {
+                events,
+                occurrences,
+                interval: start..end,
+            });
+        }
+    }
+
+    conflicts.sort_by_key(|conflict| (conflict.interval.start, conflict.events));
+    conflicts
+}
+
+/// Returns `true` iff the half-open spans `a` and `b` overlap or start at the same time.
+pub(crate) fn spans_overlap<T: Ord>(a: &Range<T>, b: &Range<T>) -> bool {
+    a.start == b.start || (a.start < b.end && b.start < a.end)
+}
+
+/// Converts a local date-time into UTC at the given offset, or as though it were in UTC if there
+/// is none.
+pub(crate) fn to_utc(local: DateTime<Local>, offset: Option<UtcOffset>) -> Option<DateTime<Utc>> {
+    match offset {
+        Some(offset) => local_to_utc(local, offset),
+        None => Some(DateTime {
+            date: local.date,
+            time: local.time,
+            marker: Utc,
+        }),
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{
+        json::TryFromJson,
+        model::time::{Hour, Minute, NonLeapSecond, Sign},
+    };
+
+    fn event(value: Value) -> Event<Value> {
+        Event::try_from_json(value).unwrap()
+    }
+
+    fn datetime(s: &str) -> DateTime<Local> {
+        DateTime::try_from_json(Value::from(s)).unwrap()
+    }
+
+    fn meeting(uid: &str, start: &str, duration: &str) -> Event<Value> {
+        event(json!({
+            "@type": "Event",
+            "uid": uid,
+            "start": start,
+            "duration": duration
+        }))
+    }
+
+    fn none(_: &Event<Value>, _: &Range<DateTime<Local>>) -> Vec<DateTime<Local>> {
+        Vec::new()
+    }
+
+    fn no_zones(_: &str, _: DateTime<Local>) -> Option<UtcOffset> {
+        None
+    }
+
+    #[test]
+    fn overlaps() {
+        let standup = meeting("a", "2024-01-01T09:00:00", "PT30M");
+        let review = meeting("b", "2024-01-01T09:15:00", "PT1H");
+        let lunch = meeting("c", "2024-01-01T09:30:00", "PT1H");
+        let reminder = event(json!({
+            "@type": "Event",
+            "uid": "d",
+            "start": "2024-01-01T09:00:00"
+        }));
+
+        assert!(standup.overlaps(&review));
+        assert!(review.overlaps(&standup));
+        assert!(!standup.overlaps(&lunch), "spans are half-open");
+        assert!(standup.overlaps(&reminder));
+        assert!(reminder.overlaps(&reminder));
+        assert!(!reminder.overlaps(&lunch));
+    }
+
+    #[test]
+    fn conflicts_with_recurrences_and_time_zones() {
+        let daily = event(json!({
+            "@type": "Event",
+            "uid": "standup",
+            "start": "2024-01-01T09:00:00",
+            "duration": "PT30M",
+            "timeZone": "Europe/Berlin",
+            "recurrenceRules": [{ "@type": "RecurrenceRule", "frequency": "daily" }],
+            "recurrenceOverrides": {
+                "2024-01-02T09:00:00": { "start": "2024-01-02T11:00:00" }
+            }
+        }));
+        let call = event(json!({
+            "@type": "Event",
+            "uid": "call",
+            "start": "2024-01-02T09:45:00",
+            "duration": "PT1H",
+            "timeZone": "Europe/London"
+        }));
+        let free = event(json!({
+            "@type": "Event",
+            "uid": "focus",
+            "start": "2024-01-01T08:00:00",
+            "duration": "PT1H",
+            "freeBusyStatus": "free"
+        }));
+        let events = [daily, call, free];
+
+        let expand = |event: &Event<Value>, range: &Range<DateTime<Local>>| {
+            if event.uid().as_str() != "standup" {
+                return Vec::new();
+            }
+            ["2024-01-01T09:00:00", "2024-01-02T09:00:00"]
+                .map(datetime)
+                .into_iter()
+                .filter(|start| range.contains(start))
+                .collect()
+        };
+        let offset = |time_zone: &str, _: DateTime<Local>| {
+            let hour = match time_zone {
+                "Europe/Berlin" => Hour::H01,
+                _ => Hour::H00,
+            };
+            Some(UtcOffset {
+                sign: Sign::Pos,
+                hour,
+                minute: Minute::M00,
+                second: NonLeapSecond::S00,
+            })
+        };
+
+        let window = datetime("2024-01-01T00:00:00")..datetime("2024-01-03T00:00:00");
+        let found = conflicts(&events, window, expand, offset);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].events, (0, 1));
+        assert_eq!(
+            found[0].occurrences,
+            (
+                datetime("2024-01-02T11:00:00"),
+                datetime("2024-01-02T09:45:00")
+            )
+        );
+        let utc = |s: &str| DateTime::<Utc>::try_from_json(Value::from(s)).unwrap();
+        assert_eq!(
+            found[0].interval,
+            utc("2024-01-02T10:00:00Z")..utc("2024-01-02T10:30:00Z")
+        );
+
+        // compared in local time, the moved standup starts after the call ends, and the free
+        // focus time never conflicts with the first standup
+        let window = datetime("2024-01-01T00:00:00")..datetime("2024-01-03T00:00:00");
+        assert!(conflicts(&events, window, expand, no_zones).is_empty());
+    }
+
+    #[test]
+    fn conflicts_ignore_occurrences_of_the_same_event() {
+        let events = [
+            meeting("a", "2024-01-01T09:00:00", "PT2H"),
+            meeting("b", "2024-01-01T10:00:00", "PT2H"),
+            meeting("c", "2024-01-01T10:30:00", "PT15M"),
+        ];
+        let window = datetime("2024-01-01T00:00:00")..datetime("2024-01-02T00:00:00");
+        let found: Vec<_> = conflicts(&events, window, none, no_zones)
+            .into_iter()
+            .map(|conflict| conflict.events)
+            .collect();
+        assert_eq!(found, [(0, 1), (0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn conflicts_with_zero_duration_policy() {
+        let events = [
+            meeting("a", "2024-01-01T09:00:00", "PT2H"),
+            meeting("b", "2024-01-01T10:00:00", "PT0S"),
+            event(json!({ "@type": "Event", "uid": "c", "start": "2024-01-01T10:30:00" })),
+        ];
+        assert!(events[1].is_instantaneous() && events[2].is_instantaneous());
+        assert!(!events[0].is_instantaneous());
+
+        let window = || datetime("2024-01-01T00:00:00")..datetime("2024-01-02T00:00:00");
+        let found = |policy| -> Vec<_> {
+            conflicts_with(&events, window(), none, no_zones, policy)
+                .into_iter()
+                .map(|conflict| conflict.events)
+                .collect()
+        };
+        assert_eq!(found(ZeroDurationPolicy::Instant), [(0, 1), (0, 2)]);
+        assert!(found(ZeroDurationPolicy::Ignore).is_empty());
+    }
+}
@@ -0,0 +1,531 @@
+//! Conversion between events and tasks.
+//!
+//! Calendar applications commonly let users schedule a task as a block of time, or turn an event
+//! back into a task. [`Task::into_event`] and [`Event::into_task`] carry over every property the
+//! two object types share, and translate the timing properties between them:
+//!
+//! - a task's `start` and `estimatedDuration` become an event's `start` and `duration`; if the
+//!   task has no `start`, it is derived from `due`, and if it has no `estimatedDuration`, the
+//!   duration runs until `due`;
+//! - an event's `start` and `duration` become a task's `start` and `estimatedDuration`;
+//! - a `cancelled` event `status` and task `progress` are translated into each other.
+//!
+//! Both conversions also apply to the patches of `recurrenceOverrides` and `localizations`. The
+//! properties which the target type cannot represent are dropped, and their paths (such as
+//! `percentComplete` or `participants/p1/progress`) are returned alongside the converted object.
+//!
+//! ```
+//! # #[cfg(feature = "serde_json")]
+//! # {
+//! use jscalendar::prelude::*;
+//! use serde_json::{Value, json};
+//!
+//! let task = Task::<Value>::try_from_json(json!({
+//!     "@type": "Task",
+//!     "uid": "a",
+//!     "title": "Write report",
+//!     "due": "2024-03-01T17:00:00",
+//!     "estimatedDuration": "PT2H",
+//!     "percentComplete": 10
+//! }))
+//! .unwrap();
+//!
+//! let default_start = DateTime::<Local>::try_from_json(json!("2024-03-01T09:00:00")).unwrap();
+//! let (event, dropped) = task.into_event(default_start);
+//! assert_eq!(event.start().to_string(), "2024-03-01T15:00:00");
+//! assert_eq!(event.title().map(String::as_str), Some("Write report"));
+//! assert_eq!(dropped, ["percentComplete"]);
+//! # }
+//! ```
+
+use std::{collections::HashMap, fmt::Display, hash::Hash};
+
+use crate::{
+    json::JsonValue,
+    model::{
+        object::{Event, Participant, PatchObject, Task, TaskParticipant},
+        props,
+        set::{EventStatus, TaskProgress, Token},
+        string::ImplicitJsonPointer,
+        time::{DateTime, Local},
+    },
+};
+
+/// Moves each of the given optional properties from one structible value to another.
+macro_rules! move_fields {
+    ($from:ident => $to:ident; $($take:ident => $set:ident),* $(,)?) => {
+        $(
+            if let Some(value) = $from.$take() {
+                $to.$set(value);
+            }
+        )*
+    };
+}
+
+impl<V: JsonValue> Task<V> {
+    /// Converts this task into an event, returning the event and the paths of the properties
+    /// which were dropped.
+    ///
+    /// The start of the event is the `start` of the task; otherwise its `due` time less its
+    /// `estimatedDuration`; otherwise `default_start`. The `due` time is dropped if it does not
+    /// coincide with the end of the event.
+    pub fn into_event(self, default_start: DateTime<Local>) -> (Event<V>, Vec<String>) {
+        let mut dropped = Vec::new();
+        let mut f = self.into_fields();
+
+        let due = f.take_due();
+        let estimated_duration = f.take_estimated_duration();
+        let start = f
+            .take_start()
+            .or_else(|| {
+                due.and_then(|due| match estimated_duration {
+                    Some(duration) => due.checked_sub(duration),
+                    None => Some(due),
+                })
+            })
+            .unwrap_or(default_start);
+        let duration = estimated_duration.or_else(|| {
+            due.filter(|due| *due > start)
+                .map(|due| due.signed_duration_since(&start).duration)
+        });
+        if let Some(due) = due
+            && duration.map_or(Some(start), |duration| start.checked_add(duration)) != Some(due)
+        {
+            dropped.push(props::DUE.to_string());
+        }
+
+        let mut event = Event::new(start, f.take_uid().expect("uid is required"));
+        if let Some(duration) = duration {
+            event.set_duration(duration);
+        }
+        match f.take_progress() {
+            Some(Token::Known(TaskProgress::Cancelled)) => {
+                event.set_status(Token::Known(EventStatus::Cancelled));
+            }
+            Some(_) => dropped.push(props::PROGRESS.to_string()),
+            None => {}
+        }
+        if f.take_percent_complete().is_some() {
+            dropped.push(props::PERCENT_COMPLETE.to_string());
+        }
+        if f.take_progress_updated().is_some() {
+            dropped.push(props::PROGRESS_UPDATED.to_string());
+        }
+
+        move_fields!(f => event;
+            take_related_to => set_related_to,
+            take_prod_id => set_prod_id,
+            take_created => set_created,
+            take_updated => set_updated,
+            take_sequence => set_sequence,
+            take_method => set_method,
+            take_title => set_title,
+            take_description => set_description,
+            take_description_content_type => set_description_content_type,
+            take_show_without_time => set_show_without_time,
+            take_locations => set_locations,
+            take_virtual_locations => set_virtual_locations,
+            take_links => set_links,
+            take_locale => set_locale,
+            take_keywords => set_keywords,
+            take_categories => set_categories,
+            take_color => set_color,
+            take_recurrence_id => set_recurrence_id,
+            take_recurrence_id_time_zone => set_recurrence_id_time_zone,
+            take_recurrence_rules => set_recurrence_rules,
+            take_excluded_recurrence_rules => set_excluded_recurrence_rules,
+            take_excluded => set_excluded,
+            take_priority => set_priority,
+            take_free_busy_status => set_free_busy_status,
+            take_privacy => set_privacy,
+            take_reply_to => set_reply_to,
+            take_sent_by => set_sent_by,
+            take_request_status => set_request_status,
+            take_use_default_alerts => set_use_default_alerts,
+            take_alerts => set_alerts,
+            take_time_zone => set_time_zone,
+            take_time_zones => set_time_zones,
+        );
+
+        if let Some(participants) = f.take_participants() {
+            let participants = participants
+                .into_iter()
+                .map(|(id, participant)| {
+                    let prefix = format!("{}/{id}", props::PARTICIPANTS);
+                    (id, participant.into_participant(&prefix, &mut dropped))
+                })
+                .collect();
+            event.set_participants(participants);
+        }
+        if let Some(overrides) = f.take_recurrence_overrides() {
+            let overrides = convert_patches(
+                overrides,
+                props::RECURRENCE_OVERRIDES,
+                TASK_TO_EVENT,
+                &mut dropped,
+            );
+            event.set_recurrence_overrides(overrides);
+        }
+        if let Some(localizations) = f.take_localizations() {
+            let localizations = convert_patches(
+                localizations,
+                props::LOCALIZATIONS,
+                TASK_TO_EVENT,
+                &mut dropped,
+            );
+            event.set_localizations(localizations);
+        }
+        for (key, value) in f.drain_vendor_property() {
+            event.insert_vendor_property(key, value);
+        }
+
+        (event, dropped)
+    }
+}
+
+impl<V: JsonValue> Event<V> {
+    /// Converts this event into a task, returning the task and the paths of the properties which
+    /// were dropped.
+    ///
+    /// The task is not given a `due` time, since an event has no deadline. The `status` of the
+    /// event is dropped unless it is `cancelled`.
+    pub fn into_task(self) -> (Task<V>, Vec<String>) {
+        let mut dropped = Vec::new();
+        let mut f = self.into_fields();
+
+        let mut task = Task::new(f.take_uid().expect("uid is required"));
+        task.set_start(f.take_start().expect("start is required"));
+        if let Some(duration) = f.take_duration() {
+            task.set_estimated_duration(duration);
+        }
+        match f.take_status() {
+            Some(Token::Known(EventStatus::Cancelled)) => {
+                task.set_progress(Token::Known(TaskProgress::Cancelled));
+            }
+            Some(_) => dropped.push(props::STATUS.to_string()),
+            None => {}
+        }
+
+        move_fields!(f => task;
+            take_related_to => set_related_to,
+            take_prod_id => set_prod_id,
+            take_created => set_created,
+            take_updated => set_updated,
+            take_sequence => set_sequence,
+            take_method => set_method,
+            take_title => set_title,
+            take_description => set_description,
+            take_description_content_type => set_description_content_type,
+            take_show_without_time => set_show_without_time,
+            take_locations => set_locations,
+            take_virtual_locations => set_virtual_locations,
+            take_links => set_links,
+            take_locale => set_locale,
+            take_keywords => set_keywords,
+            take_categories => set_categories,
+            take_color => set_color,
+            take_recurrence_id => set_recurrence_id,
+            take_recurrence_id_time_zone => set_recurrence_id_time_zone,
+            take_recurrence_rules => set_recurrence_rules,
+            take_excluded_recurrence_rules => set_excluded_recurrence_rules,
+            take_excluded => set_excluded,
+            take_priority => set_priority,
+            take_free_busy_status => set_free_busy_status,
+            take_privacy => set_privacy,
+            take_reply_to => set_reply_to,
+            take_sent_by => set_sent_by,
+            take_request_status => set_request_status,
+            take_use_default_alerts => set_use_default_alerts,
+            take_alerts => set_alerts,
+            take_time_zone => set_time_zone,
+            take_time_zones => set_time_zones,
+        );
+
+        if let Some(participants) = f.take_participants() {
+            let participants = participants
+                .into_iter()
+                .map(|(id, participant)| (id, participant.into_task_participant()))
+                .collect();
+            task.set_participants(participants);
+        }
+        if let Some(overrides) = f.take_recurrence_overrides() {
+            let overrides = convert_patches(
+                overrides,
+                props::RECURRENCE_OVERRIDES,
+                EVENT_TO_TASK,
+                &mut dropped,
+            );
+            task.set_recurrence_overrides(overrides);
+        }
+        if let Some(localizations) = f.take_localizations() {
+            let localizations = convert_patches(
+                localizations,
+                props::LOCALIZATIONS,
+                EVENT_TO_TASK,
+                &mut dropped,
+            );
+            task.set_localizations(localizations);
+        }
+        for (key, value) in f.drain_vendor_property() {
+            task.insert_vendor_property(key, value);
+        }
+
+        (task, dropped)
+    }
+}
+
+impl<V: JsonValue> TaskParticipant<V> {
+    /// Converts this task participant into an event participant, recording the paths of its
+    /// task-specific properties under `prefix`.
+    fn into_participant(self, prefix: &str, dropped: &mut Vec<String>) -> Participant<V> {
+        let mut f = self.into_fields();
+        let mut participant = Participant::new();
+        for (name, present) in [
+            (props::PROGRESS, f.take_progress().is_some()),
+            (props::PROGRESS_UPDATED, f.take_progress_updated().is_some()),
+            (props::PERCENT_COMPLETE, f.take_percent_complete().is_some()),
+        ] {
+            if present {
+                dropped.push(format!("{prefix}/{name}"));
+            }
+        }
+
+        move_fields!(f => participant;
+            take_name => set_name,
+            take_email => set_email,
+            take_description => set_description,
+            take_send_to => set_send_to,
+            take_kind => set_kind,
+            take_roles => set_roles,
+            take_location_id => set_location_id,
+            take_language => set_language,
+            take_participation_status => set_participation_status,
+            take_participation_comment => set_participation_comment,
+            take_expect_reply => set_expect_reply,
+            take_schedule_agent => set_schedule_agent,
+            take_schedule_force_send => set_schedule_force_send,
+            take_schedule_sequence => set_schedule_sequence,
+            take_schedule_status => set_schedule_status,
+            take_schedule_updated => set_schedule_updated,
+            take_sent_by => set_sent_by,
+            take_invited_by => set_invited_by,
+            take_delegated_to => set_delegated_to,
+            take_delegated_from => set_delegated_from,
+            take_member_of => set_member_of,
+            take_links => set_links,
+        );
+        for (key, value) in f.drain_vendor_property() {
+            participant.insert_vendor_property(key, value);
+        }
+        participant
+    }
+}
+
+impl<V: JsonValue> Participant<V> {
+    /// Converts this event participant into a task participant, which is lossless.
+    fn into_task_participant(self) -> TaskParticipant<V> {
+        let mut f = self.into_fields();
+        let mut participant = TaskParticipant::new();
+        move_fields!(f => participant;
+            take_name => set_name,
+            take_email => set_email,
+            take_description => set_description,
+            take_send_to => set_send_to,
+            take_kind => set_kind,
+            take_roles => set_roles,
+            take_location_id => set_location_id,
+            take_language => set_language,
+            take_participation_status => set_participation_status,
+            take_participation_comment => set_participation_comment,
+            take_expect_reply => set_expect_reply,
+            take_schedule_agent => set_schedule_agent,
+            take_schedule_force_send => set_schedule_force_send,
+            take_schedule_sequence => set_schedule_sequence,
+            take_schedule_status => set_schedule_status,
+            take_schedule_updated => set_schedule_updated,
+            take_sent_by => set_sent_by,
+            take_invited_by => set_invited_by,
+            take_delegated_to => set_delegated_to,
+            take_delegated_from => set_delegated_from,
+            take_member_of => set_member_of,
+            take_links => set_links,
+        );
+        for (key, value) in f.drain_vendor_property() {
+            participant.insert_vendor_property(key, value);
+        }
+        participant
+    }
+}
+
+/// How the top-level properties of patches are translated: each property is either renamed,
+/// or dropped if it has no counterpart.
+struct PatchMapping {
+    renamed: &'static [(&'static str, &'static str)],
+    dropped: &'static [&'static str],
+}
+
+const TASK_TO_EVENT: PatchMapping = PatchMapping {
+    renamed: &[(props::ESTIMATED_DURATION, props::DURATION)],
+    dropped: &[
+        props::DUE,
+        props::PERCENT_COMPLETE,
+        props::PROGRESS,
+        props::PROGRESS_UPDATED,
+    ],
+};
+
+const EVENT_TO_TASK: PatchMapping = PatchMapping {
+    renamed: &[(props::DURATION, props::ESTIMATED_DURATION)],
+    dropped: &[props::STATUS],
+};
+
+/// Translates the top-level properties of each patch object in `patches`, which is the value of
+/// `property`, recording the paths of the patches which were dropped.
+fn convert_patches<K, V>(
+    patches: HashMap<K, PatchObject<V>>,
+    property: &str,
+    mapping: PatchMapping,
+    dropped: &mut Vec<String>,
+) -> HashMap<K, PatchObject<V>>
+where
+    K: Eq + Hash + Display,
+{
+    patches
+        .into_iter()
+        .map(|(key, patch)| {
+            let patch = patch
+                .into_inner()
+                .into_iter()
+                .filter_map(|(pointer, value)| {
+                    let name = pointer.segments().next().unwrap_or_default();
+                    if mapping.dropped.contains(&name.as_ref()) {
+                        dropped.push(format!("{property}/{key}/{pointer}"));
+                        return None;
+                    }
+                    let renamed = mapping
+                        .renamed
+                        .iter()
+                        .find(|(from, _)| *from == pointer.to_string());
+                    let pointer = match renamed {
+                        Some((_, to)) => ImplicitJsonPointer::new(to)
+                            .expect("property names are valid pointers")
+                            .into(),
+                        None => pointer,
+                    };
+                    Some((pointer, value))
+                })
+                .collect();
+            (key, patch)
+        })
+        .collect()
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use crate::{
+        json::{IntoJson, TryFromJson},
+        model::{
+            object::{Event, Task},
+            time::{DateTime, Local},
+        },
+    };
+
+    fn local(value: &str) -> DateTime<Local> {
+        DateTime::try_from_json(json!(value)).unwrap()
+    }
+
+    #[test]
+    fn task_into_event() {
+        let task = Task::<Value>::try_from_json(json!({
+            "@type": "Task",
+            "uid": "a",
+            "start": "2024-03-01T09:00:00",
+            "due": "2024-03-01T12:00:00",
+            "progress": "cancelled",
+            "participants": {
+                "p1": { "@type": "Participant", "name": "Ana", "progress": "completed" }
+            },
+            "recurrenceRules": [{ "@type": "RecurrenceRule", "frequency": "daily" }],
+            "recurrenceOverrides": {
+                "2024-03-02T09:00:00": { "estimatedDuration": "PT1H", "due": "2024-03-02T10:00:00" }
+            },
+            "example.com:flag": true
+        }))
+        .unwrap();
+
+        let (event, dropped) = task.into_event(local("2000-01-01T00:00:00"));
+        assert_eq!(
+            dropped,
+            [
+                "participants/p1/progress",
+                "recurrenceOverrides/2024-03-02T09:00:00/due",
+            ]
+        );
+        assert_eq!(
+            event.into_json(),
+            json!({
+                "@type": "Event",
+                "uid": "a",
+                "start": "2024-03-01T09:00:00",
+                "duration": "PT3H",
+                "status": "cancelled",
+                "participants": { "p1": { "@type": "Participant", "name": "Ana" } },
+                "recurrenceRules": [{ "@type": "RecurrenceRule", "frequency": "daily" }],
+                "recurrenceOverrides": { "2024-03-02T09:00:00": { "duration": "PT1H" } },
+                "example.com:flag": true
+            })
+        );
+    }
+
+    #[test]
+    fn task_without_times_uses_default_start() {
+        let task = Task::<Value>::try_from_json(json!({ "@type": "Task", "uid": "a" })).unwrap();
+        let (event, dropped) = task.into_event(local("2024-03-01T09:00:00"));
+        assert!(dropped.is_empty());
+        assert_eq!(event.start().to_string(), "2024-03-01T09:00:00");
+        assert_eq!(event.duration(), None);
+    }
+
+    #[test]
+    fn event_round_trip() {
+        let input = json!({
+            "@type": "Event",
+            "uid": "a",
+            "title": "Standup",
+            "start": "2024-03-01T09:00:00",
+            "duration": "PT15M",
+            "timeZone": "Europe/Berlin",
+            "participants": { "p1": { "@type": "Participant", "roles": { "owner": true } } }
+        });
+        let event = Event::<Value>::try_from_json(input.clone()).unwrap();
+
+        let (task, dropped) = event.into_task();
+        assert!(dropped.is_empty());
+        assert_eq!(
+            task.estimated_duration()
+                .map(ToString::to_string)
+                .as_deref(),
+            Some("PT15M")
+        );
+
+        let (event, dropped) = task.into_event(local("2000-01-01T00:00:00"));
+        assert!(dropped.is_empty());
+        assert_eq!(event.into_json(), input);
+    }
+
+    #[test]
+    fn event_status_is_dropped() {
+        let event = Event::<Value>::try_from_json(json!({
+            "@type": "Event",
+            "uid": "a",
+            "start": "2024-03-01T09:00:00",
+            "status": "tentative"
+        }))
+        .unwrap();
+        let (task, dropped) = event.into_task();
+        assert_eq!(dropped, ["status"]);
+        assert_eq!(task.progress(), None);
+    }
+}
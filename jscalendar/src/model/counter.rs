@@ -0,0 +1,354 @@
+//! Counter-proposals for events, in JSCalendar terms.
+//!
+//! iTIP (RFC 5546 §3.2.7, §3.2.8) lets an attendee propose changes to an event with a `COUNTER`
+//! message, which the organizer either applies (and redistributes as a new revision) or rejects
+//! with a `DECLINECOUNTER` message. The helpers in this module implement the same flow on
+//! [`Event`] objects, using the `method` property to tag the messages and treating participants
+//! with the `owner` role as the organizer.
+
+use thiserror::Error;
+
+use crate::{
+    json::{JsonValue, UnsignedInt},
+    model::{
+        object::{Event, Participant},
+        set::{Method, ParticipantRole, ParticipationStatus, Token},
+        string::Id,
+        time::{DateTime, Local},
+    },
+};
+
+/// An error produced while creating or handling a counter-proposal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum CounterError {
+    /// The proposing participant does not occur in the event.
+    #[error("the proposing participant does not occur in the event")]
+    UnknownParticipant,
+    /// The proposal does not have the `method` `counter`.
+    #[error("the proposal is not tagged with the counter method")]
+    NotACounter,
+    /// The proposal refers to a different event or occurrence.
+    #[error("the proposal refers to a different event or occurrence")]
+    DifferentObject,
+    /// The proposal was made against a different revision of the event.
+    #[error("the proposal was made against a different sequence number")]
+    SequenceMismatch,
+    /// The proposing participant does not occur in the proposal.
+    #[error("the proposing participant does not occur in the proposal")]
+    MissingProposer,
+}
+
+impl<V> Event<V>
+where
+    V: JsonValue + Clone,
+    V::Object: Clone,
+{
+    /// Returns a counter-proposal from the given participant which moves the event to
+    /// `new_start`.
+    ///
+    /// The proposal is a copy of the event tagged with the `counter` method, keeping the `uid`,
+    /// `recurrenceId`, and `sequence` of the original. Its `participants` are restricted to the
+    /// proposer and the owners of the event.
+    pub fn propose_new_time(
+        &self,
+        participant: &Id,
+        new_start: DateTime<Local>,
+    ) -> Result<Self, CounterError> {
+        if !self
+            .participants()
+            .is_some_and(|participants| participants.contains_key(participant))
+        {
+            return Err(CounterError::UnknownParticipant);
+        }
+
+        let mut proposal = self.clone();
+        proposal.set_method(Token::Known(Method::Counter));
+        proposal.set_start(new_start);
+        retain_counterparties(&mut proposal, participant);
+        Ok(proposal)
+    }
+
+    /// Applies a counter-proposal produced by [`Event::propose_new_time`] on the organizer side.
+    ///
+    /// The proposed `start`, `duration`, and `timeZone` replace those of the event, and the
+    /// `sequence` is incremented. Since the event has been rescheduled, every participant other
+    /// than the owners must reply again: their `participationStatus` is reset to `needs-action`,
+    /// except for delegated participants and for the proposer, whose status is taken from the
+    /// proposal.
+    ///
+    /// Like the sender of an iTIP message, the `proposer` is identified by the transport rather
+    /// than by the proposal itself, and must occur in both the event and the proposal.
+    pub fn apply_counter(&mut self, proposal: &Self, proposer: &Id) -> Result<(), CounterError> {
+        self.check_counter(proposal, proposer)?;
+        let proposed_status = proposal
+            .participants()
+            .and_then(|participants| participants.get(proposer))
+            .and_then(|participant| participant.participation_status())
+            .cloned();
+
+        self.set_start(*proposal.start());
+        match proposal.duration() {
+            Some(duration) => self.set_duration(*duration),
+            None => drop(self.remove_duration()),
+        }
+        match proposal.time_zone() {
+            Some(time_zone) => self.set_time_zone(time_zone.clone()),
+            None => drop(self.remove_time_zone()),
+        }
+
+        let sequence = self.sequence_or_default().get() + 1;
+        self.set_sequence(UnsignedInt::new(sequence).unwrap_or(UnsignedInt::MAX));
+
+        for (id, participant) in self.participants_mut().into_iter().flatten() {
+            if **id == *proposer {
+                match &proposed_status {
+                    Some(status) => participant.set_participation_status(status.clone()),
+                    None => participant
+                        .set_participation_status(Token::Known(ParticipationStatus::NeedsAction)),
+                }
+            } else if !is_owner(participant)
+                && participant.participation_status()
+                    != Some(&Token::Known(ParticipationStatus::Delegated))
+            {
+                participant
+                    .set_participation_status(Token::Known(ParticipationStatus::NeedsAction));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the organizer's rejection of a counter-proposal, equivalent to an iTIP
+    /// `DECLINECOUNTER` message.
+    ///
+    /// The rejection is a copy of the current event tagged with the `declinecounter` method,
+    /// whose `participants` are restricted to the `proposer` and the owners of the event.
+    pub fn decline_counter(&self, proposal: &Self, proposer: &Id) -> Result<Self, CounterError> {
+        self.check_counter(proposal, proposer)?;
+        let mut response = self.clone();
+        response.set_method(Token::Known(Method::DeclineCounter));
+        retain_counterparties(&mut response, proposer);
+        Ok(response)
+    }
+
+    /// Checks that `proposal` is a counter-proposal by `proposer` for this revision of the event.
+    fn check_counter(&self, proposal: &Self, proposer: &Id) -> Result<(), CounterError> {
+        if proposal.method() != Some(&Token::Known(Method::Counter)) {
+            return Err(CounterError::NotACounter);
+        }
+
+        if proposal.uid() != self.uid() || proposal.recurrence_id() != self.recurrence_id() {
+            return Err(CounterError::DifferentObject);
+        }
+
+        if proposal.sequence_or_default() != self.sequence_or_default() {
+            return Err(CounterError::SequenceMismatch);
+        }
+
+        let contains_proposer = |event: &Self| {
+            event
+                .participants()
+                .is_some_and(|participants| participants.contains_key(proposer))
+        };
+
+        if !contains_proposer(self) {
+            return Err(CounterError::UnknownParticipant);
+        }
+
+        if !contains_proposer(proposal) {
+            return Err(CounterError::MissingProposer);
+        }
+
+        Ok(())
+    }
+}
+
+/// Removes every participant of `event` other than `participant` and the owners.
+fn retain_counterparties<V: JsonValue>(event: &mut Event<V>, participant: &Id) {
+    if let Some(participants) = event.participants_mut() {
+        participants.retain(|id, p| **id == *participant || is_owner(p));
+    }
+}
+
+/// Returns `true` iff the participant has the `owner` role.
+fn is_owner<V: JsonValue>(participant: &Participant<V>) -> bool {
+    participant
+        .roles()
+        .is_some_and(|roles| roles.contains(ParticipantRole::Owner))
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::json::{IntoJson, TryFromJson};
+
+    fn event() -> Event<Value> {
+        Event::try_from_json(json!({
+            "@type": "Event",
+            "uid": "meeting@example.com",
+            "start": "2024-01-15T13:00:00",
+            "duration": "PT1H",
+            "sequence": 2,
+            "participants": {
+                "org": {
+                    "@type": "Participant",
+                    "roles": { "owner": true },
+                    "participationStatus": "accepted"
+                },
+                "alice": {
+                    "@type": "Participant",
+                    "roles": { "attendee": true },
+                    "participationStatus": "accepted"
+                },
+                "bob": {
+                    "@type": "Participant",
+                    "roles": { "attendee": true },
+                    "participationStatus": "tentative"
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn start(s: &str) -> DateTime<Local> {
+        DateTime::try_from_json(Value::from(s)).unwrap()
+    }
+
+    #[test]
+    fn propose_and_apply_counter() {
+        let mut event = event();
+        let alice = Id::new("alice").unwrap();
+        let new_start = start("2024-01-15T15:00:00");
+
+        let proposal = event.propose_new_time(alice, new_start).unwrap();
+        assert_eq!(proposal.method(), Some(&Token::Known(Method::Counter)));
+        assert_eq!(proposal.start(), &new_start);
+        assert_eq!(proposal.sequence(), event.sequence());
+        let mut ids: Vec<_> = proposal
+            .participants()
+            .unwrap()
+            .keys()
+            .map(|id| id.as_str())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, ["alice", "org"]);
+
+        event.apply_counter(&proposal, alice).unwrap();
+        let json: Value = event.clone().into_json();
+        assert_eq!(json["start"], "2024-01-15T15:00:00");
+        assert_eq!(json["duration"], "PT1H");
+        assert_eq!(json["sequence"], 3);
+        let participants = &json["participants"];
+        assert_eq!(participants["org"]["participationStatus"], "accepted");
+        assert_eq!(participants["alice"]["participationStatus"], "accepted");
+        assert_eq!(participants["bob"]["participationStatus"], "needs-action");
+
+        // the proposal now refers to an older revision
+        assert_eq!(
+            event.apply_counter(&proposal, alice),
+            Err(CounterError::SequenceMismatch)
+        );
+    }
+
+    #[test]
+    fn decline_counter() {
+        let event = event();
+        let bob = Id::new("bob").unwrap();
+        let proposal = event
+            .propose_new_time(bob, start("2024-01-16T09:00:00"))
+            .unwrap();
+
+        let response = event.decline_counter(&proposal, bob).unwrap();
+        assert_eq!(
+            response.method(),
+            Some(&Token::Known(Method::DeclineCounter))
+        );
+        assert_eq!(response.start(), event.start());
+        assert_eq!(response.sequence(), event.sequence());
+        assert_eq!(response.participants().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn counter_errors() {
+        let mut event = event();
+        let carol = Id::new("carol").unwrap();
+        let new_start = start("2024-01-15T15:00:00");
+        assert_eq!(
+            event.propose_new_time(carol, new_start),
+            Err(CounterError::UnknownParticipant)
+        );
+
+        let alice = Id::new("alice").unwrap();
+        let mut proposal = event.propose_new_time(alice, new_start).unwrap();
+        let mut request = proposal.clone();
+        request.set_method(Token::Known(Method::Request));
+        assert_eq!(
+            event.apply_counter(&request, alice),
+            Err(CounterError::NotACounter)
+        );
+
+        let mut other = proposal.clone();
+        other.set_recurrence_id(new_start);
+        assert_eq!(
+            event.apply_counter(&other, alice),
+            Err(CounterError::DifferentObject)
+        );
+
+        assert_eq!(
+            event.apply_counter(&proposal, carol),
+            Err(CounterError::UnknownParticipant)
+        );
+
+        proposal.participants_mut().unwrap().remove(alice);
+        assert_eq!(
+            event.apply_counter(&proposal, alice),
+            Err(CounterError::MissingProposer)
+        );
+    }
+
+    #[test]
+    fn counter_with_several_attendees() {
+        let mut event = event();
+        let bob = Id::new("bob").unwrap();
+
+        // a proposal built by hand may keep every participant
+        let mut proposal = event.clone();
+        proposal.set_method(Token::Known(Method::Counter));
+        proposal.set_start(start("2024-01-17T10:00:00"));
+        let participants = proposal.participants_mut().unwrap();
+        participants
+            .get_mut(bob)
+            .unwrap()
+            .set_participation_status(Token::Known(ParticipationStatus::Accepted));
+
+        event.apply_counter(&proposal, bob).unwrap();
+        let json: Value = event.into_json();
+        let participants = &json["participants"];
+        assert_eq!(participants["alice"]["participationStatus"], "needs-action");
+        assert_eq!(participants["bob"]["participationStatus"], "accepted");
+    }
+
+    #[test]
+    fn owner_counter() {
+        let mut event = event();
+        let org = Id::new("org").unwrap();
+        let proposal = event
+            .propose_new_time(org, start("2024-01-15T16:00:00"))
+            .unwrap();
+        assert_eq!(proposal.participants().unwrap().len(), 1);
+
+        event.apply_counter(&proposal, org).unwrap();
+        let json: Value = event.into_json();
+        assert_eq!(json["start"], "2024-01-15T16:00:00");
+        assert_eq!(
+            json["participants"]["org"]["participationStatus"],
+            "accepted"
+        );
+        assert_eq!(
+            json["participants"]["alice"]["participationStatus"],
+            "needs-action"
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! Verification and traversal of the delegation and invitation references between participants.
+//!
+//! The participants of an event or task refer to each other by ID in their `invitedBy`,
+//! `delegatedTo`, and `delegatedFrom` properties, and to the participant who acted on their
+//! behalf by email address in their `sentBy` property (RFC 8984 §4.4.6). A [`DelegationGraph`]
+//! collects these references, so that a calendar user agent can show who attends on behalf of
+//! whom, and [`DelegationGraph::issues`] lists the references which are inconsistent.
+//!
+//! Delegation is recorded on both sides: if `a` delegated to `b`, then the `delegatedTo` of `a`
+//! contains `b`, and the `delegatedFrom` of `b` contains `a`. The graph treats a delegation
+//! recorded on either side as present, and reports those recorded on only one side as
+//! [`DelegationIssue::NotMutual`].
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use crate::{
+    json::JsonValue,
+    model::{
+        object::{Event, Task},
+        props,
+        string::{EmailAddr, Id},
+    },
+};
+
+/// An inconsistency between the references of the participants in a [`DelegationGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum DelegationIssue<'a> {
+    /// The `invitedBy`, `delegatedTo`, or `delegatedFrom` property of a participant refers to an
+    /// ID which is not a participant.
+    UnknownParticipant {
+        /// The participant whose property holds the reference.
+        participant: &'a Id,
+        /// The name of the property.
+        property: &'static str,
+        /// The ID which is not a participant.
+        reference: &'a Id,
+    },
+    /// A delegation is recorded in the `delegatedTo` of the delegator or the `delegatedFrom` of
+    /// the delegate, but not both.
+    NotMutual {
+        /// The participant who delegated.
+        delegator: &'a Id,
+        /// The participant who was delegated to.
+        delegate: &'a Id,
+    },
+}
+
+/// The delegation and invitation references between the participants of an object, indexed by
+/// participant ID.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DelegationGraph<'a> {
+    participants: BTreeMap<&'a Id, Node<'a>>,
+}
+
+/// The references of a single participant.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Node<'a> {
+    email: Option<&'a EmailAddr>,
+    sent_by: Option<&'a EmailAddr>,
+    invited_by: Option<&'a Id>,
+    delegated_to: BTreeSet<&'a Id>,
+    delegated_from: BTreeSet<&'a Id>,
+}
+
+impl<'a> DelegationGraph<'a> {
+    /// Returns `true` iff `id` is a participant in the graph.
+    pub fn contains(&self, id: &Id) -> bool {
+        self.participants.contains_key(id)
+    }
+
+    /// Returns the IDs of the participants in the graph, in order.
+    pub fn ids(&self) -> impl Iterator<Item = &'a Id> + '_ {
+        self.participants.keys().copied()
+    }
+
+    /// Returns the participants to whom `id` delegated, as recorded on either side, in order.
+    pub fn delegates(&self, id: &Id) -> BTreeSet<&'a Id> {
+        let recorded = self.participants.get(id).into_iter();
+        let reverse = self
+            .participants
+            .iter()
+            .filter(|(_, node)| node.delegated_from.contains(id))
+            .map(|(other, _)| *other);
+        recorded
+            .flat_map(|node| node.delegated_to.iter().copied())
+            .chain(reverse)
+            .collect()
+    }
+
+    /// Returns the participants who delegated to `id`, as recorded on either side, in order.
+    pub fn delegators(&self, id: &Id) -> BTreeSet<&'a Id> {
+        let recorded = self.participants.get(id).into_iter();
+        let reverse = self
+            .participants
+            .iter()
+            .filter(|(_, node)| node.delegated_to.contains(id))
+            .map(|(other, _)| *other);
+        recorded
+            .flat_map(|node| node.delegated_from.iter().copied())
+            .chain(reverse)
+            .collect()
+    }
+
+    /// Returns the participants on whose behalf `id` attends: those who delegated to it, directly
+    /// or through other delegates, and did not themselves receive a delegation.
+    ///
+    /// A participant who was not delegated to attends on its own behalf, and is returned alone.
+    pub fn on_behalf_of(&self, id: &'a Id) -> BTreeSet<&'a Id> {
+        let mut roots = BTreeSet::new();
+        let mut visited = BTreeSet::new();
+        let mut pending = vec![id];
+        while let Some(next) = pending.pop() {
+            if !visited.insert(next) {
+                continue;
+            }
+            let delegators = self.delegators(next);
+            if delegators.is_empty() {
+                roots.insert(next);
+            }
+            pending.extend(delegators);
+        }
+        roots
+    }
+
+    /// Returns the participant who invited `id`, or `None` if its `invitedBy` is absent.
+    pub fn inviter(&self, id: &Id) -> Option<&'a Id> {
+        self.participants.get(id)?.invited_by
+    }
+
+    /// Returns the chain of inviters of `id`, starting with the participant who invited it and
+    /// ending with a participant who was not invited by another, or at the first repeated
+    /// participant.
+    pub fn invitation_chain(&self, id: &Id) -> Vec<&'a Id> {
+        let mut chain: Vec<&'a Id> = Vec::new();
+        let mut next = self.inviter(id);
+        while let Some(inviter) = next {
+            if *inviter == *id || chain.contains(&inviter) {
+                break;
+            }
+            chain.push(inviter);
+            next = self.inviter(inviter);
+        }
+        chain
+    }
+
+    /// Returns the participant whose `email` is the `sentBy` address of `id`, compared
+    /// case-insensitively, or `None` if there is no such participant.
+    ///
+    /// The least such ID is returned if several participants have the same email address.
+    pub fn sender(&self, id: &Id) -> Option<&'a Id> {
+        let sent_by = self.participants.get(id)?.sent_by?;
+        self.participants
+            .iter()
+            .find(|(_, node)| {
+                node.email
+                    .is_some_and(|email| email.as_str().eq_ignore_ascii_case(sent_by.as_str()))
+            })
+            .map(|(sender, _)| *sender)
+    }
+
+    /// Returns the inconsistent references between the participants, in order.
+    pub fn issues(&self) -> Vec<DelegationIssue<'a>> {
+        let mut issues = BTreeSet::new();
+        for (&participant, node) in &self.participants {
+            let invited_by = node
+                .invited_by
+                .into_iter()
+                .map(|id| (props::INVITED_BY, id));
+            let delegated_to = node
+                .delegated_to
+                .iter()
+                .map(|&id| (props::DELEGATED_TO, id));
+            let delegated_from = node
+                .delegated_from
+                .iter()
+                .map(|&id| (props::DELEGATED_FROM, id));
+            for (property, reference) in invited_by.chain(delegated_to).chain(delegated_from) {
+                if !self.contains(reference) {
+                    issues.insert(DelegationIssue::UnknownParticipant {
+                        participant,
+                        property,
+                        reference,
+                    });
+                }
+            }
+
+            for &delegate in &node.delegated_to {
+                let other = self.participants.get(delegate);
+                if other.is_some_and(|other| !other.delegated_from.contains(participant)) {
+                    issues.insert(DelegationIssue::NotMutual {
+                        delegator: participant,
+                        delegate,
+                    });
+                }
+            }
+            for &delegator in &node.delegated_from {
+                let other = self.participants.get(delegator);
+                if other.is_some_and(|other| !other.delegated_to.contains(participant)) {
+                    issues.insert(DelegationIssue::NotMutual {
+                        delegator,
+                        delegate: participant,
+                    });
+                }
+            }
+        }
+        issues.into_iter().collect()
+    }
+}
+
+/// Implements `delegation_graph` for an object type.
+macro_rules! impl_delegation_graph {
+    ($ty:ident, $kind:literal) => {
+        impl<V: JsonValue> $ty<V> {
+            #[doc = concat!("Returns the graph of the references between the participants of this ", $kind, ".")]
+            pub fn delegation_graph(&self) -> DelegationGraph<'_> {
+                let participants = self.participants().into_iter().flatten();
+                DelegationGraph {
+                    participants: participants
+                        .map(|(id, participant)| {
+                            let node = Node {
+                                email: participant.email().map(|email| &**email),
+                                sent_by: participant.sent_by().map(|email| &**email),
+                                invited_by: participant.invited_by().map(|id| &**id),
+                                delegated_to: id_set(participant.delegated_to()),
+                                delegated_from: id_set(participant.delegated_from()),
+                            };
+                            (&**id, node)
+                        })
+                        .collect(),
+                }
+            }
+        }
+    };
+}
+
+impl_delegation_graph!(Event, "event");
+impl_delegation_graph!(Task, "task");
+
+fn id_set(ids: Option<&HashSet<Box<Id>>>) -> BTreeSet<&Id> {
+    ids.into_iter().flatten().map(|id| &**id).collect()
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::json::TryFromJson;
+
+    fn event(participants: Value) -> Event<Value> {
+        Event::try_from_json(json!({
+            "@type": "Event",
+            "uid": "a",
+            "start": "2024-01-01T09:00:00",
+            "participants": participants
+        }))
+        .unwrap()
+    }
+
+    fn id(s: &str) -> &Id {
+        Id::new(s).unwrap()
+    }
+
+    #[test]
+    fn traversal() {
+        let event = event(json!({
+            "boss": {
+                "@type": "Participant",
+                "email": "Boss@example.com",
+                "delegatedTo": ["deputy"]
+            },
+            "deputy": {
+                "@type": "Participant",
+                "delegatedFrom": ["boss"],
+                "delegatedTo": ["intern"],
+                "invitedBy": "boss"
+            },
+            "intern": {
+                "@type": "Participant",
+                "delegatedFrom": ["deputy"],
+                "sentBy": "boss@example.com",
+                "invitedBy": "deputy"
+            }
+        }));
+        let graph = event.delegation_graph();
+
+        assert_eq!(graph.delegates(id("boss")), [id("deputy")].into());
+        assert_eq!(graph.delegators(id("intern")), [id("deputy")].into());
+        assert_eq!(graph.on_behalf_of(id("intern")), [id("boss")].into());
+        assert_eq!(graph.on_behalf_of(id("boss")), [id("boss")].into());
+        assert_eq!(
+            graph.invitation_chain(id("intern")),
+            [id("deputy"), id("boss")]
+        );
+        assert_eq!(graph.sender(id("intern")), Some(id("boss")));
+        assert_eq!(graph.sender(id("deputy")), None);
+        assert!(graph.issues().is_empty());
+    }
+
+    #[test]
+    fn issues() {
+        let event = event(json!({
+            "ana": {
+                "@type": "Participant",
+                "delegatedTo": ["bo", "ghost"],
+                "invitedBy": "ana"
+            },
+            "bo": { "@type": "Participant", "invitedBy": "nobody" },
+            "cy": { "@type": "Participant", "delegatedFrom": ["bo"] }
+        }));
+        let graph = event.delegation_graph();
+
+        assert_eq!(
+            graph.issues(),
+            [
+                DelegationIssue::UnknownParticipant {
+                    participant: id("ana"),
+                    property: props::DELEGATED_TO,
+                    reference: id("ghost"),
+                },
+                DelegationIssue::UnknownParticipant {
+                    participant: id("bo"),
+                    property: props::INVITED_BY,
+                    reference: id("nobody"),
+                },
+                DelegationIssue::NotMutual {
+                    delegator: id("ana"),
+                    delegate: id("bo"),
+                },
+                DelegationIssue::NotMutual {
+                    delegator: id("bo"),
+                    delegate: id("cy"),
+                },
+            ]
+        );
+        assert_eq!(graph.delegates(id("bo")), [id("cy")].into());
+        assert!(graph.invitation_chain(id("ana")).is_empty());
+    }
+}
@@ -0,0 +1,352 @@
+//! Vendor-defined object types.
+//!
+//! RFC 8984 §3.3 allows vendors to define their own object types, such as an `acme.com:Booking`
+//! object held in a vendor property. The [`jscalendar_object!`](crate::jscalendar_object) macro
+//! defines such a type as a struct, and implements [`TryFromJson`] and [`IntoJson`] for it in the
+//! same way as for the object types of this crate:
+//!
+//! - each field is read from and written to the member with the given name, and a field whose
+//!   type is an [`Option`] may be absent;
+//! - the `@type` member is written, and rejected on input if it names a different type;
+//! - every other member is kept in the `vendor_properties` field, and written back unchanged;
+//! - an invalid member is reported as a [`DocumentError`] whose path starts with its name.
+//!
+//! ```
+//! # #[cfg(feature = "serde_json")]
+//! # {
+//! use jscalendar::json::{IntoJson, TryFromJson, UnsignedInt};
+//! use serde_json::{Value, json};
+//!
+//! jscalendar::jscalendar_object! {
+//!     /// A booking made through Acme.
+//!     "acme.com:Booking" => pub struct Booking<V> {
+//!         /// The booking reference.
+//!         "reference" => pub reference: String,
+//!         /// The number of booked seats.
+//!         "seats" => pub seats: Option<UnsignedInt>,
+//!     }
+//! }
+//!
+//! let input = json!({
+//!     "@type": "acme.com:Booking",
+//!     "reference": "X7K2",
+//!     "acme.com:desk": "north"
+//! });
+//! let booking = Booking::<Value>::try_from_json(input.clone()).unwrap();
+//! assert_eq!(booking.reference, "X7K2");
+//! assert_eq!(booking.seats, None);
+//! assert_eq!(booking.into_json(), input);
+//! # }
+//! ```
+//!
+//! Field types must implement [`TryFromJson`] and [`IntoJson`], and the non-type errors of their
+//! conversions must convert into a [`FieldValueError`], which is held by an
+//! [`ObjectFromJsonError::InvalidFieldValue`]. The errors of this crate already do; an error of
+//! another type can be converted with [`FieldValueError::other`].
+//!
+//! # Differences from the built-in object types
+//!
+//! The object types of this crate are defined with the `#[structible]` attribute, which gives
+//! them getters, setters, `vendor_property_iter`, and `insert_vendor_property`. A vendor type is
+//! instead a plain struct with public fields and a public `vendor_properties` map, and is defined
+//! with a declarative macro rather than an attribute, for two reasons:
+//!
+//! - the code generated by `#[structible]` refers to the `structible` crate by its absolute path,
+//!   and has no option to change it, so every crate using such an attribute would have to depend
+//!   on the same version of `structible` as this crate, which is not part of its public API;
+//! - an attribute needs a procedural macro crate, which would add a crate and a `syn` dependency
+//!   to the workspace for the sake of one macro, while the parsing and `@type` handling that
+//!   matter to vendors fit in a `macro_rules!` definition.
+//!
+//! [`TryFromJson`]: crate::json::TryFromJson
+//! [`IntoJson`]: crate::json::IntoJson
+//! [`DocumentError`]: crate::json::DocumentError
+//! [`ObjectFromJsonError::InvalidFieldValue`]: crate::model::object::ObjectFromJsonError::InvalidFieldValue
+//! [`FieldValueError`]: crate::model::object::FieldValueError
+//! [`FieldValueError::other`]: crate::model::object::FieldValueError::other
+
+/// Defines a vendor object type; see the [module documentation](crate::model::extension).
+///
+/// The struct is generic over its JSON value type `V`, which must be named `V`, and gains a
+/// `pub vendor_properties: BTreeMap<Box<str>, V>` field in addition to the given fields. It
+/// derives [`Debug`], [`Clone`], and [`PartialEq`].
+#[macro_export]
+macro_rules! jscalendar_object {
+    (
+        $(#[$attr:meta])*
+        $type_name:literal => $vis:vis struct $name:ident<V> {
+            $(
+                $(#[$field_attr:meta])*
+                $member:literal => $field_vis:vis $field:ident : $field_ty:ty
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq)]
+        $vis struct $name<V> {
+            $(
+                $(#[$field_attr])*
+                $field_vis $field: $field_ty,
+            )*
+            /// The vendor properties of this object.
+            pub vendor_properties: ::std::collections::BTreeMap<::std::boxed::Box<str>, V>,
+        }
+
+        impl<V> $crate::json::TryFromJson<V> for $name<V>
+        where
+            V: $crate::json::DestructibleJsonValue,
+        {
+            type Error = $crate::json::DocumentError<
+                $crate::json::TypeErrorOr<$crate::model::object::ObjectFromJsonError>,
+            >;
+
+            fn try_from_json(value: V) -> ::std::result::Result<Self, Self::Error> {
+                #[allow(unused_imports)]
+                use $crate::model::extension::__private::{ParseOptional as _, ParseRequired as _};
+
+                let object = $crate::model::extension::__private::object(value)?;
+                $(let mut $field = ::std::option::Option::None;)*
+                let mut vendor_properties = ::std::collections::BTreeMap::new();
+
+                for (key, value) in $crate::json::JsonObject::into_iter(object) {
+                    match <V::Object as $crate::json::JsonObject>::key_as_str(&key) {
+                        "@type" => $crate::model::extension::__private::check_type(value, $type_name)?,
+                        $($member => $field = ::std::option::Option::Some(value),)*
+                        _ => {
+                            let key = <V::Object as $crate::json::JsonObject>::key_into_string(key);
+                            vendor_properties.insert(key.into_boxed_str(), value);
+                        }
+                    }
+                }
+
+                ::std::result::Result::Ok(Self {
+                    $(
+                        $field: (&$crate::model::extension::__private::Member::<$field_ty>::new())
+                            .parse($field, $member)?,
+                    )*
+                    vendor_properties,
+                })
+            }
+        }
+
+        impl<V> $crate::json::IntoJson<V> for $name<V>
+        where
+            V: $crate::json::ConstructibleJsonValue,
+        {
+            fn into_json(self) -> V {
+                #[allow(unused_imports)]
+                use $crate::model::extension::__private::{EmitOptional as _, EmitRequired as _};
+
+                let mut object = <V::Object as $crate::json::JsonObject>::new();
+                $crate::json::JsonObject::insert(&mut object, "@type".into(), V::str($type_name));
+                $(
+                    let value = (&$crate::model::extension::__private::Member::<$field_ty>::new())
+                        .emit(self.$field);
+                    if let ::std::option::Option::Some(value) = value {
+                        $crate::json::JsonObject::insert(&mut object, $member.into(), value);
+                    }
+                )*
+                for (key, value) in self.vendor_properties {
+                    let key = ::std::string::String::from(key);
+                    $crate::json::JsonObject::insert(&mut object, key.into(), value);
+                }
+                V::object(object)
+            }
+        }
+    };
+}
+
+/// Support for [`jscalendar_object!`](crate::jscalendar_object), which is not part of the
+/// public API.
+///
+/// Whether a field is optional is decided by its type: method resolution prefers the
+/// `ParseOptional` and `EmitOptional` impls for a `Member<Option<T>>` to the `ParseRequired` and
+/// `EmitRequired` impls for a `&Member<T>`, since the latter need one more autoref.
+#[doc(hidden)]
+pub mod __private {
+    use std::marker::PhantomData;
+
+    use crate::{
+        json::{
+            ConstructibleJsonValue, DestructibleJsonValue, DocumentError, IntoJson, LiftTypeError,
+            TryFromJson, TypeErrorOr,
+            error::{ObjectError as ObjErr, field_error, missing},
+        },
+        model::object::{FieldValueError, ObjectFromJsonError},
+    };
+
+    /// A field of type `T`.
+    pub struct Member<T>(PhantomData<T>);
+
+    impl<T> Member<T> {
+        #[allow(clippy::new_without_default)]
+        pub fn new() -> Self {
+            Self(PhantomData)
+        }
+    }
+
+    pub trait ParseOptional<V, T> {
+        fn parse(&self, value: Option<V>, member: &'static str) -> Result<Option<T>, ObjErr>;
+    }
+
+    impl<V, T> ParseOptional<V, T> for Member<Option<T>>
+    where
+        V: DestructibleJsonValue,
+        T: TryFromJson<V>,
+        T::Error: LiftTypeError,
+        <T::Error as LiftTypeError>::Residual: Into<FieldValueError>,
+    {
+        fn parse(&self, value: Option<V>, member: &'static str) -> Result<Option<T>, ObjErr> {
+            value.map(|value| parse(value, member)).transpose()
+        }
+    }
+
+    pub trait ParseRequired<V, T> {
+        fn parse(&self, value: Option<V>, member: &'static str) -> Result<T, ObjErr>;
+    }
+
+    impl<V, T> ParseRequired<V, T> for &Member<T>
+    where
+        V: DestructibleJsonValue,
+        T: TryFromJson<V>,
+        T::Error: LiftTypeError,
+        <T::Error as LiftTypeError>::Residual: Into<FieldValueError>,
+    {
+        fn parse(&self, value: Option<V>, member: &'static str) -> Result<T, ObjErr> {
+            let value = value.ok_or_else(|| missing(member))?;
+            parse(value, member)
+        }
+    }
+
+    pub trait EmitOptional<V, T> {
+        fn emit(&self, value: Option<T>) -> Option<V>;
+    }
+
+    impl<V, T> EmitOptional<V, T> for Member<Option<T>>
+    where
+        V: ConstructibleJsonValue,
+        T: IntoJson<V>,
+    {
+        fn emit(&self, value: Option<T>) -> Option<V> {
+            value.map(IntoJson::into_json)
+        }
+    }
+
+    pub trait EmitRequired<V, T> {
+        fn emit(&self, value: T) -> Option<V>;
+    }
+
+    impl<V, T> EmitRequired<V, T> for &Member<T>
+    where
+        V: ConstructibleJsonValue,
+        T: IntoJson<V>,
+    {
+        fn emit(&self, value: T) -> Option<V> {
+            Some(value.into_json())
+        }
+    }
+
+    fn parse<V, T>(value: V, member: &'static str) -> Result<T, ObjErr>
+    where
+        V: DestructibleJsonValue,
+        T: TryFromJson<V>,
+        T::Error: LiftTypeError,
+        <T::Error as LiftTypeError>::Residual: Into<FieldValueError>,
+    {
+        T::try_from_json(value).map_err(|error| field_error(member, error))
+    }
+
+    pub fn object<V: DestructibleJsonValue>(value: V) -> Result<V::Object, ObjErr> {
+        value
+            .try_into_object()
+            .map_err(TypeErrorOr::from)
+            .map_err(DocumentError::root)
+    }
+
+    pub fn check_type<V: DestructibleJsonValue>(
+        value: V,
+        type_name: &'static str,
+    ) -> Result<(), ObjErr> {
+        let at_type = |error| DocumentError::root(error).prepend_static("@type");
+
+        let value = value
+            .try_into_string()
+            .map_err(|error| at_type(TypeErrorOr::from(error)))?;
+        match value.as_ref() == type_name {
+            true => Ok(()),
+            false => Err(at_type(TypeErrorOr::Other(
+                ObjectFromJsonError::InvalidFieldValue(FieldValueError::UnexpectedType {
+                    expected: type_name,
+                    found: value.as_ref().into(),
+                }),
+            ))),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use crate::{
+        json::{IntoJson, PathSegment, TryFromJson, UnsignedInt},
+        model::{object::PatchObject, string::Id},
+    };
+
+    crate::jscalendar_object! {
+        /// A booking made through Acme.
+        "acme.com:Booking" => pub(crate) struct Booking<V> {
+            "reference" => pub reference: String,
+            "seats" => pub seats: Option<UnsignedInt>,
+            "desk" => pub desk: Option<Box<Id>>,
+            "changes" => pub changes: Option<PatchObject<V>>,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let input = json!({
+            "@type": "acme.com:Booking",
+            "reference": "X7K2",
+            "seats": 2,
+            "changes": { "title": "Moved" },
+            "acme.com:note": ["a", "b"]
+        });
+
+        let booking = Booking::<Value>::try_from_json(input.clone()).unwrap();
+        assert_eq!(booking.reference, "X7K2");
+        assert_eq!(booking.seats.map(UnsignedInt::get), Some(2));
+        assert_eq!(booking.desk, None);
+        assert_eq!(booking.changes.as_ref().map(PatchObject::len), Some(1));
+        assert_eq!(booking.vendor_properties.len(), 1);
+        assert_eq!(booking.into_json(), input);
+
+        let minimal = Booking::<Value>::try_from_json(json!({ "reference": "A" })).unwrap();
+        assert_eq!(
+            minimal.into_json(),
+            json!({ "@type": "acme.com:Booking", "reference": "A" })
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let path = |input: Value| {
+            let error = Booking::<Value>::try_from_json(input).unwrap_err();
+            error.path().iter().cloned().collect::<Vec<_>>()
+        };
+
+        assert_eq!(path(json!({})), []);
+        assert_eq!(
+            path(json!({ "reference": 1 })),
+            [PathSegment::Static("reference")]
+        );
+        assert_eq!(
+            path(json!({ "reference": "A", "desk": "a/b" })),
+            [PathSegment::Static("desk")]
+        );
+        assert_eq!(
+            path(json!({ "@type": "Event", "reference": "A" })),
+            [PathSegment::Static("@type")]
+        );
+    }
+}
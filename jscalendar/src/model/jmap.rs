@@ -0,0 +1,134 @@
+//! The JMAP for Calendars extension properties of events and tasks.
+//!
+//! JMAP for Calendars adds properties to JSCalendar objects which control what participants may
+//! see and do, and servers such as Cyrus emit them in the JSCalendar they produce. Since RFC 8984
+//! does not define them, they are parsed as vendor properties; the accessors in this module read
+//! and write them with their proper types, so that callers need not handle the untyped values.
+//!
+//! ```
+//! # #[cfg(feature = "serde_json")]
+//! # {
+//! use jscalendar::prelude::*;
+//! use serde_json::{Value, json};
+//!
+//! let mut event = Event::<Value>::try_from_json(json!({
+//!     "@type": "Event",
+//!     "uid": "a",
+//!     "start": "2024-01-01T09:00:00",
+//!     "hideAttendees": true
+//! }))
+//! .unwrap();
+//! assert_eq!(event.hide_attendees(), Some(true));
+//!
+//! event.set_may_invite_others(Some(false));
+//! let json: Value = event.into_json();
+//! assert_eq!(json["mayInviteOthers"], false);
+//! # }
+//! ```
+
+use crate::{
+    json::{ConstructibleJsonValue, DestructibleJsonValue},
+    model::object::{Event, Task},
+};
+
+/// Whether a user may add themselves to the object as a participant.
+pub const MAY_INVITE_SELF: &str = "mayInviteSelf";
+/// Whether a participant may invite other participants to the object.
+pub const MAY_INVITE_OTHERS: &str = "mayInviteOthers";
+/// Whether the participants of the object are hidden from the other participants.
+pub const HIDE_ATTENDEES: &str = "hideAttendees";
+
+/// Implements the typed accessors for the extension properties of an object type.
+macro_rules! impl_jmap_properties {
+    ($ty:ident, $kind:literal) => {
+        impl<V: DestructibleJsonValue> $ty<V> {
+            #[doc = concat!("Returns the `mayInviteSelf` property of this ", $kind, ", or `None` if")]
+            /// it is absent or not a boolean.
+            pub fn may_invite_self(&self) -> Option<bool> {
+                self.jmap_bool(MAY_INVITE_SELF)
+            }
+
+            #[doc = concat!("Returns the `mayInviteOthers` property of this ", $kind, ", or `None`")]
+            /// if it is absent or not a boolean.
+            pub fn may_invite_others(&self) -> Option<bool> {
+                self.jmap_bool(MAY_INVITE_OTHERS)
+            }
+
+            #[doc = concat!("Returns the `hideAttendees` property of this ", $kind, ", or `None` if")]
+            /// it is absent or not a boolean.
+            pub fn hide_attendees(&self) -> Option<bool> {
+                self.jmap_bool(HIDE_ATTENDEES)
+            }
+
+            fn jmap_bool(&self, name: &str) -> Option<bool> {
+                self.vendor_property(name)?.try_as_bool().ok()
+            }
+        }
+
+        impl<V: ConstructibleJsonValue> $ty<V> {
+            #[doc = concat!("Sets the `mayInviteSelf` property of this ", $kind, ", removing it if")]
+            /// `value` is `None`.
+            pub fn set_may_invite_self(&mut self, value: Option<bool>) {
+                self.set_jmap_bool(MAY_INVITE_SELF, value);
+            }
+
+            #[doc = concat!("Sets the `mayInviteOthers` property of this ", $kind, ", removing it")]
+            /// if `value` is `None`.
+            pub fn set_may_invite_others(&mut self, value: Option<bool>) {
+                self.set_jmap_bool(MAY_INVITE_OTHERS, value);
+            }
+
+            #[doc = concat!("Sets the `hideAttendees` property of this ", $kind, ", removing it if")]
+            /// `value` is `None`.
+            pub fn set_hide_attendees(&mut self, value: Option<bool>) {
+                self.set_jmap_bool(HIDE_ATTENDEES, value);
+            }
+
+            fn set_jmap_bool(&mut self, name: &str, value: Option<bool>) {
+                let name = Box::<str>::from(name);
+                match value {
+                    Some(value) => self.insert_vendor_property(name, V::bool(value)),
+                    None => self.remove_vendor_property(&name),
+                };
+            }
+        }
+    };
+}
+
+impl_jmap_properties!(Event, "event");
+impl_jmap_properties!(Task, "task");
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::json::{IntoJson, TryFromJson};
+
+    #[test]
+    fn properties_round_trip() {
+        let mut task = Task::<Value>::try_from_json(json!({
+            "@type": "Task",
+            "uid": "a",
+            "mayInviteSelf": true,
+            "mayInviteOthers": "yes"
+        }))
+        .unwrap();
+        assert_eq!(task.may_invite_self(), Some(true));
+        assert_eq!(task.may_invite_others(), None, "wrong types are ignored");
+        assert_eq!(task.hide_attendees(), None);
+
+        task.set_may_invite_self(None);
+        task.set_may_invite_others(Some(false));
+        task.set_hide_attendees(Some(true));
+        assert_eq!(
+            task.into_json(),
+            json!({
+                "@type": "Task",
+                "uid": "a",
+                "mayInviteOthers": false,
+                "hideAttendees": true
+            })
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Resolution of the locations of events, tasks, and their participants.
+//!
+//! The `locationId` of a participant refers to an entry of the `locations` of its object only by
+//! its key (RFC 8984 §4.4.6), so rendering where each participant is requires a lookup that may
+//! fail. [`Event::participant_location`] and [`Task::participant_location`] perform this lookup,
+//! while [`Event::dangling_location_ids`] and [`Task::dangling_location_ids`] list the
+//! participants whose `locationId` has no matching entry.
+//!
+//! [`Event::primary_location`] and [`Task::primary_location`] choose the location to show for
+//! the object as a whole: the sole location if there is only one, and otherwise the location
+//! which the object starts at, according to its `relativeTo` property (RFC 8984 §4.2.5).
+
+use std::collections::HashMap;
+
+use crate::{
+    json::JsonValue,
+    model::{
+        object::{Event, Location, MaybeUnknown, Task},
+        set::Token,
+        string::Id,
+    },
+};
+
+type LocationMap<V> = HashMap<Box<Id>, MaybeUnknown<Location<V>, V>>;
+
+impl<V: JsonValue> Event<V> {
+    /// Returns the location of the participant with the given ID, or `None` if the participant
+    /// does not exist, has no `locationId`, or refers to a missing or unknown location.
+    pub fn participant_location(&self, participant: &Id) -> Option<&Location<V>> {
+        let id = self.participants()?.get(participant)?.location_id()?;
+        self.locations()?.get(id)?.as_known()
+    }
+
+    /// Returns the location at which this event takes place: the sole entry of `locations`, or
+    /// else the entry with the least ID whose `relativeTo` is `start`. Returns `None` if there is
+    /// no such location, or if it is of an unknown type.
+    pub fn primary_location(&self) -> Option<&Location<V>> {
+        primary_location(self.locations()?)
+    }
+
+    /// Returns the IDs of the participants whose `locationId` is not a key of `locations`, in
+    /// lexicographic order.
+    pub fn dangling_location_ids(&self) -> Vec<&Id> {
+        let participants = self.participants().into_iter().flatten();
+        dangling(
+            self.locations(),
+            participants.map(|(id, participant)| (&**id, participant.location_id())),
+        )
+    }
+}
+
+impl<V: JsonValue> Task<V> {
+    /// Returns the location of the participant with the given ID, or `None` if the participant
+    /// does not exist, has no `locationId`, or refers to a missing or unknown location.
+    pub fn participant_location(&self, participant: &Id) -> Option<&Location<V>> {
+        let id = self.participants()?.get(participant)?.location_id()?;
+        self.locations()?.get(id)?.as_known()
+    }
+
+    /// Returns the location at which this task takes place; see [`Event::primary_location`].
+    pub fn primary_location(&self) -> Option<&Location<V>> {
+        primary_location(self.locations()?)
+    }
+
+    /// Returns the IDs of the participants whose `locationId` is not a key of `locations`, in
+    /// lexicographic order.
+    pub fn dangling_location_ids(&self) -> Vec<&Id> {
+        let participants = self.participants().into_iter().flatten();
+        dangling(
+            self.locations(),
+            participants.map(|(id, participant)| (&**id, participant.location_id())),
+        )
+    }
+}
+
+fn primary_location<V: JsonValue>(locations: &LocationMap<V>) -> Option<&Location<V>> {
+    if locations.len() == 1 {
+        return locations.values().next()?.as_known();
+    }
+
+    let starts_at = |location: &Location<V>| match location.relative_to() {
+        Some(Token::Unknown(relative_to)) => relative_to.eq_ignore_ascii_case("start"),
+        _ => false,
+    };
+    locations
+        .iter()
+        .filter_map(|(id, location)| Some((id, location.as_known()?)))
+        .filter(|(_, location)| starts_at(location))
+        .min_by_key(|(id, _)| *id)
+        .map(|(_, location)| location)
+}
+
+fn dangling<'a, V: JsonValue + 'a>(
+    locations: Option<&LocationMap<V>>,
+    participants: impl Iterator<Item = (&'a Id, Option<&'a Box<Id>>)>,
+) -> Vec<&'a Id> {
+    let mut ids: Vec<_> = participants
+        .filter(|(_, location)| {
+            location.is_some_and(|location| {
+                !locations.is_some_and(|locations| locations.contains_key(location))
+            })
+        })
+        .map(|(id, _)| id)
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::json::TryFromJson;
+
+    fn event(locations: Value) -> Event<Value> {
+        Event::try_from_json(json!({
+            "@type": "Event",
+            "uid": "a",
+            "start": "2024-01-01T09:00:00",
+            "locations": locations,
+            "participants": {
+                "ana": { "@type": "Participant", "locationId": "office" },
+                "bo": { "@type": "Participant", "locationId": "home" },
+                "cy": { "@type": "Participant" }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn id(s: &str) -> &Id {
+        Id::new(s).unwrap()
+    }
+
+    #[test]
+    fn participant_location() {
+        let event = event(json!({
+            "office": { "@type": "Location", "name": "Office" },
+            "airport": { "@type": "Location", "name": "Airport", "relativeTo": "end" }
+        }));
+
+        let name = |participant| {
+            let location = event.participant_location(id(participant))?;
+            location.name().map(String::as_str)
+        };
+        assert_eq!(name("ana"), Some("Office"));
+        assert_eq!(name("bo"), None);
+        assert_eq!(name("cy"), None);
+        assert_eq!(name("dee"), None);
+        assert_eq!(event.dangling_location_ids(), [id("bo")]);
+    }
+
+    #[test]
+    fn primary_location() {
+        let sole = event(json!({ "home": { "@type": "Location", "relativeTo": "end" } }));
+        assert!(sole.primary_location().is_some());
+        assert_eq!(sole.dangling_location_ids(), [id("ana")]);
+
+        let several = event(json!({
+            "office": { "@type": "Location", "name": "Office", "relativeTo": "start" },
+            "airport": { "@type": "Location", "name": "Airport", "relativeTo": "end" },
+            "desk": { "@type": "Location", "name": "Desk", "relativeTo": "START" }
+        }));
+        let primary = several.primary_location().unwrap();
+        assert_eq!(primary.name().map(String::as_str), Some("Desk"));
+
+        let ambiguous = event(json!({
+            "office": { "@type": "Location" },
+            "home": { "@type": "Location" }
+        }));
+        assert!(ambiguous.primary_location().is_none());
+    }
+}
@@ -0,0 +1,280 @@
+//! Lossless round trips of JSCalendar objects through their typed representations.
+//!
+//! Serializing a parsed object regenerates its JSON from the typed representation, which
+//! normalizes the values that it parses, such as the case of tokens, and adds members such as a
+//! default `@type`, so that the output differs from the input even where nothing was changed. A
+//! sync proxy which modifies objects on their way through cannot afford to rewrite what it did not
+//! change, so a [`Lossless`] keeps the original JSON of an object alongside its typed
+//! representation.
+//!
+//! When a [`Lossless`] is serialized, its typed representation is compared with that of the
+//! original, and only the members which differ are written into the original JSON; all others
+//! keep their original values. Objects are merged member by member, so that unrecognized members
+//! of nested objects are kept as well, while any other value which differs is replaced as a
+//! whole. Members are written in the order of the original, followed by those which were added,
+//! so the original order is kept if the [`JsonObject`] type keeps the order in which its members
+//! were inserted, as that of `serde_json` does with its `preserve_order` feature, which the
+//! `preserve_order` feature of this crate enables.
+//!
+//! ```
+//! # #[cfg(feature = "serde_json")]
+//! # {
+//! use jscalendar::{
+//!     json::{IntoJson, TryFromJson},
+//!     model::{lossless::Lossless, object::Event},
+//! };
+//! use serde_json::{Value, json};
+//!
+//! let input = json!({
+//!     "uid": "a8df6573-0474-496d-8496-033ad45d7fea",
+//!     "start": "2020-01-15T13:00:00",
+//!     "status": "CONFIRMED",
+//!     "locations": {
+//!         "l1": { "@type": "Room", "building": "B" }
+//!     }
+//! });
+//! let mut event = Lossless::<Event<Value>>::try_from_json(input.clone()).unwrap();
+//! event.set_title("Team meeting".into());
+//!
+//! let mut expected = input;
+//! expected["title"] = json!("Team meeting");
+//! assert_eq!(event.into_json(), expected);
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    json::{ConstructibleJsonValue, DestructibleJsonValue, IntoJson, JsonObject, TryFromJson},
+    model::object::{Event, Group, Task},
+};
+
+/// An object type which a [`Lossless`] can hold, together with the type of the JSON values which
+/// it is parsed from.
+pub trait LosslessObject: Clone + TryFromJson<Self::Value> + IntoJson<Self::Value> {
+    /// The type of the JSON values which this object is parsed from and serialized into.
+    type Value: DestructibleJsonValue + ConstructibleJsonValue + Clone + PartialEq;
+}
+
+impl<V> LosslessObject for Event<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone + PartialEq,
+    V::Object: Clone,
+{
+    type Value = V;
+}
+
+impl<V> LosslessObject for Task<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone + PartialEq,
+    V::Object: Clone,
+{
+    type Value = V;
+}
+
+impl<V> LosslessObject for Group<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone + PartialEq,
+    V::Object: Clone,
+{
+    type Value = V;
+}
+
+/// A typed object together with the JSON which it was parsed from, which is serialized by
+/// merging the changes to the typed object into the original JSON.
+///
+/// A `Lossless` dereferences to the typed object, through which it is read and modified.
+#[derive(Debug, Clone)]
+pub struct Lossless<T: LosslessObject> {
+    typed: T,
+    original: T::Value,
+    /// The serialization of the typed object as it was parsed.
+    baseline: T::Value,
+}
+
+impl<T: LosslessObject> Lossless<T> {
+    /// Returns the typed object.
+    pub fn get(&self) -> &T {
+        &self.typed
+    }
+
+    /// Returns the typed object mutably.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.typed
+    }
+
+    /// Returns the JSON which the object was parsed from.
+    pub fn original(&self) -> &T::Value {
+        &self.original
+    }
+
+    /// Returns the typed object, discarding the original JSON.
+    pub fn into_inner(self) -> T {
+        self.typed
+    }
+}
+
+impl<T: LosslessObject> Deref for Lossless<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.typed
+    }
+}
+
+impl<T: LosslessObject> DerefMut for Lossless<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.typed
+    }
+}
+
+impl<T: LosslessObject> TryFromJson<T::Value> for Lossless<T> {
+    type Error = T::Error;
+
+    fn try_from_json(value: T::Value) -> Result<Self, Self::Error> {
+        let typed = T::try_from_json(value.clone())?;
+        let baseline = typed.clone().into_json();
+        Ok(Self {
+            typed,
+            original: value,
+            baseline,
+        })
+    }
+}
+
+impl<T: LosslessObject> IntoJson<T::Value> for Lossless<T> {
+    fn into_json(self) -> T::Value {
+        let current = self.typed.into_json();
+        merge(self.original, &self.baseline, current)
+    }
+}
+
+/// Merges the differences between `baseline` and `current` into `original`, where `baseline` is
+/// the typed serialization of `original`.
+fn merge<V>(original: V, baseline: &V, current: V) -> V
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + PartialEq,
+{
+    if current == *baseline {
+        return original;
+    }
+    let (Ok(base), true, true) = (
+        baseline.try_as_object(),
+        original.is_object(),
+        current.is_object(),
+    ) else {
+        return current;
+    };
+    let (Ok(original), Ok(current)) = (original.try_into_object(), current.try_into_object())
+    else {
+        unreachable!("both values are objects");
+    };
+
+    let mut order = Vec::with_capacity(current.len());
+    let mut current: HashMap<String, V> = current
+        .into_iter()
+        .map(|(key, value)| {
+            let key = <V::Object as JsonObject>::key_into_string(key);
+            order.push(key.clone());
+            (key, value)
+        })
+        .collect();
+
+    let mut merged = V::Object::with_capacity(original.len().max(current.len()));
+    for (key, value) in original.into_iter() {
+        let name = <V::Object as JsonObject>::key_as_str(&key);
+        match (base.get(name), current.remove(name)) {
+            (Some(base), Some(current)) => merged.insert(key, merge(value, base, current)),
+            (None, Some(current)) => merged.insert(key, current),
+            // the member was removed from the typed object
+            (Some(_), None) => {}
+            // the typed object does not represent the member
+            (None, None) => merged.insert(key, value),
+        }
+    }
+    for key in order {
+        let Some(value) = current.remove(&key) else {
+            continue;
+        };
+        // a member which the typed serialization adds unchanged, such as a default `@type`, is
+        // not written
+        if base.get(key.as_str()) != Some(&value) {
+            merged.insert(key.into(), value);
+        }
+    }
+    V::object(merged)
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::model::{
+        object::MaybeUnknown,
+        set::{EventStatus, Token},
+        string::Id,
+    };
+
+    fn input() -> Value {
+        json!({
+            "uid": "lossless",
+            "start": "2024-01-15T13:00:00",
+            "duration": "PT1H",
+            "status": "TENTATIVE",
+            "keywords": { "work": true },
+            "locations": {
+                "l1": { "@type": "Room", "building": "B", "floor": 3 },
+                "l2": { "name": "Office", "x-hint": { "nested": [1, 2] } }
+            },
+            "alerts": {
+                "a1": {
+                    "@type": "Alert",
+                    "trigger": { "@type": "OffsetTrigger", "offset": "-PT15M" }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn unchanged_round_trip() {
+        let event = Lossless::<Event<Value>>::try_from_json(input()).unwrap();
+        assert_eq!(event.original(), &input());
+        assert_eq!(event.into_json(), input());
+
+        // the typed serialization normalizes the input
+        let event = Event::<Value>::try_from_json(input()).unwrap();
+        assert_ne!(event.into_json(), input());
+    }
+
+    #[test]
+    fn changes_are_merged() {
+        let mut event = Lossless::<Event<Value>>::try_from_json(input()).unwrap();
+        event.set_title("Review".into());
+        event.remove_duration();
+        let l2 = Id::new("l2").unwrap();
+        let Some(MaybeUnknown::Known(location)) = event.locations_mut().unwrap().get_mut(l2) else {
+            panic!("l2 is a known location");
+        };
+        location.set_description("Second floor".into());
+
+        let mut expected = input();
+        let object = expected.as_object_mut().unwrap();
+        object.insert("title".into(), json!("Review"));
+        object.remove("duration");
+        expected["locations"]["l2"]["description"] = json!("Second floor");
+        assert_eq!(event.into_json(), expected);
+    }
+
+    #[test]
+    fn typed_values_replace_originals() {
+        let mut event = Lossless::<Event<Value>>::try_from_json(input()).unwrap();
+        event.set_status(Token::Known(EventStatus::Confirmed));
+        let output = event.into_json();
+        assert_eq!(output["status"], "confirmed");
+        assert_eq!(output["locations"], input()["locations"]);
+    }
+}
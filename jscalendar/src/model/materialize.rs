@@ -0,0 +1,683 @@
+//! Materialization of recurring events into standalone occurrences.
+//!
+//! Some systems cannot represent recurrence at all, so exporting a recurring [`Event`] to them
+//! requires a separate event for each occurrence. [`Event::materialize`] produces such events for
+//! the occurrences in a bounded range, collected in a [`Group`]: each one has the `recurrenceId`
+//! of its occurrence, carries any override from `recurrenceOverrides`, and has no recurrence
+//! properties of its own. [`Event::materialize_override`] produces the event for a single
+//! overridden occurrence in the same way.
+//!
+//! Conversely, [`assemble_series`] folds such standalone occurrences back into a recurring event,
+//! as needed when importing from sources which store each modified occurrence separately.
+//!
+//! This crate does not expand recurrence rules, so the occurrences generated by
+//! `recurrenceRules` must be supplied by the caller.
+
+use std::{
+    borrow::{Borrow, Cow},
+    collections::{BTreeMap, BTreeSet, HashSet},
+    ops::Range,
+};
+
+use thiserror::Error;
+
+use crate::{
+    json::{ConstructibleJsonValue, DestructibleJsonValue, IntoJson, JsonObject, TryFromJson},
+    model::{
+        object::{Event, Group, PatchObject, TaskOrEvent, escape_segment},
+        props,
+        time::{DateTime, Local},
+    },
+};
+
+/// A summary of the conversions made by [`Event::materialize`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MaterializeReport {
+    /// The number of events in the group.
+    pub materialized: usize,
+    /// The recurrence IDs of the occurrences in the range which were excluded by an override.
+    pub excluded: Vec<DateTime<Local>>,
+    /// The recurrence IDs of the occurrences whose override could not be applied, or which did
+    /// not produce a valid event. These occurrences are omitted from the group.
+    pub invalid: Vec<DateTime<Local>>,
+    /// The number of occurrences in the range which were omitted because the limit was reached.
+    pub truncated: usize,
+}
+
+impl<V> Event<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+    V::Object: Clone,
+{
+    /// Returns a group containing a standalone event for each occurrence whose recurrence ID lies
+    /// in `range`, in chronological order, together with a report of the conversion.
+    ///
+    /// If the event has `recurrenceRules`, `resolver` is called once to obtain the start times of
+    /// the occurrences they generate, less those generated by `excludedRecurrenceRules`; it only
+    /// needs to return the occurrences in the given range. Otherwise, the only occurrence is the
+    /// event itself. In both cases, the keys of `recurrenceOverrides` add further occurrences.
+    ///
+    /// Each event is a copy of this one with `start` and `recurrenceId` set to its occurrence,
+    /// `recurrenceIdTimeZone` set to the `timeZone` of this event, the recurrence properties
+    /// removed, and the matching override (if any) applied. At most `limit` events are produced.
+    /// The group has the `uid` and `title` of this event.
+    pub fn materialize<F, I>(
+        &self,
+        range: Range<DateTime<Local>>,
+        limit: usize,
+        resolver: F,
+    ) -> (Group<V>, MaterializeReport)
+    where
+        F: FnOnce(&Self, &Range<DateTime<Local>>) -> I,
+        I: IntoIterator<Item = DateTime<Local>>,
+    {
+        let mut occurrences = BTreeSet::new();
+        if self.recurrence_rules().is_some() {
+            occurrences.extend(resolver(self, &range));
+        } else {
+            occurrences.insert(*self.start());
+        }
+        if let Some(overrides) = self.recurrence_overrides() {
+            occurrences.extend(overrides.keys().copied());
+        }
+        occurrences.retain(|occurrence| range.contains(occurrence));
+
+        let base = self.occurrence_template();
+        let mut report = MaterializeReport::default();
+        let mut entries = Vec::new();
+        for recurrence_id in occurrences {
+            if entries.len() == limit {
+                report.truncated += 1;
+                continue;
+            }
+
+            let patch = self
+                .recurrence_overrides()
+                .and_then(|overrides| overrides.get(&recurrence_id));
+            match self.occurrence(base.clone(), recurrence_id, patch) {
+                Ok(event) => entries.push(TaskOrEvent::Event(event)),
+                Err(MaterializeError::Excluded(_)) => report.excluded.push(recurrence_id),
+                Err(_) => report.invalid.push(recurrence_id),
+            }
+        }
+
+        report.materialized = entries.len();
+        let mut group = Group::new(entries, self.uid().clone());
+        if let Some(title) = self.title() {
+            group.set_title(title.clone());
+        }
+        (group, report)
+    }
+
+    /// Returns the standalone occurrence of this event for the override with the given
+    /// `recurrence_id`, as delivered by iTIP when a single occurrence is modified.
+    ///
+    /// The occurrence is built in the same way as by [`Event::materialize`]: it is a copy of this
+    /// event with `start`, `recurrenceId`, and `recurrenceIdTimeZone` set, the recurrence
+    /// properties removed, and the override applied.
+    pub fn materialize_override(
+        &self,
+        recurrence_id: DateTime<Local>,
+    ) -> Result<Event<V>, MaterializeError> {
+        let patch = self
+            .recurrence_overrides()
+            .and_then(|overrides| overrides.get(&recurrence_id))
+            .ok_or(MaterializeError::MissingOverride(recurrence_id))?;
+        self.occurrence(self.occurrence_template(), recurrence_id, Some(patch))
+    }
+
+    /// Returns the occurrence with the given `recurrence_id`, built from the `base` members
+    /// returned by [`Event::occurrence_template`] and the override `patch`.
+    fn occurrence(
+        &self,
+        mut base: V::Object,
+        recurrence_id: DateTime<Local>,
+        patch: Option<&PatchObject<V>>,
+    ) -> Result<Event<V>, MaterializeError> {
+        let id: V = recurrence_id.into_json();
+        base.insert(props::START.into(), id.clone());
+        base.insert(props::RECURRENCE_ID.into(), id);
+        if let Some(time_zone) = self.time_zone() {
+            base.insert(props::RECURRENCE_ID_TIME_ZONE.into(), V::str(time_zone));
+        }
+
+        let object = match patch {
+            Some(patch) => apply_patch(V::object(base), patch)
+                .ok_or(MaterializeError::InvalidPatch(recurrence_id))?,
+            None => V::object(base),
+        };
+
+        let excluded = object
+            .try_as_object()
+            .ok()
+            .and_then(|object| object.get(props::EXCLUDED))
+            .and_then(|excluded| excluded.try_as_bool().ok());
+        if excluded == Some(true) {
+            return Err(MaterializeError::Excluded(recurrence_id));
+        }
+
+        Event::try_from_json(object).map_err(|_| MaterializeError::InvalidEvent(recurrence_id))
+    }
+
+    /// Returns the JSON members of this event without its recurrence properties.
+    fn occurrence_template(&self) -> V::Object {
+        let object = self
+            .clone()
+            .into_json()
+            .try_into_object()
+            .expect("events serialize to JSON objects");
+
+        let mut template = V::Object::with_capacity(object.len());
+        for (key, value) in object.into_iter() {
+            let name: &str = key.borrow();
+            if ![
+                props::RECURRENCE_RULES,
+                props::EXCLUDED_RECURRENCE_RULES,
+                props::RECURRENCE_OVERRIDES,
+                props::EXCLUDED,
+            ]
+            .contains(&name)
+            {
+                template.insert(key, value);
+            }
+        }
+        template
+    }
+}
+
+/// An error produced by [`Event::materialize_override`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum MaterializeError {
+    /// The event has no override for the given `recurrenceId`.
+    #[error("the event has no override for the recurrenceId {0}")]
+    MissingOverride(DateTime<Local>),
+    /// The override for the given `recurrenceId` excludes its occurrence.
+    #[error("the override for the recurrenceId {0} excludes its occurrence")]
+    Excluded(DateTime<Local>),
+    /// The override for the given `recurrenceId` patches a member of a missing or non-object
+    /// value.
+    #[error("the override for the recurrenceId {0} cannot be applied")]
+    InvalidPatch(DateTime<Local>),
+    /// Applying the override for the given `recurrenceId` does not produce a valid event.
+    #[error("the occurrence with the recurrenceId {0} is not a valid event")]
+    InvalidEvent(DateTime<Local>),
+}
+
+/// An error produced by [`assemble_series`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum AssembleError {
+    /// None of the objects lacks a `recurrenceId`.
+    #[error("none of the objects is a master object without a recurrenceId")]
+    MissingMaster,
+    /// More than one of the objects lacks a `recurrenceId`.
+    #[error("more than one object lacks a recurrenceId")]
+    MultipleMasters,
+    /// The instance with the given `recurrenceId` has a different `uid` from the master.
+    #[error("the instance with the recurrenceId {0} has a different uid from the master")]
+    UidMismatch(DateTime<Local>),
+    /// More than one instance has the given `recurrenceId`.
+    #[error("more than one instance has the recurrenceId {0}")]
+    DuplicateInstance(DateTime<Local>),
+}
+
+/// The properties which must not be patched by `recurrenceOverrides` (RFC 8984 §4.3.5), and
+/// the recurrence properties which only apply to the master object.
+const UNPATCHABLE_PROPERTIES: &[&str] = &[
+    props::UID,
+    props::RELATED_TO,
+    props::PROD_ID,
+    props::METHOD,
+    props::RECURRENCE_ID,
+    props::RECURRENCE_ID_TIME_ZONE,
+    props::RECURRENCE_RULES,
+    props::EXCLUDED_RECURRENCE_RULES,
+    props::RECURRENCE_OVERRIDES,
+    props::REPLY_TO,
+    props::EXCLUDED,
+];
+
+/// Folds standalone instances of a recurring event back into their master, which is the
+/// inverse of [`Event::materialize`].
+///
+/// Exactly one of `objects` must lack a `recurrenceId`; it is the master, and is returned with
+/// an entry in `recurrenceOverrides` for each of the other objects, replacing any existing
+/// override for the same occurrence. An instance with `excluded` set to `true` becomes an
+/// exclusion; otherwise, its override patches the top-level properties in which it differs from
+/// the master, ignoring those which overrides must not patch. Set-valued properties such as
+/// `keywords` are patched member by member, so that an override adding a keyword has a patch
+/// like `keywords/Work` rather than replacing the whole set. An instance which does not differ
+/// from the master still produces an empty override, since it may not be generated by the
+/// recurrence rules.
+pub fn assemble_series<V>(
+    objects: impl IntoIterator<Item = Event<V>>,
+) -> Result<Event<V>, AssembleError>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone + PartialEq,
+    V::Object: Clone,
+{
+    let mut master = None;
+    let mut instances = Vec::new();
+    for object in objects {
+        match object.recurrence_id() {
+            Some(&recurrence_id) => instances.push((recurrence_id, object)),
+            None if master.is_some() => return Err(AssembleError::MultipleMasters),
+            None => master = Some(object),
+        }
+    }
+
+    let mut master = master.ok_or(AssembleError::MissingMaster)?;
+    let template = patchable_members(V::object(master.occurrence_template()));
+    let mut overrides = master.remove_recurrence_overrides().unwrap_or_default();
+    let mut seen = HashSet::new();
+
+    for (recurrence_id, instance) in instances {
+        if instance.uid() != master.uid() {
+            return Err(AssembleError::UidMismatch(recurrence_id));
+        }
+        if !seen.insert(recurrence_id) {
+            return Err(AssembleError::DuplicateInstance(recurrence_id));
+        }
+
+        let mut patch = V::Object::new();
+        if instance.excluded_or_default() {
+            patch.insert(props::EXCLUDED.into(), V::bool(true));
+        } else {
+            let mut template = template.clone();
+            template.insert(props::START.into(), recurrence_id.into_json());
+            let instance = patchable_members(instance.into_json());
+
+            for (key, value) in &instance {
+                match template.get(key) {
+                    Some(old) if old == value => {}
+                    Some(old) if is_set(old) && is_set(value) => {
+                        diff_set(&escape_segment(key), old, value, &mut patch);
+                    }
+                    _ => patch.insert(escape_segment(key).into(), value.clone()),
+                }
+            }
+            for key in template.keys() {
+                if !instance.contains_key(key) {
+                    patch.insert(escape_segment(key).into(), V::null());
+                }
+            }
+        }
+
+        let patch = PatchObject::try_from_json(V::object(patch))
+            .expect("escaped member names are valid implicit JSON pointers");
+        overrides.insert(recurrence_id, patch);
+    }
+
+    if !overrides.is_empty() {
+        master.set_recurrence_overrides(overrides);
+    }
+    Ok(master)
+}
+
+/// Returns `true` iff `value` is a set in the JSCalendar sense, an object whose values are all
+/// `true` (RFC 8984 §1.4.4).
+fn is_set<V: DestructibleJsonValue>(value: &V) -> bool {
+    value.try_as_object().is_ok_and(|object| {
+        object
+            .iter()
+            .all(|(_, value)| value.try_as_bool() == Ok(true))
+    })
+}
+
+/// Adds a patch to `patch` for each member added to or removed from the set at `pointer`.
+fn diff_set<V>(pointer: &str, old: &V, new: &V, patch: &mut V::Object)
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue,
+{
+    let (Ok(old), Ok(new)) = (old.try_as_object(), new.try_as_object()) else {
+        return;
+    };
+
+    for (member, _) in new.iter() {
+        let member: &str = member.borrow();
+        if !old.contains_key(member) {
+            let pointer = format!("{pointer}/{}", escape_segment(member));
+            patch.insert(pointer.into(), V::bool(true));
+        }
+    }
+    for (member, _) in old.iter() {
+        let member: &str = member.borrow();
+        if !new.contains_key(member) {
+            let pointer = format!("{pointer}/{}", escape_segment(member));
+            patch.insert(pointer.into(), V::null());
+        }
+    }
+}
+
+/// Returns the members of a serialized event which may be patched by an override.
+fn patchable_members<V: DestructibleJsonValue>(value: V) -> BTreeMap<String, V> {
+    value
+        .try_into_object()
+        .expect("events serialize to JSON objects")
+        .into_iter()
+        .map(|(key, value)| (<V::Object as JsonObject>::key_into_string(key), value))
+        .filter(|(key, _)| !UNPATCHABLE_PROPERTIES.contains(&key.as_str()))
+        .collect()
+}
+
+/// Applies each patch in `patch` to `target`, or returns `None` if a patch refers to a missing
+/// parent or to a member of a non-object value (RFC 8984 §1.4.9).
+fn apply_patch<V>(target: V, patch: &PatchObject<V>) -> Option<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    patch.iter().try_fold(target, |target, (pointer, value)| {
+        let segments: Vec<_> = pointer.segments().collect();
+        set_member(target, &segments, value)
+    })
+}
+
+/// Sets the member of `target` at the path `segments` to `value`, removing it if `value` is
+/// `null`.
+fn set_member<V>(target: V, segments: &[Cow<'_, str>], value: &V) -> Option<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    let (name, rest) = segments.split_first()?;
+    let object = target.try_into_object().ok()?;
+
+    let mut result = V::Object::with_capacity(object.len() + 1);
+    let mut found = false;
+    for (key, member) in object.into_iter() {
+        if Borrow::<str>::borrow(&key) != name.as_ref() {
+            result.insert(key, member);
+        } else if !rest.is_empty() {
+            found = true;
+            result.insert(key, set_member(member, rest, value)?);
+        } else {
+            found = true;
+            if !value.is_null() {
+                result.insert(key, value.clone());
+            }
+        }
+    }
+
+    if !found {
+        if !rest.is_empty() {
+            return None;
+        }
+        if !value.is_null() {
+            result.insert(name.as_ref().into(), value.clone());
+        }
+    }
+
+    Some(V::object(result))
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::model::string::Uid;
+
+    fn event() -> Event<Value> {
+        Event::try_from_json(json!({
+            "@type": "Event",
+            "uid": "standup@example.com",
+            "title": "Standup",
+            "start": "2024-01-01T09:00:00",
+            "duration": "PT15M",
+            "timeZone": "Europe/Berlin",
+            "recurrenceRules": [{ "@type": "RecurrenceRule", "frequency": "daily" }],
+            "recurrenceOverrides": {
+                "2024-01-02T09:00:00": { "title": "Planning", "duration": "PT1H" },
+                "2024-01-03T09:00:00": { "excluded": true },
+                "2024-01-04T09:00:00": { "locations/missing/name": "Room 1" },
+                "2024-01-10T12:00:00": { "title": "Extra" }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn datetime(s: &str) -> DateTime<Local> {
+        DateTime::try_from_json(Value::from(s)).unwrap()
+    }
+
+    /// Expands the daily rule of `event` over the first two weeks of January.
+    fn daily(_: &Event<Value>, range: &Range<DateTime<Local>>) -> Vec<DateTime<Local>> {
+        (1..=14)
+            .map(|day| datetime(&format!("2024-01-{day:02}T09:00:00")))
+            .filter(|occurrence| range.contains(occurrence))
+            .collect()
+    }
+
+    #[test]
+    fn materialize_applies_overrides() {
+        let event = event();
+        let range = datetime("2024-01-01T00:00:00")..datetime("2024-01-06T00:00:00");
+        let (group, report) = event.materialize(range, 100, daily);
+
+        assert_eq!(group.uid(), event.uid());
+        assert_eq!(group.title().map(String::as_str), Some("Standup"));
+        assert_eq!(
+            report,
+            MaterializeReport {
+                materialized: 3,
+                excluded: vec![datetime("2024-01-03T09:00:00")],
+                invalid: vec![datetime("2024-01-04T09:00:00")],
+                truncated: 0,
+            }
+        );
+
+        let entries: Vec<Value> = group
+            .entries()
+            .iter()
+            .map(|entry| match entry {
+                TaskOrEvent::Event(event) => event.clone().into_json(),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(entries[0]["recurrenceId"], "2024-01-01T09:00:00");
+        assert_eq!(entries[0]["recurrenceIdTimeZone"], "Europe/Berlin");
+        assert_eq!(entries[0]["title"], "Standup");
+        assert_eq!(entries[1]["start"], "2024-01-02T09:00:00");
+        assert_eq!(entries[1]["title"], "Planning");
+        assert_eq!(entries[1]["duration"], "PT1H");
+        assert_eq!(entries[2]["start"], "2024-01-05T09:00:00");
+        for entry in &entries {
+            assert!(entry.get("recurrenceRules").is_none());
+            assert!(entry.get("recurrenceOverrides").is_none());
+        }
+    }
+
+    #[test]
+    fn materialize_adds_override_occurrences_and_truncates() {
+        let event = event();
+        let range = datetime("2024-01-05T00:00:00")..datetime("2024-01-20T00:00:00");
+        let (group, report) = event.materialize(range, 6, daily);
+
+        // 5th to 10th daily, plus the extra occurrence at noon on the 10th
+        assert_eq!(report.materialized, 6);
+        assert_eq!(report.truncated, 5);
+        let Some(TaskOrEvent::Event(last)) = group.entries().last() else {
+            unreachable!()
+        };
+        assert_eq!(last.recurrence_id(), Some(&datetime("2024-01-10T09:00:00")));
+    }
+
+    #[test]
+    fn materialize_single_event() {
+        let mut event = event();
+        event.remove_recurrence_rules();
+        event.remove_recurrence_overrides();
+        let range = datetime("2024-01-01T00:00:00")..datetime("2024-01-02T00:00:00");
+        let (group, report) =
+            event.materialize(range, 10, |_, _| -> Vec<DateTime<Local>> { unreachable!() });
+        assert_eq!(report.materialized, 1);
+        assert_eq!(group.entries().len(), 1);
+
+        let range = datetime("2024-02-01T00:00:00")..datetime("2024-03-01T00:00:00");
+        let (group, _) = event.materialize(range, 10, |_, _| Vec::new());
+        assert!(group.entries().is_empty());
+    }
+
+    #[test]
+    fn materialize_override() {
+        let event = event();
+        let instance = event
+            .materialize_override(datetime("2024-01-02T09:00:00"))
+            .unwrap();
+        let json: Value = instance.into_json();
+        assert_eq!(
+            json,
+            json!({
+                "@type": "Event",
+                "uid": "standup@example.com",
+                "title": "Planning",
+                "start": "2024-01-02T09:00:00",
+                "duration": "PT1H",
+                "timeZone": "Europe/Berlin",
+                "recurrenceId": "2024-01-02T09:00:00",
+                "recurrenceIdTimeZone": "Europe/Berlin"
+            })
+        );
+
+        for (recurrence_id, error) in [
+            (
+                "2024-01-05T09:00:00",
+                MaterializeError::MissingOverride as fn(_) -> _,
+            ),
+            ("2024-01-03T09:00:00", MaterializeError::Excluded),
+            ("2024-01-04T09:00:00", MaterializeError::InvalidPatch),
+        ] {
+            let recurrence_id = datetime(recurrence_id);
+            assert_eq!(
+                event.materialize_override(recurrence_id),
+                Err(error(recurrence_id))
+            );
+        }
+    }
+
+    #[test]
+    fn assemble_series_inverts_materialize() {
+        let mut master = event();
+        master.set_recurrence_overrides(HashMap::from([
+            (
+                datetime("2024-01-02T09:00:00"),
+                PatchObject::try_from_json(json!({ "title": "Planning", "duration": "PT1H" }))
+                    .unwrap(),
+            ),
+            (
+                datetime("2024-01-03T09:00:00"),
+                PatchObject::try_from_json(json!({ "excluded": true })).unwrap(),
+            ),
+        ]));
+        let range = datetime("2024-01-01T00:00:00")..datetime("2024-01-04T00:00:00");
+        let (group, _) = master.materialize(range, 10, daily);
+
+        let mut excluded = event();
+        excluded.remove_recurrence_rules();
+        excluded.remove_recurrence_overrides();
+        excluded.set_recurrence_id(datetime("2024-01-03T09:00:00"));
+        excluded.set_excluded(true);
+
+        let mut series = master.clone();
+        series.remove_recurrence_overrides();
+        let instances = group.entries().iter().map(|entry| match entry {
+            TaskOrEvent::Event(event) => event.clone(),
+            _ => unreachable!(),
+        });
+        let assembled = assemble_series(instances.chain([excluded, series])).unwrap();
+
+        let json: Value = assembled.into_json();
+        assert_eq!(
+            json["recurrenceOverrides"],
+            json!({
+                "2024-01-01T09:00:00": {},
+                "2024-01-02T09:00:00": { "title": "Planning", "duration": "PT1H" },
+                "2024-01-03T09:00:00": { "excluded": true }
+            })
+        );
+        assert_eq!(json["title"], "Standup");
+        assert!(json.get("recurrenceId").is_none());
+    }
+
+    #[test]
+    fn assemble_series_errors() {
+        let mut master = event();
+        master.remove_recurrence_overrides();
+        let mut instance = master.clone();
+        instance.remove_recurrence_rules();
+        instance.set_recurrence_id(datetime("2024-01-02T09:00:00"));
+        instance.set_start(datetime("2024-01-02T09:00:00"));
+        instance.remove_title();
+
+        let assembled = assemble_series([master.clone(), instance.clone()]).unwrap();
+        let json: Value = assembled.into_json();
+        assert_eq!(
+            json["recurrenceOverrides"],
+            json!({ "2024-01-02T09:00:00": { "title": null } })
+        );
+
+        assert_eq!(
+            assemble_series([instance.clone()]),
+            Err(AssembleError::MissingMaster)
+        );
+        assert_eq!(
+            assemble_series([master.clone(), master.clone()]),
+            Err(AssembleError::MultipleMasters)
+        );
+        assert_eq!(
+            assemble_series([master.clone(), instance.clone(), instance.clone()]),
+            Err(AssembleError::DuplicateInstance(datetime(
+                "2024-01-02T09:00:00"
+            )))
+        );
+
+        let mut other = instance;
+        other.set_uid(Uid::new("other@example.com").unwrap().into());
+        assert_eq!(
+            assemble_series([master, other]),
+            Err(AssembleError::UidMismatch(datetime("2024-01-02T09:00:00")))
+        );
+    }
+
+    #[test]
+    fn set_members_are_patched_individually() {
+        let mut master = event();
+        master.remove_recurrence_overrides();
+        let mut json: Value = master.into_json();
+        json["keywords"] = json!({ "Home": true, "Daily": true });
+        let master = Event::try_from_json(json).unwrap();
+
+        let mut instance: Value = master.clone().into_json();
+        instance["recurrenceId"] = json!("2024-01-02T09:00:00");
+        instance["start"] = json!("2024-01-02T09:00:00");
+        instance["keywords"] = json!({ "Daily": true, "Work": true });
+        instance.as_object_mut().unwrap().remove("recurrenceRules");
+        let instance = Event::try_from_json(instance).unwrap();
+
+        let series = assemble_series([master, instance]).unwrap();
+        let json: Value = series.clone().into_json();
+        assert_eq!(
+            json["recurrenceOverrides"]["2024-01-02T09:00:00"],
+            json!({ "keywords/Work": true, "keywords/Home": null })
+        );
+
+        let range = datetime("2024-01-02T00:00:00")..datetime("2024-01-03T00:00:00");
+        let (group, report) = series.materialize(range, 10, daily);
+        assert_eq!(report.materialized, 1);
+        let Some(TaskOrEvent::Event(occurrence)) = group.entries().first() else {
+            unreachable!()
+        };
+        let json: Value = occurrence.clone().into_json();
+        assert_eq!(json["keywords"], json!({ "Daily": true, "Work": true }));
+
+        // a member of a set can only be patched if the set exists
+        let mut series = event();
+        series.set_recurrence_overrides(HashMap::from([(
+            datetime("2024-01-02T09:00:00"),
+            PatchObject::try_from_json(json!({ "keywords/Work": true })).unwrap(),
+        )]));
+        let range = datetime("2024-01-02T00:00:00")..datetime("2024-01-03T00:00:00");
+        let (_, report) = series.materialize(range, 10, daily);
+        assert_eq!(report.invalid, [datetime("2024-01-02T09:00:00")]);
+    }
+}
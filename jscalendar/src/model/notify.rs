@@ -0,0 +1,485 @@
+//! Notification schedules for alerts.
+//!
+//! [`Event::alert_times`] and [`Task::alert_times`] resolve the triggers of the alerts of an
+//! object into the times at which they fire, and a [`NotificationPlanner`] turns those times into
+//! a schedule of notifications according to the quiet hours and quiet days of the user, which
+//! RFC 8984 leaves to the client.
+//!
+//! Quiet hours and days are evaluated on the wall clock of the user, which is given by a function
+//! returning the offset from UTC of their time zone at a given instant.
+
+use std::collections::HashMap;
+
+use crate::{
+    json::JsonValue,
+    model::{
+        object::{Alert, Event, MaybeUnknown, Task, Trigger, local_to_utc},
+        set::{AlertRelativeTo, Token},
+        string::Id,
+        time::{DateTime, Hour, Local, Minute, Second, Sign, Time, Utc, UtcOffset, Weekday},
+    },
+};
+
+type AlertMap<V> = HashMap<Box<Id>, MaybeUnknown<Alert<V>, V>>;
+
+impl<V: JsonValue> Event<V> {
+    /// Returns the IDs of the alerts of this event with the times at which they fire, in order.
+    ///
+    /// Offset triggers are resolved against the `start` of the event, or its end if their
+    /// `relativeTo` is `end`, and alerts with unrecognized triggers or which have been
+    /// acknowledged at or after their trigger time are omitted. `offset` returns the offset from
+    /// UTC of the `timeZone` of the event at the given local time, and a floating event, or one
+    /// whose time zone cannot be resolved, is treated as though it were in UTC.
+    ///
+    /// Only this event is considered; the alerts of each occurrence of a recurring event can be
+    /// found by applying this method to the occurrences produced by [`Event::materialize`].
+    pub fn alert_times(
+        &self,
+        mut offset: impl FnMut(&str, DateTime<Local>) -> Option<UtcOffset>,
+    ) -> Vec<(&Id, DateTime<Utc>)> {
+        let time_zone = self.time_zone().map(String::as_str);
+        let start = *self.start();
+        let end = self
+            .duration()
+            .and_then(|duration| start.checked_add(*duration))
+            .unwrap_or(start);
+        let mut to_utc = |local| to_utc(local, time_zone, &mut offset);
+        alert_times(self.alerts(), to_utc(start), to_utc(end))
+    }
+}
+
+impl<V: JsonValue> Task<V> {
+    /// Returns the IDs of the alerts of this task with the times at which they fire, in order;
+    /// see [`Event::alert_times`].
+    ///
+    /// The start of a task is its `start` property and its end is its `due` property, and an
+    /// offset trigger relative to a property which the task does not have is omitted.
+    pub fn alert_times(
+        &self,
+        mut offset: impl FnMut(&str, DateTime<Local>) -> Option<UtcOffset>,
+    ) -> Vec<(&Id, DateTime<Utc>)> {
+        let time_zone = self.time_zone().map(String::as_str);
+        let mut to_utc = |local: Option<&DateTime<Local>>| {
+            local.and_then(|local| to_utc(*local, time_zone, &mut offset))
+        };
+        alert_times(self.alerts(), to_utc(self.start()), to_utc(self.due()))
+    }
+}
+
+/// Resolves the triggers of `alerts` against the given start and end times.
+fn alert_times<V: JsonValue>(
+    alerts: Option<&AlertMap<V>>,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+) -> Vec<(&Id, DateTime<Utc>)> {
+    let mut times: Vec<_> = alerts
+        .into_iter()
+        .flatten()
+        .filter_map(|(id, alert)| {
+            let MaybeUnknown::Known(alert) = alert else {
+                return None;
+            };
+            let time = match alert.trigger() {
+                Trigger::Offset(trigger) => {
+                    let anchor = match trigger.relative_to() {
+                        Some(Token::Known(AlertRelativeTo::End)) => end,
+                        Some(Token::Known(AlertRelativeTo::Start)) | None => start,
+                        Some(Token::Unknown(_)) => None,
+                    };
+                    anchor?.checked_add_signed(*trigger.offset())?
+                }
+                Trigger::Absolute(trigger) => *trigger.when(),
+                Trigger::Unknown(_) => return None,
+            };
+            let acknowledged = alert.acknowledged().is_some_and(|ack| *ack >= time);
+            (!acknowledged).then_some((&**id, time))
+        })
+        .collect();
+    times.sort_by(|(a_id, a_time), (b_id, b_time)| (a_time, a_id).cmp(&(b_time, b_id)));
+    times
+}
+
+/// Converts a local date-time in `time_zone` into UTC, treating it as UTC if it is floating or
+/// its offset cannot be resolved.
+fn to_utc(
+    local: DateTime<Local>,
+    time_zone: Option<&str>,
+    offset: impl FnOnce(&str, DateTime<Local>) -> Option<UtcOffset>,
+) -> Option<DateTime<Utc>> {
+    match time_zone.and_then(|time_zone| offset(time_zone, local)) {
+        Some(offset) => local_to_utc(local, offset),
+        None => Some(DateTime {
+            date: local.date,
+            time: local.time,
+            marker: Utc,
+        }),
+    }
+}
+
+/// A daily period during which notifications are not delivered normally.
+///
+/// The period begins at `start` and ends before `end` on the wall clock of the user. If `end` is
+/// not after `start`, the period spans midnight, so that quiet hours from 22:00 to 07:00 cover
+/// both the late evening and the early morning of every day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct QuietHours {
+    /// The time at which the quiet hours begin.
+    pub start: Time,
+    /// The time at which the quiet hours end.
+    pub end: Time,
+}
+
+impl QuietHours {
+    /// Returns `true` iff `time` falls within these quiet hours.
+    pub fn contains(&self, time: Time) -> bool {
+        if self.start < self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// The handling of a notification which would be delivered during quiet hours or on a quiet day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum QuietPolicy {
+    /// Deliver the notification at the next time which is not quiet.
+    #[default]
+    Defer,
+    /// Do not deliver the notification.
+    Drop,
+    /// Deliver the notification on time, but without sound or other interruptions.
+    Silent,
+}
+
+/// A notification scheduled by a [`NotificationPlanner`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification<K> {
+    /// The key of the alert, such as its ID.
+    pub key: K,
+    /// The time at which the alert fires.
+    pub alert_time: DateTime<Utc>,
+    /// The time at which the notification should be delivered, which is later than
+    /// [`Notification::alert_time`] if it was deferred.
+    pub scheduled: DateTime<Utc>,
+    /// Whether the notification should be delivered silently.
+    pub silent: bool,
+}
+
+/// Schedules notifications for alerts according to quiet hours and quiet days.
+///
+/// Notifications which would be delivered during the quiet hours or on one of the quiet days are
+/// handled according to the [`QuietPolicy`] of the planner; all others are delivered when their
+/// alert fires. With the default options, nothing is quiet.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct NotificationPlanner {
+    /// The daily quiet hours, if any.
+    pub quiet_hours: Option<QuietHours>,
+    /// The days of the week which are quiet all day, such as the weekend.
+    pub quiet_days: Vec<Weekday>,
+    /// The handling of notifications at quiet times.
+    pub policy: QuietPolicy,
+}
+
+impl NotificationPlanner {
+    /// Returns this planner with the given [`QuietHours`].
+    pub fn with_quiet_hours(mut self, quiet_hours: QuietHours) -> Self {
+        self.quiet_hours = Some(quiet_hours);
+        self
+    }
+
+    /// Returns this planner with the given quiet days.
+    pub fn with_quiet_days(mut self, days: impl IntoIterator<Item = Weekday>) -> Self {
+        self.quiet_days = days.into_iter().collect();
+        self
+    }
+
+    /// Returns this planner with the given [`QuietPolicy`].
+    pub fn with_policy(mut self, policy: QuietPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns `true` iff the given wall-clock time is quiet.
+    pub fn is_quiet(&self, local: DateTime<Local>) -> bool {
+        self.quiet_days.contains(&local.date.weekday())
+            || self
+                .quiet_hours
+                .is_some_and(|quiet_hours| quiet_hours.contains(local.time))
+    }
+
+    /// Returns the notifications for the alerts firing at the given times, ordered by the time
+    /// at which they should be delivered.
+    ///
+    /// `offset` returns the offset from UTC of the time zone of the user at the given instant.
+    /// Under [`QuietPolicy::Defer`], a notification is dropped if every day of the week is quiet.
+    pub fn plan<K>(
+        &self,
+        alerts: impl IntoIterator<Item = (K, DateTime<Utc>)>,
+        mut offset: impl FnMut(DateTime<Utc>) -> UtcOffset,
+    ) -> Vec<Notification<K>> {
+        let mut notifications: Vec<_> = alerts
+            .into_iter()
+            .filter_map(|(key, alert_time)| {
+                let utc_offset = offset(alert_time);
+                let local = to_local(alert_time, utc_offset)?;
+                let (scheduled, silent) = match (self.is_quiet(local), self.policy) {
+                    (false, _) => (alert_time, false),
+                    (true, QuietPolicy::Drop) => return None,
+                    (true, QuietPolicy::Silent) => (alert_time, true),
+                    (true, QuietPolicy::Defer) => {
+                        let next = self.next_allowed(local)?;
+                        // the offset may differ at the deferred time, such as across a DST
+                        // transition, so it is resolved again at the approximate instant
+                        let approximate = local_to_utc(next, utc_offset)?;
+                        (local_to_utc(next, offset(approximate))?, false)
+                    }
+                };
+                Some(Notification {
+                    key,
+                    alert_time,
+                    scheduled,
+                    silent,
+                })
+            })
+            .collect();
+        notifications.sort_by_key(|notification| notification.scheduled);
+        notifications
+    }
+
+    /// Returns the first wall-clock time at or after `local` which is not quiet, or `None` if
+    /// every day is quiet.
+    fn next_allowed(&self, mut local: DateTime<Local>) -> Option<DateTime<Local>> {
+        // each step moves to the next day or the end of the quiet hours, and the latter can
+        // happen at most twice in a row
+        for _ in 0..3 * 7 {
+            if self.quiet_days.contains(&local.date.weekday()) {
+                local = DateTime {
+                    date: local.date.checked_add_days(1)?,
+                    time: midnight(),
+                    marker: Local,
+                };
+            } else if let Some(quiet_hours) = self
+                .quiet_hours
+                .filter(|quiet_hours| quiet_hours.contains(local.time))
+            {
+                let date = if local.time < quiet_hours.end {
+                    local.date
+                } else {
+                    local.date.checked_add_days(1)?
+                };
+                local = DateTime {
+                    date,
+                    time: quiet_hours.end,
+                    marker: Local,
+                };
+            } else {
+                return Some(local);
+            }
+        }
+        None
+    }
+}
+
+fn midnight() -> Time {
+    Time::new(Hour::H00, Minute::M00, Second::S00, None).unwrap()
+}
+
+/// Converts `utc` into the wall-clock time at the given offset from UTC.
+fn to_local(utc: DateTime<Utc>, offset: UtcOffset) -> Option<DateTime<Local>> {
+    let naive = DateTime {
+        date: utc.date,
+        time: utc.time,
+        marker: Local,
+    };
+    // local_to_utc subtracts the offset, so negating it converts in the other direction
+    let inverse = UtcOffset {
+        sign: match offset.sign {
+            Sign::Pos => Sign::Neg,
+            Sign::Neg => Sign::Pos,
+        },
+        ..offset
+    };
+    let local = local_to_utc(naive, inverse)?;
+    Some(DateTime {
+        date: local.date,
+        time: local.time,
+        marker: Local,
+    })
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{json::TryFromJson, model::time::NonLeapSecond};
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::try_from_json(Value::from(s)).unwrap()
+    }
+
+    fn time(hour: Hour) -> Time {
+        Time::new(hour, Minute::M00, Second::S00, None).unwrap()
+    }
+
+    fn offset(hour: Hour) -> UtcOffset {
+        UtcOffset {
+            sign: Sign::Pos,
+            hour,
+            minute: Minute::M00,
+            second: NonLeapSecond::S00,
+        }
+    }
+
+    #[test]
+    fn alert_times() {
+        let event: Event<Value> = Event::try_from_json(json!({
+            "@type": "Event",
+            "uid": "meeting",
+            "start": "2024-01-01T09:00:00",
+            "duration": "PT1H",
+            "timeZone": "Europe/Berlin",
+            "alerts": {
+                "before": { "trigger": { "@type": "OffsetTrigger", "offset": "-PT15M" } },
+                "after": { "trigger": { "@type": "OffsetTrigger", "offset": "PT5M", "relativeTo": "end" } },
+                "fixed": { "trigger": { "@type": "AbsoluteTrigger", "when": "2023-12-31T12:00:00Z" } },
+                "done": {
+                    "trigger": { "@type": "OffsetTrigger", "offset": "-PT1H" },
+                    "acknowledged": "2024-01-01T07:30:00Z"
+                },
+                "custom": { "trigger": { "@type": "CustomTrigger" } }
+            }
+        }))
+        .unwrap();
+
+        let times: Vec<_> = event
+            .alert_times(|_, _| Some(offset(Hour::H01)))
+            .into_iter()
+            .map(|(id, time)| (id.as_str(), time))
+            .collect();
+        assert_eq!(
+            times,
+            [
+                ("fixed", utc("2023-12-31T12:00:00Z")),
+                ("before", utc("2024-01-01T07:45:00Z")),
+                ("after", utc("2024-01-01T09:05:00Z")),
+            ]
+        );
+
+        let task: Task<Value> = Task::try_from_json(json!({
+            "@type": "Task",
+            "uid": "report",
+            "due": "2024-01-05T17:00:00",
+            "alerts": {
+                "due": { "trigger": { "@type": "OffsetTrigger", "offset": "-PT1H", "relativeTo": "end" } },
+                "start": { "trigger": { "@type": "OffsetTrigger", "offset": "PT0S" } }
+            }
+        }))
+        .unwrap();
+        let times = task.alert_times(|_, _| None);
+        assert_eq!(times.len(), 1);
+        assert_eq!(times[0].0.as_str(), "due");
+        assert_eq!(times[0].1, utc("2024-01-05T16:00:00Z"));
+    }
+
+    #[test]
+    fn quiet_hours() {
+        let night = QuietHours {
+            start: time(Hour::H22),
+            end: time(Hour::H07),
+        };
+        assert!(night.contains(time(Hour::H23)));
+        assert!(night.contains(time(Hour::H03)));
+        assert!(!night.contains(time(Hour::H07)));
+        assert!(!night.contains(time(Hour::H12)));
+
+        let lunch = QuietHours {
+            start: time(Hour::H12),
+            end: time(Hour::H13),
+        };
+        assert!(lunch.contains(time(Hour::H12)));
+        assert!(!lunch.contains(time(Hour::H13)));
+    }
+
+    #[test]
+    fn plan() {
+        // 2024-01-05 is a Friday
+        let alerts = [
+            ("late", utc("2024-01-05T22:30:00Z")),
+            ("day", utc("2024-01-05T10:00:00Z")),
+            ("early", utc("2024-01-05T05:00:00Z")),
+            ("saturday", utc("2024-01-06T12:00:00Z")),
+        ];
+        let planner = NotificationPlanner::default()
+            .with_quiet_hours(QuietHours {
+                start: time(Hour::H22),
+                end: time(Hour::H07),
+            })
+            .with_quiet_days([Weekday::Saturday, Weekday::Sunday]);
+        let cet = |_| offset(Hour::H01);
+
+        let schedule = |planner: &NotificationPlanner| {
+            planner
+                .plan(alerts, cet)
+                .into_iter()
+                .map(|notification| {
+                    (
+                        notification.key,
+                        notification.scheduled,
+                        notification.silent,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // in CET, "early" fires at 06:00 and "late" at 23:30 on Friday, and both "late" and
+        // "saturday" are deferred past the weekend to 07:00 on Monday
+        assert_eq!(
+            schedule(&planner),
+            [
+                ("early", utc("2024-01-05T06:00:00Z"), false),
+                ("day", utc("2024-01-05T10:00:00Z"), false),
+                ("late", utc("2024-01-08T06:00:00Z"), false),
+                ("saturday", utc("2024-01-08T06:00:00Z"), false),
+            ]
+        );
+
+        let dropping = planner.clone().with_policy(QuietPolicy::Drop);
+        assert_eq!(
+            schedule(&dropping),
+            [("day", utc("2024-01-05T10:00:00Z"), false)]
+        );
+
+        let silent = planner.clone().with_policy(QuietPolicy::Silent);
+        let silent: Vec<_> = schedule(&silent)
+            .into_iter()
+            .map(|(key, _, silent)| (key, silent))
+            .collect();
+        assert_eq!(
+            silent,
+            [
+                ("early", true),
+                ("day", false),
+                ("late", true),
+                ("saturday", true)
+            ]
+        );
+
+        let always_quiet = NotificationPlanner::default().with_quiet_days([
+            Weekday::Monday,
+            Weekday::Tuesday,
+            Weekday::Wednesday,
+            Weekday::Thursday,
+            Weekday::Friday,
+            Weekday::Saturday,
+            Weekday::Sunday,
+        ]);
+        assert!(always_quiet.plan(alerts, cet).is_empty());
+        assert_eq!(
+            NotificationPlanner::default().plan(alerts, cet).len(),
+            alerts.len()
+        );
+    }
+}
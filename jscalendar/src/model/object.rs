@@ -1,38 +1,48 @@
 //! Distinguished object types.
 
 use std::{
+    borrow::Borrow,
     collections::{BTreeSet, HashMap, HashSet, VecDeque},
+    convert::Infallible,
     hash::Hash,
     num::NonZero,
+    sync::Arc,
 };
 
 use structible::structible;
 use thiserror::Error;
 
-use crate::parser::{local_date_time, parse_full};
+use crate::parser::{OwnedParseError, local_date_time, parse_full, utc_date_time};
 use crate::{
     json::{
-        ConstructibleJsonValue, DestructibleJsonValue, DocumentError, IntoJson, Int,
-        IntoDocumentError, JsonArray, JsonObject, JsonValue, PathSegment, TryFromJson, TypeError,
-        TypeErrorOr, UnsignedInt,
+        ConstructibleJsonValue, DestructibleJsonValue, DocumentError, HashSetTryFromJsonError,
+        IntoDocumentError, IntoIntError, IntoJson, IntoUnsignedIntError, Int, JsonArray,
+        JsonObject, JsonValue, PathSegment, TryFromJson, TypeError, TypeErrorOr, UnsignedInt,
+        error::{ObjectError, field_error, missing, nested_field_error, prepend},
     },
     model::{
         request_status::{RequestStatus, StatusCode},
         rrule::RRule,
         set::{
             AlertAction, AlertRelativeTo, Color, DisplayPurpose, EventStatus, FreeBusyStatus,
-            LinkRelation, LocationType, Method, ParticipantKind, ParticipantRole,
-            ParticipationStatus, Percent, Priority, Privacy, RelationValue, ScheduleAgent,
-            TaskProgress, VirtualLocationFeature,
+            InvalidColorError, InvalidPercentError, InvalidPriorityError, LinkRelation,
+            LocationType, Method, ParticipantKind, ParticipantRoleSet, ParticipationStatus,
+            Percent, Priority, Privacy, RelationValue, ScheduleAgent, TaskProgress,
+            VirtualLocationFeature,
         },
         string::{
-            AlphaNumeric, CalAddress, ContentId, CustomTimeZoneId, EmailAddr, GeoUri, Id,
-            ImplicitJsonPointer, InvalidImplicitJsonPointerError, LanguageTag, MediaType, Uid, Uri,
+            AlphaNumeric, CalAddress, ContentId, CustomTimeZoneId, EmailAddr, GeoUri, HttpsUrl, Id,
+            ImplicitJsonPointer, InvalidCalAddressError, InvalidContentIdError,
+            InvalidCustomTimeZoneIdError, InvalidEmailAddrError, InvalidGeoUriError,
+            InvalidHttpsUrlError, InvalidIdError, InvalidImplicitJsonPointerError,
+            InvalidMediaTypeError, InvalidUidError, InvalidUriError, LanguageTag,
+            LanguageTagParseError, MediaType, StringError, Uid, Uri,
         },
         time::{
-            Date, DateTime, Day, Duration, Hour, IsoWeek, Local, Minute, Month, NonLeapSecond,
-            Sign, SignedDuration, Utc, UtcOffset, Weekday, Year,
+            Date, DateTime, Day, Duration, DurationFormat, ExactDuration, Hour, IsoWeek, Local,
+            Minute, Month, NonLeapSecond, Sign, SignedDuration, Utc, UtcOffset, Weekday, Year,
         },
+        vendor::{self, Extensible, VendorPlacement},
     },
 };
 use rfc5545_types::rrule::weekday_num_set::WeekdayNumSet;
@@ -40,11 +50,16 @@ use rfc5545_types::time::DateTimeOrDate;
 
 type Token<T> = super::set::Token<T, Box<str>>;
 
+type LinkMap<V> = HashMap<Box<Id>, MaybeUnknown<Link<V>, V>>;
+type LocationMap<V> = HashMap<Box<Id>, MaybeUnknown<Location<V>, V>>;
+type VirtualLocationMap<V> = HashMap<Box<Id>, MaybeUnknown<VirtualLocation<V>, V>>;
+type AlertMap<V> = HashMap<Box<Id>, MaybeUnknown<Alert<V>, V>>;
+
 /// A JSCalendar group opject (RFC 8984 §2.3).
 ///
 /// A group is a collection of [`Event`] and [`Task`] objects. Typically, objects are grouped by
 /// topic (e.g. by keywords) or calendar membership.
-#[structible]
+#[structible(with_len)]
 pub struct Group<V: JsonValue> {
     // Group Properties (RFC 8984 §5.3)
     pub entries: Vec<TaskOrEvent<V>>,
@@ -58,7 +73,7 @@ pub struct Group<V: JsonValue> {
     pub title: Option<String>,
     pub description: Option<String>,
     pub description_content_type: Option<String>,
-    pub links: Option<HashMap<Box<Id>, Link<V>>>,
+    pub links: Option<HashMap<Box<Id>, MaybeUnknown<Link<V>, V>>>,
     pub locale: Option<LanguageTag>,
     pub keywords: Option<HashSet<String>>,
     pub categories: Option<HashSet<String>>,
@@ -144,7 +159,7 @@ impl<V: JsonValue> TaskOrEvent<V> {
 /// An event represents a scheduled amount of time on a calendar, typically a meeting, appointment,
 /// reminder, or anniversary. It is required to start at a certain point in time and typically has
 /// a non-zero duration. Multiple participants may partake in the event at multiple locations.
-#[structible]
+#[structible(with_len)]
 pub struct Event<V: JsonValue> {
     // Event Properties (RFC 8984 §5.1)
     pub start: DateTime<Local>,
@@ -165,9 +180,9 @@ pub struct Event<V: JsonValue> {
     pub description: Option<String>,
     pub description_content_type: Option<String>,
     pub show_without_time: Option<bool>,
-    pub locations: Option<HashMap<Box<Id>, Location<V>>>,
-    pub virtual_locations: Option<HashMap<Box<Id>, VirtualLocation<V>>>,
-    pub links: Option<HashMap<Box<Id>, Link<V>>>,
+    pub locations: Option<HashMap<Box<Id>, MaybeUnknown<Location<V>, V>>>,
+    pub virtual_locations: Option<HashMap<Box<Id>, MaybeUnknown<VirtualLocation<V>, V>>>,
+    pub links: Option<HashMap<Box<Id>, MaybeUnknown<Link<V>, V>>>,
     pub locale: Option<LanguageTag>,
     pub keywords: Option<HashSet<String>>,
     pub categories: Option<HashSet<String>>,
@@ -192,7 +207,7 @@ pub struct Event<V: JsonValue> {
 
     // Alerts Properties (RFC 8984 §4.5)
     pub use_default_alerts: Option<bool>,
-    pub alerts: Option<HashMap<Box<Id>, Alert<V>>>,
+    pub alerts: Option<HashMap<Box<Id>, MaybeUnknown<Alert<V>, V>>>,
 
     // Multilingual Properties (RFC 8984 §4.6)
     pub localizations: Option<HashMap<LanguageTag, PatchObject<V>>>,
@@ -211,7 +226,7 @@ pub struct Event<V: JsonValue> {
 /// A task represents an action item, assignment, to-do item, or work item. It may start and be due
 /// at certain points in time, take some estimated time to complete, and recur, none of which is
 /// required.
-#[structible]
+#[structible(with_len)]
 pub struct Task<V: JsonValue> {
     // Task Properties (RFC 8984 §5.2)
     pub due: Option<DateTime<Local>>,
@@ -235,9 +250,9 @@ pub struct Task<V: JsonValue> {
     pub description: Option<String>,
     pub description_content_type: Option<String>,
     pub show_without_time: Option<bool>,
-    pub locations: Option<HashMap<Box<Id>, Location<V>>>,
-    pub virtual_locations: Option<HashMap<Box<Id>, VirtualLocation<V>>>,
-    pub links: Option<HashMap<Box<Id>, Link<V>>>,
+    pub locations: Option<HashMap<Box<Id>, MaybeUnknown<Location<V>, V>>>,
+    pub virtual_locations: Option<HashMap<Box<Id>, MaybeUnknown<VirtualLocation<V>, V>>>,
+    pub links: Option<HashMap<Box<Id>, MaybeUnknown<Link<V>, V>>>,
     pub locale: Option<LanguageTag>,
     pub keywords: Option<HashSet<String>>,
     pub categories: Option<HashSet<String>>,
@@ -262,7 +277,7 @@ pub struct Task<V: JsonValue> {
 
     // Alerts Properties (RFC 8984 §4.5)
     pub use_default_alerts: Option<bool>,
-    pub alerts: Option<HashMap<Box<Id>, Alert<V>>>,
+    pub alerts: Option<HashMap<Box<Id>, MaybeUnknown<Alert<V>, V>>>,
 
     // Multilingual Properties (RFC 8984 §4.6)
     pub localizations: Option<HashMap<LanguageTag, PatchObject<V>>>,
@@ -278,14 +293,14 @@ pub struct Task<V: JsonValue> {
 
 /// A description of a physical location (RFC 8984 §4.2.5).
 #[structible]
-pub struct Location<V> {
+pub struct Location<V: JsonValue> {
     pub name: Option<String>,
     pub description: Option<String>,
-    pub location_types: Option<HashSet<LocationType>>,
+    pub location_types: Option<HashSet<Token<LocationType>>>,
     pub relative_to: Option<Token<RelationValue>>,
     pub time_zone: Option<String>,
     pub coordinates: Option<Box<GeoUri>>,
-    pub links: Option<HashMap<Box<Id>, Link<V>>>,
+    pub links: Option<HashMap<Box<Id>, MaybeUnknown<Link<V>, V>>>,
 
     #[structible(key = Box<str>)]
     pub vendor_property: Option<V>,
@@ -310,7 +325,7 @@ pub struct Link<V> {
     pub content_id: Option<Box<ContentId>>,
     pub media_type: Option<Box<MediaType>>,
     pub size: Option<UnsignedInt>,
-    pub relation: Option<LinkRelation>,
+    pub relation: Option<Token<LinkRelation>>,
     pub display: Option<Token<DisplayPurpose>>,
     pub title: Option<String>,
 
@@ -351,13 +366,13 @@ pub struct TimeZoneRule<V> {
 
 /// A description of a participant (RFC 8984 §4.4.6).
 #[structible]
-pub struct Participant<V> {
+pub struct Participant<V: JsonValue> {
     pub name: Option<String>,
     pub email: Option<Box<EmailAddr>>,
     pub description: Option<String>,
     pub send_to: Option<SendToParticipant>,
     pub kind: Option<Token<ParticipantKind>>,
-    pub roles: Option<HashSet<Token<ParticipantRole>>>, // this could be a bitset
+    pub roles: Option<ParticipantRoleSet>,
     pub location_id: Option<Box<Id>>,
     pub language: Option<LanguageTag>,
     pub participation_status: Option<Token<ParticipationStatus>>,
@@ -373,7 +388,7 @@ pub struct Participant<V> {
     pub delegated_to: Option<HashSet<Box<Id>>>,
     pub delegated_from: Option<HashSet<Box<Id>>>,
     pub member_of: Option<HashSet<Box<Id>>>,
-    pub links: Option<HashMap<Box<Id>, Link<V>>>,
+    pub links: Option<HashMap<Box<Id>, MaybeUnknown<Link<V>, V>>>,
 
     #[structible(key = Box<str>)]
     pub vendor_property: Option<V>,
@@ -381,14 +396,14 @@ pub struct Participant<V> {
 
 /// A description of a participant which may occur in a [`Task`] (RFC 8984 §4.4.6).
 #[structible]
-pub struct TaskParticipant<V> {
+pub struct TaskParticipant<V: JsonValue> {
     // general participant fields
     pub name: Option<String>,
     pub email: Option<Box<EmailAddr>>,
     pub description: Option<String>,
     pub send_to: Option<SendToParticipant>,
     pub kind: Option<Token<ParticipantKind>>,
-    pub roles: Option<HashSet<Token<ParticipantRole>>>, // this could be a bitset
+    pub roles: Option<ParticipantRoleSet>,
     pub location_id: Option<Box<Id>>,
     pub language: Option<LanguageTag>,
     pub participation_status: Option<Token<ParticipationStatus>>,
@@ -404,7 +419,7 @@ pub struct TaskParticipant<V> {
     pub delegated_to: Option<HashSet<Box<Id>>>,
     pub delegated_from: Option<HashSet<Box<Id>>>,
     pub member_of: Option<HashSet<Box<Id>>>,
-    pub links: Option<HashMap<Box<Id>, Link<V>>>,
+    pub links: Option<HashMap<Box<Id>, MaybeUnknown<Link<V>, V>>>,
 
     // task-specific fields
     pub progress: Option<Token<TaskProgress>>,
@@ -415,18 +430,15 @@ pub struct TaskParticipant<V> {
     pub vendor_property: Option<V>,
 }
 
-// TODO: define an HttpsUrl newtype for URIs that are statically known to start with the https:
-// scheme, which should then be used for the type of ReplyTo::web
-
 /// The type of the `replyTo` property (RFC 8984 §4.4.4).
 #[structible]
 pub struct ReplyTo {
     /// If the `imip` field is defined, then the organizer accepts an iMIP (RFC 6047) response at
     /// the corresponding email address.
     pub imip: Option<Box<CalAddress>>,
-    /// If the `web` field is defined, then opening the corresponding [`Uri`] in a web browser will
-    /// provide the user with a page where they can submit a reply to the organizer.
-    pub web: Option<Box<Uri>>,
+    /// If the `web` field is defined, then opening the corresponding [`HttpsUrl`] in a web browser
+    /// will provide the user with a page where they can submit a reply to the organizer.
+    pub web: Option<Box<HttpsUrl>>,
     /// If any other `replyTo` method is present, the organizer is considered to be identified by
     /// the corresponding [`Uri`], but the method for submitting the response is undefined. This
     /// includes vendor-prefixed method names.
@@ -502,6 +514,89 @@ where
     }
 }
 
+/// An object in a map of [`Location`], [`VirtualLocation`], [`Link`], or [`Alert`] objects, which
+/// may have an unrecognized `@type`.
+///
+/// Objects whose `@type` is absent or has the expected value are parsed as `T`, while any other
+/// object is preserved as-is, so that documents using newer extensions of the specification
+/// round-trip without losing data.
+#[non_exhaustive]
+pub enum MaybeUnknown<T, V: JsonValue> {
+    /// An object with the expected `@type`.
+    Known(T),
+    /// An object with an unrecognized `@type`.
+    Unknown(V::Object),
+}
+
+impl<T, V> PartialEq for MaybeUnknown<T, V>
+where
+    T: PartialEq,
+    V: JsonValue,
+    V::Object: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Known(l0), Self::Known(r0)) => l0 == r0,
+            (Self::Unknown(l0), Self::Unknown(r0)) => l0 == r0,
+            _ => false,
+        }
+    }
+}
+
+impl<T, V> Clone for MaybeUnknown<T, V>
+where
+    T: Clone,
+    V: JsonValue,
+    V::Object: Clone,
+{
+    fn clone(&self) -> Self {
+        match self {
+            Self::Known(arg0) => Self::Known(arg0.clone()),
+            Self::Unknown(arg0) => Self::Unknown(arg0.clone()),
+        }
+    }
+}
+
+impl<T, V> std::fmt::Debug for MaybeUnknown<T, V>
+where
+    T: std::fmt::Debug,
+    V: JsonValue,
+    V::Object: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Known(arg0) => f.debug_tuple("Known").field(arg0).finish(),
+            Self::Unknown(arg0) => f.debug_tuple("Unknown").field(arg0).finish(),
+        }
+    }
+}
+
+impl<T, V: JsonValue> MaybeUnknown<T, V> {
+    /// Returns a reference to the inner object if this is the `Known` variant.
+    pub const fn as_known(&self) -> Option<&T> {
+        if let Self::Known(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the inner object if this is the `Known` variant.
+    pub const fn as_known_mut(&mut self) -> Option<&mut T> {
+        if let Self::Known(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, V: JsonValue> From<T> for MaybeUnknown<T, V> {
+    fn from(value: T) -> Self {
+        Self::Known(value)
+    }
+}
+
 /// A trigger defined relative to a time property (RFC 8984 §4.5.2).
 #[structible]
 pub struct OffsetTrigger<V> {
@@ -561,6 +656,12 @@ impl<V> PatchObject<V> {
     }
 }
 
+impl<V> FromIterator<(Box<ImplicitJsonPointer>, V)> for PatchObject<V> {
+    fn from_iter<I: IntoIterator<Item = (Box<ImplicitJsonPointer>, V)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 /// A [`PatchObject`] key was not a valid implicit JSON pointer.
 #[derive(Debug, Clone, PartialEq, Error)]
 #[error("the key {key} is not an implicit JSON pointer")]
@@ -620,55 +721,125 @@ pub enum ObjectFromJsonError {
     MissingField(&'static str),
     /// A field was present but had an invalid value.
     #[error("{0}")]
-    InvalidFieldValue(Box<str>),
-}
-
-type ObjErr = DocumentError<TypeErrorOr<ObjectFromJsonError>>;
-
-fn field_err<E: std::fmt::Display>(field: &'static str, e: TypeErrorOr<E>) -> ObjErr {
-    let err = match e {
-        TypeErrorOr::TypeError(t) => TypeErrorOr::TypeError(t),
-        TypeErrorOr::Other(e) => TypeErrorOr::Other(ObjectFromJsonError::InvalidFieldValue(
-            e.to_string().into_boxed_str(),
-        )),
-    };
-    DocumentError {
-        path: [PathSegment::Static(field)].into(),
-        error: err,
-    }
+    InvalidFieldValue(#[source] FieldValueError),
 }
 
-fn type_field_err(field: &'static str, e: TypeError) -> ObjErr {
-    DocumentError {
-        path: [PathSegment::Static(field)].into(),
-        error: TypeErrorOr::TypeError(e),
-    }
-}
+macro_rules! field_value_errors {
+    ($($(#[$meta:meta])* $variant:ident($error:ty),)+) => {
+        /// The reason that the value of a field was invalid, as held by
+        /// [`ObjectFromJsonError::InvalidFieldValue`].
+        ///
+        /// Each variant holds the error of the type which the value failed to convert into, so
+        /// that it can be matched on, and has the same [`Display`](std::fmt::Display) and
+        /// [`source`](std::error::Error::source) as that error.
+        #[derive(Debug, Clone, Error)]
+        #[non_exhaustive]
+        pub enum FieldValueError {
+            $(
+                $(#[$meta])*
+                #[error(transparent)]
+                $variant(#[from] $error),
+            )+
+            /// An `@type` other than the one expected by an extension object.
+            #[error("expected {expected:?} but found {found:?}")]
+            UnexpectedType {
+                /// The expected `@type`.
+                expected: &'static str,
+                /// The `@type` which was found.
+                found: Box<str>,
+            },
+            /// An error of a type which is not defined by this crate, such as that of a field of
+            /// an extension object.
+            ///
+            /// Two such errors are only equal if they share the same allocation.
+            #[error(transparent)]
+            Other(Arc<dyn std::error::Error + Send + Sync>),
+        }
+
+        impl FieldValueError {
+            /// Wraps an error of a type which is not defined by this crate in
+            /// [`FieldValueError::Other`].
+            pub fn other<E>(error: E) -> Self
+            where
+                E: std::error::Error + Send + Sync + 'static,
+            {
+                Self::Other(Arc::new(error))
+            }
+        }
+
+        impl From<Infallible> for FieldValueError {
+            fn from(error: Infallible) -> Self {
+                match error {}
+            }
+        }
+
+        impl PartialEq for FieldValueError {
+            fn eq(&self, other: &Self) -> bool {
+                match (self, other) {
+                    $((Self::$variant(l), Self::$variant(r)) => l == r,)+
+                    (
+                        Self::UnexpectedType { expected, found },
+                        Self::UnexpectedType { expected: other_expected, found: other_found },
+                    ) => expected == other_expected && found == other_found,
+                    (Self::Other(l), Self::Other(r)) => Arc::ptr_eq(l, r),
+                    _ => false,
+                }
+            }
+        }
 
-fn doc_field_err<E: std::fmt::Display>(
-    field: &'static str,
-    mut e: DocumentError<TypeErrorOr<E>>,
-) -> ObjErr {
-    let err = match e.error {
-        TypeErrorOr::TypeError(t) => TypeErrorOr::TypeError(t),
-        TypeErrorOr::Other(e) => TypeErrorOr::Other(ObjectFromJsonError::InvalidFieldValue(
-            e.to_string().into_boxed_str(),
-        )),
+        impl Eq for FieldValueError {}
     };
-    e.path.push_front(PathSegment::Static(field));
-    DocumentError {
-        path: e.path,
-        error: err,
-    }
-}
-
-fn prepend(field: &'static str, mut e: ObjErr) -> ObjErr {
-    e.path.push_front(PathSegment::Static(field));
-    e
 }
 
-fn missing(field: &'static str) -> ObjErr {
-    DocumentError::root(TypeErrorOr::Other(ObjectFromJsonError::MissingField(field)))
+field_value_errors! {
+    /// A value which was not an [`Int`].
+    Int(IntoIntError),
+    /// A value which was not an [`UnsignedInt`].
+    UnsignedInt(IntoUnsignedIntError),
+    /// A date, date-time, or duration string which failed to parse.
+    Parse(OwnedParseError),
+    /// A string which was not a [`UtcOffset`].
+    UtcOffset(InvalidUtcOffsetError),
+    /// A string which was not a [`StatusCode`].
+    StatusCode(InvalidStatusCodeError),
+    /// A string which was not a [`RequestStatus`].
+    RequestStatus(InvalidRequestStatusError),
+    /// A string which was not a [`Color`].
+    Color(InvalidColorError),
+    /// An integer which was not a [`Priority`].
+    Priority(InvalidPriorityError),
+    /// An integer which was not a [`Percent`].
+    Percent(InvalidPercentError),
+    /// A set of strings with a `false` value.
+    Set(HashSetTryFromJsonError<Infallible>),
+    /// A string which was not a [`LanguageTag`].
+    LanguageTag(StringError<LanguageTagParseError>),
+    /// A string which was not a [`Uid`].
+    Uid(StringError<InvalidUidError>),
+    /// A string which was not a [`Uri`].
+    Uri(StringError<InvalidUriError>),
+    /// A string which was not an [`Id`].
+    Id(StringError<InvalidIdError>),
+    /// A string which was not a [`CustomTimeZoneId`].
+    CustomTimeZoneId(StringError<InvalidCustomTimeZoneIdError>),
+    /// A string which was not a [`CalAddress`].
+    CalAddress(StringError<InvalidCalAddressError>),
+    /// A string which was not an [`HttpsUrl`].
+    HttpsUrl(StringError<InvalidHttpsUrlError>),
+    /// A string which was not an [`EmailAddr`].
+    EmailAddr(StringError<InvalidEmailAddrError>),
+    /// A string which was not a [`GeoUri`].
+    GeoUri(StringError<InvalidGeoUriError>),
+    /// A string which was not a [`ContentId`].
+    ContentId(StringError<InvalidContentIdError>),
+    /// A string which was not a [`MediaType`].
+    MediaType(StringError<InvalidMediaTypeError>),
+    /// A key of a [`PatchObject`] which was not an [`ImplicitJsonPointer`].
+    JsonPointer(InvalidImplicitJsonPointerError),
+    /// A [`PatchObject`] with a key which was not an [`ImplicitJsonPointer`].
+    PatchObject(InvalidPatchObjectError),
+    /// A value which was not an [`RRule`].
+    RRule(RRuleFromJsonError),
 }
 
 // ============================================================================
@@ -732,7 +903,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for StatusCode {
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let s = value.try_into_string()?;
-        parse_status_code(s.as_ref()).ok_or_else(|| {
+        s.as_ref().parse().map_err(|_| {
             TypeErrorOr::Other(InvalidStatusCodeError(
                 String::from(s.as_ref()).into_boxed_str(),
             ))
@@ -740,30 +911,6 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for StatusCode {
     }
 }
 
-fn parse_status_code(s: &str) -> Option<StatusCode> {
-    use crate::model::request_status::Class;
-    let mut parts = s.splitn(3, '.');
-    let class_n: u8 = parts.next()?.parse().ok()?;
-    let class = match class_n {
-        1 => Class::C1,
-        2 => Class::C2,
-        3 => Class::C3,
-        4 => Class::C4,
-        5 => Class::C5,
-        _ => return None,
-    };
-    let major: u8 = parts.next()?.parse().ok()?;
-    let minor: Option<u8> = match parts.next() {
-        Some(s) => Some(s.parse().ok()?),
-        None => None,
-    };
-    Some(StatusCode {
-        class,
-        major,
-        minor,
-    })
-}
-
 // ============================================================================
 // RequestStatus TryFromJson
 // ============================================================================
@@ -778,7 +925,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for RequestStatus {
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let s = value.try_into_string()?;
-        parse_request_status(s.as_ref()).ok_or_else(|| {
+        s.as_ref().parse().map_err(|_| {
             TypeErrorOr::Other(InvalidRequestStatusError(
                 String::from(s.as_ref()).into_boxed_str(),
             ))
@@ -786,25 +933,12 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for RequestStatus {
     }
 }
 
-fn parse_request_status(s: &str) -> Option<RequestStatus> {
-    let mut parts = s.splitn(3, ';');
-    let code_str = parts.next()?;
-    let code = parse_status_code(code_str)?;
-    let description: Box<str> = parts.next()?.into();
-    let exception_data: Option<Box<str>> = parts.next().map(Into::into);
-    Some(RequestStatus {
-        code,
-        description,
-        exception_data,
-    })
-}
-
 // ============================================================================
 // RRule TryFromJson
 // ============================================================================
 
 /// Error returned when parsing a recurrence rule from JSON.
-#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[derive(Debug, Clone, PartialEq, Error)]
 #[non_exhaustive]
 pub enum RRuleFromJsonError {
     /// A required field was not present in the JSON object.
@@ -813,27 +947,44 @@ pub enum RRuleFromJsonError {
     /// A field was present but had an invalid value.
     #[error("invalid field value: {0}")]
     InvalidValue(Box<str>),
+    /// A field was present but was not an [`UnsignedInt`].
+    #[error("invalid field value: {0}")]
+    UnsignedInt(#[from] IntoUnsignedIntError),
+    /// An element of a `byXxx` array was invalid.
+    #[error("invalid field value: {0}")]
+    ByRule(#[from] ByRuleParseError),
 }
 
+/// The vendor property of a `RecurrenceRule` object which holds the
+/// [`extras`](RRule::extras) of an [`RRule`], as an array of `NAME=value` strings.
+pub const RRULE_EXTRA_PARTS: &str = "eikopf.github.io:extraParts";
+
 impl<V: DestructibleJsonValue> TryFromJson<V> for RRule {
     type Error = DocumentError<TypeErrorOr<RRuleFromJsonError>>;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
-        fn rrule_field_err<E: std::fmt::Display>(
+        fn rrule_field_err<E: Into<RRuleFromJsonError>>(
             field: &'static str,
             e: TypeErrorOr<E>,
         ) -> DocumentError<TypeErrorOr<RRuleFromJsonError>> {
             let err = match e {
                 TypeErrorOr::TypeError(t) => TypeErrorOr::TypeError(t),
-                TypeErrorOr::Other(e) => TypeErrorOr::Other(RRuleFromJsonError::InvalidValue(
-                    e.to_string().into_boxed_str(),
-                )),
+                TypeErrorOr::Other(e) => TypeErrorOr::Other(e.into()),
             };
             DocumentError {
                 path: [PathSegment::Static(field)].into(),
                 error: err,
             }
         }
+        fn rrule_type_err(
+            field: &'static str,
+            e: TypeError,
+        ) -> DocumentError<TypeErrorOr<RRuleFromJsonError>> {
+            DocumentError {
+                path: [PathSegment::Static(field)].into(),
+                error: TypeErrorOr::TypeError(e),
+            }
+        }
         fn rrule_invalid(
             field: &'static str,
             msg: &str,
@@ -867,11 +1018,15 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for RRule {
         let mut by_month_day_val: Option<V> = None;
         let mut by_year_day_val: Option<V> = None;
         let mut by_week_no_val: Option<V> = None;
+        let mut rscale_val: Option<V> = None;
+        let mut skip_val: Option<V> = None;
+        let mut extras_val: Option<V> = None;
 
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
-                "@type" | "rscale" | "skip" => {}
+            match <V::Object as JsonObject>::key_as_str(&key) {
+                "@type" => {}
+                "rscale" => rscale_val = Some(val),
+                "skip" => skip_val = Some(val),
                 "frequency" => frequency_val = Some(val),
                 "interval" => interval_val = Some(val),
                 "count" => count_val = Some(val),
@@ -886,6 +1041,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for RRule {
                 "byMonthDay" => by_month_day_val = Some(val),
                 "byYearDay" => by_year_day_val = Some(val),
                 "byWeekNo" => by_week_no_val = Some(val),
+                RRULE_EXTRA_PARTS => extras_val = Some(val),
                 _ => {}
             }
         }
@@ -894,9 +1050,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for RRule {
         let freq_str = frequency_val
             .ok_or_else(|| rrule_missing("frequency"))?
             .try_into_string()
-            .map_err(|e| {
-                rrule_field_err::<std::convert::Infallible>("frequency", TypeErrorOr::TypeError(e))
-            })?;
+            .map_err(|e| rrule_type_err("frequency", e))?;
 
         // Parse interval
         let interval = match interval_val {
@@ -917,9 +1071,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for RRule {
                 Some(crate::model::rrule::Termination::Count(n.get()))
             }
             (None, Some(u)) => {
-                let s = u.try_into_string().map_err(|e| {
-                    rrule_field_err::<std::convert::Infallible>("until", TypeErrorOr::TypeError(e))
-                })?;
+                let s = u.try_into_string().map_err(|e| rrule_type_err("until", e))?;
                 let until = parse_date_or_datetime(s.as_ref())
                     .ok_or_else(|| rrule_invalid("until", s.as_ref()))?
                     .map_marker(Into::into);
@@ -938,12 +1090,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for RRule {
         let week_start = match week_start_val {
             None => None,
             Some(v) => {
-                let s = v.try_into_string().map_err(|e| {
-                    rrule_field_err::<std::convert::Infallible>(
-                        "firstDayOfWeek",
-                        TypeErrorOr::TypeError(e),
-                    )
-                })?;
+                let s = v.try_into_string().map_err(|e| rrule_type_err("firstDayOfWeek", e))?;
                 let wd = parse_weekday_code(s.as_ref())
                     .ok_or_else(|| rrule_invalid("firstDayOfWeek", s.as_ref()))?;
                 Some(wd)
@@ -956,9 +1103,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for RRule {
             Some(v) => Some(parse_by_day::<V>(v).map_err(|e| {
                 let error = match e.error {
                     TypeErrorOr::TypeError(t) => TypeErrorOr::TypeError(t),
-                    TypeErrorOr::Other(br) => TypeErrorOr::Other(RRuleFromJsonError::InvalidValue(
-                        br.to_string().into_boxed_str(),
-                    )),
+                    TypeErrorOr::Other(br) => TypeErrorOr::Other(RRuleFromJsonError::ByRule(br)),
                 };
                 let mut path = e.path;
                 path.push_front(PathSegment::Static("byDay"));
@@ -1073,12 +1218,55 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for RRule {
             }
         };
 
+        // Parse rscale
+        let rscale = match rscale_val {
+            None => None,
+            Some(v) => {
+                let s = v.try_into_string().map_err(|e| rrule_type_err("rscale", e))?;
+                Some(s.as_ref().parse().unwrap_or_else(|e| match e {}))
+            }
+        };
+
+        // Parse skip
+        let skip = match skip_val {
+            None => None,
+            Some(v) => {
+                let s = v.try_into_string().map_err(|e| rrule_type_err("skip", e))?;
+                let skip = s
+                    .as_ref()
+                    .parse()
+                    .map_err(|_| rrule_invalid("skip", s.as_ref()))?;
+                Some(skip)
+            }
+        };
+
+        // Parse the extension parts, which are not part of RFC 8984
+        let mut extras = Vec::new();
+        if let Some(v) = extras_val {
+            let arr = v.try_into_array().map_err(|e| rrule_type_err(RRULE_EXTRA_PARTS, e))?;
+            for item in arr.into_iter() {
+                let s = item.try_into_string().map_err(|e| rrule_type_err(RRULE_EXTRA_PARTS, e))?;
+                let part = s
+                    .as_ref()
+                    .split_once('=')
+                    .and_then(|(name, value)| {
+                        let name = rfc5545_types::string::Name::new(name).ok()?;
+                        crate::model::rrule::XPart::new(name.into(), value).ok()
+                    })
+                    .ok_or_else(|| rrule_invalid(RRULE_EXTRA_PARTS, s.as_ref()))?;
+                extras.push(part);
+            }
+        }
+
         Ok(RRule {
             freq,
             core_by_rules,
             interval,
             termination,
             week_start,
+            rscale,
+            skip,
+            extras,
         })
     }
 }
@@ -1141,8 +1329,7 @@ fn parse_by_day<V: DestructibleJsonValue>(
         let mut day_val: Option<Weekday> = None;
         let mut nth_val: Option<i64> = None;
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
                 "@type" => {}
                 "day" => {
                     let s = val.try_into_string().map_err(|e| DocumentError {
@@ -1358,7 +1545,7 @@ fn parse_by_week_no<V: DestructibleJsonValue>(
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for Relation<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -1370,16 +1557,15 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Relation<V> {
         let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
 
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
                 "@type" => {}
                 "relation" => {
                     relations = Some(
                         HashSet::<Token<RelationValue>>::try_from_json(val)
-                            .map_err(|e| doc_field_err("relation", e))?,
+                            .map_err(|e| nested_field_error("relation", e))?,
                     );
                 }
-                _ => vendor_parts.push((k.into_boxed_str(), val)),
+                _ => vendor_parts.push((<V::Object as JsonObject>::key_into_string(key).into_boxed_str(), val)),
             }
         }
 
@@ -1397,7 +1583,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Relation<V> {
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for OffsetTrigger<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -1410,21 +1596,20 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for OffsetTrigger<V> {
         let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
 
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
                 "@type" => {}
                 "offset" => {
                     offset_val = Some(
-                        SignedDuration::try_from_json(val).map_err(|e| field_err("offset", e))?,
+                        SignedDuration::try_from_json(val).map_err(|e| field_error("offset", e))?,
                     );
                 }
                 "relativeTo" => {
                     relative_to_val = Some(
                         Token::<AlertRelativeTo>::try_from_json(val)
-                            .map_err(|e| type_field_err("relativeTo", e))?,
+                            .map_err(|e| field_error("relativeTo", e))?,
                     );
                 }
-                _ => vendor_parts.push((k.into_boxed_str(), val)),
+                _ => vendor_parts.push((<V::Object as JsonObject>::key_into_string(key).into_boxed_str(), val)),
             }
         }
 
@@ -1445,7 +1630,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for OffsetTrigger<V> {
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for AbsoluteTrigger<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -1457,15 +1642,14 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for AbsoluteTrigger<V> {
         let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
 
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
                 "@type" => {}
                 "when" => {
                     when_val = Some(
-                        DateTime::<Utc>::try_from_json(val).map_err(|e| field_err("when", e))?,
+                        DateTime::<Utc>::try_from_json(val).map_err(|e| field_error("when", e))?,
                     );
                 }
-                _ => vendor_parts.push((k.into_boxed_str(), val)),
+                _ => vendor_parts.push((<V::Object as JsonObject>::key_into_string(key).into_boxed_str(), val)),
             }
         }
 
@@ -1483,7 +1667,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for AbsoluteTrigger<V> {
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for Trigger<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let type_str = value
@@ -1497,7 +1681,12 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Trigger<V> {
         match type_str.as_deref() {
             Some("OffsetTrigger") => OffsetTrigger::try_from_json(value).map(Trigger::Offset),
             Some("AbsoluteTrigger") => AbsoluteTrigger::try_from_json(value).map(Trigger::Absolute),
-            _ => Err(missing("@type")),
+            Some(_) => value
+                .try_into_object()
+                .map(Trigger::Unknown)
+                .map_err(TypeErrorOr::from)
+                .map_err(DocumentError::root),
+            None => Err(missing("@type")),
         }
     }
 }
@@ -1507,7 +1696,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Trigger<V> {
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for ReplyTo {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -1516,20 +1705,19 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for ReplyTo {
             .map_err(DocumentError::root)?;
 
         let mut imip_val: Option<Box<CalAddress>> = None;
-        let mut web_val: Option<Box<Uri>> = None;
+        let mut web_val: Option<Box<HttpsUrl>> = None;
         let mut other_parts: Vec<(String, Box<Uri>)> = Vec::new();
 
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
                 "imip" => {
                     imip_val = Some(
-                        Box::<CalAddress>::try_from_json(val).map_err(|e| field_err("imip", e))?,
+                        Box::<CalAddress>::try_from_json(val).map_err(|e| field_error("imip", e))?,
                     );
                 }
                 "web" => {
                     web_val =
-                        Some(Box::<Uri>::try_from_json(val).map_err(|e| field_err("web", e))?);
+                        Some(Box::<HttpsUrl>::try_from_json(val).map_err(|e| field_error("web", e))?);
                 }
                 other => {
                     // Try to parse value as Uri for other methods
@@ -1561,7 +1749,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for ReplyTo {
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for SendToParticipant {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -1573,11 +1761,10 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for SendToParticipant {
         let mut other_parts: Vec<(String, Box<Uri>)> = Vec::new();
 
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
                 "imip" => {
                     imip_val = Some(
-                        Box::<CalAddress>::try_from_json(val).map_err(|e| field_err("imip", e))?,
+                        Box::<CalAddress>::try_from_json(val).map_err(|e| field_error("imip", e))?,
                     );
                 }
                 other => {
@@ -1606,7 +1793,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for SendToParticipant {
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for Link<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -1618,56 +1805,51 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Link<V> {
         let mut content_id_val: Option<Box<ContentId>> = None;
         let mut media_type_val: Option<Box<MediaType>> = None;
         let mut size_val: Option<UnsignedInt> = None;
-        let mut relation_val: Option<LinkRelation> = None;
+        let mut relation_val: Option<Token<LinkRelation>> = None;
         let mut display_val: Option<Token<DisplayPurpose>> = None;
         let mut title_val: Option<String> = None;
         let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
 
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
                 "@type" => {}
                 "href" => {
                     href_val =
-                        Some(Box::<Uri>::try_from_json(val).map_err(|e| field_err("href", e))?);
+                        Some(Box::<Uri>::try_from_json(val).map_err(|e| field_error("href", e))?);
                 }
                 "contentId" => {
                     content_id_val = Some(
                         Box::<ContentId>::try_from_json(val)
-                            .map_err(|e| field_err("contentId", e))?,
+                            .map_err(|e| field_error("contentId", e))?,
                     );
                 }
                 "mediaType" => {
                     media_type_val = Some(
                         Box::<MediaType>::try_from_json(val)
-                            .map_err(|e| field_err("mediaType", e))?,
+                            .map_err(|e| field_error("mediaType", e))?,
                     );
                 }
                 "size" => {
                     size_val =
-                        Some(UnsignedInt::try_from_json(val).map_err(|e| field_err("size", e))?);
+                        Some(UnsignedInt::try_from_json(val).map_err(|e| field_error("size", e))?);
                 }
                 "rel" => {
-                    let s = val
-                        .try_into_string()
-                        .map_err(|e| type_field_err("rel", e))?;
-                    use std::str::FromStr;
                     relation_val = Some(
-                        LinkRelation::from_str(s.as_ref())
-                            .map_err(|e| field_err("rel", TypeErrorOr::Other(e)))?,
+                        Token::<LinkRelation>::try_from_json(val)
+                            .map_err(|e| field_error("rel", e))?,
                     );
                 }
                 "display" => {
                     display_val = Some(
                         Token::<DisplayPurpose>::try_from_json(val)
-                            .map_err(|e| type_field_err("display", e))?,
+                            .map_err(|e| field_error("display", e))?,
                     );
                 }
                 "title" => {
                     title_val =
-                        Some(String::try_from_json(val).map_err(|e| type_field_err("title", e))?);
+                        Some(String::try_from_json(val).map_err(|e| field_error("title", e))?);
                 }
-                _ => vendor_parts.push((k.into_boxed_str(), val)),
+                _ => vendor_parts.push((<V::Object as JsonObject>::key_into_string(key).into_boxed_str(), val)),
             }
         }
 
@@ -1702,10 +1884,10 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Link<V> {
 // Helper functions for parsing arrays, maps, and sets
 // ============================================================================
 
-fn parse_vec<V, T, F>(value: V, parse_elem: F) -> Result<Vec<T>, ObjErr>
+fn parse_vec<V, T, F>(value: V, parse_elem: F) -> Result<Vec<T>, ObjectError>
 where
     V: DestructibleJsonValue,
-    F: Fn(V) -> Result<T, ObjErr>,
+    F: Fn(V) -> Result<T, ObjectError>,
 {
     let arr = value
         .try_into_array()
@@ -1726,12 +1908,12 @@ fn parse_map<V, K, T, KF, VF>(
     value: V,
     parse_key: KF,
     parse_val: VF,
-) -> Result<HashMap<K, T>, ObjErr>
+) -> Result<HashMap<K, T>, ObjectError>
 where
     V: DestructibleJsonValue,
     K: Eq + Hash,
-    KF: Fn(&str) -> Result<K, ObjErr>,
-    VF: Fn(V) -> Result<T, ObjErr>,
+    KF: Fn(&str) -> Result<K, ObjectError>,
+    VF: Fn(V) -> Result<T, ObjectError>,
 {
     let obj = value
         .try_into_object()
@@ -1739,9 +1921,9 @@ where
         .map_err(DocumentError::root)?;
     let mut out = HashMap::new();
     for (key, val) in obj.into_iter() {
-        let k_str = <V::Object as JsonObject>::key_into_string(key);
-        let k = parse_key(k_str.as_str())?;
+        let k = parse_key(<V::Object as JsonObject>::key_as_str(&key))?;
         let v = parse_val(val).map_err(|mut e| {
+            let k_str = <V::Object as JsonObject>::key_into_string(key);
             e.path
                 .push_front(PathSegment::String(k_str.into_boxed_str()));
             e
@@ -1751,7 +1933,14 @@ where
     Ok(out)
 }
 
-fn parse_id_set<V: DestructibleJsonValue>(value: V) -> Result<HashSet<Box<Id>>, ObjErr> {
+/// Reports a value which failed to convert with `error`.
+fn invalid_value(error: impl Into<FieldValueError>) -> ObjectError {
+    DocumentError::root(TypeErrorOr::Other(ObjectFromJsonError::InvalidFieldValue(
+        error.into(),
+    )))
+}
+
+fn parse_id_set<V: DestructibleJsonValue>(value: V) -> Result<HashSet<Box<Id>>, ObjectError> {
     let arr = value
         .try_into_array()
         .map_err(TypeErrorOr::from)
@@ -1764,18 +1953,13 @@ fn parse_id_set<V: DestructibleJsonValue>(value: V) -> Result<HashSet<Box<Id>>,
         })?;
         let id: Box<Id> = Id::new(s.as_ref())
             .map(Into::into)
-            .map_err(|e| DocumentError {
-                path: [PathSegment::Index(i)].into(),
-                error: TypeErrorOr::Other(ObjectFromJsonError::InvalidFieldValue(
-                    e.to_string().into_boxed_str(),
-                )),
-            })?;
+            .map_err(|e| invalid_value(StringError::new(s.as_ref(), e)).prepend_index(i))?;
         out.insert(id);
     }
     Ok(out)
 }
 
-fn parse_str_set<V: DestructibleJsonValue>(value: V) -> Result<HashSet<Box<str>>, ObjErr> {
+fn parse_str_set<V: DestructibleJsonValue>(value: V) -> Result<HashSet<Box<str>>, ObjectError> {
     let arr = value
         .try_into_array()
         .map_err(TypeErrorOr::from)
@@ -1791,14 +1975,14 @@ fn parse_str_set<V: DestructibleJsonValue>(value: V) -> Result<HashSet<Box<str>>
     Ok(out)
 }
 
-fn rrule_vec<V: DestructibleJsonValue>(value: V) -> Result<Vec<RRule>, ObjErr> {
+fn rrule_vec<V: DestructibleJsonValue>(value: V) -> Result<Vec<RRule>, ObjectError> {
     parse_vec(value, |elem| {
         RRule::try_from_json(elem).map_err(|e| {
             let error = match e.error {
                 TypeErrorOr::TypeError(t) => TypeErrorOr::TypeError(t),
-                TypeErrorOr::Other(re) => TypeErrorOr::Other(
-                    ObjectFromJsonError::InvalidFieldValue(re.to_string().into_boxed_str()),
-                ),
+                TypeErrorOr::Other(re) => {
+                    TypeErrorOr::Other(ObjectFromJsonError::InvalidFieldValue(re.into()))
+                }
             };
             DocumentError {
                 path: e.path,
@@ -1808,19 +1992,46 @@ fn rrule_vec<V: DestructibleJsonValue>(value: V) -> Result<Vec<RRule>, ObjErr> {
     })
 }
 
-fn parse_id_map<V, T, F>(value: V, parse_val: F) -> Result<HashMap<Box<Id>, T>, ObjErr>
+/// Wraps `parse_val` so that objects with an `@type` other than `type_name` are preserved as
+/// [`MaybeUnknown::Unknown`] instead of being parsed.
+fn maybe_unknown<V, T, F>(
+    type_name: &'static str,
+    parse_val: F,
+) -> impl Fn(V) -> Result<MaybeUnknown<T, V>, ObjectError>
+where
+    V: DestructibleJsonValue,
+    F: Fn(V) -> Result<T, ObjectError>,
+{
+    move |value| {
+        let is_unknown = value
+            .try_as_object()
+            .ok()
+            .and_then(|obj| obj.get("@type"))
+            .and_then(|v| v.try_as_string().ok())
+            .is_some_and(|s| s.as_ref() != type_name);
+
+        if is_unknown {
+            let obj = value
+                .try_into_object()
+                .expect("the value was checked to be an object");
+            Ok(MaybeUnknown::Unknown(obj))
+        } else {
+            parse_val(value).map(MaybeUnknown::Known)
+        }
+    }
+}
+
+fn parse_id_map<V, T, F>(value: V, parse_val: F) -> Result<HashMap<Box<Id>, T>, ObjectError>
 where
     V: DestructibleJsonValue,
-    F: Fn(V) -> Result<T, ObjErr>,
+    F: Fn(V) -> Result<T, ObjectError>,
 {
     parse_map(
         value,
         |k| {
-            Id::new(k).map(Box::<Id>::from).map_err(|e| {
-                DocumentError::root(TypeErrorOr::Other(ObjectFromJsonError::InvalidFieldValue(
-                    e.to_string().into_boxed_str(),
-                )))
-            })
+            Id::new(k)
+                .map(Box::<Id>::from)
+                .map_err(|e| invalid_value(StringError::new(k, e)))
         },
         parse_val,
     )
@@ -1829,39 +2040,33 @@ where
 fn parse_tz_map<V, T, F>(
     value: V,
     parse_val: F,
-) -> Result<HashMap<Box<CustomTimeZoneId>, T>, ObjErr>
+) -> Result<HashMap<Box<CustomTimeZoneId>, T>, ObjectError>
 where
     V: DestructibleJsonValue,
-    F: Fn(V) -> Result<T, ObjErr>,
+    F: Fn(V) -> Result<T, ObjectError>,
 {
     parse_map(
         value,
         |k| {
             CustomTimeZoneId::new(k)
                 .map(Box::<CustomTimeZoneId>::from)
-                .map_err(|e| {
-                    DocumentError::root(TypeErrorOr::Other(ObjectFromJsonError::InvalidFieldValue(
-                        e.to_string().into_boxed_str(),
-                    )))
-                })
+                .map_err(|e| invalid_value(StringError::new(k, e)))
         },
         parse_val,
     )
 }
 
-fn parse_uid_map<V, T, F>(value: V, parse_val: F) -> Result<HashMap<Box<Uid>, T>, ObjErr>
+fn parse_uid_map<V, T, F>(value: V, parse_val: F) -> Result<HashMap<Box<Uid>, T>, ObjectError>
 where
     V: DestructibleJsonValue,
-    F: Fn(V) -> Result<T, ObjErr>,
+    F: Fn(V) -> Result<T, ObjectError>,
 {
     parse_map(
         value,
         |k| {
-            Uid::new(k).map(Box::<Uid>::from).map_err(|e| {
-                DocumentError::root(TypeErrorOr::Other(ObjectFromJsonError::InvalidFieldValue(
-                    e.to_string().into_boxed_str(),
-                )))
-            })
+            Uid::new(k)
+                .map(Box::<Uid>::from)
+                .map_err(|e| invalid_value(StringError::new(k, e)))
         },
         parse_val,
     )
@@ -1870,72 +2075,59 @@ where
 fn parse_dt_local_map<V, T, F>(
     value: V,
     parse_val: F,
-) -> Result<HashMap<DateTime<Local>, T>, ObjErr>
+) -> Result<HashMap<DateTime<Local>, T>, ObjectError>
 where
     V: DestructibleJsonValue,
-    F: Fn(V) -> Result<T, ObjErr>,
+    F: Fn(V) -> Result<T, ObjectError>,
 {
     parse_map(
         value,
         |k| {
-            crate::parser::parse_full(crate::parser::local_date_time)(k).map_err(|_| {
-                DocumentError::root(TypeErrorOr::Other(ObjectFromJsonError::InvalidFieldValue(
-                    format!("invalid local datetime key: {k:?}").into_boxed_str(),
-                )))
-            })
+            crate::parser::parse_full(crate::parser::local_date_time)(k).map_err(invalid_value)
         },
         parse_val,
     )
 }
 
-fn parse_lang_map<V, T, F>(value: V, parse_val: F) -> Result<HashMap<LanguageTag, T>, ObjErr>
+fn parse_lang_map<V, T, F>(value: V, parse_val: F) -> Result<HashMap<LanguageTag, T>, ObjectError>
 where
     V: DestructibleJsonValue,
-    F: Fn(V) -> Result<T, ObjErr>,
+    F: Fn(V) -> Result<T, ObjectError>,
 {
     parse_map(
         value,
         |k| {
-            LanguageTag::parse(k).map_err(|e| {
-                DocumentError::root(TypeErrorOr::Other(ObjectFromJsonError::InvalidFieldValue(
-                    e.to_string().into_boxed_str(),
-                )))
-            })
+            LanguageTag::parse(k).map_err(|e| invalid_value(StringError::new(k, e)))
         },
         parse_val,
     )
 }
 
-fn parse_status_code_vec<V: DestructibleJsonValue>(value: V) -> Result<Vec<StatusCode>, ObjErr> {
+fn parse_status_code_vec<V: DestructibleJsonValue>(value: V) -> Result<Vec<StatusCode>, ObjectError> {
     parse_vec(value, |elem| {
         StatusCode::try_from_json(elem).map_err(|e| {
-            let error = match e {
-                TypeErrorOr::TypeError(t) => TypeErrorOr::TypeError(t),
-                TypeErrorOr::Other(se) => TypeErrorOr::Other(
-                    ObjectFromJsonError::InvalidFieldValue(se.to_string().into_boxed_str()),
-                ),
-            };
-            DocumentError::root(error)
+            match e {
+                TypeErrorOr::TypeError(t) => DocumentError::root(TypeErrorOr::TypeError(t)),
+                TypeErrorOr::Other(se) => invalid_value(se),
+            }
         })
     })
 }
 
-fn patch_object_from_json<V: DestructibleJsonValue>(value: V) -> Result<PatchObject<V>, ObjErr> {
+fn patch_object_from_json<V: DestructibleJsonValue>(value: V) -> Result<PatchObject<V>, ObjectError> {
     PatchObject::try_from_json(value).map_err(|e| match e {
         TypeErrorOr::TypeError(t) => DocumentError::root(TypeErrorOr::TypeError(t)),
         TypeErrorOr::Other(patch_err) => {
             let doc = patch_err.into_document_error();
             DocumentError {
                 path: doc.path,
-                error: TypeErrorOr::Other(ObjectFromJsonError::InvalidFieldValue(
-                    doc.error.to_string().into_boxed_str(),
-                )),
+                error: TypeErrorOr::Other(ObjectFromJsonError::InvalidFieldValue(doc.error.into())),
             }
         }
     })
 }
 
-fn parse_str_vec<V: DestructibleJsonValue>(value: V) -> Result<Vec<String>, ObjErr> {
+fn parse_str_vec<V: DestructibleJsonValue>(value: V) -> Result<Vec<String>, ObjectError> {
     parse_vec(value, |elem| {
         String::try_from_json(elem).map_err(|e| DocumentError::root(TypeErrorOr::TypeError(e)))
     })
@@ -1946,7 +2138,7 @@ fn parse_str_vec<V: DestructibleJsonValue>(value: V) -> Result<Vec<String>, ObjE
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for Location<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -1956,55 +2148,55 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Location<V> {
 
         let mut name_val: Option<String> = None;
         let mut description_val: Option<String> = None;
-        let mut location_types_val: Option<HashSet<LocationType>> = None;
+        let mut location_types_val: Option<HashSet<Token<LocationType>>> = None;
         let mut relative_to_val: Option<Token<RelationValue>> = None;
         let mut time_zone_val: Option<String> = None;
         let mut coordinates_val: Option<Box<GeoUri>> = None;
-        let mut links_val: Option<HashMap<Box<Id>, Link<V>>> = None;
+        let mut links_val: Option<LinkMap<V>> = None;
         let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
 
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
                 "@type" => {}
                 "name" => {
                     name_val =
-                        Some(String::try_from_json(val).map_err(|e| type_field_err("name", e))?);
+                        Some(String::try_from_json(val).map_err(|e| field_error("name", e))?);
                 }
                 "description" => {
                     description_val = Some(
-                        String::try_from_json(val).map_err(|e| type_field_err("description", e))?,
+                        String::try_from_json(val).map_err(|e| field_error("description", e))?,
                     );
                 }
                 "locationTypes" => {
                     location_types_val = Some(
-                        HashSet::<LocationType>::try_from_json(val)
-                            .map_err(|e| doc_field_err("locationTypes", e))?,
+                        HashSet::<Token<LocationType>>::try_from_json(val)
+                            .map_err(|e| nested_field_error("locationTypes", e))?,
                     );
                 }
                 "relativeTo" => {
                     relative_to_val = Some(
                         Token::<RelationValue>::try_from_json(val)
-                            .map_err(|e| type_field_err("relativeTo", e))?,
+                            .map_err(|e| field_error("relativeTo", e))?,
                     );
                 }
                 "timeZone" => {
                     time_zone_val = Some(
-                        String::try_from_json(val).map_err(|e| type_field_err("timeZone", e))?,
+                        String::try_from_json(val).map_err(|e| field_error("timeZone", e))?,
                     );
                 }
                 "coordinates" => {
                     coordinates_val = Some(
                         Box::<GeoUri>::try_from_json(val)
-                            .map_err(|e| field_err("coordinates", e))?,
+                            .map_err(|e| field_error("coordinates", e))?,
                     );
                 }
                 "links" => {
                     links_val = Some(
-                        parse_id_map(val, Link::try_from_json).map_err(|e| prepend("links", e))?,
+                        parse_id_map(val, maybe_unknown("Link", Link::try_from_json))
+                            .map_err(|e| prepend("links", e))?,
                     );
                 }
-                _ => vendor_parts.push((k.into_boxed_str(), val)),
+                _ => vendor_parts.push((<V::Object as JsonObject>::key_into_string(key).into_boxed_str(), val)),
             }
         }
 
@@ -2042,7 +2234,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Location<V> {
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for VirtualLocation<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -2057,29 +2249,28 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for VirtualLocation<V> {
         let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
 
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
                 "@type" => {}
                 "name" => {
                     name_val =
-                        Some(String::try_from_json(val).map_err(|e| type_field_err("name", e))?);
+                        Some(String::try_from_json(val).map_err(|e| field_error("name", e))?);
                 }
                 "description" => {
                     description_val = Some(
-                        String::try_from_json(val).map_err(|e| type_field_err("description", e))?,
+                        String::try_from_json(val).map_err(|e| field_error("description", e))?,
                     );
                 }
                 "uri" => {
                     uri_val =
-                        Some(Box::<Uri>::try_from_json(val).map_err(|e| field_err("uri", e))?);
+                        Some(Box::<Uri>::try_from_json(val).map_err(|e| field_error("uri", e))?);
                 }
                 "features" => {
                     features_val = Some(
                         HashSet::<Token<VirtualLocationFeature>>::try_from_json(val)
-                            .map_err(|e| doc_field_err("features", e))?,
+                            .map_err(|e| nested_field_error("features", e))?,
                     );
                 }
-                _ => vendor_parts.push((k.into_boxed_str(), val)),
+                _ => vendor_parts.push((<V::Object as JsonObject>::key_into_string(key).into_boxed_str(), val)),
             }
         }
 
@@ -2106,7 +2297,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for VirtualLocation<V> {
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for Alert<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -2121,8 +2312,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Alert<V> {
         let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
 
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
                 "@type" => {}
                 "trigger" => {
                     trigger_val =
@@ -2131,7 +2321,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Alert<V> {
                 "acknowledged" => {
                     acknowledged_val = Some(
                         DateTime::<Utc>::try_from_json(val)
-                            .map_err(|e| field_err("acknowledged", e))?,
+                            .map_err(|e| field_error("acknowledged", e))?,
                     );
                 }
                 "relatedTo" => {
@@ -2143,10 +2333,10 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Alert<V> {
                 "action" => {
                     action_val = Some(
                         Token::<AlertAction>::try_from_json(val)
-                            .map_err(|e| type_field_err("action", e))?,
+                            .map_err(|e| field_error("action", e))?,
                     );
                 }
-                _ => vendor_parts.push((k.into_boxed_str(), val)),
+                _ => vendor_parts.push((<V::Object as JsonObject>::key_into_string(key).into_boxed_str(), val)),
             }
         }
 
@@ -2173,7 +2363,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Alert<V> {
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for TimeZoneRule<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -2191,22 +2381,21 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for TimeZoneRule<V> {
         let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
 
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
                 "@type" => {}
                 "start" => {
                     start_val = Some(
-                        DateTime::<Local>::try_from_json(val).map_err(|e| field_err("start", e))?,
+                        DateTime::<Local>::try_from_json(val).map_err(|e| field_error("start", e))?,
                     );
                 }
                 "offsetFrom" => {
                     offset_from_val = Some(
-                        UtcOffset::try_from_json(val).map_err(|e| field_err("offsetFrom", e))?,
+                        UtcOffset::try_from_json(val).map_err(|e| field_error("offsetFrom", e))?,
                     );
                 }
                 "offsetTo" => {
                     offset_to_val =
-                        Some(UtcOffset::try_from_json(val).map_err(|e| field_err("offsetTo", e))?);
+                        Some(UtcOffset::try_from_json(val).map_err(|e| field_error("offsetTo", e))?);
                 }
                 "recurrenceRules" => {
                     recurrence_rules_val =
@@ -2221,13 +2410,13 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for TimeZoneRule<V> {
                 "names" => {
                     names_val = Some(
                         HashSet::<String>::try_from_json(val)
-                            .map_err(|e| doc_field_err("names", e))?,
+                            .map_err(|e| nested_field_error("names", e))?,
                     );
                 }
                 "comments" => {
                     comments_val = Some(parse_str_vec(val).map_err(|e| prepend("comments", e))?);
                 }
-                _ => vendor_parts.push((k.into_boxed_str(), val)),
+                _ => vendor_parts.push((<V::Object as JsonObject>::key_into_string(key).into_boxed_str(), val)),
             }
         }
 
@@ -2259,7 +2448,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for TimeZoneRule<V> {
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for TimeZone<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -2277,26 +2466,25 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for TimeZone<V> {
         let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
 
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
                 "@type" => {}
                 "tzId" => {
                     tz_id_val =
-                        Some(String::try_from_json(val).map_err(|e| type_field_err("tzId", e))?);
+                        Some(String::try_from_json(val).map_err(|e| field_error("tzId", e))?);
                 }
                 "updated" => {
                     updated_val = Some(
-                        DateTime::<Utc>::try_from_json(val).map_err(|e| field_err("updated", e))?,
+                        DateTime::<Utc>::try_from_json(val).map_err(|e| field_error("updated", e))?,
                     );
                 }
                 "url" => {
                     url_val =
-                        Some(Box::<Uri>::try_from_json(val).map_err(|e| field_err("url", e))?);
+                        Some(Box::<Uri>::try_from_json(val).map_err(|e| field_error("url", e))?);
                 }
                 "validUntil" => {
                     valid_until_val = Some(
                         DateTime::<Utc>::try_from_json(val)
-                            .map_err(|e| field_err("validUntil", e))?,
+                            .map_err(|e| field_error("validUntil", e))?,
                     );
                 }
                 "aliases" => {
@@ -2314,7 +2502,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for TimeZone<V> {
                             .map_err(|e| prepend("daylight", e))?,
                     );
                 }
-                _ => vendor_parts.push((k.into_boxed_str(), val)),
+                _ => vendor_parts.push((<V::Object as JsonObject>::key_into_string(key).into_boxed_str(), val)),
             }
         }
 
@@ -2353,7 +2541,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for TimeZone<V> {
 // struct type to use for the argument?
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for Participant<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -2366,7 +2554,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Participant<V> {
         let mut description_val: Option<String> = None;
         let mut send_to_val: Option<SendToParticipant> = None;
         let mut kind_val: Option<Token<ParticipantKind>> = None;
-        let mut roles_val: Option<HashSet<Token<ParticipantRole>>> = None;
+        let mut roles_val: Option<ParticipantRoleSet> = None;
         let mut location_id_val: Option<Box<Id>> = None;
         let mut language_val: Option<LanguageTag> = None;
         let mut participation_status_val: Option<Token<ParticipationStatus>> = None;
@@ -2382,23 +2570,22 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Participant<V> {
         let mut delegated_to_val: Option<HashSet<Box<Id>>> = None;
         let mut delegated_from_val: Option<HashSet<Box<Id>>> = None;
         let mut member_of_val: Option<HashSet<Box<Id>>> = None;
-        let mut links_val: Option<HashMap<Box<Id>, Link<V>>> = None;
+        let mut links_val: Option<LinkMap<V>> = None;
         let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
 
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
                 "@type" => {}
                 "name" => {
-                    name_val = Some(String::try_from_json(val).map_err(|e| type_field_err("name", e))?);
+                    name_val = Some(String::try_from_json(val).map_err(|e| field_error("name", e))?);
                 }
                 "email" => {
                     email_val =
-                        Some(Box::<EmailAddr>::try_from_json(val).map_err(|e| field_err("email", e))?);
+                        Some(Box::<EmailAddr>::try_from_json(val).map_err(|e| field_error("email", e))?);
                 }
                 "description" => {
                     description_val =
-                        Some(String::try_from_json(val).map_err(|e| type_field_err("description", e))?);
+                        Some(String::try_from_json(val).map_err(|e| field_error("description", e))?);
                 }
                 "sendTo" => {
                     send_to_val =
@@ -2407,52 +2594,52 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Participant<V> {
                 "kind" => {
                     kind_val = Some(
                         Token::<ParticipantKind>::try_from_json(val)
-                            .map_err(|e| type_field_err("kind", e))?,
+                            .map_err(|e| field_error("kind", e))?,
                     );
                 }
                 "roles" => {
                     roles_val = Some(
-                        HashSet::<Token<ParticipantRole>>::try_from_json(val)
-                            .map_err(|e| doc_field_err("roles", e))?,
+                        ParticipantRoleSet::try_from_json(val)
+                            .map_err(|e| nested_field_error("roles", e))?,
                     );
                 }
                 "locationId" => {
                     location_id_val =
-                        Some(Box::<Id>::try_from_json(val).map_err(|e| field_err("locationId", e))?);
+                        Some(Box::<Id>::try_from_json(val).map_err(|e| field_error("locationId", e))?);
                 }
                 "language" => {
                     language_val =
-                        Some(LanguageTag::try_from_json(val).map_err(|e| field_err("language", e))?);
+                        Some(LanguageTag::try_from_json(val).map_err(|e| field_error("language", e))?);
                 }
                 "participationStatus" => {
                     participation_status_val = Some(
                         Token::<ParticipationStatus>::try_from_json(val)
-                            .map_err(|e| type_field_err("participationStatus", e))?,
+                            .map_err(|e| field_error("participationStatus", e))?,
                     );
                 }
                 "participationComment" => {
                     participation_comment_val = Some(
                         String::try_from_json(val)
-                            .map_err(|e| type_field_err("participationComment", e))?,
+                            .map_err(|e| field_error("participationComment", e))?,
                     );
                 }
                 "expectReply" => {
                     expect_reply_val =
-                        Some(bool::try_from_json(val).map_err(|e| type_field_err("expectReply", e))?);
+                        Some(bool::try_from_json(val).map_err(|e| field_error("expectReply", e))?);
                 }
                 "scheduleAgent" => {
                     schedule_agent_val = Some(
                         Token::<ScheduleAgent>::try_from_json(val)
-                            .map_err(|e| type_field_err("scheduleAgent", e))?,
+                            .map_err(|e| field_error("scheduleAgent", e))?,
                     );
                 }
                 "scheduleForceSend" => {
                     schedule_force_send_val =
-                        Some(bool::try_from_json(val).map_err(|e| type_field_err("scheduleForceSend", e))?);
+                        Some(bool::try_from_json(val).map_err(|e| field_error("scheduleForceSend", e))?);
                 }
                 "scheduleSequence" => {
                     schedule_sequence_val = Some(
-                        UnsignedInt::try_from_json(val).map_err(|e| field_err("scheduleSequence", e))?,
+                        UnsignedInt::try_from_json(val).map_err(|e| field_error("scheduleSequence", e))?,
                     );
                 }
                 "scheduleStatus" => {
@@ -2461,16 +2648,16 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Participant<V> {
                 }
                 "scheduleUpdated" => {
                     schedule_updated_val = Some(
-                        DateTime::<Utc>::try_from_json(val).map_err(|e| field_err("scheduleUpdated", e))?,
+                        DateTime::<Utc>::try_from_json(val).map_err(|e| field_error("scheduleUpdated", e))?,
                     );
                 }
                 "sentBy" => {
                     sent_by_val =
-                        Some(Box::<EmailAddr>::try_from_json(val).map_err(|e| field_err("sentBy", e))?);
+                        Some(Box::<EmailAddr>::try_from_json(val).map_err(|e| field_error("sentBy", e))?);
                 }
                 "invitedBy" => {
                     invited_by_val =
-                        Some(Box::<Id>::try_from_json(val).map_err(|e| field_err("invitedBy", e))?);
+                        Some(Box::<Id>::try_from_json(val).map_err(|e| field_error("invitedBy", e))?);
                 }
                 "delegatedTo" => {
                     delegated_to_val = Some(parse_id_set(val).map_err(|e| prepend("delegatedTo", e))?);
@@ -2482,10 +2669,12 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Participant<V> {
                     member_of_val = Some(parse_id_set(val).map_err(|e| prepend("memberOf", e))?);
                 }
                 "links" => {
-                    links_val =
-                        Some(parse_id_map(val, Link::try_from_json).map_err(|e| prepend("links", e))?);
+                    links_val = Some(
+                        parse_id_map(val, maybe_unknown("Link", Link::try_from_json))
+                            .map_err(|e| prepend("links", e))?,
+                    );
                 }
-                _ => vendor_parts.push((k.into_boxed_str(), val)),
+                _ => vendor_parts.push((<V::Object as JsonObject>::key_into_string(key).into_boxed_str(), val)),
                 }
         }
 
@@ -2568,7 +2757,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Participant<V> {
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for TaskParticipant<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -2581,7 +2770,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for TaskParticipant<V> {
         let mut description_val: Option<String> = None;
         let mut send_to_val: Option<SendToParticipant> = None;
         let mut kind_val: Option<Token<ParticipantKind>> = None;
-        let mut roles_val: Option<HashSet<Token<ParticipantRole>>> = None;
+        let mut roles_val: Option<ParticipantRoleSet> = None;
         let mut location_id_val: Option<Box<Id>> = None;
         let mut language_val: Option<LanguageTag> = None;
         let mut participation_status_val: Option<Token<ParticipationStatus>> = None;
@@ -2597,43 +2786,42 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for TaskParticipant<V> {
         let mut delegated_to_val: Option<HashSet<Box<Id>>> = None;
         let mut delegated_from_val: Option<HashSet<Box<Id>>> = None;
         let mut member_of_val: Option<HashSet<Box<Id>>> = None;
-        let mut links_val: Option<HashMap<Box<Id>, Link<V>>> = None;
+        let mut links_val: Option<LinkMap<V>> = None;
         let mut progress_val: Option<Token<TaskProgress>> = None;
         let mut progress_updated_val: Option<DateTime<Utc>> = None;
         let mut percent_complete_val: Option<Percent> = None;
         let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
 
         for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
                 "@type" => {}
                 "progress" => {
                     progress_val = Some(
                         Token::<TaskProgress>::try_from_json(val)
-                            .map_err(|e| type_field_err("progress", e))?,
+                            .map_err(|e| field_error("progress", e))?,
                     );
                 }
                 "progressUpdated" => {
                     progress_updated_val = Some(
                         DateTime::<Utc>::try_from_json(val)
-                            .map_err(|e| field_err("progressUpdated", e))?,
+                            .map_err(|e| field_error("progressUpdated", e))?,
                     );
                 }
                 "percentComplete" => {
                     percent_complete_val = Some(
-                        Percent::try_from_json(val).map_err(|e| field_err("percentComplete", e))?,
+                        Percent::try_from_json(val).map_err(|e| field_error("percentComplete", e))?,
                     );
                 }
                 "name" => {
-                    name_val = Some(String::try_from_json(val).map_err(|e| type_field_err("name", e))?);
+                    name_val = Some(String::try_from_json(val).map_err(|e| field_error("name", e))?);
                 }
                 "email" => {
                     email_val =
-                        Some(Box::<EmailAddr>::try_from_json(val).map_err(|e| field_err("email", e))?);
+                        Some(Box::<EmailAddr>::try_from_json(val).map_err(|e| field_error("email", e))?);
                 }
                 "description" => {
                     description_val =
-                        Some(String::try_from_json(val).map_err(|e| type_field_err("description", e))?);
+                        Some(String::try_from_json(val).map_err(|e| field_error("description", e))?);
                 }
                 "sendTo" => {
                     send_to_val =
@@ -2642,52 +2830,52 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for TaskParticipant<V> {
                 "kind" => {
                     kind_val = Some(
                         Token::<ParticipantKind>::try_from_json(val)
-                            .map_err(|e| type_field_err("kind", e))?,
+                            .map_err(|e| field_error("kind", e))?,
                     );
                 }
                 "roles" => {
                     roles_val = Some(
-                        HashSet::<Token<ParticipantRole>>::try_from_json(val)
-                            .map_err(|e| doc_field_err("roles", e))?,
+                        ParticipantRoleSet::try_from_json(val)
+                            .map_err(|e| nested_field_error("roles", e))?,
                     );
                 }
                 "locationId" => {
                     location_id_val =
-                        Some(Box::<Id>::try_from_json(val).map_err(|e| field_err("locationId", e))?);
+                        Some(Box::<Id>::try_from_json(val).map_err(|e| field_error("locationId", e))?);
                 }
                 "language" => {
                     language_val =
-                        Some(LanguageTag::try_from_json(val).map_err(|e| field_err("language", e))?);
+                        Some(LanguageTag::try_from_json(val).map_err(|e| field_error("language", e))?);
                 }
                 "participationStatus" => {
                     participation_status_val = Some(
                         Token::<ParticipationStatus>::try_from_json(val)
-                            .map_err(|e| type_field_err("participationStatus", e))?,
+                            .map_err(|e| field_error("participationStatus", e))?,
                     );
                 }
                 "participationComment" => {
                     participation_comment_val = Some(
                         String::try_from_json(val)
-                            .map_err(|e| type_field_err("participationComment", e))?,
+                            .map_err(|e| field_error("participationComment", e))?,
                     );
                 }
                 "expectReply" => {
                     expect_reply_val =
-                        Some(bool::try_from_json(val).map_err(|e| type_field_err("expectReply", e))?);
+                        Some(bool::try_from_json(val).map_err(|e| field_error("expectReply", e))?);
                 }
                 "scheduleAgent" => {
                     schedule_agent_val = Some(
                         Token::<ScheduleAgent>::try_from_json(val)
-                            .map_err(|e| type_field_err("scheduleAgent", e))?,
+                            .map_err(|e| field_error("scheduleAgent", e))?,
                     );
                 }
                 "scheduleForceSend" => {
                     schedule_force_send_val =
-                        Some(bool::try_from_json(val).map_err(|e| type_field_err("scheduleForceSend", e))?);
+                        Some(bool::try_from_json(val).map_err(|e| field_error("scheduleForceSend", e))?);
                 }
                 "scheduleSequence" => {
                     schedule_sequence_val = Some(
-                        UnsignedInt::try_from_json(val).map_err(|e| field_err("scheduleSequence", e))?,
+                        UnsignedInt::try_from_json(val).map_err(|e| field_error("scheduleSequence", e))?,
                     );
                 }
                 "scheduleStatus" => {
@@ -2696,16 +2884,16 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for TaskParticipant<V> {
                 }
                 "scheduleUpdated" => {
                     schedule_updated_val = Some(
-                        DateTime::<Utc>::try_from_json(val).map_err(|e| field_err("scheduleUpdated", e))?,
+                        DateTime::<Utc>::try_from_json(val).map_err(|e| field_error("scheduleUpdated", e))?,
                     );
                 }
                 "sentBy" => {
                     sent_by_val =
-                        Some(Box::<EmailAddr>::try_from_json(val).map_err(|e| field_err("sentBy", e))?);
+                        Some(Box::<EmailAddr>::try_from_json(val).map_err(|e| field_error("sentBy", e))?);
                 }
                 "invitedBy" => {
                     invited_by_val =
-                        Some(Box::<Id>::try_from_json(val).map_err(|e| field_err("invitedBy", e))?);
+                        Some(Box::<Id>::try_from_json(val).map_err(|e| field_error("invitedBy", e))?);
                 }
                 "delegatedTo" => {
                     delegated_to_val = Some(parse_id_set(val).map_err(|e| prepend("delegatedTo", e))?);
@@ -2717,10 +2905,12 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for TaskParticipant<V> {
                     member_of_val = Some(parse_id_set(val).map_err(|e| prepend("memberOf", e))?);
                 }
                 "links" => {
-                    links_val =
-                        Some(parse_id_map(val, Link::try_from_json).map_err(|e| prepend("links", e))?);
+                    links_val = Some(
+                        parse_id_map(val, maybe_unknown("Link", Link::try_from_json))
+                            .map_err(|e| prepend("links", e))?,
+                    );
                 }
-                _ => vendor_parts.push((k.into_boxed_str(), val)),
+                _ => vendor_parts.push((<V::Object as JsonObject>::key_into_string(key).into_boxed_str(), val)),
                 }
         }
 
@@ -2812,7 +3002,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for TaskParticipant<V> {
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for Event<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -2835,9 +3025,9 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Event<V> {
             let mut description_val: Option<String> = None;
             let mut description_content_type_val: Option<String> = None;
             let mut show_without_time_val: Option<bool> = None;
-            let mut locations_val: Option<HashMap<Box<Id>, Location<V>>> = None;
-            let mut virtual_locations_val: Option<HashMap<Box<Id>, VirtualLocation<V>>> = None;
-            let mut links_val: Option<HashMap<Box<Id>, Link<V>>> = None;
+            let mut locations_val: Option<LocationMap<V>> = None;
+            let mut virtual_locations_val: Option<VirtualLocationMap<V>> = None;
+            let mut links_val: Option<LinkMap<V>> = None;
             let mut locale_val: Option<LanguageTag> = None;
             let mut keywords_val: Option<HashSet<String>> = None;
             let mut categories_val: Option<HashSet<String>> = None;
@@ -2856,32 +3046,31 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Event<V> {
             let mut participants_val: Option<HashMap<Box<Id>, Participant<V>>> = None;
             let mut request_status_val: Option<RequestStatus> = None;
             let mut use_default_alerts_val: Option<bool> = None;
-            let mut alerts_val: Option<HashMap<Box<Id>, Alert<V>>> = None;
+            let mut alerts_val: Option<AlertMap<V>> = None;
             let mut localizations_val: Option<HashMap<LanguageTag, PatchObject<V>>> = None;
             let mut time_zone_val: Option<String> = None;
             let mut time_zones_val: Option<HashMap<Box<CustomTimeZoneId>, TimeZone<V>>> = None;
             let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
 
             for (key, val) in obj.into_iter() {
-                let k = <V::Object as JsonObject>::key_into_string(key);
-                match k.as_str() {
+                match <V::Object as JsonObject>::key_as_str(&key) {
                     "@type" => {}
                     "start" => {
                         start_val =
-                            Some(DateTime::<Local>::try_from_json(val).map_err(|e| field_err("start", e))?);
+                            Some(DateTime::<Local>::try_from_json(val).map_err(|e| field_error("start", e))?);
                     }
                     "duration" => {
                         duration_val =
-                            Some(Duration::try_from_json(val).map_err(|e| field_err("duration", e))?);
+                            Some(Duration::try_from_json(val).map_err(|e| field_error("duration", e))?);
                     }
                     "status" => {
                         status_val = Some(
                             Token::<EventStatus>::try_from_json(val)
-                                .map_err(|e| type_field_err("status", e))?,
+                                .map_err(|e| field_error("status", e))?,
                         );
                     }
                     "uid" => {
-                        uid_val = Some(Box::<Uid>::try_from_json(val).map_err(|e| field_err("uid", e))?);
+                        uid_val = Some(Box::<Uid>::try_from_json(val).map_err(|e| field_error("uid", e))?);
                     }
                     "relatedTo" => {
                         related_to_val = Some(
@@ -2891,89 +3080,91 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Event<V> {
                     }
                     "prodId" => {
                         prod_id_val =
-                            Some(String::try_from_json(val).map_err(|e| type_field_err("prodId", e))?);
+                            Some(String::try_from_json(val).map_err(|e| field_error("prodId", e))?);
                     }
                     "created" => {
                         created_val =
-                            Some(DateTime::<Utc>::try_from_json(val).map_err(|e| field_err("created", e))?);
+                            Some(DateTime::<Utc>::try_from_json(val).map_err(|e| field_error("created", e))?);
                     }
                     "updated" => {
                         updated_val =
-                            Some(DateTime::<Utc>::try_from_json(val).map_err(|e| field_err("updated", e))?);
+                            Some(DateTime::<Utc>::try_from_json(val).map_err(|e| field_error("updated", e))?);
                     }
                     "sequence" => {
                         sequence_val =
-                            Some(UnsignedInt::try_from_json(val).map_err(|e| field_err("sequence", e))?);
+                            Some(UnsignedInt::try_from_json(val).map_err(|e| field_error("sequence", e))?);
                     }
                     "method" => {
                         method_val = Some(
-                            Token::<Method>::try_from_json(val).map_err(|e| type_field_err("method", e))?,
+                            Token::<Method>::try_from_json(val).map_err(|e| field_error("method", e))?,
                         );
                     }
                     "title" => {
                         title_val =
-                            Some(String::try_from_json(val).map_err(|e| type_field_err("title", e))?);
+                            Some(String::try_from_json(val).map_err(|e| field_error("title", e))?);
                     }
                     "description" => {
                         description_val =
-                            Some(String::try_from_json(val).map_err(|e| type_field_err("description", e))?);
+                            Some(String::try_from_json(val).map_err(|e| field_error("description", e))?);
                     }
                     "descriptionContentType" => {
                         description_content_type_val = Some(
                             String::try_from_json(val)
-                                .map_err(|e| type_field_err("descriptionContentType", e))?,
+                                .map_err(|e| field_error("descriptionContentType", e))?,
                         );
                     }
                     "showWithoutTime" => {
                         show_without_time_val = Some(
-                            bool::try_from_json(val).map_err(|e| type_field_err("showWithoutTime", e))?,
+                            bool::try_from_json(val).map_err(|e| field_error("showWithoutTime", e))?,
                         );
                     }
                     "locations" => {
                         locations_val = Some(
-                            parse_id_map(val, Location::try_from_json)
+                            parse_id_map(val, maybe_unknown("Location", Location::try_from_json))
                                 .map_err(|e| prepend("locations", e))?,
                         );
                     }
                     "virtualLocations" => {
                         virtual_locations_val = Some(
-                            parse_id_map(val, VirtualLocation::try_from_json)
+                            parse_id_map(val, maybe_unknown("VirtualLocation", VirtualLocation::try_from_json))
                                 .map_err(|e| prepend("virtualLocations", e))?,
                         );
                     }
                     "links" => {
-                        links_val =
-                            Some(parse_id_map(val, Link::try_from_json).map_err(|e| prepend("links", e))?);
+                        links_val = Some(
+                            parse_id_map(val, maybe_unknown("Link", Link::try_from_json))
+                                .map_err(|e| prepend("links", e))?,
+                        );
                     }
                     "locale" => {
                         locale_val =
-                            Some(LanguageTag::try_from_json(val).map_err(|e| field_err("locale", e))?);
+                            Some(LanguageTag::try_from_json(val).map_err(|e| field_error("locale", e))?);
                     }
                     "keywords" => {
                         keywords_val = Some(
                             HashSet::<String>::try_from_json(val)
-                                .map_err(|e| doc_field_err("keywords", e))?,
+                                .map_err(|e| nested_field_error("keywords", e))?,
                         );
                     }
                     "categories" => {
                         categories_val = Some(
                             HashSet::<String>::try_from_json(val)
-                                .map_err(|e| doc_field_err("categories", e))?,
+                                .map_err(|e| nested_field_error("categories", e))?,
                         );
                     }
                     "color" => {
-                        color_val = Some(Color::try_from_json(val).map_err(|e| field_err("color", e))?);
+                        color_val = Some(Color::try_from_json(val).map_err(|e| field_error("color", e))?);
                     }
                     "recurrenceId" => {
                         recurrence_id_val = Some(
                             DateTime::<Local>::try_from_json(val)
-                                .map_err(|e| field_err("recurrenceId", e))?,
+                                .map_err(|e| field_error("recurrenceId", e))?,
                         );
                     }
                     "recurrenceIdTimeZone" => {
                         recurrence_id_time_zone_val = Some(
                             String::try_from_json(val)
-                                .map_err(|e| type_field_err("recurrenceIdTimeZone", e))?,
+                                .map_err(|e| field_error("recurrenceIdTimeZone", e))?,
                         );
                     }
                     "recurrenceRules" => {
@@ -2992,22 +3183,22 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Event<V> {
                     }
                     "excluded" => {
                         excluded_val =
-                            Some(bool::try_from_json(val).map_err(|e| type_field_err("excluded", e))?);
+                            Some(bool::try_from_json(val).map_err(|e| field_error("excluded", e))?);
                     }
                     "priority" => {
                         priority_val =
-                            Some(Priority::try_from_json(val).map_err(|e| field_err("priority", e))?);
+                            Some(Priority::try_from_json(val).map_err(|e| field_error("priority", e))?);
                     }
                     "freeBusyStatus" => {
                         free_busy_status_val = Some(
                             Token::<FreeBusyStatus>::try_from_json(val)
-                                .map_err(|e| type_field_err("freeBusyStatus", e))?,
+                                .map_err(|e| field_error("freeBusyStatus", e))?,
                         );
                     }
                     "privacy" => {
                         privacy_val = Some(
                             Token::<Privacy>::try_from_json(val)
-                                .map_err(|e| type_field_err("privacy", e))?,
+                                .map_err(|e| field_error("privacy", e))?,
                         );
                     }
                     "replyTo" => {
@@ -3016,7 +3207,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Event<V> {
                     }
                     "sentBy" => {
                         sent_by_val = Some(
-                            Box::<CalAddress>::try_from_json(val).map_err(|e| field_err("sentBy", e))?,
+                            Box::<CalAddress>::try_from_json(val).map_err(|e| field_error("sentBy", e))?,
                         );
                     }
                     "participants" => {
@@ -3027,17 +3218,18 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Event<V> {
                     }
                     "requestStatus" => {
                         request_status_val = Some(
-                            RequestStatus::try_from_json(val).map_err(|e| field_err("requestStatus", e))?,
+                            RequestStatus::try_from_json(val).map_err(|e| field_error("requestStatus", e))?,
                         );
                     }
                     "useDefaultAlerts" => {
                         use_default_alerts_val = Some(
-                            bool::try_from_json(val).map_err(|e| type_field_err("useDefaultAlerts", e))?,
+                            bool::try_from_json(val).map_err(|e| field_error("useDefaultAlerts", e))?,
                         );
                     }
                     "alerts" => {
                         alerts_val = Some(
-                            parse_id_map(val, Alert::try_from_json).map_err(|e| prepend("alerts", e))?,
+                            parse_id_map(val, maybe_unknown("Alert", Alert::try_from_json))
+                                .map_err(|e| prepend("alerts", e))?,
                         );
                     }
                     "localizations" => {
@@ -3048,7 +3240,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Event<V> {
                     }
                     "timeZone" => {
                         time_zone_val =
-                            Some(String::try_from_json(val).map_err(|e| type_field_err("timeZone", e))?);
+                            Some(String::try_from_json(val).map_err(|e| field_error("timeZone", e))?);
                     }
                     "timeZones" => {
                         time_zones_val = Some(
@@ -3056,7 +3248,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Event<V> {
                                 .map_err(|e| prepend("timeZones", e))?,
                         );
                     }
-                    _ => vendor_parts.push((k.into_boxed_str(), val)),
+                    _ => vendor_parts.push((<V::Object as JsonObject>::key_into_string(key).into_boxed_str(), val)),
                 }
             }
 
@@ -3181,12 +3373,212 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Event<V> {
     }
 }
 
+// ============================================================================
+// Event summaries
+// ============================================================================
+
+/// The properties of an [`Event`] needed to list it, such as in an index over a large store.
+///
+/// Parsing a summary with [`TryFromJson::try_from_json`] reads only these properties, and skips
+/// all other properties of the input without validating them, so that it is much cheaper than
+/// parsing the whole event. A summary may therefore be parsed from an input which is not a valid
+/// event. [`Event::extract`] reads fewer properties still, leaving the others as `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventSummary {
+    /// The `uid` property.
+    pub uid: Box<Uid>,
+    /// The `title` property.
+    pub title: Option<String>,
+    /// The `start` property.
+    pub start: DateTime<Local>,
+    /// The `duration` property.
+    pub duration: Option<Duration>,
+    /// The `timeZone` property.
+    pub time_zone: Option<String>,
+    /// The `updated` property.
+    pub updated: Option<DateTime<Utc>>,
+    /// The `status` property.
+    pub status: Option<Token<EventStatus>>,
+}
+
+/// A property of an [`EventSummary`], as selected by [`Event::extract`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SummaryProperty {
+    /// The `uid` property, which is always read.
+    Uid,
+    /// The `title` property.
+    Title,
+    /// The `start` property, which is always read.
+    Start,
+    /// The `duration` property.
+    Duration,
+    /// The `timeZone` property.
+    TimeZone,
+    /// The `updated` property.
+    Updated,
+    /// The `status` property.
+    Status,
+}
+
+impl SummaryProperty {
+    /// Every property of an [`EventSummary`].
+    pub const ALL: &'static [SummaryProperty] = &[
+        Self::Uid,
+        Self::Title,
+        Self::Start,
+        Self::Duration,
+        Self::TimeZone,
+        Self::Updated,
+        Self::Status,
+    ];
+
+    /// Returns the name of this property.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Uid => "uid",
+            Self::Title => "title",
+            Self::Start => "start",
+            Self::Duration => "duration",
+            Self::TimeZone => "timeZone",
+            Self::Updated => "updated",
+            Self::Status => "status",
+        }
+    }
+
+    /// Returns the property named `name`, or `None` if it is not a property of an
+    /// [`EventSummary`].
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|property| property.name() == name)
+    }
+}
+
+impl<V: DestructibleJsonValue> TryFromJson<V> for EventSummary {
+    type Error = ObjectError;
+
+    fn try_from_json(value: V) -> Result<Self, Self::Error> {
+        Event::extract(value, SummaryProperty::ALL)
+    }
+}
+
+impl<V: DestructibleJsonValue> Event<V> {
+    /// Parses the `properties` of the event `value` into an [`EventSummary`], skipping all other
+    /// properties without validating them.
+    ///
+    /// The `uid` and `start` properties are always read, since every summary has them, and the
+    /// other fields of the summary are `None` unless they are selected. Indexing many events for
+    /// a list view with this avoids the cost of parsing their participants, alerts, and other
+    /// large properties.
+    ///
+    /// ```
+    /// # #[cfg(feature = "serde_json")]
+    /// # {
+    /// use jscalendar::model::object::{Event, SummaryProperty};
+    /// use serde_json::json;
+    ///
+    /// let input = json!({
+    ///     "@type": "Event",
+    ///     "uid": "a8df6573-0474-496d-8496-033ad45d7fea",
+    ///     "title": "Team meeting",
+    ///     "start": "2020-01-15T13:00:00",
+    ///     "duration": "PT1H"
+    /// });
+    /// let properties = [
+    ///     SummaryProperty::Uid,
+    ///     SummaryProperty::Start,
+    ///     SummaryProperty::Title,
+    /// ];
+    /// let summary = Event::extract(input, &properties).unwrap();
+    /// assert_eq!(summary.title.as_deref(), Some("Team meeting"));
+    /// assert_eq!(summary.duration, None);
+    /// # }
+    /// ```
+    pub fn extract(
+        value: V,
+        properties: &[SummaryProperty],
+    ) -> Result<EventSummary, ObjectError> {
+        let obj = value
+            .try_into_object()
+            .map_err(TypeErrorOr::from)
+            .map_err(DocumentError::root)?;
+
+        let selected = |property: SummaryProperty| {
+            matches!(property, SummaryProperty::Uid | SummaryProperty::Start)
+                || properties.contains(&property)
+        };
+
+        let mut uid = None;
+        let mut title = None;
+        let mut start = None;
+        let mut duration = None;
+        let mut time_zone = None;
+        let mut updated = None;
+        let mut status = None;
+
+        for (key, val) in obj.into_iter() {
+            let property = SummaryProperty::from_name(key.borrow()).filter(|&p| selected(p));
+            let Some(property) = property else {
+                continue;
+            };
+            let name = property.name();
+            match property {
+                SummaryProperty::Uid => {
+                    uid = Some(Box::<Uid>::try_from_json(val).map_err(|e| field_error(name, e))?);
+                }
+                SummaryProperty::Title => {
+                    title = Some(String::try_from_json(val).map_err(|e| field_error(name, e))?);
+                }
+                SummaryProperty::Start => {
+                    start = Some(DateTime::try_from_json(val).map_err(|e| field_error(name, e))?);
+                }
+                SummaryProperty::Duration => {
+                    duration =
+                        Some(Duration::try_from_json(val).map_err(|e| field_error(name, e))?);
+                }
+                SummaryProperty::TimeZone => {
+                    time_zone = Some(String::try_from_json(val).map_err(|e| field_error(name, e))?);
+                }
+                SummaryProperty::Updated => {
+                    updated = Some(DateTime::try_from_json(val).map_err(|e| field_error(name, e))?);
+                }
+                SummaryProperty::Status => {
+                    status = Some(Token::try_from_json(val).map_err(|e| field_error(name, e))?);
+                }
+            }
+        }
+
+        Ok(EventSummary {
+            uid: uid.ok_or_else(|| missing("uid"))?,
+            title,
+            start: start.ok_or_else(|| missing("start"))?,
+            duration,
+            time_zone,
+            updated,
+            status,
+        })
+    }
+}
+
+impl<V: JsonValue> Event<V> {
+    /// Returns the [`EventSummary`] of this event.
+    pub fn summary(&self) -> EventSummary {
+        EventSummary {
+            uid: self.uid().clone(),
+            title: self.title().cloned(),
+            start: *self.start(),
+            duration: self.duration().copied(),
+            time_zone: self.time_zone().cloned(),
+            updated: self.updated().copied(),
+            status: self.status().cloned(),
+        }
+    }
+}
+
 // ============================================================================
 // Task TryFromJson
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for Task<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let obj = value
@@ -3212,9 +3604,9 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Task<V> {
             let mut description_val: Option<String> = None;
             let mut description_content_type_val: Option<String> = None;
             let mut show_without_time_val: Option<bool> = None;
-            let mut locations_val: Option<HashMap<Box<Id>, Location<V>>> = None;
-            let mut virtual_locations_val: Option<HashMap<Box<Id>, VirtualLocation<V>>> = None;
-            let mut links_val: Option<HashMap<Box<Id>, Link<V>>> = None;
+            let mut locations_val: Option<LocationMap<V>> = None;
+            let mut virtual_locations_val: Option<VirtualLocationMap<V>> = None;
+            let mut links_val: Option<LinkMap<V>> = None;
             let mut locale_val: Option<LanguageTag> = None;
             let mut keywords_val: Option<HashSet<String>> = None;
             let mut categories_val: Option<HashSet<String>> = None;
@@ -3233,47 +3625,46 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Task<V> {
             let mut participants_val: Option<HashMap<Box<Id>, TaskParticipant<V>>> = None;
             let mut request_status_val: Option<RequestStatus> = None;
             let mut use_default_alerts_val: Option<bool> = None;
-            let mut alerts_val: Option<HashMap<Box<Id>, Alert<V>>> = None;
+            let mut alerts_val: Option<AlertMap<V>> = None;
             let mut localizations_val: Option<HashMap<LanguageTag, PatchObject<V>>> = None;
             let mut time_zone_val: Option<String> = None;
             let mut time_zones_val: Option<HashMap<Box<CustomTimeZoneId>, TimeZone<V>>> = None;
             let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
 
             for (key, val) in obj.into_iter() {
-                let k = <V::Object as JsonObject>::key_into_string(key);
-                match k.as_str() {
+                match <V::Object as JsonObject>::key_as_str(&key) {
                     "@type" => {}
                     "due" => {
                         due_val =
-                            Some(DateTime::<Local>::try_from_json(val).map_err(|e| field_err("due", e))?);
+                            Some(DateTime::<Local>::try_from_json(val).map_err(|e| field_error("due", e))?);
                     }
                     "start" => {
                         start_val =
-                            Some(DateTime::<Local>::try_from_json(val).map_err(|e| field_err("start", e))?);
+                            Some(DateTime::<Local>::try_from_json(val).map_err(|e| field_error("start", e))?);
                     }
                     "estimatedDuration" => {
                         estimated_duration_val = Some(
-                            Duration::try_from_json(val).map_err(|e| field_err("estimatedDuration", e))?,
+                            Duration::try_from_json(val).map_err(|e| field_error("estimatedDuration", e))?,
                         );
                     }
                     "percentComplete" => {
                         percent_complete_val =
-                            Some(Percent::try_from_json(val).map_err(|e| field_err("percentComplete", e))?);
+                            Some(Percent::try_from_json(val).map_err(|e| field_error("percentComplete", e))?);
                     }
                     "progress" => {
                         progress_val = Some(
                             Token::<TaskProgress>::try_from_json(val)
-                                .map_err(|e| type_field_err("progress", e))?,
+                                .map_err(|e| field_error("progress", e))?,
                         );
                     }
                     "progressUpdated" => {
                         progress_updated_val = Some(
                             DateTime::<Utc>::try_from_json(val)
-                                .map_err(|e| field_err("progressUpdated", e))?,
+                                .map_err(|e| field_error("progressUpdated", e))?,
                         );
                     }
                     "uid" => {
-                        uid_val = Some(Box::<Uid>::try_from_json(val).map_err(|e| field_err("uid", e))?);
+                        uid_val = Some(Box::<Uid>::try_from_json(val).map_err(|e| field_error("uid", e))?);
                     }
                     "relatedTo" => {
                         related_to_val = Some(
@@ -3283,89 +3674,91 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Task<V> {
                     }
                     "prodId" => {
                         prod_id_val =
-                            Some(String::try_from_json(val).map_err(|e| type_field_err("prodId", e))?);
+                            Some(String::try_from_json(val).map_err(|e| field_error("prodId", e))?);
                     }
                     "created" => {
                         created_val =
-                            Some(DateTime::<Utc>::try_from_json(val).map_err(|e| field_err("created", e))?);
+                            Some(DateTime::<Utc>::try_from_json(val).map_err(|e| field_error("created", e))?);
                     }
                     "updated" => {
                         updated_val =
-                            Some(DateTime::<Utc>::try_from_json(val).map_err(|e| field_err("updated", e))?);
+                            Some(DateTime::<Utc>::try_from_json(val).map_err(|e| field_error("updated", e))?);
                     }
                     "sequence" => {
                         sequence_val =
-                            Some(UnsignedInt::try_from_json(val).map_err(|e| field_err("sequence", e))?);
+                            Some(UnsignedInt::try_from_json(val).map_err(|e| field_error("sequence", e))?);
                     }
                     "method" => {
                         method_val = Some(
-                            Token::<Method>::try_from_json(val).map_err(|e| type_field_err("method", e))?,
+                            Token::<Method>::try_from_json(val).map_err(|e| field_error("method", e))?,
                         );
                     }
                     "title" => {
                         title_val =
-                            Some(String::try_from_json(val).map_err(|e| type_field_err("title", e))?);
+                            Some(String::try_from_json(val).map_err(|e| field_error("title", e))?);
                     }
                     "description" => {
                         description_val =
-                            Some(String::try_from_json(val).map_err(|e| type_field_err("description", e))?);
+                            Some(String::try_from_json(val).map_err(|e| field_error("description", e))?);
                     }
                     "descriptionContentType" => {
                         description_content_type_val = Some(
                             String::try_from_json(val)
-                                .map_err(|e| type_field_err("descriptionContentType", e))?,
+                                .map_err(|e| field_error("descriptionContentType", e))?,
                         );
                     }
                     "showWithoutTime" => {
                         show_without_time_val = Some(
-                            bool::try_from_json(val).map_err(|e| type_field_err("showWithoutTime", e))?,
+                            bool::try_from_json(val).map_err(|e| field_error("showWithoutTime", e))?,
                         );
                     }
                     "locations" => {
                         locations_val = Some(
-                            parse_id_map(val, Location::try_from_json)
+                            parse_id_map(val, maybe_unknown("Location", Location::try_from_json))
                                 .map_err(|e| prepend("locations", e))?,
                         );
                     }
                     "virtualLocations" => {
                         virtual_locations_val = Some(
-                            parse_id_map(val, VirtualLocation::try_from_json)
+                            parse_id_map(val, maybe_unknown("VirtualLocation", VirtualLocation::try_from_json))
                                 .map_err(|e| prepend("virtualLocations", e))?,
                         );
                     }
                     "links" => {
-                        links_val =
-                            Some(parse_id_map(val, Link::try_from_json).map_err(|e| prepend("links", e))?);
+                        links_val = Some(
+                            parse_id_map(val, maybe_unknown("Link", Link::try_from_json))
+                                .map_err(|e| prepend("links", e))?,
+                        );
                     }
                     "locale" => {
                         locale_val =
-                            Some(LanguageTag::try_from_json(val).map_err(|e| field_err("locale", e))?);
+                            Some(LanguageTag::try_from_json(val).map_err(|e| field_error("locale", e))?);
                     }
                     "keywords" => {
                         keywords_val = Some(
                             HashSet::<String>::try_from_json(val)
-                                .map_err(|e| doc_field_err("keywords", e))?,
+                                .map_err(|e| nested_field_error("keywords", e))?,
                         );
                     }
                     "categories" => {
                         categories_val = Some(
                             HashSet::<String>::try_from_json(val)
-                                .map_err(|e| doc_field_err("categories", e))?,
+                                .map_err(|e| nested_field_error("categories", e))?,
                         );
                     }
                     "color" => {
-                        color_val = Some(Color::try_from_json(val).map_err(|e| field_err("color", e))?);
+                        color_val = Some(Color::try_from_json(val).map_err(|e| field_error("color", e))?);
                     }
                     "recurrenceId" => {
                         recurrence_id_val = Some(
                             DateTime::<Local>::try_from_json(val)
-                                .map_err(|e| field_err("recurrenceId", e))?,
+                                .map_err(|e| field_error("recurrenceId", e))?,
                         );
                     }
                     "recurrenceIdTimeZone" => {
                         recurrence_id_time_zone_val = Some(
                             String::try_from_json(val)
-                                .map_err(|e| type_field_err("recurrenceIdTimeZone", e))?,
+                                .map_err(|e| field_error("recurrenceIdTimeZone", e))?,
                         );
                     }
                     "recurrenceRules" => {
@@ -3384,22 +3777,22 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Task<V> {
                     }
                     "excluded" => {
                         excluded_val =
-                            Some(bool::try_from_json(val).map_err(|e| type_field_err("excluded", e))?);
+                            Some(bool::try_from_json(val).map_err(|e| field_error("excluded", e))?);
                     }
                     "priority" => {
                         priority_val =
-                            Some(Priority::try_from_json(val).map_err(|e| field_err("priority", e))?);
+                            Some(Priority::try_from_json(val).map_err(|e| field_error("priority", e))?);
                     }
                     "freeBusyStatus" => {
                         free_busy_status_val = Some(
                             Token::<FreeBusyStatus>::try_from_json(val)
-                                .map_err(|e| type_field_err("freeBusyStatus", e))?,
+                                .map_err(|e| field_error("freeBusyStatus", e))?,
                         );
                     }
                     "privacy" => {
                         privacy_val = Some(
                             Token::<Privacy>::try_from_json(val)
-                                .map_err(|e| type_field_err("privacy", e))?,
+                                .map_err(|e| field_error("privacy", e))?,
                         );
                     }
                     "replyTo" => {
@@ -3408,7 +3801,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Task<V> {
                     }
                     "sentBy" => {
                         sent_by_val = Some(
-                            Box::<CalAddress>::try_from_json(val).map_err(|e| field_err("sentBy", e))?,
+                            Box::<CalAddress>::try_from_json(val).map_err(|e| field_error("sentBy", e))?,
                         );
                     }
                     "participants" => {
@@ -3419,17 +3812,18 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Task<V> {
                     }
                     "requestStatus" => {
                         request_status_val = Some(
-                            RequestStatus::try_from_json(val).map_err(|e| field_err("requestStatus", e))?,
+                            RequestStatus::try_from_json(val).map_err(|e| field_error("requestStatus", e))?,
                         );
                     }
                     "useDefaultAlerts" => {
                         use_default_alerts_val = Some(
-                            bool::try_from_json(val).map_err(|e| type_field_err("useDefaultAlerts", e))?,
+                            bool::try_from_json(val).map_err(|e| field_error("useDefaultAlerts", e))?,
                         );
                     }
                     "alerts" => {
                         alerts_val = Some(
-                            parse_id_map(val, Alert::try_from_json).map_err(|e| prepend("alerts", e))?,
+                            parse_id_map(val, maybe_unknown("Alert", Alert::try_from_json))
+                                .map_err(|e| prepend("alerts", e))?,
                         );
                     }
                     "localizations" => {
@@ -3440,7 +3834,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Task<V> {
                     }
                     "timeZone" => {
                         time_zone_val =
-                            Some(String::try_from_json(val).map_err(|e| type_field_err("timeZone", e))?);
+                            Some(String::try_from_json(val).map_err(|e| field_error("timeZone", e))?);
                     }
                     "timeZones" => {
                         time_zones_val = Some(
@@ -3448,7 +3842,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Task<V> {
                                 .map_err(|e| prepend("timeZones", e))?,
                         );
                     }
-                    _ => vendor_parts.push((k.into_boxed_str(), val)),
+                    _ => vendor_parts.push((<V::Object as JsonObject>::key_into_string(key).into_boxed_str(), val)),
                 }
             }
 
@@ -3585,105 +3979,885 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Task<V> {
 }
 
 // ============================================================================
-// Group TryFromJson
+// Defaulting accessors
 // ============================================================================
 
-impl<V: DestructibleJsonValue> TryFromJson<V> for Group<V> {
-    type Error = ObjErr;
+/// The default value of the `descriptionContentType` property (RFC 8984 §4.2.3).
+const DEFAULT_DESCRIPTION_CONTENT_TYPE: &str = "text/plain";
 
-    fn try_from_json(value: V) -> Result<Self, Self::Error> {
-        let obj = value
-            .try_into_object()
-            .map_err(TypeErrorOr::from)
-            .map_err(DocumentError::root)?;
+/// Implements the defaulting accessors shared by [`Event`] and [`Task`].
+macro_rules! impl_common_defaults {
+    ($ty:ident) => {
+        impl<V: JsonValue> $ty<V> {
+            /// Returns the `sequence`, or its default value of 0 (RFC 8984 §4.1.7).
+            pub fn sequence_or_default(&self) -> UnsignedInt {
+                self.sequence().copied().unwrap_or(UnsignedInt::MIN)
+            }
 
-        let mut entries_val: Option<Vec<TaskOrEvent<V>>> = None;
-        let mut source_val: Option<Box<Uri>> = None;
-        let mut uid_val: Option<Box<Uid>> = None;
-        let mut prod_id_val: Option<String> = None;
-        let mut created_val: Option<DateTime<Utc>> = None;
-        let mut updated_val: Option<DateTime<Utc>> = None;
-        let mut title_val: Option<String> = None;
-        let mut description_val: Option<String> = None;
-        let mut description_content_type_val: Option<String> = None;
-        let mut links_val: Option<HashMap<Box<Id>, Link<V>>> = None;
-        let mut locale_val: Option<LanguageTag> = None;
-        let mut keywords_val: Option<HashSet<String>> = None;
-        let mut categories_val: Option<HashSet<String>> = None;
-        let mut color_val: Option<Color> = None;
-        let mut time_zones_val: Option<HashMap<Box<CustomTimeZoneId>, TimeZone<V>>> = None;
-        let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
+            /// Returns the `descriptionContentType`, or its default value of `text/plain` (RFC
+            /// 8984 §4.2.3).
+            pub fn description_content_type_or_default(&self) -> &str {
+                self.description_content_type()
+                    .map_or(DEFAULT_DESCRIPTION_CONTENT_TYPE, String::as_str)
+            }
 
-        for (key, val) in obj.into_iter() {
-            let k = <V::Object as JsonObject>::key_into_string(key);
-            match k.as_str() {
-                "@type" => {}
-                "entries" => {
-                    entries_val = Some(
-                        parse_vec(val, TaskOrEvent::try_from_json)
-                            .map_err(|e| prepend("entries", e))?,
-                    );
-                }
-                "source" => {
-                    source_val =
-                        Some(Box::<Uri>::try_from_json(val).map_err(|e| field_err("source", e))?);
-                }
-                "uid" => {
-                    uid_val =
-                        Some(Box::<Uid>::try_from_json(val).map_err(|e| field_err("uid", e))?);
-                }
-                "prodId" => {
-                    prod_id_val =
-                        Some(String::try_from_json(val).map_err(|e| type_field_err("prodId", e))?);
-                }
-                "created" => {
-                    created_val = Some(
-                        DateTime::<Utc>::try_from_json(val).map_err(|e| field_err("created", e))?,
-                    );
-                }
-                "updated" => {
-                    updated_val = Some(
-                        DateTime::<Utc>::try_from_json(val).map_err(|e| field_err("updated", e))?,
-                    );
-                }
-                "title" => {
-                    title_val =
-                        Some(String::try_from_json(val).map_err(|e| type_field_err("title", e))?);
-                }
-                "description" => {
-                    description_val = Some(
-                        String::try_from_json(val).map_err(|e| type_field_err("description", e))?,
-                    );
-                }
-                "descriptionContentType" => {
+            /// Returns the `showWithoutTime` flag, or its default value of `false` (RFC 8984
+            /// §4.2.4).
+            pub fn show_without_time_or_default(&self) -> bool {
+                self.show_without_time().copied().unwrap_or(false)
+            }
+
+            /// Returns the `excluded` flag, or its default value of `false` (RFC 8984 §4.3.6).
+            pub fn excluded_or_default(&self) -> bool {
+                self.excluded().copied().unwrap_or(false)
+            }
+
+            /// Returns the `priority`, or its default value of 0 (RFC 8984 §4.4.1).
+            pub fn priority_or_default(&self) -> Priority {
+                self.priority().copied().unwrap_or_default()
+            }
+
+            /// Returns the `freeBusyStatus`, or its default value of `busy` (RFC 8984 §4.4.2).
+            pub fn free_busy_status_or_default(&self) -> Token<FreeBusyStatus> {
+                self.free_busy_status()
+                    .cloned()
+                    .unwrap_or(Token::Known(FreeBusyStatus::Busy))
+            }
+
+            /// Returns the `privacy`, or its default value of `public` (RFC 8984 §4.4.3).
+            pub fn privacy_or_default(&self) -> Token<Privacy> {
+                self.privacy()
+                    .cloned()
+                    .unwrap_or(Token::Known(Privacy::Public))
+            }
+
+            /// Returns the `useDefaultAlerts` flag, or its default value of `false` (RFC 8984
+            /// §4.5.1).
+            pub fn use_default_alerts_or_default(&self) -> bool {
+                self.use_default_alerts().copied().unwrap_or(false)
+            }
+
+            /// Returns the `color`, or else a color assigned by [`Color::assign`] from the `uid`
+            /// and `keywords`.
+            pub fn display_color(&self) -> Color {
+                self.color().copied().unwrap_or_else(|| {
+                    let keywords = self.keywords().into_iter().flatten();
+                    Color::assign(self.uid().as_str(), keywords.map(String::as_str))
+                })
+            }
+        }
+    };
+}
+
+impl_common_defaults!(Event);
+impl_common_defaults!(Task);
+
+impl<V: JsonValue> Event<V> {
+    /// Returns the `duration`, or its default value of `PT0S` (RFC 8984 §5.1.2).
+    pub fn duration_or_default(&self) -> Duration {
+        self.duration().copied().unwrap_or(Duration::ZERO)
+    }
+
+    /// Returns the `status`, or its default value of `confirmed` (RFC 8984 §5.1.3).
+    pub fn status_or_default(&self) -> Token<EventStatus> {
+        self.status()
+            .cloned()
+            .unwrap_or(Token::Known(EventStatus::Confirmed))
+    }
+}
+
+impl<V: JsonValue> Group<V> {
+    /// Returns the `descriptionContentType`, or its default value of `text/plain` (RFC 8984
+    /// §4.2.3).
+    pub fn description_content_type_or_default(&self) -> &str {
+        self.description_content_type()
+            .map_or(DEFAULT_DESCRIPTION_CONTENT_TYPE, String::as_str)
+    }
+}
+
+/// Implements the defaulting accessors shared by [`Participant`] and [`TaskParticipant`].
+macro_rules! impl_participant_defaults {
+    ($ty:ident) => {
+        impl<V: JsonValue> $ty<V> {
+            /// Returns the `participationStatus`, or its default value of `needs-action` (RFC 8984
+            /// §4.4.6).
+            pub fn participation_status_or_default(&self) -> Token<ParticipationStatus> {
+                self.participation_status()
+                    .cloned()
+                    .unwrap_or(Token::Known(ParticipationStatus::NeedsAction))
+            }
+
+            /// Returns the `expectReply` flag, or its default value of `false` (RFC 8984 §4.4.6).
+            pub fn expect_reply_or_default(&self) -> bool {
+                self.expect_reply().copied().unwrap_or(false)
+            }
+
+            /// Returns the `scheduleAgent`, or its default value of `server` (RFC 8984 §4.4.6).
+            pub fn schedule_agent_or_default(&self) -> Token<ScheduleAgent> {
+                self.schedule_agent()
+                    .cloned()
+                    .unwrap_or(Token::Known(ScheduleAgent::Server))
+            }
+
+            /// Returns the `scheduleForceSend` flag, or its default value of `false` (RFC 8984
+            /// §4.4.6).
+            pub fn schedule_force_send_or_default(&self) -> bool {
+                self.schedule_force_send().copied().unwrap_or(false)
+            }
+
+            /// Returns the `scheduleSequence`, or its default value of 0 (RFC 8984 §4.4.6).
+            pub fn schedule_sequence_or_default(&self) -> UnsignedInt {
+                self.schedule_sequence()
+                    .copied()
+                    .unwrap_or(UnsignedInt::MIN)
+            }
+        }
+    };
+}
+
+impl_participant_defaults!(Participant);
+impl_participant_defaults!(TaskParticipant);
+
+impl<V: JsonValue> Alert<V> {
+    /// Returns the `action`, or its default value of `display` (RFC 8984 §4.5.2).
+    pub fn action_or_default(&self) -> Token<AlertAction> {
+        self.action()
+            .cloned()
+            .unwrap_or(Token::Known(AlertAction::Display))
+    }
+}
+
+impl<V> OffsetTrigger<V> {
+    /// Returns the `relativeTo` property, or its default value of `start` (RFC 8984 §4.5.2).
+    pub fn relative_to_or_default(&self) -> Token<AlertRelativeTo> {
+        self.relative_to()
+            .cloned()
+            .unwrap_or(Token::Known(AlertRelativeTo::Start))
+    }
+}
+
+// ============================================================================
+// Link queries
+// ============================================================================
+
+/// Implements the query methods for objects with a `links` property.
+macro_rules! impl_link_queries {
+    ($ty:ident) => {
+        impl<V: JsonValue> $ty<V> {
+            /// Iterates over the links whose `@type` is known, in arbitrary order.
+            fn known_links(&self) -> impl Iterator<Item = (&Id, &Link<V>)> {
+                self.links()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(id, link)| link.as_known().map(|link| (&**id, link)))
+            }
+
+            /// Iterates over the links with the given `rel` property, in arbitrary order.
+            pub fn links_by_rel(
+                &self,
+                relation: LinkRelation,
+            ) -> impl Iterator<Item = (&Id, &Link<V>)> {
+                self.known_links()
+                    .filter(move |(_, link)| link.relation() == Some(&Token::Known(relation)))
+            }
+
+            /// Returns a link with the first relation in `relations` for which this object has
+            /// any links, such as `[DescribedBy, Alternate]`. Ties are broken by ID.
+            pub fn preferred_link(&self, relations: &[LinkRelation]) -> Option<(&Id, &Link<V>)> {
+                relations
+                    .iter()
+                    .find_map(|relation| self.links_by_rel(*relation).min_by_key(|(id, _)| *id))
+            }
+
+            /// Returns the image link best suited to the given display purpose.
+            ///
+            /// A link is an image if it has a `display` property or its `mediaType` matches
+            /// `image/*`. Images whose `display` is `purpose` are preferred, followed by those
+            /// without a `display`. Among these, the smallest image by `size` is chosen for
+            /// badges and thumbnails, and the largest for graphics and full-size images, with
+            /// unsized images ranked last and ties broken by ID.
+            pub fn image_for(&self, purpose: DisplayPurpose) -> Option<(&Id, &Link<V>)> {
+                let prefer_small =
+                    matches!(purpose, DisplayPurpose::Badge | DisplayPurpose::Thumbnail);
+                self.known_links()
+                    .filter_map(|(id, link)| {
+                        let tier = match link.display() {
+                            Some(Token::Known(display)) if *display == purpose => 0,
+                            Some(_) => return None,
+                            None if link
+                                .media_type()
+                                .is_some_and(|media_type| media_type.matches("image/*")) =>
+                            {
+                                1
+                            }
+                            None => return None,
+                        };
+                        let size = match link.size() {
+                            Some(size) if prefer_small => Some(size.get()),
+                            Some(size) => Some(u64::MAX - size.get()),
+                            None => None,
+                        };
+                        Some(((tier, size.is_none(), size), id, link))
+                    })
+                    .min_by(|(a_rank, a_id, _), (b_rank, b_id, _)| {
+                        a_rank.cmp(b_rank).then_with(|| a_id.cmp(b_id))
+                    })
+                    .map(|(_, id, link)| (id, link))
+            }
+
+            /// Returns the largest image link whose `size` is at most `max_size` octets.
+            ///
+            /// A link is an image if its `mediaType` matches `image/*`. Images without a `size`
+            /// are only returned if no image of a known size fits, and ties are broken by ID.
+            pub fn preferred_image(&self, max_size: u64) -> Option<(&Id, &Link<V>)> {
+                self.known_links()
+                    .filter(|(_, link)| {
+                        link.media_type()
+                            .is_some_and(|media_type| media_type.matches("image/*"))
+                    })
+                    .filter_map(|(id, link)| match link.size() {
+                        Some(size) if size.get() > max_size => None,
+                        size => Some((size.map(|size| size.get()), id, link)),
+                    })
+                    .max_by(|(a_size, a_id, _), (b_size, b_id, _)| {
+                        a_size.cmp(b_size).then_with(|| b_id.cmp(a_id))
+                    })
+                    .map(|(_, id, link)| (id, link))
+            }
+
+            /// Iterates over the attachments (links with the `enclosure` relation) whose
+            /// `mediaType` matches `pattern`, as in [`MediaType::matches`], in arbitrary order.
+            ///
+            /// An attachment without a `mediaType` only matches the pattern `*/*`.
+            pub fn attachments<'a>(
+                &'a self,
+                pattern: &'a str,
+            ) -> impl Iterator<Item = (&'a Id, &'a Link<V>)> {
+                self.links_by_rel(LinkRelation::Enclosure)
+                    .filter(move |(_, link)| match link.media_type() {
+                        Some(media_type) => media_type.matches(pattern),
+                        None => pattern.trim() == "*/*",
+                    })
+            }
+        }
+    };
+}
+
+impl_link_queries!(Group);
+impl_link_queries!(Event);
+impl_link_queries!(Task);
+impl_link_queries!(Location);
+impl_link_queries!(Participant);
+impl_link_queries!(TaskParticipant);
+
+// ============================================================================
+// Partial parsing
+// ============================================================================
+
+/// A property or map entry which was left out of an object by [`Event::try_from_json_partial`]
+/// or [`Task::try_from_json_partial`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidProperty<V> {
+    /// The error of the property or entry, whose path leads to it from the object.
+    pub error: ObjectError,
+    /// The raw value of the property or entry.
+    pub value: V,
+}
+
+impl<V> Event<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    /// A lenient alternative to [`TryFromJson::try_from_json`] which tolerates invalid optional
+    /// properties.
+    ///
+    /// Each top-level property that fails to parse is left out of the event, and is returned
+    /// with its error and raw value. A map of objects such as `locations` or `participants` loses
+    /// only its invalid entries, which are returned in the same way. An editor can show the
+    /// returned values to the user, and set the corrected properties with the typed setters. This
+    /// fails only if the value is not an object, or if the required `start` or `uid` properties
+    /// are missing or invalid.
+    pub fn try_from_json_partial(value: V) -> Result<(Self, Vec<InvalidProperty<V>>), ObjectError> {
+        partial_from_json(value, &["start", "uid"], Self::try_from_json)
+    }
+}
+
+impl<V> Task<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    /// A lenient alternative to [`TryFromJson::try_from_json`] which tolerates invalid optional
+    /// properties; see [`Event::try_from_json_partial`].
+    pub fn try_from_json_partial(value: V) -> Result<(Self, Vec<InvalidProperty<V>>), ObjectError> {
+        partial_from_json(value, &["uid"], Self::try_from_json)
+    }
+}
+
+/// Parses the object `value` with `parse`, leaving out each top-level property, or each entry of
+/// a map-valued property, which fails to parse. Errors in the `@type` or any of the `required`
+/// properties are returned as-is.
+///
+/// Every property is checked with `parse` alongside only the `@type` and required properties,
+/// so that each is parsed a bounded number of times however many properties are invalid.
+fn partial_from_json<T, V>(
+    value: V,
+    required: &[&str],
+    parse: impl Fn(V) -> Result<T, ObjectError>,
+) -> Result<(T, Vec<InvalidProperty<V>>), ObjectError>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    let entries: Vec<(String, V)> = value
+        .try_into_object()
+        .map_err(TypeErrorOr::from)
+        .map_err(DocumentError::root)?
+        .into_iter()
+        .map(|(key, val)| (<V::Object as JsonObject>::key_into_string(key), val))
+        .collect();
+
+    // the common case of a valid object is parsed only once
+    if let Ok(result) = parse(object_of(&entries, None)) {
+        return Ok((result, Vec::new()));
+    }
+
+    let (mut valid, others): (Vec<(String, V)>, Vec<_>) = IntoIterator::into_iter(entries)
+        .partition(|(key, _)| key == "@type" || required.contains(&key.as_str()));
+    parse(object_of(&valid, None))?;
+
+    let required_len = valid.len();
+    let mut invalid = Vec::new();
+    for (key, value) in others {
+        let alone = object_of(&valid[..required_len], Some((&key, value.clone())));
+        let error = match parse(alone) {
+            Ok(_) => {
+                valid.push((key, value));
+                continue;
+            }
+            Err(error) => error,
+        };
+
+        // an error within an entry of a map leaves the other entries in place
+        if !matches!(error.path.get(1), Some(PathSegment::String(_))) || !value.is_object() {
+            invalid.push(InvalidProperty { error, value });
+            continue;
+        }
+
+        let map = value.try_into_object().expect("checked to be an object");
+        let mut kept = V::Object::with_capacity(map.len());
+        for (id, entry) in map.into_iter() {
+            let mut single = V::Object::with_capacity(1);
+            single.insert(
+                <V::Object as JsonObject>::key_as_str(&id).into(),
+                entry.clone(),
+            );
+            let property = (key.as_str(), V::object(single));
+            match parse(object_of(&valid[..required_len], Some(property))) {
+                Ok(_) => kept.insert(id, entry),
+                Err(error) => invalid.push(InvalidProperty {
+                    error,
+                    value: entry,
+                }),
+            }
+        }
+        if !kept.is_empty() {
+            valid.push((key, V::object(kept)));
+        }
+    }
+
+    // properties which are valid on their own may still conflict, so the remaining errors are
+    // resolved by leaving out the offending property, which always terminates
+    loop {
+        let error = match parse(object_of(&valid, None)) {
+            Ok(result) => return Ok((result, invalid)),
+            Err(error) => error,
+        };
+
+        let index = match error.path.front() {
+            Some(PathSegment::Static(field)) if !required.contains(field) => valid
+                .iter()
+                .position(|(key, _)| key == field)
+                .filter(|&index| index >= required_len),
+            _ => None,
+        };
+
+        match index {
+            Some(index) => {
+                let (_, value) = valid.remove(index);
+                invalid.push(InvalidProperty { error, value });
+            }
+            None => return Err(error),
+        }
+    }
+}
+
+/// Builds a JSON object from `entries` and an optional `extra` entry.
+fn object_of<V>(entries: &[(String, V)], extra: Option<(&str, V)>) -> V
+where
+    V: ConstructibleJsonValue + Clone,
+{
+    let mut object = V::Object::with_capacity(entries.len() + 1);
+    for (key, value) in entries {
+        object.insert(key.as_str().into(), value.clone());
+    }
+    if let Some((key, value)) = extra {
+        object.insert(key.into(), value);
+    }
+    V::object(object)
+}
+
+// ============================================================================
+// UTCDateTime coercion
+// ============================================================================
+
+/// The handling of local date-times in properties of type `UTCDateTime` (RFC 8984 §1.4.4).
+///
+/// Some producers write `created`, `updated`, or `acknowledged` as local date-times without the
+/// required `Z` suffix. This policy determines whether such values are rejected or coerced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UtcDateTimePolicy {
+    /// Reject local date-times, as [`TryFromJson::try_from_json`] does.
+    #[default]
+    Strict,
+    /// Interpret local date-times as UTC, as if a `Z` suffix had been appended.
+    AssumeUtc,
+    /// Interpret local date-times as local times at the given offset from UTC.
+    AssumeOffset(UtcOffset),
+}
+
+impl UtcDateTimePolicy {
+    /// Converts a local date-time into UTC according to this policy, or returns `None` if the
+    /// policy is strict or the result is out of range.
+    fn to_utc(self, local: DateTime<Local>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Strict => None,
+            Self::AssumeUtc => Some(DateTime {
+                date: local.date,
+                time: local.time,
+                marker: Utc,
+            }),
+            Self::AssumeOffset(offset) => local_to_utc(local, offset),
+        }
+    }
+}
+
+/// Converts a local date-time at the given offset from UTC into UTC, or returns `None` if the
+/// result is out of range.
+pub(crate) fn local_to_utc(local: DateTime<Local>, offset: UtcOffset) -> Option<DateTime<Utc>> {
+    let duration = Duration::Exact(ExactDuration {
+        hours: offset.hour as u32,
+        minutes: offset.minute as u32,
+        seconds: offset.second as u32,
+        frac: None,
+    });
+    let sign = match offset.sign {
+        Sign::Pos => Sign::Neg,
+        Sign::Neg => Sign::Pos,
+    };
+    let local = local.checked_add_signed(SignedDuration { sign, duration })?;
+
+    Some(DateTime {
+        date: local.date,
+        time: local.time,
+        marker: Utc,
+    })
+}
+
+/// Options for [`Event::try_from_json_with`] and the analogous methods of [`Task`] and [`Group`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// The handling of local date-times in `UTCDateTime` properties.
+    pub utc_date_times: UtcDateTimePolicy,
+}
+
+impl ParseOptions {
+    /// Returns these options with the given [`UtcDateTimePolicy`].
+    pub const fn with_utc_date_times(mut self, policy: UtcDateTimePolicy) -> Self {
+        self.utc_date_times = policy;
+        self
+    }
+}
+
+/// A local date-time which was coerced into a `UTCDateTime` according to [`ParseOptions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoercedDateTime {
+    /// The location of the value in the input.
+    pub pointer: Box<ImplicitJsonPointer>,
+    /// The original value.
+    pub original: Box<str>,
+    /// The value after coercion.
+    pub coerced: DateTime<Utc>,
+}
+
+impl<V> Event<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue,
+{
+    /// Parses an event like [`TryFromJson::try_from_json`], but according to the given options.
+    ///
+    /// The `created` and `updated` properties and the `acknowledged` property of each alert are
+    /// coerced according to [`ParseOptions::utc_date_times`], and every coerced value is
+    /// recorded in the returned list.
+    pub fn try_from_json_with(
+        value: V,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<CoercedDateTime>), ObjectError> {
+        let mut coerced = Vec::new();
+        let value = coerce_object_date_times(value, options.utc_date_times, "", &mut coerced);
+        Self::try_from_json(value).map(|event| (event, coerced))
+    }
+}
+
+impl<V> Task<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue,
+{
+    /// Parses a task like [`TryFromJson::try_from_json`], but according to the given options;
+    /// see [`Event::try_from_json_with`].
+    pub fn try_from_json_with(
+        value: V,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<CoercedDateTime>), ObjectError> {
+        let mut coerced = Vec::new();
+        let value = coerce_object_date_times(value, options.utc_date_times, "", &mut coerced);
+        Self::try_from_json(value).map(|task| (task, coerced))
+    }
+}
+
+impl<V> Group<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue,
+{
+    /// Parses a group like [`TryFromJson::try_from_json`], but according to the given options;
+    /// see [`Event::try_from_json_with`]. The options also apply to each of the `entries`.
+    pub fn try_from_json_with(
+        value: V,
+        options: &ParseOptions,
+    ) -> Result<(Self, Vec<CoercedDateTime>), ObjectError> {
+        let policy = options.utc_date_times;
+        let mut coerced = Vec::new();
+        let value = map_members(value, |name, member| match name {
+            "created" | "updated" => coerce_date_time(member, policy, name, &mut coerced),
+            "entries" if member.is_array() => {
+                let Ok(entries) = member.try_into_array() else {
+                    unreachable!()
+                };
+                let mut result = V::Array::with_capacity(entries.len());
+                for (index, entry) in entries.into_iter().enumerate() {
+                    let prefix = format!("entries/{index}/");
+                    result.push(coerce_object_date_times(
+                        entry,
+                        policy,
+                        &prefix,
+                        &mut coerced,
+                    ));
+                }
+                V::array(result)
+            }
+            _ => member,
+        });
+        Self::try_from_json(value).map(|group| (group, coerced))
+    }
+}
+
+/// Coerces the `UTCDateTime` properties of the event or task `value` according to `policy`,
+/// recording each coerced value with the given pointer prefix in `coerced`.
+fn coerce_object_date_times<V>(
+    value: V,
+    policy: UtcDateTimePolicy,
+    prefix: &str,
+    coerced: &mut Vec<CoercedDateTime>,
+) -> V
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue,
+{
+    if policy == UtcDateTimePolicy::Strict {
+        return value;
+    }
+
+    map_members(value, |name, member| match name {
+        "created" | "updated" => {
+            coerce_date_time(member, policy, &format!("{prefix}{name}"), coerced)
+        }
+        "alerts" => map_members(member, |id, alert| {
+            map_members(alert, |name, member| match name {
+                "acknowledged" => {
+                    let pointer = format!("{prefix}alerts/{}/{name}", escape_segment(id));
+                    coerce_date_time(member, policy, &pointer, coerced)
+                }
+                _ => member,
+            })
+        }),
+        _ => member,
+    })
+}
+
+/// Applies `f` to each member of `value` if it is an object, and returns `value` unchanged
+/// otherwise.
+fn map_members<V>(value: V, mut f: impl FnMut(&str, V) -> V) -> V
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue,
+{
+    if !value.is_object() {
+        return value;
+    }
+
+    let Ok(object) = value.try_into_object() else {
+        unreachable!()
+    };
+    let mut result = V::Object::with_capacity(object.len());
+    for (key, member) in object.into_iter() {
+        let member = f(Borrow::<str>::borrow(&key), member);
+        result.insert(key, member);
+    }
+    V::object(result)
+}
+
+/// Coerces `value` into a `UTCDateTime` according to `policy` if it is a local date-time,
+/// recording the coercion in `coerced`. Any other value is returned unchanged.
+fn coerce_date_time<V>(
+    value: V,
+    policy: UtcDateTimePolicy,
+    pointer: &str,
+    coerced: &mut Vec<CoercedDateTime>,
+) -> V
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue,
+{
+    let Ok(input) = value.try_as_string() else {
+        return value;
+    };
+    let input = input.as_ref();
+
+    if parse_full(utc_date_time)(input).is_ok() {
+        return value;
+    }
+    let Some(date_time) = parse_full(local_date_time)(input)
+        .ok()
+        .and_then(|local| policy.to_utc(local))
+    else {
+        return value;
+    };
+
+    coerced.push(CoercedDateTime {
+        pointer: ImplicitJsonPointer::new(pointer)
+            .expect("coercion pointers are escaped")
+            .into(),
+        original: input.into(),
+        coerced: date_time,
+    });
+    date_time.into_json()
+}
+
+/// Escapes `~` and `/` in a JSON pointer segment (RFC 6901 §3).
+pub(crate) fn escape_segment(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+// ============================================================================
+// Duration formatting
+// ============================================================================
+
+/// Options for [`Event::into_json_with`] and the analogous methods of [`Task`] and [`Group`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct SerializeOptions {
+    /// The units in which `Duration` and `SignedDuration` values are written.
+    pub durations: DurationFormat,
+    /// Where the vendor properties of each object are written.
+    pub vendor_properties: VendorPlacement,
+}
+
+impl SerializeOptions {
+    /// Returns these options with the given [`DurationFormat`].
+    pub const fn with_durations(mut self, format: DurationFormat) -> Self {
+        self.durations = format;
+        self
+    }
+
+    /// Returns these options with the given [`VendorPlacement`].
+    pub const fn with_vendor_properties(mut self, placement: VendorPlacement) -> Self {
+        self.vendor_properties = placement;
+        self
+    }
+}
+
+/// Applies the [`SerializeOptions::vendor_properties`] of `options` to `object`.
+fn place_vendor_properties<V: ConstructibleJsonValue>(
+    object: &mut dyn Extensible<V>,
+    options: &SerializeOptions,
+) {
+    match options.vendor_properties {
+        VendorPlacement::Inline => {}
+        VendorPlacement::Wrapped(key) => vendor::wrap(object, key),
+    }
+}
+
+impl<V: ConstructibleJsonValue> Event<V> {
+    /// Serializes this event like [`IntoJson::into_json`], but according to the given options.
+    ///
+    /// The `duration` property and the `offset` of each alert trigger are reformatted according
+    /// to [`SerializeOptions::durations`], except for values which would overflow, and vendor
+    /// properties are placed according to [`SerializeOptions::vendor_properties`]. The patches
+    /// in `recurrenceOverrides` are written unchanged.
+    pub fn into_json_with(mut self, options: &SerializeOptions) -> V {
+        self.reformat_durations(options.durations);
+        place_vendor_properties(&mut self, options);
+        self.into_json()
+    }
+
+    fn reformat_durations(&mut self, format: DurationFormat) {
+        if let Some(duration) = self.duration_mut() {
+            *duration = duration.reformat(format).unwrap_or(*duration);
+        }
+        reformat_alert_offsets(self.alerts_mut(), format);
+    }
+}
+
+impl<V: ConstructibleJsonValue> Task<V> {
+    /// Serializes this task like [`IntoJson::into_json`], but according to the given options;
+    /// see [`Event::into_json_with`]. The `estimatedDuration` property is reformatted instead of
+    /// `duration`.
+    pub fn into_json_with(mut self, options: &SerializeOptions) -> V {
+        self.reformat_durations(options.durations);
+        place_vendor_properties(&mut self, options);
+        self.into_json()
+    }
+
+    fn reformat_durations(&mut self, format: DurationFormat) {
+        if let Some(duration) = self.estimated_duration_mut() {
+            *duration = duration.reformat(format).unwrap_or(*duration);
+        }
+        reformat_alert_offsets(self.alerts_mut(), format);
+    }
+}
+
+impl<V: ConstructibleJsonValue> Group<V> {
+    /// Serializes this group like [`IntoJson::into_json`], but according to the given options,
+    /// which apply to each of the `entries`; see [`Event::into_json_with`].
+    pub fn into_json_with(mut self, options: &SerializeOptions) -> V {
+        for entry in self.entries_mut() {
+            match entry {
+                TaskOrEvent::Task(task) => task.reformat_durations(options.durations),
+                TaskOrEvent::Event(event) => event.reformat_durations(options.durations),
+            }
+        }
+        place_vendor_properties(&mut self, options);
+        self.into_json()
+    }
+}
+
+/// Reformats the offset of each known alert with an offset trigger.
+fn reformat_alert_offsets<V: JsonValue>(alerts: Option<&mut AlertMap<V>>, format: DurationFormat) {
+    let alerts = alerts.into_iter().flatten();
+    for alert in alerts.filter_map(|(_, alert)| alert.as_known_mut()) {
+        if let Trigger::Offset(trigger) = alert.trigger_mut() {
+            let offset = trigger.offset_mut();
+            *offset = offset.reformat(format).unwrap_or(*offset);
+        }
+    }
+}
+
+// ============================================================================
+// Group TryFromJson
+// ============================================================================
+
+impl<V: DestructibleJsonValue> TryFromJson<V> for Group<V> {
+    type Error = ObjectError;
+
+    fn try_from_json(value: V) -> Result<Self, Self::Error> {
+        let obj = value
+            .try_into_object()
+            .map_err(TypeErrorOr::from)
+            .map_err(DocumentError::root)?;
+
+        let mut entries_val: Option<Vec<TaskOrEvent<V>>> = None;
+        let mut source_val: Option<Box<Uri>> = None;
+        let mut uid_val: Option<Box<Uid>> = None;
+        let mut prod_id_val: Option<String> = None;
+        let mut created_val: Option<DateTime<Utc>> = None;
+        let mut updated_val: Option<DateTime<Utc>> = None;
+        let mut title_val: Option<String> = None;
+        let mut description_val: Option<String> = None;
+        let mut description_content_type_val: Option<String> = None;
+        let mut links_val: Option<LinkMap<V>> = None;
+        let mut locale_val: Option<LanguageTag> = None;
+        let mut keywords_val: Option<HashSet<String>> = None;
+        let mut categories_val: Option<HashSet<String>> = None;
+        let mut color_val: Option<Color> = None;
+        let mut time_zones_val: Option<HashMap<Box<CustomTimeZoneId>, TimeZone<V>>> = None;
+        let mut vendor_parts: Vec<(Box<str>, V)> = Vec::new();
+
+        for (key, val) in obj.into_iter() {
+            match <V::Object as JsonObject>::key_as_str(&key) {
+                "@type" => {}
+                "entries" => {
+                    entries_val = Some(
+                        parse_vec(val, TaskOrEvent::try_from_json)
+                            .map_err(|e| prepend("entries", e))?,
+                    );
+                }
+                "source" => {
+                    source_val =
+                        Some(Box::<Uri>::try_from_json(val).map_err(|e| field_error("source", e))?);
+                }
+                "uid" => {
+                    uid_val =
+                        Some(Box::<Uid>::try_from_json(val).map_err(|e| field_error("uid", e))?);
+                }
+                "prodId" => {
+                    prod_id_val =
+                        Some(String::try_from_json(val).map_err(|e| field_error("prodId", e))?);
+                }
+                "created" => {
+                    created_val = Some(
+                        DateTime::<Utc>::try_from_json(val).map_err(|e| field_error("created", e))?,
+                    );
+                }
+                "updated" => {
+                    updated_val = Some(
+                        DateTime::<Utc>::try_from_json(val).map_err(|e| field_error("updated", e))?,
+                    );
+                }
+                "title" => {
+                    title_val =
+                        Some(String::try_from_json(val).map_err(|e| field_error("title", e))?);
+                }
+                "description" => {
+                    description_val = Some(
+                        String::try_from_json(val).map_err(|e| field_error("description", e))?,
+                    );
+                }
+                "descriptionContentType" => {
                     description_content_type_val = Some(
                         String::try_from_json(val)
-                            .map_err(|e| type_field_err("descriptionContentType", e))?,
+                            .map_err(|e| field_error("descriptionContentType", e))?,
                     );
                 }
                 "links" => {
                     links_val = Some(
-                        parse_id_map(val, Link::try_from_json).map_err(|e| prepend("links", e))?,
+                        parse_id_map(val, maybe_unknown("Link", Link::try_from_json))
+                            .map_err(|e| prepend("links", e))?,
                     );
                 }
                 "locale" => {
                     locale_val =
-                        Some(LanguageTag::try_from_json(val).map_err(|e| field_err("locale", e))?);
+                        Some(LanguageTag::try_from_json(val).map_err(|e| field_error("locale", e))?);
                 }
                 "keywords" => {
                     keywords_val = Some(
                         HashSet::<String>::try_from_json(val)
-                            .map_err(|e| doc_field_err("keywords", e))?,
+                            .map_err(|e| nested_field_error("keywords", e))?,
                     );
                 }
                 "categories" => {
                     categories_val = Some(
                         HashSet::<String>::try_from_json(val)
-                            .map_err(|e| doc_field_err("categories", e))?,
+                            .map_err(|e| nested_field_error("categories", e))?,
                     );
                 }
                 "color" => {
-                    color_val = Some(Color::try_from_json(val).map_err(|e| field_err("color", e))?);
+                    color_val = Some(Color::try_from_json(val).map_err(|e| field_error("color", e))?);
                 }
                 "timeZones" => {
                     time_zones_val = Some(
@@ -3691,7 +4865,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Group<V> {
                             .map_err(|e| prepend("timeZones", e))?,
                     );
                 }
-                _ => vendor_parts.push((k.into_boxed_str(), val)),
+                _ => vendor_parts.push((<V::Object as JsonObject>::key_into_string(key).into_boxed_str(), val)),
             }
         }
 
@@ -3749,7 +4923,7 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Group<V> {
 // ============================================================================
 
 impl<V: DestructibleJsonValue> TryFromJson<V> for TaskOrEvent<V> {
-    type Error = ObjErr;
+    type Error = ObjectError;
 
     fn try_from_json(value: V) -> Result<Self, Self::Error> {
         let is_event = {
@@ -3786,6 +4960,12 @@ macro_rules! insert_optional {
 }
 
 /// Helper: insert a required field into a JSON object.
+///
+/// The `into_fields` structs generated by structible 0.5 only have `take_*` methods returning an
+/// `Option`, even for required fields, and there is no way to move a required field out of the
+/// object by value. Required fields are therefore taken with `expect`: every constructor and
+/// `TryFromJson` impl sets them, and no setter can remove them, so the `None` case is unreachable.
+/// Serializing without that `expect` would need a by-value accessor in structible itself.
 macro_rules! insert_required {
     ($obj:expr, $key:expr, $val:expr) => {
         $obj.insert($key.into(), $val.into_json());
@@ -3795,15 +4975,27 @@ macro_rules! insert_required {
 /// Helper: insert vendor properties (consuming) into a JSON object.
 macro_rules! insert_vendor_properties {
     ($obj:expr, $fields:expr) => {
-        for (key, value) in $fields.drain_vendor_property() {
-            $obj.insert(String::from(key).into(), value);
-        }
+        $obj.insert_unordered(
+            IntoIterator::into_iter($fields.drain_vendor_property())
+                .map(|(key, value)| (String::from(key).into(), value)),
+        );
     };
 }
 
 impl<V: ConstructibleJsonValue> IntoJson<V> for UtcOffset {
     fn into_json(self) -> V {
-        V::string(self.to_string())
+        // RFC 8984 uses the extended format, unlike the Display impl of UtcOffset
+        let mut s = format!(
+            "{}{:02}:{:02}",
+            self.sign.as_char(),
+            self.hour as u8,
+            self.minute as u8
+        );
+        let second = self.second as u8;
+        if second != 0 {
+            s.push_str(&format!(":{second:02}"));
+        }
+        V::string(s)
     }
 }
 
@@ -3823,9 +5015,11 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for PatchObject<V> {
     fn into_json(self) -> V {
         let inner = self.into_inner();
         let mut obj = V::Object::with_capacity(inner.len());
-        for (key, value) in inner {
-            obj.insert(key.to_string().into(), value);
-        }
+        obj.insert_unordered(
+            inner
+                .into_iter()
+                .map(|(key, value)| (key.to_string().into(), value)),
+        );
         V::object(obj)
     }
 }
@@ -3850,7 +5044,7 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for OffsetTrigger<V> {
         let mut f = self.into_fields();
         let mut obj = V::Object::new();
         obj.insert("@type".into(), V::str("OffsetTrigger"));
-        insert_required!(obj, "offset", f.take_offset().unwrap());
+        insert_required!(obj, "offset", f.take_offset().expect("offset is required"));
         insert_optional!(obj, "relativeTo", f.take_relative_to());
         insert_vendor_properties!(obj, f);
         V::object(obj)
@@ -3862,7 +5056,7 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for AbsoluteTrigger<V> {
         let mut f = self.into_fields();
         let mut obj = V::Object::new();
         obj.insert("@type".into(), V::str("AbsoluteTrigger"));
-        insert_required!(obj, "when", f.take_when().unwrap());
+        insert_required!(obj, "when", f.take_when().expect("when is required"));
         insert_vendor_properties!(obj, f);
         V::object(obj)
     }
@@ -3878,6 +5072,15 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for Trigger<V> {
     }
 }
 
+impl<T: IntoJson<V>, V: ConstructibleJsonValue> IntoJson<V> for MaybeUnknown<T, V> {
+    fn into_json(self) -> V {
+        match self {
+            MaybeUnknown::Known(t) => t.into_json(),
+            MaybeUnknown::Unknown(obj) => V::object(obj),
+        }
+    }
+}
+
 impl<V: ConstructibleJsonValue> IntoJson<V> for ReplyTo {
     fn into_json(self) -> V {
         let mut f = self.into_fields();
@@ -3908,13 +5111,11 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for Link<V> {
         let mut f = self.into_fields();
         let mut obj = V::Object::new();
         obj.insert("@type".into(), V::str("Link"));
-        insert_required!(obj, "href", f.take_href().unwrap());
+        insert_required!(obj, "href", f.take_href().expect("href is required"));
         insert_optional!(obj, "contentId", f.take_content_id());
         insert_optional!(obj, "mediaType", f.take_media_type());
         insert_optional!(obj, "size", f.take_size());
-        if let Some(rel) = f.take_relation() {
-            obj.insert("rel".into(), V::string(rel.to_string()));
-        }
+        insert_optional!(obj, "rel", f.take_relation());
         insert_optional!(obj, "display", f.take_display());
         insert_optional!(obj, "title", f.take_title());
         insert_vendor_properties!(obj, f);
@@ -3946,7 +5147,7 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for VirtualLocation<V> {
         obj.insert("@type".into(), V::str("VirtualLocation"));
         insert_optional!(obj, "name", f.take_name());
         insert_optional!(obj, "description", f.take_description());
-        insert_required!(obj, "uri", f.take_uri().unwrap());
+        insert_required!(obj, "uri", f.take_uri().expect("uri is required"));
         insert_optional!(obj, "features", f.take_features());
         insert_vendor_properties!(obj, f);
         V::object(obj)
@@ -3958,7 +5159,7 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for Alert<V> {
         let mut f = self.into_fields();
         let mut obj = V::Object::new();
         obj.insert("@type".into(), V::str("Alert"));
-        insert_required!(obj, "trigger", f.take_trigger().unwrap());
+        insert_required!(obj, "trigger", f.take_trigger().expect("trigger is required"));
         insert_optional!(obj, "acknowledged", f.take_acknowledged());
         insert_optional!(obj, "relatedTo", f.take_related_to());
         insert_optional!(obj, "action", f.take_action());
@@ -3972,9 +5173,9 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for TimeZoneRule<V> {
         let mut f = self.into_fields();
         let mut obj = V::Object::new();
         obj.insert("@type".into(), V::str("TimeZoneRule"));
-        insert_required!(obj, "start", f.take_start().unwrap());
-        insert_required!(obj, "offsetFrom", f.take_offset_from().unwrap());
-        insert_required!(obj, "offsetTo", f.take_offset_to().unwrap());
+        insert_required!(obj, "start", f.take_start().expect("start is required"));
+        insert_required!(obj, "offsetFrom", f.take_offset_from().expect("offset_from is required"));
+        insert_required!(obj, "offsetTo", f.take_offset_to().expect("offset_to is required"));
         insert_optional!(obj, "recurrenceRules", f.take_recurrence_rules());
         insert_optional!(obj, "recurrenceOverrides", f.take_recurrence_overrides());
         insert_optional!(obj, "names", f.take_names());
@@ -3989,7 +5190,7 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for TimeZone<V> {
         let mut f = self.into_fields();
         let mut obj = V::Object::new();
         obj.insert("@type".into(), V::str("TimeZone"));
-        insert_required!(obj, "tzId", f.take_tz_id().unwrap());
+        insert_required!(obj, "tzId", f.take_tz_id().expect("tz_id is required"));
         insert_optional!(obj, "updated", f.take_updated());
         insert_optional!(obj, "url", f.take_url());
         insert_optional!(obj, "validUntil", f.take_valid_until());
@@ -4082,8 +5283,8 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for Event<V> {
         let mut f = self.into_fields();
         let mut obj = V::Object::new();
         obj.insert("@type".into(), V::str("Event"));
-        insert_required!(obj, "uid", f.take_uid().unwrap());
-        insert_required!(obj, "start", f.take_start().unwrap());
+        insert_required!(obj, "uid", f.take_uid().expect("uid is required"));
+        insert_required!(obj, "start", f.take_start().expect("start is required"));
         insert_optional!(obj, "duration", f.take_duration());
         insert_optional!(obj, "status", f.take_status());
         insert_optional!(obj, "relatedTo", f.take_related_to());
@@ -4131,7 +5332,7 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for Task<V> {
         let mut f = self.into_fields();
         let mut obj = V::Object::new();
         obj.insert("@type".into(), V::str("Task"));
-        insert_required!(obj, "uid", f.take_uid().unwrap());
+        insert_required!(obj, "uid", f.take_uid().expect("uid is required"));
         insert_optional!(obj, "due", f.take_due());
         insert_optional!(obj, "start", f.take_start());
         insert_optional!(obj, "estimatedDuration", f.take_estimated_duration());
@@ -4183,7 +5384,7 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for Group<V> {
         let mut f = self.into_fields();
         let mut obj = V::Object::new();
         obj.insert("@type".into(), V::str("Group"));
-        insert_required!(obj, "uid", f.take_uid().unwrap());
+        insert_required!(obj, "uid", f.take_uid().expect("uid is required"));
         if let Some(entries) = f.take_entries()
             && !entries.is_empty()
         {
@@ -4239,8 +5440,8 @@ fn serialize_by_day<V: ConstructibleJsonValue>(set: &WeekdayNumSet) -> V {
         day_obj.insert("@type".into(), V::str("NDay"));
         day_obj.insert("day".into(), V::str(weekday_code(wdn.weekday)));
         if let Some((sign, week)) = wdn.ordinal {
-            let n = (sign as i64) * (week as i64);
-            day_obj.insert("nthOfPeriod".into(), V::int(crate::json::Int::new(n).unwrap()));
+            let n = sign as i32 * week as i32;
+            day_obj.insert("nthOfPeriod".into(), V::int(crate::json::Int::from(n)));
         }
         arr.push(V::object(day_obj));
     }
@@ -4248,65 +5449,44 @@ fn serialize_by_day<V: ConstructibleJsonValue>(set: &WeekdayNumSet) -> V {
 }
 
 fn serialize_second_set<V: ConstructibleJsonValue>(set: &rfc5545_types::rrule::SecondSet) -> V {
-    let mut arr = V::Array::new();
-    for sec in rfc5545_types::rrule::Second::iter() {
-        if set.get(sec) {
-            arr.push(V::unsigned_int(UnsignedInt::new(sec as u64).unwrap()));
-        }
+    let mut arr = V::Array::with_capacity(set.len());
+    for sec in set {
+        arr.push(V::unsigned_int(UnsignedInt::from(sec as u8)));
     }
     V::array(arr)
 }
 
 fn serialize_minute_set<V: ConstructibleJsonValue>(set: &rfc5545_types::rrule::MinuteSet) -> V {
-    let mut arr = V::Array::new();
-    for min in rfc5545_types::rrule::Minute::iter() {
-        if set.get(min) {
-            arr.push(V::unsigned_int(UnsignedInt::new(min as u64).unwrap()));
-        }
+    let mut arr = V::Array::with_capacity(set.len());
+    for min in set {
+        arr.push(V::unsigned_int(UnsignedInt::from(min as u8)));
     }
     V::array(arr)
 }
 
 fn serialize_hour_set<V: ConstructibleJsonValue>(set: &rfc5545_types::rrule::HourSet) -> V {
-    let mut arr = V::Array::new();
-    for hr in rfc5545_types::rrule::Hour::iter() {
-        if set.get(hr) {
-            arr.push(V::unsigned_int(UnsignedInt::new(hr as u64).unwrap()));
-        }
+    let mut arr = V::Array::with_capacity(set.len());
+    for hr in set {
+        arr.push(V::unsigned_int(UnsignedInt::from(hr as u8)));
     }
     V::array(arr)
 }
 
 fn serialize_month_set<V: ConstructibleJsonValue>(set: &rfc5545_types::rrule::MonthSet) -> V {
-    let mut arr = V::Array::new();
-    for m in Month::iter() {
-        if set.get(m) {
-            arr.push(V::unsigned_int(UnsignedInt::new(m.number().get() as u64).unwrap()));
-        }
+    let mut arr = V::Array::with_capacity(set.len());
+    for m in set {
+        arr.push(V::unsigned_int(UnsignedInt::from(m.number().get())));
     }
     V::array(arr)
 }
 
 fn serialize_month_day_set<V: ConstructibleJsonValue>(set: &rfc5545_types::rrule::MonthDaySet) -> V {
-    use rfc5545_types::rrule::{MonthDay, MonthDaySetIndex};
-    let mut arr = V::Array::new();
-    // Positive days 1..=31
-    for d in 1..=31u8 {
-        if let Some(md) = MonthDay::from_repr(d) {
-            let idx = MonthDaySetIndex::from_signed_month_day(Sign::Pos, md);
-            if set.get(idx) {
-                arr.push(V::int(crate::json::Int::new(d as i64).unwrap()));
-            }
-        }
-    }
-    // Negative days -31..=-1
-    for d in 1..=31u8 {
-        if let Some(md) = MonthDay::from_repr(d) {
-            let idx = MonthDaySetIndex::from_signed_month_day(Sign::Neg, md);
-            if set.get(idx) {
-                arr.push(V::int(crate::json::Int::new(-(d as i64)).unwrap()));
-            }
-        }
+    let mut arr = V::Array::with_capacity(set.len());
+    // positive days precede negative days
+    for idx in set {
+        let (sign, md) = idx.to_signed_month_day();
+        let n = sign as i32 * md as i32;
+        arr.push(V::int(crate::json::Int::from(n)));
     }
     V::array(arr)
 }
@@ -4316,31 +5496,18 @@ fn serialize_year_day_nums<V: ConstructibleJsonValue>(set: &BTreeSet<rfc5545_typ
     for ydn in set {
         // YearDayNum wraps a NonZero<i16>
         let n = ydn.get();
-        arr.push(V::int(crate::json::Int::new(n as i64).unwrap()));
+        arr.push(V::int(crate::json::Int::from(n)));
     }
     V::array(arr)
 }
 
 fn serialize_week_no_set<V: ConstructibleJsonValue>(set: &rfc5545_types::rrule::WeekNoSet) -> V {
-    use rfc5545_types::rrule::WeekNoSetIndex;
-    let mut arr = V::Array::new();
-    // Positive weeks 1..=53
-    for w in 1..=53u8 {
-        if let Some(iw) = IsoWeek::from_index(w) {
-            let idx = WeekNoSetIndex::from_signed_week(Sign::Pos, iw);
-            if set.get(idx) {
-                arr.push(V::int(crate::json::Int::new(w as i64).unwrap()));
-            }
-        }
-    }
-    // Negative weeks -53..=-1
-    for w in 1..=53u8 {
-        if let Some(iw) = IsoWeek::from_index(w) {
-            let idx = WeekNoSetIndex::from_signed_week(Sign::Neg, iw);
-            if set.get(idx) {
-                arr.push(V::int(crate::json::Int::new(-(w as i64)).unwrap()));
-            }
-        }
+    let mut arr = V::Array::with_capacity(set.len());
+    // positive weeks precede negative weeks
+    for idx in set {
+        let (sign, week) = idx.to_signed_week();
+        let n = sign as i32 * week as i32;
+        arr.push(V::int(crate::json::Int::from(n)));
     }
     V::array(arr)
 }
@@ -4386,7 +5553,7 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for RRule {
         if let Some(interval) = self.interval {
             obj.insert(
                 "interval".into(),
-                V::unsigned_int(UnsignedInt::new(interval.get().get()).unwrap()),
+                V::unsigned_int(UnsignedInt::saturating_new(interval.get().get())),
             );
         }
 
@@ -4394,7 +5561,7 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for RRule {
             Some(rfc5545_types::rrule::Termination::Count(c)) => {
                 obj.insert(
                     "count".into(),
-                    V::unsigned_int(UnsignedInt::new(c).unwrap()),
+                    V::unsigned_int(UnsignedInt::saturating_new(c)),
                 );
             }
             Some(rfc5545_types::rrule::Termination::Until(ref u)) => {
@@ -4407,6 +5574,27 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for RRule {
             obj.insert("firstDayOfWeek".into(), V::str(weekday_code(ws)));
         }
 
+        // the iCalendar names of these values are uppercase, but JSCalendar uses lowercase
+        match self.rscale {
+            Some(Token::Known(rscale)) => {
+                obj.insert(
+                    "rscale".into(),
+                    V::string(rscale.to_string().to_ascii_lowercase()),
+                );
+            }
+            Some(Token::Unknown(rscale)) => {
+                obj.insert("rscale".into(), V::string(rscale.into()));
+            }
+            None => {}
+        }
+
+        if let Some(skip) = self.skip {
+            obj.insert(
+                "skip".into(),
+                V::string(skip.to_string().to_ascii_lowercase()),
+            );
+        }
+
         // Core by-rules
         if let Some(ref set) = self.core_by_rules.by_second {
             obj.insert("bySecond".into(), serialize_second_set::<V>(set));
@@ -4438,6 +5626,14 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for RRule {
             obj.insert("byWeekNo".into(), serialize_week_no_set::<V>(set));
         }
 
+        if !self.extras.is_empty() {
+            let mut arr = V::Array::with_capacity(self.extras.len());
+            for part in &self.extras {
+                arr.push(V::string(part.to_string()));
+            }
+            obj.insert(RRULE_EXTRA_PARTS.into(), V::array(arr));
+        }
+
         V::object(obj)
     }
 }
@@ -4544,6 +5740,340 @@ mod tests {
         assert!(matches!(toe2, TaskOrEvent::Task(_)));
     }
 
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn unknown_type_sub_objects() {
+        use serde_json::json;
+
+        let input = json!({
+            "@type": "Event",
+            "uid": "unknown-types",
+            "start": "2024-03-01T10:00:00",
+            "locations": {
+                "l1": { "@type": "Location", "name": "Room 1" },
+                "l2": { "name": "Room 2" },
+                "l3": { "@type": "FutureLocation", "name": 7 }
+            },
+            "alerts": {
+                "a1": {
+                    "@type": "Alert",
+                    "trigger": { "@type": "FutureTrigger", "every": "PT1H" }
+                },
+                "a2": { "@type": "FutureAlert" }
+            }
+        });
+
+        let event = Event::<serde_json::Value>::try_from_json(input).expect("valid event");
+        let locations = event.locations().unwrap();
+        let id = |s| Id::new(s).unwrap();
+        assert!(locations[id("l1")].as_known().is_some());
+        assert!(locations[id("l2")].as_known().is_some());
+        assert!(matches!(locations[id("l3")], MaybeUnknown::Unknown(_)));
+
+        let alerts = event.alerts().unwrap();
+        let a1 = alerts[id("a1")].as_known().unwrap();
+        assert!(matches!(a1.trigger(), Trigger::Unknown(_)));
+        assert!(matches!(alerts[id("a2")], MaybeUnknown::Unknown(_)));
+
+        // a trigger without any @type is still rejected
+        let input = json!({ "@type": "Alert", "trigger": { "offset": "PT1H" } });
+        assert!(Alert::<serde_json::Value>::try_from_json(input).is_err());
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn defaulting_accessors() {
+        use serde_json::json;
+
+        let input = json!({
+            "@type": "Event",
+            "uid": "defaults",
+            "start": "2024-03-01T10:00:00",
+            "participants": { "p1": { "@type": "Participant" } },
+            "alerts": {
+                "a1": { "@type": "Alert", "trigger": { "@type": "OffsetTrigger", "offset": "-PT5M" } }
+            }
+        });
+        let event = Event::<serde_json::Value>::try_from_json(input).expect("valid event");
+
+        assert_eq!(event.sequence_or_default().get(), 0);
+        assert_eq!(event.priority_or_default(), Priority::Zero);
+        assert_eq!(
+            event.free_busy_status_or_default(),
+            Token::Known(FreeBusyStatus::Busy)
+        );
+        assert_eq!(event.privacy_or_default(), Token::Known(Privacy::Public));
+        assert_eq!(
+            event.status_or_default(),
+            Token::Known(EventStatus::Confirmed)
+        );
+        assert_eq!(event.duration_or_default(), Duration::ZERO);
+        assert_eq!(event.description_content_type_or_default(), "text/plain");
+        assert!(!event.excluded_or_default());
+        assert!(!event.show_without_time_or_default());
+        assert!(!event.use_default_alerts_or_default());
+        assert_eq!(event.display_color(), Color::assign("defaults", []));
+
+        let participant = &event.participants().unwrap()[Id::new("p1").unwrap()];
+        assert!(!participant.expect_reply_or_default());
+        assert_eq!(
+            participant.participation_status_or_default(),
+            Token::Known(ParticipationStatus::NeedsAction)
+        );
+        assert_eq!(
+            participant.schedule_agent_or_default(),
+            Token::Known(ScheduleAgent::Server)
+        );
+
+        let alert = event.alerts().unwrap()[Id::new("a1").unwrap()]
+            .as_known()
+            .unwrap();
+        assert_eq!(
+            alert.action_or_default(),
+            Token::Known(AlertAction::Display)
+        );
+        let Trigger::Offset(trigger) = alert.trigger() else {
+            panic!("expected an offset trigger");
+        };
+        assert_eq!(
+            trigger.relative_to_or_default(),
+            Token::Known(AlertRelativeTo::Start)
+        );
+
+        let input = json!({
+            "@type": "Task",
+            "uid": "explicit",
+            "sequence": 4,
+            "freeBusyStatus": "free",
+            "excluded": true
+        });
+        let task = Task::<serde_json::Value>::try_from_json(input).expect("valid task");
+        assert_eq!(task.sequence_or_default().get(), 4);
+        assert_eq!(
+            task.free_busy_status_or_default(),
+            Token::Known(FreeBusyStatus::Free)
+        );
+        assert!(task.excluded_or_default());
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn link_queries() {
+        use serde_json::json;
+
+        let input = json!({
+            "@type": "Event",
+            "uid": "links",
+            "start": "2024-03-01T10:00:00",
+            "links": {
+                "small": { "@type": "Link", "href": "https://example.com/s.png", "mediaType": "image/png", "size": 1000 },
+                "large": { "@type": "Link", "href": "https://example.com/l.jpg", "mediaType": "image/jpeg", "size": 90000 },
+                "unsized": { "@type": "Link", "href": "https://example.com/u.gif", "mediaType": "image/gif" },
+                "agenda": { "@type": "Link", "href": "https://example.com/a.pdf", "mediaType": "application/pdf", "rel": "enclosure" },
+                "notes": { "@type": "Link", "href": "https://example.com/notes", "rel": "enclosure" },
+                "other": { "@type": "example.com:Link", "href": "https://example.com/x", "rel": "enclosure" }
+            }
+        });
+        let event = Event::<serde_json::Value>::try_from_json(input).expect("valid event");
+
+        let mut enclosures: Vec<_> = event
+            .links_by_rel(LinkRelation::Enclosure)
+            .map(|(id, _)| id.as_str())
+            .collect();
+        enclosures.sort();
+        assert_eq!(enclosures, ["agenda", "notes"]);
+        assert_eq!(event.links_by_rel(LinkRelation::Icon).count(), 0);
+
+        let image = |max_size| event.preferred_image(max_size).map(|(id, _)| id.as_str());
+        assert_eq!(image(100_000), Some("large"));
+        assert_eq!(image(50_000), Some("small"));
+        assert_eq!(image(10), Some("unsized"));
+
+        let attachments = |pattern| {
+            let mut ids: Vec<_> = event
+                .attachments(pattern)
+                .map(|(id, _)| id.as_str())
+                .collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(attachments("application/pdf"), ["agenda"]);
+        assert_eq!(attachments("*/*"), ["agenda", "notes"]);
+        assert!(attachments("image/*").is_empty());
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn link_preferences() {
+        use serde_json::json;
+
+        let input = json!({
+            "@type": "Event",
+            "uid": "links",
+            "start": "2024-03-01T10:00:00",
+            "links": {
+                "alt": { "@type": "Link", "href": "https://example.com/alt", "rel": "alternate" },
+                "desc2": { "@type": "Link", "href": "https://example.com/d2", "rel": "describedby" },
+                "desc1": { "@type": "Link", "href": "https://example.com/d1", "rel": "describedby" },
+                "badge": { "@type": "Link", "href": "https://example.com/b.svg", "display": "badge" },
+                "tiny": { "@type": "Link", "href": "https://example.com/t.png", "mediaType": "image/png", "size": 100 },
+                "huge": { "@type": "Link", "href": "https://example.com/h.png", "mediaType": "image/png", "size": 900000 },
+                "banner": { "@type": "Link", "href": "https://example.com/g.png", "display": "graphic" }
+            }
+        });
+        let event = Event::<serde_json::Value>::try_from_json(input).expect("valid event");
+
+        let link =
+            |relations: &[LinkRelation]| event.preferred_link(relations).map(|(id, _)| id.as_str());
+        assert_eq!(
+            link(&[LinkRelation::DescribedBy, LinkRelation::Alternate]),
+            Some("desc1")
+        );
+        assert_eq!(
+            link(&[LinkRelation::Icon, LinkRelation::Alternate]),
+            Some("alt")
+        );
+        assert_eq!(link(&[LinkRelation::Icon]), None);
+
+        let image = |purpose| event.image_for(purpose).map(|(id, _)| id.as_str());
+        assert_eq!(image(DisplayPurpose::Badge), Some("badge"));
+        assert_eq!(image(DisplayPurpose::Graphic), Some("banner"));
+        assert_eq!(image(DisplayPurpose::Thumbnail), Some("tiny"));
+        assert_eq!(image(DisplayPurpose::FullSize), Some("huge"));
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn utc_date_time_policy() {
+        use serde_json::json;
+
+        let input = json!({
+            "@type": "Event",
+            "uid": "lenient",
+            "start": "2024-03-01T10:00:00",
+            "created": "2024-02-01T08:30:00",
+            "updated": "2024-02-02T08:30:00Z",
+            "alerts": {
+                "a1": {
+                    "@type": "Alert",
+                    "acknowledged": "2024-03-01T09:55:00",
+                    "trigger": { "@type": "OffsetTrigger", "offset": "-PT5M" }
+                }
+            }
+        });
+
+        let strict = ParseOptions::default();
+        assert!(Event::<serde_json::Value>::try_from_json_with(input.clone(), &strict).is_err());
+
+        let options = strict.with_utc_date_times(UtcDateTimePolicy::AssumeUtc);
+        let (event, coerced) =
+            Event::<serde_json::Value>::try_from_json_with(input.clone(), &options).unwrap();
+        assert_eq!(event.created().unwrap().to_string(), "2024-02-01T08:30:00Z");
+        assert_eq!(event.updated().unwrap().to_string(), "2024-02-02T08:30:00Z");
+        let mut pointers: Vec<_> = coerced.iter().map(|c| c.pointer.to_string()).collect();
+        pointers.sort();
+        assert_eq!(pointers, ["alerts/a1/acknowledged", "created"]);
+
+        let offset = parse_utc_offset("+02:00").unwrap();
+        let options = strict.with_utc_date_times(UtcDateTimePolicy::AssumeOffset(offset));
+        let (event, _) = Event::<serde_json::Value>::try_from_json_with(input, &options).unwrap();
+        assert_eq!(event.created().unwrap().to_string(), "2024-02-01T06:30:00Z");
+        let alert = event.alerts().unwrap()[Id::new("a1").unwrap()]
+            .as_known()
+            .unwrap();
+        assert_eq!(
+            alert.acknowledged().unwrap().to_string(),
+            "2024-03-01T07:55:00Z"
+        );
+
+        let group = json!({
+            "@type": "Group",
+            "uid": "group",
+            "updated": "2024-02-03T00:00:00",
+            "entries": [{ "@type": "Task", "uid": "task", "created": "2024-02-04T00:00:00" }]
+        });
+        let options = strict.with_utc_date_times(UtcDateTimePolicy::AssumeUtc);
+        let (_, coerced) = Group::<serde_json::Value>::try_from_json_with(group, &options).unwrap();
+        let pointers: Vec<_> = coerced.iter().map(|c| c.pointer.to_string()).collect();
+        assert_eq!(pointers.len(), 2);
+        assert!(pointers.contains(&"entries/0/created".to_string()));
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn serialize_duration_format() {
+        use serde_json::json;
+
+        let event: Event<serde_json::Value> = Event::try_from_json(json!({
+            "@type": "Event",
+            "uid": "a",
+            "start": "2024-01-01T09:00:00",
+            "duration": "PT90M",
+            "alerts": {
+                "1": {
+                    "@type": "Alert",
+                    "trigger": { "@type": "OffsetTrigger", "offset": "-PT3600S" }
+                }
+            }
+        }))
+        .unwrap();
+        let serialize = |format| {
+            let options = SerializeOptions::default().with_durations(format);
+            let json: serde_json::Value = event.clone().into_json_with(&options);
+            (
+                json["duration"].clone(),
+                json["alerts"]["1"]["trigger"]["offset"].clone(),
+            )
+        };
+
+        assert_eq!(
+            serialize(DurationFormat::PreserveInput),
+            (json!("PT90M"), json!("-PT3600S"))
+        );
+        assert_eq!(
+            serialize(DurationFormat::LargestUnits),
+            (json!("PT1H30M"), json!("-PT1H"))
+        );
+        assert_eq!(
+            serialize(DurationFormat::SecondsOnly),
+            (json!("PT5400S"), json!("-PT3600S"))
+        );
+
+        let group: Group<serde_json::Value> = Group::try_from_json(json!({
+            "@type": "Group",
+            "uid": "g",
+            "entries": [{
+                "@type": "Task",
+                "uid": "t",
+                "estimatedDuration": "PT1H30M"
+            }]
+        }))
+        .unwrap();
+        let options = SerializeOptions::default().with_durations(DurationFormat::SecondsOnly);
+        let json: serde_json::Value = group.into_json_with(&options);
+        assert_eq!(json["entries"][0]["estimatedDuration"], "PT5400S");
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn reply_to_web_must_be_https() {
+        use serde_json::json;
+
+        let input = json!({ "web": "https://example.com/rsvp" });
+        let reply_to = ReplyTo::try_from_json(input).expect("valid replyTo");
+        assert_eq!(
+            reply_to.web().map(|web| web.as_str()),
+            Some("https://example.com/rsvp")
+        );
+
+        let input = json!({ "web": "http://example.com/rsvp" });
+        let err = ReplyTo::try_from_json(input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "web: invalid value \"http://example.com/rsvp\": expected https: scheme, found http:"
+        );
+    }
+
     #[cfg(feature = "serde_json")]
     #[test]
     fn missing_required_field_error() {
@@ -4577,4 +6107,256 @@ mod tests {
         assert!(matches!(err.error, TypeErrorOr::TypeError(_)));
         assert_eq!(err.path.front(), Some(&PathSegment::Static("uid")));
     }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn event_try_from_json_partial() {
+        use serde_json::{Value, json};
+
+        let input = json!({
+            "@type": "Event",
+            "uid": "partial-1",
+            "start": "2024-01-01T09:00:00",
+            "title": 5,
+            "description": "Broken but readable",
+            "duration": "one hour",
+            "locations": {
+                "loc-1": { "@type": "Location", "name": 7 },
+                "loc-2": { "@type": "Location", "name": "Room 2" }
+            }
+        });
+
+        assert!(Event::<Value>::try_from_json(input.clone()).is_err());
+        let (mut event, invalid) = Event::try_from_json_partial(input).unwrap();
+        assert_eq!(
+            event.description().map(String::as_str),
+            Some("Broken but readable")
+        );
+        assert!(event.duration().is_none());
+        assert!(event.title().is_none());
+        assert_eq!(event.vendor_property_iter().count(), 0);
+
+        let locations = event.locations().unwrap();
+        assert_eq!(locations.len(), 1);
+        let (id, location) = locations.iter().next().unwrap();
+        assert_eq!(id.as_str(), "loc-2");
+        let MaybeUnknown::Known(location) = location else {
+            panic!("expected a known location");
+        };
+        assert_eq!(location.name().map(String::as_str), Some("Room 2"));
+
+        let mut invalid: Vec<_> = IntoIterator::into_iter(invalid)
+            .map(|property| (Vec::from(property.error.path().clone()), property.value))
+            .collect();
+        invalid.sort_by_key(|(path, _)| format!("{path:?}"));
+        assert_eq!(
+            invalid,
+            [
+                (vec![PathSegment::Static("duration")], json!("one hour")),
+                (
+                    vec![
+                        PathSegment::Static("locations"),
+                        PathSegment::String("loc-1".into()),
+                        PathSegment::Static("name"),
+                    ],
+                    json!({ "@type": "Location", "name": 7 })
+                ),
+                (vec![PathSegment::Static("title")], json!(5)),
+            ]
+        );
+
+        // a corrected property is set with its typed setter
+        event.set_title("Fixed".into());
+        let output: Value = event.into_json();
+        assert_eq!(output["title"], "Fixed");
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn try_from_json_partial_required_fields() {
+        use serde_json::{Value, json};
+
+        let input = json!({ "@type": "Event", "uid": "partial-2", "start": "tomorrow" });
+        let err = Event::<Value>::try_from_json_partial(input).unwrap_err();
+        assert!(matches!(
+            err.path().front(),
+            Some(PathSegment::Static("start"))
+        ));
+
+        let input = json!({ "@type": "Task", "title": 1 });
+        let err = Task::<Value>::try_from_json_partial(input).unwrap_err();
+        assert!(matches!(
+            err.error,
+            TypeErrorOr::Other(ObjectFromJsonError::MissingField("uid"))
+        ));
+
+        let (task, invalid) =
+            Task::<Value>::try_from_json_partial(json!({ "uid": "t", "title": 1 })).unwrap();
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].value, json!(1));
+        assert!(task.title().is_none());
+        assert!(task.vendor_property("title").is_none());
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn event_summary() {
+        use serde_json::{Value, json};
+
+        let input = json!({
+            "@type": "Event",
+            "uid": "summary",
+            "title": "Review",
+            "start": "2024-01-01T09:00:00",
+            "duration": "PT1H",
+            "timeZone": "Europe/Berlin",
+            "updated": "2024-01-01T00:00:00Z",
+            "status": "tentative",
+            "participants": { "a": { "@type": "Participant", "roles": { "owner": true } } }
+        });
+        let summary = EventSummary::try_from_json(input.clone()).unwrap();
+        let event: Event<Value> = Event::try_from_json(input).unwrap();
+        assert_eq!(summary, event.summary());
+        assert_eq!(summary.title.as_deref(), Some("Review"));
+        assert_eq!(summary.status, Some(Token::Known(EventStatus::Tentative)));
+
+        // other properties are not validated
+        let input = json!({
+            "uid": "summary",
+            "start": "2024-01-01T09:00:00",
+            "participants": 42,
+            "recurrenceRules": "invalid"
+        });
+        assert!(Event::<Value>::try_from_json(input.clone()).is_err());
+        let summary = EventSummary::try_from_json(input).unwrap();
+        assert_eq!(summary.duration, None);
+
+        let err = EventSummary::try_from_json(json!({ "uid": "summary" })).unwrap_err();
+        assert!(matches!(
+            err.error,
+            TypeErrorOr::Other(ObjectFromJsonError::MissingField("start"))
+        ));
+        let err = EventSummary::try_from_json(json!({
+            "uid": "summary",
+            "start": "2024-01-01T09:00:00",
+            "duration": "1 hour"
+        }))
+        .unwrap_err();
+        assert_eq!(err.path.len(), 1);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn extract_selected_properties() {
+        use serde_json::json;
+
+        let input = json!({
+            "@type": "Event",
+            "uid": "summary",
+            "title": "Review",
+            "start": "2024-01-01T09:00:00",
+            "duration": "not a duration",
+            "status": "confirmed",
+            "alerts": 42
+        });
+        let summary =
+            Event::extract(input.clone(), &[SummaryProperty::Title, SummaryProperty::Status])
+                .unwrap();
+        assert_eq!(summary.uid.as_str(), "summary");
+        assert_eq!(summary.title.as_deref(), Some("Review"));
+        assert_eq!(summary.status, Some(Token::Known(EventStatus::Confirmed)));
+        assert_eq!(summary.duration, None);
+
+        // the start and uid are read even if they are not selected
+        let summary = Event::extract(input.clone(), &[]).unwrap();
+        assert_eq!(summary.title, None);
+        assert_eq!(summary.start.to_string(), "2024-01-01T09:00:00");
+
+        // a selected property is validated
+        let err = Event::extract(input.clone(), &[SummaryProperty::Duration]).unwrap_err();
+        assert_eq!(err.path, [PathSegment::Static("duration")]);
+        assert!(EventSummary::try_from_json(input).is_err());
+
+        assert_eq!(SummaryProperty::from_name("timeZone"), Some(SummaryProperty::TimeZone));
+        assert_eq!(SummaryProperty::from_name("alerts"), None);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn rrule_count_saturates() {
+        use serde_json::{Value, json};
+
+        let mut rule = RRule::try_from_json(json!({
+            "@type": "RecurrenceRule",
+            "frequency": "daily",
+            "count": 3
+        }))
+        .unwrap();
+        rule.termination = Some(rfc5545_types::rrule::Termination::Count(u64::MAX));
+
+        let json: Value = rule.into_json();
+        assert_eq!(json["count"], json!(crate::json::UnsignedInt::MAX.get()));
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn field_value_errors_are_typed() {
+        use std::error::Error;
+
+        use serde_json::{Value, json};
+
+        let invalid = |input: Value| match Event::<Value>::try_from_json(input).unwrap_err().error {
+            TypeErrorOr::Other(ObjectFromJsonError::InvalidFieldValue(error)) => error,
+            error => panic!("unexpected error: {error:?}"),
+        };
+        let event = |extra: Value| {
+            let mut event = json!({ "@type": "Event", "uid": "e", "start": "2024-01-01T09:00:00" });
+            event.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+            event
+        };
+
+        let error = invalid(event(json!({
+            "timeZones": { "/tz": {
+                "@type": "TimeZone",
+                "tzId": "tz",
+                "standard": [{
+                    "@type": "TimeZoneRule",
+                    "start": "2024-01-01T00:00:00",
+                    "offsetFrom": "+0100",
+                    "offsetTo": "+01:00"
+                }]
+            } }
+        })));
+        assert_eq!(
+            error,
+            FieldValueError::UtcOffset(InvalidUtcOffsetError("+0100".into()))
+        );
+        assert_eq!(error.to_string(), "invalid UTC offset string: \"+0100\"");
+
+        let error = invalid(event(json!({
+            "recurrenceRules": [{ "frequency": "weekly", "byDay": [{ "day": "xx" }] }]
+        })));
+        assert!(matches!(
+            error,
+            FieldValueError::RRule(RRuleFromJsonError::ByRule(ByRuleParseError::InvalidValue))
+        ));
+
+        let error = invalid(event(json!({ "priority": 10 })));
+        assert!(matches!(error, FieldValueError::Priority(_)));
+
+        let error = invalid(event(json!({ "locations": { "not an id": {} } })));
+        assert!(matches!(error, FieldValueError::Id(_)));
+
+        let error = Event::<Value>::try_from_json(event(json!({ "color": "nocolor" }))).unwrap_err();
+        let source = error.error().source().unwrap();
+        assert!(matches!(
+            source.downcast_ref::<FieldValueError>(),
+            Some(FieldValueError::Color(_))
+        ));
+
+        // errors of other types are only equal to their own clones
+        let other = FieldValueError::other(std::fmt::Error);
+        assert_eq!(other, other.clone());
+        assert_ne!(other, FieldValueError::other(std::fmt::Error));
+    }
 }
@@ -0,0 +1,180 @@
+//! Equality, hashing, and chronological ordering of calendar objects.
+//!
+//! [`Event`], [`Task`], and [`TaskOrEvent`] are [`Eq`] whenever their JSON value type is, and
+//! are always [`Hash`]. Since their properties include maps and sets, which cannot be hashed, an
+//! object is hashed by the properties which identify it and its revision: its `uid`,
+//! `recurrenceId`, `sequence`, and `start` (or `due`). Objects which differ only in other
+//! properties therefore hash alike, but remain unequal.
+//!
+//! The [`SortKey`] of an object orders it chronologically, so that a collection can be sorted
+//! with `sort_by(|a, b| a.sort_key().cmp(&b.sort_key()))`:
+//!
+//! ```
+//! # #[cfg(feature = "serde_json")]
+//! # {
+//! use jscalendar::prelude::*;
+//! use serde_json::{Value, json};
+//!
+//! let event = |uid: &str, start: &str| -> Event<Value> {
+//!     Event::try_from_json(json!({ "@type": "Event", "uid": uid, "start": start })).unwrap()
+//! };
+//!
+//! let mut events = vec![
+//!     event("b", "2024-01-02T09:00:00"),
+//!     event("a", "2024-01-01T09:00:00"),
+//!     event("b", "2024-01-02T09:00:00"),
+//! ];
+//! events.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+//! events.dedup();
+//! assert_eq!(events.len(), 2);
+//! assert_eq!(events[0].uid().as_str(), "a");
+//! # }
+//! ```
+
+use std::hash::{Hash, Hasher};
+
+use crate::{
+    json::JsonValue,
+    model::{
+        object::{Event, Task, TaskOrEvent},
+        string::Uid,
+        time::{DateTime, Local},
+    },
+};
+
+/// A key which orders calendar objects by their local start time, then by `uid`, then by
+/// `recurrenceId`.
+///
+/// The start of a task is its `start`, or else its `due` time; a task with neither orders before
+/// every object with a start. Since time zones are not resolved, start times in different time
+/// zones are compared as if they were in the same one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SortKey<'a> {
+    /// The local start time of the object.
+    pub start: Option<DateTime<Local>>,
+    /// The UID of the object.
+    pub uid: &'a Uid,
+    /// The recurrence ID of the object.
+    pub recurrence_id: Option<DateTime<Local>>,
+}
+
+impl<V: JsonValue> Event<V> {
+    /// Returns the key which orders this event chronologically.
+    pub fn sort_key(&self) -> SortKey<'_> {
+        SortKey {
+            start: Some(*self.start()),
+            uid: self.uid(),
+            recurrence_id: self.recurrence_id().copied(),
+        }
+    }
+}
+
+impl<V: JsonValue> Task<V> {
+    /// Returns the key which orders this task chronologically.
+    pub fn sort_key(&self) -> SortKey<'_> {
+        SortKey {
+            start: self.start().or(self.due()).copied(),
+            uid: self.uid(),
+            recurrence_id: self.recurrence_id().copied(),
+        }
+    }
+}
+
+impl<V: JsonValue> TaskOrEvent<V> {
+    /// Returns the key which orders this object chronologically.
+    pub fn sort_key(&self) -> SortKey<'_> {
+        match self {
+            Self::Task(task) => task.sort_key(),
+            Self::Event(event) => event.sort_key(),
+        }
+    }
+}
+
+impl<V> Eq for Event<V>
+where
+    V: JsonValue + Eq,
+    Self: PartialEq,
+{
+}
+
+impl<V> Eq for Task<V>
+where
+    V: JsonValue + Eq,
+    Self: PartialEq,
+{
+}
+
+impl<V> Eq for TaskOrEvent<V>
+where
+    V: JsonValue + Eq,
+    Self: PartialEq,
+{
+}
+
+impl<V: JsonValue> Hash for Event<V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.sort_key().hash(state);
+        self.sequence().hash(state);
+    }
+}
+
+impl<V: JsonValue> Hash for Task<V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.sort_key().hash(state);
+        self.sequence().hash(state);
+    }
+}
+
+impl<V: JsonValue> Hash for TaskOrEvent<V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::Task(task) => task.hash(state),
+            Self::Event(event) => event.hash(state),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use std::collections::HashSet;
+
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::json::TryFromJson;
+
+    fn task(uid: &str, members: Value) -> Task<Value> {
+        let mut value = json!({ "@type": "Task", "uid": uid });
+        value
+            .as_object_mut()
+            .unwrap()
+            .extend(members.as_object().unwrap().clone());
+        Task::try_from_json(value).unwrap()
+    }
+
+    #[test]
+    fn sort_key_orders_by_start_then_uid() {
+        let mut tasks = [
+            task("c", json!({ "due": "2024-01-02T00:00:00" })),
+            task("b", json!({ "start": "2024-01-01T00:00:00" })),
+            task("a", json!({ "start": "2024-01-01T00:00:00" })),
+            task("d", json!({})),
+        ];
+        tasks.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+
+        let uids: Vec<_> = tasks.iter().map(|task| task.uid().as_str()).collect();
+        assert_eq!(uids, ["d", "a", "b", "c"]);
+    }
+
+    #[test]
+    fn hash_set_deduplicates() {
+        let entries: HashSet<TaskOrEvent<Value>> = [
+            TaskOrEvent::Task(task("a", json!({ "title": "One" }))),
+            TaskOrEvent::Task(task("a", json!({ "title": "One" }))),
+            TaskOrEvent::Task(task("a", json!({ "title": "Two" }))),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(entries.len(), 2);
+    }
+}
@@ -0,0 +1,1035 @@
+//! Constants for the member names of JSCalendar objects.
+//!
+//! Server code often refers to properties by name, for example when building JMAP property
+//! filters or the pointers of a [`PatchObject`](crate::model::object::PatchObject). Using these
+//! constants instead of string literals turns a misspelled name into a compile error.
+//!
+//! Each name is defined once, even if it occurs in several kinds of object; for example, [`START`]
+//! is the `start` property of events, tasks, and time zone rules alike.
+//!
+//! [`Property`] enumerates the same names, and describes the kinds of object which have each
+//! property and the type of its value there.
+
+use std::fmt;
+
+// Metadata properties (RFC 8984 §4.1)
+
+/// The type of a JSCalendar object (RFC 8984 §4.1.1).
+pub const TYPE: &str = "@type";
+/// The unique identifier of an object (RFC 8984 §4.1.2).
+pub const UID: &str = "uid";
+/// The relations of an object or alert to other objects (RFC 8984 §4.1.3).
+pub const RELATED_TO: &str = "relatedTo";
+/// The product which created an object (RFC 8984 §4.1.4).
+pub const PROD_ID: &str = "prodId";
+/// The creation time of an object (RFC 8984 §4.1.5).
+pub const CREATED: &str = "created";
+/// The last modification time of an object or time zone (RFC 8984 §4.1.6).
+pub const UPDATED: &str = "updated";
+/// The revision number of an object (RFC 8984 §4.1.7).
+pub const SEQUENCE: &str = "sequence";
+/// The iTIP method of an object (RFC 8984 §4.1.8).
+pub const METHOD: &str = "method";
+
+// What and where properties (RFC 8984 §4.2)
+
+/// The title of an object or link (RFC 8984 §4.2.1).
+pub const TITLE: &str = "title";
+/// The description of an object, location, or participant (RFC 8984 §4.2.2).
+pub const DESCRIPTION: &str = "description";
+/// The media type of the `description` (RFC 8984 §4.2.3).
+pub const DESCRIPTION_CONTENT_TYPE: &str = "descriptionContentType";
+/// Whether the time of an object is unimportant (RFC 8984 §4.2.4).
+pub const SHOW_WITHOUT_TIME: &str = "showWithoutTime";
+/// The physical locations of an object (RFC 8984 §4.2.5).
+pub const LOCATIONS: &str = "locations";
+/// The virtual locations of an object (RFC 8984 §4.2.6).
+pub const VIRTUAL_LOCATIONS: &str = "virtualLocations";
+/// The links of an object, location, or participant (RFC 8984 §4.2.7).
+pub const LINKS: &str = "links";
+/// The language of an object (RFC 8984 §4.2.8).
+pub const LOCALE: &str = "locale";
+/// The keywords of an object (RFC 8984 §4.2.9).
+pub const KEYWORDS: &str = "keywords";
+/// The categories of an object (RFC 8984 §4.2.10).
+pub const CATEGORIES: &str = "categories";
+/// The display color of an object (RFC 8984 §4.2.11).
+pub const COLOR: &str = "color";
+
+// Recurrence properties (RFC 8984 §4.3)
+
+/// The start of an occurrence of a recurring object (RFC 8984 §4.3.1).
+pub const RECURRENCE_ID: &str = "recurrenceId";
+/// The time zone of the `recurrenceId` (RFC 8984 §4.3.2).
+pub const RECURRENCE_ID_TIME_ZONE: &str = "recurrenceIdTimeZone";
+/// The recurrence rules of an object or time zone rule (RFC 8984 §4.3.3).
+pub const RECURRENCE_RULES: &str = "recurrenceRules";
+/// The recurrence rules excluding occurrences of an object (RFC 8984 §4.3.4).
+pub const EXCLUDED_RECURRENCE_RULES: &str = "excludedRecurrenceRules";
+/// The overridden occurrences of an object or time zone rule (RFC 8984 §4.3.5).
+pub const RECURRENCE_OVERRIDES: &str = "recurrenceOverrides";
+/// Whether an occurrence is excluded (RFC 8984 §4.3.6).
+pub const EXCLUDED: &str = "excluded";
+
+// Sharing and scheduling properties (RFC 8984 §4.4)
+
+/// The priority of an object (RFC 8984 §4.4.1).
+pub const PRIORITY: &str = "priority";
+/// How an object affects free-busy time (RFC 8984 §4.4.2).
+pub const FREE_BUSY_STATUS: &str = "freeBusyStatus";
+/// The privacy classification of an object (RFC 8984 §4.4.3).
+pub const PRIVACY: &str = "privacy";
+/// The methods for replying to the organizer (RFC 8984 §4.4.4).
+pub const REPLY_TO: &str = "replyTo";
+/// The sender of an object or participant (RFC 8984 §4.4.5).
+pub const SENT_BY: &str = "sentBy";
+/// The participants of an object (RFC 8984 §4.4.6).
+pub const PARTICIPANTS: &str = "participants";
+/// The scheduling status of an object (RFC 8984 §4.4.7).
+pub const REQUEST_STATUS: &str = "requestStatus";
+
+// Alerts properties (RFC 8984 §4.5)
+
+/// Whether to use the default alerts of the calendar (RFC 8984 §4.5.1).
+pub const USE_DEFAULT_ALERTS: &str = "useDefaultAlerts";
+/// The alerts of an object (RFC 8984 §4.5.2).
+pub const ALERTS: &str = "alerts";
+
+// Multilingual properties (RFC 8984 §4.6)
+
+/// The localizations of an object (RFC 8984 §4.6.1).
+pub const LOCALIZATIONS: &str = "localizations";
+
+// Time zone properties (RFC 8984 §4.7)
+
+/// The time zone of an object or location (RFC 8984 §4.7.1).
+pub const TIME_ZONE: &str = "timeZone";
+/// The custom time zones of an object (RFC 8984 §4.7.2).
+pub const TIME_ZONES: &str = "timeZones";
+
+// Event, task, and group properties (RFC 8984 §5)
+
+/// The start of an event, task, or time zone rule (RFC 8984 §5.1.1).
+pub const START: &str = "start";
+/// The duration of an event (RFC 8984 §5.1.2).
+pub const DURATION: &str = "duration";
+/// The scheduling status of an event (RFC 8984 §5.1.3).
+pub const STATUS: &str = "status";
+/// The due date of a task (RFC 8984 §5.2.1).
+pub const DUE: &str = "due";
+/// The estimated duration of a task (RFC 8984 §5.2.3).
+pub const ESTIMATED_DURATION: &str = "estimatedDuration";
+/// The completion percentage of a task or task participant (RFC 8984 §5.2.4).
+pub const PERCENT_COMPLETE: &str = "percentComplete";
+/// The progress of a task or task participant (RFC 8984 §5.2.5).
+pub const PROGRESS: &str = "progress";
+/// The last time the `progress` changed (RFC 8984 §5.2.6).
+pub const PROGRESS_UPDATED: &str = "progressUpdated";
+/// The entries of a group (RFC 8984 §5.3.1).
+pub const ENTRIES: &str = "entries";
+/// The source of a group (RFC 8984 §5.3.2).
+pub const SOURCE: &str = "source";
+
+// Relation properties (RFC 8984 §1.4.10)
+
+/// The kinds of a relation (RFC 8984 §1.4.10).
+pub const RELATION: &str = "relation";
+
+// Link properties (RFC 8984 §1.4.11)
+
+/// The URI of a link (RFC 8984 §1.4.11).
+pub const HREF: &str = "href";
+/// The content ID of a link (RFC 8984 §1.4.11).
+pub const CONTENT_ID: &str = "contentId";
+/// The media type of a link (RFC 8984 §1.4.11).
+pub const MEDIA_TYPE: &str = "mediaType";
+/// The size in octets of a link target (RFC 8984 §1.4.11).
+pub const SIZE: &str = "size";
+/// The relation of a link to its object (RFC 8984 §1.4.11).
+pub const REL: &str = "rel";
+/// The intended purpose of an image link (RFC 8984 §1.4.11).
+pub const DISPLAY: &str = "display";
+
+// Location properties (RFC 8984 §4.2.5, §4.2.6)
+
+/// The name of a location, virtual location, or participant (RFC 8984 §4.2.5).
+pub const NAME: &str = "name";
+/// The types of a location (RFC 8984 §4.2.5).
+pub const LOCATION_TYPES: &str = "locationTypes";
+/// The relation of a location to an object, or of a trigger to a time (RFC 8984 §4.2.5).
+pub const RELATIVE_TO: &str = "relativeTo";
+/// The geographic coordinates of a location (RFC 8984 §4.2.5).
+pub const COORDINATES: &str = "coordinates";
+/// The URI of a virtual location (RFC 8984 §4.2.6).
+pub const URI: &str = "uri";
+/// The features of a virtual location (RFC 8984 §4.2.6).
+pub const FEATURES: &str = "features";
+
+// Recurrence rule properties (RFC 8984 §4.3.3)
+
+/// The frequency of a recurrence rule (RFC 8984 §4.3.3).
+pub const FREQUENCY: &str = "frequency";
+/// The interval of a recurrence rule (RFC 8984 §4.3.3).
+pub const INTERVAL: &str = "interval";
+/// The calendar system of a recurrence rule (RFC 8984 §4.3.3).
+pub const RSCALE: &str = "rscale";
+/// The handling of invalid dates in a recurrence rule (RFC 8984 §4.3.3).
+pub const SKIP: &str = "skip";
+/// The first day of the week of a recurrence rule (RFC 8984 §4.3.3).
+pub const FIRST_DAY_OF_WEEK: &str = "firstDayOfWeek";
+/// The days of the week of a recurrence rule (RFC 8984 §4.3.3).
+pub const BY_DAY: &str = "byDay";
+/// The days of the month of a recurrence rule (RFC 8984 §4.3.3).
+pub const BY_MONTH_DAY: &str = "byMonthDay";
+/// The months of a recurrence rule (RFC 8984 §4.3.3).
+pub const BY_MONTH: &str = "byMonth";
+/// The days of the year of a recurrence rule (RFC 8984 §4.3.3).
+pub const BY_YEAR_DAY: &str = "byYearDay";
+/// The weeks of the year of a recurrence rule (RFC 8984 §4.3.3).
+pub const BY_WEEK_NO: &str = "byWeekNo";
+/// The hours of a recurrence rule (RFC 8984 §4.3.3).
+pub const BY_HOUR: &str = "byHour";
+/// The minutes of a recurrence rule (RFC 8984 §4.3.3).
+pub const BY_MINUTE: &str = "byMinute";
+/// The seconds of a recurrence rule (RFC 8984 §4.3.3).
+pub const BY_SECOND: &str = "bySecond";
+/// The occurrence positions of a recurrence rule (RFC 8984 §4.3.3).
+pub const BY_SET_POSITION: &str = "bySetPosition";
+/// The number of occurrences of a recurrence rule (RFC 8984 §4.3.3).
+pub const COUNT: &str = "count";
+/// The end of a recurrence rule (RFC 8984 §4.3.3).
+pub const UNTIL: &str = "until";
+/// The day of the week of an `NDay` (RFC 8984 §4.3.3).
+pub const DAY: &str = "day";
+/// The occurrence of the day within the period of an `NDay` (RFC 8984 §4.3.3).
+pub const NTH_OF_PERIOD: &str = "nthOfPeriod";
+
+// Reply and send-to methods (RFC 8984 §4.4.4)
+
+/// The iMIP address of an organizer or participant (RFC 8984 §4.4.4).
+pub const IMIP: &str = "imip";
+/// The web page for replying to an organizer (RFC 8984 §4.4.4).
+pub const WEB: &str = "web";
+
+// Participant properties (RFC 8984 §4.4.6)
+
+/// The email address of a participant (RFC 8984 §4.4.6).
+pub const EMAIL: &str = "email";
+/// The methods for contacting a participant (RFC 8984 §4.4.6).
+pub const SEND_TO: &str = "sendTo";
+/// The kind of a participant (RFC 8984 §4.4.6).
+pub const KIND: &str = "kind";
+/// The roles of a participant (RFC 8984 §4.4.6).
+pub const ROLES: &str = "roles";
+/// The location of a participant (RFC 8984 §4.4.6).
+pub const LOCATION_ID: &str = "locationId";
+/// The preferred language of a participant (RFC 8984 §4.4.6).
+pub const LANGUAGE: &str = "language";
+/// The participation status of a participant (RFC 8984 §4.4.6).
+pub const PARTICIPATION_STATUS: &str = "participationStatus";
+/// The comment of a participant on their status (RFC 8984 §4.4.6).
+pub const PARTICIPATION_COMMENT: &str = "participationComment";
+/// Whether a reply is expected from a participant (RFC 8984 §4.4.6).
+pub const EXPECT_REPLY: &str = "expectReply";
+/// The agent responsible for scheduling messages to a participant (RFC 8984 §4.4.6).
+pub const SCHEDULE_AGENT: &str = "scheduleAgent";
+/// Whether to send scheduling messages regardless of changes (RFC 8984 §4.4.6).
+pub const SCHEDULE_FORCE_SEND: &str = "scheduleForceSend";
+/// The last `sequence` sent to a participant (RFC 8984 §4.4.6).
+pub const SCHEDULE_SEQUENCE: &str = "scheduleSequence";
+/// The status of the last scheduling message to a participant (RFC 8984 §4.4.6).
+pub const SCHEDULE_STATUS: &str = "scheduleStatus";
+/// The time of the last reply of a participant (RFC 8984 §4.4.6).
+pub const SCHEDULE_UPDATED: &str = "scheduleUpdated";
+/// The participant who invited a participant (RFC 8984 §4.4.6).
+pub const INVITED_BY: &str = "invitedBy";
+/// The participants to whom a participant delegated (RFC 8984 §4.4.6).
+pub const DELEGATED_TO: &str = "delegatedTo";
+/// The participants who delegated to a participant (RFC 8984 §4.4.6).
+pub const DELEGATED_FROM: &str = "delegatedFrom";
+/// The groups of which a participant is a member (RFC 8984 §4.4.6).
+pub const MEMBER_OF: &str = "memberOf";
+
+// Alert properties (RFC 8984 §4.5.2)
+
+/// The trigger of an alert (RFC 8984 §4.5.2).
+pub const TRIGGER: &str = "trigger";
+/// The time an alert was last dismissed (RFC 8984 §4.5.2).
+pub const ACKNOWLEDGED: &str = "acknowledged";
+/// The action of an alert (RFC 8984 §4.5.2).
+pub const ACTION: &str = "action";
+/// The offset of a relative trigger (RFC 8984 §4.5.2).
+pub const OFFSET: &str = "offset";
+/// The time of an absolute trigger (RFC 8984 §4.5.2).
+pub const WHEN: &str = "when";
+
+// Time zone object properties (RFC 8984 §4.7.2)
+
+/// The TZID of a custom time zone (RFC 8984 §4.7.2).
+pub const TZ_ID: &str = "tzId";
+/// The location of an updated version of a time zone (RFC 8984 §4.7.2).
+pub const URL: &str = "url";
+/// The end of the validity of a time zone (RFC 8984 §4.7.2).
+pub const VALID_UNTIL: &str = "validUntil";
+/// The alternative names of a time zone (RFC 8984 §4.7.2).
+pub const ALIASES: &str = "aliases";
+/// The standard time rules of a time zone (RFC 8984 §4.7.2).
+pub const STANDARD: &str = "standard";
+/// The daylight saving time rules of a time zone (RFC 8984 §4.7.2).
+pub const DAYLIGHT: &str = "daylight";
+/// The UTC offset before a time zone rule applies (RFC 8984 §4.7.2).
+pub const OFFSET_FROM: &str = "offsetFrom";
+/// The UTC offset while a time zone rule applies (RFC 8984 §4.7.2).
+pub const OFFSET_TO: &str = "offsetTo";
+/// The names of a time zone rule (RFC 8984 §4.7.2).
+pub const NAMES: &str = "names";
+/// The comments of a time zone rule (RFC 8984 §4.7.2).
+pub const COMMENTS: &str = "comments";
+
+/// Every member name defined in this module.
+pub const ALL: &[&str] = &[
+    TYPE,
+    UID,
+    RELATED_TO,
+    PROD_ID,
+    CREATED,
+    UPDATED,
+    SEQUENCE,
+    METHOD,
+    TITLE,
+    DESCRIPTION,
+    DESCRIPTION_CONTENT_TYPE,
+    SHOW_WITHOUT_TIME,
+    LOCATIONS,
+    VIRTUAL_LOCATIONS,
+    LINKS,
+    LOCALE,
+    KEYWORDS,
+    CATEGORIES,
+    COLOR,
+    RECURRENCE_ID,
+    RECURRENCE_ID_TIME_ZONE,
+    RECURRENCE_RULES,
+    EXCLUDED_RECURRENCE_RULES,
+    RECURRENCE_OVERRIDES,
+    EXCLUDED,
+    PRIORITY,
+    FREE_BUSY_STATUS,
+    PRIVACY,
+    REPLY_TO,
+    SENT_BY,
+    PARTICIPANTS,
+    REQUEST_STATUS,
+    USE_DEFAULT_ALERTS,
+    ALERTS,
+    LOCALIZATIONS,
+    TIME_ZONE,
+    TIME_ZONES,
+    START,
+    DURATION,
+    STATUS,
+    DUE,
+    ESTIMATED_DURATION,
+    PERCENT_COMPLETE,
+    PROGRESS,
+    PROGRESS_UPDATED,
+    ENTRIES,
+    SOURCE,
+    RELATION,
+    HREF,
+    CONTENT_ID,
+    MEDIA_TYPE,
+    SIZE,
+    REL,
+    DISPLAY,
+    NAME,
+    LOCATION_TYPES,
+    RELATIVE_TO,
+    COORDINATES,
+    URI,
+    FEATURES,
+    FREQUENCY,
+    INTERVAL,
+    RSCALE,
+    SKIP,
+    FIRST_DAY_OF_WEEK,
+    BY_DAY,
+    BY_MONTH_DAY,
+    BY_MONTH,
+    BY_YEAR_DAY,
+    BY_WEEK_NO,
+    BY_HOUR,
+    BY_MINUTE,
+    BY_SECOND,
+    BY_SET_POSITION,
+    COUNT,
+    UNTIL,
+    DAY,
+    NTH_OF_PERIOD,
+    IMIP,
+    WEB,
+    EMAIL,
+    SEND_TO,
+    KIND,
+    ROLES,
+    LOCATION_ID,
+    LANGUAGE,
+    PARTICIPATION_STATUS,
+    PARTICIPATION_COMMENT,
+    EXPECT_REPLY,
+    SCHEDULE_AGENT,
+    SCHEDULE_FORCE_SEND,
+    SCHEDULE_SEQUENCE,
+    SCHEDULE_STATUS,
+    SCHEDULE_UPDATED,
+    INVITED_BY,
+    DELEGATED_TO,
+    DELEGATED_FROM,
+    MEMBER_OF,
+    TRIGGER,
+    ACKNOWLEDGED,
+    ACTION,
+    OFFSET,
+    WHEN,
+    TZ_ID,
+    URL,
+    VALID_UNTIL,
+    ALIASES,
+    STANDARD,
+    DAYLIGHT,
+    OFFSET_FROM,
+    OFFSET_TO,
+    NAMES,
+    COMMENTS,
+];
+
+// Reflection
+
+/// The group of properties in which RFC 8984 defines a [`Property`].
+///
+/// The properties common to events and tasks are grouped as in RFC 8984 §4, and those of the
+/// other kinds of object by the object which defines them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    /// Metadata properties (RFC 8984 §4.1).
+    Metadata,
+    /// What and where properties (RFC 8984 §4.2).
+    WhatAndWhere,
+    /// Recurrence properties (RFC 8984 §4.3).
+    Recurrence,
+    /// Sharing and scheduling properties (RFC 8984 §4.4).
+    SharingAndScheduling,
+    /// Alerts properties (RFC 8984 §4.5).
+    Alerts,
+    /// Multilingual properties (RFC 8984 §4.6).
+    Multilingual,
+    /// Time zone properties (RFC 8984 §4.7).
+    TimeZones,
+    /// Properties specific to events, tasks, or groups (RFC 8984 §5).
+    Entry,
+    /// Properties of a `Relation` (RFC 8984 §1.4.10).
+    Relation,
+    /// Properties of a `Link` (RFC 8984 §1.4.11).
+    Link,
+    /// Properties of a `Location` or `VirtualLocation` (RFC 8984 §4.2.5, §4.2.6).
+    Location,
+    /// Properties of a `RecurrenceRule` or `NDay` (RFC 8984 §4.3.3).
+    RecurrenceRule,
+    /// The methods of `replyTo` and `sendTo` (RFC 8984 §4.4.4).
+    ReplyTo,
+    /// Properties of a `Participant` (RFC 8984 §4.4.6).
+    Participant,
+    /// Properties of an `Alert` or its trigger (RFC 8984 §4.5.2).
+    Alert,
+    /// Properties of a `TimeZone` or `TimeZoneRule` (RFC 8984 §4.7.2).
+    TimeZone,
+}
+
+/// A kind of JSCalendar object, as named by its `@type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObjectType {
+    /// An `Event` (RFC 8984 §5.1).
+    Event,
+    /// A `Task` (RFC 8984 §5.2).
+    Task,
+    /// A `Group` (RFC 8984 §5.3).
+    Group,
+    /// A `Relation` (RFC 8984 §1.4.10).
+    Relation,
+    /// A `Link` (RFC 8984 §1.4.11).
+    Link,
+    /// A `Location` (RFC 8984 §4.2.5).
+    Location,
+    /// A `VirtualLocation` (RFC 8984 §4.2.6).
+    VirtualLocation,
+    /// A `RecurrenceRule` (RFC 8984 §4.3.3).
+    RecurrenceRule,
+    /// An `NDay` (RFC 8984 §4.3.3).
+    NDay,
+    /// A `Participant` (RFC 8984 §4.4.6).
+    Participant,
+    /// An `Alert` (RFC 8984 §4.5.2).
+    Alert,
+    /// An `OffsetTrigger` (RFC 8984 §4.5.2).
+    OffsetTrigger,
+    /// An `AbsoluteTrigger` (RFC 8984 §4.5.2).
+    AbsoluteTrigger,
+    /// A `TimeZone` (RFC 8984 §4.7.2).
+    TimeZone,
+    /// A `TimeZoneRule` (RFC 8984 §4.7.2).
+    TimeZoneRule,
+}
+
+impl ObjectType {
+    /// Every kind of object.
+    pub const ALL: &'static [ObjectType] = &[
+        Self::Event,
+        Self::Task,
+        Self::Group,
+        Self::Relation,
+        Self::Link,
+        Self::Location,
+        Self::VirtualLocation,
+        Self::RecurrenceRule,
+        Self::NDay,
+        Self::Participant,
+        Self::Alert,
+        Self::OffsetTrigger,
+        Self::AbsoluteTrigger,
+        Self::TimeZone,
+        Self::TimeZoneRule,
+    ];
+
+    /// Returns the `@type` of this kind of object.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Event => "Event",
+            Self::Task => "Task",
+            Self::Group => "Group",
+            Self::Relation => "Relation",
+            Self::Link => "Link",
+            Self::Location => "Location",
+            Self::VirtualLocation => "VirtualLocation",
+            Self::RecurrenceRule => "RecurrenceRule",
+            Self::NDay => "NDay",
+            Self::Participant => "Participant",
+            Self::Alert => "Alert",
+            Self::OffsetTrigger => "OffsetTrigger",
+            Self::AbsoluteTrigger => "AbsoluteTrigger",
+            Self::TimeZone => "TimeZone",
+            Self::TimeZoneRule => "TimeZoneRule",
+        }
+    }
+}
+
+impl fmt::Display for ObjectType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The type of the value of a [`Property`], as parsed by this crate.
+///
+/// These are the data types of RFC 8984 §1.4, refined where this crate parses a string with a
+/// particular syntax, such as a URI or a language tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyType {
+    /// A `Boolean`.
+    Boolean,
+    /// A `String` with no particular syntax.
+    String,
+    /// An `Int` (RFC 8984 §1.4.1).
+    Int,
+    /// An `UnsignedInt` (RFC 8984 §1.4.2).
+    UnsignedInt,
+    /// An `Id` (RFC 8984 §1.4.1).
+    Id,
+    /// A unique identifier, as in the `uid` of an object.
+    Uid,
+    /// A URI (RFC 3986).
+    Uri,
+    /// A `mailto:` URI naming a calendar user.
+    CalAddress,
+    /// An email address (RFC 5322).
+    EmailAddress,
+    /// A `geo:` URI (RFC 5870).
+    GeoUri,
+    /// A content ID (RFC 2392).
+    ContentId,
+    /// A media type (RFC 6838).
+    MediaType,
+    /// A language tag (RFC 5646).
+    LanguageTag,
+    /// A CSS color name or RGB value (RFC 8984 §4.2.11).
+    Color,
+    /// A time zone ID, either from the IANA Time Zone Database or of a custom time zone.
+    TimeZoneId,
+    /// A `UTCDateTime` (RFC 8984 §1.4.4).
+    UtcDateTime,
+    /// A `LocalDateTime` (RFC 8984 §1.4.5).
+    LocalDateTime,
+    /// A `Duration` (RFC 8984 §1.4.6).
+    Duration,
+    /// A `SignedDuration` (RFC 8984 §1.4.7).
+    SignedDuration,
+    /// A UTC offset, as in `+05:30`.
+    UtcOffset,
+    /// A request status, as in `2.0;Success` (RFC 5545 §3.8.8.3).
+    RequestStatus,
+    /// A status code, as in `2.0`.
+    StatusCode,
+    /// A token from a registry or enumeration, which may be open to unknown values.
+    Token,
+    /// An object of the given type.
+    Object(ObjectType),
+    /// An `Event` or a `Task`, as in the `entries` of a group.
+    Entry,
+    /// An `OffsetTrigger`, an `AbsoluteTrigger`, or an unknown trigger.
+    Trigger,
+    /// A `PatchObject` (RFC 8984 §1.4.9).
+    PatchObject,
+    /// An array of values of the given type.
+    Array(&'static PropertyType),
+    /// A set of values of the given type, represented as an object whose values are `true`.
+    Set(&'static PropertyType),
+    /// An object from keys of the first type to values of the second.
+    Map(&'static PropertyType, &'static PropertyType),
+}
+
+macro_rules! properties {
+    ($($variant:ident = $name:ident, $category:ident;)+) => {
+        /// A property name defined by RFC 8984, together with metadata describing it.
+        ///
+        /// [`Property::value_type`] describes the value of a property in a given kind of object,
+        /// which enables code to handle properties generically, for example to diff or patch
+        /// objects or to generate forms.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Property {
+            $(
+                #[doc = concat!("The [`", stringify!($name), "`] property.")]
+                $variant,
+            )+
+        }
+
+        impl Property {
+            /// Every property, in the order of [`ALL`](self::ALL).
+            pub const ALL: &'static [Property] = &[$(Self::$variant,)+];
+
+            /// Returns the name of this property.
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name,)+
+                }
+            }
+
+            /// Returns the group of properties in which this property is defined.
+            pub const fn category(self) -> Category {
+                match self {
+                    $(Self::$variant => Category::$category,)+
+                }
+            }
+        }
+    };
+}
+
+properties! {
+    Type = TYPE, Metadata;
+    Uid = UID, Metadata;
+    RelatedTo = RELATED_TO, Metadata;
+    ProdId = PROD_ID, Metadata;
+    Created = CREATED, Metadata;
+    Updated = UPDATED, Metadata;
+    Sequence = SEQUENCE, Metadata;
+    Method = METHOD, Metadata;
+    Title = TITLE, WhatAndWhere;
+    Description = DESCRIPTION, WhatAndWhere;
+    DescriptionContentType = DESCRIPTION_CONTENT_TYPE, WhatAndWhere;
+    ShowWithoutTime = SHOW_WITHOUT_TIME, WhatAndWhere;
+    Locations = LOCATIONS, WhatAndWhere;
+    VirtualLocations = VIRTUAL_LOCATIONS, WhatAndWhere;
+    Links = LINKS, WhatAndWhere;
+    Locale = LOCALE, WhatAndWhere;
+    Keywords = KEYWORDS, WhatAndWhere;
+    Categories = CATEGORIES, WhatAndWhere;
+    Color = COLOR, WhatAndWhere;
+    RecurrenceId = RECURRENCE_ID, Recurrence;
+    RecurrenceIdTimeZone = RECURRENCE_ID_TIME_ZONE, Recurrence;
+    RecurrenceRules = RECURRENCE_RULES, Recurrence;
+    ExcludedRecurrenceRules = EXCLUDED_RECURRENCE_RULES, Recurrence;
+    RecurrenceOverrides = RECURRENCE_OVERRIDES, Recurrence;
+    Excluded = EXCLUDED, Recurrence;
+    Priority = PRIORITY, SharingAndScheduling;
+    FreeBusyStatus = FREE_BUSY_STATUS, SharingAndScheduling;
+    Privacy = PRIVACY, SharingAndScheduling;
+    ReplyTo = REPLY_TO, SharingAndScheduling;
+    SentBy = SENT_BY, SharingAndScheduling;
+    Participants = PARTICIPANTS, SharingAndScheduling;
+    RequestStatus = REQUEST_STATUS, SharingAndScheduling;
+    UseDefaultAlerts = USE_DEFAULT_ALERTS, Alerts;
+    Alerts = ALERTS, Alerts;
+    Localizations = LOCALIZATIONS, Multilingual;
+    TimeZone = TIME_ZONE, TimeZones;
+    TimeZones = TIME_ZONES, TimeZones;
+    Start = START, Entry;
+    Duration = DURATION, Entry;
+    Status = STATUS, Entry;
+    Due = DUE, Entry;
+    EstimatedDuration = ESTIMATED_DURATION, Entry;
+    PercentComplete = PERCENT_COMPLETE, Entry;
+    Progress = PROGRESS, Entry;
+    ProgressUpdated = PROGRESS_UPDATED, Entry;
+    Entries = ENTRIES, Entry;
+    Source = SOURCE, Entry;
+    Relation = RELATION, Relation;
+    Href = HREF, Link;
+    ContentId = CONTENT_ID, Link;
+    MediaType = MEDIA_TYPE, Link;
+    Size = SIZE, Link;
+    Rel = REL, Link;
+    Display = DISPLAY, Link;
+    Name = NAME, Location;
+    LocationTypes = LOCATION_TYPES, Location;
+    RelativeTo = RELATIVE_TO, Location;
+    Coordinates = COORDINATES, Location;
+    Uri = URI, Location;
+    Features = FEATURES, Location;
+    Frequency = FREQUENCY, RecurrenceRule;
+    Interval = INTERVAL, RecurrenceRule;
+    Rscale = RSCALE, RecurrenceRule;
+    Skip = SKIP, RecurrenceRule;
+    FirstDayOfWeek = FIRST_DAY_OF_WEEK, RecurrenceRule;
+    ByDay = BY_DAY, RecurrenceRule;
+    ByMonthDay = BY_MONTH_DAY, RecurrenceRule;
+    ByMonth = BY_MONTH, RecurrenceRule;
+    ByYearDay = BY_YEAR_DAY, RecurrenceRule;
+    ByWeekNo = BY_WEEK_NO, RecurrenceRule;
+    ByHour = BY_HOUR, RecurrenceRule;
+    ByMinute = BY_MINUTE, RecurrenceRule;
+    BySecond = BY_SECOND, RecurrenceRule;
+    BySetPosition = BY_SET_POSITION, RecurrenceRule;
+    Count = COUNT, RecurrenceRule;
+    Until = UNTIL, RecurrenceRule;
+    Day = DAY, RecurrenceRule;
+    NthOfPeriod = NTH_OF_PERIOD, RecurrenceRule;
+    Imip = IMIP, ReplyTo;
+    Web = WEB, ReplyTo;
+    Email = EMAIL, Participant;
+    SendTo = SEND_TO, Participant;
+    Kind = KIND, Participant;
+    Roles = ROLES, Participant;
+    LocationId = LOCATION_ID, Participant;
+    Language = LANGUAGE, Participant;
+    ParticipationStatus = PARTICIPATION_STATUS, Participant;
+    ParticipationComment = PARTICIPATION_COMMENT, Participant;
+    ExpectReply = EXPECT_REPLY, Participant;
+    ScheduleAgent = SCHEDULE_AGENT, Participant;
+    ScheduleForceSend = SCHEDULE_FORCE_SEND, Participant;
+    ScheduleSequence = SCHEDULE_SEQUENCE, Participant;
+    ScheduleStatus = SCHEDULE_STATUS, Participant;
+    ScheduleUpdated = SCHEDULE_UPDATED, Participant;
+    InvitedBy = INVITED_BY, Participant;
+    DelegatedTo = DELEGATED_TO, Participant;
+    DelegatedFrom = DELEGATED_FROM, Participant;
+    MemberOf = MEMBER_OF, Participant;
+    Trigger = TRIGGER, Alert;
+    Acknowledged = ACKNOWLEDGED, Alert;
+    Action = ACTION, Alert;
+    Offset = OFFSET, Alert;
+    When = WHEN, Alert;
+    TzId = TZ_ID, TimeZone;
+    Url = URL, TimeZone;
+    ValidUntil = VALID_UNTIL, TimeZone;
+    Aliases = ALIASES, TimeZone;
+    Standard = STANDARD, TimeZone;
+    Daylight = DAYLIGHT, TimeZone;
+    OffsetFrom = OFFSET_FROM, TimeZone;
+    OffsetTo = OFFSET_TO, TimeZone;
+    Names = NAMES, TimeZone;
+    Comments = COMMENTS, TimeZone;
+}
+
+impl Property {
+    /// Returns the property named `name`, or `None` if RFC 8984 defines no such property.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|property| property.as_str() == name)
+    }
+
+    /// Returns the type of the value of this property in an object of the type `object`, or
+    /// `None` if that type of object does not have this property.
+    ///
+    /// A property may have different types in different objects; for example, the `sentBy` of
+    /// an event is a [`CalAddress`](PropertyType::CalAddress), but that of a participant is an
+    /// [`EmailAddress`](PropertyType::EmailAddress). The `imip` and `web` methods are keys of
+    /// `replyTo` and `sendTo` rather than properties of an object, so they apply to no type.
+    pub fn value_type(self, object: ObjectType) -> Option<PropertyType> {
+        use ObjectType as O;
+        use PropertyType as T;
+
+        let entry = matches!(object, O::Event | O::Task);
+        let top_level = entry || object == O::Group;
+        let rule = object == O::RecurrenceRule;
+        let participant = object == O::Participant;
+        let time_zone = object == O::TimeZone;
+
+        Some(match self {
+            Self::Type => T::String,
+
+            Self::Uid if top_level => T::Uid,
+            Self::RelatedTo if entry => T::Map(&T::Uid, &T::Object(O::Relation)),
+            Self::RelatedTo if object == O::Alert => T::Map(&T::String, &T::Object(O::Relation)),
+            Self::ProdId if top_level => T::String,
+            Self::Created if top_level => T::UtcDateTime,
+            Self::Updated if top_level || time_zone => T::UtcDateTime,
+            Self::Sequence if entry => T::UnsignedInt,
+            Self::Method if entry => T::Token,
+
+            Self::Title if top_level || object == O::Link => T::String,
+            Self::Description
+                if top_level
+                    || participant
+                    || matches!(object, O::Location | O::VirtualLocation) =>
+            {
+                T::String
+            }
+            Self::DescriptionContentType if top_level => T::String,
+            Self::ShowWithoutTime if entry => T::Boolean,
+            Self::Locations if entry => T::Map(&T::Id, &T::Object(O::Location)),
+            Self::VirtualLocations if entry => T::Map(&T::Id, &T::Object(O::VirtualLocation)),
+            Self::Links if top_level || participant || object == O::Location => {
+                T::Map(&T::Id, &T::Object(O::Link))
+            }
+            Self::Locale if top_level => T::LanguageTag,
+            Self::Keywords | Self::Categories if top_level => T::Set(&T::String),
+            Self::Color if top_level => T::Color,
+
+            Self::RecurrenceId if entry => T::LocalDateTime,
+            Self::RecurrenceIdTimeZone if entry => T::TimeZoneId,
+            Self::RecurrenceRules if entry || object == O::TimeZoneRule => {
+                T::Array(&T::Object(O::RecurrenceRule))
+            }
+            Self::ExcludedRecurrenceRules if entry => T::Array(&T::Object(O::RecurrenceRule)),
+            Self::RecurrenceOverrides if entry || object == O::TimeZoneRule => {
+                T::Map(&T::LocalDateTime, &T::PatchObject)
+            }
+            Self::Excluded if entry => T::Boolean,
+
+            Self::Priority if entry => T::Int,
+            Self::FreeBusyStatus | Self::Privacy if entry => T::Token,
+            Self::ReplyTo if entry => T::Map(&T::Token, &T::Uri),
+            Self::SentBy if entry => T::CalAddress,
+            Self::SentBy if participant => T::EmailAddress,
+            Self::Participants if entry => T::Map(&T::Id, &T::Object(O::Participant)),
+            Self::RequestStatus if entry => T::RequestStatus,
+
+            Self::UseDefaultAlerts if entry => T::Boolean,
+            Self::Alerts if entry => T::Map(&T::Id, &T::Object(O::Alert)),
+
+            Self::Localizations if entry => T::Map(&T::LanguageTag, &T::PatchObject),
+
+            Self::TimeZone if entry || object == O::Location => T::TimeZoneId,
+            Self::TimeZones if top_level => T::Map(&T::TimeZoneId, &T::Object(O::TimeZone)),
+
+            Self::Start if entry || object == O::TimeZoneRule => T::LocalDateTime,
+            Self::Duration if object == O::Event => T::Duration,
+            Self::Status if object == O::Event => T::Token,
+            Self::Due if object == O::Task => T::LocalDateTime,
+            Self::EstimatedDuration if object == O::Task => T::Duration,
+            Self::PercentComplete if object == O::Task || participant => T::UnsignedInt,
+            Self::Progress if object == O::Task || participant => T::Token,
+            Self::ProgressUpdated if object == O::Task || participant => T::UtcDateTime,
+            Self::Entries if object == O::Group => T::Array(&T::Entry),
+            Self::Source if object == O::Group => T::Uri,
+
+            Self::Relation if object == O::Relation => T::Set(&T::Token),
+
+            Self::Href if object == O::Link => T::Uri,
+            Self::ContentId if object == O::Link => T::ContentId,
+            Self::MediaType if object == O::Link => T::MediaType,
+            Self::Size if object == O::Link => T::UnsignedInt,
+            Self::Rel | Self::Display if object == O::Link => T::Token,
+
+            Self::Name if participant || matches!(object, O::Location | O::VirtualLocation) => {
+                T::String
+            }
+            Self::LocationTypes if object == O::Location => T::Set(&T::Token),
+            Self::RelativeTo if matches!(object, O::Location | O::OffsetTrigger) => T::Token,
+            Self::Coordinates if object == O::Location => T::GeoUri,
+            Self::Uri if object == O::VirtualLocation => T::Uri,
+            Self::Features if object == O::VirtualLocation => T::Set(&T::Token),
+
+            Self::Frequency | Self::Rscale | Self::Skip | Self::FirstDayOfWeek if rule => T::Token,
+            Self::Interval | Self::Count if rule => T::UnsignedInt,
+            Self::ByDay if rule => T::Array(&T::Object(O::NDay)),
+            Self::ByMonthDay | Self::ByYearDay | Self::ByWeekNo | Self::BySetPosition if rule => {
+                T::Array(&T::Int)
+            }
+            Self::ByMonth | Self::ByHour | Self::ByMinute | Self::BySecond if rule => {
+                T::Array(&T::UnsignedInt)
+            }
+            Self::Until if rule => T::LocalDateTime,
+            Self::Day if object == O::NDay => T::Token,
+            Self::NthOfPeriod if object == O::NDay => T::Int,
+
+            Self::Email if participant => T::EmailAddress,
+            Self::SendTo if participant => T::Map(&T::Token, &T::Uri),
+            Self::Kind | Self::ParticipationStatus | Self::ScheduleAgent if participant => T::Token,
+            Self::Roles if participant => T::Set(&T::Token),
+            Self::LocationId | Self::InvitedBy if participant => T::Id,
+            Self::Language if participant => T::LanguageTag,
+            Self::ParticipationComment if participant => T::String,
+            Self::ExpectReply | Self::ScheduleForceSend if participant => T::Boolean,
+            Self::ScheduleSequence if participant => T::UnsignedInt,
+            Self::ScheduleStatus if participant => T::Array(&T::StatusCode),
+            Self::ScheduleUpdated if participant => T::UtcDateTime,
+            Self::DelegatedTo | Self::DelegatedFrom | Self::MemberOf if participant => {
+                T::Set(&T::Id)
+            }
+
+            Self::Trigger if object == O::Alert => T::Trigger,
+            Self::Acknowledged if object == O::Alert => T::UtcDateTime,
+            Self::Action if object == O::Alert => T::Token,
+            Self::Offset if object == O::OffsetTrigger => T::SignedDuration,
+            Self::When if object == O::AbsoluteTrigger => T::UtcDateTime,
+
+            Self::TzId if time_zone => T::String,
+            Self::Url if time_zone => T::Uri,
+            Self::ValidUntil if time_zone => T::UtcDateTime,
+            Self::Aliases if time_zone => T::Set(&T::String),
+            Self::Standard | Self::Daylight if time_zone => T::Array(&T::Object(O::TimeZoneRule)),
+            Self::OffsetFrom | Self::OffsetTo if object == O::TimeZoneRule => T::UtcOffset,
+            Self::Names if object == O::TimeZoneRule => T::Set(&T::String),
+            Self::Comments if object == O::TimeZoneRule => T::Array(&T::String),
+
+            _ => return None,
+        })
+    }
+
+    /// Returns whether an object of the type `object` has this property.
+    pub fn applies_to(self, object: ObjectType) -> bool {
+        self.value_type(object).is_some()
+    }
+
+    /// Returns the types of object which have this property.
+    pub fn object_types(self) -> impl Iterator<Item = ObjectType> {
+        ObjectType::ALL
+            .iter()
+            .copied()
+            .filter(move |&object| self.applies_to(object))
+    }
+}
+
+impl fmt::Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn names_are_unique() {
+        let names: HashSet<_> = ALL.iter().collect();
+        assert_eq!(names.len(), ALL.len());
+    }
+
+    #[test]
+    fn properties() {
+        let names: Vec<_> = Property::ALL.iter().map(|p| p.as_str()).collect();
+        assert_eq!(names, ALL);
+        assert_eq!(Property::from_name("sentBy"), Some(Property::SentBy));
+        assert_eq!(Property::from_name("x-sentBy"), None);
+        assert_eq!(Property::ByDay.category(), Category::RecurrenceRule);
+
+        assert_eq!(
+            Property::SentBy.value_type(ObjectType::Event),
+            Some(PropertyType::CalAddress)
+        );
+        assert_eq!(
+            Property::SentBy.value_type(ObjectType::Participant),
+            Some(PropertyType::EmailAddress)
+        );
+        assert_eq!(
+            Property::Locations.value_type(ObjectType::Task),
+            Some(PropertyType::Map(
+                &PropertyType::Id,
+                &PropertyType::Object(ObjectType::Location)
+            ))
+        );
+        assert_eq!(Property::Due.value_type(ObjectType::Event), None);
+
+        assert!(
+            Property::Type
+                .object_types()
+                .eq(ObjectType::ALL.iter().copied())
+        );
+        assert!(Property::Duration.object_types().eq([ObjectType::Event]));
+        for property in Property::ALL {
+            let applies = property.object_types().next().is_some();
+            assert_eq!(
+                applies,
+                property.category() != Category::ReplyTo,
+                "{property}"
+            );
+        }
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn serialized_names_are_defined() {
+        use serde_json::{Value, json};
+
+        use crate::{
+            json::{IntoJson, TryFromJson},
+            model::object::Event,
+        };
+
+        let input = json!({
+            "@type": "Event",
+            "uid": "props",
+            "start": "2024-01-15T13:00:00",
+            "duration": "PT1H",
+            "title": "Planning",
+            "sequence": 1,
+            "freeBusyStatus": "free",
+            "showWithoutTime": false,
+            "recurrenceRules": [{ "@type": "RecurrenceRule", "frequency": "weekly", "count": 4 }],
+            "participants": {
+                "p1": {
+                    "@type": "Participant",
+                    "roles": { "owner": true },
+                    "participationStatus": "accepted",
+                    "expectReply": false
+                }
+            },
+            "alerts": {
+                "a1": {
+                    "@type": "Alert",
+                    "trigger": { "@type": "OffsetTrigger", "offset": "-PT15M" }
+                }
+            }
+        });
+        let output: Value = Event::<Value>::try_from_json(input).unwrap().into_json();
+
+        let participant = &output[PARTICIPANTS]["p1"];
+        let alert = &output[ALERTS]["a1"];
+        let objects = [
+            &output,
+            &output[RECURRENCE_RULES][0],
+            participant,
+            alert,
+            &alert[TRIGGER],
+        ];
+        for object in objects {
+            for key in object.as_object().unwrap().keys() {
+                assert!(ALL.contains(&key.as_str()), "{key}");
+            }
+        }
+    }
+}
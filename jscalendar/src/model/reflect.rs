@@ -0,0 +1,305 @@
+//! Access to the properties of an event by [`Property`].
+//!
+//! The accessors generated for [`Event`] are specific to each property, so code which handles
+//! properties generically, such as to diff or patch events or to build a form from the metadata
+//! of [`Property`], would otherwise need a `match` over every property. [`Event::get_property`],
+//! [`Event::set_property`], and [`Event::remove_property`] instead exchange the value of any
+//! property as JSON, converting it from or into the type of the field which holds it.
+//!
+//! ```
+//! # #[cfg(feature = "serde_json")]
+//! # {
+//! use jscalendar::{
+//!     json::TryFromJson,
+//!     model::{object::Event, props::Property},
+//! };
+//! use serde_json::{Value, json};
+//!
+//! let old = Event::<Value>::try_from_json(json!({
+//!     "uid": "a8df6573-0474-496d-8496-033ad45d7fea",
+//!     "start": "2020-01-15T13:00:00",
+//!     "title": "Team meeting"
+//! }))
+//! .unwrap();
+//! let mut new = old.clone();
+//! new.set_property(Property::Title, json!("Planning")).unwrap();
+//! new.set_property(Property::Duration, json!("PT1H")).unwrap();
+//!
+//! let changed: Vec<_> = Property::ALL
+//!     .iter()
+//!     .filter(|&&property| old.get_property(property) != new.get_property(property))
+//!     .map(|property| property.as_str())
+//!     .collect();
+//! assert_eq!(changed, ["title", "duration"]);
+//! # }
+//! ```
+
+use thiserror::Error;
+
+use crate::{
+    json::{
+        ConstructibleJsonValue, DestructibleJsonValue, IntoJson, JsonObject, TryFromJson,
+        error::ObjectError,
+    },
+    model::{
+        object::Event,
+        props::{self, ObjectType, Property},
+    },
+};
+
+/// The error returned when changing a property through [`Event::set_property`] or
+/// [`Event::remove_property`] fails.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum SetPropertyError {
+    /// The object does not have the property.
+    #[error("an event has no property {0}")]
+    NotApplicable(Property),
+    /// The property cannot be changed, as is the case for the `@type` of an object.
+    #[error("the property {0} cannot be changed")]
+    ReadOnly(Property),
+    /// The property is required, and so cannot be removed.
+    #[error("the property {0} is required")]
+    Required(Property),
+    /// The value is not valid for the property.
+    #[error(transparent)]
+    InvalidValue(#[from] ObjectError),
+}
+
+/// Defines the reflection accessors of [`Event`] for its optional properties, each given with
+/// the accessors which structible generates for its field.
+macro_rules! event_properties {
+    ($($property:ident: $get:ident, $set:ident, $remove:ident;)+) => {
+        impl<V> Event<V>
+        where
+            V: ConstructibleJsonValue + Clone,
+            V::Object: Clone,
+        {
+            /// Returns the value of `property` as JSON, or `None` if it is absent or the property
+            /// is not one which an event has.
+            pub fn get_property(&self, property: Property) -> Option<V> {
+                match property {
+                    Property::Type => Some(V::str(ObjectType::Event.as_str())),
+                    Property::Uid => Some(self.uid().clone().into_json()),
+                    Property::Start => Some((*self.start()).into_json()),
+                    $(Property::$property => self.$get().cloned().map(IntoJson::into_json),)+
+                    _ => None,
+                }
+            }
+        }
+
+        impl<V: DestructibleJsonValue + ConstructibleJsonValue> Event<V> {
+            /// Sets `property` to `value`, which is parsed as it would be in the JSON of an event.
+            ///
+            /// The event is left unchanged if this fails, which it does if the event does not have
+            /// the property, if the property is `@type`, or if `value` is invalid. The errors of
+            /// an invalid value have the same paths as if the event were parsed as a whole.
+            pub fn set_property(
+                &mut self,
+                property: Property,
+                value: V,
+            ) -> Result<(), SetPropertyError> {
+                match property {
+                    Property::Type => return Err(SetPropertyError::ReadOnly(property)),
+                    _ if !property.applies_to(ObjectType::Event) => {
+                        return Err(SetPropertyError::NotApplicable(property));
+                    }
+                    _ => {}
+                }
+
+                let mut object = V::Object::with_capacity(3);
+                object.insert(props::UID.into(), self.uid().clone().into_json());
+                object.insert(props::START.into(), (*self.start()).into_json());
+                object.insert(property.as_str().into(), value);
+                let mut parsed = Event::<V>::try_from_json(V::object(object))?;
+
+                match property {
+                    Property::Uid => self.set_uid(parsed.uid().clone()),
+                    Property::Start => self.set_start(*parsed.start()),
+                    $(
+                        Property::$property => {
+                            if let Some(value) = parsed.$remove() {
+                                self.$set(value);
+                            }
+                        }
+                    )+
+                    _ => unreachable!("{property} is not a property of an event"),
+                }
+                Ok(())
+            }
+
+            /// Removes `property`, returning its value as JSON, or `None` if it was absent.
+            ///
+            /// This fails if the event does not have the property, or if the property is
+            /// required.
+            pub fn remove_property(
+                &mut self,
+                property: Property,
+            ) -> Result<Option<V>, SetPropertyError> {
+                match property {
+                    Property::Type | Property::Uid | Property::Start => {
+                        Err(SetPropertyError::Required(property))
+                    }
+                    $(Property::$property => Ok(self.$remove().map(IntoJson::into_json)),)+
+                    _ => Err(SetPropertyError::NotApplicable(property)),
+                }
+            }
+        }
+    };
+}
+
+event_properties! {
+    Duration: duration, set_duration, remove_duration;
+    Status: status, set_status, remove_status;
+    RelatedTo: related_to, set_related_to, remove_related_to;
+    ProdId: prod_id, set_prod_id, remove_prod_id;
+    Created: created, set_created, remove_created;
+    Updated: updated, set_updated, remove_updated;
+    Sequence: sequence, set_sequence, remove_sequence;
+    Method: method, set_method, remove_method;
+    Title: title, set_title, remove_title;
+    Description: description, set_description, remove_description;
+    DescriptionContentType:
+        description_content_type, set_description_content_type, remove_description_content_type;
+    ShowWithoutTime: show_without_time, set_show_without_time, remove_show_without_time;
+    Locations: locations, set_locations, remove_locations;
+    VirtualLocations: virtual_locations, set_virtual_locations, remove_virtual_locations;
+    Links: links, set_links, remove_links;
+    Locale: locale, set_locale, remove_locale;
+    Keywords: keywords, set_keywords, remove_keywords;
+    Categories: categories, set_categories, remove_categories;
+    Color: color, set_color, remove_color;
+    RecurrenceId: recurrence_id, set_recurrence_id, remove_recurrence_id;
+    RecurrenceIdTimeZone:
+        recurrence_id_time_zone, set_recurrence_id_time_zone, remove_recurrence_id_time_zone;
+    RecurrenceRules: recurrence_rules, set_recurrence_rules, remove_recurrence_rules;
+    ExcludedRecurrenceRules:
+        excluded_recurrence_rules, set_excluded_recurrence_rules, remove_excluded_recurrence_rules;
+    RecurrenceOverrides: recurrence_overrides, set_recurrence_overrides, remove_recurrence_overrides;
+    Excluded: excluded, set_excluded, remove_excluded;
+    Priority: priority, set_priority, remove_priority;
+    FreeBusyStatus: free_busy_status, set_free_busy_status, remove_free_busy_status;
+    Privacy: privacy, set_privacy, remove_privacy;
+    ReplyTo: reply_to, set_reply_to, remove_reply_to;
+    SentBy: sent_by, set_sent_by, remove_sent_by;
+    Participants: participants, set_participants, remove_participants;
+    RequestStatus: request_status, set_request_status, remove_request_status;
+    UseDefaultAlerts: use_default_alerts, set_use_default_alerts, remove_use_default_alerts;
+    Alerts: alerts, set_alerts, remove_alerts;
+    Localizations: localizations, set_localizations, remove_localizations;
+    TimeZone: time_zone, set_time_zone, remove_time_zone;
+    TimeZones: time_zones, set_time_zones, remove_time_zones;
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::json::PathSegment;
+
+    fn event() -> Event<Value> {
+        Event::try_from_json(json!({
+            "@type": "Event",
+            "uid": "reflect",
+            "start": "2024-01-15T13:00:00",
+            "title": "Planning",
+            "keywords": { "work": true },
+            "participants": {
+                "p1": { "@type": "Participant", "roles": { "owner": true } }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn get_property() {
+        let event = event();
+        assert_eq!(event.get_property(Property::Type), Some(json!("Event")));
+        assert_eq!(event.get_property(Property::Uid), Some(json!("reflect")));
+        assert_eq!(
+            event.get_property(Property::Start),
+            Some(json!("2024-01-15T13:00:00"))
+        );
+        assert_eq!(event.get_property(Property::Title), Some(json!("Planning")));
+        assert_eq!(
+            event.get_property(Property::Keywords),
+            Some(json!({ "work": true }))
+        );
+        assert_eq!(event.get_property(Property::Duration), None);
+        assert_eq!(event.get_property(Property::Due), None);
+
+        // every property of the serialized event is found, with the same value
+        let json: Value = event.clone().into_json();
+        for (name, value) in json.as_object().unwrap() {
+            let property = Property::from_name(name).unwrap();
+            assert_eq!(event.get_property(property).as_ref(), Some(value), "{name}");
+        }
+    }
+
+    #[test]
+    fn set_property() {
+        let mut event = event();
+        event
+            .set_property(Property::Duration, json!("PT1H30M"))
+            .unwrap();
+        assert_eq!(event.duration().unwrap().to_string(), "PT1H30M");
+        event
+            .set_property(Property::Start, json!("2024-01-16T09:00:00"))
+            .unwrap();
+        assert_eq!(event.start().to_string(), "2024-01-16T09:00:00");
+        assert_eq!(event.title().map(String::as_str), Some("Planning"));
+
+        let err = event
+            .set_property(Property::Locations, json!({ "l1": { "coordinates": 5 } }))
+            .unwrap_err();
+        let SetPropertyError::InvalidValue(err) = err else {
+            panic!("{err:?}");
+        };
+        assert_eq!(
+            err.path(),
+            &[
+                PathSegment::Static("locations"),
+                PathSegment::String("l1".into()),
+                PathSegment::Static("coordinates"),
+            ]
+        );
+        assert!(event.locations().is_none());
+
+        assert_eq!(
+            event.set_property(Property::Due, json!("2024-01-16T09:00:00")),
+            Err(SetPropertyError::NotApplicable(Property::Due))
+        );
+        assert_eq!(
+            event.set_property(Property::Type, json!("Task")),
+            Err(SetPropertyError::ReadOnly(Property::Type))
+        );
+    }
+
+    #[test]
+    fn remove_property() {
+        let mut event = event();
+        assert_eq!(
+            event.remove_property(Property::Title),
+            Ok(Some(json!("Planning")))
+        );
+        assert_eq!(event.remove_property(Property::Title), Ok(None));
+        assert!(event.title().is_none());
+        assert_eq!(
+            event.remove_property(Property::Start),
+            Err(SetPropertyError::Required(Property::Start))
+        );
+        assert_eq!(
+            event.remove_property(Property::Href),
+            Err(SetPropertyError::NotApplicable(Property::Href))
+        );
+
+        // the properties which can be removed are those of an event, except the required ones
+        for &property in Property::ALL {
+            let applies = property.applies_to(ObjectType::Event);
+            match event.remove_property(property) {
+                Err(SetPropertyError::NotApplicable(_)) => assert!(!applies, "{property}"),
+                _ => assert!(applies, "{property}"),
+            }
+        }
+    }
+}
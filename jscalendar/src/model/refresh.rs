@@ -0,0 +1,195 @@
+//! Refresh metadata for groups which mirror a remote feed.
+//!
+//! The `source` property of a [`Group`] is the URI from which its contents can be refreshed
+//! (RFC 8984 §5.3.2), but JSCalendar has no properties describing when or how to refresh it. A
+//! subscription manager needs the interval suggested by the feed and the validators captured at
+//! the last fetch, so that it can poll on schedule and make conditional requests. These are
+//! collected in [`RefreshHints`], and stored on the group as the vendor properties named by the
+//! constants in this module, so that they survive serialization alongside the group itself.
+
+use crate::{
+    json::{ConstructibleJsonValue, DestructibleJsonValue, IntoJson, TryFromJson},
+    model::{
+        object::Group,
+        time::{DateTime, Duration, NominalDuration, Utc},
+    },
+};
+
+/// The vendor property holding [`RefreshHints::interval`].
+pub const REFRESH_INTERVAL: &str = "eikopf.github.io:refreshInterval";
+/// The vendor property holding [`RefreshHints::etag`].
+pub const ETAG: &str = "eikopf.github.io:etag";
+/// The vendor property holding [`RefreshHints::last_modified`].
+pub const LAST_MODIFIED: &str = "eikopf.github.io:lastModified";
+/// The vendor property holding [`RefreshHints::fetched`].
+pub const FETCHED: &str = "eikopf.github.io:fetched";
+
+/// The refresh interval used by [`Group::needs_refresh`] if the feed does not suggest one.
+pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::Nominal(NominalDuration {
+    weeks: 0,
+    days: 1,
+    exact: None,
+});
+
+/// The refresh metadata of a group whose `source` is a remote feed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RefreshHints {
+    /// The interval between refreshes suggested by the feed, such as its `REFRESH-INTERVAL`
+    /// (RFC 7986 §5.7) or HTTP caching headers.
+    pub interval: Option<Duration>,
+    /// The HTTP `ETag` of the feed at the last fetch.
+    pub etag: Option<String>,
+    /// The HTTP `Last-Modified` time of the feed at the last fetch.
+    pub last_modified: Option<DateTime<Utc>>,
+    /// The time of the last successful fetch.
+    pub fetched: Option<DateTime<Utc>>,
+}
+
+impl RefreshHints {
+    /// Returns the time at which the group should next be refreshed, or `None` if it has never
+    /// been fetched. The interval defaults to [`DEFAULT_REFRESH_INTERVAL`].
+    pub fn next_refresh(&self) -> Option<DateTime<Utc>> {
+        let interval = self.interval.unwrap_or(DEFAULT_REFRESH_INTERVAL);
+        self.fetched?.checked_add(interval)
+    }
+}
+
+impl<V> Group<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    /// Returns the refresh metadata stored on this group. Values of the wrong type are ignored.
+    pub fn refresh_hints(&self) -> RefreshHints {
+        let string = |name| {
+            let value = self.vendor_property(name)?.clone();
+            String::try_from_json(value).ok()
+        };
+        let date_time = |name| {
+            let value = self.vendor_property(name)?.clone();
+            DateTime::try_from_json(value).ok()
+        };
+
+        RefreshHints {
+            interval: self
+                .vendor_property(REFRESH_INTERVAL)
+                .and_then(|value| Duration::try_from_json(value.clone()).ok()),
+            etag: string(ETAG),
+            last_modified: date_time(LAST_MODIFIED),
+            fetched: date_time(FETCHED),
+        }
+    }
+
+    /// Stores `hints` on this group, removing the properties of absent values.
+    pub fn set_refresh_hints(&mut self, hints: RefreshHints) {
+        self.set_refresh_property(REFRESH_INTERVAL, hints.interval);
+        self.set_refresh_property(ETAG, hints.etag);
+        self.set_refresh_property(LAST_MODIFIED, hints.last_modified);
+        self.set_refresh_property(FETCHED, hints.fetched);
+    }
+
+    /// Records a successful fetch of the source feed at `fetched`, replacing the validators of
+    /// the previous fetch and keeping the refresh interval.
+    pub fn record_fetch(
+        &mut self,
+        fetched: DateTime<Utc>,
+        etag: Option<String>,
+        last_modified: Option<DateTime<Utc>>,
+    ) {
+        let hints = RefreshHints {
+            etag,
+            last_modified,
+            fetched: Some(fetched),
+            ..self.refresh_hints()
+        };
+        self.set_refresh_hints(hints);
+    }
+
+    /// Returns `true` iff this group has a `source` and should be refreshed at `now`, because it
+    /// has never been fetched or its refresh interval has elapsed since the last fetch.
+    pub fn needs_refresh(&self, now: DateTime<Utc>) -> bool {
+        if self.source().is_none() {
+            return false;
+        }
+
+        let hints = self.refresh_hints();
+        match hints.fetched {
+            None => true,
+            Some(_) => hints.next_refresh().is_none_or(|next| next <= now),
+        }
+    }
+
+    fn set_refresh_property<T: IntoJson<V>>(&mut self, name: &str, value: Option<T>) {
+        let name = Box::<str>::from(name);
+        match value {
+            Some(value) => self.insert_vendor_property(name, value.into_json()),
+            None => self.remove_vendor_property(&name),
+        };
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    fn group() -> Group<Value> {
+        Group::try_from_json(json!({
+            "@type": "Group",
+            "uid": "feed",
+            "source": "https://example.com/holidays.json",
+            "entries": []
+        }))
+        .unwrap()
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::try_from_json(Value::from(s)).unwrap()
+    }
+
+    #[test]
+    fn hints_round_trip_through_vendor_properties() {
+        let mut group = group();
+        assert_eq!(group.refresh_hints(), RefreshHints::default());
+
+        let hints = RefreshHints {
+            interval: Some(Duration::try_from_json(Value::from("PT6H")).unwrap()),
+            etag: Some(String::from("\"abc123\"")),
+            last_modified: Some(utc("2024-01-01T00:00:00Z")),
+            fetched: Some(utc("2024-01-02T12:00:00Z")),
+        };
+        group.set_refresh_hints(hints.clone());
+
+        let json: Value = group.clone().into_json();
+        assert_eq!(json[REFRESH_INTERVAL], "PT6H");
+        assert_eq!(json[FETCHED], "2024-01-02T12:00:00Z");
+
+        let group = Group::try_from_json(json).unwrap();
+        assert_eq!(group.refresh_hints(), hints);
+    }
+
+    #[test]
+    fn needs_refresh() {
+        let mut group = group();
+        assert!(group.needs_refresh(utc("2024-01-01T00:00:00Z")));
+
+        group.record_fetch(utc("2024-01-01T00:00:00Z"), None, None);
+        assert!(!group.needs_refresh(utc("2024-01-01T23:59:59Z")));
+        assert!(group.needs_refresh(utc("2024-01-02T00:00:00Z")));
+
+        let mut hints = group.refresh_hints();
+        hints.interval = Some(Duration::try_from_json(Value::from("PT1H")).unwrap());
+        group.set_refresh_hints(hints);
+        group.record_fetch(
+            utc("2024-01-02T00:00:00Z"),
+            Some(String::from("\"v2\"")),
+            None,
+        );
+        assert_eq!(group.refresh_hints().etag.as_deref(), Some("\"v2\""));
+        assert!(!group.needs_refresh(utc("2024-01-02T00:30:00Z")));
+        assert!(group.needs_refresh(utc("2024-01-02T01:00:00Z")));
+
+        group.remove_source();
+        assert!(!group.needs_refresh(utc("2024-01-03T00:00:00Z")));
+    }
+}
@@ -0,0 +1,410 @@
+//! Traversal of the relationships declared by `relatedTo` properties.
+//!
+//! The `relatedTo` property of an object maps the UIDs of other objects to the ways in which they
+//! are related to it (RFC 8984 §4.1.3). A [`RelationGraph`] collects these maps across a set of
+//! objects, such as the entries of a [`Group`], and answers queries about the parent/child
+//! hierarchy which they describe. Since either side of a relationship may declare it, an object
+//! `b` is a child of `a` if `a` relates to `b` as a `child` or `b` relates to `a` as a `parent`.
+//!
+//! Alerts use the same mechanism to record snoozing: a snooze alert relates to the alert from
+//! which it was derived as its `parent` (RFC 8984 §4.5.2), which is queried with
+//! [`Event::snooze_alerts`] and [`Task::snooze_alerts`].
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use thiserror::Error;
+
+use crate::{
+    json::JsonValue,
+    model::{
+        object::{Alert, Event, Group, MaybeUnknown, Relation, Task, TaskOrEvent},
+        set::{RelationValue, Token},
+        string::{Id, Uid},
+    },
+};
+
+type AlertMap<V> = HashMap<Box<Id>, MaybeUnknown<Alert<V>, V>>;
+
+/// An error returned by [`RelationGraph::topological_order`] if the parent/child hierarchy is
+/// not acyclic.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("the parent/child relations contain a cycle of {} objects", .cycle.len())]
+pub struct CycleError<'a> {
+    /// The UIDs on the cycle, each of which is a parent of the next, and the last of which is a
+    /// parent of the first.
+    pub cycle: Vec<&'a Uid>,
+}
+
+/// The relationships between a set of calendar objects, indexed by UID.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RelationGraph<'a> {
+    objects: BTreeSet<&'a Uid>,
+    relations: BTreeMap<&'a Uid, BTreeMap<&'a Uid, BTreeSet<RelationValue>>>,
+}
+
+impl<'a> RelationGraph<'a> {
+    /// Returns an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the graph of the relationships between the entries of `group`.
+    pub fn from_group<V: JsonValue>(group: &'a Group<V>) -> Self {
+        Self::from_entries(group.entries())
+    }
+
+    /// Returns the graph of the relationships between the given objects.
+    pub fn from_entries<V: JsonValue + 'a>(
+        entries: impl IntoIterator<Item = &'a TaskOrEvent<V>>,
+    ) -> Self {
+        let mut graph = Self::new();
+        for entry in entries {
+            match entry {
+                TaskOrEvent::Event(event) => graph.insert(event.uid(), event.related_to()),
+                TaskOrEvent::Task(task) => graph.insert(task.uid(), task.related_to()),
+            }
+        }
+        graph
+    }
+
+    /// Adds the object with the given UID and `relatedTo` property to the graph.
+    ///
+    /// Unrecognized relation types are ignored.
+    pub fn insert<V: 'a>(
+        &mut self,
+        uid: &'a Uid,
+        related_to: Option<&'a HashMap<Box<Uid>, Relation<V>>>,
+    ) {
+        self.objects.insert(uid);
+        for (other, relation) in related_to.into_iter().flatten() {
+            let values = relation.relations().iter().filter_map(|value| match value {
+                Token::Known(value) => Some(*value),
+                Token::Unknown(_) => None,
+            });
+            self.relations
+                .entry(uid)
+                .or_default()
+                .entry(&**other)
+                .or_default()
+                .extend(values);
+        }
+    }
+
+    /// Returns `true` iff an object with the given UID has been added to the graph.
+    pub fn contains(&self, uid: &Uid) -> bool {
+        self.objects.contains(uid)
+    }
+
+    /// Returns the UIDs of the objects in the graph, in order.
+    pub fn uids(&self) -> impl Iterator<Item = &'a Uid> + '_ {
+        self.objects.iter().copied()
+    }
+
+    /// Returns the UIDs which the object `uid` declares to be related to it by `relation`, in
+    /// order.
+    pub fn related(
+        &self,
+        uid: &Uid,
+        relation: RelationValue,
+    ) -> impl Iterator<Item = &'a Uid> + '_ {
+        self.relations
+            .get(uid)
+            .into_iter()
+            .flatten()
+            .filter(move |(_, values)| values.contains(&relation))
+            .map(|(other, _)| *other)
+    }
+
+    /// Returns the UIDs of the children of `uid`, including those which are not in the graph.
+    pub fn children(&self, uid: &Uid) -> BTreeSet<&'a Uid> {
+        self.hierarchy().remove(uid).unwrap_or_default()
+    }
+
+    /// Returns the UIDs of the parents of `uid`, including those which are not in the graph.
+    pub fn parents(&self, uid: &Uid) -> BTreeSet<&'a Uid> {
+        self.hierarchy()
+            .into_iter()
+            .filter(|(_, children)| children.contains(uid))
+            .map(|(parent, _)| parent)
+            .collect()
+    }
+
+    /// Returns the UIDs of every descendant of `uid`, in order.
+    pub fn descendants(&self, uid: &Uid) -> BTreeSet<&'a Uid> {
+        let hierarchy = self.hierarchy();
+        let mut descendants = BTreeSet::new();
+        let mut pending: Vec<_> = hierarchy.get(uid).into_iter().flatten().copied().collect();
+        while let Some(next) = pending.pop() {
+            if descendants.insert(next) {
+                pending.extend(hierarchy.get(next).into_iter().flatten().copied());
+            }
+        }
+        descendants
+    }
+
+    /// Returns a cycle in the parent/child hierarchy, if there is one; see [`CycleError::cycle`].
+    pub fn find_cycle(&self) -> Option<Vec<&'a Uid>> {
+        /// Searches for a cycle through the descendants of `uid`, which is on `path`.
+        fn visit<'a>(
+            hierarchy: &BTreeMap<&'a Uid, BTreeSet<&'a Uid>>,
+            uid: &'a Uid,
+            path: &mut Vec<&'a Uid>,
+            done: &mut BTreeSet<&'a Uid>,
+        ) -> Option<Vec<&'a Uid>> {
+            if let Some(position) = path.iter().position(|other| *other == uid) {
+                return Some(path[position..].to_vec());
+            }
+            if done.contains(uid) {
+                return None;
+            }
+
+            path.push(uid);
+            for child in hierarchy.get(uid).into_iter().flatten() {
+                if let Some(cycle) = visit(hierarchy, child, path, done) {
+                    return Some(cycle);
+                }
+            }
+            path.pop();
+            done.insert(uid);
+            None
+        }
+
+        let hierarchy = self.hierarchy();
+        let mut done = BTreeSet::new();
+        hierarchy
+            .keys()
+            .find_map(|uid| visit(&hierarchy, uid, &mut Vec::new(), &mut done))
+    }
+
+    /// Returns the UIDs of the objects in the graph, ordered so that every parent precedes its
+    /// children, or an error if the parent/child hierarchy contains a cycle.
+    ///
+    /// Objects which are not ordered by the hierarchy are sorted by UID.
+    pub fn topological_order(&self) -> Result<Vec<&'a Uid>, CycleError<'a>> {
+        let hierarchy = self.hierarchy();
+        let mut parent_counts: BTreeMap<&'a Uid, usize> = (self.objects.iter())
+            .chain(hierarchy.keys())
+            .map(|uid| (*uid, 0))
+            .collect();
+        for child in hierarchy.values().flatten() {
+            *parent_counts.entry(child).or_default() += 1;
+        }
+
+        let mut ready: BTreeSet<_> = parent_counts
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(uid, _)| *uid)
+            .collect();
+        let mut order = Vec::with_capacity(self.objects.len());
+        let mut visited = 0;
+
+        while let Some(uid) = ready.pop_first() {
+            visited += 1;
+            if self.objects.contains(uid) {
+                order.push(uid);
+            }
+            for child in hierarchy.get(uid).into_iter().flatten() {
+                let count = parent_counts
+                    .get_mut(child)
+                    .expect("every child is counted");
+                *count -= 1;
+                if *count == 0 {
+                    ready.insert(child);
+                }
+            }
+        }
+
+        if visited < parent_counts.len() {
+            let cycle = self
+                .find_cycle()
+                .expect("an unordered hierarchy has a cycle");
+            return Err(CycleError { cycle });
+        }
+        Ok(order)
+    }
+
+    /// Returns the children of each object which has any, by UID.
+    fn hierarchy(&self) -> BTreeMap<&'a Uid, BTreeSet<&'a Uid>> {
+        let mut hierarchy: BTreeMap<_, BTreeSet<_>> = BTreeMap::new();
+        for (uid, related) in &self.relations {
+            for (other, values) in related {
+                if values.contains(&RelationValue::Child) {
+                    hierarchy.entry(*uid).or_default().insert(*other);
+                }
+                if values.contains(&RelationValue::Parent) {
+                    hierarchy.entry(*other).or_default().insert(*uid);
+                }
+            }
+        }
+        hierarchy
+    }
+}
+
+impl<V: JsonValue> Event<V> {
+    /// Returns the IDs of the alerts which were derived from the alert `id` by snoozing it, in
+    /// order.
+    pub fn snooze_alerts(&self, id: &Id) -> Vec<&Id> {
+        snooze_alerts(self.alerts(), id)
+    }
+}
+
+impl<V: JsonValue> Task<V> {
+    /// Returns the IDs of the alerts which were derived from the alert `id` by snoozing it; see
+    /// [`Event::snooze_alerts`].
+    pub fn snooze_alerts(&self, id: &Id) -> Vec<&Id> {
+        snooze_alerts(self.alerts(), id)
+    }
+}
+
+/// Returns the IDs of the known alerts which relate to the alert `id` as their parent.
+fn snooze_alerts<'a, V: JsonValue>(
+    alerts: Option<&'a AlertMap<V>>,
+    id: &Id,
+) -> Vec<&'a Id> {
+    let mut snoozed: Vec<_> = alerts
+        .into_iter()
+        .flatten()
+        .filter(|(_, alert)| {
+            alert
+                .as_known()
+                .and_then(Alert::related_to)
+                .and_then(|related_to| related_to.get(id.as_str()))
+                .is_some_and(|relation| {
+                    relation
+                        .relations()
+                        .contains(&Token::Known(RelationValue::Parent))
+                })
+        })
+        .map(|(snooze, _)| &**snooze)
+        .collect();
+    snoozed.sort();
+    snoozed
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::json::TryFromJson;
+
+    fn uid(s: &str) -> &Uid {
+        Uid::new(s).unwrap()
+    }
+
+    fn group(entries: Value) -> Group<Value> {
+        Group::try_from_json(json!({
+            "@type": "Group",
+            "uid": "group",
+            "entries": entries
+        }))
+        .unwrap()
+    }
+
+    fn task(uid: &str, related_to: Value) -> Value {
+        json!({ "@type": "Task", "uid": uid, "relatedTo": related_to })
+    }
+
+    fn relation(value: &str) -> Value {
+        json!({ "@type": "Relation", "relation": { value: true } })
+    }
+
+    #[test]
+    fn hierarchy_queries() {
+        let group = group(json!([
+            task("project", json!({ "design": relation("child") })),
+            task("design", json!({})),
+            task("build", json!({ "project": relation("parent") })),
+            task(
+                "wire",
+                json!({ "build": relation("parent"), "design": relation("next") })
+            ),
+            task("orphan", json!({ "missing": relation("parent") })),
+            task("alone", json!({})),
+        ]));
+        let graph = RelationGraph::from_group(&group);
+
+        assert!(graph.contains(uid("wire")));
+        assert!(!graph.contains(uid("missing")));
+        assert_eq!(
+            graph.children(uid("project")),
+            BTreeSet::from([uid("build"), uid("design")])
+        );
+        assert_eq!(graph.parents(uid("wire")), BTreeSet::from([uid("build")]));
+        assert_eq!(
+            graph.descendants(uid("project")),
+            BTreeSet::from([uid("build"), uid("design"), uid("wire")])
+        );
+        assert_eq!(
+            graph
+                .related(uid("wire"), RelationValue::Next)
+                .collect::<Vec<_>>(),
+            [uid("design")]
+        );
+        assert_eq!(graph.find_cycle(), None);
+        assert_eq!(
+            graph.topological_order().unwrap(),
+            [
+                uid("alone"),
+                uid("orphan"),
+                uid("project"),
+                uid("build"),
+                uid("design"),
+                uid("wire")
+            ]
+        );
+    }
+
+    #[test]
+    fn cycle_detection() {
+        let group = group(json!([
+            task("a", json!({ "b": relation("child") })),
+            task("b", json!({ "c": relation("child") })),
+            task(
+                "c",
+                json!({ "b": relation("parent"), "a": relation("child") })
+            ),
+            task("d", json!({})),
+        ]));
+        let graph = RelationGraph::from_group(&group);
+
+        let cycle = vec![uid("a"), uid("b"), uid("c")];
+        assert_eq!(graph.find_cycle(), Some(cycle.clone()));
+        assert_eq!(graph.topological_order(), Err(CycleError { cycle }));
+    }
+
+    #[test]
+    fn snooze_alerts() {
+        let event: Event<Value> = Event::try_from_json(json!({
+            "@type": "Event",
+            "uid": "event",
+            "start": "2024-01-01T09:00:00",
+            "alerts": {
+                "original": {
+                    "@type": "Alert",
+                    "trigger": { "@type": "OffsetTrigger", "offset": "-PT15M" }
+                },
+                "snooze2": {
+                    "@type": "Alert",
+                    "trigger": { "@type": "AbsoluteTrigger", "when": "2024-01-01T08:55:00Z" },
+                    "relatedTo": { "original": relation("parent") }
+                },
+                "snooze1": {
+                    "@type": "Alert",
+                    "trigger": { "@type": "AbsoluteTrigger", "when": "2024-01-01T08:50:00Z" },
+                    "relatedTo": { "original": relation("parent") }
+                }
+            }
+        }))
+        .unwrap();
+
+        let original = Id::new("original").unwrap();
+        let snoozed: Vec<_> = event
+            .snooze_alerts(original)
+            .into_iter()
+            .map(Id::as_str)
+            .collect();
+        assert_eq!(snoozed, ["snooze1", "snooze2"]);
+        assert!(event.snooze_alerts(Id::new("snooze1").unwrap()).is_empty());
+    }
+}
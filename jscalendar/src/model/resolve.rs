@@ -0,0 +1,300 @@
+//! Resolution of the custom time zones of group entries.
+//!
+//! The `timeZones` of a [`Group`] define custom time zones which its entries may use in the same
+//! way as those in their own `timeZones` (RFC 8984 §4.7.2). The accessors of an entry only see its
+//! own properties, so a [`ResolvedEntry`] views an entry together with its group, and looks up a
+//! custom time zone first in the `timeZones` of the entry and then in those of the group.
+//!
+//! An entry taken out of its group loses the definitions of the group, so
+//! [`ResolvedEntry::to_standalone`] copies those which it uses into its own `timeZones`.
+
+use std::collections::HashMap;
+
+use crate::{
+    json::{DestructibleJsonValue, JsonValue},
+    model::{
+        conflict::to_utc,
+        object::{Group, TaskOrEvent, TimeZone},
+        string::CustomTimeZoneId,
+        time::{DateTime, Local, Utc, UtcOffset},
+        tz::referenced,
+        window::offset_in,
+    },
+};
+
+type TimeZoneMap<V> = HashMap<Box<CustomTimeZoneId>, TimeZone<V>>;
+
+/// An entry of a [`Group`] viewed together with the group, so that the custom time zones of the
+/// group are available to it.
+pub struct ResolvedEntry<'g, V: JsonValue> {
+    group: &'g Group<V>,
+    entry: &'g TaskOrEvent<V>,
+}
+
+impl<V: JsonValue> Clone for ResolvedEntry<'_, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V: JsonValue> Copy for ResolvedEntry<'_, V> {}
+
+impl<V: JsonValue> Group<V> {
+    /// Returns the entries of this group together with the group, in order.
+    pub fn resolved_entries(&self) -> impl Iterator<Item = ResolvedEntry<'_, V>> {
+        self.entries()
+            .iter()
+            .map(|entry| ResolvedEntry::new(self, entry))
+    }
+}
+
+impl<'g, V: JsonValue> ResolvedEntry<'g, V> {
+    /// Views `entry` as an entry of `group`, whether or not it is one of its `entries`.
+    pub fn new(group: &'g Group<V>, entry: &'g TaskOrEvent<V>) -> Self {
+        Self { group, entry }
+    }
+
+    /// Returns the group.
+    pub fn group(&self) -> &'g Group<V> {
+        self.group
+    }
+
+    /// Returns the entry.
+    pub fn entry(&self) -> &'g TaskOrEvent<V> {
+        self.entry
+    }
+
+    /// Returns the `timeZone` of the entry.
+    pub fn time_zone(&self) -> Option<&'g str> {
+        match self.entry {
+            TaskOrEvent::Event(event) => event.time_zone(),
+            TaskOrEvent::Task(task) => task.time_zone(),
+        }
+        .map(String::as_str)
+    }
+
+    /// Returns the definition of the custom time zone `id`, from the `timeZones` of the entry if
+    /// it defines `id` and from those of the group otherwise.
+    pub fn custom_time_zone(&self, id: &CustomTimeZoneId) -> Option<&'g TimeZone<V>> {
+        self.own_time_zones()
+            .and_then(|time_zones| time_zones.get(id))
+            .or_else(|| self.group.time_zones()?.get(id))
+    }
+
+    /// Returns the definition of the `timeZone` of the entry, or `None` if it is absent or not a
+    /// custom time zone defined by the entry or the group.
+    pub fn time_zone_definition(&self) -> Option<&'g TimeZone<V>> {
+        let id = CustomTimeZoneId::new(self.time_zone()?).ok()?;
+        self.custom_time_zone(id)
+    }
+
+    /// Returns the custom time zones available to the entry, where those of the entry take
+    /// precedence over those of the group with the same IDs.
+    pub fn time_zones(&self) -> HashMap<&'g CustomTimeZoneId, &'g TimeZone<V>> {
+        let group = self.group.time_zones().into_iter().flatten();
+        let own = self.own_time_zones().into_iter().flatten();
+        group
+            .chain(own)
+            .map(|(id, time_zone)| (&**id, time_zone))
+            .collect()
+    }
+
+    fn own_time_zones(&self) -> Option<&'g TimeZoneMap<V>> {
+        match self.entry {
+            TaskOrEvent::Event(event) => event.time_zones(),
+            TaskOrEvent::Task(task) => task.time_zones(),
+        }
+    }
+}
+
+impl<V: DestructibleJsonValue> ResolvedEntry<'_, V> {
+    /// Returns the `start` of the entry in UTC, or `None` if it has no `start` or it is out of
+    /// range.
+    ///
+    /// The `timeZone` of the entry is resolved from its custom time zones and those of the group,
+    /// and any other time zone by `offset`, as for [`Event::utc_start`]. Floating entries, and
+    /// those whose time zone cannot be resolved, are treated as though they were in UTC.
+    ///
+    /// [`Event::utc_start`]: crate::model::object::Event::utc_start
+    pub fn utc_start(
+        &self,
+        offset: impl FnOnce(&str, DateTime<Local>) -> Option<UtcOffset>,
+    ) -> Option<DateTime<Utc>> {
+        let local = match self.entry {
+            TaskOrEvent::Event(event) => *event.start(),
+            TaskOrEvent::Task(task) => *task.start()?,
+        };
+        let zone = self
+            .time_zone()
+            .and_then(|time_zone| offset_in(time_zone, self.time_zone_definition(), local, offset));
+        to_utc(local, zone)
+    }
+
+    /// Returns a copy of the entry to which the custom time zones of the group that it refers to
+    /// are added, so that it can be used outside the group.
+    ///
+    /// Time zones which the entry defines itself are kept, and so are the other properties of the
+    /// entry.
+    pub fn to_standalone(&self) -> TaskOrEvent<V>
+    where
+        V: Clone,
+        V::Object: Clone,
+    {
+        let mut entry = self.entry.clone();
+        let referenced = match self.entry {
+            TaskOrEvent::Event(event) => {
+                referenced(event.referenced_time_zones(), event.recurrence_overrides())
+            }
+            TaskOrEvent::Task(task) => {
+                referenced(task.referenced_time_zones(), task.recurrence_overrides())
+            }
+        };
+
+        let own = self.own_time_zones();
+        let inherited: Vec<_> = self
+            .group
+            .time_zones()
+            .into_iter()
+            .flatten()
+            .filter(|(id, _)| referenced.contains(&id.to_string()))
+            .filter(|(id, _)| !own.is_some_and(|own| own.contains_key(*id)))
+            .map(|(id, time_zone)| (id.clone(), time_zone.clone()))
+            .collect();
+        if inherited.is_empty() {
+            return entry;
+        }
+
+        match &mut entry {
+            TaskOrEvent::Event(event) => {
+                let mut time_zones = event.remove_time_zones().unwrap_or_default();
+                time_zones.extend(inherited);
+                event.set_time_zones(time_zones);
+            }
+            TaskOrEvent::Task(task) => {
+                let mut time_zones = task.remove_time_zones().unwrap_or_default();
+                time_zones.extend(inherited);
+                task.set_time_zones(time_zones);
+            }
+        }
+        entry
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::json::TryFromJson;
+
+    fn fixed(offset: &str) -> Value {
+        json!({
+            "@type": "TimeZone",
+            "tzId": offset,
+            "standard": [{
+                "@type": "TimeZoneRule",
+                "start": "1970-01-01T00:00:00",
+                "offsetFrom": offset,
+                "offsetTo": offset
+            }]
+        })
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::try_from_json(Value::from(s)).unwrap()
+    }
+
+    fn group() -> Group<Value> {
+        Group::try_from_json(json!({
+            "@type": "Group",
+            "uid": "g",
+            "timeZones": { "/plus5": fixed("+05:00"), "/plus1": fixed("+01:00") },
+            "entries": [
+                {
+                    "@type": "Event",
+                    "uid": "inherits",
+                    "start": "2024-01-01T12:00:00",
+                    "timeZone": "/plus5"
+                },
+                {
+                    "@type": "Event",
+                    "uid": "overrides",
+                    "start": "2024-01-01T12:00:00",
+                    "timeZone": "/plus5",
+                    "timeZones": { "/plus5": fixed("+03:00") }
+                },
+                {
+                    "@type": "Task",
+                    "uid": "floating",
+                    "start": "2024-01-01T12:00:00"
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn group_time_zones_resolve() {
+        let group = group();
+        let entries: Vec<_> = group.resolved_entries().collect();
+        let unknown = |_: &str, _| -> Option<UtcOffset> { None };
+
+        assert_eq!(
+            entries[0].utc_start(unknown),
+            Some(utc("2024-01-01T07:00:00Z"))
+        );
+        assert_eq!(
+            entries[1].utc_start(unknown),
+            Some(utc("2024-01-01T09:00:00Z"))
+        );
+        assert_eq!(
+            entries[2].utc_start(unknown),
+            Some(utc("2024-01-01T12:00:00Z"))
+        );
+
+        let plus5 = CustomTimeZoneId::new("/plus5").unwrap();
+        assert!(std::ptr::eq(
+            entries[0].time_zone_definition().unwrap(),
+            &group.time_zones().unwrap()[plus5]
+        ));
+        assert_eq!(entries[0].time_zones().len(), 2);
+        assert!(entries[2].time_zone_definition().is_none());
+
+        // the event on its own does not see the time zones of the group
+        let TaskOrEvent::Event(event) = entries[0].entry() else {
+            unreachable!()
+        };
+        assert_eq!(event.utc_start(unknown), Some(utc("2024-01-01T12:00:00Z")));
+    }
+
+    #[test]
+    fn to_standalone() {
+        let group = group();
+        let entries: Vec<_> = group.resolved_entries().collect();
+        let unknown = |_: &str, _| -> Option<UtcOffset> { None };
+
+        let TaskOrEvent::Event(inherits) = entries[0].to_standalone() else {
+            unreachable!()
+        };
+        let time_zones = inherits.time_zones().unwrap();
+        assert_eq!(time_zones.len(), 1);
+        assert!(time_zones.contains_key(CustomTimeZoneId::new("/plus5").unwrap()));
+        assert_eq!(
+            inherits.utc_start(unknown),
+            Some(utc("2024-01-01T07:00:00Z"))
+        );
+
+        let TaskOrEvent::Event(overrides) = entries[1].to_standalone() else {
+            unreachable!()
+        };
+        assert_eq!(
+            overrides.utc_start(unknown),
+            Some(utc("2024-01-01T09:00:00Z"))
+        );
+
+        let TaskOrEvent::Task(floating) = entries[2].to_standalone() else {
+            unreachable!()
+        };
+        assert!(floating.time_zones().is_none());
+    }
+}
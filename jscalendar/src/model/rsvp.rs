@@ -0,0 +1,295 @@
+//! Per-participant RSVP links (RFC 8984 §4.4.4).
+//!
+//! Self-service RSVP pages usually give each participant a distinct `replyTo.web` link, which
+//! identifies both the event and the participant who is replying. An [`RsvpLinkTemplate`]
+//! describes the shape of such links, and can both generate them and recover the event and
+//! participant from a link that was followed.
+
+use thiserror::Error;
+
+use crate::model::{
+    object::ReplyTo,
+    string::{HttpsUrl, Id, InvalidHttpsUrlError, InvalidUriError, Uid, Uri},
+};
+
+/// The placeholder replaced by the (percent-encoded) event UID.
+const UID_PLACEHOLDER: &str = "{uid}";
+/// The placeholder replaced by the participant ID.
+const PARTICIPANT_PLACEHOLDER: &str = "{participant}";
+
+/// An error indicating that a string is not a valid [`RsvpLinkTemplate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum InvalidRsvpLinkTemplateError {
+    /// The template does not contain exactly one `{uid}` placeholder.
+    #[error("expected exactly one {{uid}} placeholder")]
+    UidPlaceholder,
+    /// The template does not contain exactly one `{participant}` placeholder.
+    #[error("expected exactly one {{participant}} placeholder")]
+    ParticipantPlaceholder,
+    /// The text between the placeholders contains no reserved character (such as `/`), so expanded
+    /// links could not be reliably split apart again.
+    #[error("the placeholders must be separated by a reserved character")]
+    AmbiguousSeparator,
+    /// The expanded template would not be a valid URI.
+    #[error("invalid URI: {0}")]
+    Uri(#[from] InvalidUriError),
+}
+
+/// A template for per-participant RSVP links, such as
+/// `https://example.com/rsvp/{uid}/{participant}`.
+///
+/// The template must contain exactly one `{uid}` and one `{participant}` placeholder, and the
+/// text between them must contain a reserved character such as `/` or `&`. The UID is
+/// percent-encoded on expansion, since it may contain arbitrary characters; participant IDs never
+/// need encoding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RsvpLinkTemplate {
+    template: Box<str>,
+    /// The literal text before the first placeholder, between the two placeholders, and after
+    /// the second placeholder.
+    literals: [Box<str>; 3],
+    /// Whether the `{uid}` placeholder comes before the `{participant}` placeholder.
+    uid_first: bool,
+}
+
+impl RsvpLinkTemplate {
+    /// Parses a template, returning an error if the placeholders are missing, repeated, or not
+    /// clearly separated, or if the template does not begin with a URI scheme.
+    pub fn new(template: &str) -> Result<Self, InvalidRsvpLinkTemplateError> {
+        let uid = find_unique(template, UID_PLACEHOLDER)
+            .ok_or(InvalidRsvpLinkTemplateError::UidPlaceholder)?;
+        let participant = find_unique(template, PARTICIPANT_PLACEHOLDER)
+            .ok_or(InvalidRsvpLinkTemplateError::ParticipantPlaceholder)?;
+
+        let uid_first = uid < participant;
+        let (first, first_len, second, second_len) = if uid_first {
+            (
+                uid,
+                UID_PLACEHOLDER.len(),
+                participant,
+                PARTICIPANT_PLACEHOLDER.len(),
+            )
+        } else {
+            (
+                participant,
+                PARTICIPANT_PLACEHOLDER.len(),
+                uid,
+                UID_PLACEHOLDER.len(),
+            )
+        };
+
+        let infix = &template[first + first_len..second];
+        if infix
+            .bytes()
+            .all(|byte| is_unreserved(byte) || byte == b'%')
+        {
+            return Err(InvalidRsvpLinkTemplateError::AmbiguousSeparator);
+        }
+
+        let literals = [&template[..first], infix, &template[second + second_len..]].map(Box::from);
+
+        // the scheme must lie entirely before the first placeholder
+        Uri::new(&format!("{}x", literals[0]))?;
+
+        Ok(Self {
+            template: template.into(),
+            literals,
+            uid_first,
+        })
+    }
+
+    /// Returns the template string.
+    pub fn as_str(&self) -> &str {
+        &self.template
+    }
+
+    /// Returns the RSVP link for the participant with the given ID in the event with the given
+    /// UID.
+    pub fn expand(&self, uid: &Uid, participant_id: &Id) -> Box<Uri> {
+        let uid = percent_encode(uid.as_str());
+        let (first, second) = if self.uid_first {
+            (uid.as_str(), participant_id.as_str())
+        } else {
+            (participant_id.as_str(), uid.as_str())
+        };
+
+        let [prefix, infix, suffix] = &self.literals;
+        let link = format!("{prefix}{first}{infix}{second}{suffix}");
+        Uri::new(&link)
+            .expect("the template begins with a valid scheme")
+            .into()
+    }
+
+    /// Recovers the event UID and participant ID from a link produced by
+    /// [`RsvpLinkTemplate::expand`], returning `None` if `link` does not match this template.
+    pub fn parse(&self, link: &Uri) -> Option<(Box<Uid>, Box<Id>)> {
+        let [prefix, infix, suffix] = &self.literals;
+        let rest = link.as_str().strip_prefix(&**prefix)?;
+        let rest = rest.strip_suffix(&**suffix)?;
+        // the infix contains a reserved character, which never occurs in either expanded value
+        let (first, second) = rest.split_once(&**infix)?;
+
+        let (uid, participant) = if self.uid_first {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        let uid = percent_decode(uid)?;
+        let uid = Uid::new(&uid).ok()?.into();
+        let participant = Id::new(participant).ok()?.into();
+        Some((uid, participant))
+    }
+}
+
+impl std::fmt::Display for RsvpLinkTemplate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+impl ReplyTo {
+    /// Returns a `replyTo` value whose `web` link is the RSVP link for the given participant,
+    /// generated from `template`, or an error if the link is not an `https` URL.
+    pub fn web_for(
+        uid: &Uid,
+        participant_id: &Id,
+        template: &RsvpLinkTemplate,
+    ) -> Result<Self, InvalidHttpsUrlError> {
+        let link = template.expand(uid, participant_id);
+        let mut reply_to = ReplyTo::new();
+        reply_to.set_web(HttpsUrl::new(link.as_str())?.into());
+        Ok(reply_to)
+    }
+}
+
+/// Returns the byte index of the only occurrence of `needle` in `haystack`, if there is exactly
+/// one.
+fn find_unique(haystack: &str, needle: &str) -> Option<usize> {
+    let mut matches = haystack.match_indices(needle).map(|(index, _)| index);
+    let index = matches.next()?;
+    matches.next().is_none().then_some(index)
+}
+
+/// Percent-encodes every byte of `s` except the unreserved characters of RFC 3986 §2.3.
+fn percent_encode(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if is_unreserved(byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+/// Returns `true` iff `byte` is an unreserved character (RFC 3986 §2.3).
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+}
+
+/// Inverse of [`percent_encode`], returning `None` if an escape is malformed or the decoded bytes
+/// are not valid UTF-8.
+fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = tail.get(..2)?;
+            let hex = std::str::from_utf8(hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rsvp_link_round_trip() {
+        let template =
+            RsvpLinkTemplate::new("https://example.com/rsvp/{uid}/{participant}").unwrap();
+        let uid = Uid::new("event 1@example.com").unwrap();
+        let participant = Id::new("dG9tQGZvb2Jhci5xlLmNvbQ").unwrap();
+
+        let link = template.expand(uid, participant);
+        assert_eq!(
+            link.as_str(),
+            "https://example.com/rsvp/event%201%40example.com/dG9tQGZvb2Jhci5xlLmNvbQ"
+        );
+
+        let (parsed_uid, parsed_participant) = template.parse(&link).unwrap();
+        assert_eq!(&*parsed_uid, uid);
+        assert_eq!(&*parsed_participant, participant);
+
+        let reply_to = ReplyTo::web_for(uid, participant, &template).unwrap();
+        assert_eq!(reply_to.web().map(|web| web.as_uri()), Some(&*link));
+
+        let template =
+            RsvpLinkTemplate::new("http://example.com/rsvp/{uid}/{participant}").unwrap();
+        assert_eq!(
+            ReplyTo::web_for(uid, participant, &template),
+            Err(InvalidHttpsUrlError::NotHttps {
+                scheme: "http".into()
+            })
+        );
+    }
+
+    #[test]
+    fn rsvp_link_participant_first() {
+        let template =
+            RsvpLinkTemplate::new("https://example.com/r?p={participant}&e={uid}").unwrap();
+        let uid = Uid::new("abc/def").unwrap();
+        let participant = Id::new("p1").unwrap();
+
+        let link = template.expand(uid, participant);
+        assert_eq!(link.as_str(), "https://example.com/r?p=p1&e=abc%2Fdef");
+        let (parsed_uid, parsed_participant) = template.parse(&link).unwrap();
+        assert_eq!(parsed_uid.as_str(), "abc/def");
+        assert_eq!(parsed_participant.as_str(), "p1");
+    }
+
+    #[test]
+    fn rsvp_link_parse_rejects_foreign_links() {
+        let template =
+            RsvpLinkTemplate::new("https://example.com/rsvp/{uid}/{participant}").unwrap();
+
+        for link in [
+            "https://example.org/rsvp/a/b",
+            "https://example.com/rsvp/a",
+            "https://example.com/rsvp/a/not%20an%20id",
+            "https://example.com/rsvp/%ZZ/b",
+        ] {
+            assert_eq!(template.parse(Uri::new(link).unwrap()), None, "{link}");
+        }
+    }
+
+    #[test]
+    fn rsvp_link_template_errors() {
+        use InvalidRsvpLinkTemplateError as E;
+
+        let err = |s| RsvpLinkTemplate::new(s).unwrap_err();
+        assert_eq!(err("https://example.com/{participant}"), E::UidPlaceholder);
+        assert_eq!(
+            err("https://example.com/{uid}/{uid}/{participant}"),
+            E::UidPlaceholder
+        );
+        assert_eq!(err("https://example.com/{uid}"), E::ParticipantPlaceholder);
+        assert_eq!(
+            err("https://example.com/{uid}{participant}"),
+            E::AmbiguousSeparator
+        );
+        assert_eq!(
+            err("https://example.com/{uid}-{participant}"),
+            E::AmbiguousSeparator
+        );
+        assert!(matches!(err("{uid}/{participant}"), E::Uri(_)));
+    }
+}
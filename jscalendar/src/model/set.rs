@@ -1,17 +1,51 @@
 //! Types for values which appear in sets.
 
+use std::{collections::BTreeSet, convert::Infallible};
+
 pub use calendar_types::{
     css::Css3Color,
     set::{LinkRelation, LocationType, Token},
 };
 pub use rfc5545_types::set::{Method, Percent, Priority};
-use strum::{Display, EnumString};
+use strum::{Display, EnumString, IntoStaticStr, VariantArray};
 use thiserror::Error;
 
-use crate::json::{ConstructibleJsonValue, DestructibleJsonValue, IntoJson, TryFromJson, TypeErrorOr, UnsignedInt};
+use crate::json::{
+    ConstructibleJsonValue, DestructibleJsonValue, DocumentError, HashSetTryFromJsonError,
+    IntoJson, JsonObject, PathSegment, TryFromJson, TypeErrorOr, UnsignedInt,
+};
+
+calendar_types::impl_token_parse!(
+    RelationValue,
+    DisplayPurpose,
+    FreeBusyStatus,
+    Privacy,
+    EventStatus,
+    TaskProgress,
+    VirtualLocationFeature,
+    ParticipantKind,
+    ParticipantRole,
+    ParticipationStatus,
+    ScheduleAgent,
+    AlertRelativeTo,
+    AlertAction,
+);
 
 /// A value which may appear in the `relation` field of a `Relation` object (RFC 8984 §1.4.10).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 pub enum RelationValue {
@@ -26,7 +60,20 @@ pub enum RelationValue {
 }
 
 /// The intended purpose of a link to an image (RFC 8984 §1.4.11).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 pub enum DisplayPurpose {
@@ -41,7 +88,20 @@ pub enum DisplayPurpose {
 }
 
 /// A free/busy status (RFC 8984 §4.4.2).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 pub enum FreeBusyStatus {
@@ -52,7 +112,20 @@ pub enum FreeBusyStatus {
 }
 
 /// A privacy level (RFC 8984 §4.4.3).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 pub enum Privacy {
@@ -65,7 +138,20 @@ pub enum Privacy {
 }
 
 /// An event status (RFC 8984 §5.1.3).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 pub enum EventStatus {
@@ -78,7 +164,20 @@ pub enum EventStatus {
 }
 
 /// A task progress status (RFC 8984 §5.2.5).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 pub enum TaskProgress {
@@ -95,7 +194,20 @@ pub enum TaskProgress {
 }
 
 /// A feature supported by a virutal location (RFC 8984 §4.2.6).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 pub enum VirtualLocationFeature {
@@ -116,7 +228,20 @@ pub enum VirtualLocationFeature {
 }
 
 /// The kind of a participant (RFC 8984 §4.4.6).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 pub enum ParticipantKind {
@@ -131,7 +256,20 @@ pub enum ParticipantKind {
 }
 
 /// The role of a participant (RFC 8984 §4.4.6).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 pub enum ParticipantRole {
@@ -150,7 +288,20 @@ pub enum ParticipantRole {
 }
 
 /// The status of a participant (RFC 8984 §4.4.6).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 pub enum ParticipationStatus {
@@ -168,7 +319,20 @@ pub enum ParticipationStatus {
 }
 
 /// The agent responsible for sending scheduling messages to a participant (RFC 8984 §4.4.6).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 pub enum ScheduleAgent {
@@ -181,7 +345,20 @@ pub enum ScheduleAgent {
 }
 
 /// The time property that an alert is relative to (RFC 8984 §4.5.2).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 pub enum AlertRelativeTo {
@@ -192,7 +369,20 @@ pub enum AlertRelativeTo {
 }
 
 /// The action by which an alert is conveyed (RFC 8984 §4.5.2).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive, serialize_all = "lowercase")]
 pub enum AlertAction {
@@ -231,6 +421,113 @@ impl std::fmt::Display for Color {
     }
 }
 
+/// The colors used by [`Color::assign`], chosen to be distinguishable from each other and
+/// legible with either black or white text.
+const ASSIGNED_COLORS: [Css3Color; 12] = [
+    Css3Color::SteelBlue,
+    Css3Color::SeaGreen,
+    Css3Color::Coral,
+    Css3Color::GoldenRod,
+    Css3Color::MediumPurple,
+    Css3Color::Tomato,
+    Css3Color::Teal,
+    Css3Color::OliveDrab,
+    Css3Color::SlateBlue,
+    Css3Color::IndianRed,
+    Css3Color::DarkCyan,
+    Css3Color::Peru,
+];
+
+impl Color {
+    /// Returns the RGB value of this color, resolving CSS3 color names.
+    pub const fn to_rgb(self) -> Rgb {
+        match self {
+            Color::Css(css) => {
+                let (red, green, blue) = css.to_rgb();
+                Rgb { red, green, blue }
+            }
+            Color::Rgb(rgb) => rgb,
+        }
+    }
+
+    /// Returns the hex color with the given `(red, green, blue)` components, which is
+    /// serialized as `#rrggbb`.
+    pub const fn from_rgb((red, green, blue): (u8, u8, u8)) -> Self {
+        Color::Rgb(Rgb { red, green, blue })
+    }
+
+    /// Returns the CSS3 color name closest to this color, as described by
+    /// [`Css3Color::from_rgb_nearest`]. Named colors are returned unchanged.
+    pub fn nearest_css_name(self) -> Css3Color {
+        match self {
+            Color::Css(css) => css,
+            Color::Rgb(rgb) => Css3Color::from_rgb_nearest(rgb.into()),
+        }
+    }
+
+    /// Returns this color as a lowercase `#rrggbb` hex string, resolving CSS3 color names.
+    pub fn as_display_hex(self) -> String {
+        let Rgb { red, green, blue } = self.to_rgb();
+        format!("#{red:02x}{green:02x}{blue:02x}")
+    }
+
+    /// Returns black or white, whichever has the higher contrast ratio (as defined by WCAG 2.2)
+    /// as text on a background of this color.
+    pub fn contrast_text_color(self) -> Color {
+        let luminance = self.to_rgb().relative_luminance();
+        // the contrast ratios against black and white are (L + 0.05) / 0.05 and 1.05 / (L + 0.05)
+        if (luminance + 0.05).powi(2) >= 0.05 * 1.05 {
+            Color::Css(Css3Color::Black)
+        } else {
+            Color::Css(Css3Color::White)
+        }
+    }
+
+    /// Deterministically assigns a color to a calendar object which does not have one.
+    ///
+    /// Objects sharing a keyword are given the same color, so the color is derived from the
+    /// lexicographically smallest of the `keywords` if there are any, and from the `uid`
+    /// otherwise. The result is stable across platforms and releases of this crate.
+    pub fn assign<'a>(uid: &str, keywords: impl IntoIterator<Item = &'a str>) -> Color {
+        let key = keywords.into_iter().min().unwrap_or(uid);
+
+        // 64-bit FNV-1a
+        let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+
+        Color::Css(ASSIGNED_COLORS[(hash % ASSIGNED_COLORS.len() as u64) as usize])
+    }
+}
+
+impl From<(u8, u8, u8)> for Rgb {
+    fn from((red, green, blue): (u8, u8, u8)) -> Self {
+        Rgb { red, green, blue }
+    }
+}
+
+impl From<Rgb> for (u8, u8, u8) {
+    fn from(rgb: Rgb) -> Self {
+        (rgb.red, rgb.green, rgb.blue)
+    }
+}
+
+impl Rgb {
+    /// Returns the relative luminance of this color as defined by WCAG 2.2, between 0 and 1.
+    fn relative_luminance(self) -> f64 {
+        let linear = |channel: u8| {
+            let c = f64::from(channel) / 255.0;
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        0.2126 * linear(self.red) + 0.7152 * linear(self.green) + 0.0722 * linear(self.blue)
+    }
+}
+
 impl<V: ConstructibleJsonValue> IntoJson<V> for Color {
     fn into_json(self) -> V {
         V::string(self.to_string())
@@ -317,3 +614,310 @@ impl<V: DestructibleJsonValue> TryFromJson<V> for Percent {
             .map_err(TypeErrorOr::Other)
     }
 }
+
+/// A set of [`ParticipantRole`] tokens, as in the `roles` property of a participant (RFC 8984
+/// §4.4.6).
+///
+/// The known roles are stored as a bitset, so a set of known roles does not allocate; unknown
+/// roles are kept in a separate set, so that they survive a round trip through JSON.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ParticipantRoleSet {
+    known: u8,
+    unknown: BTreeSet<Box<str>>,
+}
+
+impl ParticipantRoleSet {
+    /// Every known role, in the order of their bits.
+    const ROLES: [ParticipantRole; 6] = [
+        ParticipantRole::Owner,
+        ParticipantRole::Attendee,
+        ParticipantRole::Optional,
+        ParticipantRole::Informational,
+        ParticipantRole::Chair,
+        ParticipantRole::Contact,
+    ];
+
+    /// Returns an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    const fn bit(role: ParticipantRole) -> u8 {
+        1 << role as u8
+    }
+
+    /// Returns the number of roles in this set.
+    pub fn len(&self) -> usize {
+        self.known.count_ones() as usize + self.unknown.len()
+    }
+
+    /// Returns `true` if this set contains no roles.
+    pub fn is_empty(&self) -> bool {
+        self.known == 0 && self.unknown.is_empty()
+    }
+
+    /// Returns `true` if this set contains the known `role`.
+    pub fn contains(&self, role: ParticipantRole) -> bool {
+        self.known & Self::bit(role) != 0
+    }
+
+    /// Returns `true` if this set contains the given role token.
+    pub fn contains_token(&self, role: &Token<ParticipantRole, Box<str>>) -> bool {
+        match role {
+            Token::Known(role) => self.contains(*role),
+            Token::Unknown(role) => self.unknown.contains(role),
+        }
+    }
+
+    /// Adds a role to this set, returning `true` if it was not already present.
+    pub fn insert(&mut self, role: Token<ParticipantRole, Box<str>>) -> bool {
+        match role {
+            Token::Known(role) => {
+                let absent = !self.contains(role);
+                self.known |= Self::bit(role);
+                absent
+            }
+            Token::Unknown(role) => self.unknown.insert(role),
+        }
+    }
+
+    /// Removes a role from this set, returning `true` if it was present.
+    pub fn remove(&mut self, role: &Token<ParticipantRole, Box<str>>) -> bool {
+        match role {
+            Token::Known(role) => {
+                let present = self.contains(*role);
+                self.known &= !Self::bit(*role);
+                present
+            }
+            Token::Unknown(role) => self.unknown.remove(role),
+        }
+    }
+
+    /// Returns an iterator over the roles in this set: the known roles in declaration order,
+    /// followed by the unknown roles in lexicographic order.
+    pub fn iter(&self) -> impl Iterator<Item = Token<ParticipantRole, &str>> {
+        let known = Self::ROLES
+            .into_iter()
+            .filter(|role| self.contains(*role))
+            .map(Token::Known);
+        known.chain(self.unknown.iter().map(|role| Token::Unknown(&**role)))
+    }
+
+    /// Returns the roles in either this set or `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            known: self.known | other.known,
+            unknown: self.unknown.union(&other.unknown).cloned().collect(),
+        }
+    }
+
+    /// Returns the roles in both this set and `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            known: self.known & other.known,
+            unknown: self.unknown.intersection(&other.unknown).cloned().collect(),
+        }
+    }
+
+    /// Returns the roles in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        Self {
+            known: self.known & !other.known,
+            unknown: self.unknown.difference(&other.unknown).cloned().collect(),
+        }
+    }
+
+    /// Returns `true` if every role in this set is also in `other`.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.known & !other.known == 0 && self.unknown.is_subset(&other.unknown)
+    }
+}
+
+impl Extend<Token<ParticipantRole, Box<str>>> for ParticipantRoleSet {
+    fn extend<I: IntoIterator<Item = Token<ParticipantRole, Box<str>>>>(&mut self, iter: I) {
+        for role in iter {
+            self.insert(role);
+        }
+    }
+}
+
+impl FromIterator<Token<ParticipantRole, Box<str>>> for ParticipantRoleSet {
+    fn from_iter<I: IntoIterator<Item = Token<ParticipantRole, Box<str>>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl FromIterator<ParticipantRole> for ParticipantRoleSet {
+    fn from_iter<I: IntoIterator<Item = ParticipantRole>>(iter: I) -> Self {
+        iter.into_iter().map(Token::Known).collect()
+    }
+}
+
+impl<V: DestructibleJsonValue> TryFromJson<V> for ParticipantRoleSet {
+    type Error = DocumentError<TypeErrorOr<HashSetTryFromJsonError<Infallible>>>;
+
+    fn try_from_json(value: V) -> Result<Self, Self::Error> {
+        let object = value
+            .try_into_object()
+            .map_err(TypeErrorOr::from)
+            .map_err(DocumentError::root)?;
+
+        let mut set = Self::new();
+        for (key, value) in object.into_iter() {
+            let error = match value.try_as_bool() {
+                Ok(true) => {
+                    let key = <V::Object as JsonObject>::key_as_str(&key);
+                    let Ok(role) = key.parse();
+                    set.insert(role);
+                    continue;
+                }
+                Ok(false) => TypeErrorOr::Other(HashSetTryFromJsonError::UnexpectedFalseValue),
+                Err(error) => TypeErrorOr::from(error),
+            };
+            let key = <V::Object as JsonObject>::key_into_string(key);
+            return Err(DocumentError {
+                path: vec![PathSegment::String(key.into_boxed_str())].into(),
+                error,
+            });
+        }
+        Ok(set)
+    }
+}
+
+impl<V: ConstructibleJsonValue> IntoJson<V> for ParticipantRoleSet {
+    fn into_json(self) -> V {
+        let mut object = V::Object::with_capacity(self.len());
+        for role in self.iter() {
+            object.insert(role.to_string().into(), V::bool(true));
+        }
+        V::object(object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn color_display_hex() {
+        assert_eq!(
+            Color::Css(Css3Color::CornflowerBlue).as_display_hex(),
+            "#6495ed"
+        );
+        let rgb = Rgb {
+            red: 0xAB,
+            green: 0x01,
+            blue: 0xFF,
+        };
+        assert_eq!(Color::Rgb(rgb).as_display_hex(), "#ab01ff");
+    }
+
+    #[test]
+    fn color_rgb_round_trip() {
+        let color = Color::from_rgb((0x12, 0x34, 0x56));
+        assert_eq!(color.to_string(), "#123456");
+        assert_eq!(<(u8, u8, u8)>::from(color.to_rgb()), (0x12, 0x34, 0x56));
+        assert_eq!(
+            Color::Css(Css3Color::Tomato).to_rgb(),
+            Rgb::from((0xFF, 0x63, 0x47))
+        );
+    }
+
+    #[test]
+    fn color_nearest_css_name() {
+        assert_eq!(
+            Color::Css(Css3Color::Cyan).nearest_css_name(),
+            Css3Color::Cyan
+        );
+        assert_eq!(
+            Color::from_rgb((0xFF, 0x00, 0x00)).nearest_css_name(),
+            Css3Color::Red
+        );
+        assert_eq!(
+            Color::from_rgb((0x64, 0x94, 0xEC)).nearest_css_name(),
+            Css3Color::CornflowerBlue
+        );
+    }
+
+    #[test]
+    fn color_contrast_text_color() {
+        let black = Color::Css(Css3Color::Black);
+        let white = Color::Css(Css3Color::White);
+        let rgb = |red, green, blue| Color::Rgb(Rgb { red, green, blue });
+
+        assert_eq!(white.contrast_text_color(), black);
+        assert_eq!(black.contrast_text_color(), white);
+        assert_eq!(Color::Css(Css3Color::Yellow).contrast_text_color(), black);
+        assert_eq!(Color::Css(Css3Color::Navy).contrast_text_color(), white);
+        // the greys on either side of the luminance threshold of about 0.179
+        assert_eq!(rgb(0x75, 0x75, 0x75).contrast_text_color(), white);
+        assert_eq!(rgb(0x76, 0x76, 0x76).contrast_text_color(), black);
+    }
+
+    #[test]
+    fn color_assign_is_deterministic() {
+        let a = Color::assign("a@example.com", []);
+        assert_eq!(a, Color::assign("a@example.com", []));
+        assert!(matches!(a, Color::Css(css) if ASSIGNED_COLORS.contains(&css)));
+
+        // the smallest keyword determines the color, regardless of order or uid
+        assert_eq!(
+            Color::assign("a@example.com", ["work", "planning"]),
+            Color::assign("b@example.com", ["planning", "work"]),
+        );
+        assert_eq!(
+            Color::assign("a@example.com", ["planning"]),
+            Color::assign("planning", []),
+        );
+
+        let assigned: std::collections::HashSet<_> = (0..100)
+            .map(|i| Color::assign(&format!("event-{i}"), []))
+            .collect();
+        assert!(assigned.len() > 1);
+    }
+
+    #[test]
+    fn participant_role_set_operations() {
+        let chair: ParticipantRoleSet = [ParticipantRole::Chair, ParticipantRole::Attendee]
+            .into_iter()
+            .collect();
+        let mut owner: ParticipantRoleSet = [ParticipantRole::Owner].into_iter().collect();
+        assert!(owner.insert(Token::Unknown("x-vip".into())));
+        assert!(!owner.insert(Token::Known(ParticipantRole::Owner)));
+
+        let both = chair.union(&owner);
+        assert_eq!(both.len(), 4);
+        assert!(both.contains(ParticipantRole::Chair));
+        assert!(both.contains_token(&Token::Unknown("x-vip".into())));
+        assert!(chair.is_subset(&both));
+        assert!(chair.intersection(&owner).is_empty());
+        assert_eq!(both.difference(&chair), owner);
+        assert_eq!(
+            both.iter().map(|role| role.to_string()).collect::<Vec<_>>(),
+            ["owner", "attendee", "chair", "x-vip"]
+        );
+
+        let mut both = both;
+        assert!(both.remove(&Token::Known(ParticipantRole::Attendee)));
+        assert!(!both.remove(&Token::Known(ParticipantRole::Attendee)));
+        assert!(!both.contains(ParticipantRole::Attendee));
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn participant_role_set_json() {
+        use serde_json::{Value, json};
+
+        let roles = ParticipantRoleSet::try_from_json(json!({ "Owner": true, "x-vip": true }))
+            .unwrap();
+        assert!(roles.contains(ParticipantRole::Owner));
+        let json: Value = roles.into_json();
+        assert_eq!(json, json!({ "owner": true, "x-vip": true }));
+
+        let error = ParticipantRoleSet::try_from_json(json!({ "chair": false })).unwrap_err();
+        assert_eq!(error.path().len(), 1);
+        assert!(ParticipantRoleSet::try_from_json(json!(["chair"])).is_err());
+    }
+}
@@ -0,0 +1,445 @@
+//! Detection of sub-objects repeated across the entries of a group.
+//!
+//! Groups exported from a calendar often repeat the same sub-objects in many of their entries,
+//! such as the same [`Location`](super::object::Location) on every event of a conference.
+//! [`Group::sharing_stats`] counts the alerts, links, and locations of the entries of a group
+//! which are identical to one in another entry (or the same entry), which estimates how much
+//! sharing them would save.
+//!
+//! With the `shared-refs` feature, [`Group::into_json_with_refs`] also emits each repeated
+//! sub-object only once, in the [`SHARED`] vendor property of the group, and replaces its
+//! occurrences with `{"$ref": "#/eikopf.github.io:shared/<index>"}` objects in the style of JSON
+//! Reference. Since no other implementation understands these references, [`expand_refs`] must be
+//! applied to the serialized group before parsing it. This extension is experimental, and its
+//! encoding may change between releases.
+
+#[cfg(feature = "shared-refs")]
+use std::borrow::Borrow;
+
+#[cfg(feature = "shared-refs")]
+use thiserror::Error;
+
+use crate::{
+    json::{ConstructibleJsonValue, DestructibleJsonValue, IntoJson, JsonArray, JsonObject},
+    model::object::Group,
+};
+
+/// The vendor property holding the sub-objects shared by [`Group::into_json_with_refs`].
+#[cfg(feature = "shared-refs")]
+pub const SHARED: &str = "eikopf.github.io:shared";
+
+/// The properties of group entries whose sub-objects are considered for sharing.
+const PROPERTIES: [&str; 3] = ["alerts", "links", "locations"];
+
+/// Sharing statistics for one kind of sub-object.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KindStats {
+    /// The number of sub-objects of this kind in the entries.
+    pub total: usize,
+    /// The number of distinct sub-objects of this kind.
+    pub distinct: usize,
+    /// The number of distinct sub-objects which occur more than once.
+    pub shared: usize,
+    /// The largest number of occurrences of a single sub-object.
+    pub max_occurrences: usize,
+}
+
+impl KindStats {
+    /// Returns the number of sub-objects which are identical to an earlier one, and so could be
+    /// replaced by a reference.
+    pub fn duplicates(&self) -> usize {
+        self.total - self.distinct
+    }
+}
+
+/// Sharing statistics for the sub-objects of the entries of a group.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SharingStats {
+    /// The number of entries in the group.
+    pub entries: usize,
+    /// Statistics for the values of the `alerts` properties of the entries.
+    pub alerts: KindStats,
+    /// Statistics for the values of the `links` properties of the entries.
+    pub links: KindStats,
+    /// Statistics for the values of the `locations` properties of the entries.
+    pub locations: KindStats,
+}
+
+impl SharingStats {
+    /// Returns the total number of sub-objects which could be replaced by a reference.
+    pub fn duplicates(&self) -> usize {
+        self.alerts.duplicates() + self.links.duplicates() + self.locations.duplicates()
+    }
+}
+
+impl<V> Group<V>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone + PartialEq,
+    V::Object: Clone,
+{
+    /// Returns the sharing statistics for the alerts, links, and locations of the entries of this
+    /// group. Sub-objects are compared by their serialized values, so their ids are ignored.
+    pub fn sharing_stats(&self) -> SharingStats {
+        let json: V = self.clone().into_json();
+        let mut stats = SharingStats {
+            entries: self.entries().len(),
+            ..SharingStats::default()
+        };
+
+        for (property, kind) in
+            PROPERTIES
+                .into_iter()
+                .zip([&mut stats.alerts, &mut stats.links, &mut stats.locations])
+        {
+            let mut pool = Pool::default();
+            for object in sub_objects(&json, property) {
+                pool.insert(object);
+            }
+            *kind = pool.stats();
+        }
+
+        stats
+    }
+
+    /// Serializes this group, replacing each alert, link, and location which occurs more than
+    /// once in its entries with a reference to a single copy in the [`SHARED`] vendor property.
+    /// If nothing is repeated, this is the same as [`IntoJson::into_json`].
+    ///
+    /// The output must be passed through [`expand_refs`] before it can be parsed.
+    #[cfg(feature = "shared-refs")]
+    pub fn into_json_with_refs(self) -> V {
+        let json = self.into_json();
+
+        let mut pool = Pool::default();
+        for property in PROPERTIES {
+            for object in sub_objects(&json, property) {
+                pool.insert(object);
+            }
+        }
+
+        let mut shared = V::Array::new();
+        let refs: Vec<Option<V>> = pool
+            .objects
+            .iter()
+            .map(|(object, occurrences)| {
+                (*occurrences > 1).then(|| {
+                    let reference = reference(shared.len());
+                    shared.push(object.clone());
+                    reference
+                })
+            })
+            .collect();
+
+        if shared.is_empty() {
+            return json;
+        }
+
+        let group = map_members(json, |key, value| match key {
+            "entries" => map_elements(value, |entry| {
+                map_members(entry, |key, value| {
+                    if !PROPERTIES.contains(&key) {
+                        return value;
+                    }
+
+                    map_members(value, |_, object| {
+                        let index = pool.index_of(&object).expect("all sub-objects are pooled");
+                        refs[index].clone().unwrap_or(object)
+                    })
+                })
+            }),
+            _ => value,
+        });
+
+        let mut group = group
+            .try_into_object()
+            .expect("groups serialize to JSON objects");
+        group.insert(SHARED.into(), V::array(shared));
+        V::object(group)
+    }
+}
+
+/// An error produced by [`expand_refs`].
+#[cfg(feature = "shared-refs")]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ExpandRefsError {
+    /// The [`SHARED`] vendor property was not an array.
+    #[error("the {SHARED} property is not an array")]
+    InvalidShared,
+    /// A `$ref` member was not a string referring to an element of the [`SHARED`] property.
+    #[error("the reference {0:?} does not refer to a shared object")]
+    InvalidRef(String),
+}
+
+/// Replaces the references in a group serialized by [`Group::into_json_with_refs`] with the
+/// sub-objects they refer to, and removes the [`SHARED`] vendor property. Values other than
+/// objects are returned unchanged.
+#[cfg(feature = "shared-refs")]
+pub fn expand_refs<V>(group: V) -> Result<V, ExpandRefsError>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue + Clone,
+{
+    if !group.is_object() {
+        return Ok(group);
+    }
+
+    let mut shared = Vec::new();
+    let mut members = V::Object::new();
+    for (key, value) in group.try_into_object().expect("checked above").into_iter() {
+        if key.borrow() == SHARED {
+            let array = value
+                .try_into_array()
+                .map_err(|_| ExpandRefsError::InvalidShared)?;
+            shared.extend(array.into_iter());
+        } else {
+            members.insert(key, value);
+        }
+    }
+
+    let mut error = None;
+    let group = map_members(V::object(members), |key, value| match key {
+        "entries" => map_elements(value, |entry| {
+            map_members(entry, |key, value| {
+                if !PROPERTIES.contains(&key) {
+                    return value;
+                }
+
+                map_members(value, |_, object| match resolve(&object, &shared) {
+                    Ok(Some(target)) => target.clone(),
+                    Ok(None) => object,
+                    Err(e) => {
+                        error.get_or_insert(e);
+                        object
+                    }
+                })
+            })
+        }),
+        _ => value,
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(group),
+    }
+}
+
+/// The distinct values in a sequence, with their numbers of occurrences in order of their first
+/// occurrence.
+struct Pool<V> {
+    objects: Vec<(V, usize)>,
+}
+
+impl<V> Default for Pool<V> {
+    fn default() -> Self {
+        Self {
+            objects: Vec::new(),
+        }
+    }
+}
+
+impl<V: PartialEq + Clone> Pool<V> {
+    fn index_of(&self, value: &V) -> Option<usize> {
+        self.objects.iter().position(|(object, _)| object == value)
+    }
+
+    fn insert(&mut self, value: &V) {
+        match self.index_of(value) {
+            Some(index) => self.objects[index].1 += 1,
+            None => self.objects.push((value.clone(), 1)),
+        }
+    }
+
+    fn stats(&self) -> KindStats {
+        let occurrences = || self.objects.iter().map(|(_, occurrences)| *occurrences);
+        KindStats {
+            total: occurrences().sum(),
+            distinct: self.objects.len(),
+            shared: occurrences().filter(|&n| n > 1).count(),
+            max_occurrences: occurrences().max().unwrap_or(0),
+        }
+    }
+}
+
+/// Returns the values of the `property` members of the entries of the serialized group `group`.
+fn sub_objects<'a, V: DestructibleJsonValue>(
+    group: &'a V,
+    property: &'a str,
+) -> impl Iterator<Item = &'a V> {
+    let entries = group
+        .try_as_object()
+        .ok()
+        .and_then(|group| group.get("entries"))
+        .and_then(|entries| entries.try_as_array().ok());
+
+    entries
+        .into_iter()
+        .flat_map(|entries| entries.iter())
+        .filter_map(move |entry| entry.try_as_object().ok()?.get(property))
+        .filter_map(|map| map.try_as_object().ok())
+        .flat_map(|map| map.values())
+}
+
+/// Returns a reference to the element of the [`SHARED`] property at `index`.
+#[cfg(feature = "shared-refs")]
+fn reference<V: ConstructibleJsonValue>(index: usize) -> V {
+    let mut object = V::Object::with_capacity(1);
+    object.insert("$ref".into(), V::string(format!("#/{SHARED}/{index}")));
+    V::object(object)
+}
+
+/// Returns the shared object referred to by `object`, or `None` if it is not a reference.
+#[cfg(feature = "shared-refs")]
+fn resolve<'a, V: DestructibleJsonValue>(
+    object: &V,
+    shared: &'a [V],
+) -> Result<Option<&'a V>, ExpandRefsError> {
+    let Some(target) = object
+        .try_as_object()
+        .ok()
+        .filter(|object| object.len() == 1)
+        .and_then(|object| object.get("$ref"))
+    else {
+        return Ok(None);
+    };
+
+    let target = target
+        .try_as_string()
+        .map_err(|_| ExpandRefsError::InvalidRef(String::new()))?
+        .as_ref();
+    target
+        .strip_prefix("#/")
+        .and_then(|pointer| pointer.strip_prefix(SHARED))
+        .and_then(|pointer| pointer.strip_prefix('/'))
+        .and_then(|index| index.parse::<usize>().ok())
+        .and_then(|index| shared.get(index))
+        .map(Some)
+        .ok_or_else(|| ExpandRefsError::InvalidRef(target.to_string()))
+}
+
+/// Applies `f` to the members of `value` if it is an object.
+#[cfg(feature = "shared-refs")]
+fn map_members<V>(value: V, mut f: impl FnMut(&str, V) -> V) -> V
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue,
+{
+    if !value.is_object() {
+        return value;
+    }
+
+    let object = value.try_into_object().expect("checked above");
+    let mut result = V::Object::with_capacity(object.len());
+    for (key, value) in object.into_iter() {
+        let value = f(key.borrow(), value);
+        result.insert(key, value);
+    }
+    V::object(result)
+}
+
+/// Applies `f` to the elements of `value` if it is an array.
+#[cfg(feature = "shared-refs")]
+fn map_elements<V>(value: V, f: impl FnMut(V) -> V) -> V
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue,
+{
+    if !value.is_array() {
+        return value;
+    }
+
+    let array = value.try_into_array().expect("checked above");
+    let mut result = V::Array::with_capacity(array.len());
+    for elem in array.into_iter().map(f) {
+        result.push(elem);
+    }
+    V::array(result)
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::json::TryFromJson;
+
+    fn group() -> Group<Value> {
+        let location = json!({ "@type": "Location", "name": "Main hall" });
+        let alert = json!({
+            "@type": "Alert",
+            "trigger": { "@type": "OffsetTrigger", "offset": "-PT15M" }
+        });
+        let event = |uid: &str, location: &Value| {
+            json!({
+                "@type": "Event",
+                "uid": uid,
+                "start": "2024-05-01T09:00:00",
+                "locations": { "l1": location },
+                "alerts": { "a1": alert }
+            })
+        };
+
+        Group::try_from_json(json!({
+            "@type": "Group",
+            "uid": "conference",
+            "entries": [
+                event("talk-1", &location),
+                event("talk-2", &location),
+                event("talk-3", &json!({ "@type": "Location", "name": "Room B" })),
+                { "@type": "Task", "uid": "prepare" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn stats() {
+        let stats = group().sharing_stats();
+        assert_eq!(stats.entries, 4);
+        assert_eq!(
+            stats.locations,
+            KindStats {
+                total: 3,
+                distinct: 2,
+                shared: 1,
+                max_occurrences: 2,
+            }
+        );
+        assert_eq!(stats.alerts.max_occurrences, 3);
+        assert_eq!(stats.alerts.duplicates(), 2);
+        assert_eq!(stats.links, KindStats::default());
+        assert_eq!(stats.duplicates(), 3);
+    }
+
+    #[cfg(feature = "shared-refs")]
+    #[test]
+    fn refs_round_trip() {
+        let group = group();
+        let json = group.clone().into_json_with_refs();
+
+        assert_eq!(json[SHARED].as_array().unwrap().len(), 2);
+        assert_eq!(
+            json["entries"][1]["locations"]["l1"],
+            json!({ "$ref": "#/eikopf.github.io:shared/1" })
+        );
+        assert_eq!(json["entries"][2]["locations"]["l1"]["name"], "Room B");
+        assert!(Group::<Value>::try_from_json(json.clone()).is_err());
+
+        let expanded = expand_refs(json).unwrap();
+        assert_eq!(expanded, group.clone().into_json());
+        assert_eq!(Group::try_from_json(expanded).unwrap(), group);
+    }
+
+    #[cfg(feature = "shared-refs")]
+    #[test]
+    fn expand_invalid_refs() {
+        let mut json = group().into_json_with_refs();
+        json["entries"][0]["alerts"]["a1"] = json!({ "$ref": "#/eikopf.github.io:shared/9" });
+        assert_eq!(
+            expand_refs(json),
+            Err(ExpandRefsError::InvalidRef(String::from(
+                "#/eikopf.github.io:shared/9"
+            )))
+        );
+
+        let mut json = group().into_json_with_refs();
+        json[SHARED] = json!({});
+        assert_eq!(expand_refs(json), Err(ExpandRefsError::InvalidShared));
+    }
+}
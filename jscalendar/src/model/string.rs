@@ -6,7 +6,7 @@ pub use calendar_types::string::{
     InvalidUidError, InvalidUriError, LanguageTag, LanguageTagParseError, Uid, UidBuf, Uri, UriBuf,
 };
 use dizzy::DstNewtype;
-use rfc5545_types::string::ParamText;
+use rfc5545_types::{string::ParamText, value::Geo};
 use thiserror::Error;
 
 use crate::json::{DestructibleJsonValue, TryFromJson, TypeErrorOr};
@@ -66,6 +66,15 @@ pub struct StringError<E> {
     pub(crate) error: E,
 }
 
+impl<E> StringError<E> {
+    pub(crate) fn new(input: &str, error: E) -> Self {
+        Self {
+            input: input.into(),
+            error,
+        }
+    }
+}
+
 impl<E: std::fmt::Display> std::fmt::Display for StringError<E> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "invalid value {:?}: {}", self.input, self.error)
@@ -666,6 +675,73 @@ impl CalAddress {
     }
 }
 
+/// An error indicating that a string is not a valid HTTPS URL.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum InvalidHttpsUrlError {
+    /// The string was not a valid URI.
+    #[error("invalid URI: {0}")]
+    Uri(#[from] InvalidUriError),
+    /// The URI had a scheme other than `https`.
+    #[error("expected https: scheme, found {scheme}:")]
+    NotHttps {
+        /// The scheme of the URI.
+        scheme: Box<str>,
+    },
+}
+
+/// A URI with the `https` scheme (RFC 9110 §4.2.2).
+///
+/// The scheme is matched case-insensitively, as required by RFC 3986 §3.1.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, DstNewtype)]
+#[dizzy(invariant = HttpsUrl::str_is_https_url, error = InvalidHttpsUrlError)]
+#[dizzy(constructor = #[doc = "Returns `value` as an [`HttpsUrl`], if it is one."] pub new)]
+#[dizzy(getter = #[doc = "Returns the URL as a string slice."] pub const as_str)]
+#[dizzy(derive(Debug, CloneBoxed, IntoBoxed))]
+#[dizzy(owned = #[doc = "An owned [`HttpsUrl`]."] pub HttpsUrlBuf(String))]
+#[dizzy(derive_owned(Debug, IntoBoxed))]
+#[repr(transparent)]
+pub struct HttpsUrl(str);
+
+impl<V: DestructibleJsonValue> TryFromJson<V> for Box<HttpsUrl> {
+    type Error = TypeErrorOr<StringError<InvalidHttpsUrlError>>;
+
+    fn try_from_json(value: V) -> Result<Self, Self::Error> {
+        let input = value.try_into_string()?;
+
+        HttpsUrl::new(input.as_ref())
+            .map(Into::into)
+            .map_err(|error| StringError {
+                input: String::from(input.as_ref()).into(),
+                error,
+            })
+            .map_err(TypeErrorOr::Other)
+    }
+}
+
+impl std::fmt::Display for HttpsUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl HttpsUrl {
+    fn str_is_https_url(s: &str) -> Result<(), InvalidHttpsUrlError> {
+        let scheme = Uri::new(s)?.scheme();
+        if !scheme.eq_ignore_ascii_case("https") {
+            return Err(InvalidHttpsUrlError::NotHttps {
+                scheme: scheme.into(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns this URL as a [`Uri`].
+    #[inline(always)]
+    pub fn as_uri(&self) -> &Uri {
+        Uri::new(self.as_str()).expect("an HttpsUrl must be a valid Uri")
+    }
+}
+
 /// An error indicating that a string is not a valid email address.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
 pub enum InvalidEmailAddrError {
@@ -775,12 +851,18 @@ pub enum InvalidGeoUriError {
     /// No longitude value was found after the latitude.
     #[error("missing longitude value")]
     MissingLongitude,
-    /// The latitude could not be parsed as a number.
+    /// The latitude was not a finite number.
     #[error("invalid latitude value")]
     InvalidLatitude,
-    /// The longitude could not be parsed as a number.
+    /// The longitude was not a finite number.
     #[error("invalid longitude value")]
     InvalidLongitude,
+    /// The altitude was not a finite number, or was followed by further components.
+    #[error("invalid altitude value")]
+    InvalidAltitude,
+    /// The `u` parameter was not a non-negative number.
+    #[error("invalid uncertainty value")]
+    InvalidUncertainty,
 }
 
 /// A geographic URI (RFC 5870).
@@ -819,6 +901,10 @@ impl std::fmt::Display for GeoUri {
 
 impl GeoUri {
     fn str_is_geo_uri(s: &str) -> Result<(), InvalidGeoUriError> {
+        Self::parse(s).map(|_| ())
+    }
+
+    fn parse(s: &str) -> Result<GeoCoordinates, InvalidGeoUriError> {
         if s.is_empty() {
             return Err(InvalidGeoUriError::EmptyString);
         }
@@ -828,7 +914,8 @@ impl GeoUri {
             .ok_or(InvalidGeoUriError::NotGeoScheme)?;
 
         // Split off parameters (after `;`)
-        let coords = body.split(';').next().unwrap_or(body);
+        let mut params = body.split(';');
+        let coords = params.next().unwrap_or(body);
 
         let mut parts = coords.split(',');
 
@@ -836,23 +923,143 @@ impl GeoUri {
         if lat_str.is_empty() {
             return Err(InvalidGeoUriError::MissingLatitude);
         }
-        lat_str
-            .parse::<f64>()
-            .map_err(|_| InvalidGeoUriError::InvalidLatitude)?;
+        let latitude = parse_finite(lat_str).ok_or(InvalidGeoUriError::InvalidLatitude)?;
 
         let lon_str = parts.next().ok_or(InvalidGeoUriError::MissingLongitude)?;
         if lon_str.is_empty() {
             return Err(InvalidGeoUriError::MissingLongitude);
         }
-        lon_str
-            .parse::<f64>()
-            .map_err(|_| InvalidGeoUriError::InvalidLongitude)?;
+        let longitude = parse_finite(lon_str).ok_or(InvalidGeoUriError::InvalidLongitude)?;
 
-        // Altitude is optional, we don't validate it strictly
+        let altitude = match (parts.next(), parts.next()) {
+            (None, _) => None,
+            (Some(alt_str), None) => {
+                Some(parse_finite(alt_str).ok_or(InvalidGeoUriError::InvalidAltitude)?)
+            }
+            (Some(_), Some(_)) => return Err(InvalidGeoUriError::InvalidAltitude),
+        };
+
+        let mut coordinates = GeoCoordinates {
+            latitude,
+            longitude,
+            altitude,
+            crs: None,
+            uncertainty: None,
+        };
+
+        // parameter names are case-insensitive (RFC 5870 §3.3)
+        for param in params {
+            let (name, value) = param.split_once('=').unwrap_or((param, ""));
+            if name.eq_ignore_ascii_case("crs") {
+                coordinates.crs = Some(value.into());
+            } else if name.eq_ignore_ascii_case("u") {
+                let uncertainty = parse_finite(value)
+                    .filter(|u| *u >= 0.0)
+                    .ok_or(InvalidGeoUriError::InvalidUncertainty)?;
+                coordinates.uncertainty = Some(uncertainty);
+            }
+        }
+
+        Ok(coordinates)
+    }
+
+    /// Returns the coordinates of this URI, along with its `crs` and `u` parameters. Any other
+    /// parameters are ignored.
+    pub fn coordinates(&self) -> GeoCoordinates {
+        Self::parse(self.as_str()).expect("a GeoUri must be a valid geo URI")
+    }
+
+    /// Returns the geo URI for the given latitude and longitude, or an error if either of them is
+    /// not finite.
+    pub fn from_lat_lon(latitude: f64, longitude: f64) -> Result<Box<Self>, InvalidGeoUriError> {
+        GeoCoordinates::new(latitude, longitude).to_geo_uri()
+    }
+
+    /// Returns the latitude and longitude of this URI as a GEO property value (RFC 5545 §3.8.1.6).
+    pub fn to_geo(&self) -> Geo {
+        self.coordinates().into()
+    }
+
+    /// Returns the geo URI for the given GEO property value (RFC 5545 §3.8.1.6), or an error if
+    /// either of its components is not finite.
+    pub fn from_geo(geo: Geo) -> Result<Box<Self>, InvalidGeoUriError> {
+        GeoCoordinates::from(geo).to_geo_uri()
+    }
+}
+
+/// Parses a decimal number, returning `None` if it is invalid or not finite.
+fn parse_finite(s: &str) -> Option<f64> {
+    s.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+/// The components of a [`GeoUri`] (RFC 5870 §3.3).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoCoordinates {
+    /// The latitude in decimal degrees.
+    pub latitude: f64,
+    /// The longitude in decimal degrees.
+    pub longitude: f64,
+    /// The altitude in meters, if any.
+    pub altitude: Option<f64>,
+    /// The coordinate reference system, if given by the `crs` parameter. If this is `None`, the
+    /// coordinates are in the default `wgs84` system.
+    pub crs: Option<Box<str>>,
+    /// The uncertainty of the location in meters, if given by the `u` parameter.
+    pub uncertainty: Option<f64>,
+}
+
+impl GeoCoordinates {
+    /// Returns the coordinates with the given latitude and longitude, and no altitude or
+    /// parameters.
+    pub const fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            latitude,
+            longitude,
+            altitude: None,
+            crs: None,
+            uncertainty: None,
+        }
+    }
+
+    /// Returns the geo URI for these coordinates, or an error if any of the numbers are not
+    /// finite or the uncertainty is negative.
+    pub fn to_geo_uri(&self) -> Result<Box<GeoUri>, InvalidGeoUriError> {
+        GeoUri::new(&self.to_string()).map(Into::into)
+    }
+}
+
+impl std::fmt::Display for GeoCoordinates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "geo:{},{}", self.latitude, self.longitude)?;
+        if let Some(altitude) = self.altitude {
+            write!(f, ",{altitude}")?;
+        }
+        // the crs parameter must come first (RFC 5870 §3.3)
+        if let Some(crs) = &self.crs {
+            write!(f, ";crs={crs}")?;
+        }
+        if let Some(uncertainty) = self.uncertainty {
+            write!(f, ";u={uncertainty}")?;
+        }
         Ok(())
     }
 }
 
+impl From<Geo> for GeoCoordinates {
+    fn from(geo: Geo) -> Self {
+        Self::new(geo.lat, geo.lon)
+    }
+}
+
+impl From<GeoCoordinates> for Geo {
+    fn from(coordinates: GeoCoordinates) -> Self {
+        Geo {
+            lat: coordinates.latitude,
+            lon: coordinates.longitude,
+        }
+    }
+}
+
 /// An error indicating that a string is not a valid Content-ID.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
 pub enum InvalidContentIdError {
@@ -979,29 +1186,77 @@ impl MediaType {
         Ok(())
     }
 
-    /// Returns the type part (before `/`).
+    /// Returns the `type/subtype` part, without any parameters.
+    fn essence(&self) -> &str {
+        let essence = self.as_str().split(';').next().unwrap();
+        essence.trim_end()
+    }
+
+    /// Returns the type (before `/`), such as `image` in `image/png`.
     #[inline(always)]
-    pub fn type_part(&self) -> &str {
-        self.as_str()
-            .split(';')
-            .next()
-            .unwrap()
+    pub fn type_(&self) -> &str {
+        self.essence()
             .split_once('/')
             .expect("a MediaType must contain /")
             .0
     }
 
+    /// Returns the type part (before `/`); an alias for [`MediaType::type_`].
+    #[inline(always)]
+    pub fn type_part(&self) -> &str {
+        self.type_()
+    }
+
     /// Returns the subtype part (after `/`, before parameters).
     #[inline(always)]
     pub fn subtype(&self) -> &str {
-        self.as_str()
-            .split(';')
-            .next()
-            .unwrap()
+        self.essence()
             .split_once('/')
             .expect("a MediaType must contain /")
             .1
     }
+
+    /// Iterates over the `(name, value)` pairs of the parameters, in order. Whitespace around
+    /// each parameter is ignored, and quoted values are returned without their quotes.
+    pub fn parameters(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.as_str().split(';').skip(1).filter_map(|param| {
+            let (name, value) = param.trim().split_once('=')?;
+            let value = value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+                .unwrap_or(value);
+            Some((name, value))
+        })
+    }
+
+    /// Returns the value of the first parameter with the given name, which is matched
+    /// case-insensitively.
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the value of the `charset` parameter, if any.
+    pub fn charset(&self) -> Option<&str> {
+        self.parameter("charset")
+    }
+
+    /// Returns `true` iff this media type matches `pattern`, which is a `type/subtype` pair where
+    /// either part may be the wildcard `*`, as in `image/*` or `*/*`.
+    ///
+    /// Types and subtypes are compared case-insensitively, and parameters are ignored on both
+    /// sides. A pattern without a `/` matches nothing.
+    pub fn matches(&self, pattern: &str) -> bool {
+        let pattern = pattern.split(';').next().unwrap().trim();
+        let Some((type_, subtype)) = pattern.split_once('/') else {
+            return false;
+        };
+
+        let part_matches =
+            |pattern: &str, part: &str| pattern == "*" || pattern.eq_ignore_ascii_case(part);
+        part_matches(type_, self.type_()) && part_matches(subtype, self.subtype())
+    }
 }
 
 /// A string slice where every character is ASCII alphanumeric.
@@ -1080,6 +1335,12 @@ impl<V: ConstructibleJsonValue> IntoJson<V> for Box<CalAddress> {
     }
 }
 
+impl<V: ConstructibleJsonValue> IntoJson<V> for Box<HttpsUrl> {
+    fn into_json(self) -> V {
+        V::string(self.as_str().to_owned())
+    }
+}
+
 impl<V: ConstructibleJsonValue> IntoJson<V> for Box<EmailAddr> {
     fn into_json(self) -> V {
         V::string(self.as_str().to_owned())
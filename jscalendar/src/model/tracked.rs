@@ -0,0 +1,323 @@
+//! Tracking of the changes made to an object, for partial updates.
+//!
+//! A JMAP client which edits an event need not send the whole event back to the server: a
+//! `CalendarEvent/set` update is a [`PatchObject`] which sets only the members which changed
+//! (RFC 8620 §5.3). A [`Tracked`] object records the paths of the members changed through it,
+//! and [`Tracked::update`] returns a patch which sets each of them to its current value, or
+//! removes it with `null`.
+//!
+//! Changes are made either through [`Tracked::set_property`] and [`Tracked::remove_property`],
+//! which record the property that they change, or through [`Tracked::edit`], which gives access
+//! to the typed object and records the members whose serialization differs afterwards. The
+//! latter compares objects member by member, so that changing the status of one participant is
+//! recorded as a path such as `participants/p1/participationStatus`, and adding a keyword as
+//! `keywords/Work`.
+//!
+//! ```
+//! # #[cfg(feature = "serde_json")]
+//! # {
+//! use jscalendar::{
+//!     json::{IntoJson, TryFromJson},
+//!     model::{object::Event, props::Property, tracked::Tracked},
+//! };
+//! use serde_json::{Value, json};
+//!
+//! let event = Event::<Value>::try_from_json(json!({
+//!     "uid": "a8df6573-0474-496d-8496-033ad45d7fea",
+//!     "start": "2020-01-15T13:00:00",
+//!     "duration": "PT1H"
+//! }))
+//! .unwrap();
+//! let mut event = Tracked::new(event);
+//! event.set_property(Property::Title, json!("Team meeting")).unwrap();
+//! event.edit(|event| event.remove_duration());
+//!
+//! let update: Value = event.update().into_json();
+//! assert_eq!(update, json!({ "title": "Team meeting", "duration": null }));
+//! # }
+//! ```
+
+use std::collections::BTreeSet;
+
+use crate::{
+    json::{ConstructibleJsonValue, DestructibleJsonValue, IntoJson, JsonObject},
+    model::{
+        object::{Event, Group, PatchObject, Task, escape_segment},
+        props::Property,
+        reflect::SetPropertyError,
+        string::ImplicitJsonPointer,
+    },
+};
+
+/// The path of a member, as its unescaped segments.
+type Path = Vec<Box<str>>;
+
+/// An object which records the paths of the members changed through it.
+///
+/// A `Tracked` dereferences to the object for reading, but not for writing, so that every change
+/// is recorded.
+#[derive(Debug, Clone)]
+pub struct Tracked<T> {
+    inner: T,
+    changed: BTreeSet<Path>,
+}
+
+impl<T> Tracked<T> {
+    /// Tracks the changes made to `inner` from now on.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            changed: BTreeSet::new(),
+        }
+    }
+
+    /// Returns the object.
+    pub fn get(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the object, discarding the recorded changes.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns whether any change has been recorded.
+    pub fn is_changed(&self) -> bool {
+        !self.changed.is_empty()
+    }
+
+    /// Returns the paths of the recorded changes as implicit JSON pointers, in order, omitting
+    /// those within another changed member.
+    pub fn changed_paths(&self) -> Vec<Box<ImplicitJsonPointer>> {
+        self.outermost().map(|path| pointer(path)).collect()
+    }
+
+    /// Forgets the recorded changes, such as once an update has been sent to the server.
+    pub fn clear(&mut self) {
+        self.changed.clear();
+    }
+
+    /// Returns the recorded paths which are not within another recorded path.
+    fn outermost(&self) -> impl Iterator<Item = &Path> {
+        // the paths within a path follow it directly in the order of the set
+        let mut outer: Option<&Path> = None;
+        self.changed.iter().filter(move |&path| {
+            if outer.is_some_and(|outer| path.starts_with(outer)) {
+                return false;
+            }
+            outer = Some(path);
+            true
+        })
+    }
+}
+
+impl<T> std::ops::Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+/// Implements the change tracking of an object type.
+macro_rules! impl_tracked {
+    ($ty:ident, $kind:literal) => {
+        impl<V> Tracked<$ty<V>>
+        where
+            V: DestructibleJsonValue + ConstructibleJsonValue + Clone + PartialEq,
+            V::Object: Clone,
+        {
+            #[doc = concat!("Changes the ", $kind, " with `f`, recording the members whose")]
+            /// serialization differs afterwards.
+            pub fn edit<R>(&mut self, f: impl FnOnce(&mut $ty<V>) -> R) -> R {
+                let before = self.inner.clone().into_json();
+                let result = f(&mut self.inner);
+                let after = self.inner.clone().into_json();
+                diff(&before, &after, &mut Vec::new(), &mut self.changed);
+                result
+            }
+
+            /// Returns a patch which sets each changed member to its current value, or to `null`
+            /// if it has been removed.
+            pub fn update(&self) -> PatchObject<V> {
+                let current = self.inner.clone().into_json();
+                self.outermost()
+                    .map(|path| {
+                        let value = lookup(&current, path).cloned().unwrap_or_else(V::null);
+                        (pointer(path), value)
+                    })
+                    .collect()
+            }
+        }
+    };
+}
+
+impl_tracked!(Event, "event");
+impl_tracked!(Task, "task");
+impl_tracked!(Group, "group");
+
+impl<V> Tracked<Event<V>>
+where
+    V: DestructibleJsonValue + ConstructibleJsonValue,
+{
+    /// Sets `property` to `value` as [`Event::set_property`] does, recording the change.
+    pub fn set_property(&mut self, property: Property, value: V) -> Result<(), SetPropertyError> {
+        self.inner.set_property(property, value)?;
+        self.changed.insert(vec![property.as_str().into()]);
+        Ok(())
+    }
+
+    /// Removes `property` as [`Event::remove_property`] does, recording the change if the
+    /// property was present.
+    pub fn remove_property(&mut self, property: Property) -> Result<Option<V>, SetPropertyError> {
+        let removed = self.inner.remove_property(property)?;
+        if removed.is_some() {
+            self.changed.insert(vec![property.as_str().into()]);
+        }
+        Ok(removed)
+    }
+}
+
+/// Records the path of each member in which `after` differs from `before`, comparing objects
+/// member by member.
+fn diff<V>(before: &V, after: &V, path: &mut Path, changed: &mut BTreeSet<Path>)
+where
+    V: DestructibleJsonValue + PartialEq,
+{
+    if before == after {
+        return;
+    }
+    let (Ok(before), Ok(after)) = (before.try_as_object(), after.try_as_object()) else {
+        changed.insert(path.clone());
+        return;
+    };
+
+    for (key, value) in after.iter() {
+        let key: &str = <V::Object as JsonObject>::key_as_str(key);
+        path.push(key.into());
+        match before.get(key) {
+            Some(old) => diff(old, value, path, changed),
+            None => {
+                changed.insert(path.clone());
+            }
+        }
+        path.pop();
+    }
+    for (key, _) in before.iter() {
+        let key: &str = <V::Object as JsonObject>::key_as_str(key);
+        if !after.contains_key(key) {
+            path.push(key.into());
+            changed.insert(path.clone());
+            path.pop();
+        }
+    }
+}
+
+/// Returns the member of `value` at `path`, or `None` if it is absent.
+fn lookup<'a, V: DestructibleJsonValue>(value: &'a V, path: &[Box<str>]) -> Option<&'a V> {
+    path.iter().try_fold(value, |value, segment| {
+        value.try_as_object().ok()?.get(&**segment)
+    })
+}
+
+/// Returns the implicit JSON pointer of `path`.
+fn pointer(path: &[Box<str>]) -> Box<ImplicitJsonPointer> {
+    let pointer = path
+        .iter()
+        .map(|segment| escape_segment(segment))
+        .collect::<Vec<_>>()
+        .join("/");
+    ImplicitJsonPointer::new(&pointer)
+        .expect("member pointers are escaped")
+        .into()
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{
+        json::TryFromJson,
+        model::{
+            set::{ParticipationStatus, Token},
+            string::Id,
+        },
+    };
+
+    fn event() -> Event<Value> {
+        Event::try_from_json(json!({
+            "@type": "Event",
+            "uid": "tracked",
+            "start": "2024-01-15T13:00:00",
+            "duration": "PT1H",
+            "keywords": { "a/b": true },
+            "participants": {
+                "p1": { "@type": "Participant", "participationStatus": "needs-action" },
+                "p2": { "@type": "Participant", "name": "Bob" }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn update(tracked: &Tracked<Event<Value>>) -> Value {
+        tracked.update().into_json()
+    }
+
+    #[test]
+    fn edits_are_recorded() {
+        let mut event = Tracked::new(event());
+        assert!(!event.is_changed());
+        assert_eq!(update(&event), json!({}));
+
+        event.edit(|event| {
+            let p1 = Id::new("p1").unwrap();
+            let p1 = event.participants_mut().unwrap().get_mut(p1).unwrap();
+            p1.set_participation_status(Token::Known(ParticipationStatus::Accepted));
+            event.keywords_mut().unwrap().insert("Work".into());
+            event.keywords_mut().unwrap().remove("a/b");
+            event.remove_duration();
+        });
+        assert_eq!(
+            update(&event),
+            json!({
+                "duration": null,
+                "keywords/Work": true,
+                "keywords/a~1b": null,
+                "participants/p1/participationStatus": "accepted"
+            })
+        );
+
+        // an edit which changes nothing records nothing
+        event.clear();
+        event.edit(|event| event.title().cloned());
+        assert!(!event.is_changed());
+    }
+
+    #[test]
+    fn nested_changes_are_subsumed() {
+        let mut event = Tracked::new(event());
+        event.edit(|event| {
+            let p2 = Id::new("p2").unwrap();
+            let p2 = event.participants_mut().unwrap().get_mut(p2).unwrap();
+            p2.set_name("Robert".into());
+        });
+        event
+            .set_property(Property::Participants, json!({}))
+            .unwrap();
+        assert_eq!(event.changed_paths().len(), 1);
+        assert_eq!(update(&event), json!({ "participants": {} }));
+    }
+
+    #[test]
+    fn properties_are_recorded() {
+        let mut event = Tracked::new(event());
+        event
+            .set_property(Property::Title, json!("Planning"))
+            .unwrap();
+        assert_eq!(event.remove_property(Property::Color), Ok(None));
+        assert!(event.set_property(Property::Priority, json!(10)).is_err());
+        assert_eq!(update(&event), json!({ "title": "Planning" }));
+        assert_eq!(event.title().map(String::as_str), Some("Planning"));
+    }
+}
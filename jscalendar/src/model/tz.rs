@@ -0,0 +1,475 @@
+//! Evaluation of custom time zones to concrete UTC offsets.
+//!
+//! A [`TimeZone`] describes its UTC offsets by `standard` and `daylight` rules, which are
+//! expanded into transitions by [`vtimezone_to_transitions`] exactly as the STANDARD and DAYLIGHT
+//! components of a VTIMEZONE are. The keys of the `recurrenceOverrides` of a rule are additional
+//! onsets of its observance, unless their patch sets `excluded` to `true`, in which case they are
+//! removed from the onsets generated by its `recurrenceRules`.
+//!
+//! Custom time zones tend to accumulate in the `timeZones` of an object as it is edited and
+//! merged, so [`Event::prune_unused_time_zones`], [`Task::prune_unused_time_zones`], and
+//! [`Group::prune_unused_time_zones`] remove those which nothing refers to any more.
+
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+use rfc5545_types::tz::{Observance, vtimezone_to_transitions};
+
+pub use rfc5545_types::tz::{Transition, TransitionError};
+
+use crate::{
+    json::{DestructibleJsonValue, JsonObject, JsonValue},
+    model::{
+        object::{Event, Group, PatchObject, Task, TaskOrEvent, TimeZone, TimeZoneRule},
+        props,
+        string::CustomTimeZoneId,
+        time::{DateTime, Local, Utc, UtcOffset, Year},
+    },
+};
+
+type TimeZoneMap<V> = HashMap<Box<CustomTimeZoneId>, TimeZone<V>>;
+type Overrides<V> = HashMap<DateTime<Local>, PatchObject<V>>;
+
+/// A change of the local time type of a [`TimeZone`], as returned by
+/// [`TimeZone::to_transitions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZoneTransition<'a> {
+    /// The instant of the transition.
+    pub utc: DateTime<Utc>,
+    /// The UTC offset after the transition.
+    pub offset: UtcOffset,
+    /// Whether the rule which begins at the transition is a `daylight` rule.
+    pub is_dst: bool,
+    /// The least of the `names` of the rule which begins at the transition, if it has any.
+    pub name: Option<&'a str>,
+}
+
+impl<V: DestructibleJsonValue> TimeZone<V> {
+    /// Returns the rules of this time zone: the `standard` rules followed by the `daylight` rules.
+    /// The `observance` of a [`Transition`] is an index into this sequence.
+    pub fn rules(&self) -> impl Iterator<Item = &TimeZoneRule<V>> {
+        let standard = self.standard().map_or(&[][..], Vec::as_slice);
+        let daylight = self.daylight().map_or(&[][..], Vec::as_slice);
+        standard.iter().chain(daylight)
+    }
+
+    /// Returns the transitions of this time zone whose local times fall in or before the year
+    /// `until`, in chronological order.
+    pub fn transitions(&self, until: Year) -> Result<Vec<Transition>, TransitionError> {
+        let observances: Vec<_> = self.rules().map(observance).collect();
+        vtimezone_to_transitions(&observances, until)
+    }
+
+    /// Returns the UTC offset of this time zone at the instant `utc`, or `None` if it has no
+    /// rules. Before the first transition, this is the offset from which the first transition
+    /// changes.
+    pub fn offset_at_utc(&self, utc: DateTime<Utc>) -> Result<Option<UtcOffset>, TransitionError> {
+        let Some(first) = self.rules().map(|rule| rule.start().date.year()).min() else {
+            return Ok(None);
+        };
+
+        // the transitions up to the year after that of `utc` include every transition before it,
+        // whatever the offsets of the time zone
+        let next_year = Year::new(utc.date.year().get() + 1).unwrap_or(Year::MAX);
+        let transitions = self.transitions(next_year.max(first))?;
+        let offset = match transitions.iter().rev().find(|t| t.utc <= utc) {
+            Some(transition) => transition.offset_to,
+            None => transitions[0].offset_from,
+        };
+
+        Ok(Some(offset))
+    }
+
+    /// Returns the transitions of this time zone whose instants fall in `range`, in
+    /// chronological order, together with the offsets, kinds, and names of the rules which begin
+    /// at them.
+    pub fn to_transitions(
+        &self,
+        range: Range<DateTime<Utc>>,
+    ) -> Result<Vec<ZoneTransition<'_>>, TransitionError> {
+        let mut transitions = self.zone_transitions(range.end)?;
+        transitions.retain(|transition| range.contains(&transition.utc));
+        Ok(transitions)
+    }
+
+    /// Returns the transitions of this time zone before the instant `until`, in chronological
+    /// order.
+    pub(crate) fn zone_transitions(
+        &self,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<ZoneTransition<'_>>, TransitionError> {
+        let rules: Vec<_> = self.rules().collect();
+        let standard = self.standard().map_or(0, Vec::len);
+
+        // local times are within a day of UTC, so the transitions up to the year after that of
+        // `until` include every transition before it
+        let horizon = Year::new(until.date.year().get() + 1).unwrap_or(Year::MAX);
+        let transitions = self.transitions(horizon)?.into_iter();
+        let transitions = transitions.take_while(|transition| transition.utc < until);
+        Ok(transitions
+            .map(|transition| ZoneTransition {
+                utc: transition.utc,
+                offset: transition.offset_to,
+                is_dst: transition.observance >= standard,
+                name: rules[transition.observance]
+                    .names()
+                    .and_then(|names| names.iter().min())
+                    .map(String::as_str),
+            })
+            .collect())
+    }
+}
+
+impl<V: JsonValue> Event<V> {
+    /// Returns the time zone IDs used by the properties of this event, excluding its
+    /// `recurrenceOverrides`.
+    pub(crate) fn referenced_time_zones(&self) -> HashSet<&str> {
+        let locations = self.locations().into_iter().flatten();
+        locations
+            .filter_map(|(_, location)| location.as_known()?.time_zone())
+            .chain(self.time_zone())
+            .chain(self.recurrence_id_time_zone())
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+impl<V: DestructibleJsonValue> Event<V> {
+    /// Removes the entries of `timeZones` which are not referenced by the `timeZone`,
+    /// `recurrenceIdTimeZone`, or `locations` of this event, or by a patch in its
+    /// `recurrenceOverrides`, and returns their IDs in order. If no entries remain, `timeZones`
+    /// is removed.
+    pub fn prune_unused_time_zones(&mut self) -> Vec<Box<CustomTimeZoneId>> {
+        let referenced = referenced(self.referenced_time_zones(), self.recurrence_overrides());
+        let removed = prune(self.time_zones_mut(), &referenced);
+        if self.time_zones().is_some_and(HashMap::is_empty) {
+            self.remove_time_zones();
+        }
+        removed
+    }
+}
+
+impl<V: JsonValue> Task<V> {
+    /// Returns the time zone IDs used by the properties of this task, excluding its
+    /// `recurrenceOverrides`.
+    pub(crate) fn referenced_time_zones(&self) -> HashSet<&str> {
+        let locations = self.locations().into_iter().flatten();
+        locations
+            .filter_map(|(_, location)| location.as_known()?.time_zone())
+            .chain(self.time_zone())
+            .chain(self.recurrence_id_time_zone())
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+impl<V: DestructibleJsonValue> Task<V> {
+    /// Removes the entries of `timeZones` which are not referenced by this task; see
+    /// [`Event::prune_unused_time_zones`].
+    pub fn prune_unused_time_zones(&mut self) -> Vec<Box<CustomTimeZoneId>> {
+        let referenced = referenced(self.referenced_time_zones(), self.recurrence_overrides());
+        let removed = prune(self.time_zones_mut(), &referenced);
+        if self.time_zones().is_some_and(HashMap::is_empty) {
+            self.remove_time_zones();
+        }
+        removed
+    }
+}
+
+impl<V: DestructibleJsonValue> Group<V> {
+    /// Prunes the time zones of each entry of this group, then removes the entries of the
+    /// `timeZones` of the group which are not referenced by any entry, and returns their IDs in
+    /// order. If no entries remain, `timeZones` is removed.
+    ///
+    /// An entry may refer to a time zone of the group even if it defines a time zone with the
+    /// same ID, so such references keep the time zone of the group.
+    pub fn prune_unused_time_zones(&mut self) -> Vec<Box<CustomTimeZoneId>> {
+        let mut referenced = HashSet::new();
+        for entry in self.entries_mut() {
+            let ids = match entry {
+                TaskOrEvent::Event(event) => {
+                    event.prune_unused_time_zones();
+                    self::referenced(event.referenced_time_zones(), event.recurrence_overrides())
+                }
+                TaskOrEvent::Task(task) => {
+                    task.prune_unused_time_zones();
+                    self::referenced(task.referenced_time_zones(), task.recurrence_overrides())
+                }
+            };
+            referenced.extend(ids);
+        }
+
+        let removed = prune(self.time_zones_mut(), &referenced);
+        if self.time_zones().is_some_and(HashMap::is_empty) {
+            self.remove_time_zones();
+        }
+        removed
+    }
+}
+
+/// Returns the time zone IDs in `direct` together with those set by the patches in `overrides`.
+pub(crate) fn referenced<V: DestructibleJsonValue>(
+    direct: HashSet<&str>,
+    overrides: Option<&Overrides<V>>,
+) -> HashSet<String> {
+    let mut referenced: HashSet<String> = direct.into_iter().map(str::to_string).collect();
+    for patch in overrides.into_iter().flat_map(HashMap::values) {
+        for (pointer, value) in patch.iter() {
+            let last = pointer.segments().last();
+            match (last.as_deref(), value.try_as_string()) {
+                (Some(props::TIME_ZONE | props::RECURRENCE_ID_TIME_ZONE), Ok(id)) => {
+                    referenced.insert(id.as_ref().to_string());
+                }
+                _ => nested_time_zones(value, &mut referenced),
+            }
+        }
+    }
+    referenced
+}
+
+/// Adds the `timeZone` members of the objects nested in `value` to `referenced`, such as those
+/// of the locations in a patch which replaces `locations`.
+fn nested_time_zones<V: DestructibleJsonValue>(value: &V, referenced: &mut HashSet<String>) {
+    let Ok(object) = value.try_as_object() else {
+        return;
+    };
+
+    for (key, value) in object.iter() {
+        match value.try_as_string() {
+            Ok(id) if Borrow::<str>::borrow(key) == props::TIME_ZONE => {
+                referenced.insert(id.as_ref().to_string());
+            }
+            _ => nested_time_zones(value, referenced),
+        }
+    }
+}
+
+/// Removes the entries of `time_zones` whose IDs are not in `referenced`, returning their IDs in
+/// order.
+fn prune<V>(
+    time_zones: Option<&mut TimeZoneMap<V>>,
+    referenced: &HashSet<String>,
+) -> Vec<Box<CustomTimeZoneId>> {
+    let Some(time_zones) = time_zones else {
+        return Vec::new();
+    };
+
+    let mut removed: Vec<_> = time_zones
+        .keys()
+        .filter(|id| !referenced.contains(&id.to_string()))
+        .cloned()
+        .collect();
+    removed.sort();
+    for id in &removed {
+        time_zones.remove(id);
+    }
+    removed
+}
+
+/// Returns `rule` as an [`Observance`].
+fn observance<V: DestructibleJsonValue>(rule: &TimeZoneRule<V>) -> Observance<'_> {
+    let mut rdates = Vec::new();
+    let mut exdates = Vec::new();
+    for (onset, patch) in rule.recurrence_overrides().into_iter().flatten() {
+        if patch.excludes() {
+            exdates.push(*onset);
+        } else {
+            rdates.push(*onset);
+        }
+    }
+
+    Observance {
+        start: *rule.start(),
+        offset_from: *rule.offset_from(),
+        offset_to: *rule.offset_to(),
+        rrules: rule.recurrence_rules().into_iter().flatten().collect(),
+        rdates,
+        exdates,
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{
+        json::TryFromJson,
+        model::time::{Date, Day, Hour, Minute, Month, Second, Sign, Time},
+    };
+
+    /// Returns the rules of America/New_York since 2007, with the given `recurrenceOverrides` on
+    /// the daylight rule.
+    fn new_york(overrides: Value) -> TimeZone<Value> {
+        let rule = |month: u8, nth: i64, from: &str, to: &str, start: &str| {
+            json!({
+                "@type": "TimeZoneRule",
+                "start": start,
+                "offsetFrom": from,
+                "offsetTo": to,
+                "recurrenceRules": [{
+                    "@type": "RecurrenceRule",
+                    "frequency": "yearly",
+                    "byMonth": [month],
+                    "byDay": [{ "@type": "NDay", "day": "su", "nthOfPeriod": nth }]
+                }]
+            })
+        };
+
+        let mut daylight = rule(3, 2, "-05:00", "-04:00", "2007-03-11T02:00:00");
+        daylight["recurrenceOverrides"] = overrides;
+        TimeZone::try_from_json(json!({
+            "@type": "TimeZone",
+            "tzId": "America/New_York",
+            "standard": [rule(11, 1, "-04:00", "-05:00", "2007-11-04T02:00:00")],
+            "daylight": [daylight]
+        }))
+        .unwrap()
+    }
+
+    fn utc(year: u16, month: u8, day: u8, hour: u8) -> DateTime<Utc> {
+        DateTime {
+            date: Date::new(
+                Year::new(year).unwrap(),
+                Month::new(month).unwrap(),
+                Day::new(day).unwrap(),
+            )
+            .unwrap(),
+            time: Time::new(Hour::new(hour).unwrap(), Minute::M00, Second::S00, None).unwrap(),
+            marker: Utc,
+        }
+    }
+
+    fn offset(hours: i8) -> UtcOffset {
+        UtcOffset {
+            sign: if hours < 0 { Sign::Neg } else { Sign::Pos },
+            hour: Hour::new(hours.unsigned_abs()).unwrap(),
+            minute: Minute::M00,
+            second: Default::default(),
+        }
+    }
+
+    #[test]
+    fn transitions() {
+        let tz = new_york(json!({}));
+        let transitions = tz.transitions(Year::new(2024).unwrap()).unwrap();
+        assert_eq!(transitions.len(), 2 * (2024 - 2007 + 1));
+
+        let last = transitions.last().unwrap();
+        assert_eq!(last.utc, utc(2024, 11, 3, 6));
+        assert_eq!(last.observance, 0);
+        assert_eq!(last.offset_to, offset(-5));
+    }
+
+    #[test]
+    fn offset_at_utc() {
+        let tz = new_york(json!({}));
+        let at = |utc| tz.offset_at_utc(utc).unwrap().unwrap();
+        assert_eq!(at(utc(2024, 1, 15, 12)), offset(-5));
+        assert_eq!(at(utc(2024, 7, 1, 12)), offset(-4));
+        assert_eq!(at(utc(2024, 3, 10, 6)), offset(-5));
+        assert_eq!(at(utc(2024, 3, 10, 7)), offset(-4));
+        assert_eq!(at(utc(1990, 7, 1, 12)), offset(-5));
+
+        let empty = TimeZone::<Value>::new("Etc/Unknown".into());
+        assert_eq!(empty.offset_at_utc(utc(2024, 1, 1, 0)), Ok(None));
+    }
+
+    #[test]
+    fn to_transitions() {
+        let mut tz = new_york(json!({}));
+        tz.daylight_mut().unwrap()[0].set_names(["EDT".into()].into());
+        let transitions = tz
+            .to_transitions(utc(2024, 1, 1, 0)..utc(2025, 1, 1, 0))
+            .unwrap();
+        assert_eq!(
+            transitions,
+            [
+                ZoneTransition {
+                    utc: utc(2024, 3, 10, 7),
+                    offset: offset(-4),
+                    is_dst: true,
+                    name: Some("EDT"),
+                },
+                ZoneTransition {
+                    utc: utc(2024, 11, 3, 6),
+                    offset: offset(-5),
+                    is_dst: false,
+                    name: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn recurrence_overrides() {
+        let tz = new_york(json!({
+            "2024-03-10T02:00:00": { "excluded": true },
+            "2024-04-01T02:00:00": {}
+        }));
+        let at = |utc| tz.offset_at_utc(utc).unwrap().unwrap();
+        assert_eq!(at(utc(2024, 3, 20, 12)), offset(-5));
+        assert_eq!(at(utc(2024, 4, 1, 12)), offset(-4));
+        assert_eq!(at(utc(2025, 3, 20, 12)), offset(-4));
+    }
+
+    #[test]
+    fn prune_unused_time_zones() {
+        let zone = |id: &str| json!({ "@type": "TimeZone", "tzId": id });
+        let mut event = Event::<Value>::try_from_json(json!({
+            "@type": "Event",
+            "uid": "e",
+            "start": "2024-01-01T10:00:00",
+            "timeZone": "/start",
+            "locations": {
+                "l1": { "@type": "Location", "timeZone": "/location" }
+            },
+            "recurrenceOverrides": {
+                "2024-01-08T10:00:00": { "timeZone": "/patch" },
+                "2024-01-15T10:00:00": {
+                    "locations": { "l2": { "@type": "Location", "timeZone": "/nested" } }
+                }
+            },
+            "timeZones": {
+                "/start": zone("/start"),
+                "/location": zone("/location"),
+                "/patch": zone("/patch"),
+                "/nested": zone("/nested"),
+                "/stale": zone("/stale"),
+                "/old": zone("/old")
+            }
+        }))
+        .unwrap();
+
+        let removed = event.prune_unused_time_zones();
+        let removed: Vec<_> = removed.iter().map(ToString::to_string).collect();
+        assert_eq!(removed, ["/old", "/stale"]);
+        assert_eq!(event.time_zones().unwrap().len(), 4);
+        assert!(event.prune_unused_time_zones().is_empty());
+
+        let mut group = Group::<Value>::try_from_json(json!({
+            "@type": "Group",
+            "uid": "g",
+            "entries": [{
+                "@type": "Task",
+                "uid": "t",
+                "timeZone": "/shared",
+                "timeZones": { "/own": zone("/own") }
+            }],
+            "timeZones": { "/shared": zone("/shared"), "/unused": zone("/unused") }
+        }))
+        .unwrap();
+
+        let removed = group.prune_unused_time_zones();
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].to_string(), "/unused");
+        assert_eq!(group.time_zones().unwrap().len(), 1);
+        let TaskOrEvent::Task(task) = &group.entries()[0] else {
+            panic!("expected a task");
+        };
+        assert_eq!(task.time_zones(), None);
+    }
+}
@@ -0,0 +1,655 @@
+//! Conversion of custom time zones to and from the TZif format (RFC 8536).
+//!
+//! The rules of a custom [`TimeZone`] can only be evaluated by code which understands
+//! JSCalendar, while most time zone libraries read the compiled TZif files of the IANA time zone
+//! database. [`TimeZone::to_tzif`] compiles the transitions of a custom time zone in a range of
+//! instants into such a file, so that the time zone can be used with these libraries.
+//!
+//! The file has version 2, and its footer has no TZ string, so local time is unspecified after
+//! the end of the range (RFC 8536 §3.3). The range should therefore cover every instant at which
+//! the time zone will be used.
+//!
+//! Conversely, [`TimeZone::from_tzif`] synthesizes the rules of a custom time zone from a TZif
+//! file, such as one of the files installed in `/usr/share/zoneinfo`, for consumers of
+//! JSCalendar which lack a time zone database.
+
+use std::{collections::HashSet, ops::Range};
+
+use rfc5545_types::rrule::{
+    CoreByRules, FreqByRules, MonthSet, RRule, Termination, WeekdayNum, YearlyByRules,
+    weekday_num_set::WeekdayNumSet,
+};
+use thiserror::Error;
+
+use crate::{
+    json::DestructibleJsonValue,
+    model::{
+        object::{TimeZone, TimeZoneRule},
+        time::{
+            Date, DateTime, Day, Hour, IsoWeek, Local, Minute, Month, NonLeapSecond, Second, Sign,
+            Time, Utc, UtcOffset, Weekday, Year,
+        },
+        tz::{TransitionError, ZoneTransition},
+    },
+};
+
+/// An error arising from TZif data which [`TimeZone::from_tzif`] cannot read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum TzifError {
+    /// The data does not begin with a TZif header.
+    #[error("the data does not begin with a TZif header")]
+    InvalidHeader,
+    /// The data ends within a data block.
+    #[error("the data ends within a data block")]
+    Truncated,
+    /// A transition or designation refers to a local time type or designation which does not
+    /// exist.
+    #[error("a transition or local time type has an invalid index")]
+    InvalidIndex,
+    /// The instant of a transition, or a UTC offset, cannot be represented.
+    #[error("a transition or UTC offset is out of range")]
+    OutOfRange,
+}
+
+/// A local time type of a TZif file (RFC 8536 §3.2).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LocalTimeType {
+    offset: i32,
+    is_dst: bool,
+    designation: String,
+}
+
+impl LocalTimeType {
+    fn new(offset: UtcOffset, is_dst: bool, name: Option<&str>) -> Self {
+        Self {
+            offset: offset.as_seconds(),
+            is_dst,
+            designation: designation(offset, name),
+        }
+    }
+}
+
+impl<V: DestructibleJsonValue> TimeZone<V> {
+    /// Returns the transitions of this time zone in `range` as the contents of a TZif file, or
+    /// `None` if it has no rules.
+    ///
+    /// Instants before the first transition in `range` use the local time type in effect at its
+    /// start. The name of a rule is used as the designation of its local time type if it is at
+    /// least three ASCII letters, digits, `+`, or `-`; otherwise, the offset is used, as in `+0530`.
+    pub fn to_tzif(&self, range: Range<DateTime<Utc>>) -> Result<Option<Vec<u8>>, TransitionError> {
+        let transitions = self.zone_transitions(range.end)?;
+        let split = transitions.partition_point(|transition| transition.utc < range.start);
+        let initial = match split.checked_sub(1) {
+            Some(index) => local_time_type(&transitions[index]),
+            None => match self.offset_at_utc(range.start)? {
+                Some(offset) => LocalTimeType::new(offset, false, None),
+                None => return Ok(None),
+            },
+        };
+
+        let mut types = vec![initial];
+        let mut times = Vec::new();
+        for transition in &transitions[split..] {
+            let local_time_type = local_time_type(transition);
+            let index = match types.iter().position(|other| *other == local_time_type) {
+                Some(index) => index,
+                None => {
+                    types.push(local_time_type);
+                    types.len() - 1
+                }
+            };
+            // a TZif file has at most 256 local time types
+            let index = u8::try_from(index).map_err(|_| TransitionError::OutOfRange)?;
+            times.push((unix_seconds(transition.utc), index));
+        }
+
+        let mut designations = Vec::new();
+        let mut starts: Vec<(&str, usize)> = Vec::new();
+        let mut records = Vec::with_capacity(types.len());
+        for local_time_type in &types {
+            let designation = local_time_type.designation.as_str();
+            let index = match starts.iter().find(|(other, _)| *other == designation) {
+                Some((_, index)) => *index,
+                None => {
+                    let index = designations.len();
+                    designations.extend_from_slice(designation.as_bytes());
+                    designations.push(0);
+                    starts.push((designation, index));
+                    index
+                }
+            };
+            let index = u8::try_from(index).map_err(|_| TransitionError::OutOfRange)?;
+            records.push((local_time_type.offset, local_time_type.is_dst, index));
+        }
+
+        // the version 1 data block only holds the transitions representable in 32 bits
+        let narrow: Vec<_> = times
+            .iter()
+            .filter(|(time, _)| i32::try_from(*time).is_ok())
+            .copied()
+            .collect();
+        let mut tzif = Vec::new();
+        write_block(&mut tzif, &narrow, &records, &designations, false);
+        write_block(&mut tzif, &times, &records, &designations, true);
+        tzif.extend_from_slice(b"\n\n");
+        Ok(Some(tzif))
+    }
+}
+
+impl<V> TimeZone<V> {
+    /// Returns a time zone with the ID `tz_id`, whose rules reproduce the transitions in `window`
+    /// of the TZif data `tzif`.
+    ///
+    /// The transitions with the same offsets and designation which fall on the same weekday of
+    /// the same month, counted from its start or end, at the same local time in consecutive years
+    /// are described by one rule with a yearly recurrence rule, and every other transition by a
+    /// rule of its own. Since the recurrence rules end at the last transition in `window`, the
+    /// `validUntil` of the time zone is the end of `window`. If there are no transitions in
+    /// `window`, the time zone has a single `standard` rule with the offset in effect at its start.
+    pub fn from_tzif(
+        tz_id: impl Into<String>,
+        tzif: &[u8],
+        window: Range<DateTime<Utc>>,
+    ) -> Result<Self, TzifError> {
+        let Tzif { times, types } = parse(tzif)?;
+        let mut onsets = Vec::new();
+        let (mut previous, mut at_start) = (&types[0], &types[0]);
+        for &(time, index) in &times {
+            let next = &types[usize::from(index)];
+            // instants which cannot be represented, such as the "big bang" transitions of
+            // the IANA database, lie outside any window
+            let utc = from_unix_seconds(time);
+            if utc.map_or(time < 0, |utc| utc < window.start) {
+                at_start = next;
+            } else if let Some(utc) = utc.filter(|utc| *utc < window.end) {
+                let from = utc_offset(previous.offset)?;
+                onsets.push(Onset {
+                    local: from.utc_to_local(utc).ok_or(TzifError::OutOfRange)?,
+                    from,
+                    to: utc_offset(next.offset)?,
+                    is_dst: next.is_dst,
+                    name: &next.designation,
+                });
+            }
+            previous = next;
+        }
+
+        if onsets.is_empty() {
+            let offset = utc_offset(at_start.offset)?;
+            onsets.push(Onset {
+                local: offset
+                    .utc_to_local(window.start)
+                    .ok_or(TzifError::OutOfRange)?,
+                from: offset,
+                to: offset,
+                is_dst: at_start.is_dst,
+                name: &at_start.designation,
+            });
+        }
+
+        let mut runs: Vec<Run<'_>> = Vec::new();
+        for onset in onsets {
+            let extended = runs.iter_mut().find_map(|run| {
+                let next_year = run.last.local.date.year().get() + 1;
+                let pattern = run.pattern.merge(&Pattern::of(onset.local))?;
+                (onset.local.date.year().get() == next_year && run.first.has_type_of(&onset))
+                    .then_some((run, pattern))
+            });
+            match extended {
+                Some((run, pattern)) => {
+                    run.pattern = pattern;
+                    run.last = onset;
+                    run.count += 1;
+                }
+                None => runs.push(Run {
+                    pattern: Pattern::of(onset.local),
+                    first: onset,
+                    last: onset,
+                    count: 1,
+                }),
+            }
+        }
+
+        let mut time_zone = TimeZone::new(tz_id.into());
+        let (mut standard, mut daylight) = (Vec::new(), Vec::new());
+        for run in runs {
+            let is_dst = run.first.is_dst;
+            let rule = run.into_rule();
+            if is_dst {
+                daylight.push(rule);
+            } else {
+                standard.push(rule);
+            }
+        }
+        if !standard.is_empty() {
+            time_zone.set_standard(standard);
+        }
+        if !daylight.is_empty() {
+            time_zone.set_daylight(daylight);
+        }
+        time_zone.set_valid_until(window.end);
+        Ok(time_zone)
+    }
+}
+
+/// A transition read from TZif data, which begins a rule.
+#[derive(Debug, Clone, Copy)]
+struct Onset<'a> {
+    local: DateTime<Local>,
+    from: UtcOffset,
+    to: UtcOffset,
+    is_dst: bool,
+    name: &'a str,
+}
+
+impl Onset<'_> {
+    /// Returns `true` iff `other` changes between the same local time types.
+    fn has_type_of(&self, other: &Self) -> bool {
+        (self.from, self.to, self.is_dst, self.name)
+            == (other.from, other.to, other.is_dst, other.name)
+    }
+}
+
+/// The day of the year on which a sequence of onsets fall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pattern {
+    month: Month,
+    weekday: Weekday,
+    time: Time,
+    /// The week of the month in which the onsets fall, if it is the same for all of them.
+    nth: Option<IsoWeek>,
+    /// Whether the onsets fall in the last week of the month.
+    last: bool,
+}
+
+impl Pattern {
+    fn of(local: DateTime<Local>) -> Self {
+        let (date, day) = (local.date, local.date.day() as u8);
+        Self {
+            month: date.month(),
+            weekday: date.weekday(),
+            time: local.time,
+            nth: IsoWeek::from_index((day - 1) / 7 + 1).filter(|nth| (*nth as u8) < 5),
+            last: day + 7 > date.month().days_in(date.year()),
+        }
+    }
+
+    /// Returns the pattern of the onsets of `self` and `other`, or `None` if there is none.
+    fn merge(&self, other: &Self) -> Option<Self> {
+        let merged = Self {
+            nth: self.nth.filter(|nth| other.nth == Some(*nth)),
+            last: self.last && other.last,
+            ..*self
+        };
+        let same_day =
+            (self.month, self.weekday, self.time) == (other.month, other.weekday, other.time);
+        (same_day && (merged.nth.is_some() || merged.last)).then_some(merged)
+    }
+}
+
+/// A sequence of onsets in consecutive years which follow a single pattern.
+struct Run<'a> {
+    first: Onset<'a>,
+    last: Onset<'a>,
+    pattern: Pattern,
+    count: u64,
+}
+
+impl Run<'_> {
+    fn into_rule<V>(self) -> TimeZoneRule<V> {
+        let Onset {
+            local,
+            from,
+            to,
+            name,
+            ..
+        } = self.first;
+        let mut rule = TimeZoneRule::new(local, from, to);
+        rule.set_names(HashSet::from([name.to_owned()]));
+        if self.count > 1 {
+            let ordinal = match self.pattern.nth {
+                Some(nth) => (Sign::Pos, nth),
+                None => (Sign::Neg, IsoWeek::W1),
+            };
+            let mut by_day = WeekdayNumSet::default();
+            by_day.insert(WeekdayNum {
+                ordinal: Some(ordinal),
+                weekday: self.pattern.weekday,
+            });
+            let mut by_month = MonthSet::default();
+            by_month.set(self.pattern.month);
+
+            rule.set_recurrence_rules(vec![RRule {
+                freq: FreqByRules::Yearly(YearlyByRules::default()),
+                core_by_rules: CoreByRules {
+                    by_month: Some(by_month),
+                    by_day: Some(by_day),
+                    ..Default::default()
+                },
+                interval: None,
+                termination: Some(Termination::Count(self.count)),
+                week_start: None,
+                rscale: None,
+                skip: None,
+                extras: Vec::new(),
+            }]);
+        }
+        rule
+    }
+}
+
+/// The transitions and local time types of TZif data.
+struct Tzif {
+    times: Vec<(i64, u8)>,
+    types: Vec<LocalTimeType>,
+}
+
+/// Reads the data block of `tzif` with the widest transition times.
+fn parse(mut tzif: &[u8]) -> Result<Tzif, TzifError> {
+    let (version, counts) = header(&mut tzif)?;
+    if version == 0 {
+        return block(&mut tzif, counts, 4);
+    }
+
+    block(&mut tzif, counts, 4)?;
+    let (_, counts) = header(&mut tzif)?;
+    block(&mut tzif, counts, 8)
+}
+
+/// Reads a header (RFC 8536 §3.1), returning its version and counts.
+fn header(tzif: &mut &[u8]) -> Result<(u8, [usize; 6]), TzifError> {
+    let header = take(tzif, 44).map_err(|_| TzifError::InvalidHeader)?;
+    if !header.starts_with(b"TZif") {
+        return Err(TzifError::InvalidHeader);
+    }
+
+    let mut counts = [0; 6];
+    for (count, bytes) in counts.iter_mut().zip(header[20..].chunks_exact(4)) {
+        *count = u32::from_be_bytes(bytes.try_into().unwrap()) as usize;
+    }
+    Ok((header[4], counts))
+}
+
+/// Reads a data block (RFC 8536 §3.2) with the given counts and size of transition times.
+fn block(tzif: &mut &[u8], counts: [usize; 6], time_size: usize) -> Result<Tzif, TzifError> {
+    let [isutcnt, isstdcnt, leapcnt, timecnt, typecnt, charcnt] = counts;
+    // the counts come from the data, so their sizes may not fit in a 32-bit usize
+    let size = |count: usize, width: usize| count.checked_mul(width).ok_or(TzifError::Truncated);
+    let times = take(tzif, size(timecnt, time_size)?)?;
+    let indices = take(tzif, timecnt)?;
+    let records = take(tzif, size(typecnt, 6)?)?;
+    let designations = take(tzif, charcnt)?;
+    let rest = size(leapcnt, time_size + 4)?
+        .checked_add(isstdcnt)
+        .and_then(|len| len.checked_add(isutcnt))
+        .ok_or(TzifError::Truncated)?;
+    take(tzif, rest)?;
+
+    let mut types = Vec::with_capacity(typecnt);
+    for record in records.chunks_exact(6) {
+        let designation = designations
+            .get(usize::from(record[5])..)
+            .and_then(|rest| rest.split(|byte| *byte == 0).next())
+            .ok_or(TzifError::InvalidIndex)?;
+        types.push(LocalTimeType {
+            offset: i32::from_be_bytes(record[..4].try_into().unwrap()),
+            is_dst: record[4] != 0,
+            designation: String::from_utf8_lossy(designation).into_owned(),
+        });
+    }
+    if types.is_empty()
+        || indices
+            .iter()
+            .any(|index| usize::from(*index) >= types.len())
+    {
+        return Err(TzifError::InvalidIndex);
+    }
+
+    let times = times
+        .chunks_exact(time_size)
+        .map(|bytes| match bytes.try_into() {
+            Ok(bytes) => i64::from_be_bytes(bytes),
+            Err(_) => i32::from_be_bytes(bytes.try_into().unwrap()).into(),
+        });
+    Ok(Tzif {
+        times: times.zip(indices.iter().copied()).collect(),
+        types,
+    })
+}
+
+/// Splits the first `len` bytes off `tzif`.
+fn take<'a>(tzif: &mut &'a [u8], len: usize) -> Result<&'a [u8], TzifError> {
+    if tzif.len() < len {
+        return Err(TzifError::Truncated);
+    }
+    let (head, tail) = tzif.split_at(len);
+    *tzif = tail;
+    Ok(head)
+}
+
+fn utc_offset(seconds: i32) -> Result<UtcOffset, TzifError> {
+    let sign = if seconds < 0 { Sign::Neg } else { Sign::Pos };
+    let seconds = seconds.unsigned_abs();
+    let component = |value: u32| u8::try_from(value).map_err(|_| TzifError::OutOfRange);
+    Ok(UtcOffset {
+        sign,
+        hour: Hour::new(component(seconds / 3600)?).map_err(|_| TzifError::OutOfRange)?,
+        minute: Minute::new(component(seconds / 60 % 60)?).unwrap(),
+        second: NonLeapSecond::new(component(seconds % 60)?).unwrap(),
+    })
+}
+
+fn local_time_type(transition: &ZoneTransition<'_>) -> LocalTimeType {
+    LocalTimeType::new(transition.offset, transition.is_dst, transition.name)
+}
+
+/// Returns the designation of a local time type with the given offset and rule name.
+fn designation(offset: UtcOffset, name: Option<&str>) -> String {
+    let is_valid = |name: &&str| {
+        name.len() >= 3
+            && name
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || byte == b'+' || byte == b'-')
+    };
+    if let Some(name) = name.filter(is_valid) {
+        return name.to_owned();
+    }
+
+    let sign = match offset.sign {
+        Sign::Pos => '+',
+        Sign::Neg => '-',
+    };
+    let (hour, minute, second) = (offset.hour as u8, offset.minute as u8, offset.second as u8);
+    match (minute, second) {
+        (0, 0) => format!("{sign}{hour:02}"),
+        (_, 0) => format!("{sign}{hour:02}{minute:02}"),
+        _ => format!("{sign}{hour:02}{minute:02}{second:02}"),
+    }
+}
+
+/// Appends a header and data block (RFC 8536 §3.1 and §3.2) to `tzif`, with 64-bit transition
+/// times if `wide` is `true` and 32-bit times otherwise.
+fn write_block(
+    tzif: &mut Vec<u8>,
+    times: &[(i64, u8)],
+    types: &[(i32, bool, u8)],
+    designations: &[u8],
+    wide: bool,
+) {
+    tzif.extend_from_slice(b"TZif2");
+    tzif.extend_from_slice(&[0; 15]);
+    // isutcnt, isstdcnt, leapcnt, timecnt, typecnt, charcnt
+    for count in [0, 0, 0, times.len(), types.len(), designations.len()] {
+        tzif.extend_from_slice(&(count as u32).to_be_bytes());
+    }
+
+    for (time, _) in times {
+        if wide {
+            tzif.extend_from_slice(&time.to_be_bytes());
+        } else {
+            tzif.extend_from_slice(&(*time as i32).to_be_bytes());
+        }
+    }
+    tzif.extend(times.iter().map(|(_, index)| index));
+    for (offset, is_dst, index) in types {
+        tzif.extend_from_slice(&offset.to_be_bytes());
+        tzif.extend_from_slice(&[u8::from(*is_dst), *index]);
+    }
+    tzif.extend_from_slice(designations);
+}
+
+/// Returns the number of seconds from 1970-01-01T00:00:00Z to `utc`.
+fn unix_seconds(utc: DateTime<Utc>) -> i64 {
+    let time = utc.time;
+    let seconds = time.hour() as i64 * 3600 + time.minute() as i64 * 60 + time.second() as i64;
+    utc.date.days_since(epoch()) * 86_400 + seconds
+}
+
+/// Inverse of [`unix_seconds`], returning `None` if the result is out of range.
+fn from_unix_seconds(seconds: i64) -> Option<DateTime<Utc>> {
+    let date = epoch().checked_add_days(seconds.div_euclid(86_400))?;
+    let seconds = seconds.rem_euclid(86_400) as u32;
+    let time = Time::new(
+        Hour::new((seconds / 3600) as u8).ok()?,
+        Minute::new((seconds / 60 % 60) as u8).ok()?,
+        Second::new((seconds % 60) as u8).ok()?,
+        None,
+    )
+    .ok()?;
+    Some(DateTime {
+        date,
+        time,
+        marker: Utc,
+    })
+}
+
+fn epoch() -> Date {
+    Date::new(Year::new(1970).unwrap(), Month::Jan, Day::D01).unwrap()
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::json::TryFromJson;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::try_from_json(Value::from(s)).unwrap()
+    }
+
+    fn u32_at(tzif: &[u8], offset: usize) -> u32 {
+        u32::from_be_bytes(tzif[offset..offset + 4].try_into().unwrap())
+    }
+
+    /// Returns the rules of Central European Time since 1996.
+    fn example() -> TimeZone<Value> {
+        let rule = |start: &str, from: &str, to: &str, month: u8, names: Value| {
+            json!({
+                "@type": "TimeZoneRule",
+                "start": start,
+                "offsetFrom": from,
+                "offsetTo": to,
+                "names": names,
+                "recurrenceRules": [{
+                    "@type": "RecurrenceRule",
+                    "frequency": "yearly",
+                    "byMonth": [month],
+                    "byDay": [{ "@type": "NDay", "day": "su", "nthOfPeriod": -1 }]
+                }]
+            })
+        };
+        TimeZone::try_from_json(json!({
+            "@type": "TimeZone",
+            "tzId": "Example",
+            "standard": [rule("1996-10-27T03:00:00", "+02:00", "+01:00", 10, json!({ "CET": true }))],
+            "daylight": [rule("1981-03-29T02:00:00", "+01:00", "+02:00", 3, json!({ "summer time": true }))]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn to_tzif() {
+        let tz = example();
+        let tzif = tz
+            .to_tzif(utc("2024-01-01T00:00:00Z")..utc("2025-01-01T00:00:00Z"))
+            .unwrap()
+            .unwrap();
+        let designations = b"CET\0+02\0";
+        // both blocks hold both transitions, with 32-bit and 64-bit times respectively
+        let block = 44 + 2 * 4 + 2 + 2 * 6 + designations.len();
+        assert_eq!(tzif.len(), block + (block + 2 * 4) + 2);
+        assert!(tzif.starts_with(b"TZif2") && tzif[block..].starts_with(b"TZif2"));
+        assert_eq!(tzif[44..48], 1711846800i32.to_be_bytes());
+
+        // timecnt, typecnt, and charcnt of the version 2 data block
+        let (timecnt, typecnt, charcnt) = (block + 32, block + 36, block + 40);
+        assert_eq!(u32_at(&tzif, timecnt), 2);
+        assert_eq!(u32_at(&tzif, typecnt), 2);
+        assert_eq!(u32_at(&tzif, charcnt), designations.len() as u32);
+
+        let data = &tzif[block + 44..];
+        assert_eq!(data[..8], 1711846800i64.to_be_bytes());
+        assert_eq!(data[8..16], 1729990800i64.to_be_bytes());
+        assert_eq!(data[16..18], [1, 0]);
+        assert_eq!(data[18..24], [0, 0, 0x0e, 0x10, 0, 0]);
+        assert_eq!(data[24..30], [0, 0, 0x1c, 0x20, 1, 4]);
+        assert_eq!(&data[30..], &[&designations[..], b"\n\n"].concat());
+
+        let empty = TimeZone::<Value>::new("Empty".into());
+        let range = utc("2024-01-01T00:00:00Z")..utc("2025-01-01T00:00:00Z");
+        assert_eq!(empty.to_tzif(range), Ok(None));
+    }
+
+    #[test]
+    fn from_tzif() {
+        let year = |year| utc(&format!("{year}-01-01T00:00:00Z"));
+        let tzif = example().to_tzif(year(2020)..year(2030)).unwrap().unwrap();
+
+        let tz = TimeZone::<Value>::from_tzif("Example", &tzif, year(2020)..year(2030)).unwrap();
+        assert_eq!(tz.valid_until(), Some(&year(2030)));
+        let [standard] = tz.standard().unwrap().as_slice() else {
+            panic!("expected one standard rule");
+        };
+        assert_eq!(standard.start().to_string(), "2020-10-25T03:00:00");
+        assert_eq!(standard.names(), Some(&HashSet::from(["CET".into()])));
+        let rrule = &standard.recurrence_rules().unwrap()[0];
+        assert_eq!(rrule.termination, Some(Termination::Count(10)));
+
+        let offsets = |tz: &TimeZone<Value>, window| {
+            let transitions = tz.to_transitions(window).unwrap();
+            let offsets = transitions
+                .iter()
+                .map(|transition| (transition.utc, transition.offset, transition.is_dst));
+            offsets.collect::<Vec<_>>()
+        };
+        assert_eq!(
+            offsets(&tz, year(2000)..year(2040)),
+            offsets(&example(), year(2020)..year(2030))
+        );
+
+        let winter =
+            TimeZone::<Value>::from_tzif("Example", &tzif, year(2020)..utc("2020-02-01T00:00:00Z"))
+                .unwrap();
+        assert_eq!(winter.daylight(), None);
+        let [standard] = winter.standard().unwrap().as_slice() else {
+            panic!("expected one standard rule");
+        };
+        assert_eq!(standard.start().to_string(), "2020-01-01T01:00:00");
+        assert_eq!(standard.recurrence_rules(), None);
+
+        assert_eq!(
+            TimeZone::<Value>::from_tzif("Example", &tzif[..60], year(2020)..year(2030))
+                .unwrap_err(),
+            TzifError::Truncated
+        );
+
+        let mut huge = tzif[..44].to_vec();
+        huge[28..36].fill(0xff);
+        assert_eq!(
+            TimeZone::<Value>::from_tzif("Example", &huge, year(2020)..year(2030)).unwrap_err(),
+            TzifError::Truncated
+        );
+    }
+}
@@ -0,0 +1,551 @@
+//! Next-occurrence queries for recurring events and tasks.
+//!
+//! Finding the next occurrence of a series is the most common recurrence query, and answering it
+//! by expanding the recurrence rules from the start of the series gets slower the further that
+//! start lies in the past. [`Event::next_occurrence`] and [`Task::next_due`] instead compute the
+//! next occurrence directly for simple rules: those with a `frequency`, `interval`, `count`, and
+//! `until`, but no `byX` parts. Sub-daily, daily, and weekly rules jump straight to the relevant
+//! period, and monthly and yearly rules only step through the periods which may be skipped for
+//! lack of a valid date (such as the 31st of a month).
+//!
+//! Recurrences are evaluated in the local time of the object, and `until` is compared with local
+//! occurrences as written, without converting it between time zones.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use crate::{
+    json::DestructibleJsonValue,
+    model::{
+        object::{Event, PatchObject, Task, local_to_utc},
+        props,
+        rrule::{CoreByRules, Freq, FreqByRules, RRule, Rscale, Skip, Termination, YearlyByRules},
+        set::Token,
+        string::ImplicitJsonPointer,
+        time::{
+            Date, DateTime, Day, Duration, ExactDuration, Local, Month, Sign, Utc, UtcOffset, Year,
+        },
+    },
+};
+use rfc5545_types::time::DateTimeOrDate;
+
+/// The number of consecutive excluded occurrences after which a query gives up.
+const MAX_SKIPPED_OCCURRENCES: usize = 10_000;
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+/// An error returned if a recurrence rule has parts which cannot be evaluated without expanding
+/// the full recurrence set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("the recurrence rule has parts which require full expansion")]
+pub struct UnsupportedRuleError;
+
+impl<V: DestructibleJsonValue> Event<V> {
+    /// Returns the recurrence ID of the first occurrence of this event which starts after
+    /// `after`, or `None` if there are no more occurrences.
+    ///
+    /// The occurrences are generated by `recurrenceRules` less `excludedRecurrenceRules`, plus the
+    /// keys of `recurrenceOverrides` whose patches do not exclude them; an override which moves
+    /// an occurrence is not taken into account. A non-recurring event has the single occurrence
+    /// at its `start`. `offset` returns the offset from UTC of the `timeZone` of the event at the
+    /// given local time, and a floating event, or one whose time zone cannot be resolved, is
+    /// treated as though it were in UTC.
+    ///
+    /// Returns an error if a rule has `byX` parts or a non-Gregorian `rscale`, and `None` if
+    /// more than 10,000 consecutive occurrences are excluded.
+    pub fn next_occurrence(
+        &self,
+        after: DateTime<Utc>,
+        offset: impl FnOnce(&str, DateTime<Local>) -> Option<UtcOffset>,
+    ) -> Result<Option<DateTime<Local>>, UnsupportedRuleError> {
+        let after = to_local(after, self.time_zone().map(String::as_str), offset);
+        Series {
+            anchor: *self.start(),
+            rules: self.recurrence_rules().map_or(&[], Vec::as_slice),
+            excluded_rules: self.excluded_recurrence_rules().map_or(&[], Vec::as_slice),
+            overrides: self.recurrence_overrides(),
+        }
+        .next_after(after)
+    }
+}
+
+impl<V: DestructibleJsonValue> Task<V> {
+    /// Returns the `due` date-time of the first occurrence of this task which is due after
+    /// `after`, or `None` if the task has no `due` property or no more occurrences.
+    ///
+    /// The occurrences are anchored at the `start` of the task if it has one, in which case each
+    /// occurrence is due at the same offset from its start as the task itself, and at `due`
+    /// otherwise. See [`Event::next_occurrence`] for the other details.
+    pub fn next_due(
+        &self,
+        after: DateTime<Utc>,
+        offset: impl FnOnce(&str, DateTime<Local>) -> Option<UtcOffset>,
+    ) -> Result<Option<DateTime<Local>>, UnsupportedRuleError> {
+        let Some(&due) = self.due() else {
+            return Ok(None);
+        };
+        let anchor = self.start().copied().unwrap_or(due);
+        let lead = due.signed_duration_since(&anchor);
+
+        let after = to_local(after, self.time_zone().map(String::as_str), offset);
+        let Some(after) = after.checked_add_signed(-lead) else {
+            return Ok(None);
+        };
+        let next = Series {
+            anchor,
+            rules: self.recurrence_rules().map_or(&[], Vec::as_slice),
+            excluded_rules: self.excluded_recurrence_rules().map_or(&[], Vec::as_slice),
+            overrides: self.recurrence_overrides(),
+        }
+        .next_after(after)?;
+        Ok(next.and_then(|next| next.checked_add_signed(lead)))
+    }
+}
+
+impl<V> PatchObject<V> {
+    /// Returns `true` iff this patch sets `excluded` to `true`.
+    pub(crate) fn excludes(&self) -> bool
+    where
+        V: DestructibleJsonValue,
+    {
+        let pointer = ImplicitJsonPointer::new(props::EXCLUDED).expect("a valid pointer");
+        self.get(pointer)
+            .is_some_and(|value| value.try_as_bool() == Ok(true))
+    }
+}
+
+/// The recurrence properties of an event or task.
+struct Series<'a, V> {
+    anchor: DateTime<Local>,
+    rules: &'a [RRule],
+    excluded_rules: &'a [RRule],
+    overrides: Option<&'a HashMap<DateTime<Local>, PatchObject<V>>>,
+}
+
+impl<V: DestructibleJsonValue> Series<'_, V> {
+    /// Returns the first occurrence later than `after`.
+    fn next_after(
+        &self,
+        mut after: DateTime<Local>,
+    ) -> Result<Option<DateTime<Local>>, UnsupportedRuleError> {
+        if self.rules.is_empty() {
+            return Ok((self.anchor > after).then_some(self.anchor));
+        }
+        for rule in self.rules.iter().chain(self.excluded_rules) {
+            check_simple(rule)?;
+        }
+
+        for _ in 0..MAX_SKIPPED_OCCURRENCES {
+            let overrides = self.overrides.into_iter().flatten();
+            let next = (self.rules.iter())
+                .filter_map(|rule| next_in_rule(self.anchor, rule, after, false))
+                .chain(overrides.map(|(id, _)| *id).filter(|id| *id > after))
+                .min();
+            let Some(next) = next else {
+                return Ok(None);
+            };
+
+            let excluded = match self.overrides.and_then(|overrides| overrides.get(&next)) {
+                Some(patch) => patch.excludes(),
+                None => self
+                    .excluded_rules
+                    .iter()
+                    .any(|rule| next_in_rule(self.anchor, rule, next, true) == Some(next)),
+            };
+            if !excluded {
+                return Ok(Some(next));
+            }
+            after = next;
+        }
+
+        Ok(None)
+    }
+}
+
+/// Returns an error unless `rule` is simple enough for [`next_in_rule`].
+fn check_simple(rule: &RRule) -> Result<(), UnsupportedRuleError> {
+    let no_freq_by_rules = match &rule.freq {
+        FreqByRules::Secondly(rules)
+        | FreqByRules::Minutely(rules)
+        | FreqByRules::Hourly(rules) => rules.by_month_day.is_none() && rules.by_year_day.is_none(),
+        FreqByRules::Daily(rule) | FreqByRules::Monthly(rule) => rule.by_month_day.is_none(),
+        FreqByRules::Weekly => true,
+        FreqByRules::Yearly(rules) => *rules == YearlyByRules::default(),
+    };
+    let gregorian = matches!(rule.rscale, None | Some(Token::Known(Rscale::Gregorian)));
+
+    if no_freq_by_rules
+        && rule.core_by_rules == CoreByRules::default()
+        && gregorian
+        && matches!(rule.skip, None | Some(Skip::Omit))
+    {
+        Ok(())
+    } else {
+        Err(UnsupportedRuleError)
+    }
+}
+
+/// Returns the first occurrence of the simple `rule` anchored at `start` which is later than
+/// `after`, or at least `after` if `inclusive` is set.
+fn next_in_rule(
+    start: DateTime<Local>,
+    rule: &RRule,
+    after: DateTime<Local>,
+    inclusive: bool,
+) -> Option<DateTime<Local>> {
+    let interval = rule.interval.map_or(1, |interval| interval.get().get());
+    let count = match rule.termination {
+        Some(Termination::Count(count)) => Some(count),
+        _ => None,
+    };
+    let is_due = |candidate: DateTime<Local>| {
+        if inclusive {
+            candidate >= after
+        } else {
+            candidate > after
+        }
+    };
+
+    let (index, candidate) = match Freq::from(&rule.freq) {
+        Freq::Monthly => next_in_months(start, interval, count, is_due, after)?,
+        Freq::Yearly => next_in_months(start, interval.checked_mul(12)?, count, is_due, after)?,
+        freq => {
+            let seconds = match freq {
+                Freq::Secondly => 1,
+                Freq::Minutely => 60,
+                Freq::Hourly => 3600,
+                Freq::Daily => 86_400,
+                _ => 604_800,
+            };
+            let period = u128::from(interval) * seconds * NANOS_PER_SECOND;
+
+            let index = if after < start {
+                0
+            } else {
+                let elapsed = after
+                    .signed_duration_since(&start)
+                    .duration
+                    .to_exact()
+                    .ok()?
+                    .as_nanos();
+                let index = elapsed / period;
+                if inclusive && elapsed % period == 0 {
+                    index
+                } else {
+                    index + 1
+                }
+            };
+            let offset = ExactDuration::from_nanos(index.checked_mul(period)?)?;
+            (
+                u64::try_from(index).ok()?,
+                start.checked_add(Duration::Exact(offset))?,
+            )
+        }
+    };
+
+    if count.is_some_and(|count| index >= count) {
+        return None;
+    }
+    match rule.termination {
+        Some(Termination::Until(DateTimeOrDate::Date(until))) if candidate.date > until => None,
+        Some(Termination::Until(DateTimeOrDate::DateTime(until)))
+            if (candidate.date, candidate.time) > (until.date, until.time) =>
+        {
+            None
+        }
+        _ => Some(candidate),
+    }
+}
+
+/// Returns the index and the date-time of the first occurrence of a rule repeating every
+/// `step` months from `start` which satisfies `is_due`.
+///
+/// Months in which the day of `start` does not exist are skipped without being counted. If that
+/// can happen and the rule has a `count`, every period from `start` is visited to keep the index
+/// exact; otherwise, the search starts at the month of `after`.
+fn next_in_months(
+    start: DateTime<Local>,
+    step: u64,
+    count: Option<u64>,
+    is_due: impl Fn(DateTime<Local>) -> bool,
+    after: DateTime<Local>,
+) -> Option<(u64, DateTime<Local>)> {
+    let months = |date: Date| i64::from(date.year().get()) * 12 + i64::from(month_index(date));
+    let day = start.date.day() as u8;
+    let always_valid = match step % 12 {
+        0 => !(start.date.month() == Month::Feb && day == 29),
+        _ => day <= 28,
+    };
+
+    let mut period = 0;
+    if always_valid || count.is_none() {
+        let elapsed = months(after.date) - months(start.date);
+        period = u64::try_from(elapsed).unwrap_or(0) / step;
+    }
+    let mut index = period;
+
+    loop {
+        let total =
+            months(start.date).checked_add(i64::try_from(period.checked_mul(step)?).ok()?)?;
+        let year = Year::new(u16::try_from(total / 12).ok()?).ok()?;
+        let month = Month::new((total % 12) as u8 + 1).ok()?;
+
+        if let Ok(date) = Date::new(year, month, Day::new(day).ok()?) {
+            let candidate = DateTime { date, ..start };
+            if is_due(candidate) {
+                return Some((index, candidate));
+            }
+            index += 1;
+            if count.is_some_and(|count| index >= count) {
+                return None;
+            }
+        }
+        period += 1;
+    }
+}
+
+/// Returns the zero-based index of the month of `date`.
+fn month_index(date: Date) -> u8 {
+    date.month().number().get() - 1
+}
+
+/// Converts `after` into the local time of an object in the given time zone.
+fn to_local(
+    after: DateTime<Utc>,
+    time_zone: Option<&str>,
+    offset: impl FnOnce(&str, DateTime<Local>) -> Option<UtcOffset>,
+) -> DateTime<Local> {
+    let naive = DateTime {
+        date: after.date,
+        time: after.time,
+        marker: Local,
+    };
+    let Some(offset) = time_zone.and_then(|time_zone| offset(time_zone, naive)) else {
+        return naive;
+    };
+
+    // local_to_utc subtracts the offset, so negating it converts in the other direction
+    let inverse = UtcOffset {
+        sign: match offset.sign {
+            Sign::Pos => Sign::Neg,
+            Sign::Neg => Sign::Pos,
+        },
+        ..offset
+    };
+    local_to_utc(naive, inverse)
+        .map(|local| DateTime {
+            date: local.date,
+            time: local.time,
+            marker: Local,
+        })
+        .unwrap_or(naive)
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{
+        json::TryFromJson,
+        model::time::{Hour, Minute, NonLeapSecond},
+    };
+
+    fn local(s: &str) -> DateTime<Local> {
+        DateTime::try_from_json(Value::from(s)).unwrap()
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::try_from_json(Value::from(s)).unwrap()
+    }
+
+    fn no_zones(_: &str, _: DateTime<Local>) -> Option<UtcOffset> {
+        None
+    }
+
+    fn event(start: &str, rules: Value, extra: Value) -> Event<Value> {
+        let mut json = json!({
+            "@type": "Event",
+            "uid": "series",
+            "start": start,
+            "recurrenceRules": rules
+        });
+        json.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        Event::try_from_json(json).unwrap()
+    }
+
+    fn next(event: &Event<Value>, after: &str) -> Option<DateTime<Local>> {
+        event.next_occurrence(utc(after), no_zones).unwrap()
+    }
+
+    #[test]
+    fn exact_frequencies() {
+        let weekly = event(
+            "2000-01-03T09:00:00",
+            json!([{ "@type": "RecurrenceRule", "frequency": "weekly", "interval": 2 }]),
+            json!({}),
+        );
+        assert_eq!(
+            next(&weekly, "1999-01-01T00:00:00Z"),
+            Some(local("2000-01-03T09:00:00"))
+        );
+        assert_eq!(
+            next(&weekly, "2000-01-03T09:00:00Z"),
+            Some(local("2000-01-17T09:00:00"))
+        );
+        // more than a thousand periods later
+        assert_eq!(
+            next(&weekly, "2024-06-12T12:00:00Z"),
+            Some(local("2024-06-17T09:00:00"))
+        );
+
+        let hourly = event(
+            "2024-01-01T09:30:00",
+            json!([{ "@type": "RecurrenceRule", "frequency": "hourly", "count": 3 }]),
+            json!({}),
+        );
+        assert_eq!(
+            next(&hourly, "2024-01-01T10:30:00Z"),
+            Some(local("2024-01-01T11:30:00"))
+        );
+        assert_eq!(next(&hourly, "2024-01-01T11:30:00Z"), None);
+
+        let until = event(
+            "2024-01-01T09:00:00",
+            json!([{ "@type": "RecurrenceRule", "frequency": "daily", "until": "2024-01-05T09:00:00" }]),
+            json!({}),
+        );
+        assert_eq!(
+            next(&until, "2024-01-04T12:00:00Z"),
+            Some(local("2024-01-05T09:00:00"))
+        );
+        assert_eq!(next(&until, "2024-01-05T09:00:00Z"), None);
+    }
+
+    #[test]
+    fn monthly_and_yearly() {
+        let monthly = event(
+            "2024-01-31T09:00:00",
+            json!([{ "@type": "RecurrenceRule", "frequency": "monthly" }]),
+            json!({}),
+        );
+        assert_eq!(
+            next(&monthly, "2024-02-01T00:00:00Z"),
+            Some(local("2024-03-31T09:00:00"))
+        );
+        assert_eq!(
+            next(&monthly, "2030-04-15T00:00:00Z"),
+            Some(local("2030-05-31T09:00:00"))
+        );
+
+        // the 5th occurrence is in August, since February, April, and June are skipped
+        let counted = event(
+            "2024-01-31T09:00:00",
+            json!([{ "@type": "RecurrenceRule", "frequency": "monthly", "count": 5 }]),
+            json!({}),
+        );
+        assert_eq!(
+            next(&counted, "2024-07-01T00:00:00Z"),
+            Some(local("2024-07-31T09:00:00"))
+        );
+        assert_eq!(
+            next(&counted, "2024-08-01T00:00:00Z"),
+            Some(local("2024-08-31T09:00:00"))
+        );
+        assert_eq!(next(&counted, "2024-09-01T00:00:00Z"), None);
+
+        let leap = event(
+            "2024-02-29T12:00:00",
+            json!([{ "@type": "RecurrenceRule", "frequency": "yearly" }]),
+            json!({}),
+        );
+        assert_eq!(
+            next(&leap, "2024-03-01T00:00:00Z"),
+            Some(local("2028-02-29T12:00:00"))
+        );
+    }
+
+    #[test]
+    fn exclusions_overrides_and_time_zones() {
+        let daily = event(
+            "2024-01-01T09:00:00",
+            json!([{ "@type": "RecurrenceRule", "frequency": "daily" }]),
+            json!({
+                "timeZone": "Asia/Tokyo",
+                "excludedRecurrenceRules": [
+                    { "@type": "RecurrenceRule", "frequency": "weekly", "interval": 1 }
+                ],
+                "recurrenceOverrides": {
+                    "2024-01-02T09:00:00": { "excluded": true },
+                    "2024-01-03T09:00:00": { "title": "Moved" },
+                    "2024-01-08T09:00:00": { "title": "Kept" },
+                    "2024-01-01T18:00:00": { "title": "Extra" }
+                }
+            }),
+        );
+        let tokyo = |_: &str, _: DateTime<Local>| {
+            Some(UtcOffset {
+                sign: Sign::Pos,
+                hour: Hour::H09,
+                minute: Minute::M00,
+                second: NonLeapSecond::S00,
+            })
+        };
+        let next = |after| daily.next_occurrence(utc(after), tokyo).unwrap();
+
+        // 01:00Z is 10:00 in Tokyo
+        assert_eq!(
+            next("2024-01-01T01:00:00Z"),
+            Some(local("2024-01-01T18:00:00"))
+        );
+        assert_eq!(
+            next("2024-01-01T09:00:00Z"),
+            Some(local("2024-01-03T09:00:00"))
+        );
+        // the 8th matches the excluded rule, but its override keeps it
+        assert_eq!(
+            next("2024-01-07T00:00:00Z"),
+            Some(local("2024-01-08T09:00:00"))
+        );
+        assert_eq!(
+            next("2024-01-14T12:00:00Z"),
+            Some(local("2024-01-16T09:00:00"))
+        );
+
+        let complex = event(
+            "2024-01-01T09:00:00",
+            json!([{ "@type": "RecurrenceRule", "frequency": "weekly", "byDay": [{ "@type": "NDay", "day": "mo" }] }]),
+            json!({}),
+        );
+        assert_eq!(
+            complex.next_occurrence(utc("2024-01-01T00:00:00Z"), no_zones),
+            Err(UnsupportedRuleError)
+        );
+    }
+
+    #[test]
+    fn next_due() {
+        let task = Task::<Value>::try_from_json(json!({
+            "@type": "Task",
+            "uid": "report",
+            "start": "2024-01-01T09:00:00",
+            "due": "2024-01-03T17:00:00",
+            "recurrenceRules": [{ "@type": "RecurrenceRule", "frequency": "weekly" }]
+        }))
+        .unwrap();
+        assert_eq!(
+            task.next_due(utc("2024-01-04T00:00:00Z"), no_zones),
+            Ok(Some(local("2024-01-10T17:00:00")))
+        );
+
+        let undated =
+            Task::<Value>::try_from_json(json!({ "@type": "Task", "uid": "todo" })).unwrap();
+        assert_eq!(
+            undated.next_due(utc("2024-01-04T00:00:00Z"), no_zones),
+            Ok(None)
+        );
+    }
+}
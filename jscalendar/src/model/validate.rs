@@ -0,0 +1,542 @@
+//! Sanity checks for calendar objects.
+//!
+//! Many combinations of properties are valid according to RFC 8984 but cannot describe what
+//! their producer intended, such as a task which is due before it starts, or a group containing
+//! two events with the same UID. The checks in this module report such combinations as
+//! [`Violation`]s, each with a distinct [`ViolationCode`] and the location of the offending
+//! value, so that callers can decide how to handle them.
+
+use std::collections::{HashMap, HashSet};
+
+use strum::Display;
+
+use crate::{
+    json::JsonValue,
+    model::{
+        object::{
+            Alert, Event, Group, MaybeUnknown, Task, TaskOrEvent, TimeZone, Trigger, escape_segment,
+        },
+        rrule::{RRule, Termination},
+        string::{CustomTimeZoneId, Id, ImplicitJsonPointer, Uid},
+        time::{Date, DateTime, Duration, Local, Utc},
+    },
+};
+use rfc5545_types::time::DateTimeOrDate;
+
+type AlertMap<V> = HashMap<Box<Id>, MaybeUnknown<Alert<V>, V>>;
+
+/// The number of days by which an absolute alert trigger may lie outside the time span of its
+/// object before it is reported as [`ViolationCode::AlertFarFromObject`].
+pub const ALERT_WINDOW_DAYS: i64 = 366;
+
+/// The kind of a [`Violation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Display)]
+#[non_exhaustive]
+#[strum(serialize_all = "kebab-case")]
+pub enum ViolationCode {
+    /// The `due` time of a task is before its `start`.
+    DueBeforeStart,
+    /// The `until` date of a recurrence rule is before the `start` of its object.
+    UntilBeforeStart,
+    /// A referenced time zone has a `validUntil` time in the past.
+    TimeZoneExpired,
+    /// An absolute alert trigger lies more than [`ALERT_WINDOW_DAYS`] days outside the time span
+    /// of its object.
+    AlertFarFromObject,
+    /// An entry of a group has the same `uid` and `recurrenceId` as an earlier entry.
+    DuplicateUid,
+    /// An entry of a group has a `recurrenceId`, but the group has no entry with the same `uid`
+    /// and no `recurrenceId`.
+    MissingMaster,
+    /// The `recurrenceId` of an entry of a group is not an occurrence of its master entry.
+    UnmatchedRecurrenceId,
+    /// A `relatedTo` key refers to a UID which does not occur in the group.
+    UnknownRelatedUid,
+    /// The `locationId` of a participant is not a key of the `locations` of its object.
+    UnknownLocation,
+    /// The `duration` of an event or the `estimatedDuration` of a task is explicitly zero, which
+    /// consumers treat inconsistently as either an instant or no time at all.
+    ZeroDuration,
+}
+
+/// A value which failed a sanity check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// The kind of violation.
+    pub code: ViolationCode,
+    /// The location of the offending value.
+    pub pointer: Box<ImplicitJsonPointer>,
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}", self.code, self.pointer)
+    }
+}
+
+impl<V: JsonValue> Event<V> {
+    /// Returns `true` iff this event takes no time, because its `duration` is absent or zero.
+    pub fn is_instantaneous(&self) -> bool {
+        self.duration().is_none_or(Duration::is_zero)
+    }
+
+    /// Returns the temporal sanity violations of this event, where `now` is used to decide
+    /// whether time zones have expired.
+    ///
+    /// Since the time zone of the event is not resolved, alert triggers are compared with the
+    /// time span of the event only to the day.
+    pub fn temporal_violations(&self, now: DateTime<Utc>) -> Vec<Violation> {
+        let start = *self.start();
+        let end = self
+            .duration()
+            .and_then(|duration| start.checked_add(*duration))
+            .unwrap_or(start);
+
+        let mut violations = Vec::new();
+        if self.duration().is_some_and(Duration::is_zero) {
+            violations.push(violation(ViolationCode::ZeroDuration, "duration".into()));
+        }
+        check_until(&mut violations, self.recurrence_rules(), start);
+        check_time_zones(
+            &mut violations,
+            self.time_zones(),
+            self.referenced_time_zones(),
+            now,
+        );
+        check_alerts(&mut violations, self.alerts(), (start, end));
+        violations
+    }
+}
+
+impl<V: JsonValue> Task<V> {
+    /// Returns `true` iff the `estimatedDuration` of this task is present and zero. Unlike the
+    /// `duration` of an event, an absent `estimatedDuration` means that it is unknown.
+    pub fn has_zero_estimated_duration(&self) -> bool {
+        self.estimated_duration().is_some_and(Duration::is_zero)
+    }
+
+    /// Returns the temporal sanity violations of this task; see [`Event::temporal_violations`].
+    pub fn temporal_violations(&self, now: DateTime<Utc>) -> Vec<Violation> {
+        let mut violations = Vec::new();
+
+        if self.has_zero_estimated_duration() {
+            violations.push(violation(
+                ViolationCode::ZeroDuration,
+                "estimatedDuration".into(),
+            ));
+        }
+
+        if let (Some(start), Some(due)) = (self.start(), self.due())
+            && due < start
+        {
+            violations.push(violation(ViolationCode::DueBeforeStart, "due".into()));
+        }
+
+        if let Some(start) = self.start().or(self.due()) {
+            check_until(&mut violations, self.recurrence_rules(), *start);
+        }
+
+        check_time_zones(
+            &mut violations,
+            self.time_zones(),
+            self.referenced_time_zones(),
+            now,
+        );
+
+        let span = match (self.start(), self.due()) {
+            (Some(start), Some(due)) => Some((*start, *due.max(start))),
+            (Some(time), None) | (None, Some(time)) => Some((*time, *time)),
+            (None, None) => None,
+        };
+        if let Some(span) = span {
+            check_alerts(&mut violations, self.alerts(), span);
+        }
+
+        violations
+    }
+}
+
+impl<V: JsonValue> Group<V> {
+    /// Returns the violations of referential integrity among the entries of this group.
+    ///
+    /// An entry with a `recurrenceId` must have a master entry with the same `uid`, and its
+    /// `recurrenceId` must be an occurrence of the master: either the start of the master, a key
+    /// of its `recurrenceOverrides`, or a time for which `occurs` returns `true`. Since this crate
+    /// does not expand recurrence rules, `occurs` is only called for masters with
+    /// `recurrenceRules`, and must decide whether they generate an occurrence at the given time.
+    pub fn check_integrity<F>(&self, mut occurs: F) -> Vec<Violation>
+    where
+        F: FnMut(&TaskOrEvent<V>, DateTime<Local>) -> bool,
+    {
+        let entries = self.entries();
+        let uids: HashSet<&Uid> = entries.iter().map(entry_uid).collect();
+        let mut seen = HashSet::new();
+        let mut violations = Vec::new();
+
+        for (index, entry) in entries.iter().enumerate() {
+            let uid = entry_uid(entry);
+            let recurrence_id = entry_recurrence_id(entry);
+
+            if !seen.insert((uid, recurrence_id)) {
+                violations.push(violation(
+                    ViolationCode::DuplicateUid,
+                    format!("entries/{index}/uid"),
+                ));
+            }
+
+            if let Some(recurrence_id) = recurrence_id {
+                let master = entries.iter().find(|master| {
+                    entry_uid(master) == uid && entry_recurrence_id(master).is_none()
+                });
+                let code = match master {
+                    None => Some(ViolationCode::MissingMaster),
+                    Some(master) if !is_occurrence(master, recurrence_id, &mut occurs) => {
+                        Some(ViolationCode::UnmatchedRecurrenceId)
+                    }
+                    Some(_) => None,
+                };
+                if let Some(code) = code {
+                    violations.push(violation(code, format!("entries/{index}/recurrenceId")));
+                }
+            }
+
+            let related_to = match entry {
+                TaskOrEvent::Event(event) => event.related_to(),
+                TaskOrEvent::Task(task) => task.related_to(),
+            };
+            for related in related_to.into_iter().flat_map(HashMap::keys) {
+                if !uids.contains(&**related) {
+                    violations.push(violation(
+                        ViolationCode::UnknownRelatedUid,
+                        format!(
+                            "entries/{index}/relatedTo/{}",
+                            escape_segment(related.as_str())
+                        ),
+                    ));
+                }
+            }
+
+            let dangling = match entry {
+                TaskOrEvent::Event(event) => event.dangling_location_ids(),
+                TaskOrEvent::Task(task) => task.dangling_location_ids(),
+            };
+            for participant in dangling {
+                violations.push(violation(
+                    ViolationCode::UnknownLocation,
+                    format!(
+                        "entries/{index}/participants/{}/locationId",
+                        escape_segment(participant.as_str())
+                    ),
+                ));
+            }
+        }
+
+        violations
+    }
+}
+
+/// Returns the UID of a group entry.
+fn entry_uid<V: JsonValue>(entry: &TaskOrEvent<V>) -> &Uid {
+    match entry {
+        TaskOrEvent::Event(event) => event.uid(),
+        TaskOrEvent::Task(task) => task.uid(),
+    }
+}
+
+/// Returns the recurrence ID of a group entry.
+fn entry_recurrence_id<V: JsonValue>(entry: &TaskOrEvent<V>) -> Option<DateTime<Local>> {
+    match entry {
+        TaskOrEvent::Event(event) => event.recurrence_id().copied(),
+        TaskOrEvent::Task(task) => task.recurrence_id().copied(),
+    }
+}
+
+/// Returns `true` iff `recurrence_id` is an occurrence of `master`, as described by
+/// [`Group::check_integrity`].
+fn is_occurrence<V: JsonValue>(
+    master: &TaskOrEvent<V>,
+    recurrence_id: DateTime<Local>,
+    occurs: &mut impl FnMut(&TaskOrEvent<V>, DateTime<Local>) -> bool,
+) -> bool {
+    let (start, overrides, rules) = match master {
+        TaskOrEvent::Event(event) => (
+            Some(event.start()),
+            event.recurrence_overrides(),
+            event.recurrence_rules(),
+        ),
+        TaskOrEvent::Task(task) => (
+            task.start().or(task.due()),
+            task.recurrence_overrides(),
+            task.recurrence_rules(),
+        ),
+    };
+
+    start == Some(&recurrence_id)
+        || overrides.is_some_and(|overrides| overrides.contains_key(&recurrence_id))
+        || (rules.is_some() && occurs(master, recurrence_id))
+}
+
+/// Reports each recurrence rule whose `until` date is before `start`.
+fn check_until(
+    violations: &mut Vec<Violation>,
+    rules: Option<&Vec<RRule>>,
+    start: DateTime<Local>,
+) {
+    for (index, rule) in rules.into_iter().flatten().enumerate() {
+        let before_start = match rule.termination {
+            Some(Termination::Until(DateTimeOrDate::Date(until))) => until < start.date,
+            Some(Termination::Until(DateTimeOrDate::DateTime(until))) => {
+                (until.date, until.time) < (start.date, start.time)
+            }
+            _ => false,
+        };
+
+        if before_start {
+            violations.push(violation(
+                ViolationCode::UntilBeforeStart,
+                format!("recurrenceRules/{index}/until"),
+            ));
+        }
+    }
+}
+
+/// Reports each referenced time zone whose `validUntil` time is before `now`.
+fn check_time_zones<V>(
+    violations: &mut Vec<Violation>,
+    time_zones: Option<&HashMap<Box<CustomTimeZoneId>, TimeZone<V>>>,
+    referenced: HashSet<&str>,
+    now: DateTime<Utc>,
+) {
+    for (id, time_zone) in time_zones.into_iter().flatten() {
+        let id = id.to_string();
+        if referenced.contains(id.as_str())
+            && time_zone
+                .valid_until()
+                .is_some_and(|valid_until| *valid_until < now)
+        {
+            violations.push(violation(
+                ViolationCode::TimeZoneExpired,
+                format!("timeZones/{}/validUntil", escape_segment(&id)),
+            ));
+        }
+    }
+}
+
+/// Reports each absolute alert trigger which is far from the time span `(start, end)`.
+fn check_alerts<V: JsonValue>(
+    violations: &mut Vec<Violation>,
+    alerts: Option<&AlertMap<V>>,
+    (start, end): (DateTime<Local>, DateTime<Local>),
+) {
+    let days_outside = |date: Date| start.date.days_since(date).max(date.days_since(end.date));
+
+    for (id, alert) in alerts.into_iter().flatten() {
+        let Some(Trigger::Absolute(trigger)) = alert.as_known().map(Alert::trigger) else {
+            continue;
+        };
+
+        if days_outside(trigger.when().date) > ALERT_WINDOW_DAYS {
+            violations.push(violation(
+                ViolationCode::AlertFarFromObject,
+                format!("alerts/{}/trigger/when", escape_segment(id.as_str())),
+            ));
+        }
+    }
+}
+
+/// Returns a violation at the given pointer, which must be valid.
+fn violation(code: ViolationCode, pointer: String) -> Violation {
+    Violation {
+        code,
+        pointer: ImplicitJsonPointer::new(&pointer)
+            .expect("violation pointers are escaped")
+            .into(),
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::json::TryFromJson;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::try_from_json(Value::from("2024-06-01T00:00:00Z")).unwrap()
+    }
+
+    fn codes(violations: &[Violation]) -> Vec<(ViolationCode, String)> {
+        let mut codes: Vec<_> = violations
+            .iter()
+            .map(|violation| (violation.code, violation.pointer.to_string()))
+            .collect();
+        codes.sort();
+        codes
+    }
+
+    #[test]
+    fn event_violations() {
+        let event = Event::<Value>::try_from_json(json!({
+            "@type": "Event",
+            "uid": "sanity",
+            "start": "2024-03-01T10:00:00",
+            "duration": "PT1H",
+            "timeZone": "/Custom/Zone",
+            "recurrenceRules": [
+                { "@type": "RecurrenceRule", "frequency": "weekly", "until": "2024-02-01T00:00:00" },
+                { "@type": "RecurrenceRule", "frequency": "weekly", "until": "2024-12-01T00:00:00" }
+            ],
+            "timeZones": {
+                "/Custom/Zone": { "@type": "TimeZone", "tzId": "Custom", "validUntil": "2024-01-01T00:00:00Z" },
+                "/Unused": { "@type": "TimeZone", "tzId": "Unused", "validUntil": "2024-01-01T00:00:00Z" }
+            },
+            "alerts": {
+                "near": { "@type": "Alert", "trigger": { "@type": "AbsoluteTrigger", "when": "2024-02-28T09:00:00Z" } },
+                "far": { "@type": "Alert", "trigger": { "@type": "AbsoluteTrigger", "when": "2026-03-01T09:00:00Z" } },
+                "offset": { "@type": "Alert", "trigger": { "@type": "OffsetTrigger", "offset": "-P800D" } }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            codes(&event.temporal_violations(now())),
+            [
+                (
+                    ViolationCode::UntilBeforeStart,
+                    "recurrenceRules/0/until".into()
+                ),
+                (
+                    ViolationCode::TimeZoneExpired,
+                    "timeZones/~1Custom~1Zone/validUntil".into()
+                ),
+                (
+                    ViolationCode::AlertFarFromObject,
+                    "alerts/far/trigger/when".into()
+                ),
+            ]
+        );
+        assert_eq!(
+            ViolationCode::TimeZoneExpired.to_string(),
+            "time-zone-expired"
+        );
+    }
+
+    #[test]
+    fn task_violations() {
+        let task = Task::<Value>::try_from_json(json!({
+            "@type": "Task",
+            "uid": "task",
+            "start": "2024-03-02T10:00:00",
+            "due": "2024-03-01T10:00:00"
+        }))
+        .unwrap();
+        assert_eq!(
+            codes(&task.temporal_violations(now())),
+            [(ViolationCode::DueBeforeStart, "due".into())]
+        );
+
+        let task =
+            Task::<Value>::try_from_json(json!({ "@type": "Task", "uid": "empty" })).unwrap();
+        assert!(task.temporal_violations(now()).is_empty());
+        assert!(!task.has_zero_estimated_duration());
+    }
+
+    #[test]
+    fn zero_durations() {
+        let event = Event::<Value>::try_from_json(json!({
+            "@type": "Event",
+            "uid": "zero",
+            "start": "2024-03-01T10:00:00",
+            "duration": "P0D"
+        }))
+        .unwrap();
+        assert!(event.is_instantaneous());
+        assert_eq!(
+            codes(&event.temporal_violations(now())),
+            [(ViolationCode::ZeroDuration, "duration".into())]
+        );
+
+        // an absent duration is the usual way to describe an instant
+        let event = Event::<Value>::try_from_json(json!({
+            "@type": "Event",
+            "uid": "instant",
+            "start": "2024-03-01T10:00:00"
+        }))
+        .unwrap();
+        assert!(event.is_instantaneous());
+        assert!(event.temporal_violations(now()).is_empty());
+
+        let task = Task::<Value>::try_from_json(json!({
+            "@type": "Task",
+            "uid": "task",
+            "estimatedDuration": "PT0S"
+        }))
+        .unwrap();
+        assert!(task.has_zero_estimated_duration());
+        assert_eq!(
+            codes(&task.temporal_violations(now())),
+            [(ViolationCode::ZeroDuration, "estimatedDuration".into())]
+        );
+    }
+
+    #[test]
+    fn group_integrity() {
+        let group = Group::<Value>::try_from_json(json!({
+            "@type": "Group",
+            "uid": "group",
+            "entries": [
+                {
+                    "@type": "Event",
+                    "uid": "weekly",
+                    "start": "2024-01-01T09:00:00",
+                    "recurrenceRules": [{ "@type": "RecurrenceRule", "frequency": "weekly" }],
+                    "relatedTo": {
+                        "task": { "@type": "Relation" },
+                        "elsewhere": { "@type": "Relation" }
+                    },
+                    "locations": { "room": { "@type": "Location", "name": "Room" } },
+                    "participants": {
+                        "alice": { "@type": "Participant", "locationId": "room" },
+                        "bob": { "@type": "Participant", "locationId": "home" }
+                    }
+                },
+                { "@type": "Event", "uid": "weekly", "start": "2024-01-08T10:00:00", "recurrenceId": "2024-01-08T09:00:00" },
+                { "@type": "Event", "uid": "weekly", "start": "2024-01-09T10:00:00", "recurrenceId": "2024-01-09T09:00:00" },
+                { "@type": "Event", "uid": "orphan", "start": "2024-01-08T10:00:00", "recurrenceId": "2024-01-08T09:00:00" },
+                { "@type": "Task", "uid": "task" },
+                { "@type": "Task", "uid": "task" }
+            ]
+        }))
+        .unwrap();
+
+        let mut calls = 0;
+        let violations = group.check_integrity(|master, recurrence_id| {
+            calls += 1;
+            assert_eq!(master.as_event().unwrap().uid().as_str(), "weekly");
+            recurrence_id.date.weekday() == crate::model::time::Weekday::Monday
+        });
+        assert_eq!(calls, 2);
+        assert_eq!(
+            codes(&violations),
+            [
+                (ViolationCode::DuplicateUid, "entries/5/uid".into()),
+                (
+                    ViolationCode::MissingMaster,
+                    "entries/3/recurrenceId".into()
+                ),
+                (
+                    ViolationCode::UnmatchedRecurrenceId,
+                    "entries/2/recurrenceId".into()
+                ),
+                (
+                    ViolationCode::UnknownRelatedUid,
+                    "entries/0/relatedTo/elsewhere".into()
+                ),
+                (
+                    ViolationCode::UnknownLocation,
+                    "entries/0/participants/bob/locationId".into()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,454 @@
+//! Inspection and placement of vendor properties.
+//!
+//! Members which RFC 8984 does not define are preserved as vendor properties (RFC 8984 §3.3) of
+//! the object in which they occur, including members with a standard name whose value could not
+//! be parsed by a lenient parser. When a peer produces unexpected output, the
+//! [`vendor_members`](Event::vendor_members) of a parsed object list where these members are,
+//! and [`VendorPlacement::Wrapped`] separates them from the standard members on output.
+
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    json::{ConstructibleJsonValue, JsonObject, JsonValue},
+    model::{
+        object::{
+            AbsoluteTrigger, Alert, Event, Group, Link, Location, MaybeUnknown, OffsetTrigger,
+            Participant, Relation, Task, TaskOrEvent, TaskParticipant, TimeZone, TimeZoneRule,
+            Trigger, VirtualLocation, escape_segment,
+        },
+        string::ImplicitJsonPointer,
+    },
+};
+
+/// Where the vendor properties of each object are written by
+/// [`Event::into_json_with`](crate::model::object::Event::into_json_with).
+///
+/// In either case, vendor properties are written after the standard members of their object, so
+/// that they are grouped together by JSON backends which preserve the order of members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum VendorPlacement {
+    /// Vendor properties are members of their object, as RFC 8984 §3.3 requires.
+    #[default]
+    Inline,
+    /// The vendor properties of each object are gathered into a single member of that object
+    /// with the given name, whose value is an object. This is not valid JSCalendar, and is meant
+    /// for debugging.
+    Wrapped(&'static str),
+}
+
+impl<V: JsonValue> Event<V> {
+    /// Returns the location of each vendor property in this event and in the objects nested in
+    /// it, in lexicographic order.
+    pub fn vendor_members(&self) -> Vec<Box<ImplicitJsonPointer>> {
+        collect(self)
+    }
+}
+
+impl<V: JsonValue> Task<V> {
+    /// Returns the location of each vendor property in this task and in the objects nested in
+    /// it, in lexicographic order.
+    pub fn vendor_members(&self) -> Vec<Box<ImplicitJsonPointer>> {
+        collect(self)
+    }
+}
+
+impl<V: JsonValue> Group<V> {
+    /// Returns the location of each vendor property in this group and in its entries, in
+    /// lexicographic order.
+    pub fn vendor_members(&self) -> Vec<Box<ImplicitJsonPointer>> {
+        collect(self)
+    }
+}
+
+/// Moves the vendor properties of `object` and of the objects nested in it into a member named
+/// `key` of their object.
+pub(crate) fn wrap<V: ConstructibleJsonValue>(object: &mut dyn Extensible<V>, key: &str) {
+    let properties = object.take_vendor_properties();
+    if !properties.is_empty() {
+        let mut wrapper = V::Object::with_capacity(properties.len());
+        for (name, value) in properties {
+            wrapper.insert(String::from(name).into(), value);
+        }
+        object.insert_vendor_property(key.into(), V::object(wrapper));
+    }
+
+    object.for_each_child_mut(&mut |child| wrap(child, key));
+}
+
+fn collect<V: JsonValue>(object: &dyn Extensible<V>) -> Vec<Box<ImplicitJsonPointer>> {
+    let mut pointers = Vec::new();
+    visit(object, "", &mut pointers);
+    pointers.sort();
+    pointers
+        .into_iter()
+        .map(|pointer| {
+            ImplicitJsonPointer::new(&pointer)
+                .expect("vendor member pointers are escaped")
+                .into()
+        })
+        .collect()
+}
+
+fn visit<V: JsonValue>(object: &dyn Extensible<V>, prefix: &str, pointers: &mut Vec<String>) {
+    for name in object.vendor_property_names() {
+        pointers.push(format!("{prefix}{}", escape_segment(name)));
+    }
+
+    object.for_each_child(&mut |path, child| visit(child, &format!("{prefix}{path}/"), pointers));
+}
+
+/// An object which may have vendor properties and contain other such objects.
+pub(crate) trait Extensible<V: JsonValue> {
+    fn vendor_property_names(&self) -> Vec<&str>;
+
+    fn take_vendor_properties(&mut self) -> Vec<(Box<str>, V)>;
+
+    fn insert_vendor_property(&mut self, key: Box<str>, value: V);
+
+    /// Calls `f` with each nested object and its escaped path relative to this object.
+    fn for_each_child(&self, f: &mut dyn FnMut(&str, &dyn Extensible<V>));
+
+    /// Calls `f` with each nested object.
+    fn for_each_child_mut(&mut self, f: &mut dyn FnMut(&mut dyn Extensible<V>));
+}
+
+/// Implements [`Extensible`] for a structible type, whose nested objects are in the given fields.
+macro_rules! impl_extensible {
+    ($ty:ident { $($name:literal => $field:ident, $field_mut:ident;)* }) => {
+        impl<V: JsonValue> Extensible<V> for $ty<V> {
+            fn vendor_property_names(&self) -> Vec<&str> {
+                self.vendor_property_iter().map(|(key, _)| &**key).collect()
+            }
+
+            fn take_vendor_properties(&mut self) -> Vec<(Box<str>, V)> {
+                let keys: Vec<Box<str>> =
+                    self.vendor_property_iter().map(|(key, _)| key.clone()).collect();
+                keys.into_iter()
+                    .filter_map(|key| {
+                        let value = self.remove_vendor_property(&key)?;
+                        Some((key, value))
+                    })
+                    .collect()
+            }
+
+            fn insert_vendor_property(&mut self, key: Box<str>, value: V) {
+                $ty::insert_vendor_property(self, key, value);
+            }
+
+            #[allow(unused_variables)]
+            fn for_each_child(&self, f: &mut dyn FnMut(&str, &dyn Extensible<V>)) {
+                $(Children::for_each(self.$field(), $name, f);)*
+            }
+
+            #[allow(unused_variables)]
+            fn for_each_child_mut(&mut self, f: &mut dyn FnMut(&mut dyn Extensible<V>)) {
+                $(Children::for_each_mut(self.$field_mut(), f);)*
+            }
+        }
+    };
+}
+
+impl_extensible!(Event {
+    "relatedTo" => related_to, related_to_mut;
+    "locations" => locations, locations_mut;
+    "virtualLocations" => virtual_locations, virtual_locations_mut;
+    "links" => links, links_mut;
+    "participants" => participants, participants_mut;
+    "alerts" => alerts, alerts_mut;
+    "timeZones" => time_zones, time_zones_mut;
+});
+
+impl_extensible!(Task {
+    "relatedTo" => related_to, related_to_mut;
+    "locations" => locations, locations_mut;
+    "virtualLocations" => virtual_locations, virtual_locations_mut;
+    "links" => links, links_mut;
+    "participants" => participants, participants_mut;
+    "alerts" => alerts, alerts_mut;
+    "timeZones" => time_zones, time_zones_mut;
+});
+
+impl_extensible!(Group {
+    "entries" => entries_opt, entries_opt_mut;
+    "links" => links, links_mut;
+    "timeZones" => time_zones, time_zones_mut;
+});
+
+impl_extensible!(Location {
+    "links" => links, links_mut;
+});
+
+impl_extensible!(VirtualLocation {});
+
+impl_extensible!(Link {});
+
+impl_extensible!(TimeZone {
+    "standard" => standard, standard_mut;
+    "daylight" => daylight, daylight_mut;
+});
+
+impl_extensible!(TimeZoneRule {});
+
+impl_extensible!(Participant {
+    "links" => links, links_mut;
+});
+
+impl_extensible!(TaskParticipant {
+    "links" => links, links_mut;
+});
+
+impl_extensible!(Alert {
+    "relatedTo" => related_to, related_to_mut;
+    "trigger" => trigger_opt, trigger_opt_mut;
+});
+
+impl_extensible!(OffsetTrigger {});
+
+impl_extensible!(AbsoluteTrigger {});
+
+impl_extensible!(Relation {});
+
+// The `entries` of a group and the `trigger` of an alert are required, so their accessors return
+// the value itself rather than an `Option`.
+
+impl<V: JsonValue> Group<V> {
+    fn entries_opt(&self) -> Option<&Vec<TaskOrEvent<V>>> {
+        Some(self.entries())
+    }
+
+    fn entries_opt_mut(&mut self) -> Option<&mut Vec<TaskOrEvent<V>>> {
+        Some(self.entries_mut())
+    }
+}
+
+impl<V: JsonValue> Alert<V> {
+    fn trigger_opt(&self) -> Option<&Trigger<V>> {
+        Some(self.trigger())
+    }
+
+    fn trigger_opt_mut(&mut self) -> Option<&mut Trigger<V>> {
+        Some(self.trigger_mut())
+    }
+}
+
+/// A field which holds nested [`Extensible`] objects.
+trait Children<V: JsonValue> {
+    fn for_each(this: Option<&Self>, path: &str, f: &mut dyn FnMut(&str, &dyn Extensible<V>));
+
+    fn for_each_mut(this: Option<&mut Self>, f: &mut dyn FnMut(&mut dyn Extensible<V>));
+}
+
+impl<K, T, V> Children<V> for HashMap<K, T>
+where
+    K: fmt::Display,
+    T: Child<V>,
+    V: JsonValue,
+{
+    fn for_each(this: Option<&Self>, path: &str, f: &mut dyn FnMut(&str, &dyn Extensible<V>)) {
+        for (key, value) in this.into_iter().flatten() {
+            if let Some(child) = value.as_extensible() {
+                f(
+                    &format!("{path}/{}", escape_segment(&key.to_string())),
+                    child,
+                );
+            }
+        }
+    }
+
+    fn for_each_mut(this: Option<&mut Self>, f: &mut dyn FnMut(&mut dyn Extensible<V>)) {
+        for value in this.into_iter().flat_map(HashMap::values_mut) {
+            if let Some(child) = value.as_extensible_mut() {
+                f(child);
+            }
+        }
+    }
+}
+
+impl<T: Child<V>, V: JsonValue> Children<V> for Vec<T> {
+    fn for_each(this: Option<&Self>, path: &str, f: &mut dyn FnMut(&str, &dyn Extensible<V>)) {
+        for (index, value) in this.into_iter().flatten().enumerate() {
+            if let Some(child) = value.as_extensible() {
+                f(&format!("{path}/{index}"), child);
+            }
+        }
+    }
+
+    fn for_each_mut(this: Option<&mut Self>, f: &mut dyn FnMut(&mut dyn Extensible<V>)) {
+        for value in this.into_iter().flatten() {
+            if let Some(child) = value.as_extensible_mut() {
+                f(child);
+            }
+        }
+    }
+}
+
+impl<V: JsonValue> Children<V> for Trigger<V> {
+    fn for_each(this: Option<&Self>, path: &str, f: &mut dyn FnMut(&str, &dyn Extensible<V>)) {
+        if let Some(child) = this.and_then(Child::as_extensible) {
+            f(path, child);
+        }
+    }
+
+    fn for_each_mut(this: Option<&mut Self>, f: &mut dyn FnMut(&mut dyn Extensible<V>)) {
+        if let Some(child) = this.and_then(Child::as_extensible_mut) {
+            f(child);
+        }
+    }
+}
+
+/// A value which may be an [`Extensible`] object.
+trait Child<V: JsonValue> {
+    fn as_extensible(&self) -> Option<&dyn Extensible<V>>;
+
+    fn as_extensible_mut(&mut self) -> Option<&mut dyn Extensible<V>>;
+}
+
+macro_rules! impl_child {
+    ($($ty:ident),*) => {
+        $(
+            impl<V: JsonValue> Child<V> for $ty<V> {
+                fn as_extensible(&self) -> Option<&dyn Extensible<V>> {
+                    Some(self)
+                }
+
+                fn as_extensible_mut(&mut self) -> Option<&mut dyn Extensible<V>> {
+                    Some(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_child!(
+    Relation,
+    Participant,
+    TaskParticipant,
+    TimeZone,
+    TimeZoneRule
+);
+
+impl<T: Extensible<V>, V: JsonValue> Child<V> for MaybeUnknown<T, V> {
+    fn as_extensible(&self) -> Option<&dyn Extensible<V>> {
+        self.as_known().map(|known| known as _)
+    }
+
+    fn as_extensible_mut(&mut self) -> Option<&mut dyn Extensible<V>> {
+        self.as_known_mut().map(|known| known as _)
+    }
+}
+
+impl<V: JsonValue> Child<V> for TaskOrEvent<V> {
+    fn as_extensible(&self) -> Option<&dyn Extensible<V>> {
+        match self {
+            TaskOrEvent::Task(task) => Some(task),
+            TaskOrEvent::Event(event) => Some(event),
+        }
+    }
+
+    fn as_extensible_mut(&mut self) -> Option<&mut dyn Extensible<V>> {
+        match self {
+            TaskOrEvent::Task(task) => Some(task),
+            TaskOrEvent::Event(event) => Some(event),
+        }
+    }
+}
+
+impl<V: JsonValue> Child<V> for Trigger<V> {
+    fn as_extensible(&self) -> Option<&dyn Extensible<V>> {
+        match self {
+            Trigger::Offset(trigger) => Some(trigger),
+            Trigger::Absolute(trigger) => Some(trigger),
+            Trigger::Unknown(_) => None,
+        }
+    }
+
+    fn as_extensible_mut(&mut self) -> Option<&mut dyn Extensible<V>> {
+        match self {
+            Trigger::Offset(trigger) => Some(trigger),
+            Trigger::Absolute(trigger) => Some(trigger),
+            Trigger::Unknown(_) => None,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{json::TryFromJson, model::object::SerializeOptions};
+
+    fn event() -> Event<Value> {
+        Event::try_from_json(json!({
+            "@type": "Event",
+            "uid": "a8df6573-0474-496d-8496-033ad45d7fea",
+            "start": "2020-01-15T13:00:00",
+            "example.com:color": "teal",
+            "locations": {
+                "l1": { "@type": "Location", "name": "Office", "example.com:floor": 3 }
+            },
+            "alerts": {
+                "a1": {
+                    "@type": "Alert",
+                    "trigger": { "@type": "OffsetTrigger", "offset": "-PT15M", "example.com:snooze": true }
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn vendor_members() {
+        let members: Vec<_> = event()
+            .vendor_members()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            members,
+            [
+                "alerts/a1/trigger/example.com:snooze",
+                "example.com:color",
+                "locations/l1/example.com:floor",
+            ]
+        );
+
+        let group = Group::try_from_json(json!({
+            "@type": "Group",
+            "uid": "group",
+            "entries": [event().into_json_with(&SerializeOptions::default())]
+        }))
+        .unwrap();
+        assert_eq!(
+            group.vendor_members()[1].to_string(),
+            "entries/0/example.com:color"
+        );
+    }
+
+    #[test]
+    fn wrapped() {
+        let options =
+            SerializeOptions::default().with_vendor_properties(VendorPlacement::Wrapped("_vendor"));
+        let value: Value = event().into_json_with(&options);
+
+        assert_eq!(value["_vendor"], json!({ "example.com:color": "teal" }));
+        assert!(value.get("example.com:color").is_none());
+        assert_eq!(
+            value["locations"]["l1"],
+            json!({ "@type": "Location", "name": "Office", "_vendor": { "example.com:floor": 3 } })
+        );
+        assert_eq!(
+            value["alerts"]["a1"]["trigger"]["_vendor"],
+            json!({ "example.com:snooze": true })
+        );
+        assert!(value["alerts"]["a1"].get("_vendor").is_none());
+    }
+
+    #[test]
+    fn inline_is_unchanged() {
+        let value: Value = event().into_json_with(&SerializeOptions::default());
+        assert_eq!(value["example.com:color"], "teal");
+        assert_eq!(value["locations"]["l1"]["example.com:floor"], 3);
+    }
+}
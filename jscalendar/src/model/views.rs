@@ -0,0 +1,461 @@
+//! Per-day and per-week agendas of occurrences in a viewer's time zone.
+//!
+//! A calendar view lays out occurrences by the dates of the time zone in which it is shown, which
+//! need not be the time zone of any of the occurrences. [`days`] sorts occurrences into a [`Day`]
+//! for each date in a range, and [`weeks`] groups these days into [`Week`]s. An occurrence which
+//! crosses midnight is split into a [`Segment`] for each day on which it takes place, so that a
+//! view can draw each day independently.
+//!
+//! Occurrences are given as spans of UTC time, so that they may come from events in different
+//! time zones; [`event_spans`] computes these spans for events, such as the occurrences produced
+//! by [`Event::materialize`]. As elsewhere, the spans are half-open intervals, and an
+//! instantaneous occurrence is on the day of its start.
+//!
+//! The time zone of the view is given by a `zone` function, which returns its offset from UTC at
+//! the given instant, typically by consulting the IANA time zone database; `None` is treated as
+//! UTC. Days run from midnight to midnight in this time zone, so the day on which a daylight
+//! saving transition occurs is longer or shorter than 24 hours, and a day whose midnight is
+//! skipped by a transition starts at the transition.
+
+use std::ops::Range;
+
+use crate::{
+    json::DestructibleJsonValue,
+    model::{
+        object::Event,
+        time::{
+            Date, DateTime, Duration, ExactDuration, Hour, Local, Minute, NonLeapSecond, Second,
+            Sign, Time, Utc, UtcOffset, Weekday,
+        },
+    },
+};
+
+/// The part of an occurrence which takes place on a single [`Day`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment<T> {
+    /// The occurrence.
+    pub item: T,
+    /// The whole span of the occurrence.
+    pub span: Range<DateTime<Utc>>,
+    /// The part of the span on this day, in the local time of the view, which ends at the
+    /// following midnight if the occurrence continues onto the next day.
+    pub local: Range<DateTime<Local>>,
+    /// Whether the occurrence started on an earlier day.
+    pub continued: bool,
+    /// Whether the occurrence ends on a later day.
+    pub continues: bool,
+}
+
+/// The segments of the occurrences on a single date, in order of their start times.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Day<T> {
+    /// The date.
+    pub date: Date,
+    /// The segments on this date.
+    pub segments: Vec<Segment<T>>,
+}
+
+/// The days of a single week.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Week<T> {
+    /// The first date of the week.
+    pub start: Date,
+    /// The days of the week, in order.
+    pub days: Vec<Day<T>>,
+}
+
+/// Returns the `events` paired with their spans in UTC, in their original order.
+///
+/// Each event spans from its [`utc_start`](Event::utc_start) to its [`utc_end`](Event::utc_end),
+/// where `offset` resolves its `timeZone` in the same way as for those methods. Events whose
+/// times are out of range are omitted.
+pub fn event_spans<'a, V, Z>(
+    events: impl IntoIterator<Item = &'a Event<V>>,
+    mut offset: Z,
+) -> impl Iterator<Item = (&'a Event<V>, Range<DateTime<Utc>>)>
+where
+    V: DestructibleJsonValue + 'a,
+    Z: FnMut(&str, DateTime<Local>) -> Option<UtcOffset>,
+{
+    events.into_iter().filter_map(move |event| {
+        let start = event.utc_start(&mut offset)?;
+        let end = match event.duration() {
+            Some(duration) => start.checked_add(*duration)?,
+            None => start,
+        };
+        Some((event, start..end))
+    })
+}
+
+/// Returns a [`Day`] for each date in `dates`, holding the segments of the `occurrences` on that
+/// date in the time zone given by `zone`.
+///
+/// Segments which start at the same time are in the order of their occurrences. A span whose end
+/// is before its start is treated as instantaneous. Dates within a day of the limits of the
+/// representable range are omitted.
+pub fn days<T, Z>(
+    occurrences: impl IntoIterator<Item = (T, Range<DateTime<Utc>>)>,
+    dates: Range<Date>,
+    mut zone: Z,
+) -> Vec<Day<T>>
+where
+    T: Clone,
+    Z: FnMut(DateTime<Utc>) -> Option<UtcOffset>,
+{
+    // the start of each day, followed by the end of the last day
+    let mut bounds = Vec::new();
+    let mut date = Some(dates.start);
+    while let Some(next) = date.filter(|date| *date <= dates.end) {
+        if let Some(start) = day_start(next, &mut zone) {
+            bounds.push((next, start));
+        }
+        date = next.succ();
+    }
+
+    let mut days: Vec<_> = bounds
+        .windows(2)
+        .map(|window| Day {
+            date: window[0].0,
+            segments: Vec::new(),
+        })
+        .collect();
+    let mut starts: Vec<Vec<DateTime<Utc>>> = vec![Vec::new(); days.len()];
+
+    for (item, span) in occurrences {
+        let span = span.start..span.end.max(span.start);
+        let first = bounds.partition_point(|(_, start)| *start <= span.start);
+        for (index, window) in bounds.windows(2).enumerate().skip(first.saturating_sub(1)) {
+            let ((_, day_start), (_, day_end)) = (window[0], window[1]);
+            if day_start >= span.end && !(span.is_empty() && day_start == span.start) {
+                break;
+            }
+            if span.start >= day_end {
+                continue;
+            }
+
+            let start = span.start.max(day_start);
+            let end = span.end.min(day_end);
+            let (Some(local_start), Some(local_end)) =
+                (to_local(start, zone(start)), to_local(end, zone(end)))
+            else {
+                continue;
+            };
+
+            let position = starts[index].partition_point(|other| *other <= start);
+            starts[index].insert(position, start);
+            days[index].segments.insert(
+                position,
+                Segment {
+                    item: item.clone(),
+                    span: span.clone(),
+                    local: local_start..local_end,
+                    continued: span.start < day_start,
+                    continues: span.end > day_end,
+                },
+            );
+        }
+    }
+
+    days
+}
+
+/// Returns the weeks which contain the dates in `dates`, each starting on `first_day` and holding
+/// a [`Day`] for each of its dates as for [`days`].
+///
+/// The first and last weeks include the dates of those weeks outside `dates`, so that every week
+/// has seven days except near the limits of the representable range.
+pub fn weeks<T, Z>(
+    occurrences: impl IntoIterator<Item = (T, Range<DateTime<Utc>>)>,
+    dates: Range<Date>,
+    first_day: Weekday,
+    zone: Z,
+) -> Vec<Week<T>>
+where
+    T: Clone,
+    Z: FnMut(DateTime<Utc>) -> Option<UtcOffset>,
+{
+    let week_start = |date: Date| {
+        let offset = (date.weekday() as i64 - first_day as i64).rem_euclid(7);
+        date.checked_add_days(-offset).unwrap_or(Date::MIN)
+    };
+
+    let start = week_start(dates.start);
+    let end = match dates.end > dates.start {
+        true => week_start(dates.end.pred().unwrap_or(Date::MIN))
+            .checked_add_days(7)
+            .unwrap_or(Date::MAX),
+        false => start,
+    };
+
+    let mut weeks: Vec<Week<T>> = Vec::new();
+    for day in days(occurrences, start..end, zone) {
+        let start = week_start(day.date);
+        match weeks.last_mut() {
+            Some(week) if week.start == start => week.days.push(day),
+            _ => weeks.push(Week {
+                start,
+                days: vec![day],
+            }),
+        }
+    }
+    weeks
+}
+
+/// Returns the first instant at which the local date in `zone` is `date` or later.
+fn day_start(
+    date: Date,
+    zone: &mut impl FnMut(DateTime<Utc>) -> Option<UtcOffset>,
+) -> Option<DateTime<Utc>> {
+    let midnight = DateTime {
+        date,
+        time: Time::new(Hour::H00, Minute::M00, Second::S00, None).ok()?,
+        marker: Local,
+    };
+    let second = Duration::Exact(ExactDuration {
+        seconds: 1,
+        ..ExactDuration::ZERO
+    });
+
+    // away from transitions, the offset at midnight read as UTC is off by at most one transition,
+    // which the second lookup corrects
+    let naive = DateTime {
+        date,
+        time: midnight.time,
+        marker: Utc,
+    };
+    let guess = offset_or_utc(zone(naive)).local_to_utc(midnight)?;
+    let start = offset_or_utc(zone(guess)).local_to_utc(midnight)?;
+    let mut local = |utc: DateTime<Utc>| to_local(utc, zone(utc));
+    let before = start.checked_sub(second)?;
+    if local(start)? == midnight && local(before)? < midnight {
+        return Some(start);
+    }
+
+    // otherwise midnight is skipped or repeated, so search the two days around it, within which
+    // every offset from UTC places it
+    let day = Duration::Exact(ExactDuration {
+        hours: 24,
+        ..ExactDuration::ZERO
+    });
+    let mut low = naive.checked_sub(day)?;
+    let (mut lo, mut hi) = (0u32, 2 * 86_400);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        let instant = low.checked_add(Duration::Exact(ExactDuration {
+            seconds: mid,
+            ..ExactDuration::ZERO
+        }))?;
+        match local(instant)? >= midnight {
+            true => hi = mid,
+            false => lo = mid + 1,
+        }
+    }
+    low = low.checked_add(Duration::Exact(ExactDuration {
+        seconds: lo,
+        ..ExactDuration::ZERO
+    }))?;
+    Some(low)
+}
+
+fn to_local(utc: DateTime<Utc>, offset: Option<UtcOffset>) -> Option<DateTime<Local>> {
+    offset_or_utc(offset).utc_to_local(utc)
+}
+
+fn offset_or_utc(offset: Option<UtcOffset>) -> UtcOffset {
+    offset.unwrap_or(UtcOffset {
+        sign: Sign::Pos,
+        hour: Hour::H00,
+        minute: Minute::M00,
+        second: NonLeapSecond::S00,
+    })
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::json::TryFromJson;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::try_from_json(Value::from(s)).unwrap()
+    }
+
+    fn local(s: &str) -> DateTime<Local> {
+        DateTime::try_from_json(Value::from(s)).unwrap()
+    }
+
+    fn date(s: &str) -> Date {
+        local(&format!("{s}T00:00:00")).date
+    }
+
+    fn hours(sign: Sign, hour: Hour) -> UtcOffset {
+        UtcOffset {
+            sign,
+            hour,
+            minute: Minute::M00,
+            second: NonLeapSecond::S00,
+        }
+    }
+
+    /// Europe/Berlin in 2024.
+    fn berlin(instant: DateTime<Utc>) -> Option<UtcOffset> {
+        let summer =
+            utc("2024-03-31T01:00:00Z") <= instant && instant < utc("2024-10-27T01:00:00Z");
+        Some(hours(Sign::Pos, if summer { Hour::H02 } else { Hour::H01 }))
+    }
+
+    fn spans<'a>(segments: &[Segment<&'a str>]) -> Vec<(&'a str, String, String, bool, bool)> {
+        segments
+            .iter()
+            .map(|segment| {
+                (
+                    segment.item,
+                    segment.local.start.to_string(),
+                    segment.local.end.to_string(),
+                    segment.continued,
+                    segment.continues,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn split_at_midnight_across_dst() {
+        let occurrences = [
+            (
+                "party",
+                utc("2024-03-30T21:00:00Z")..utc("2024-03-31T01:00:00Z"),
+            ),
+            (
+                "breakfast",
+                utc("2024-03-31T07:00:00Z")..utc("2024-03-31T08:00:00Z"),
+            ),
+            (
+                "reminder",
+                utc("2024-03-31T22:00:00Z")..utc("2024-03-31T22:00:00Z"),
+            ),
+            (
+                "late",
+                utc("2024-03-31T20:00:00Z")..utc("2024-03-31T22:00:00Z"),
+            ),
+        ];
+        let days = days(occurrences, date("2024-03-30")..date("2024-04-02"), berlin);
+
+        assert_eq!(
+            days.iter().map(|day| day.date).collect::<Vec<_>>(),
+            [date("2024-03-30"), date("2024-03-31"), date("2024-04-01")]
+        );
+        assert_eq!(
+            spans(&days[0].segments),
+            [(
+                "party",
+                "2024-03-30T22:00:00".into(),
+                "2024-03-31T00:00:00".into(),
+                false,
+                true
+            )]
+        );
+        assert_eq!(
+            spans(&days[1].segments),
+            [
+                (
+                    "party",
+                    "2024-03-31T00:00:00".into(),
+                    "2024-03-31T03:00:00".into(),
+                    true,
+                    false
+                ),
+                (
+                    "breakfast",
+                    "2024-03-31T09:00:00".into(),
+                    "2024-03-31T10:00:00".into(),
+                    false,
+                    false
+                ),
+                (
+                    "late",
+                    "2024-03-31T22:00:00".into(),
+                    "2024-04-01T00:00:00".into(),
+                    false,
+                    false
+                ),
+            ]
+        );
+        assert_eq!(
+            spans(&days[2].segments),
+            [(
+                "reminder",
+                "2024-04-01T00:00:00".into(),
+                "2024-04-01T00:00:00".into(),
+                false,
+                false
+            )]
+        );
+    }
+
+    #[test]
+    fn skipped_midnight() {
+        // clocks go forward from midnight to 1am on 2024-03-31
+        let zone = |instant| {
+            Some(match instant < utc("2024-03-31T00:00:00Z") {
+                true => hours(Sign::Pos, Hour::H00),
+                false => hours(Sign::Pos, Hour::H01),
+            })
+        };
+        let occurrences = [(
+            "night",
+            utc("2024-03-30T23:00:00Z")..utc("2024-03-31T02:00:00Z"),
+        )];
+        let days = days(occurrences, date("2024-03-30")..date("2024-04-01"), zone);
+
+        assert_eq!(
+            spans(&days[1].segments),
+            [(
+                "night",
+                "2024-03-31T01:00:00".into(),
+                "2024-03-31T03:00:00".into(),
+                true,
+                false
+            )]
+        );
+    }
+
+    #[test]
+    fn weeks_and_event_spans() {
+        let event = Event::<Value>::try_from_json(json!({
+            "@type": "Event",
+            "uid": "trip",
+            "start": "2024-06-01T18:00:00",
+            "timeZone": "Europe/Berlin",
+            "duration": "P2D"
+        }))
+        .unwrap();
+        let events = [event];
+        let offset = |_: &str, _: DateTime<Local>| Some(hours(Sign::Pos, Hour::H02));
+        let occurrences = event_spans(&events, offset).map(|(event, span)| (event.uid(), span));
+
+        let weeks = weeks(
+            occurrences,
+            date("2024-06-01")..date("2024-06-04"),
+            Weekday::Monday,
+            berlin,
+        );
+        assert_eq!(
+            weeks.iter().map(|week| week.start).collect::<Vec<_>>(),
+            [date("2024-05-27"), date("2024-06-03")]
+        );
+        assert!(weeks.iter().all(|week| week.days.len() == 7));
+
+        let busy: Vec<_> = weeks
+            .iter()
+            .flat_map(|week| &week.days)
+            .filter(|day| !day.segments.is_empty())
+            .map(|day| day.date)
+            .collect();
+        assert_eq!(
+            busy,
+            [date("2024-06-01"), date("2024-06-02"), date("2024-06-03")]
+        );
+    }
+}
@@ -0,0 +1,400 @@
+//! Absolute start and end times of events, and filtering by time window.
+//!
+//! The `start` of an event is a local date-time in its `timeZone`, so listing the events between
+//! two instants requires converting each start into UTC. [`Event::utc_start`] and
+//! [`Event::utc_end`] perform this conversion, and [`filter_in_window`] uses them to select the
+//! events whose time spans overlap a window, as half-open intervals in the same way as
+//! [`conflicts`](crate::model::conflict::conflicts).
+//!
+//! A time zone in the `timeZones` of an event is resolved from its rules. Any other time zone is
+//! resolved by an `offset` function, which returns the offset from UTC of the given `timeZone` at
+//! the given local time, typically by consulting the IANA time zone database. Floating events,
+//! and those whose time zone cannot be resolved, are treated as though they were in UTC.
+//!
+//! Recurrences are not expanded, so only the first occurrence of a recurring event is taken into
+//! account; use [`Event::materialize`] to obtain the other occurrences as events.
+//!
+//! An event records its length as a `duration`, while many other calendar APIs record an end time
+//! instead. [`Event::end`] and [`Event::set_end`] convert between the two in local time, and a
+//! [`Span`] accepts a time span in either form wherever one is compared with an event.
+
+use std::ops::Range;
+
+use thiserror::Error;
+
+use crate::{
+    json::{DestructibleJsonValue, JsonValue},
+    model::{
+        conflict::{spans_overlap, to_utc},
+        object::{Event, TimeZone},
+        string::CustomTimeZoneId,
+        time::{DateTime, Duration, ExactDuration, Local, NominalDuration, Utc, UtcOffset},
+    },
+};
+
+/// A time span given by its start and either its end or its duration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Span<M> {
+    /// The span from `start` up to but excluding `end`.
+    End {
+        /// The start of the span.
+        start: DateTime<M>,
+        /// The end of the span.
+        end: DateTime<M>,
+    },
+    /// The span from `start` which lasts for `duration`.
+    Duration {
+        /// The start of the span.
+        start: DateTime<M>,
+        /// The length of the span.
+        duration: Duration,
+    },
+}
+
+impl<M: Copy + Ord> Span<M> {
+    /// Returns this span as a half-open range, or `None` if its end is out of range.
+    ///
+    /// An end before the start is treated as the start, so that the span is instantaneous.
+    pub fn to_range(&self) -> Option<Range<DateTime<M>>> {
+        let (start, end) = match *self {
+            Span::End { start, end } => (start, end),
+            Span::Duration { start, duration } => (start, start.checked_add(duration)?),
+        };
+        Some(start..end.max(start))
+    }
+}
+
+impl<M> From<Range<DateTime<M>>> for Span<M> {
+    fn from(value: Range<DateTime<M>>) -> Self {
+        Span::End {
+            start: value.start,
+            end: value.end,
+        }
+    }
+}
+
+impl<M> From<(DateTime<M>, Duration)> for Span<M> {
+    fn from((start, duration): (DateTime<M>, Duration)) -> Self {
+        Span::Duration { start, duration }
+    }
+}
+
+/// The end passed to [`Event::set_end`] was before the `start` of the event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("the end {end} is before the start {start}")]
+pub struct EndBeforeStartError {
+    /// The `start` of the event.
+    pub start: DateTime<Local>,
+    /// The rejected end.
+    pub end: DateTime<Local>,
+}
+
+impl<V: JsonValue> Event<V> {
+    /// Returns the end of this event in its own local time, which is its `start` plus its
+    /// `duration`, or `None` if it is out of range.
+    ///
+    /// Nominal days are added as 24 hours, which is exact in local time.
+    pub fn end(&self) -> Option<DateTime<Local>> {
+        let start = *self.start();
+        match self.duration() {
+            Some(duration) => start.checked_add(*duration),
+            None => Some(start),
+        }
+    }
+
+    /// Sets the `duration` of this event so that it ends at the local time `end`.
+    ///
+    /// The duration is written in whole days and a remainder, such as `P1DT2H`, so that it keeps
+    /// its length in local time when applied in a time zone with daylight saving transitions. If
+    /// `end` is the `start` of the event, the `duration` is removed.
+    pub fn set_end(&mut self, end: DateTime<Local>) -> Result<(), EndBeforeStartError> {
+        let start = *self.start();
+        if end < start {
+            return Err(EndBeforeStartError { start, end });
+        }
+        if end == start {
+            self.remove_duration();
+            return Ok(());
+        }
+
+        let Duration::Exact(exact) = end.signed_duration_since(&start).duration else {
+            unreachable!("the difference of two date-times is exact")
+        };
+        let days = exact.hours / 24;
+        let rest = ExactDuration {
+            hours: exact.hours % 24,
+            ..exact
+        };
+        self.set_duration(match days {
+            0 => Duration::Exact(rest),
+            days => Duration::Nominal(NominalDuration {
+                weeks: 0,
+                days,
+                exact: (rest != ExactDuration::ZERO).then_some(rest),
+            }),
+        });
+        Ok(())
+    }
+
+    /// Returns `true` iff the time span of this event overlaps `span`, both in local time.
+    ///
+    /// As with [`Event::overlaps`], this is only meaningful if `span` is in the `timeZone` of this
+    /// event, and recurrences are ignored. A span whose end is out of range never overlaps.
+    pub fn overlaps_span(&self, span: impl Into<Span<Local>>) -> bool {
+        let start = *self.start();
+        let own = start..self.end().unwrap_or(start);
+        span.into()
+            .to_range()
+            .is_some_and(|span| spans_overlap(&own, &span))
+    }
+}
+
+impl<V: DestructibleJsonValue> Event<V> {
+    /// Returns the `start` of this event in UTC, or `None` if it is out of range.
+    pub fn utc_start(
+        &self,
+        offset: impl FnOnce(&str, DateTime<Local>) -> Option<UtcOffset>,
+    ) -> Option<DateTime<Utc>> {
+        let local = *self.start();
+        to_utc(local, self.offset_at(local, offset))
+    }
+
+    /// Returns the end of this event in UTC, which is its [`utc_start`](Event::utc_start) plus
+    /// its `duration`, or `None` if it is out of range.
+    ///
+    /// Nominal durations are added as though every day were 24 hours long, so an event spanning
+    /// a daylight saving transition ends an hour early or late.
+    pub fn utc_end(
+        &self,
+        offset: impl FnOnce(&str, DateTime<Local>) -> Option<UtcOffset>,
+    ) -> Option<DateTime<Utc>> {
+        let start = self.utc_start(offset)?;
+        match self.duration() {
+            Some(duration) => start.checked_add(*duration),
+            None => Some(start),
+        }
+    }
+
+    /// Returns the offset from UTC of the `timeZone` of this event at the local time `local`.
+    fn offset_at(
+        &self,
+        local: DateTime<Local>,
+        offset: impl FnOnce(&str, DateTime<Local>) -> Option<UtcOffset>,
+    ) -> Option<UtcOffset> {
+        let time_zone = self.time_zone()?;
+        let custom = CustomTimeZoneId::new(time_zone)
+            .ok()
+            .and_then(|id| self.time_zones()?.get(id));
+        offset_in(time_zone, custom, local, offset)
+    }
+}
+
+/// Returns the offset from UTC of `time_zone` at the local time `local`, from its definition
+/// `custom` if there is one and from `offset` otherwise.
+pub(crate) fn offset_in<V: DestructibleJsonValue>(
+    time_zone: &str,
+    custom: Option<&TimeZone<V>>,
+    local: DateTime<Local>,
+    offset: impl FnOnce(&str, DateTime<Local>) -> Option<UtcOffset>,
+) -> Option<UtcOffset> {
+    let Some(custom) = custom else {
+        return offset(time_zone, local);
+    };
+
+    // the offset at `local` read as UTC is off by at most one transition, which the second
+    // lookup corrects
+    let guess = custom.offset_at_utc(to_utc(local, None)?).ok()??;
+    custom
+        .offset_at_utc(to_utc(local, Some(guess))?)
+        .ok()
+        .flatten()
+}
+
+/// Returns the `events` whose time spans overlap `window`, in their original order.
+///
+/// Each event spans from its [`utc_start`](Event::utc_start) to its [`utc_end`](Event::utc_end)
+/// as a half-open interval, and an instantaneous event is in the window if its start is. Events
+/// whose times are out of range are omitted, as are all events if the end of `window` is.
+pub fn filter_in_window<'a, V, Z>(
+    events: impl IntoIterator<Item = &'a Event<V>>,
+    window: impl Into<Span<Utc>>,
+    mut offset: Z,
+) -> impl Iterator<Item = &'a Event<V>>
+where
+    V: DestructibleJsonValue + 'a,
+    Z: FnMut(&str, DateTime<Local>) -> Option<UtcOffset>,
+{
+    let window = window.into().to_range();
+    events.into_iter().filter(move |event| {
+        let Some(window) = &window else {
+            return false;
+        };
+        let Some(start) = event.utc_start(&mut offset) else {
+            return false;
+        };
+        let end = match event.duration() {
+            Some(duration) => start.checked_add(*duration),
+            None => Some(start),
+        };
+        end.is_some_and(|end| spans_overlap(&(start..end), window))
+    })
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{
+        json::TryFromJson,
+        model::time::{Hour, Minute, NonLeapSecond, Sign},
+    };
+
+    fn event(start: &str, extra: Value) -> Event<Value> {
+        let mut json = json!({ "@type": "Event", "uid": start, "start": start });
+        json.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        Event::try_from_json(json).unwrap()
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::try_from_json(Value::from(s)).unwrap()
+    }
+
+    fn berlin(time_zone: &str, _: DateTime<Local>) -> Option<UtcOffset> {
+        (time_zone == "Europe/Berlin").then_some(UtcOffset {
+            sign: Sign::Pos,
+            hour: Hour::H01,
+            minute: Minute::M00,
+            second: NonLeapSecond::S00,
+        })
+    }
+
+    #[test]
+    fn utc_start_and_end() {
+        let meeting = event(
+            "2024-01-01T09:00:00",
+            json!({ "duration": "PT1H30M", "timeZone": "Europe/Berlin" }),
+        );
+        assert_eq!(meeting.utc_start(berlin), Some(utc("2024-01-01T08:00:00Z")));
+        assert_eq!(meeting.utc_end(berlin), Some(utc("2024-01-01T09:30:00Z")));
+
+        let floating = event("2024-01-01T09:00:00", json!({}));
+        assert_eq!(floating.utc_end(berlin), Some(utc("2024-01-01T09:00:00Z")));
+    }
+
+    #[test]
+    fn utc_start_with_custom_time_zone() {
+        let rule = |start: &str, from: &str, to: &str, month: u8| {
+            json!({
+                "@type": "TimeZoneRule",
+                "start": start,
+                "offsetFrom": from,
+                "offsetTo": to,
+                "recurrenceRules": [{
+                    "@type": "RecurrenceRule",
+                    "frequency": "yearly",
+                    "byMonth": [month],
+                    "byDay": [{ "@type": "NDay", "day": "su", "nthOfPeriod": -1 }]
+                }]
+            })
+        };
+        let zone = json!({
+            "/example": {
+                "@type": "TimeZone",
+                "tzId": "Example",
+                "standard": [rule("1996-10-27T03:00:00", "+02:00", "+01:00", 10)],
+                "daylight": [rule("1981-03-29T02:00:00", "+01:00", "+02:00", 3)]
+            }
+        });
+
+        let summer = event(
+            "2024-07-01T12:00:00",
+            json!({ "timeZone": "/example", "timeZones": zone }),
+        );
+        let winter = event(
+            "2024-01-01T12:00:00",
+            json!({ "timeZone": "/example", "timeZones": zone }),
+        );
+        let none = |_: &str, _| -> Option<UtcOffset> { unreachable!() };
+        assert_eq!(summer.utc_start(none), Some(utc("2024-07-01T10:00:00Z")));
+        assert_eq!(winter.utc_start(none), Some(utc("2024-01-01T11:00:00Z")));
+    }
+
+    fn local(s: &str) -> DateTime<Local> {
+        DateTime::try_from_json(Value::from(s)).unwrap()
+    }
+
+    #[test]
+    fn end_and_set_end() {
+        let mut meeting = event("2024-03-30T22:00:00", json!({ "duration": "PT3H" }));
+        assert_eq!(meeting.end(), Some(local("2024-03-31T01:00:00")));
+
+        meeting.set_end(local("2024-04-01T23:30:00")).unwrap();
+        assert_eq!(meeting.duration().unwrap().to_string(), "P2DT1H30M");
+        assert_eq!(meeting.end(), Some(local("2024-04-01T23:30:00")));
+
+        meeting.set_end(local("2024-03-31T22:00:00")).unwrap();
+        assert_eq!(meeting.duration().unwrap().to_string(), "P1D");
+        meeting.set_end(local("2024-03-30T22:45:00")).unwrap();
+        assert_eq!(meeting.duration().unwrap().to_string(), "PT45M");
+        meeting.set_end(local("2024-03-30T22:00:00")).unwrap();
+        assert_eq!(meeting.duration(), None);
+
+        let early = local("2024-03-30T21:00:00");
+        assert_eq!(
+            meeting.set_end(early),
+            Err(EndBeforeStartError {
+                start: local("2024-03-30T22:00:00"),
+                end: early,
+            })
+        );
+    }
+
+    #[test]
+    fn overlaps_span_accepts_end_or_duration() {
+        let meeting = event("2024-01-01T09:00:00", json!({ "duration": "PT1H" }));
+        let hour = Duration::try_from_json(Value::from("PT1H")).unwrap();
+
+        assert!(meeting.overlaps_span(local("2024-01-01T09:30:00")..local("2024-01-01T11:00:00")));
+        assert!(!meeting.overlaps_span(local("2024-01-01T10:00:00")..local("2024-01-01T11:00:00")));
+        assert!(meeting.overlaps_span((local("2024-01-01T08:30:00"), hour)));
+        assert!(!meeting.overlaps_span((local("2024-01-01T08:00:00"), hour)));
+        assert!(meeting.overlaps_span(Span::End {
+            start: local("2024-01-01T09:00:00"),
+            end: local("2024-01-01T08:00:00"),
+        }));
+
+        let events = [meeting];
+        let window = (utc("2024-01-01T08:30:00Z"), hour);
+        assert_eq!(filter_in_window(&events, window, berlin).count(), 1);
+    }
+
+    #[test]
+    fn filter_in_window_uses_half_open_spans() {
+        let events = [
+            event("2024-01-01T08:00:00", json!({ "duration": "PT1H" })),
+            event("2024-01-01T10:00:00", json!({ "duration": "PT1H" })),
+            event(
+                "2024-01-01T10:00:00",
+                json!({ "timeZone": "Europe/Berlin" }),
+            ),
+            event("2024-01-01T11:00:00", json!({})),
+            event("2024-01-01T11:30:00", json!({})),
+        ];
+
+        let window = utc("2024-01-01T09:00:00Z")..utc("2024-01-01T11:30:00Z");
+        let found: Vec<_> = filter_in_window(&events, window, berlin)
+            .map(|event| event.start().to_string())
+            .collect();
+        assert_eq!(
+            found,
+            [
+                "2024-01-01T10:00:00",
+                "2024-01-01T10:00:00",
+                "2024-01-01T11:00:00"
+            ]
+        );
+    }
+}
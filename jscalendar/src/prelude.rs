@@ -0,0 +1,38 @@
+//! Commonly used traits and types.
+//!
+//! A glob import of this module brings the JSON conversion traits, the JSCalendar object types,
+//! and the scalar types which appear in their fields into scope:
+//!
+//! ```
+//! # #[cfg(feature = "serde_json")]
+//! # {
+//! use jscalendar::prelude::*;
+//! use serde_json::{Value, json};
+//!
+//! let event: Event<Value> = Event::try_from_json(json!({
+//!     "@type": "Event",
+//!     "uid": "a8df6573-0474-496d-8496-033ad45d7fea",
+//!     "start": "2020-01-15T13:00:00",
+//!     "duration": "PT1H"
+//! }))
+//! .unwrap();
+//! assert_eq!(event.start().date.month(), Month::Jan);
+//! # }
+//! ```
+
+pub use crate::json::{
+    ConstructibleJsonValue, DestructibleJsonValue, IntoJson, JsonValue, TryFromJson, TryIntoJson,
+};
+pub use crate::model::{
+    object::{
+        Alert, Event, Group, Link, Location, MaybeUnknown, Participant, PatchObject, Relation,
+        Task, TaskOrEvent, TaskParticipant, TimeZone, TimeZoneRule, Trigger, VirtualLocation,
+    },
+    rrule::RRule,
+    set::Token,
+    string::{Id, ImplicitJsonPointer, VendorStr},
+    time::{
+        Date, DateTime, Day, Duration, Hour, Local, Minute, Month, Second, SignedDuration, Time,
+        Utc, UtcOffset, Weekday, Year,
+    },
+};
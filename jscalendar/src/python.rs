@@ -0,0 +1,257 @@
+//! Python bindings for the object model (requires the `pyo3` feature).
+//!
+//! [`PyEvent`], [`PyTask`], and [`PyGroup`] are exported to Python as the classes `Event`,
+//! `Task`, and `Group`. Their constructors validate a `dict` as a JSCalendar object, raising a
+//! `ValueError` which names the path of the first invalid property, and their `to_dict` and
+//! `to_json` methods serialize the object again. [`validate`] checks a `dict` against the object
+//! type named by its `@type` property, without keeping the parsed object.
+//!
+//! Python values are converted through [`serde_json::Value`]: `None`, `bool`, `int`, `float`,
+//! `str`, `list`, `tuple`, and `dict` (with `str` keys) are accepted, and any other value raises a
+//! `TypeError`.
+//!
+//! This crate is not itself a Python extension module. An extension module adds the classes to
+//! its module with [`register`]:
+//!
+//! ```ignore
+//! use pyo3::prelude::*;
+//!
+//! #[pymodule]
+//! fn calendar(m: &Bound<'_, PyModule>) -> PyResult<()> {
+//!     jscalendar::python::register(m)
+//! }
+//! ```
+//!
+//! ```python
+//! from calendar import Event
+//!
+//! event = Event({
+//!     "@type": "Event",
+//!     "uid": "a8df6573-0474-496d-8496-033ad45d7fea",
+//!     "start": "2020-01-15T13:00:00",
+//! })
+//! assert event.to_dict()["start"] == "2020-01-15T13:00:00"
+//! ```
+
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    prelude::*,
+    types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple},
+};
+use serde_json::{Map, Number, Value};
+
+use crate::{
+    json::{IntoJson, TryFromJson},
+    model::object::{Event, Group, Task},
+};
+
+/// Converts a Python value into JSON.
+fn from_python(value: &Bound<'_, PyAny>) -> PyResult<Value> {
+    if value.is_none() {
+        Ok(Value::Null)
+    } else if let Ok(b) = value.cast::<PyBool>() {
+        Ok(Value::Bool(b.is_true()))
+    } else if value.is_instance_of::<PyInt>() {
+        if let Ok(n) = value.extract::<i64>() {
+            Ok(n.into())
+        } else if let Ok(n) = value.extract::<u64>() {
+            Ok(n.into())
+        } else {
+            Err(PyValueError::new_err(format!(
+                "the integer {value} is too large to be converted into JSON"
+            )))
+        }
+    } else if let Ok(f) = value.cast::<PyFloat>() {
+        let f = f.value();
+        Number::from_f64(f).map(Value::Number).ok_or_else(|| {
+            PyValueError::new_err(format!("the number {f} cannot be converted into JSON"))
+        })
+    } else if let Ok(s) = value.cast::<PyString>() {
+        Ok(Value::String(s.to_str()?.to_owned()))
+    } else if let Ok(list) = value.cast::<PyList>() {
+        list.iter().map(|elem| from_python(&elem)).collect()
+    } else if let Ok(tuple) = value.cast::<PyTuple>() {
+        tuple.iter().map(|elem| from_python(&elem)).collect()
+    } else if let Ok(dict) = value.cast::<PyDict>() {
+        let mut object = Map::with_capacity(dict.len());
+        for (key, value) in dict.iter() {
+            let key = key
+                .cast::<PyString>()
+                .map_err(|_| PyTypeError::new_err(format!("the dict key {key} is not a str")))?;
+            object.insert(key.to_str()?.to_owned(), from_python(&value)?);
+        }
+        Ok(Value::Object(object))
+    } else {
+        let kind = value.get_type().name()?;
+        Err(PyTypeError::new_err(format!(
+            "a value of type {kind} cannot be converted into JSON"
+        )))
+    }
+}
+
+/// Converts JSON into a Python value.
+fn into_python<'py>(py: Python<'py>, value: Value) -> PyResult<Bound<'py, PyAny>> {
+    Ok(match value {
+        Value::Null => py.None().into_bound(py),
+        Value::Bool(b) => PyBool::new(py, b).to_owned().into_any(),
+        Value::Number(n) => {
+            if let Some(n) = n.as_i64() {
+                n.into_pyobject(py)?.into_any()
+            } else if let Some(n) = n.as_u64() {
+                n.into_pyobject(py)?.into_any()
+            } else {
+                n.as_f64().unwrap_or(f64::NAN).into_pyobject(py)?.into_any()
+            }
+        }
+        Value::String(s) => PyString::new(py, &s).into_any(),
+        Value::Array(array) => {
+            let elems = array
+                .into_iter()
+                .map(|elem| into_python(py, elem))
+                .collect::<PyResult<Vec<_>>>()?;
+            PyList::new(py, elems)?.into_any()
+        }
+        Value::Object(object) => {
+            let dict = PyDict::new(py);
+            for (key, value) in object {
+                dict.set_item(key, into_python(py, value)?)?;
+            }
+            dict.into_any()
+        }
+    })
+}
+
+fn value_error(error: impl std::fmt::Display) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+/// Defines the Python class of an object type.
+macro_rules! py_class {
+    ($wrapper:ident, $ty:ident, $name:literal, $kind:literal) => {
+        #[doc = concat!("A JSCalendar ", $kind, ", exported to Python as `", $name, "`.")]
+        #[pyclass(name = $name, module = "jscalendar", frozen, from_py_object)]
+        #[derive(Debug, Clone)]
+        pub struct $wrapper($ty<Value>);
+
+        #[pymethods]
+        impl $wrapper {
+            #[doc = concat!("Validates a `dict` as ", $kind, ".")]
+            #[new]
+            fn new(value: &Bound<'_, PyAny>) -> PyResult<Self> {
+                let json = from_python(value)?;
+                $ty::try_from_json(json).map(Self).map_err(value_error)
+            }
+
+            #[doc = concat!("Parses ", $kind, " from a JSON string.")]
+            #[staticmethod]
+            fn from_json(json: &str) -> PyResult<Self> {
+                let json: Value = serde_json::from_str(json).map_err(value_error)?;
+                $ty::try_from_json(json).map(Self).map_err(value_error)
+            }
+
+            #[doc = concat!("Returns this ", $kind, " as a `dict`.")]
+            fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+                into_python(py, self.0.clone().into_json())
+            }
+
+            #[doc = concat!("Serializes this ", $kind, " as a JSON string.")]
+            fn to_json(&self) -> String {
+                let json: Value = self.0.clone().into_json();
+                json.to_string()
+            }
+        }
+
+        impl $wrapper {
+            #[doc = concat!("Returns the wrapped ", $kind, ".")]
+            pub fn into_inner(self) -> $ty<Value> {
+                self.0
+            }
+        }
+
+        impl From<$ty<Value>> for $wrapper {
+            fn from(value: $ty<Value>) -> Self {
+                Self(value)
+            }
+        }
+
+        impl AsRef<$ty<Value>> for $wrapper {
+            fn as_ref(&self) -> &$ty<Value> {
+                &self.0
+            }
+        }
+    };
+}
+
+py_class!(PyEvent, Event, "Event", "an event");
+py_class!(PyTask, Task, "Task", "a task");
+py_class!(PyGroup, Group, "Group", "a group");
+
+/// Validates a `dict` as the object type named by its `@type` property, raising a `ValueError`
+/// if it is invalid.
+#[pyfunction]
+pub fn validate(value: &Bound<'_, PyAny>) -> PyResult<()> {
+    let json = from_python(value)?;
+    match json.get("@type").and_then(Value::as_str) {
+        Some("Event") => Event::try_from_json(json).map(drop),
+        Some("Task") => Task::try_from_json(json).map(drop),
+        Some("Group") => Group::try_from_json(json).map(drop),
+        _ => {
+            return Err(PyValueError::new_err(
+                "@type: expected \"Event\", \"Task\", or \"Group\"",
+            ));
+        }
+    }
+    .map_err(value_error)
+}
+
+/// Adds the `Event`, `Task`, and `Group` classes and the `validate` function to a Python module.
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyEvent>()?;
+    m.add_class::<PyTask>()?;
+    m.add_class::<PyGroup>()?;
+    m.add_function(wrap_pyfunction!(validate, m)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dict_round_trip() {
+        Python::initialize();
+        Python::attach(|py| {
+            let dict = PyDict::new(py);
+            dict.set_item("@type", "Event").unwrap();
+            dict.set_item("uid", "a8df6573-0474-496d-8496-033ad45d7fea")
+                .unwrap();
+            dict.set_item("start", "2020-01-15T13:00:00").unwrap();
+            dict.set_item("sequence", 3).unwrap();
+            dict.set_item("keywords", PyDict::new(py)).unwrap();
+            dict.get_item("keywords")
+                .unwrap()
+                .unwrap()
+                .set_item("work", true)
+                .unwrap();
+
+            let event = PyEvent::new(dict.as_any()).unwrap();
+            let output = event.to_dict(py).unwrap();
+            assert!(output.eq(&dict).unwrap());
+            assert!(validate(dict.as_any()).is_ok());
+
+            dict.set_item("sequence", 1.5).unwrap();
+            let error = PyEvent::new(dict.as_any()).unwrap_err();
+            assert!(error.is_instance_of::<PyValueError>(py));
+            assert!(validate(dict.as_any()).is_err());
+
+            dict.set_item("sequence", py.None()).unwrap();
+            dict.set_item("extra", PyTuple::new(py, [1, 2]).unwrap())
+                .unwrap();
+            let json = from_python(dict.as_any()).unwrap();
+            assert_eq!(json["extra"], serde_json::json!([1, 2]));
+
+            let set = py.eval(c"{1}", None, None).unwrap();
+            let error = from_python(&set).unwrap_err();
+            assert!(error.is_instance_of::<PyTypeError>(py));
+        });
+    }
+}
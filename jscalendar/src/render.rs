@@ -0,0 +1,335 @@
+//! Plain text and Markdown agendas.
+//!
+//! [`render_agenda`] groups events by the date on which they start and writes one section per
+//! day, with the events of each day in chronological order. It is intended for expanded
+//! occurrences, such as the entries produced by
+//! [`Event::materialize`](crate::model::object::Event::materialize); recurrence rules are not
+//! expanded. The output format is determined by an [`AgendaRenderer`], and [`Markdown`] and
+//! [`PlainText`] renderers are provided.
+//!
+//! ```text
+//! ## Monday, 2024-01-15
+//!
+//! - **09:00–09:30** Standup (Room 1)
+//! - **All day** Offsite
+//! ```
+
+use std::fmt::{self, Write};
+
+use crate::{
+    json::JsonValue,
+    model::{
+        object::Event,
+        set::{EventStatus, Token},
+        time::{Date, DateTime, Local, Weekday},
+    },
+};
+
+/// The information about an event which is shown in an agenda.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgendaItem<'a> {
+    /// The start of the event.
+    pub start: DateTime<Local>,
+    /// The end of the event, if it has a non-zero duration.
+    pub end: Option<DateTime<Local>>,
+    /// Whether the time of the event should be hidden (its `showWithoutTime` property).
+    pub all_day: bool,
+    /// The title of the event.
+    pub title: Option<&'a str>,
+    /// The name of the first named location of the event, by ID.
+    pub location: Option<&'a str>,
+    /// Whether the event has been cancelled.
+    pub cancelled: bool,
+}
+
+impl<'a> AgendaItem<'a> {
+    /// Returns the agenda item for the given event.
+    pub fn from_event<V: JsonValue>(event: &'a Event<V>) -> Self {
+        let start = *event.start();
+        let end = event
+            .duration()
+            .and_then(|duration| start.checked_add(*duration))
+            .filter(|end| *end != start);
+
+        let mut locations: Vec<_> = event.locations().into_iter().flatten().collect();
+        locations.sort_by_key(|(id, _)| *id);
+        let location = locations
+            .into_iter()
+            .find_map(|(_, location)| location.as_known()?.name())
+            .map(String::as_str);
+
+        Self {
+            start,
+            end,
+            all_day: event.show_without_time_or_default(),
+            title: event.title().map(String::as_str),
+            location,
+            cancelled: event.status() == Some(&Token::Known(EventStatus::Cancelled)),
+        }
+    }
+
+    /// Returns the time span of this item as text, such as `09:00–10:00` or `All day`.
+    ///
+    /// The end date is included if it differs from the start date.
+    pub fn time_label(&self) -> String {
+        if self.all_day {
+            return String::from("All day");
+        }
+
+        let time = |date_time: DateTime<Local>| {
+            format!("{}:{}", date_time.time.hour(), date_time.time.minute())
+        };
+        match self.end {
+            Some(end) if end.date == self.start.date => {
+                format!("{}–{}", time(self.start), time(end))
+            }
+            Some(end) => format!("{}–{} {}", time(self.start), end.date, time(end)),
+            None => time(self.start),
+        }
+    }
+}
+
+/// A format for agendas written by [`render_agenda`].
+pub trait AgendaRenderer {
+    /// Writes the heading of the section for `date`.
+    fn day_heading(&mut self, out: &mut dyn Write, date: Date) -> fmt::Result;
+
+    /// Writes a single item.
+    fn item(&mut self, out: &mut dyn Write, item: &AgendaItem<'_>) -> fmt::Result;
+
+    /// Writes the separator between two sections; by default, an empty line.
+    fn day_separator(&mut self, out: &mut dyn Write) -> fmt::Result {
+        writeln!(out)
+    }
+}
+
+/// Writes an agenda of `events` to `out` in the format of `renderer`.
+///
+/// Events are grouped by start date, and sorted by start time and then by title within each
+/// group.
+pub fn render_agenda<'a, V: JsonValue + 'a>(
+    events: impl IntoIterator<Item = &'a Event<V>>,
+    renderer: &mut impl AgendaRenderer,
+    out: &mut dyn Write,
+) -> fmt::Result {
+    let mut items: Vec<_> = events.into_iter().map(AgendaItem::from_event).collect();
+    items.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| a.title.cmp(&b.title)));
+
+    let mut current = None;
+    for item in &items {
+        if current != Some(item.start.date) {
+            if current.is_some() {
+                renderer.day_separator(out)?;
+            }
+            renderer.day_heading(out, item.start.date)?;
+            current = Some(item.start.date);
+        }
+        renderer.item(out, item)?;
+    }
+
+    Ok(())
+}
+
+/// Returns a Markdown agenda of `events`; see [`render_agenda`].
+pub fn markdown_agenda<'a, V: JsonValue + 'a>(
+    events: impl IntoIterator<Item = &'a Event<V>>,
+) -> String {
+    let mut out = String::new();
+    render_agenda(events, &mut Markdown, &mut out).expect("writing to a string cannot fail");
+    out
+}
+
+/// Returns a plain text agenda of `events`; see [`render_agenda`].
+pub fn plain_text_agenda<'a, V: JsonValue + 'a>(
+    events: impl IntoIterator<Item = &'a Event<V>>,
+) -> String {
+    let mut out = String::new();
+    render_agenda(events, &mut PlainText, &mut out).expect("writing to a string cannot fail");
+    out
+}
+
+/// An [`AgendaRenderer`] which writes each day as a second-level heading followed by a list.
+///
+/// Titles and locations are escaped, and cancelled events are struck through.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Markdown;
+
+impl AgendaRenderer for Markdown {
+    fn day_heading(&mut self, out: &mut dyn Write, date: Date) -> fmt::Result {
+        writeln!(out, "## {}, {date}", weekday_name(date.weekday()))?;
+        writeln!(out)
+    }
+
+    fn item(&mut self, out: &mut dyn Write, item: &AgendaItem<'_>) -> fmt::Result {
+        let mut text = escape_markdown(item.title.unwrap_or(UNTITLED));
+        if let Some(location) = item.location {
+            text = format!("{text} ({})", escape_markdown(location));
+        }
+        if item.cancelled {
+            text = format!("~~{text}~~");
+        }
+        writeln!(out, "- **{}** {text}", item.time_label())
+    }
+}
+
+/// An [`AgendaRenderer`] which writes each day as a line followed by indented items.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlainText;
+
+impl AgendaRenderer for PlainText {
+    fn day_heading(&mut self, out: &mut dyn Write, date: Date) -> fmt::Result {
+        writeln!(out, "{}, {date}", weekday_name(date.weekday()))
+    }
+
+    fn item(&mut self, out: &mut dyn Write, item: &AgendaItem<'_>) -> fmt::Result {
+        write!(
+            out,
+            "  {}  {}",
+            item.time_label(),
+            item.title.unwrap_or(UNTITLED)
+        )?;
+        if let Some(location) = item.location {
+            write!(out, " ({location})")?;
+        }
+        if item.cancelled {
+            write!(out, " [cancelled]")?;
+        }
+        writeln!(out)
+    }
+}
+
+/// The text shown for events without a title.
+const UNTITLED: &str = "(untitled)";
+
+/// Returns the English name of `weekday`.
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Monday => "Monday",
+        Weekday::Tuesday => "Tuesday",
+        Weekday::Wednesday => "Wednesday",
+        Weekday::Thursday => "Thursday",
+        Weekday::Friday => "Friday",
+        Weekday::Saturday => "Saturday",
+        Weekday::Sunday => "Sunday",
+    }
+}
+
+/// Escapes the characters of `text` which have a meaning in inline Markdown.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '~' | '[' | ']' | '<' | '>' | '#'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::json::TryFromJson;
+
+    fn events() -> Vec<Event<Value>> {
+        [
+            json!({
+                "@type": "Event",
+                "uid": "b",
+                "title": "Review *draft*",
+                "start": "2024-01-15T14:00:00",
+                "duration": "PT1H30M",
+                "status": "cancelled"
+            }),
+            json!({
+                "@type": "Event",
+                "uid": "a",
+                "title": "Standup",
+                "start": "2024-01-15T09:00:00",
+                "duration": "PT30M",
+                "locations": {
+                    "2": { "@type": "Location", "name": "Room 2" },
+                    "1": { "@type": "Location", "name": "Room 1" }
+                }
+            }),
+            json!({
+                "@type": "Event",
+                "uid": "c",
+                "title": "Offsite",
+                "start": "2024-01-16T00:00:00",
+                "duration": "P1D",
+                "showWithoutTime": true
+            }),
+            json!({
+                "@type": "Event",
+                "uid": "d",
+                "start": "2024-01-16T22:00:00",
+                "duration": "PT4H"
+            }),
+        ]
+        .into_iter()
+        .map(|event| Event::try_from_json(event).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn markdown() {
+        assert_eq!(
+            markdown_agenda(&events()),
+            "## Monday, 2024-01-15\n\
+             \n\
+             - **09:00–09:30** Standup (Room 1)\n\
+             - **14:00–15:30** ~~Review \\*draft\\*~~\n\
+             \n\
+             ## Tuesday, 2024-01-16\n\
+             \n\
+             - **All day** Offsite\n\
+             - **22:00–2024-01-17 02:00** (untitled)\n"
+        );
+    }
+
+    #[test]
+    fn plain_text() {
+        assert_eq!(
+            plain_text_agenda(&events()),
+            "Monday, 2024-01-15\n\
+             \x20 09:00–09:30  Standup (Room 1)\n\
+             \x20 14:00–15:30  Review *draft* [cancelled]\n\
+             \n\
+             Tuesday, 2024-01-16\n\
+             \x20 All day  Offsite\n\
+             \x20 22:00–2024-01-17 02:00  (untitled)\n"
+        );
+    }
+
+    #[test]
+    fn custom_renderer() {
+        struct Titles;
+
+        impl AgendaRenderer for Titles {
+            fn day_heading(&mut self, out: &mut dyn Write, date: Date) -> fmt::Result {
+                write!(out, "{date}:")
+            }
+
+            fn item(&mut self, out: &mut dyn Write, item: &AgendaItem<'_>) -> fmt::Result {
+                write!(out, " {}", item.title.unwrap_or("-"))
+            }
+
+            fn day_separator(&mut self, out: &mut dyn Write) -> fmt::Result {
+                write!(out, "; ")
+            }
+        }
+
+        let mut out = String::new();
+        render_agenda(&events(), &mut Titles, &mut out).unwrap();
+        assert_eq!(
+            out,
+            "2024-01-15: Standup Review *draft*; 2024-01-16: Offsite -"
+        );
+    }
+}
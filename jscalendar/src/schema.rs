@@ -0,0 +1,803 @@
+//! JSON Schema for the object model (requires the `schema` feature).
+//!
+//! [`schema`] returns a [JSON Schema (draft 2020-12)] document which accepts the JSON
+//! representation of an [`Event`], a [`Task`], or a [`Group`], so that services written in other
+//! languages, such as API gateways, can validate payloads before they reach this crate. The
+//! document is generated from the object model: the property names come from [`props`], the known
+//! values of the enumerations from their [`TokenParse`] implementations, and the formats of dates,
+//! durations, offsets, and identifiers from the same grammars as the parsers of this crate.
+//!
+//! The schema describes what [`TryFromJson`] accepts, with these differences:
+//!
+//! - `@type` is required on the top-level object, which selects the definition to validate it
+//!   against, whereas [`TryFromJson`] only requires it on the entries of a group;
+//! - the schema does not check constraints between properties other than `count` and `until` of
+//!   a recurrence rule, nor that dates exist (so `2023-02-30` matches the date-time pattern);
+//! - the `uri` and `email` formats are annotations, unless a validator is configured to assert
+//!   them.
+//!
+//! Enumerations whose unknown values this crate keeps (those held by a [`Token`]) list their
+//! known values but accept any string, and names are matched ignoring ASCII case, as
+//! [`TokenParse::parse_token`] does. Vendor properties are allowed on every object.
+//!
+//! ```
+//! use jscalendar::schema::{DIALECT, schema};
+//!
+//! let schema = schema();
+//! assert_eq!(schema["$schema"], DIALECT);
+//! assert!(schema["$defs"]["Event"]["properties"]["start"].is_object());
+//! ```
+//!
+//! [JSON Schema (draft 2020-12)]: https://json-schema.org/draft/2020-12/json-schema-core
+//! [`Event`]: crate::model::object::Event
+//! [`Task`]: crate::model::object::Task
+//! [`Group`]: crate::model::object::Group
+//! [`TryFromJson`]: crate::json::TryFromJson
+//! [`Token`]: crate::model::set::Token
+
+use calendar_types::set::TokenParse;
+use rfc5545_types::rrule::{Rscale, Skip};
+use serde_json::{Map, Value, json};
+
+use crate::model::{
+    object::RRULE_EXTRA_PARTS,
+    props,
+    set::{
+        AlertAction, AlertRelativeTo, Css3Color, DisplayPurpose, EventStatus, FreeBusyStatus,
+        LinkRelation, LocationType, Method, ParticipantKind, ParticipantRole, ParticipationStatus,
+        Privacy, RelationValue, ScheduleAgent, TaskProgress, VirtualLocationFeature,
+    },
+};
+
+/// The URI of the JSON Schema dialect of the documents returned by this module.
+pub const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// The largest magnitude of an `Int` or `UnsignedInt` (RFC 8984 §1.4.1).
+const MAX_INT: i64 = (1 << 53) - 1;
+
+/// A fractional second without trailing zeros, as required by RFC 8984 §1.4.3.
+const FRACTION: &str = r"(?:\.\d{0,8}[1-9])?";
+
+/// Returns a schema which accepts an [`Event`], a [`Task`], or a [`Group`], as selected by its
+/// `@type` property.
+///
+/// [`Event`]: crate::model::object::Event
+/// [`Task`]: crate::model::object::Task
+/// [`Group`]: crate::model::object::Group
+pub fn schema() -> Value {
+    document(json!({
+        "required": [props::TYPE],
+        "oneOf": [reference("Event"), reference("Task"), reference("Group")],
+    }))
+}
+
+/// Returns a schema which accepts an [`Event`](crate::model::object::Event).
+pub fn event_schema() -> Value {
+    document(reference("Event"))
+}
+
+/// Returns a schema which accepts a [`Task`](crate::model::object::Task).
+pub fn task_schema() -> Value {
+    document(reference("Task"))
+}
+
+/// Returns a schema which accepts a [`Group`](crate::model::object::Group).
+pub fn group_schema() -> Value {
+    document(reference("Group"))
+}
+
+/// Returns a schema document with the root schema `root` and every definition of this module.
+fn document(root: Value) -> Value {
+    let mut document = Map::new();
+    document.insert("$schema".into(), DIALECT.into());
+    if let Value::Object(root) = root {
+        document.extend(root);
+    }
+    document.insert("$defs".into(), definitions());
+    Value::Object(document)
+}
+
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/$defs/{name}") })
+}
+
+fn definitions() -> Value {
+    let mut defs = Map::new();
+    let mut define = |name: &str, schema: Value| defs.insert(name.into(), schema);
+
+    define("Event", event());
+    define("Task", task());
+    define("Group", group());
+    define("Location", location());
+    define("VirtualLocation", virtual_location());
+    define("Link", link());
+    define("Relation", relation());
+    define("Participant", participant(false));
+    define("TaskParticipant", participant(true));
+    define("Alert", alert());
+    define("OffsetTrigger", offset_trigger());
+    define("AbsoluteTrigger", absolute_trigger());
+    define("TimeZone", time_zone());
+    define("TimeZoneRule", time_zone_rule());
+    define("RecurrenceRule", recurrence_rule());
+    define("NDay", n_day());
+    define("PatchObject", json!({ "type": "object" }));
+
+    define("Id", pattern(r"^[A-Za-z0-9_-]{1,255}$"));
+    define("UTCDateTime", utc_date_time());
+    define("LocalDateTime", local_date_time());
+    define("Duration", pattern(&format!("^{}$", duration())));
+    define("SignedDuration", pattern(&format!("^[+-]?{}$", duration())));
+    define("UtcOffset", pattern(r"^[+-]\d{2}:\d{2}(?::\d{2})?$"));
+    define("Int", int(-MAX_INT, MAX_INT));
+    define("UnsignedInt", int(0, MAX_INT));
+
+    Value::Object(defs)
+}
+
+// Top-level objects (RFC 8984 §5)
+
+fn event() -> Value {
+    let mut properties = vec![
+        (props::START, reference("LocalDateTime")),
+        (props::DURATION, reference("Duration")),
+        (props::STATUS, open_tokens::<EventStatus>()),
+    ];
+    properties.extend(common_properties());
+    properties.push((props::PARTICIPANTS, map_of(id(), reference("Participant"))));
+    object("Event", true, &[props::UID, props::START], properties)
+}
+
+fn task() -> Value {
+    let mut properties = vec![
+        (props::DUE, reference("LocalDateTime")),
+        (props::START, reference("LocalDateTime")),
+        (props::ESTIMATED_DURATION, reference("Duration")),
+        (props::PERCENT_COMPLETE, int(0, 100)),
+        (props::PROGRESS, open_tokens::<TaskProgress>()),
+        (props::PROGRESS_UPDATED, reference("UTCDateTime")),
+    ];
+    properties.extend(common_properties());
+    properties.push((
+        props::PARTICIPANTS,
+        map_of(id(), reference("TaskParticipant")),
+    ));
+    object("Task", true, &[props::UID], properties)
+}
+
+fn group() -> Value {
+    let properties = vec![
+        (
+            props::ENTRIES,
+            array_of(json!({ "oneOf": [reference("Event"), reference("Task")] })),
+        ),
+        (props::SOURCE, uri()),
+        (props::UID, uid()),
+        (props::PROD_ID, string()),
+        (props::CREATED, reference("UTCDateTime")),
+        (props::UPDATED, reference("UTCDateTime")),
+        (props::TITLE, string()),
+        (props::DESCRIPTION, string()),
+        (props::DESCRIPTION_CONTENT_TYPE, string()),
+        (props::LINKS, map_of(id(), maybe_unknown("Link"))),
+        (props::LOCALE, language_tag()),
+        (props::KEYWORDS, set_of(string())),
+        (props::CATEGORIES, set_of(string())),
+        (props::COLOR, color()),
+        (props::TIME_ZONES, time_zones()),
+    ];
+    object("Group", true, &[props::UID, props::ENTRIES], properties)
+}
+
+/// Returns the properties shared by events and tasks (RFC 8984 §4), except `participants`.
+fn common_properties() -> Vec<(&'static str, Value)> {
+    vec![
+        // Metadata properties (RFC 8984 §4.1)
+        (props::UID, uid()),
+        (props::RELATED_TO, map_of(uid(), reference("Relation"))),
+        (props::PROD_ID, string()),
+        (props::CREATED, reference("UTCDateTime")),
+        (props::UPDATED, reference("UTCDateTime")),
+        (props::SEQUENCE, reference("UnsignedInt")),
+        (props::METHOD, open_tokens::<Method>()),
+        // What and where properties (RFC 8984 §4.2)
+        (props::TITLE, string()),
+        (props::DESCRIPTION, string()),
+        (props::DESCRIPTION_CONTENT_TYPE, string()),
+        (props::SHOW_WITHOUT_TIME, boolean()),
+        (props::LOCATIONS, map_of(id(), maybe_unknown("Location"))),
+        (
+            props::VIRTUAL_LOCATIONS,
+            map_of(id(), maybe_unknown("VirtualLocation")),
+        ),
+        (props::LINKS, map_of(id(), maybe_unknown("Link"))),
+        (props::LOCALE, language_tag()),
+        (props::KEYWORDS, set_of(string())),
+        (props::CATEGORIES, set_of(string())),
+        (props::COLOR, color()),
+        // Recurrence properties (RFC 8984 §4.3)
+        (props::RECURRENCE_ID, reference("LocalDateTime")),
+        (props::RECURRENCE_ID_TIME_ZONE, string()),
+        (
+            props::RECURRENCE_RULES,
+            array_of(reference("RecurrenceRule")),
+        ),
+        (
+            props::EXCLUDED_RECURRENCE_RULES,
+            array_of(reference("RecurrenceRule")),
+        ),
+        (props::RECURRENCE_OVERRIDES, recurrence_overrides()),
+        (props::EXCLUDED, boolean()),
+        // Sharing and scheduling properties (RFC 8984 §4.4)
+        (props::PRIORITY, int(0, 9)),
+        (props::FREE_BUSY_STATUS, open_tokens::<FreeBusyStatus>()),
+        (props::PRIVACY, open_tokens::<Privacy>()),
+        (props::REPLY_TO, methods(true)),
+        (props::SENT_BY, cal_address()),
+        (props::REQUEST_STATUS, pattern(r"^\d\.\d+(?:\.\d+)?;")),
+        // Alerts properties (RFC 8984 §4.5)
+        (props::USE_DEFAULT_ALERTS, boolean()),
+        (props::ALERTS, map_of(id(), maybe_unknown("Alert"))),
+        // Multilingual properties (RFC 8984 §4.6)
+        (
+            props::LOCALIZATIONS,
+            map_of(language_tag(), reference("PatchObject")),
+        ),
+        // Time zone properties (RFC 8984 §4.7)
+        (props::TIME_ZONE, string()),
+        (props::TIME_ZONES, time_zones()),
+    ]
+}
+
+// Nested objects
+
+fn location() -> Value {
+    let properties = vec![
+        (props::NAME, string()),
+        (props::DESCRIPTION, string()),
+        (props::LOCATION_TYPES, set_of(open_tokens::<LocationType>())),
+        (props::RELATIVE_TO, open_tokens::<RelationValue>()),
+        (props::TIME_ZONE, string()),
+        (props::COORDINATES, pattern("^[gG][eE][oO]:")),
+        (props::LINKS, map_of(id(), maybe_unknown("Link"))),
+    ];
+    object("Location", false, &[], properties)
+}
+
+fn virtual_location() -> Value {
+    let properties = vec![
+        (props::NAME, string()),
+        (props::DESCRIPTION, string()),
+        (props::URI, uri()),
+        (
+            props::FEATURES,
+            set_of(open_tokens::<VirtualLocationFeature>()),
+        ),
+    ];
+    object("VirtualLocation", false, &[props::URI], properties)
+}
+
+fn link() -> Value {
+    let properties = vec![
+        (props::HREF, uri()),
+        (props::CONTENT_ID, non_empty()),
+        (props::MEDIA_TYPE, pattern("^[^/]+/[^/]+$")),
+        (props::SIZE, reference("UnsignedInt")),
+        (props::REL, open_tokens::<LinkRelation>()),
+        (props::DISPLAY, open_tokens::<DisplayPurpose>()),
+        (props::TITLE, string()),
+    ];
+    object("Link", false, &[props::HREF], properties)
+}
+
+fn relation() -> Value {
+    let properties = vec![(props::RELATION, set_of(open_tokens::<RelationValue>()))];
+    object("Relation", false, &[], properties)
+}
+
+/// Returns the definition of a participant, with the properties which only occur in the
+/// participants of tasks if `task` is true.
+fn participant(task: bool) -> Value {
+    let mut properties = vec![
+        (props::NAME, string()),
+        (props::EMAIL, email()),
+        (props::DESCRIPTION, string()),
+        (props::SEND_TO, methods(false)),
+        (props::KIND, open_tokens::<ParticipantKind>()),
+        (props::ROLES, set_of(open_tokens::<ParticipantRole>())),
+        (props::LOCATION_ID, id()),
+        (props::LANGUAGE, language_tag()),
+        (
+            props::PARTICIPATION_STATUS,
+            open_tokens::<ParticipationStatus>(),
+        ),
+        (props::PARTICIPATION_COMMENT, string()),
+        (props::EXPECT_REPLY, boolean()),
+        (props::SCHEDULE_AGENT, open_tokens::<ScheduleAgent>()),
+        (props::SCHEDULE_FORCE_SEND, boolean()),
+        (props::SCHEDULE_SEQUENCE, reference("UnsignedInt")),
+        (
+            props::SCHEDULE_STATUS,
+            array_of(pattern(r"^\d\.\d+(?:\.\d+)?$")),
+        ),
+        (props::SCHEDULE_UPDATED, reference("UTCDateTime")),
+        (props::SENT_BY, email()),
+        (props::INVITED_BY, id()),
+        (props::DELEGATED_TO, set_of(id())),
+        (props::DELEGATED_FROM, set_of(id())),
+        (props::MEMBER_OF, set_of(id())),
+        (props::LINKS, map_of(id(), maybe_unknown("Link"))),
+    ];
+    if task {
+        properties.extend([
+            (props::PROGRESS, open_tokens::<TaskProgress>()),
+            (props::PROGRESS_UPDATED, reference("UTCDateTime")),
+            (props::PERCENT_COMPLETE, int(0, 100)),
+        ]);
+    }
+    object("Participant", false, &[], properties)
+}
+
+fn alert() -> Value {
+    let trigger = |name: &str| {
+        json!({
+            "if": { "properties": { (props::TYPE): { "const": name } } },
+            "then": reference(name),
+        })
+    };
+    let properties = vec![
+        (
+            props::TRIGGER,
+            json!({
+                "type": "object",
+                "properties": { (props::TYPE): string() },
+                "required": [props::TYPE],
+                "allOf": [trigger("OffsetTrigger"), trigger("AbsoluteTrigger")],
+            }),
+        ),
+        (props::ACKNOWLEDGED, reference("UTCDateTime")),
+        (props::RELATED_TO, map_of(string(), reference("Relation"))),
+        (props::ACTION, open_tokens::<AlertAction>()),
+    ];
+    object("Alert", false, &[props::TRIGGER], properties)
+}
+
+fn offset_trigger() -> Value {
+    let properties = vec![
+        (props::OFFSET, reference("SignedDuration")),
+        (props::RELATIVE_TO, open_tokens::<AlertRelativeTo>()),
+    ];
+    object("OffsetTrigger", false, &[props::OFFSET], properties)
+}
+
+fn absolute_trigger() -> Value {
+    let properties = vec![(props::WHEN, reference("UTCDateTime"))];
+    object("AbsoluteTrigger", false, &[props::WHEN], properties)
+}
+
+fn time_zone() -> Value {
+    let properties = vec![
+        (props::TZ_ID, string()),
+        (props::UPDATED, reference("UTCDateTime")),
+        (props::URL, uri()),
+        (props::VALID_UNTIL, reference("UTCDateTime")),
+        (props::ALIASES, set_of(string())),
+        (props::STANDARD, array_of(reference("TimeZoneRule"))),
+        (props::DAYLIGHT, array_of(reference("TimeZoneRule"))),
+    ];
+    object("TimeZone", false, &[props::TZ_ID], properties)
+}
+
+fn time_zone_rule() -> Value {
+    let properties = vec![
+        (props::START, reference("LocalDateTime")),
+        (props::OFFSET_FROM, reference("UtcOffset")),
+        (props::OFFSET_TO, reference("UtcOffset")),
+        (
+            props::RECURRENCE_RULES,
+            array_of(reference("RecurrenceRule")),
+        ),
+        (props::RECURRENCE_OVERRIDES, recurrence_overrides()),
+        (props::NAMES, set_of(string())),
+        (props::COMMENTS, array_of(string())),
+    ];
+    let required = [props::START, props::OFFSET_FROM, props::OFFSET_TO];
+    object("TimeZoneRule", false, &required, properties)
+}
+
+/// Returns the definition of a recurrence rule (RFC 8984 §4.3.3).
+///
+/// `byMonth` holds month numbers, as in the RRULE of iCalendar, since that is what the parser of
+/// this crate accepts.
+fn recurrence_rule() -> Value {
+    const FREQUENCIES: [&str; 7] = [
+        "yearly", "monthly", "weekly", "daily", "hourly", "minutely", "secondly",
+    ];
+
+    let lowercase = |values: &[&str]| -> Vec<String> {
+        values.iter().map(|value| value.to_lowercase()).collect()
+    };
+    let rscales: Vec<_> = Rscale::VALUES.iter().map(Rscale::canonical_str).collect();
+    let skips: Vec<_> = Skip::VALUES.iter().map(Skip::canonical_str).collect();
+
+    let properties = vec![
+        (props::FREQUENCY, closed_tokens(FREQUENCIES)),
+        (props::INTERVAL, int(1, MAX_INT)),
+        (
+            props::RSCALE,
+            json!({ "anyOf": [{ "enum": lowercase(&rscales) }, string()] }),
+        ),
+        (props::SKIP, closed_tokens(lowercase(&skips))),
+        (props::FIRST_DAY_OF_WEEK, weekday()),
+        (props::BY_DAY, array_of(reference("NDay"))),
+        (props::BY_MONTH_DAY, array_of(nonzero_int(31))),
+        (props::BY_MONTH, array_of(int(1, 12))),
+        (props::BY_YEAR_DAY, array_of(nonzero_int(366))),
+        (props::BY_WEEK_NO, array_of(nonzero_int(53))),
+        (props::BY_HOUR, array_of(int(0, 23))),
+        (props::BY_MINUTE, array_of(int(0, 59))),
+        (props::BY_SECOND, array_of(int(0, 60))),
+        (props::BY_SET_POSITION, array_of(nonzero_int(366))),
+        (props::COUNT, reference("UnsignedInt")),
+        (
+            props::UNTIL,
+            pattern(&format!(
+                r"^\d{{4}}-\d{{2}}-\d{{2}}(?:T\d{{2}}:\d{{2}}:\d{{2}}{FRACTION})?$"
+            )),
+        ),
+        (RRULE_EXTRA_PARTS, array_of(pattern("^[A-Za-z0-9-]+="))),
+    ];
+    let mut rule = object("RecurrenceRule", false, &[props::FREQUENCY], properties);
+    rule["not"] = json!({ "required": [props::COUNT, props::UNTIL] });
+    rule
+}
+
+fn n_day() -> Value {
+    let properties = vec![
+        (props::DAY, weekday()),
+        (props::NTH_OF_PERIOD, nonzero_int(53)),
+    ];
+    object("NDay", false, &[props::DAY], properties)
+}
+
+// Builders
+
+/// Returns the definition of an object with the given `@type`, which is required if `typed` is
+/// true and optional otherwise.
+fn object(
+    type_name: &str,
+    typed: bool,
+    required: &[&str],
+    properties: Vec<(&str, Value)>,
+) -> Value {
+    let mut map = Map::new();
+    map.insert(props::TYPE.into(), json!({ "const": type_name }));
+    map.extend(
+        properties
+            .into_iter()
+            .map(|(name, schema)| (name.to_owned(), schema)),
+    );
+
+    let required: Vec<_> = typed
+        .then_some(props::TYPE)
+        .into_iter()
+        .chain(required.iter().copied())
+        .collect();
+    json!({ "type": "object", "properties": map, "required": required })
+}
+
+/// Returns a schema for an object which is the definition `name` if its `@type` is absent or
+/// `name`, and any object otherwise, like a [`MaybeUnknown`](crate::model::object::MaybeUnknown).
+fn maybe_unknown(name: &str) -> Value {
+    json!({
+        "type": "object",
+        "if": {
+            "properties": { (props::TYPE): { "not": { "const": name } } },
+            "required": [props::TYPE],
+        },
+        "else": reference(name),
+    })
+}
+
+fn map_of(keys: Value, values: Value) -> Value {
+    json!({ "type": "object", "propertyNames": keys, "additionalProperties": values })
+}
+
+/// Returns a schema for a set, which is an object whose values are all `true` (RFC 8984 §1.4.1).
+fn set_of(keys: Value) -> Value {
+    map_of(keys, json!({ "const": true }))
+}
+
+fn array_of(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+/// Returns a schema for a [`Token`](crate::model::set::Token) of `T`, which lists the known values
+/// of `T` but accepts any string.
+fn open_tokens<T: TokenParse>() -> Value {
+    let known: Vec<_> = T::VALUES.iter().map(T::canonical_str).collect();
+    json!({ "anyOf": [{ "enum": known }, string()] })
+}
+
+/// Returns a schema for a string which is one of `values`, ignoring ASCII case.
+fn closed_tokens<S: AsRef<str>>(values: impl IntoIterator<Item = S>) -> Value {
+    let values: Vec<String> = values
+        .into_iter()
+        .map(|value| value.as_ref().to_owned())
+        .collect();
+    let alternatives: Vec<_> = values
+        .iter()
+        .map(|value| ascii_case_insensitive(value))
+        .collect();
+    json!({
+        "type": "string",
+        "pattern": format!("^(?:{})$", alternatives.join("|")),
+        "examples": values,
+    })
+}
+
+/// Returns a regular expression which matches `value` ignoring ASCII case.
+fn ascii_case_insensitive(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphabetic() {
+                format!("[{}{}]", c.to_ascii_lowercase(), c.to_ascii_uppercase())
+            } else if r"\^$.|?*+()[]{}/".contains(c) {
+                format!(r"\{c}")
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+fn pattern(pattern: &str) -> Value {
+    json!({ "type": "string", "pattern": pattern })
+}
+
+fn int(minimum: i64, maximum: i64) -> Value {
+    json!({ "type": "integer", "minimum": minimum, "maximum": maximum })
+}
+
+fn nonzero_int(magnitude: i64) -> Value {
+    json!({ "allOf": [int(-magnitude, magnitude), { "not": { "const": 0 } }] })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn non_empty() -> Value {
+    json!({ "type": "string", "minLength": 1 })
+}
+
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+// Strings with a format
+
+fn id() -> Value {
+    reference("Id")
+}
+
+fn uid() -> Value {
+    non_empty()
+}
+
+fn uri() -> Value {
+    json!({ "type": "string", "format": "uri" })
+}
+
+fn email() -> Value {
+    json!({ "type": "string", "format": "email", "pattern": "^[^@]+@[^@]+$" })
+}
+
+fn cal_address() -> Value {
+    pattern("^[mM][aA][iI][lL][tT][oO]:")
+}
+
+fn language_tag() -> Value {
+    pattern("^[A-Za-z]{1,8}(?:-[A-Za-z0-9]{1,8})*$")
+}
+
+fn weekday() -> Value {
+    closed_tokens(["mo", "tu", "we", "th", "fr", "sa", "su"])
+}
+
+fn color() -> Value {
+    let names: Vec<_> = Css3Color::iter().map(|color| color.to_string()).collect();
+    let mut names = closed_tokens(names);
+    names["pattern"] = format!(
+        "{}|^#[0-9A-Fa-f]{{6}}$",
+        names["pattern"].as_str().unwrap_or_default()
+    )
+    .into();
+    names
+}
+
+/// Returns a schema for the `replyTo` of an object (RFC 8984 §4.4.4) if `web` is true, and for
+/// the `sendTo` of a participant (RFC 8984 §4.4.6) otherwise.
+fn methods(web: bool) -> Value {
+    let mut properties = Map::new();
+    properties.insert(props::IMIP.into(), cal_address());
+    if web {
+        properties.insert(props::WEB.into(), pattern("^[hH][tT][tT][pP][sS]:"));
+    }
+    json!({
+        "type": "object",
+        "properties": properties,
+        "propertyNames": { "pattern": "^[A-Za-z0-9]+$" },
+        "additionalProperties": uri(),
+    })
+}
+
+fn time_zones() -> Value {
+    map_of(pattern("^/"), reference("TimeZone"))
+}
+
+fn recurrence_overrides() -> Value {
+    map_of(reference("LocalDateTime"), reference("PatchObject"))
+}
+
+fn utc_date_time() -> Value {
+    pattern(&format!(
+        r"^\d{{4}}-\d{{2}}-\d{{2}}T\d{{2}}:\d{{2}}:\d{{2}}{FRACTION}Z$"
+    ))
+}
+
+fn local_date_time() -> Value {
+    pattern(&format!(
+        r"^\d{{4}}-\d{{2}}-\d{{2}}T\d{{2}}:\d{{2}}:\d{{2}}{FRACTION}$"
+    ))
+}
+
+/// Returns a regular expression for the `duration` rule of RFC 8984 §1.4.6, without anchors.
+fn duration() -> String {
+    let second = format!(r"\d+{FRACTION}S");
+    let time = format!(r"T(?:\d+H(?:\d+M(?:{second})?)?|\d+M(?:{second})?|{second})");
+    format!(r"P(?:(?:\d+W(?:\d+D)?|\d+D)(?:{time})?|{time})")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Collects the targets of the `$ref`s in `value`.
+    fn references<'a>(value: &'a Value, targets: &mut Vec<&'a str>) {
+        match value {
+            Value::Object(object) => {
+                for (key, value) in object {
+                    match (key.as_str(), value) {
+                        ("$ref", Value::String(target)) => targets.push(target),
+                        _ => references(value, targets),
+                    }
+                }
+            }
+            Value::Array(array) => array.iter().for_each(|value| references(value, targets)),
+            _ => {}
+        }
+    }
+
+    /// Returns whether `value` describes a property named `name`.
+    fn describes(value: &Value, name: &str) -> bool {
+        match value {
+            Value::Object(object) => {
+                object
+                    .get("properties")
+                    .is_some_and(|properties| properties.get(name).is_some())
+                    || object.values().any(|value| describes(value, name))
+            }
+            Value::Array(array) => array.iter().any(|value| describes(value, name)),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn references_resolve() {
+        let schema = schema();
+        let mut targets = Vec::new();
+        references(&schema, &mut targets);
+        assert!(!targets.is_empty());
+        for target in targets {
+            let name = target.strip_prefix("#/$defs/").unwrap();
+            assert!(schema["$defs"][name].is_object(), "{target}");
+        }
+    }
+
+    #[test]
+    fn every_property_is_described() {
+        let schema = schema();
+        let defs = schema["$defs"].as_object().unwrap();
+        for name in props::ALL {
+            assert!(describes(&schema, name), "{name}");
+        }
+
+        let event = &defs["Event"];
+        assert_eq!(event["required"], json!(["@type", "uid", "start"]));
+        assert_eq!(event["properties"]["@type"], json!({ "const": "Event" }));
+        assert_eq!(
+            defs["Task"]["properties"]["participants"]["additionalProperties"],
+            reference("TaskParticipant")
+        );
+        assert!(defs["TaskParticipant"]["properties"]["progress"].is_object());
+        assert!(defs["Participant"]["properties"]["progress"].is_null());
+    }
+
+    #[test]
+    fn tokens() {
+        let schema = event_schema();
+        let event = &schema["$defs"]["Event"]["properties"];
+        let known = event["status"]["anyOf"][0]["enum"].as_array().unwrap();
+        assert!(known.contains(&json!("confirmed")));
+        assert!(known.contains(&json!("tentative")));
+
+        let rule = &schema["$defs"]["RecurrenceRule"]["properties"];
+        assert_eq!(rule["frequency"]["examples"][0], "yearly");
+        assert!(
+            rule["frequency"]["pattern"]
+                .as_str()
+                .unwrap()
+                .starts_with("^(?:[yY][eE][aA][rR][lL][yY]|")
+        );
+        assert!(
+            rule["rscale"]["anyOf"][0]["enum"]
+                .as_array()
+                .unwrap()
+                .contains(&json!("gregorian"))
+        );
+
+        let color = schema["$defs"]["Event"]["properties"]["color"]["pattern"]
+            .as_str()
+            .unwrap();
+        assert!(color.contains("[sS][tT][eE][eE][lL][bB][lL][uU][eE]"));
+        assert!(color.ends_with("|^#[0-9A-Fa-f]{6}$"));
+    }
+
+    #[test]
+    fn duration_pattern() {
+        assert_eq!(
+            duration(),
+            r"P(?:(?:\d+W(?:\d+D)?|\d+D)(?:T(?:\d+H(?:\d+M(?:\d+(?:\.\d{0,8}[1-9])?S)?)?|\d+M(?:\d+(?:\.\d{0,8}[1-9])?S)?|\d+(?:\.\d{0,8}[1-9])?S))?|T(?:\d+H(?:\d+M(?:\d+(?:\.\d{0,8}[1-9])?S)?)?|\d+M(?:\d+(?:\.\d{0,8}[1-9])?S)?|\d+(?:\.\d{0,8}[1-9])?S))"
+        );
+        assert_eq!(ascii_case_insensitive("a-1."), r"[aA]-1\.");
+    }
+
+    #[test]
+    fn properties_match_reflection() {
+        use std::collections::BTreeSet;
+
+        use props::{ObjectType, Property};
+
+        let schema = schema();
+        for &object in ObjectType::ALL {
+            // the properties of task participants are a superset of those of event participants
+            let name = match object {
+                ObjectType::Participant => "TaskParticipant",
+                _ => object.as_str(),
+            };
+            let described: BTreeSet<_> = schema["$defs"][name]["properties"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(String::as_str)
+                .filter(|&key| key != RRULE_EXTRA_PARTS)
+                .collect();
+            let expected: BTreeSet<_> = Property::ALL
+                .iter()
+                .filter(|property| property.applies_to(object))
+                .map(|property| property.as_str())
+                .collect();
+            assert_eq!(described, expected, "{object}");
+        }
+    }
+
+    #[test]
+    fn documents() {
+        assert_eq!(group_schema()["$ref"], "#/$defs/Group");
+        assert_eq!(task_schema()["$schema"], DIALECT);
+        assert_eq!(schema()["required"], json!(["@type"]));
+        assert_eq!(schema()["oneOf"].as_array().unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,128 @@
+//! The mapping between VALARM components and JSCalendar alerts, shared by the [`jcal`] module and
+//! the `ical` module.
+//!
+//! [`jcal`]: crate::jcal
+
+use crate::{
+    json::JsonValue,
+    model::{
+        object::{AbsoluteTrigger, Alert, OffsetTrigger, Trigger},
+        set::{AlertAction, AlertRelativeTo, Token},
+        time::{DateTime, SignedDuration, Utc},
+    },
+};
+
+/// The parts of a VALARM component which an [`Alert`] can represent.
+pub(crate) struct Valarm {
+    /// The ACTION of the VALARM, in uppercase.
+    pub(crate) action: String,
+    /// The TRIGGER of the VALARM.
+    pub(crate) trigger: ValarmTrigger,
+    /// The ACKNOWLEDGED time of the VALARM.
+    pub(crate) acknowledged: Option<DateTime<Utc>>,
+}
+
+/// The value of a TRIGGER property.
+pub(crate) enum ValarmTrigger {
+    /// A duration, which is relative to the end of the object if `end` is set (`RELATED=END`).
+    Offset { offset: SignedDuration, end: bool },
+    /// A UTC date-time.
+    Absolute(DateTime<Utc>),
+}
+
+/// The parts of an [`Alert`] which a VALARM cannot represent.
+pub(crate) struct AlertLosses {
+    /// Whether the alert has a `relatedTo` property.
+    pub(crate) related_to: bool,
+    /// The names of the vendor-specific properties of the alert and its trigger.
+    pub(crate) vendor_properties: Vec<String>,
+}
+
+impl Valarm {
+    /// Converts this VALARM into an [`Alert`].
+    ///
+    /// The DISPLAY and EMAIL actions become the registered actions of the same name, and any other
+    /// action, including AUDIO, becomes an unknown action token with the lowercase action name.
+    pub(crate) fn into_alert<V: JsonValue>(self) -> Alert<V> {
+        let trigger = match self.trigger {
+            ValarmTrigger::Offset { offset, end } => {
+                let mut trigger = OffsetTrigger::new(offset);
+                if end {
+                    trigger.set_relative_to(Token::Known(AlertRelativeTo::End));
+                }
+                Trigger::Offset(trigger)
+            }
+            ValarmTrigger::Absolute(when) => Trigger::Absolute(AbsoluteTrigger::new(when)),
+        };
+
+        let mut alert = Alert::new(trigger);
+        let Ok(action) = self.action.to_ascii_lowercase().parse();
+        alert.set_action(action);
+        if let Some(acknowledged) = self.acknowledged {
+            alert.set_acknowledged(acknowledged);
+        }
+        alert
+    }
+
+    /// Converts an [`Alert`] into a VALARM, returning the parts of `alert` which were lost, or
+    /// returns `None` if its trigger has an unknown type.
+    ///
+    /// An alert without an action becomes a DISPLAY alarm (RFC 8984 §4.5.2).
+    pub(crate) fn from_alert<V: JsonValue>(alert: &Alert<V>) -> Option<(Self, AlertLosses)> {
+        let mut vendor_properties: Vec<_> = alert
+            .vendor_property_iter()
+            .map(|(key, _)| key.to_string())
+            .collect();
+
+        let trigger = match alert.trigger() {
+            Trigger::Offset(trigger) => {
+                vendor_properties.extend(
+                    trigger
+                        .vendor_property_iter()
+                        .map(|(key, _)| format!("trigger/{key}")),
+                );
+                ValarmTrigger::Offset {
+                    offset: *trigger.offset(),
+                    end: trigger.relative_to() == Some(&Token::Known(AlertRelativeTo::End)),
+                }
+            }
+            Trigger::Absolute(trigger) => {
+                vendor_properties.extend(
+                    trigger
+                        .vendor_property_iter()
+                        .map(|(key, _)| format!("trigger/{key}")),
+                );
+                ValarmTrigger::Absolute(*trigger.when())
+            }
+            _ => return None,
+        };
+
+        let action = match alert.action() {
+            None => AlertAction::Display.to_string(),
+            Some(Token::Known(action)) => action.to_string(),
+            Some(Token::Unknown(action)) => action.to_string(),
+        };
+
+        let valarm = Self {
+            action: action.to_ascii_uppercase(),
+            trigger,
+            acknowledged: alert.acknowledged().copied(),
+        };
+        let losses = AlertLosses {
+            related_to: alert.related_to().is_some(),
+            vendor_properties,
+        };
+        Some((valarm, losses))
+    }
+
+    /// Returns `true` if the VALARM has a DESCRIPTION, which every action but AUDIO requires
+    /// (RFC 5545 §3.6.6).
+    pub(crate) fn has_description(&self) -> bool {
+        self.action != "AUDIO"
+    }
+
+    /// Returns `true` if the VALARM has a SUMMARY, which the EMAIL action requires.
+    pub(crate) fn has_summary(&self) -> bool {
+        self.action == "EMAIL"
+    }
+}
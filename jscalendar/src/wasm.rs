@@ -0,0 +1,398 @@
+//! JavaScript bindings for the object model (requires the `wasm` feature).
+//!
+//! The [`JsonValue`] traits lend out the strings, arrays, and objects of a value by reference,
+//! which a [`JsValue`] cannot do, since it is only a handle to a value in the JavaScript heap. A
+//! [`JsJson`] is instead an owned copy of a JSON value, converted from and into a [`JsValue`] at
+//! the boundary.
+//!
+//! [`JsEvent`], [`JsTask`], and [`JsGroup`] are exported to JavaScript as the classes `Event`,
+//! `Task`, and `Group`. Their constructors validate a plain JavaScript object as a JSCalendar
+//! object, throwing an `Error` which names the path of the first invalid property, and their
+//! `toJSON` methods return the object as a plain JavaScript object, so that `JSON.stringify`
+//! serializes them directly.
+//!
+//! ```js
+//! import { Event } from "jscalendar";
+//!
+//! const event = new Event({
+//!   "@type": "Event",
+//!   uid: "a8df6573-0474-496d-8496-033ad45d7fea",
+//!   start: "2020-01-15T13:00:00",
+//! });
+//! JSON.stringify(event);
+//! ```
+
+use std::{borrow::Cow, collections::HashMap};
+
+use js_sys::{Array, Object, Reflect};
+use thiserror::Error;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    json::{
+        ConstructibleJsonValue, DestructibleJsonValue, Int, IntoIntError, IntoJson,
+        IntoUnsignedIntError, JsonValue, TryFromJson, TypeError, TypeErrorOr, UnsignedInt,
+        ValueType,
+    },
+    model::object::{Event, Group, Task},
+};
+
+/// An owned JSON value, as converted from a JavaScript value.
+///
+/// Numbers are stored as `f64`, as in JavaScript.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsJson {
+    /// `null`.
+    Null,
+    /// A boolean.
+    Bool(bool),
+    /// A finite number.
+    Number(f64),
+    /// A string.
+    String(String),
+    /// An array.
+    Array(Vec<JsJson>),
+    /// An object.
+    Object(HashMap<String, JsJson>),
+}
+
+/// An error indicating that a JavaScript value has no JSON representation.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum FromJsValueError {
+    /// The value was `undefined`, a function, a symbol, or a bigint.
+    #[error("a JavaScript {0} cannot be converted into JSON")]
+    Unsupported(String),
+    /// The value was `NaN` or infinite.
+    #[error("the number {0} cannot be converted into JSON")]
+    NonFiniteNumber(f64),
+}
+
+impl TryFrom<JsValue> for JsJson {
+    type Error = FromJsValueError;
+
+    /// Copies a JavaScript value into a [`JsJson`].
+    ///
+    /// As with `JSON.stringify`, the properties of an object whose values are `undefined` are
+    /// skipped, and only the own enumerable properties of an object are copied.
+    fn try_from(value: JsValue) -> Result<Self, Self::Error> {
+        if value.is_null() {
+            Ok(Self::Null)
+        } else if let Some(b) = value.as_bool() {
+            Ok(Self::Bool(b))
+        } else if let Some(n) = value.as_f64() {
+            if n.is_finite() {
+                Ok(Self::Number(n))
+            } else {
+                Err(FromJsValueError::NonFiniteNumber(n))
+            }
+        } else if let Some(s) = value.as_string() {
+            Ok(Self::String(s))
+        } else if Array::is_array(&value) {
+            let array = Array::from(&value);
+            array
+                .iter()
+                .map(Self::try_from)
+                .collect::<Result<_, _>>()
+                .map(Self::Array)
+        } else if value.is_object() && !value.is_function() {
+            let mut object = HashMap::new();
+            for entry in Object::entries(value.unchecked_ref()).iter() {
+                let entry = Array::from(&entry);
+                let (key, value) = (entry.get(0), entry.get(1));
+                if value.is_undefined() {
+                    continue;
+                }
+                let key = key.as_string().unwrap_or_default();
+                object.insert(key, Self::try_from(value)?);
+            }
+            Ok(Self::Object(object))
+        } else {
+            let kind = value.js_typeof().as_string().unwrap_or_default();
+            Err(FromJsValueError::Unsupported(kind))
+        }
+    }
+}
+
+impl From<JsJson> for JsValue {
+    fn from(value: JsJson) -> Self {
+        match value {
+            JsJson::Null => JsValue::NULL,
+            JsJson::Bool(b) => JsValue::from_bool(b),
+            JsJson::Number(n) => JsValue::from_f64(n),
+            JsJson::String(s) => JsValue::from_str(&s),
+            JsJson::Array(array) => array
+                .into_iter()
+                .map(JsValue::from)
+                .collect::<Array>()
+                .into(),
+            JsJson::Object(object) => {
+                let target = Object::new();
+                for (key, value) in object {
+                    Reflect::set(&target, &JsValue::from_str(&key), &value.into())
+                        .expect("setting a property of a plain object cannot fail");
+                }
+                target.into()
+            }
+        }
+    }
+}
+
+impl JsJson {
+    fn type_error(&self, expected: ValueType) -> TypeError {
+        TypeError {
+            expected,
+            received: self.value_type(),
+        }
+    }
+
+    fn try_as_js_number(&self) -> Result<f64, TypeError> {
+        match self {
+            JsJson::Number(n) => Ok(*n),
+            _ => Err(self.type_error(ValueType::Number)),
+        }
+    }
+}
+
+impl JsonValue for JsJson {
+    type String = String;
+    type Array = Vec<JsJson>;
+    type Object = HashMap<String, JsJson>;
+}
+
+impl DestructibleJsonValue for JsJson {
+    fn value_type(&self) -> ValueType {
+        match self {
+            JsJson::Null => ValueType::Null,
+            JsJson::Bool(_) => ValueType::Bool,
+            JsJson::Number(_) => ValueType::Number,
+            JsJson::String(_) => ValueType::String,
+            JsJson::Array(_) => ValueType::Array,
+            JsJson::Object(_) => ValueType::Object,
+        }
+    }
+
+    fn try_as_bool(&self) -> Result<bool, TypeError> {
+        match self {
+            JsJson::Bool(b) => Ok(*b),
+            _ => Err(self.type_error(ValueType::Bool)),
+        }
+    }
+
+    fn try_as_f64(&self) -> Result<f64, TypeError> {
+        self.try_as_js_number()
+    }
+
+    fn try_as_int(&self) -> Result<Int, TypeErrorOr<IntoIntError>> {
+        let n = self.try_as_js_number()?;
+        if n.fract() != 0.0 {
+            return Err(TypeErrorOr::Other(IntoIntError::NotAnInteger(n)));
+        }
+        // the cast saturates, and every saturated value is outside the range of Int
+        let n = n as i64;
+        Int::new(n).ok_or(TypeErrorOr::Other(IntoIntError::OutsideRangeSigned(n)))
+    }
+
+    fn try_as_unsigned_int(&self) -> Result<UnsignedInt, TypeErrorOr<IntoUnsignedIntError>> {
+        let n = self.try_as_js_number()?;
+        let error = if n.fract() != 0.0 {
+            IntoUnsignedIntError::NotAnInteger(n)
+        } else if n < 0.0 {
+            IntoUnsignedIntError::NegativeInteger(n as i64)
+        } else {
+            let n = n as u64;
+            return UnsignedInt::new(n)
+                .ok_or(TypeErrorOr::Other(IntoUnsignedIntError::OutsideRange(n)));
+        };
+        Err(TypeErrorOr::Other(error))
+    }
+
+    fn try_as_string(&self) -> Result<&<Self as JsonValue>::String, TypeError> {
+        match self {
+            JsJson::String(s) => Ok(s),
+            _ => Err(self.type_error(ValueType::String)),
+        }
+    }
+
+    fn try_as_array(&self) -> Result<&<Self as JsonValue>::Array, TypeError> {
+        match self {
+            JsJson::Array(array) => Ok(array),
+            _ => Err(self.type_error(ValueType::Array)),
+        }
+    }
+
+    fn try_as_object(&self) -> Result<&<Self as JsonValue>::Object, TypeError> {
+        match self {
+            JsJson::Object(object) => Ok(object),
+            _ => Err(self.type_error(ValueType::Object)),
+        }
+    }
+
+    fn try_into_string(self) -> Result<<Self as JsonValue>::String, TypeError> {
+        match self {
+            JsJson::String(s) => Ok(s),
+            _ => Err(self.type_error(ValueType::String)),
+        }
+    }
+
+    fn try_into_array(self) -> Result<<Self as JsonValue>::Array, TypeError> {
+        match self {
+            JsJson::Array(array) => Ok(array),
+            _ => Err(self.type_error(ValueType::Array)),
+        }
+    }
+
+    fn try_into_object(self) -> Result<<Self as JsonValue>::Object, TypeError> {
+        match self {
+            JsJson::Object(object) => Ok(object),
+            _ => Err(self.type_error(ValueType::Object)),
+        }
+    }
+}
+
+impl ConstructibleJsonValue for JsJson {
+    fn null() -> Self {
+        JsJson::Null
+    }
+
+    fn bool(value: bool) -> Self {
+        JsJson::Bool(value)
+    }
+
+    fn string(value: String) -> Self {
+        JsJson::String(value)
+    }
+
+    fn str(value: &str) -> Self {
+        JsJson::String(value.to_owned())
+    }
+
+    fn cow_str(value: Cow<'_, str>) -> Self {
+        JsJson::String(value.into_owned())
+    }
+
+    fn f64(value: f64) -> Self {
+        JsJson::Number(value)
+    }
+
+    fn int(value: Int) -> Self {
+        // exact, since the magnitude of an Int is less than 2^53
+        JsJson::Number(value.get() as f64)
+    }
+
+    fn unsigned_int(value: UnsignedInt) -> Self {
+        JsJson::Number(value.get() as f64)
+    }
+
+    fn array(value: <Self as JsonValue>::Array) -> Self {
+        JsJson::Array(value)
+    }
+
+    fn object(value: <Self as JsonValue>::Object) -> Self {
+        JsJson::Object(value)
+    }
+}
+
+/// Defines the JavaScript class of an object type.
+macro_rules! js_class {
+    ($wrapper:ident, $ty:ident, $kind:literal) => {
+        #[doc = concat!("A JSCalendar ", $kind, ", exported to JavaScript as `", stringify!($ty), "`.")]
+        #[wasm_bindgen(js_name = $ty)]
+        #[derive(Debug, Clone)]
+        pub struct $wrapper($ty<JsJson>);
+
+        #[wasm_bindgen(js_class = $ty)]
+        impl $wrapper {
+            #[doc = concat!("Validates a plain JavaScript object as ", $kind, ".")]
+            #[wasm_bindgen(constructor)]
+            pub fn new(value: JsValue) -> Result<$wrapper, JsError> {
+                let json = JsJson::try_from(value)?;
+                Ok(Self($ty::try_from_json(json)?))
+            }
+
+            #[doc = concat!("Returns this ", $kind, " as a plain JavaScript object.")]
+            #[wasm_bindgen(js_name = toJSON)]
+            pub fn to_json(&self) -> JsValue {
+                let json: JsJson = self.0.clone().into_json();
+                json.into()
+            }
+        }
+
+        impl $wrapper {
+            #[doc = concat!("Returns the wrapped ", $kind, ".")]
+            pub fn into_inner(self) -> $ty<JsJson> {
+                self.0
+            }
+        }
+
+        impl From<$ty<JsJson>> for $wrapper {
+            fn from(value: $ty<JsJson>) -> Self {
+                Self(value)
+            }
+        }
+
+        impl AsRef<$ty<JsJson>> for $wrapper {
+            fn as_ref(&self) -> &$ty<JsJson> {
+                &self.0
+            }
+        }
+    };
+}
+
+js_class!(JsEvent, Event, "an event");
+js_class!(JsTask, Task, "a task");
+js_class!(JsGroup, Group, "a group");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(entries: impl IntoIterator<Item = (&'static str, JsJson)>) -> JsJson {
+        let entries = entries
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value));
+        JsJson::Object(entries.collect())
+    }
+
+    #[test]
+    fn event_round_trip() {
+        let string = |s: &str| JsJson::String(s.to_owned());
+        let input = object([
+            ("@type", string("Event")),
+            ("uid", string("a8df6573-0474-496d-8496-033ad45d7fea")),
+            ("start", string("2020-01-15T13:00:00")),
+            ("sequence", JsJson::Number(3.0)),
+            ("showWithoutTime", JsJson::Bool(false)),
+        ]);
+
+        let event = Event::<JsJson>::try_from_json(input.clone()).unwrap();
+        let output: JsJson = event.into_json();
+        assert_eq!(output, input);
+
+        let JsJson::Object(mut invalid) = input else {
+            unreachable!()
+        };
+        invalid.insert("sequence".into(), JsJson::Number(1.5));
+        assert!(Event::<JsJson>::try_from_json(JsJson::Object(invalid)).is_err());
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(JsJson::Number(-4.0).try_as_int().map(Int::get), Ok(-4));
+        assert!(matches!(
+            JsJson::Number(-4.0).try_as_unsigned_int(),
+            Err(TypeErrorOr::Other(IntoUnsignedIntError::NegativeInteger(
+                -4
+            )))
+        ));
+        assert!(matches!(
+            JsJson::Number(1e300).try_as_int(),
+            Err(TypeErrorOr::Other(IntoIntError::OutsideRangeSigned(
+                i64::MAX
+            )))
+        ));
+        assert!(matches!(
+            JsJson::Number(0.5).try_as_unsigned_int(),
+            Err(TypeErrorOr::Other(IntoUnsignedIntError::NotAnInteger(_)))
+        ));
+    }
+}
@@ -4,6 +4,8 @@
 
 use jscalendar::json::{IntoJson, TryFromJson};
 use jscalendar::model::object::{Event, Group, Task};
+use jscalendar::model::rrule::{Rscale, Skip};
+use jscalendar::model::set::Token;
 use serde_json::{json, Value};
 
 /// Parse → clone → serialize → re-parse → compare the two Rust structs.
@@ -156,6 +158,75 @@ fn round_trip_all_day_event() {
     }));
 }
 
+#[test]
+fn round_trip_rscale_and_skip() {
+    let input = json!({
+        "@type": "Event",
+        "uid": "rscale-uid",
+        "title": "Chinese New Year",
+        "start": "2024-02-10T00:00:00",
+        "recurrenceRules": [
+            {
+                "@type": "RecurrenceRule",
+                "rscale": "chinese",
+                "frequency": "yearly",
+                "skip": "forward"
+            },
+            {
+                "@type": "RecurrenceRule",
+                "rscale": "x-lunar",
+                "frequency": "monthly"
+            }
+        ]
+    });
+    assert_event_round_trips(input.clone());
+
+    let event: Event<Value> = Event::try_from_json(input).unwrap();
+    let rules = event.recurrence_rules().unwrap();
+    assert_eq!(rules[0].rscale, Some(Token::Known(Rscale::Chinese)));
+    assert_eq!(rules[0].skip, Some(Skip::Forward));
+    assert_eq!(rules[1].rscale, Some(Token::Unknown("x-lunar".into())));
+}
+
+#[test]
+fn round_trip_rrule_extra_parts() {
+    let input = json!({
+        "@type": "Event",
+        "uid": "extra-parts-uid",
+        "start": "2024-02-10T00:00:00",
+        "recurrenceRules": [{
+            "@type": "RecurrenceRule",
+            "frequency": "weekly",
+            "eikopf.github.io:extraParts": ["X-EXAMPLE=1,2", "X-OTHER="]
+        }]
+    });
+    assert_event_round_trips(input.clone());
+
+    let event: Event<Value> = Event::try_from_json(input).unwrap();
+    let extras = &event.recurrence_rules().unwrap()[0].extras;
+    assert_eq!(extras[0].name().as_str(), "X-EXAMPLE");
+    assert_eq!(extras[0].value(), "1,2");
+    assert_eq!(extras[1].value(), "");
+
+    let output: Value = event.into_json();
+    assert_eq!(
+        output["recurrenceRules"][0]["eikopf.github.io:extraParts"],
+        json!(["X-EXAMPLE=1,2", "X-OTHER="])
+    );
+
+    let invalid = json!({
+        "@type": "Event",
+        "uid": "extra-parts-uid",
+        "start": "2024-02-10T00:00:00",
+        "recurrenceRules": [{
+            "@type": "RecurrenceRule",
+            "frequency": "weekly",
+            "eikopf.github.io:extraParts": ["COUNT=2"]
+        }]
+    });
+    assert!(Event::<Value>::try_from_json(invalid).is_err());
+}
+
 #[test]
 fn round_trip_simple_task() {
     assert_task_round_trips(json!({
@@ -214,3 +285,71 @@ fn round_trip_vendor_properties() {
         "example.com:nested": { "key": "value" }
     }));
 }
+
+#[test]
+fn round_trip_unregistered_link_relation_and_location_types() {
+    let input = json!({
+        "@type": "Event",
+        "uid": "unknown-registry-values-uid",
+        "start": "2024-01-01T00:00:00",
+        "links": {
+            "link-1": {
+                "@type": "Link",
+                "href": "https://example.com/feed",
+                "rel": "example-vendor-rel"
+            }
+        },
+        "locations": {
+            "loc-1": {
+                "@type": "Location",
+                "name": "Depot",
+                "locationTypes": { "parking": true, "x-example-depot": true }
+            }
+        }
+    });
+    assert_event_round_trips(input.clone());
+
+    let event: Event<Value> = Event::try_from_json(input).unwrap();
+    let output: Value = event.into_json();
+    assert_eq!(output["links"]["link-1"]["rel"], "example-vendor-rel");
+    assert_eq!(
+        output["locations"]["loc-1"]["locationTypes"],
+        json!({ "parking": true, "x-example-depot": true })
+    );
+}
+
+#[test]
+fn round_trip_unknown_type_sub_objects() {
+    let input = json!({
+        "@type": "Event",
+        "uid": "unknown-type-uid",
+        "start": "2024-01-01T00:00:00",
+        "links": {
+            "link-1": { "@type": "FutureLink", "href": 42 }
+        },
+        "locations": {
+            "loc-1": { "@type": "FutureLocation", "where": { "floor": 3 } }
+        },
+        "virtualLocations": {
+            "vloc-1": { "@type": "FutureVirtualLocation", "uri": "xmpp:room@example.com" }
+        },
+        "alerts": {
+            "alert-1": { "@type": "FutureAlert", "when": "soon" },
+            "alert-2": {
+                "@type": "Alert",
+                "trigger": { "@type": "FutureTrigger", "cron": "0 9 * * *" }
+            }
+        },
+        "participants": {
+            "p1": {
+                "@type": "Participant",
+                "links": { "l1": { "@type": "FutureLink", "rel": 1 } }
+            }
+        }
+    });
+    assert_event_round_trips(input.clone());
+
+    let event: Event<Value> = Event::try_from_json(input.clone()).unwrap();
+    let output: Value = event.into_json();
+    assert_eq!(output, input);
+}
@@ -1,9 +1,9 @@
 use jscalendar::model::string::{
-    AlphaNumeric, CalAddress, ContentId, CustomTimeZoneId, EmailAddr, GeoUri, Id,
-    ImplicitJsonPointer, InvalidCalAddressError, InvalidContentIdError,
-    InvalidCustomTimeZoneIdError, InvalidEmailAddrError, InvalidGeoUriError, InvalidIdError,
-    InvalidImplicitJsonPointerError, InvalidMediaTypeError, InvalidVendorStrError, MediaType,
-    VendorStr,
+    AlphaNumeric, CalAddress, ContentId, CustomTimeZoneId, EmailAddr, GeoCoordinates, GeoUri,
+    HttpsUrl, Id, ImplicitJsonPointer, InvalidCalAddressError, InvalidContentIdError,
+    InvalidCustomTimeZoneIdError, InvalidEmailAddrError, InvalidGeoUriError, InvalidHttpsUrlError,
+    InvalidIdError, InvalidImplicitJsonPointerError, InvalidMediaTypeError, InvalidUriError,
+    InvalidVendorStrError, MediaType, VendorStr,
 };
 
 // Id edge cases
@@ -170,6 +170,69 @@ fn geo_uri_extreme_coords() {
     assert!(GeoUri::new("geo:0.0,0.0").is_ok());
 }
 
+#[test]
+fn geo_uri_non_finite_coords() {
+    assert_eq!(GeoUri::new("geo:NaN,0"), Err(InvalidGeoUriError::InvalidLatitude));
+    assert_eq!(GeoUri::new("geo:0,inf"), Err(InvalidGeoUriError::InvalidLongitude));
+}
+
+#[test]
+fn geo_uri_invalid_altitude() {
+    assert_eq!(GeoUri::new("geo:0,0,high"), Err(InvalidGeoUriError::InvalidAltitude));
+    assert_eq!(GeoUri::new("geo:0,0,1,2"), Err(InvalidGeoUriError::InvalidAltitude));
+}
+
+#[test]
+fn geo_uri_invalid_uncertainty() {
+    assert_eq!(GeoUri::new("geo:0,0;u=-1"), Err(InvalidGeoUriError::InvalidUncertainty));
+    assert_eq!(GeoUri::new("geo:0,0;U=abc"), Err(InvalidGeoUriError::InvalidUncertainty));
+}
+
+#[test]
+fn geo_uri_coordinates() {
+    let uri = GeoUri::new("geo:48.2010,16.3695,183;CRS=wgs84;u=40;foo=bar").unwrap();
+    assert_eq!(
+        uri.coordinates(),
+        GeoCoordinates {
+            latitude: 48.201,
+            longitude: 16.3695,
+            altitude: Some(183.0),
+            crs: Some("wgs84".into()),
+            uncertainty: Some(40.0),
+        }
+    );
+
+    let uri = GeoUri::new("geo:-33.8688,151.2093").unwrap();
+    assert_eq!(uri.coordinates(), GeoCoordinates::new(-33.8688, 151.2093));
+}
+
+#[test]
+fn geo_uri_from_coordinates() {
+    let uri = GeoUri::from_lat_lon(37.786971, -122.399677).unwrap();
+    assert_eq!(uri.as_str(), "geo:37.786971,-122.399677");
+    assert_eq!(GeoUri::from_lat_lon(f64::NAN, 0.0), Err(InvalidGeoUriError::InvalidLatitude));
+
+    let coordinates = GeoCoordinates {
+        altitude: Some(-12.5),
+        crs: Some("wgs84".into()),
+        uncertainty: Some(0.5),
+        ..GeoCoordinates::new(1.0, -2.0)
+    };
+    let uri = coordinates.to_geo_uri().unwrap();
+    assert_eq!(uri.as_str(), "geo:1,-2,-12.5;crs=wgs84;u=0.5");
+    assert_eq!(uri.coordinates(), coordinates);
+}
+
+#[test]
+fn geo_uri_geo_conversion() {
+    let uri = GeoUri::new("geo:37.386013,-122.082932,10;u=5").unwrap();
+    let geo = uri.to_geo();
+    assert_eq!((geo.lat, geo.lon), (37.386013, -122.082932));
+
+    let uri = GeoUri::from_geo(geo).unwrap();
+    assert_eq!(uri.as_str(), "geo:37.386013,-122.082932");
+}
+
 // CalAddress edge cases
 
 #[test]
@@ -189,6 +252,39 @@ fn cal_address_valid() {
     assert_eq!(ca.email(), "user@example.com");
 }
 
+// HttpsUrl edge cases
+
+#[test]
+fn https_url_invalid_uri() {
+    assert_eq!(
+        HttpsUrl::new(""),
+        Err(InvalidHttpsUrlError::Uri(InvalidUriError::EmptyString))
+    );
+    assert_eq!(
+        HttpsUrl::new("example.com"),
+        Err(InvalidHttpsUrlError::Uri(InvalidUriError::MissingColon))
+    );
+}
+
+#[test]
+fn https_url_not_https() {
+    assert_eq!(
+        HttpsUrl::new("http://example.com"),
+        Err(InvalidHttpsUrlError::NotHttps { scheme: "http".into() })
+    );
+    assert_eq!(
+        HttpsUrl::new("mailto:user@example.com"),
+        Err(InvalidHttpsUrlError::NotHttps { scheme: "mailto".into() })
+    );
+}
+
+#[test]
+fn https_url_valid() {
+    assert!(HttpsUrl::new("https://example.com/rsvp").is_ok());
+    let url = HttpsUrl::new("HTTPS://example.com").unwrap();
+    assert_eq!(url.as_uri().scheme(), "HTTPS");
+}
+
 // ImplicitJsonPointer edge cases
 
 #[test]
@@ -289,6 +385,33 @@ fn media_type_with_params() {
     assert_eq!(mt.subtype(), "plain");
 }
 
+#[test]
+fn media_type_parameters() {
+    let mt = MediaType::new("text/html; Charset=\"UTF-8\"; level=1").unwrap();
+    assert_eq!(mt.type_(), "text");
+    assert_eq!(mt.subtype(), "html");
+    assert_eq!(mt.charset(), Some("UTF-8"));
+    assert_eq!(mt.parameter("LEVEL"), Some("1"));
+    assert_eq!(mt.parameter("format"), None);
+    assert_eq!(
+        mt.parameters().collect::<Vec<_>>(),
+        [("Charset", "UTF-8"), ("level", "1")]
+    );
+    assert_eq!(MediaType::new("image/png").unwrap().charset(), None);
+}
+
+#[test]
+fn media_type_matches() {
+    let mt = MediaType::new("Image/PNG;foo=bar").unwrap();
+    assert!(mt.matches("image/png"));
+    assert!(mt.matches("image/*"));
+    assert!(mt.matches("*/*"));
+    assert!(mt.matches("image/png; q=0.5"));
+    assert!(!mt.matches("image/jpeg"));
+    assert!(!mt.matches("text/*"));
+    assert!(!mt.matches("image"));
+}
+
 // ── ContentId edge cases ─────────────────────────────────────────────
 
 #[test]
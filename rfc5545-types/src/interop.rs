@@ -0,0 +1,4 @@
+//! Conversions between [`RRule`](crate::rrule::RRule) and the recurrence models of third-party
+//! calendar APIs.
+
+pub mod graph;
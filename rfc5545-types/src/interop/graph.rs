@@ -0,0 +1,752 @@
+//! Microsoft Graph `patternedRecurrence` conversions.
+//!
+//! The types in this module mirror the shape of the Graph [`patternedRecurrence`] resource field
+//! by field, so that they can be mapped to and from JSON by whichever serialization library the
+//! caller already uses. Enum values render to the exact strings used by Graph via [`Display`] and
+//! [`FromStr`](std::str::FromStr).
+//!
+//! Graph can only express a small subset of recurrence rules, so converting an [`RRule`] may fail
+//! with a [`FromRRuleError`]; the reverse conversion is total apart from missing required fields.
+//!
+//! [`patternedRecurrence`]: https://learn.microsoft.com/en-us/graph/api/resources/patternedrecurrence
+
+use std::{collections::BTreeSet, num::NonZero};
+
+use calendar_types::{
+    primitive::Sign,
+    set::Token,
+    time::{Date, Day, Month, Weekday},
+};
+use strum::{Display, EnumString};
+
+use crate::{
+    rrule::{
+        ByMonthDayRule, Freq, FreqByRules, Interval, MonthDay, MonthDaySet, MonthDaySetIndex,
+        MonthSet, PartName, RRule, Rscale, Skip, Termination, WeekdayNum, YearDayNum,
+        YearlyByRules, weekday_num_set::WeekdayNumSet,
+    },
+    time::DateTimeOrDate,
+};
+
+/// A Graph `patternedRecurrence` resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternedRecurrence {
+    /// The `pattern` field.
+    pub pattern: RecurrencePattern,
+    /// The `range` field.
+    pub range: RecurrenceRange,
+}
+
+/// A Graph `recurrencePattern` resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrencePattern {
+    /// The `type` field.
+    pub kind: RecurrencePatternType,
+    /// The `interval` field.
+    pub interval: u32,
+    /// The `month` field, used by the yearly pattern types.
+    pub month: Option<Month>,
+    /// The `dayOfMonth` field, used by the absolute pattern types.
+    pub day_of_month: Option<Day>,
+    /// The `daysOfWeek` field, used by the weekly and relative pattern types.
+    pub days_of_week: BTreeSet<DayOfWeek>,
+    /// The `firstDayOfWeek` field, used by the weekly pattern type. Graph defaults this to
+    /// [`DayOfWeek::Sunday`].
+    pub first_day_of_week: Option<DayOfWeek>,
+    /// The `index` field, used by the relative pattern types. Graph defaults this to
+    /// [`WeekIndex::First`].
+    pub index: Option<WeekIndex>,
+}
+
+impl RecurrencePattern {
+    /// Creates a pattern of the given type and interval with all other fields unset.
+    pub fn new(kind: RecurrencePatternType, interval: u32) -> Self {
+        Self {
+            kind,
+            interval,
+            month: None,
+            day_of_month: None,
+            days_of_week: BTreeSet::new(),
+            first_day_of_week: None,
+            index: None,
+        }
+    }
+}
+
+/// The value of the `type` field of a [`RecurrencePattern`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[strum(serialize_all = "camelCase")]
+pub enum RecurrencePatternType {
+    Daily,
+    Weekly,
+    AbsoluteMonthly,
+    RelativeMonthly,
+    AbsoluteYearly,
+    RelativeYearly,
+}
+
+/// A Graph `dayOfWeek` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum DayOfWeek {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<Weekday> for DayOfWeek {
+    fn from(value: Weekday) -> Self {
+        match value {
+            Weekday::Monday => Self::Monday,
+            Weekday::Tuesday => Self::Tuesday,
+            Weekday::Wednesday => Self::Wednesday,
+            Weekday::Thursday => Self::Thursday,
+            Weekday::Friday => Self::Friday,
+            Weekday::Saturday => Self::Saturday,
+            Weekday::Sunday => Self::Sunday,
+        }
+    }
+}
+
+impl From<DayOfWeek> for Weekday {
+    fn from(value: DayOfWeek) -> Self {
+        match value {
+            DayOfWeek::Monday => Self::Monday,
+            DayOfWeek::Tuesday => Self::Tuesday,
+            DayOfWeek::Wednesday => Self::Wednesday,
+            DayOfWeek::Thursday => Self::Thursday,
+            DayOfWeek::Friday => Self::Friday,
+            DayOfWeek::Saturday => Self::Saturday,
+            DayOfWeek::Sunday => Self::Sunday,
+        }
+    }
+}
+
+/// A Graph `weekIndex` value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum WeekIndex {
+    First,
+    Second,
+    Third,
+    Fourth,
+    Last,
+}
+
+impl WeekIndex {
+    /// Returns the equivalent signed ordinal, i.e. 1 through 4 or -1.
+    pub const fn ordinal(self) -> i16 {
+        match self {
+            Self::First => 1,
+            Self::Second => 2,
+            Self::Third => 3,
+            Self::Fourth => 4,
+            Self::Last => -1,
+        }
+    }
+
+    /// Converts a signed ordinal to a `WeekIndex`, returning `None` if Graph cannot express it.
+    pub const fn from_ordinal(ordinal: i16) -> Option<Self> {
+        match ordinal {
+            1 => Some(Self::First),
+            2 => Some(Self::Second),
+            3 => Some(Self::Third),
+            4 => Some(Self::Fourth),
+            -1 => Some(Self::Last),
+            _ => None,
+        }
+    }
+}
+
+/// A Graph `recurrenceRange` resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecurrenceRange {
+    /// The `type` field.
+    pub kind: RecurrenceRangeType,
+    /// The `startDate` field.
+    pub start_date: Date,
+    /// The `endDate` field, required by [`RecurrenceRangeType::EndDate`].
+    pub end_date: Option<Date>,
+    /// The `numberOfOccurrences` field, required by [`RecurrenceRangeType::Numbered`].
+    pub number_of_occurrences: Option<u32>,
+    /// The `recurrenceTimeZone` field.
+    pub recurrence_time_zone: Option<String>,
+}
+
+/// The value of the `type` field of a [`RecurrenceRange`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[strum(serialize_all = "camelCase")]
+pub enum RecurrenceRangeType {
+    EndDate,
+    NoEnd,
+    Numbered,
+}
+
+/// An error indicating that an [`RRule`] cannot be expressed as a [`PatternedRecurrence`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum FromRRuleError {
+    /// Graph has no pattern type for the given frequency.
+    #[error("the frequency {0:?} cannot be expressed in Graph")]
+    UnsupportedFrequency(Freq),
+    /// The rule uses a part which Graph cannot express with the given frequency.
+    #[error("the {0:?} part cannot be expressed in Graph")]
+    UnsupportedPart(PartName),
+    /// The rule uses a part with a value which Graph cannot express.
+    #[error("the value of the {0:?} part cannot be expressed in Graph")]
+    UnsupportedValue(PartName),
+}
+
+/// An error indicating that a [`PatternedRecurrence`] cannot be converted into an [`RRule`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ToRRuleError {
+    /// A field required by the pattern or range type was not set.
+    #[error("missing required field: {0}")]
+    MissingField(&'static str),
+    /// The `interval` field was zero.
+    #[error("the interval must be greater than zero")]
+    ZeroInterval,
+}
+
+/// The day-selection part of a monthly or yearly pattern.
+enum DayRule {
+    Absolute(Day),
+    Relative {
+        days: BTreeSet<DayOfWeek>,
+        index: WeekIndex,
+    },
+}
+
+impl PatternedRecurrence {
+    /// Converts `rrule` into a Graph recurrence starting on `start`.
+    ///
+    /// The start date is required because Graph stores it in the range, and because RRULE
+    /// derives omitted day and month values from DTSTART whereas Graph requires them explicitly.
+    pub fn from_rrule(rrule: &RRule, start: Date) -> Result<Self, FromRRuleError> {
+        let core = &rrule.core_by_rules;
+
+        // Graph has no notion of sub-day recurrence
+        for (part, present) in [
+            (PartName::BySecond, core.by_second.is_some()),
+            (PartName::ByMinute, core.by_minute.is_some()),
+            (PartName::ByHour, core.by_hour.is_some()),
+        ] {
+            if present {
+                return Err(FromRRuleError::UnsupportedPart(part));
+            }
+        }
+
+        // nor of calendars other than the Gregorian calendar
+        let gregorian = matches!(rrule.rscale, None | Some(Token::Known(Rscale::Gregorian)));
+        reject(PartName::RScale, !gregorian)?;
+        reject(
+            PartName::Skip,
+            rrule.skip.is_some_and(|skip| skip != Skip::Omit),
+        )?;
+        reject(PartName::Extension, !rrule.extras.is_empty())?;
+
+        let interval = u32::try_from(rrule.interval.unwrap_or_default().get().get())
+            .map_err(|_| FromRRuleError::UnsupportedValue(PartName::Interval))?;
+
+        let pattern = match &rrule.freq {
+            FreqByRules::Daily(rules) => {
+                reject(PartName::ByMonthDay, rules.by_month_day.is_some())?;
+                reject(PartName::ByDay, core.by_day.is_some())?;
+                reject(PartName::ByMonth, core.by_month.is_some())?;
+                reject(PartName::BySetPos, core.by_set_pos.is_some())?;
+
+                RecurrencePattern::new(RecurrencePatternType::Daily, interval)
+            }
+            FreqByRules::Weekly => {
+                reject(PartName::ByMonth, core.by_month.is_some())?;
+                reject(PartName::BySetPos, core.by_set_pos.is_some())?;
+
+                let days_of_week = match &core.by_day {
+                    None => BTreeSet::from([start.weekday().into()]),
+                    Some(set) => set
+                        .iter()
+                        .map(|weekday_num| match weekday_num.ordinal {
+                            None => Ok(weekday_num.weekday.into()),
+                            Some(_) => Err(FromRRuleError::UnsupportedValue(PartName::ByDay)),
+                        })
+                        .collect::<Result<_, _>>()?,
+                };
+
+                RecurrencePattern {
+                    days_of_week,
+                    first_day_of_week: Some(rrule.week_start.unwrap_or(Weekday::Monday).into()),
+                    ..RecurrencePattern::new(RecurrencePatternType::Weekly, interval)
+                }
+            }
+            FreqByRules::Monthly(rules) => {
+                reject(PartName::ByMonth, core.by_month.is_some())?;
+
+                match day_rule(rrule, rules.by_month_day, start)? {
+                    DayRule::Absolute(day) => RecurrencePattern {
+                        day_of_month: Some(day),
+                        ..RecurrencePattern::new(RecurrencePatternType::AbsoluteMonthly, interval)
+                    },
+                    DayRule::Relative { days, index } => RecurrencePattern {
+                        days_of_week: days,
+                        index: Some(index),
+                        ..RecurrencePattern::new(RecurrencePatternType::RelativeMonthly, interval)
+                    },
+                }
+            }
+            FreqByRules::Yearly(rules) => {
+                reject(PartName::ByYearDay, rules.by_year_day.is_some())?;
+                reject(PartName::ByWeekNo, rules.by_week_no.is_some())?;
+
+                let month = match core.by_month {
+                    None => start.month(),
+                    Some(set) => {
+                        let mut months = Month::iter().filter(|&month| set.get(month));
+
+                        match (months.next(), months.next()) {
+                            (Some(month), None) => month,
+                            _ => return Err(FromRRuleError::UnsupportedValue(PartName::ByMonth)),
+                        }
+                    }
+                };
+
+                match day_rule(rrule, rules.by_month_day, start)? {
+                    DayRule::Absolute(day) => RecurrencePattern {
+                        month: Some(month),
+                        day_of_month: Some(day),
+                        ..RecurrencePattern::new(RecurrencePatternType::AbsoluteYearly, interval)
+                    },
+                    DayRule::Relative { days, index } => RecurrencePattern {
+                        month: Some(month),
+                        days_of_week: days,
+                        index: Some(index),
+                        ..RecurrencePattern::new(RecurrencePatternType::RelativeYearly, interval)
+                    },
+                }
+            }
+            freq => return Err(FromRRuleError::UnsupportedFrequency(freq.into())),
+        };
+
+        let range = match rrule.termination {
+            None => RecurrenceRange {
+                kind: RecurrenceRangeType::NoEnd,
+                start_date: start,
+                end_date: None,
+                number_of_occurrences: None,
+                recurrence_time_zone: None,
+            },
+            Some(Termination::Count(count)) => RecurrenceRange {
+                kind: RecurrenceRangeType::Numbered,
+                start_date: start,
+                end_date: None,
+                number_of_occurrences: Some(
+                    u32::try_from(count)
+                        .map_err(|_| FromRRuleError::UnsupportedValue(PartName::Count))?,
+                ),
+                recurrence_time_zone: None,
+            },
+            Some(Termination::Until(until)) => RecurrenceRange {
+                kind: RecurrenceRangeType::EndDate,
+                start_date: start,
+                end_date: Some(match until {
+                    DateTimeOrDate::Date(date) => date,
+                    DateTimeOrDate::DateTime(date_time) => date_time.date,
+                }),
+                number_of_occurrences: None,
+                recurrence_time_zone: None,
+            },
+        };
+
+        Ok(Self { pattern, range })
+    }
+
+    /// Converts `self` into an equivalent [`RRule`].
+    ///
+    /// The start date of the range is not part of the rule; it corresponds to the DTSTART of the
+    /// recurring component. An end date is converted to a date-valued UNTIL part.
+    pub fn to_rrule(&self) -> Result<RRule, ToRRuleError> {
+        let pattern = &self.pattern;
+
+        let interval =
+            NonZero::new(u64::from(pattern.interval)).ok_or(ToRRuleError::ZeroInterval)?;
+        let interval = (interval.get() > 1).then_some(Interval::new(interval));
+
+        let mut rrule = RRule {
+            freq: FreqByRules::Weekly,
+            core_by_rules: Default::default(),
+            interval,
+            termination: None,
+            week_start: None,
+            rscale: None,
+            skip: None,
+            extras: Vec::new(),
+        };
+
+        let month_day = || {
+            pattern
+                .day_of_month
+                .map(|day| {
+                    let mut set = MonthDaySet::default();
+                    let day = MonthDay::from_repr(day as u8).unwrap();
+                    set.set(MonthDaySetIndex::from_signed_month_day(Sign::Pos, day));
+                    set
+                })
+                .ok_or(ToRRuleError::MissingField("dayOfMonth"))
+        };
+
+        let month = || {
+            pattern
+                .month
+                .map(|month| {
+                    let mut set = MonthSet::default();
+                    set.set(month);
+                    set
+                })
+                .ok_or(ToRRuleError::MissingField("month"))
+        };
+
+        match pattern.kind {
+            RecurrencePatternType::Daily => {
+                rrule.freq = FreqByRules::Daily(ByMonthDayRule { by_month_day: None });
+            }
+            RecurrencePatternType::Weekly => {
+                rrule.core_by_rules.by_day = Some(weekday_set(&pattern.days_of_week, None)?);
+                rrule.week_start = Some(
+                    pattern
+                        .first_day_of_week
+                        .unwrap_or(DayOfWeek::Sunday)
+                        .into(),
+                );
+            }
+            RecurrencePatternType::AbsoluteMonthly => {
+                rrule.freq = FreqByRules::Monthly(ByMonthDayRule {
+                    by_month_day: Some(month_day()?),
+                });
+            }
+            RecurrencePatternType::RelativeMonthly => {
+                rrule.freq = FreqByRules::Monthly(ByMonthDayRule { by_month_day: None });
+                set_relative_days(&mut rrule, pattern)?;
+            }
+            RecurrencePatternType::AbsoluteYearly => {
+                rrule.freq = FreqByRules::Yearly(YearlyByRules {
+                    by_month_day: Some(month_day()?),
+                    ..Default::default()
+                });
+                rrule.core_by_rules.by_month = Some(month()?);
+            }
+            RecurrencePatternType::RelativeYearly => {
+                rrule.freq = FreqByRules::Yearly(YearlyByRules::default());
+                rrule.core_by_rules.by_month = Some(month()?);
+                set_relative_days(&mut rrule, pattern)?;
+            }
+        }
+
+        rrule.termination = match self.range.kind {
+            RecurrenceRangeType::NoEnd => None,
+            RecurrenceRangeType::EndDate => Some(Termination::Until(DateTimeOrDate::Date(
+                self.range
+                    .end_date
+                    .ok_or(ToRRuleError::MissingField("endDate"))?,
+            ))),
+            RecurrenceRangeType::Numbered => Some(Termination::Count(u64::from(
+                self.range
+                    .number_of_occurrences
+                    .ok_or(ToRRuleError::MissingField("numberOfOccurrences"))?,
+            ))),
+        };
+
+        Ok(rrule)
+    }
+}
+
+fn reject(part: PartName, present: bool) -> Result<(), FromRRuleError> {
+    match present {
+        true => Err(FromRRuleError::UnsupportedPart(part)),
+        false => Ok(()),
+    }
+}
+
+/// Extracts the day-selection rule of a monthly or yearly [`RRule`].
+fn day_rule(
+    rrule: &RRule,
+    by_month_day: Option<MonthDaySet>,
+    start: Date,
+) -> Result<DayRule, FromRRuleError> {
+    let core = &rrule.core_by_rules;
+
+    let set_pos = match &core.by_set_pos {
+        None => None,
+        Some(set) if set.len() == 1 => {
+            let pos = set.first().unwrap().get();
+            Some(
+                WeekIndex::from_ordinal(pos)
+                    .ok_or(FromRRuleError::UnsupportedValue(PartName::BySetPos))?,
+            )
+        }
+        Some(_) => return Err(FromRRuleError::UnsupportedValue(PartName::BySetPos)),
+    };
+
+    let Some(by_day) = &core.by_day else {
+        reject(PartName::BySetPos, set_pos.is_some())?;
+
+        let day = match by_month_day {
+            None => start.day(),
+            Some(set) => {
+                let mut days = (1..=31)
+                    .filter_map(MonthDay::from_repr)
+                    .map(|day| (day, MonthDaySetIndex::from_signed_month_day(Sign::Pos, day)))
+                    .filter(|&(_, index)| set.get(index));
+
+                let has_negative = (1..=31)
+                    .filter_map(MonthDay::from_repr)
+                    .any(|day| set.get(MonthDaySetIndex::from_signed_month_day(Sign::Neg, day)));
+
+                match (days.next(), days.next(), has_negative) {
+                    (Some((day, _)), None, false) => Day::new(day as u8).unwrap(),
+                    _ => return Err(FromRRuleError::UnsupportedValue(PartName::ByMonthDay)),
+                }
+            }
+        };
+
+        return Ok(DayRule::Absolute(day));
+    };
+
+    reject(PartName::ByMonthDay, by_month_day.is_some())?;
+
+    let mut days = BTreeSet::new();
+    let mut ordinal = None;
+
+    for (i, weekday_num) in by_day.iter().enumerate() {
+        let current = weekday_num
+            .ordinal
+            .map(|(sign, week)| (sign as i16) * (week as i16));
+
+        if i > 0 && current != ordinal {
+            return Err(FromRRuleError::UnsupportedValue(PartName::ByDay));
+        }
+
+        ordinal = current;
+        days.insert(weekday_num.weekday.into());
+    }
+
+    let index = match (ordinal, set_pos) {
+        (Some(ordinal), None) => WeekIndex::from_ordinal(ordinal)
+            .ok_or(FromRRuleError::UnsupportedValue(PartName::ByDay))?,
+        (None, Some(index)) => index,
+        (Some(_), Some(_)) => return Err(FromRRuleError::UnsupportedPart(PartName::BySetPos)),
+        // every matching weekday of the period has no Graph equivalent
+        (None, None) => return Err(FromRRuleError::UnsupportedValue(PartName::ByDay)),
+    };
+
+    Ok(DayRule::Relative { days, index })
+}
+
+/// Builds a BYDAY set from Graph weekdays, each with the given ordinal.
+fn weekday_set(
+    days: &BTreeSet<DayOfWeek>,
+    ordinal: Option<WeekIndex>,
+) -> Result<WeekdayNumSet, ToRRuleError> {
+    if days.is_empty() {
+        return Err(ToRRuleError::MissingField("daysOfWeek"));
+    }
+
+    let ordinal = ordinal.map(|index| {
+        let ordinal = index.ordinal();
+        let sign = if ordinal < 0 { Sign::Neg } else { Sign::Pos };
+        let week = calendar_types::time::IsoWeek::from_index(ordinal.unsigned_abs() as u8).unwrap();
+        (sign, week)
+    });
+
+    let mut set = WeekdayNumSet::with_capacity(days.len());
+    for &day in days {
+        set.insert(WeekdayNum {
+            ordinal,
+            weekday: day.into(),
+        });
+    }
+
+    Ok(set)
+}
+
+/// Sets the BYDAY (and possibly BYSETPOS) parts for a relative pattern.
+///
+/// A single weekday is expressed with an ordinal (e.g. `BYDAY=2TU`), whereas several weekdays
+/// select the n-th of any of them and so require a BYSETPOS part (e.g. `BYDAY=MO,TU;BYSETPOS=2`).
+fn set_relative_days(rrule: &mut RRule, pattern: &RecurrencePattern) -> Result<(), ToRRuleError> {
+    let index = pattern.index.unwrap_or(WeekIndex::First);
+
+    if pattern.days_of_week.len() == 1 {
+        rrule.core_by_rules.by_day = Some(weekday_set(&pattern.days_of_week, Some(index))?);
+    } else {
+        let ordinal = index.ordinal();
+        let sign = if ordinal < 0 { Sign::Neg } else { Sign::Pos };
+        let pos = YearDayNum::from_signed_index(sign, ordinal.unsigned_abs()).unwrap();
+
+        rrule.core_by_rules.by_day = Some(weekday_set(&pattern.days_of_week, None)?);
+        rrule.core_by_rules.by_set_pos = Some(BTreeSet::from([pos]));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use calendar_types::time::{IsoWeek, Year};
+
+    fn date(y: u16, m: u8, d: u8) -> Date {
+        Date::new(
+            Year::new(y).unwrap(),
+            Month::new(m).unwrap(),
+            Day::new(d).unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn rrule(freq: FreqByRules) -> RRule {
+        RRule {
+            freq,
+            core_by_rules: Default::default(),
+            interval: None,
+            termination: None,
+            week_start: None,
+            rscale: None,
+            skip: None,
+            extras: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn weekly_defaults_to_start_weekday() {
+        let start = date(2024, 6, 5);
+        let graph = PatternedRecurrence::from_rrule(&rrule(FreqByRules::Weekly), start).unwrap();
+
+        assert_eq!(graph.pattern.kind, RecurrencePatternType::Weekly);
+        assert_eq!(
+            graph.pattern.days_of_week,
+            BTreeSet::from([DayOfWeek::Wednesday])
+        );
+        assert_eq!(graph.pattern.first_day_of_week, Some(DayOfWeek::Monday));
+        assert_eq!(graph.range.kind, RecurrenceRangeType::NoEnd);
+        assert_eq!(graph.range.start_date, start);
+    }
+
+    #[test]
+    fn relative_monthly_roundtrip() {
+        let mut rule = rrule(FreqByRules::Monthly(ByMonthDayRule { by_month_day: None }));
+        let mut by_day = WeekdayNumSet::default();
+        by_day.insert(WeekdayNum {
+            ordinal: Some((Sign::Neg, IsoWeek::W1)),
+            weekday: Weekday::Friday,
+        });
+        rule.core_by_rules.by_day = Some(by_day);
+        rule.termination = Some(Termination::Count(10));
+
+        let graph = PatternedRecurrence::from_rrule(&rule, date(2024, 1, 26)).unwrap();
+        assert_eq!(graph.pattern.kind, RecurrencePatternType::RelativeMonthly);
+        assert_eq!(graph.pattern.index, Some(WeekIndex::Last));
+        assert_eq!(graph.range.number_of_occurrences, Some(10));
+        assert_eq!(graph.to_rrule().unwrap(), rule);
+    }
+
+    #[test]
+    fn relative_with_several_days_uses_set_pos() {
+        let graph = PatternedRecurrence {
+            pattern: RecurrencePattern {
+                days_of_week: BTreeSet::from([DayOfWeek::Saturday, DayOfWeek::Sunday]),
+                index: Some(WeekIndex::Second),
+                ..RecurrencePattern::new(RecurrencePatternType::RelativeMonthly, 1)
+            },
+            range: RecurrenceRange {
+                kind: RecurrenceRangeType::NoEnd,
+                start_date: date(2024, 1, 1),
+                end_date: None,
+                number_of_occurrences: None,
+                recurrence_time_zone: None,
+            },
+        };
+
+        let rule = graph.to_rrule().unwrap();
+        assert_eq!(rule.core_by_rules.by_day.as_ref().unwrap().len(), 2);
+        assert_eq!(rule.core_by_rules.by_set_pos.as_ref().unwrap().len(), 1);
+        assert_eq!(
+            PatternedRecurrence::from_rrule(&rule, date(2024, 1, 1)).unwrap(),
+            graph
+        );
+    }
+
+    #[test]
+    fn absolute_yearly_uses_start_date_defaults() {
+        let mut rule = rrule(FreqByRules::Yearly(YearlyByRules::default()));
+        rule.termination = Some(Termination::Until(DateTimeOrDate::Date(date(2030, 3, 14))));
+
+        let graph = PatternedRecurrence::from_rrule(&rule, date(2024, 3, 14)).unwrap();
+        assert_eq!(graph.pattern.kind, RecurrencePatternType::AbsoluteYearly);
+        assert_eq!(graph.pattern.month, Some(Month::Mar));
+        assert_eq!(graph.pattern.day_of_month, Some(Day::D14));
+        assert_eq!(graph.range.end_date, Some(date(2030, 3, 14)));
+
+        let back = graph.to_rrule().unwrap();
+        assert_eq!(back.termination, rule.termination);
+        assert!(back.core_by_rules.by_month.unwrap().get(Month::Mar));
+    }
+
+    #[test]
+    fn unsupported_rules_are_rejected() {
+        let start = date(2024, 1, 1);
+
+        let hourly = rrule(FreqByRules::Hourly(crate::rrule::ByPeriodDayRules {
+            by_month_day: None,
+            by_year_day: None,
+        }));
+        assert_eq!(
+            PatternedRecurrence::from_rrule(&hourly, start),
+            Err(FromRRuleError::UnsupportedFrequency(Freq::Hourly))
+        );
+
+        let mut every_monday = rrule(FreqByRules::Monthly(ByMonthDayRule { by_month_day: None }));
+        every_monday.core_by_rules.by_day = Some(WeekdayNumSet::from_byday_str("MO").unwrap());
+        assert_eq!(
+            PatternedRecurrence::from_rrule(&every_monday, start),
+            Err(FromRRuleError::UnsupportedValue(PartName::ByDay))
+        );
+
+        let mut chinese = rrule(FreqByRules::Yearly(YearlyByRules::default()));
+        chinese.rscale = Some(Token::Known(Rscale::Chinese));
+        assert_eq!(
+            PatternedRecurrence::from_rrule(&chinese, start),
+            Err(FromRRuleError::UnsupportedPart(PartName::RScale))
+        );
+    }
+
+    #[test]
+    fn missing_fields_are_reported() {
+        let graph = PatternedRecurrence {
+            pattern: RecurrencePattern::new(RecurrencePatternType::AbsoluteMonthly, 1),
+            range: RecurrenceRange {
+                kind: RecurrenceRangeType::NoEnd,
+                start_date: date(2024, 1, 1),
+                end_date: None,
+                number_of_occurrences: None,
+                recurrence_time_zone: None,
+            },
+        };
+        assert_eq!(
+            graph.to_rrule(),
+            Err(ToRRuleError::MissingField("dayOfMonth"))
+        );
+    }
+
+    #[test]
+    fn enum_strings_match_graph() {
+        assert_eq!(
+            RecurrencePatternType::AbsoluteMonthly.to_string(),
+            "absoluteMonthly"
+        );
+        assert_eq!(RecurrenceRangeType::NoEnd.to_string(), "noEnd");
+        assert_eq!("tuesday".parse(), Ok(DayOfWeek::Tuesday));
+        assert_eq!(WeekIndex::Last.to_string(), "last");
+    }
+}
@@ -13,6 +13,8 @@
 //! - **Time types** ([`time`]): [`DateTimeOrDate`](time::DateTimeOrDate),
 //!   [`Period`](time::Period), [`RDate`](time::RDate), [`TriggerValue`](time::TriggerValue),
 //!   and [`UtcOffset`](time::UtcOffset).
+//! - **Time zone transitions** ([`tz`]): expansion of VTIMEZONE observances into
+//!   [`Transition`](tz::Transition)s by [`vtimezone_to_transitions`](tz::vtimezone_to_transitions).
 //! - **Property value enums** ([`set`]): status types, parameter value enums, and
 //!   alarm action markers.
 //! - **String types** ([`string`]): validated iCalendar string newtypes
@@ -22,13 +24,17 @@
 //!   [`Attachment`](value::Attachment), and [`FormatType`](value::FormatType).
 //! - **Request status** ([`request_status`]): [`RequestStatus`](request_status::RequestStatus)
 //!   and [`StatusCode`](request_status::StatusCode).
+//! - **Interoperability** ([`interop`]): conversions between [`RRule`](rrule::RRule) and the
+//!   recurrence models of third-party calendar APIs.
 //! - **Primitives** ([`primitive`]): type aliases for iCalendar integer and float values.
 
+pub mod interop;
 pub mod request_status;
 pub mod rrule;
 pub mod set;
 pub mod string;
 pub mod time;
+pub mod tz;
 pub mod value;
 
 /// iCalendar primitive value types.
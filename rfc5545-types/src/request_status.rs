@@ -1,4 +1,26 @@
 //! Types for describing request statuses.
+//!
+//! The status codes registered by RFC 5546 §3.6 and RFC 6638 §3.2.9 are available as constants of
+//! [`StatusCode`], such as [`StatusCode::SUCCESS`] and [`StatusCode::INVALID_CALENDAR_USER`].
+
+use std::str::FromStr;
+
+use thiserror::Error;
+
+/// An error arising from parsing a [`StatusCode`] or [`RequestStatus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum ParseStatusError {
+    /// The status code is not of the form `N.N` or `N.N.N`.
+    #[error("invalid status code")]
+    InvalidCode,
+    /// The first number of the status code is not a class from 1 through 5.
+    #[error("invalid status class")]
+    InvalidClass,
+    /// The request status has no description after its code.
+    #[error("missing status description")]
+    MissingDescription,
+}
 
 /// A value of the REQUEST-STATUS property (RFC 5545 §3.8.8.3).
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -21,6 +43,23 @@ impl std::fmt::Display for RequestStatus {
     }
 }
 
+/// Parses the `code;description[;data]` form of a request status. Semicolons in the exception
+/// data are kept, and escape sequences are not interpreted.
+impl FromStr for RequestStatus {
+    type Err = ParseStatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, ';');
+        let code = parts.next().unwrap_or_default().parse()?;
+        let description = parts.next().ok_or(ParseStatusError::MissingDescription)?;
+        Ok(Self {
+            code,
+            description: description.into(),
+            exception_data: parts.next().map(Into::into),
+        })
+    }
+}
+
 /// A hierarchical status code (e.g. `2.0`, `3.1.2`).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct StatusCode {
@@ -32,6 +71,160 @@ pub struct StatusCode {
     pub minor: Option<u8>,
 }
 
+impl StatusCode {
+    /// 1.0: the scheduling message is pending (RFC 6638 §3.2.9).
+    pub const PENDING: Self = Self::new(Class::C1, 0, None);
+    /// 1.1: the scheduling message was sent (RFC 6638 §3.2.9).
+    pub const SENT: Self = Self::new(Class::C1, 1, None);
+    /// 1.2: the scheduling message was delivered (RFC 6638 §3.2.9).
+    pub const DELIVERED: Self = Self::new(Class::C1, 2, None);
+    /// 2.0: success.
+    pub const SUCCESS: Self = Self::new(Class::C2, 0, None);
+    /// 2.1: success, but a fallback was taken on one or more property values.
+    pub const SUCCESS_FALLBACK: Self = Self::new(Class::C2, 1, None);
+    /// 2.2: success, but an invalid property was ignored.
+    pub const INVALID_PROPERTY_IGNORED: Self = Self::new(Class::C2, 2, None);
+    /// 2.3: success, but an invalid property parameter was ignored.
+    pub const INVALID_PARAMETER_IGNORED: Self = Self::new(Class::C2, 3, None);
+    /// 2.4: success, but an unknown nonstandard property was ignored.
+    pub const UNKNOWN_PROPERTY_IGNORED: Self = Self::new(Class::C2, 4, None);
+    /// 2.5: success, but an unknown nonstandard property value was ignored.
+    pub const UNKNOWN_VALUE_IGNORED: Self = Self::new(Class::C2, 5, None);
+    /// 2.6: success, but an invalid calendar component was ignored.
+    pub const INVALID_COMPONENT_IGNORED: Self = Self::new(Class::C2, 6, None);
+    /// 2.7: success, and the request was forwarded to a calendar user.
+    pub const FORWARDED: Self = Self::new(Class::C2, 7, None);
+    /// 2.8: success, but a repeating event was scheduled as a single component.
+    pub const REPEATING_EVENT_IGNORED: Self = Self::new(Class::C2, 8, None);
+    /// 2.9: success, but an end date-time was truncated to a date boundary.
+    pub const END_TRUNCATED: Self = Self::new(Class::C2, 9, None);
+    /// 2.10: success, but a repeating to-do was scheduled as a single to-do.
+    pub const REPEATING_TODO_IGNORED: Self = Self::new(Class::C2, 10, None);
+    /// 2.11: success, but an unbounded RRULE was clipped to a finite number of instances.
+    pub const UNBOUNDED_RRULE_CLIPPED: Self = Self::new(Class::C2, 11, None);
+    /// 3.0: invalid property name.
+    pub const INVALID_PROPERTY_NAME: Self = Self::new(Class::C3, 0, None);
+    /// 3.1: invalid property value.
+    pub const INVALID_PROPERTY_VALUE: Self = Self::new(Class::C3, 1, None);
+    /// 3.2: invalid property parameter.
+    pub const INVALID_PARAMETER: Self = Self::new(Class::C3, 2, None);
+    /// 3.3: invalid property parameter value.
+    pub const INVALID_PARAMETER_VALUE: Self = Self::new(Class::C3, 3, None);
+    /// 3.4: invalid calendar component sequence.
+    pub const INVALID_COMPONENT_SEQUENCE: Self = Self::new(Class::C3, 4, None);
+    /// 3.5: invalid date or time.
+    pub const INVALID_DATE_TIME: Self = Self::new(Class::C3, 5, None);
+    /// 3.6: invalid rule.
+    pub const INVALID_RULE: Self = Self::new(Class::C3, 6, None);
+    /// 3.7: invalid calendar user.
+    pub const INVALID_CALENDAR_USER: Self = Self::new(Class::C3, 7, None);
+    /// 3.8: no authority.
+    pub const NO_AUTHORITY: Self = Self::new(Class::C3, 8, None);
+    /// 3.9: unsupported version.
+    pub const UNSUPPORTED_VERSION: Self = Self::new(Class::C3, 9, None);
+    /// 3.10: request entity too large.
+    pub const TOO_LARGE: Self = Self::new(Class::C3, 10, None);
+    /// 3.11: required component or property missing.
+    pub const REQUIRED_MISSING: Self = Self::new(Class::C3, 11, None);
+    /// 3.12: unknown component or property found.
+    pub const UNKNOWN_FOUND: Self = Self::new(Class::C3, 12, None);
+    /// 3.13: unsupported component or property found.
+    pub const UNSUPPORTED_FOUND: Self = Self::new(Class::C3, 13, None);
+    /// 3.14: unsupported capability.
+    pub const UNSUPPORTED_CAPABILITY: Self = Self::new(Class::C3, 14, None);
+    /// 4.0: event conflict, since the date or time is busy.
+    pub const EVENT_CONFLICT: Self = Self::new(Class::C4, 0, None);
+    /// 5.0: request not supported.
+    pub const NOT_SUPPORTED: Self = Self::new(Class::C5, 0, None);
+    /// 5.1: service unavailable.
+    pub const SERVICE_UNAVAILABLE: Self = Self::new(Class::C5, 1, None);
+    /// 5.2: invalid calendar service.
+    pub const INVALID_SERVICE: Self = Self::new(Class::C5, 2, None);
+    /// 5.3: no scheduling support for the calendar user.
+    pub const NO_SCHEDULING_SUPPORT: Self = Self::new(Class::C5, 3, None);
+
+    /// Creates a status code from its components.
+    pub const fn new(class: Class, major: u8, minor: Option<u8>) -> Self {
+        Self {
+            class,
+            major,
+            minor,
+        }
+    }
+
+    /// Returns `true` if the class of this code is [`Class::C1`], i.e. the request is pending.
+    pub const fn is_pending(&self) -> bool {
+        matches!(self.class, Class::C1)
+    }
+
+    /// Returns `true` if the class of this code is [`Class::C2`], i.e. the request succeeded.
+    pub const fn is_success(&self) -> bool {
+        matches!(self.class, Class::C2)
+    }
+
+    /// Returns `true` if the class of this code is [`Class::C3`], i.e. the request was invalid.
+    pub const fn is_client_error(&self) -> bool {
+        matches!(self.class, Class::C3)
+    }
+
+    /// Returns `true` if the class of this code is [`Class::C4`], i.e. the request could not be
+    /// scheduled.
+    pub const fn is_scheduling_error(&self) -> bool {
+        matches!(self.class, Class::C4)
+    }
+
+    /// Returns `true` if the class of this code is [`Class::C5`], i.e. the service failed.
+    pub const fn is_service_error(&self) -> bool {
+        matches!(self.class, Class::C5)
+    }
+
+    /// Returns the description of this code in RFC 5546 §3.6, or `None` if it is not registered
+    /// there.
+    pub const fn registered_description(&self) -> Option<&'static str> {
+        let description = match (self.class, self.major, self.minor) {
+            (_, _, Some(_)) => return None,
+            (Class::C2, 0, _) => "Success.",
+            (Class::C2, 1, _) => "Success, but fallback taken on one or more property values.",
+            (Class::C2, 2, _) => "Success; invalid property ignored.",
+            (Class::C2, 3, _) => "Success; invalid property parameter ignored.",
+            (Class::C2, 4, _) => "Success; unknown, non-standard property ignored.",
+            (Class::C2, 5, _) => "Success; unknown, non-standard property value ignored.",
+            (Class::C2, 6, _) => "Success; invalid calendar component ignored.",
+            (Class::C2, 7, _) => "Success; request forwarded to Calendar User.",
+            (Class::C2, 8, _) => {
+                "Success; repeating event ignored. Scheduled as a single component."
+            }
+            (Class::C2, 9, _) => "Success; truncated end date time to date boundary.",
+            (Class::C2, 10, _) => "Success; repeating VTODO ignored. Scheduled as a single VTODO.",
+            (Class::C2, 11, _) => {
+                "Success; unbounded RRULE clipped at some finite number of instances."
+            }
+            (Class::C3, 0, _) => "Invalid property name.",
+            (Class::C3, 1, _) => "Invalid property value.",
+            (Class::C3, 2, _) => "Invalid property parameter.",
+            (Class::C3, 3, _) => "Invalid property parameter value.",
+            (Class::C3, 4, _) => "Invalid calendar component sequence.",
+            (Class::C3, 5, _) => "Invalid date or time.",
+            (Class::C3, 6, _) => "Invalid rule.",
+            (Class::C3, 7, _) => "Invalid Calendar User.",
+            (Class::C3, 8, _) => "No authority.",
+            (Class::C3, 9, _) => "Unsupported version.",
+            (Class::C3, 10, _) => "Request entity too large.",
+            (Class::C3, 11, _) => "Required component or property missing.",
+            (Class::C3, 12, _) => "Unknown component or property found.",
+            (Class::C3, 13, _) => "Unsupported component or property found.",
+            (Class::C3, 14, _) => "Unsupported capability.",
+            (Class::C4, 0, _) => "Event conflict. Date/time is busy.",
+            (Class::C5, 0, _) => "Request MAY supported.",
+            (Class::C5, 1, _) => "Service unavailable.",
+            (Class::C5, 2, _) => "Invalid calendar service.",
+            (Class::C5, 3, _) => "No scheduling support for user.",
+            _ => return None,
+        };
+        Some(description)
+    }
+}
+
 impl std::fmt::Display for StatusCode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}.{}", self.class.as_u8(), self.major)?;
@@ -42,6 +235,24 @@ impl std::fmt::Display for StatusCode {
     }
 }
 
+impl FromStr for StatusCode {
+    type Err = ParseStatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = |digits: &str| match digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            true => digits.parse().map_err(|_| ParseStatusError::InvalidCode),
+            false => Err(ParseStatusError::InvalidCode),
+        };
+
+        let mut parts = s.splitn(3, '.');
+        let class = number(parts.next().unwrap_or_default())?;
+        let class = Class::from_u8(class).ok_or(ParseStatusError::InvalidClass)?;
+        let major = number(parts.next().ok_or(ParseStatusError::InvalidCode)?)?;
+        let minor = parts.next().map(number).transpose()?;
+        Ok(Self::new(class, major, minor))
+    }
+}
+
 /// The class of a [`StatusCode`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Class {
@@ -96,3 +307,61 @@ impl Class {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_code_text() {
+        assert_eq!("3.7".parse(), Ok(StatusCode::INVALID_CALENDAR_USER));
+        assert_eq!("2.8.1".parse(), Ok(StatusCode::new(Class::C2, 8, Some(1))));
+        assert_eq!(StatusCode::UNBOUNDED_RRULE_CLIPPED.to_string(), "2.11");
+        assert_eq!(
+            "6.0".parse::<StatusCode>(),
+            Err(ParseStatusError::InvalidClass)
+        );
+        for s in ["", "2", "2.", "2.+1", "2.0.1.1", "2.256"] {
+            assert_eq!(
+                s.parse::<StatusCode>(),
+                Err(ParseStatusError::InvalidCode),
+                "{s}"
+            );
+        }
+    }
+
+    #[test]
+    fn request_status_text() {
+        let s = "3.1;Invalid property value;DTSTART:96-Apr-01";
+        let status: RequestStatus = s.parse().unwrap();
+        assert_eq!(status.code, StatusCode::INVALID_PROPERTY_VALUE);
+        assert_eq!(&*status.description, "Invalid property value");
+        assert_eq!(status.exception_data.as_deref(), Some("DTSTART:96-Apr-01"));
+        assert_eq!(status.to_string(), s);
+
+        assert_eq!(
+            "2.0".parse::<RequestStatus>(),
+            Err(ParseStatusError::MissingDescription)
+        );
+    }
+
+    #[test]
+    fn classification() {
+        assert!(StatusCode::DELIVERED.is_pending());
+        assert!(StatusCode::FORWARDED.is_success());
+        assert!(StatusCode::INVALID_RULE.is_client_error());
+        assert!(StatusCode::EVENT_CONFLICT.is_scheduling_error());
+        assert!(StatusCode::SERVICE_UNAVAILABLE.is_service_error());
+        assert!(!StatusCode::SUCCESS.is_client_error());
+
+        assert_eq!(
+            StatusCode::EVENT_CONFLICT.registered_description(),
+            Some("Event conflict. Date/time is busy.")
+        );
+        assert_eq!(StatusCode::SENT.registered_description(), None);
+        assert_eq!(
+            StatusCode::new(Class::C3, 7, Some(1)).registered_description(),
+            None
+        );
+    }
+}
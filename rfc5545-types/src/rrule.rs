@@ -6,14 +6,21 @@ use weekday_num_set::WeekdayNumSet;
 
 use calendar_types::{
     primitive::Sign,
+    set::Token,
     time::{IsoWeek, Month, Weekday},
 };
+use strum::{Display, EnumString, IntoStaticStr, VariantArray};
+use thiserror::Error;
 
-use crate::time::DateTimeOrDate;
+use crate::{string::Name, time::DateTimeOrDate};
+
+calendar_types::impl_token_parse!(Rscale, Skip);
 
 // TODO: implement another mixed representation set module for
 // year_day_num
 
+pub mod builder;
+pub mod validate;
 pub mod weekday_num_set;
 
 /// A recurrence rule (RFC 5545 §3.3.10).
@@ -29,6 +36,12 @@ pub struct RRule {
     pub termination: Option<Termination>,
     /// The WKST part.
     pub week_start: Option<Weekday>,
+    /// The RSCALE part (RFC 7529 §4.1).
+    pub rscale: Option<Token<Rscale, Box<str>>>,
+    /// The SKIP part (RFC 7529 §4.1).
+    pub skip: Option<Skip>,
+    /// The parts with names not defined by RFC 5545 or RFC 7529, in their original order.
+    pub extras: Vec<XPart>,
 }
 
 /// The termination condition for a recurrence rule: either a count or an until date.
@@ -40,6 +53,170 @@ pub enum Termination {
     Until(DateTimeOrDate),
 }
 
+/// A calendar system which may be named by the RSCALE part (RFC 7529 §4.1).
+///
+/// These are the calendar types defined by CLDR. Only the Gregorian calendar is used when
+/// expanding recurrences; other values are preserved so that rules can be converted and written
+/// back unchanged.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
+#[non_exhaustive]
+#[strum(ascii_case_insensitive)]
+pub enum Rscale {
+    #[strum(serialize = "BUDDHIST")]
+    Buddhist,
+    #[strum(serialize = "CHINESE")]
+    Chinese,
+    #[strum(serialize = "COPTIC")]
+    Coptic,
+    #[strum(serialize = "DANGI")]
+    Dangi,
+    #[strum(serialize = "ETHIOAA")]
+    Ethioaa,
+    #[strum(serialize = "ETHIOPIC")]
+    Ethiopic,
+    #[strum(serialize = "GREGORIAN")]
+    Gregorian,
+    #[strum(serialize = "HEBREW")]
+    Hebrew,
+    #[strum(serialize = "INDIAN")]
+    Indian,
+    #[strum(serialize = "ISLAMIC")]
+    Islamic,
+    #[strum(serialize = "ISLAMIC-CIVIL")]
+    IslamicCivil,
+    #[strum(serialize = "ISLAMIC-RGSA")]
+    IslamicRgsa,
+    #[strum(serialize = "ISLAMIC-TBLA")]
+    IslamicTbla,
+    #[strum(serialize = "ISLAMIC-UMALQURA")]
+    IslamicUmalqura,
+    #[strum(serialize = "ISO8601")]
+    Iso8601,
+    #[strum(serialize = "JAPANESE")]
+    Japanese,
+    #[strum(serialize = "PERSIAN")]
+    Persian,
+    #[strum(serialize = "ROC")]
+    Roc,
+}
+
+/// The value of the SKIP part (RFC 7529 §4.1), which determines how a recurrence that falls on
+/// an invalid date (such as February 30th) is handled.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum Skip {
+    /// The recurrence is omitted.
+    #[default]
+    #[strum(serialize = "OMIT")]
+    Omit,
+    /// The recurrence moves to the previous valid date.
+    #[strum(serialize = "BACKWARD")]
+    Backward,
+    /// The recurrence moves to the next valid date.
+    #[strum(serialize = "FORWARD")]
+    Forward,
+}
+
+/// A part of a recurrence rule whose name is not defined by RFC 5545 or RFC 7529, such as
+/// `X-EXAMPLE=1`.
+///
+/// Parts like these carry experimental recurrence extensions, whose meaning is unknown to this
+/// crate. They are preserved so that rules can be written back unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct XPart {
+    name: Box<Name>,
+    value: Box<str>,
+}
+
+/// An error arising from an invalid [`XPart`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum InvalidXPartError {
+    /// The name is the name of a part defined by RFC 5545 or RFC 7529.
+    #[error("the name of a standard recurrence rule part cannot be used for an extension part")]
+    StandardName,
+    /// The value contains a semicolon or a control character.
+    #[error("invalid character {c:?} at byte index {index}")]
+    InvalidChar {
+        /// The byte index of the invalid character.
+        index: usize,
+        /// The invalid character.
+        c: char,
+    },
+}
+
+impl XPart {
+    /// The names of the parts defined by RFC 5545 and RFC 7529.
+    const STANDARD_NAMES: [&str; 16] = [
+        "FREQ",
+        "UNTIL",
+        "COUNT",
+        "INTERVAL",
+        "BYSECOND",
+        "BYMINUTE",
+        "BYHOUR",
+        "BYDAY",
+        "BYMONTHDAY",
+        "BYYEARDAY",
+        "BYWEEKNO",
+        "BYMONTH",
+        "BYSETPOS",
+        "WKST",
+        "RSCALE",
+        "SKIP",
+    ];
+
+    /// Creates an extension part, returning an error if `name` is the name of a standard part or
+    /// `value` contains a semicolon or a control character.
+    pub fn new(name: Box<Name>, value: impl Into<Box<str>>) -> Result<Self, InvalidXPartError> {
+        if Self::STANDARD_NAMES
+            .iter()
+            .any(|standard| standard.eq_ignore_ascii_case(name.as_str()))
+        {
+            return Err(InvalidXPartError::StandardName);
+        }
+
+        let value = value.into();
+        if let Some((index, c)) = value
+            .char_indices()
+            .find(|&(_, c)| c == ';' || c.is_control())
+        {
+            return Err(InvalidXPartError::InvalidChar { index, c });
+        }
+
+        Ok(Self { name, value })
+    }
+
+    /// Returns the name of this part.
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    /// Returns the value of this part.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+impl std::fmt::Display for XPart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.name, self.value)
+    }
+}
+
 /// The value of the INTERVAL rule part.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Interval(pub(crate) NonZero<u64>);
@@ -271,6 +448,11 @@ impl Debug for SecondSet {
 impl SecondSet {
     pub(crate) const EMPTY: Self = Self(NonZero::new(1 << 63).unwrap());
 
+    /// Returns `true` if this set contains no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.0.get() == Self::EMPTY.0.get()
+    }
+
     /// Returns `true` if `second` is in this set.
     pub const fn get(&self, second: Second) -> bool {
         let mask = 1 << (second as u8);
@@ -419,6 +601,11 @@ impl Debug for MinuteSet {
 impl MinuteSet {
     pub(crate) const EMPTY: Self = Self(NonZero::new(1 << 63).unwrap());
 
+    /// Returns `true` if this set contains no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.0.get() == Self::EMPTY.0.get()
+    }
+
     /// Returns `true` if `minute` is in this set.
     pub const fn get(&self, minute: Minute) -> bool {
         let mask = 1 << (minute as u8);
@@ -566,6 +753,11 @@ impl Debug for HourSet {
 impl HourSet {
     pub(crate) const EMPTY: Self = Self(NonZero::new(1 << 31).unwrap());
 
+    /// Returns `true` if this set contains no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.0.get() == Self::EMPTY.0.get()
+    }
+
     /// Returns `true` if `hour` is in this set.
     pub const fn get(&self, hour: Hour) -> bool {
         let mask = 1 << (hour as u8);
@@ -678,6 +870,11 @@ impl Debug for MonthSet {
 impl MonthSet {
     pub(crate) const EMPTY: Self = Self(NonZero::new(1 << 15).unwrap());
 
+    /// Returns `true` if this set contains no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.0.get() == Self::EMPTY.0.get()
+    }
+
     /// Returns `true` if `index` is in this set.
     pub const fn get(&self, index: Month) -> bool {
         let mask = 1 << index.number().get();
@@ -720,6 +917,11 @@ pub struct MonthDaySetIndex(NonZero<u8>);
 impl MonthDaySet {
     pub(crate) const EMPTY: Self = Self(NonZero::new(1 << 63).unwrap());
 
+    /// Returns `true` if this set contains no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.0.get() == Self::EMPTY.0.get()
+    }
+
     /// Returns `true` if `index` is in this set.
     pub const fn get(&self, index: MonthDaySetIndex) -> bool {
         let mask = 1 << index.0.get();
@@ -748,6 +950,20 @@ impl MonthDaySetIndex {
         // SAFETY: (day as u8) lies in the range 1..=31
         Self(unsafe { NonZero::new_unchecked(day + offset) })
     }
+
+    /// Returns the sign and day of this index; the inverse of
+    /// [`from_signed_month_day`](Self::from_signed_month_day).
+    pub const fn to_signed_month_day(self) -> (Sign, MonthDay) {
+        let (sign, day) = match self.0.get() {
+            index @ 1..=31 => (Sign::Pos, index),
+            index => (Sign::Neg, index - 31),
+        };
+
+        match MonthDay::from_repr(day) {
+            Some(day) => (sign, day),
+            None => unreachable!(),
+        }
+    }
 }
 
 impl Default for MonthDaySet {
@@ -777,6 +993,11 @@ pub struct WeekNoSetIndex(NonZero<u8>);
 impl WeekNoSet {
     pub(crate) const EMPTY: Self = Self(NonZero::new(1 << 127).unwrap());
 
+    /// Returns `true` if this set contains no elements.
+    pub const fn is_empty(&self) -> bool {
+        self.0.get() == Self::EMPTY.0.get()
+    }
+
     /// Returns `true` if `index` is in this set.
     pub const fn get(&self, index: WeekNoSetIndex) -> bool {
         let mask = 1 << (index.0.get());
@@ -805,6 +1026,20 @@ impl WeekNoSetIndex {
         // SAFETY: (week as u8) is guaranteed to lie in the range 1..=53
         Self(unsafe { NonZero::new_unchecked(week + offset) })
     }
+
+    /// Returns the sign and ISO week number of this index; the inverse of
+    /// [`from_signed_week`](Self::from_signed_week).
+    pub const fn to_signed_week(self) -> (Sign, IsoWeek) {
+        let (sign, week) = match self.0.get() {
+            index @ 1..=53 => (Sign::Pos, index),
+            index => (Sign::Neg, index - 64),
+        };
+
+        match IsoWeek::from_index(week) {
+            Some(week) => (sign, week),
+            None => unreachable!(),
+        }
+    }
 }
 
 impl Default for WeekNoSet {
@@ -813,6 +1048,133 @@ impl Default for WeekNoSet {
     }
 }
 
+/// Implements the common set API for a bitset type, given the type of its
+/// iterator, its representation, and the conversion from a bit index to the
+/// element it represents.
+macro_rules! impl_bitset {
+    ($set:ident, $iter:ident, $repr:ty, $elem:ty, $from_bit:expr) => {
+        impl $set {
+            /// Returns the number of elements in this set.
+            pub const fn len(&self) -> usize {
+                (self.0.get().count_ones() - 1) as usize
+            }
+
+            /// Returns `true` if `value` is in this set.
+            pub const fn contains(&self, value: $elem) -> bool {
+                self.get(value)
+            }
+
+            /// Returns an iterator over the elements of this set, in ascending
+            /// order.
+            pub const fn iter(&self) -> $iter {
+                $iter(self.0.get() & !Self::EMPTY.0.get())
+            }
+
+            /// Returns the set of elements in `self` or `other`.
+            pub const fn union(&self, other: &Self) -> Self {
+                Self::from_bits(self.0.get() | other.0.get())
+            }
+
+            /// Returns the set of elements in both `self` and `other`.
+            pub const fn intersection(&self, other: &Self) -> Self {
+                Self::from_bits(self.0.get() & other.0.get())
+            }
+
+            /// Returns the set of elements in `self` but not in `other`.
+            pub const fn difference(&self, other: &Self) -> Self {
+                Self::from_bits(self.0.get() & !other.0.get())
+            }
+
+            const fn from_bits(bits: $repr) -> Self {
+                Self(NonZero::new(bits | Self::EMPTY.0.get()).unwrap())
+            }
+        }
+
+        impl FromIterator<$elem> for $set {
+            fn from_iter<I: IntoIterator<Item = $elem>>(iter: I) -> Self {
+                let mut set = Self::EMPTY;
+                set.extend(iter);
+                set
+            }
+        }
+
+        impl Extend<$elem> for $set {
+            fn extend<I: IntoIterator<Item = $elem>>(&mut self, iter: I) {
+                for value in iter {
+                    self.set(value);
+                }
+            }
+        }
+
+        impl IntoIterator for $set {
+            type Item = $elem;
+            type IntoIter = $iter;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+
+        impl IntoIterator for &$set {
+            type Item = $elem;
+            type IntoIter = $iter;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+
+        #[doc = concat!("An iterator over the elements of a [`", stringify!($set), "`].")]
+        #[derive(Debug, Clone)]
+        pub struct $iter($repr);
+
+        impl Iterator for $iter {
+            type Item = $elem;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let from_bit: fn(u32) -> Option<$elem> = $from_bit;
+                while self.0 != 0 {
+                    let bit = self.0.trailing_zeros();
+                    self.0 &= self.0 - 1;
+                    if let Some(value) = from_bit(bit) {
+                        return Some(value);
+                    }
+                }
+                None
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = self.0.count_ones() as usize;
+                (len, Some(len))
+            }
+        }
+
+        impl ExactSizeIterator for $iter {}
+
+        impl std::iter::FusedIterator for $iter {}
+    };
+}
+
+impl_bitset!(SecondSet, SecondSetIter, u64, Second, |bit| {
+    Second::from_repr(bit as u8)
+});
+impl_bitset!(MinuteSet, MinuteSetIter, u64, Minute, |bit| {
+    Minute::from_repr(bit as u8)
+});
+impl_bitset!(HourSet, HourSetIter, u32, Hour, |bit| Hour::from_repr(
+    bit as u8
+));
+impl_bitset!(MonthSet, MonthSetIter, u16, Month, |bit| Month::new(
+    bit as u8
+)
+.ok());
+impl_bitset!(MonthDaySet, MonthDaySetIter, u64, MonthDaySetIndex, |bit| {
+    NonZero::new(bit as u8).map(MonthDaySetIndex)
+});
+impl_bitset!(WeekNoSet, WeekNoSetIter, u128, WeekNoSetIndex, |bit| {
+    NonZero::new(bit as u8).map(WeekNoSetIndex)
+});
+
 /// A particular day in a month, ranging from D1 to D31.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[repr(u8)]
@@ -881,6 +1243,10 @@ pub enum PartName {
     ByMonth,
     BySetPos,
     WkSt,
+    RScale,
+    Skip,
+    /// Any part with a nonstandard name (see [`XPart`]).
+    Extension,
 }
 
 impl From<&Part> for PartName {
@@ -900,6 +1266,9 @@ impl From<&Part> for PartName {
             Part::ByMonth(_) => Self::ByMonth,
             Part::BySetPos(_) => Self::BySetPos,
             Part::WkSt(_) => Self::WkSt,
+            Part::RScale(_) => Self::RScale,
+            Part::Skip(_) => Self::Skip,
+            Part::Extension(_) => Self::Extension,
         }
     }
 }
@@ -1009,6 +1378,9 @@ pub enum Part {
     ByMonth(MonthSet),
     BySetPos(BTreeSet<YearDayNum>),
     WkSt(Weekday),
+    RScale(Token<Rscale, Box<str>>),
+    Skip(Skip),
+    Extension(XPart),
 }
 
 #[cfg(test)]
@@ -0,0 +1,380 @@
+//! The [`RRuleBuilder`] type.
+//!
+//! The shape of [`FreqByRules`] depends on the frequency, so constructing an [`RRule`] directly
+//! requires knowing the frequency before any BYxxx part. An [`RRuleBuilder`] instead accepts every
+//! part in any order, as when a rule is assembled from user input, and checks whether they are
+//! permitted with the frequency in [`RRuleBuilder::build`].
+//!
+//! ```
+//! use calendar_types::{
+//!     primitive::Sign,
+//!     time::{IsoWeek, Month},
+//! };
+//! use rfc5545_types::rrule::{
+//!     ByRuleName, Freq, MonthSet, RRule, WeekNoSet, WeekNoSetIndex, builder::BuildRRuleError,
+//!     weekday_num_set::WeekdayNumSet,
+//! };
+//!
+//! let builder = RRule::builder(Freq::Monthly)
+//!     .by_day(WeekdayNumSet::from_byday_str("-1FR").unwrap())
+//!     .by_month([Month::Jun, Month::Dec].into_iter().collect::<MonthSet>())
+//!     .count(4);
+//! assert!(builder.clone().build().is_ok());
+//!
+//! // ordinals in BYDAY are only permitted with MONTHLY and YEARLY
+//! assert!(builder.freq(Freq::Weekly).build().is_err());
+//!
+//! let error = RRule::builder(Freq::Daily)
+//!     .by_week_no(WeekNoSet::from_iter([WeekNoSetIndex::from_signed_week(
+//!         Sign::Pos,
+//!         IsoWeek::W1,
+//!     )]))
+//!     .build()
+//!     .unwrap_err();
+//! assert_eq!(
+//!     error,
+//!     BuildRRuleError::UnexpectedByRule {
+//!         freq: Freq::Daily,
+//!         by_rule: ByRuleName::ByWeekNo,
+//!     }
+//! );
+//! ```
+
+use std::collections::BTreeSet;
+
+use calendar_types::{set::Token, time::Weekday};
+use thiserror::Error;
+
+use super::{
+    ByMonthDayRule, ByPeriodDayRules, ByRuleName, CoreByRules, Freq, FreqByRules, HourSet,
+    Interval, MinuteSet, MonthDaySet, MonthSet, Part, RRule, Rscale, SecondSet, Skip, Termination,
+    WeekNoSet, XPart, YearDayNum, YearlyByRules, validate::InvalidRRuleError,
+    weekday_num_set::WeekdayNumSet,
+};
+use crate::time::DateTimeOrDate;
+
+/// An error arising from [`RRuleBuilder::build`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum BuildRRuleError {
+    /// A BYxxx part is never permitted with the frequency (RFC 5545 page 44).
+    #[error("the {by_rule:?} rule part is not permitted with the {freq:?} frequency")]
+    UnexpectedByRule {
+        /// The frequency of the rule.
+        freq: Freq,
+        /// The BYxxx part which is not permitted.
+        by_rule: ByRuleName,
+    },
+    /// Both the COUNT and UNTIL parts were given.
+    #[error("COUNT and UNTIL must not both occur")]
+    CountAndUntil,
+    /// The parts violate a constraint checked by [`RRule::validate`].
+    #[error(transparent)]
+    Invalid(#[from] InvalidRRuleError),
+}
+
+/// A builder for [`RRule`] values whose frequency is only known at runtime.
+///
+/// Each method replaces the value of its part given by an earlier call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RRuleBuilder {
+    freq: Freq,
+    core_by_rules: CoreByRules,
+    by_month_day: Option<MonthDaySet>,
+    by_year_day: Option<BTreeSet<YearDayNum>>,
+    by_week_no: Option<WeekNoSet>,
+    interval: Option<Interval>,
+    count: Option<u64>,
+    until: Option<DateTimeOrDate>,
+    week_start: Option<Weekday>,
+    rscale: Option<Token<Rscale, Box<str>>>,
+    skip: Option<Skip>,
+    extras: Vec<XPart>,
+}
+
+impl RRule {
+    /// Returns a builder for a rule with the given frequency.
+    pub fn builder(freq: Freq) -> RRuleBuilder {
+        RRuleBuilder::new(freq)
+    }
+}
+
+impl RRuleBuilder {
+    /// Creates a builder for a rule with the given frequency and no other parts.
+    pub fn new(freq: Freq) -> Self {
+        Self {
+            freq,
+            core_by_rules: CoreByRules::default(),
+            by_month_day: None,
+            by_year_day: None,
+            by_week_no: None,
+            interval: None,
+            count: None,
+            until: None,
+            week_start: None,
+            rscale: None,
+            skip: None,
+            extras: Vec::new(),
+        }
+    }
+
+    /// Sets the FREQ part.
+    pub fn freq(mut self, freq: Freq) -> Self {
+        self.freq = freq;
+        self
+    }
+
+    /// Sets the BYSECOND part.
+    pub fn by_second(mut self, set: SecondSet) -> Self {
+        self.core_by_rules.by_second = Some(set);
+        self
+    }
+
+    /// Sets the BYMINUTE part.
+    pub fn by_minute(mut self, set: MinuteSet) -> Self {
+        self.core_by_rules.by_minute = Some(set);
+        self
+    }
+
+    /// Sets the BYHOUR part.
+    pub fn by_hour(mut self, set: HourSet) -> Self {
+        self.core_by_rules.by_hour = Some(set);
+        self
+    }
+
+    /// Sets the BYDAY part.
+    pub fn by_day(mut self, set: WeekdayNumSet) -> Self {
+        self.core_by_rules.by_day = Some(set);
+        self
+    }
+
+    /// Sets the BYMONTHDAY part.
+    pub fn by_month_day(mut self, set: MonthDaySet) -> Self {
+        self.by_month_day = Some(set);
+        self
+    }
+
+    /// Sets the BYYEARDAY part.
+    pub fn by_year_day(mut self, set: BTreeSet<YearDayNum>) -> Self {
+        self.by_year_day = Some(set);
+        self
+    }
+
+    /// Sets the BYWEEKNO part.
+    pub fn by_week_no(mut self, set: WeekNoSet) -> Self {
+        self.by_week_no = Some(set);
+        self
+    }
+
+    /// Sets the BYMONTH part.
+    pub fn by_month(mut self, set: MonthSet) -> Self {
+        self.core_by_rules.by_month = Some(set);
+        self
+    }
+
+    /// Sets the BYSETPOS part.
+    pub fn by_set_pos(mut self, set: BTreeSet<YearDayNum>) -> Self {
+        self.core_by_rules.by_set_pos = Some(set);
+        self
+    }
+
+    /// Sets the INTERVAL part.
+    pub fn interval(mut self, interval: Interval) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Sets the COUNT part.
+    pub fn count(mut self, count: u64) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    /// Sets the UNTIL part.
+    pub fn until(mut self, until: DateTimeOrDate) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Sets the WKST part.
+    pub fn week_start(mut self, week_start: Weekday) -> Self {
+        self.week_start = Some(week_start);
+        self
+    }
+
+    /// Sets the RSCALE part (RFC 7529 §4.1).
+    pub fn rscale(mut self, rscale: Token<Rscale, Box<str>>) -> Self {
+        self.rscale = Some(rscale);
+        self
+    }
+
+    /// Sets the SKIP part (RFC 7529 §4.1).
+    pub fn skip(mut self, skip: Skip) -> Self {
+        self.skip = Some(skip);
+        self
+    }
+
+    /// Appends a part with a nonstandard name.
+    pub fn extra(mut self, part: XPart) -> Self {
+        self.extras.push(part);
+        self
+    }
+
+    /// Sets the part given by `part`, or appends it if it has a nonstandard name.
+    pub fn part(self, part: Part) -> Self {
+        match part {
+            Part::Freq(freq) => self.freq(freq),
+            Part::Until(until) => self.until(until),
+            Part::Count(count) => self.count(count),
+            Part::Interval(interval) => self.interval(interval),
+            Part::BySecond(set) => self.by_second(set),
+            Part::ByMinute(set) => self.by_minute(set),
+            Part::ByHour(set) => self.by_hour(set),
+            Part::ByDay(set) => self.by_day(set),
+            Part::ByMonthDay(set) => self.by_month_day(set),
+            Part::ByYearDay(set) => self.by_year_day(set),
+            Part::ByWeekNo(set) => self.by_week_no(set),
+            Part::ByMonth(set) => self.by_month(set),
+            Part::BySetPos(set) => self.by_set_pos(set),
+            Part::WkSt(week_start) => self.week_start(week_start),
+            Part::RScale(rscale) => self.rscale(rscale),
+            Part::Skip(skip) => self.skip(skip),
+            Part::Extension(part) => self.extra(part),
+        }
+    }
+
+    /// Builds the rule, checking that its BYxxx parts are permitted with its frequency and that
+    /// it satisfies [`RRule::validate`].
+    pub fn build(self) -> Result<RRule, BuildRRuleError> {
+        let Self {
+            freq,
+            core_by_rules,
+            by_month_day,
+            by_year_day,
+            by_week_no,
+            interval,
+            count,
+            until,
+            week_start,
+            rscale,
+            skip,
+            extras,
+        } = self;
+
+        // the first part which is not permitted, in the order of the table of RFC 5545 page 44
+        let unexpected = [
+            (ByRuleName::ByWeekNo, by_week_no.is_some()),
+            (ByRuleName::ByYearDay, by_year_day.is_some()),
+            (ByRuleName::ByMonthDay, by_month_day.is_some()),
+        ]
+        .into_iter()
+        .find(|&(by_rule, present)| present && by_rule.behavior_with(freq).is_none());
+        if let Some((by_rule, _)) = unexpected {
+            return Err(BuildRRuleError::UnexpectedByRule { freq, by_rule });
+        }
+
+        let period_day_rules = ByPeriodDayRules {
+            by_month_day,
+            by_year_day,
+        };
+        let freq = match freq {
+            Freq::Secondly => FreqByRules::Secondly(period_day_rules),
+            Freq::Minutely => FreqByRules::Minutely(period_day_rules),
+            Freq::Hourly => FreqByRules::Hourly(period_day_rules),
+            Freq::Daily => FreqByRules::Daily(ByMonthDayRule { by_month_day }),
+            Freq::Weekly => FreqByRules::Weekly,
+            Freq::Monthly => FreqByRules::Monthly(ByMonthDayRule { by_month_day }),
+            Freq::Yearly => FreqByRules::Yearly(YearlyByRules {
+                by_month_day,
+                by_year_day: period_day_rules.by_year_day,
+                by_week_no,
+            }),
+        };
+
+        let termination = match (count, until) {
+            (Some(_), Some(_)) => return Err(BuildRRuleError::CountAndUntil),
+            (Some(count), None) => Some(Termination::Count(count)),
+            (None, Some(until)) => Some(Termination::Until(until)),
+            (None, None) => None,
+        };
+
+        let rule = RRule {
+            freq,
+            core_by_rules,
+            interval,
+            termination,
+            week_start,
+            rscale,
+            skip,
+            extras,
+        };
+        rule.validate(None)?;
+        Ok(rule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use calendar_types::{
+        primitive::Sign,
+        time::{Date, Day, Month, Year},
+    };
+
+    use super::*;
+    use crate::rrule::{MonthDay, MonthDaySetIndex};
+
+    fn first_of_month() -> MonthDaySet {
+        let index = MonthDaySetIndex::from_signed_month_day(Sign::Pos, MonthDay::D1);
+        MonthDaySet::from_iter([index])
+    }
+
+    #[test]
+    fn build_from_parts() {
+        let rule = [
+            Part::ByMonthDay(first_of_month()),
+            Part::Count(3),
+            Part::Freq(Freq::Yearly),
+            Part::WkSt(Weekday::Sunday),
+        ]
+        .into_iter()
+        .fold(RRule::builder(Freq::Weekly), RRuleBuilder::part)
+        .build()
+        .unwrap();
+
+        assert_eq!(
+            rule.freq,
+            FreqByRules::Yearly(YearlyByRules {
+                by_month_day: Some(first_of_month()),
+                ..Default::default()
+            })
+        );
+        assert_eq!(rule.termination, Some(Termination::Count(3)));
+        assert_eq!(rule.week_start, Some(Weekday::Sunday));
+    }
+
+    #[test]
+    fn build_errors() {
+        let monthly = RRule::builder(Freq::Monthly).by_month_day(first_of_month());
+        assert!(monthly.clone().build().is_ok());
+        assert_eq!(
+            monthly.clone().freq(Freq::Weekly).build(),
+            Err(BuildRRuleError::UnexpectedByRule {
+                freq: Freq::Weekly,
+                by_rule: ByRuleName::ByMonthDay,
+            })
+        );
+
+        let until = Date::new(Year::new(2025).unwrap(), Month::Jan, Day::D01).unwrap();
+        assert_eq!(
+            monthly
+                .clone()
+                .count(2)
+                .until(DateTimeOrDate::Date(until))
+                .build(),
+            Err(BuildRRuleError::CountAndUntil)
+        );
+        assert_eq!(
+            monthly.count(0).build(),
+            Err(BuildRRuleError::Invalid(InvalidRRuleError::ZeroCount))
+        );
+    }
+}
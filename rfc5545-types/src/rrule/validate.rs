@@ -0,0 +1,466 @@
+//! Validation and normalization of [`RRule`] values.
+//!
+//! The [`RRule`] type already rules out BYxxx parts which are never permitted with its frequency,
+//! but some constraints of RFC 5545 §3.3.10 depend on the values of several parts at once, or on
+//! the DTSTART of the component. [`RRule::validate`] checks those constraints, and
+//! [`RRule::normalize`] removes parts which only restate the defaults implied by DTSTART.
+
+use calendar_types::time::{TimeFormat, Weekday};
+
+use super::{
+    ByRuleName, Freq, FreqByRules, Hour, HourSet, Interval, Minute, MinuteSet, MonthDay,
+    MonthDaySet, MonthDaySetIndex, MonthSet, RRule, Second, SecondSet, Termination, WeekdayNum,
+};
+use crate::time::DateTimeOrDate;
+use calendar_types::primitive::Sign;
+
+/// An error indicating that a recurrence rule violates a constraint of RFC 5545 §3.3.10.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidRRuleError {
+    /// The COUNT part is zero, so the rule has no occurrences.
+    #[error("COUNT must be positive")]
+    ZeroCount,
+    /// A BYxxx part has no values.
+    #[error("the {0:?} rule part must not be empty")]
+    EmptyByRule(ByRuleName),
+    /// The BYSETPOS part occurs without any other BYxxx part.
+    #[error("BYSETPOS must be used together with another BYxxx rule part")]
+    LoneBySetPos,
+    /// The BYDAY part has an ordinal, but the frequency is neither MONTHLY nor YEARLY, or it is
+    /// YEARLY and the BYWEEKNO part is present.
+    #[error("BYDAY must not have ordinals with this frequency")]
+    ByDayOrdinal,
+    /// UNTIL is a DATE while DTSTART is a DATE-TIME, or vice versa.
+    #[error("UNTIL must have the same value type as DTSTART")]
+    UntilValueType,
+    /// DTSTART is a UTC time but UNTIL is not.
+    #[error("UNTIL must be a UTC time when DTSTART is")]
+    UntilNotUtc,
+    /// The SKIP part occurs without the RSCALE part (RFC 7529 §4.1).
+    #[error("SKIP must be used together with RSCALE")]
+    SkipWithoutRscale,
+}
+
+impl RRule {
+    /// Checks the constraints of RFC 5545 §3.3.10 which are not enforced by the type of `self`.
+    ///
+    /// If `dtstart` is given, UNTIL is also checked against it. A local DTSTART may either be
+    /// floating or have a TZID, so in that case UNTIL may be either local or UTC.
+    pub fn validate(&self, dtstart: Option<&DateTimeOrDate>) -> Result<(), InvalidRRuleError> {
+        match self.termination {
+            Some(Termination::Count(0)) => return Err(InvalidRRuleError::ZeroCount),
+            Some(Termination::Until(until)) => {
+                if let Some(dtstart) = dtstart {
+                    check_until(&until, dtstart)?;
+                }
+            }
+            _ => {}
+        }
+
+        if self.skip.is_some() && self.rscale.is_none() {
+            return Err(InvalidRRuleError::SkipWithoutRscale);
+        }
+
+        let present = self.present_by_rules();
+        if let Some(&(name, _)) = present.iter().find(|(_, empty)| *empty) {
+            return Err(InvalidRRuleError::EmptyByRule(name));
+        }
+
+        if present
+            .iter()
+            .all(|&(name, _)| name == ByRuleName::BySetPos)
+            && self.core_by_rules.by_set_pos.is_some()
+        {
+            return Err(InvalidRRuleError::LoneBySetPos);
+        }
+
+        if let Some(by_day) = &self.core_by_rules.by_day {
+            let ordinals_allowed = match &self.freq {
+                FreqByRules::Monthly(_) => true,
+                FreqByRules::Yearly(rules) => rules.by_week_no.is_none(),
+                _ => false,
+            };
+
+            if !ordinals_allowed && by_day.iter().any(|day| day.ordinal.is_some()) {
+                return Err(InvalidRRuleError::ByDayOrdinal);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes parts of `self` which restate defaults, so that the rule has the same occurrences
+    /// when starting from `dtstart`.
+    ///
+    /// This removes `INTERVAL=1` and `WKST=MO`, as well as single-valued BYxxx parts that match
+    /// the corresponding component of `dtstart` (e.g. `FREQ=WEEKLY;BYDAY=TU` for a Tuesday
+    /// DTSTART). BYxxx parts are left unchanged if BYSETPOS is present.
+    pub fn normalize(&mut self, dtstart: &DateTimeOrDate) {
+        if self.interval == Some(Interval::default()) {
+            self.interval = None;
+        }
+
+        if self.week_start == Some(Weekday::Monday) {
+            self.week_start = None;
+        }
+
+        if self.core_by_rules.by_set_pos.is_some() {
+            return;
+        }
+
+        let freq = Freq::from(&self.freq);
+        let date = match dtstart {
+            DateTimeOrDate::DateTime(dt) => dt.date,
+            DateTimeOrDate::Date(date) => *date,
+        };
+
+        if let DateTimeOrDate::DateTime(dt) = dtstart {
+            let core = &mut self.core_by_rules;
+
+            if freq != Freq::Secondly {
+                let mut second = SecondSet::default();
+                second.set(Second::from_repr(dt.time.second() as u8).unwrap());
+                remove_if_eq(&mut core.by_second, second);
+            }
+
+            if !matches!(freq, Freq::Secondly | Freq::Minutely) {
+                let mut minute = MinuteSet::default();
+                minute.set(Minute::from_repr(dt.time.minute() as u8).unwrap());
+                remove_if_eq(&mut core.by_minute, minute);
+            }
+
+            if !matches!(freq, Freq::Secondly | Freq::Minutely | Freq::Hourly) {
+                let mut hour = HourSet::default();
+                hour.set(Hour::from_repr(dt.time.hour() as u8).unwrap());
+                remove_if_eq(&mut core.by_hour, hour);
+            }
+        }
+
+        let mut month_day = MonthDaySet::default();
+        month_day.set(MonthDaySetIndex::from_signed_month_day(
+            Sign::Pos,
+            MonthDay::from_repr(date.day() as u8).unwrap(),
+        ));
+
+        let core = &mut self.core_by_rules;
+        match &mut self.freq {
+            FreqByRules::Weekly => {
+                let weekday = WeekdayNum {
+                    ordinal: None,
+                    weekday: date.weekday(),
+                };
+
+                if core
+                    .by_day
+                    .as_ref()
+                    .is_some_and(|by_day| by_day.len() == 1 && by_day.contains(weekday))
+                {
+                    core.by_day = None;
+                }
+            }
+            FreqByRules::Monthly(rules) if core.by_day.is_none() => {
+                remove_if_eq(&mut rules.by_month_day, month_day);
+            }
+            FreqByRules::Yearly(rules)
+                if core.by_day.is_none()
+                    && rules.by_week_no.is_none()
+                    && rules.by_year_day.is_none() =>
+            {
+                // without BYMONTH, a BYMONTHDAY part would expand to every month of the year
+                let mut month = MonthSet::default();
+                month.set(date.month());
+
+                if core.by_month == Some(month)
+                    && rules.by_month_day.is_none_or(|days| days == month_day)
+                {
+                    core.by_month = None;
+                    rules.by_month_day = None;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the name of each BYxxx part in `self`, together with whether it is empty.
+    fn present_by_rules(&self) -> Vec<(ByRuleName, bool)> {
+        let core = &self.core_by_rules;
+        let mut present = Vec::new();
+
+        let mut push = |name, empty: Option<bool>| {
+            if let Some(empty) = empty {
+                present.push((name, empty));
+            }
+        };
+
+        push(ByRuleName::BySecond, core.by_second.map(|s| s.is_empty()));
+        push(ByRuleName::ByMinute, core.by_minute.map(|s| s.is_empty()));
+        push(ByRuleName::ByHour, core.by_hour.map(|s| s.is_empty()));
+        push(ByRuleName::ByMonth, core.by_month.map(|s| s.is_empty()));
+        push(
+            ByRuleName::ByDay,
+            core.by_day.as_ref().map(|s| s.is_empty()),
+        );
+        push(
+            ByRuleName::BySetPos,
+            core.by_set_pos.as_ref().map(|s| s.is_empty()),
+        );
+
+        let (by_month_day, by_year_day, by_week_no) = match &self.freq {
+            FreqByRules::Secondly(rules)
+            | FreqByRules::Minutely(rules)
+            | FreqByRules::Hourly(rules) => (rules.by_month_day, rules.by_year_day.as_ref(), None),
+            FreqByRules::Daily(rule) | FreqByRules::Monthly(rule) => {
+                (rule.by_month_day, None, None)
+            }
+            FreqByRules::Weekly => (None, None, None),
+            FreqByRules::Yearly(rules) => (
+                rules.by_month_day,
+                rules.by_year_day.as_ref(),
+                rules.by_week_no,
+            ),
+        };
+
+        push(ByRuleName::ByMonthDay, by_month_day.map(|s| s.is_empty()));
+        push(ByRuleName::ByYearDay, by_year_day.map(|s| s.is_empty()));
+        push(ByRuleName::ByWeekNo, by_week_no.map(|s| s.is_empty()));
+
+        present
+    }
+}
+
+/// Checks that `until` has a value type compatible with `dtstart`.
+fn check_until(until: &DateTimeOrDate, dtstart: &DateTimeOrDate) -> Result<(), InvalidRRuleError> {
+    match (until, dtstart) {
+        (DateTimeOrDate::Date(_), DateTimeOrDate::Date(_)) => Ok(()),
+        (DateTimeOrDate::DateTime(until), DateTimeOrDate::DateTime(dtstart)) => {
+            match (until.marker, dtstart.marker) {
+                (TimeFormat::Local, TimeFormat::Utc) => Err(InvalidRRuleError::UntilNotUtc),
+                _ => Ok(()),
+            }
+        }
+        _ => Err(InvalidRRuleError::UntilValueType),
+    }
+}
+
+/// Sets `rule` to `None` if it is equal to `value`.
+fn remove_if_eq<T: PartialEq>(rule: &mut Option<T>, value: T) {
+    if rule.as_ref() == Some(&value) {
+        *rule = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, num::NonZero};
+
+    use calendar_types::time::{Date, DateTime, Day, Month, Time, Year};
+
+    use super::*;
+    use crate::rrule::{
+        ByMonthDayRule, CoreByRules, Rscale, Skip, WeekNoSet, WeekNoSetIndex, YearDayNum,
+        YearlyByRules, weekday_num_set::WeekdayNumSet,
+    };
+
+    fn rrule(freq: FreqByRules) -> RRule {
+        RRule {
+            freq,
+            core_by_rules: CoreByRules::default(),
+            interval: None,
+            termination: None,
+            week_start: None,
+            rscale: None,
+            skip: None,
+            extras: Vec::new(),
+        }
+    }
+
+    fn date(year: u16, month: u8, day: u8) -> Date {
+        Date::new(
+            Year::new(year).unwrap(),
+            Month::new(month).unwrap(),
+            Day::new(day).unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn date_time(date: Date, hour: u8, marker: TimeFormat) -> DateTimeOrDate {
+        let time = Time::new(
+            calendar_types::time::Hour::new(hour).unwrap(),
+            calendar_types::time::Minute::new(30).unwrap(),
+            calendar_types::time::Second::new(0).unwrap(),
+            None,
+        )
+        .unwrap();
+
+        DateTimeOrDate::DateTime(DateTime { date, time, marker })
+    }
+
+    fn by_day(days: &[WeekdayNum]) -> WeekdayNumSet {
+        let mut set = WeekdayNumSet::with_capacity(days.len());
+        for &day in days {
+            set.insert(day);
+        }
+        set
+    }
+
+    #[test]
+    fn validate_count_and_empty_rules() {
+        let mut rule = rrule(FreqByRules::Daily(ByMonthDayRule { by_month_day: None }));
+        assert_eq!(rule.validate(None), Ok(()));
+
+        rule.termination = Some(Termination::Count(0));
+        assert_eq!(rule.validate(None), Err(InvalidRRuleError::ZeroCount));
+
+        rule.termination = None;
+        rule.skip = Some(Skip::Forward);
+        assert_eq!(
+            rule.validate(None),
+            Err(InvalidRRuleError::SkipWithoutRscale)
+        );
+        rule.rscale = Some(calendar_types::set::Token::Known(Rscale::Hebrew));
+        assert_eq!(rule.validate(None), Ok(()));
+
+        rule.termination = None;
+        rule.core_by_rules.by_hour = Some(HourSet::default());
+        assert_eq!(
+            rule.validate(None),
+            Err(InvalidRRuleError::EmptyByRule(ByRuleName::ByHour))
+        );
+
+        let mut rule = rrule(FreqByRules::Yearly(YearlyByRules {
+            by_week_no: Some(WeekNoSet::default()),
+            ..Default::default()
+        }));
+        assert_eq!(
+            rule.validate(None),
+            Err(InvalidRRuleError::EmptyByRule(ByRuleName::ByWeekNo))
+        );
+
+        if let FreqByRules::Yearly(rules) = &mut rule.freq {
+            rules.by_week_no = None;
+        }
+        rule.core_by_rules.by_set_pos =
+            Some(BTreeSet::from([YearDayNum(NonZero::new(-1).unwrap())]));
+        assert_eq!(rule.validate(None), Err(InvalidRRuleError::LoneBySetPos));
+    }
+
+    #[test]
+    fn validate_by_day_ordinals() {
+        let first_monday = WeekdayNum {
+            ordinal: Some((Sign::Pos, calendar_types::time::IsoWeek::W1)),
+            weekday: Weekday::Monday,
+        };
+
+        let mut rule = rrule(FreqByRules::Weekly);
+        rule.core_by_rules.by_day = Some(by_day(&[first_monday]));
+        assert_eq!(rule.validate(None), Err(InvalidRRuleError::ByDayOrdinal));
+
+        rule.freq = FreqByRules::Monthly(ByMonthDayRule { by_month_day: None });
+        assert_eq!(rule.validate(None), Ok(()));
+
+        let mut week_no = WeekNoSet::default();
+        week_no.set(WeekNoSetIndex::from_signed_week(
+            Sign::Pos,
+            calendar_types::time::IsoWeek::W20,
+        ));
+        rule.freq = FreqByRules::Yearly(YearlyByRules {
+            by_week_no: Some(week_no),
+            ..Default::default()
+        });
+        assert_eq!(rule.validate(None), Err(InvalidRRuleError::ByDayOrdinal));
+    }
+
+    #[test]
+    fn validate_until_against_dtstart() {
+        let start = date(2024, 1, 1);
+        let mut rule = rrule(FreqByRules::Weekly);
+
+        rule.termination = Some(Termination::Until(DateTimeOrDate::Date(date(2024, 6, 1))));
+        assert_eq!(
+            rule.validate(Some(&date_time(start, 9, TimeFormat::Local))),
+            Err(InvalidRRuleError::UntilValueType)
+        );
+        assert_eq!(rule.validate(Some(&DateTimeOrDate::Date(start))), Ok(()));
+
+        let until = date_time(date(2024, 6, 1), 9, TimeFormat::Local);
+        rule.termination = Some(Termination::Until(until));
+        assert_eq!(
+            rule.validate(Some(&date_time(start, 9, TimeFormat::Utc))),
+            Err(InvalidRRuleError::UntilNotUtc)
+        );
+        assert_eq!(
+            rule.validate(Some(&date_time(start, 9, TimeFormat::Local))),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn normalize_removes_implied_parts() {
+        // 2024-01-02 was a Tuesday
+        let start = date_time(date(2024, 1, 2), 9, TimeFormat::Local);
+        let tuesday = WeekdayNum {
+            ordinal: None,
+            weekday: Weekday::Tuesday,
+        };
+
+        let mut rule = rrule(FreqByRules::Weekly);
+        rule.interval = Some(Interval::default());
+        rule.week_start = Some(Weekday::Monday);
+        rule.core_by_rules.by_day = Some(by_day(&[tuesday]));
+        let mut hour = HourSet::default();
+        hour.set(Hour::H9);
+        rule.core_by_rules.by_hour = Some(hour);
+
+        rule.normalize(&start);
+        assert_eq!(rule, rrule(FreqByRules::Weekly));
+
+        // a BYDAY with several values is not implied by DTSTART
+        let wednesday = WeekdayNum {
+            ordinal: None,
+            weekday: Weekday::Wednesday,
+        };
+        let mut rule = rrule(FreqByRules::Weekly);
+        rule.core_by_rules.by_day = Some(by_day(&[tuesday, wednesday]));
+        let expected = rule.clone();
+        rule.normalize(&start);
+        assert_eq!(rule, expected);
+    }
+
+    #[test]
+    fn normalize_yearly_month_and_day() {
+        let start = DateTimeOrDate::Date(date(2024, 3, 15));
+        let mut month = MonthSet::default();
+        month.set(Month::Mar);
+        let mut day = MonthDaySet::default();
+        day.set(MonthDaySetIndex::from_signed_month_day(
+            Sign::Pos,
+            MonthDay::D15,
+        ));
+
+        let mut rule = rrule(FreqByRules::Yearly(YearlyByRules {
+            by_month_day: Some(day),
+            ..Default::default()
+        }));
+        rule.core_by_rules.by_month = Some(month);
+        rule.normalize(&start);
+        assert_eq!(rule, rrule(FreqByRules::Yearly(YearlyByRules::default())));
+
+        // BYMONTHDAY alone expands to every month, so it must be kept
+        let mut rule = rrule(FreqByRules::Yearly(YearlyByRules {
+            by_month_day: Some(day),
+            ..Default::default()
+        }));
+        let expected = rule.clone();
+        rule.normalize(&start);
+        assert_eq!(rule, expected);
+
+        let mut rule = rrule(FreqByRules::Monthly(ByMonthDayRule {
+            by_month_day: Some(day),
+        }));
+        rule.normalize(&start);
+        assert_eq!(
+            rule,
+            rrule(FreqByRules::Monthly(ByMonthDayRule { by_month_day: None }))
+        );
+    }
+}
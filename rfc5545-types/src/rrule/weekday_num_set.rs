@@ -1,6 +1,6 @@
 //! The [`WeekdayNumSet`] type.
 
-use std::{collections::BTreeSet, num::NonZero};
+use std::{collections::BTreeSet, fmt::Write, num::NonZero};
 
 use calendar_types::{
     primitive::Sign,
@@ -100,6 +100,184 @@ impl WeekdayNumSet {
     }
 }
 
+impl WeekdayNumSet {
+    /// Renders the set in the textual BYDAY form (e.g. `1MO,-1SU,FR`).
+    ///
+    /// Elements are written in ascending order, separated by commas. Positive ordinals are
+    /// written without a leading `+`. An empty set produces an empty string.
+    pub fn to_byday_string(&self) -> String {
+        let mut buf = String::new();
+
+        for (i, weekday_num) in self.iter().enumerate() {
+            if i > 0 {
+                buf.push(',');
+            }
+
+            if let Some((sign, week)) = weekday_num.ordinal {
+                if sign == Sign::Neg {
+                    buf.push('-');
+                }
+
+                // writing to a String is infallible
+                let _ = write!(buf, "{}", week as u8);
+            }
+
+            buf.push_str(weekday_code(weekday_num.weekday));
+        }
+
+        buf
+    }
+
+    /// Parses the textual BYDAY form (e.g. `1MO,-1SU,FR`) into a set.
+    ///
+    /// Weekday codes are matched case-insensitively, and ordinals may carry an explicit `+` or
+    /// `-` sign. Duplicate elements are permitted and collapse into a single entry. An empty
+    /// string produces an empty set, as [`to_byday_string`](Self::to_byday_string) writes it.
+    pub fn from_byday_str(s: &str) -> Result<Self, InvalidByDayError> {
+        let mut set = Self::default();
+        if s.is_empty() {
+            return Ok(set);
+        }
+
+        for (index, element) in s.split(',').enumerate() {
+            set.insert(
+                parse_weekday_num(element).map_err(|kind| InvalidByDayError { index, kind })?,
+            );
+        }
+
+        Ok(set)
+    }
+}
+
+/// An error indicating that a string is not a valid BYDAY value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("invalid BYDAY element at position {index}: {kind}")]
+pub struct InvalidByDayError {
+    /// The zero-based position of the offending element in the comma-separated list.
+    pub index: usize,
+    /// The reason the element was rejected.
+    pub kind: InvalidByDayErrorKind,
+}
+
+/// The reason a single BYDAY element was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidByDayErrorKind {
+    /// The element was empty.
+    #[error("expected a weekday")]
+    Empty,
+    /// The ordinal was malformed or outside the range 1 through 53.
+    #[error("expected an ordinal in the range 1 through 53")]
+    InvalidOrdinal,
+    /// The weekday code was not one of `MO`, `TU`, `WE`, `TH`, `FR`, `SA`, or `SU`.
+    #[error("expected one of MO, TU, WE, TH, FR, SA, or SU")]
+    InvalidWeekday,
+}
+
+const fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Monday => "MO",
+        Weekday::Tuesday => "TU",
+        Weekday::Wednesday => "WE",
+        Weekday::Thursday => "TH",
+        Weekday::Friday => "FR",
+        Weekday::Saturday => "SA",
+        Weekday::Sunday => "SU",
+    }
+}
+
+fn parse_weekday_num(s: &str) -> Result<WeekdayNum, InvalidByDayErrorKind> {
+    if s.is_empty() {
+        return Err(InvalidByDayErrorKind::Empty);
+    }
+
+    // the weekday code is always the last two bytes
+    let split = s
+        .len()
+        .checked_sub(2)
+        .filter(|&i| s.is_char_boundary(i))
+        .ok_or(InvalidByDayErrorKind::InvalidWeekday)?;
+    let (ordinal, code) = s.split_at(split);
+
+    let weekday = Weekday::iter()
+        .find(|&weekday| weekday_code(weekday).eq_ignore_ascii_case(code))
+        .ok_or(InvalidByDayErrorKind::InvalidWeekday)?;
+
+    let ordinal = match ordinal.as_bytes().first() {
+        None => None,
+        Some(first) => {
+            let (sign, digits) = match first {
+                b'+' => (Sign::Pos, &ordinal[1..]),
+                b'-' => (Sign::Neg, &ordinal[1..]),
+                _ => (Sign::Pos, ordinal),
+            };
+
+            if digits.is_empty() || digits.len() > 2 || !digits.bytes().all(|b| b.is_ascii_digit())
+            {
+                return Err(InvalidByDayErrorKind::InvalidOrdinal);
+            }
+
+            let week = digits
+                .parse::<u8>()
+                .ok()
+                .and_then(IsoWeek::from_index)
+                .ok_or(InvalidByDayErrorKind::InvalidOrdinal)?;
+
+            Some((sign, week))
+        }
+    };
+
+    Ok(WeekdayNum { ordinal, weekday })
+}
+
+impl WeekdayNumSet {
+    /// Returns the set of elements in `self` or `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        self.iter().chain(other).collect()
+    }
+
+    /// Returns the set of elements in both `self` and `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.iter().filter(|&x| other.contains(x)).collect()
+    }
+
+    /// Returns the set of elements in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        self.iter().filter(|&x| !other.contains(x)).collect()
+    }
+}
+
+impl FromIterator<WeekdayNum> for WeekdayNumSet {
+    fn from_iter<I: IntoIterator<Item = WeekdayNum>>(iter: I) -> Self {
+        let mut set = Self::default();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<WeekdayNum> for WeekdayNumSet {
+    fn extend<I: IntoIterator<Item = WeekdayNum>>(&mut self, iter: I) {
+        for weekday_num in iter {
+            self.insert(weekday_num);
+        }
+    }
+}
+
+impl IntoIterator for WeekdayNumSet {
+    type Item = WeekdayNum;
+    type IntoIter = WeekdayNumSetIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        WeekdayNumSetIntoIter(match self.0 {
+            InnerWDNSet::Small(set) => InnerIntoIter::Small(set.into_iter()),
+            InnerWDNSet::Large(set) => InnerIntoIter::Large {
+                set,
+                byte_index: 0,
+                day: 0,
+            },
+        })
+    }
+}
+
 impl<'a> IntoIterator for &'a WeekdayNumSet {
     type Item = WeekdayNum;
     type IntoIter = WeekdayNumSetIter<'a>;
@@ -131,24 +309,34 @@ impl Iterator for WeekdayNumSetIter<'_> {
                 set,
                 byte_index,
                 day,
-            } => {
-                while (*byte_index as usize) < set.0.len() {
-                    let byte = set.0[*byte_index as usize].get();
-                    while *day < 7 {
-                        let current_day = *day;
-                        *day += 1;
-                        let mask = 1 << current_day;
-                        if (byte & mask) != 0
-                            && let Some(wdn) = index_to_weekday_num((*byte_index, current_day))
-                        {
-                            return Some(wdn);
-                        }
-                    }
-                    *day = 0;
-                    *byte_index += 1;
-                }
-                None
-            }
+            } => set.next_from(byte_index, day),
+        }
+    }
+}
+
+/// An owning iterator over the [`WeekdayNum`] values in a [`WeekdayNumSet`].
+pub struct WeekdayNumSetIntoIter(InnerIntoIter);
+
+enum InnerIntoIter {
+    Small(std::collections::btree_set::IntoIter<WeekdayNum>),
+    Large {
+        set: Box<FixedWeekdayNumSet>,
+        byte_index: u8,
+        day: u8,
+    },
+}
+
+impl Iterator for WeekdayNumSetIntoIter {
+    type Item = WeekdayNum;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            InnerIntoIter::Small(iter) => iter.next(),
+            InnerIntoIter::Large {
+                set,
+                byte_index,
+                day,
+            } => set.next_from(byte_index, day),
         }
     }
 }
@@ -206,6 +394,27 @@ impl FixedWeekdayNumSet {
         let updated = unsafe { NonZero::new_unchecked(byte | mask) };
         self.0[byte_index as usize] = updated;
     }
+
+    /// Returns the first element at or after the `(byte_index, day)` cursor,
+    /// and advances the cursor past it.
+    fn next_from(&self, byte_index: &mut u8, day: &mut u8) -> Option<WeekdayNum> {
+        while (*byte_index as usize) < self.0.len() {
+            let byte = self.0[*byte_index as usize].get();
+            while *day < 7 {
+                let current_day = *day;
+                *day += 1;
+                let mask = 1 << current_day;
+                if (byte & mask) != 0
+                    && let Some(wdn) = index_to_weekday_num((*byte_index, current_day))
+                {
+                    return Some(wdn);
+                }
+            }
+            *day = 0;
+            *byte_index += 1;
+        }
+        None
+    }
 }
 
 impl Default for FixedWeekdayNumSet {
@@ -275,4 +484,58 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn byday_string_roundtrip() {
+        let set = WeekdayNumSet::from_byday_str("1MO,-1SU,FR").unwrap();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(WeekdayNum {
+            ordinal: Some((Sign::Neg, IsoWeek::W1)),
+            weekday: Weekday::Sunday,
+        }));
+        assert_eq!(set.to_byday_string(), "FR,-1SU,1MO");
+        assert_eq!(
+            WeekdayNumSet::from_byday_str(&set.to_byday_string()).unwrap(),
+            set
+        );
+    }
+
+    #[test]
+    fn byday_string_empty_roundtrip() {
+        let set = WeekdayNumSet::default();
+        assert_eq!(set.to_byday_string(), "");
+        assert_eq!(WeekdayNumSet::from_byday_str("").unwrap(), set);
+    }
+
+    #[test]
+    fn byday_string_accepts_sign_and_case() {
+        let set = WeekdayNumSet::from_byday_str("+53tu,-53Sa,we").unwrap();
+        assert_eq!(set.to_byday_string(), "WE,-53SA,53TU");
+    }
+
+    #[test]
+    fn byday_string_large_set_roundtrip() {
+        let mut set = WeekdayNumSet::with_capacity(64);
+        for byte in 0..=106 {
+            set.insert(index_to_weekday_num((byte, 3)).unwrap());
+        }
+
+        let s = set.to_byday_string();
+        assert_eq!(WeekdayNumSet::from_byday_str(&s).unwrap().len(), 107);
+    }
+
+    #[test]
+    fn byday_string_rejects_invalid_elements() {
+        let err = |s| WeekdayNumSet::from_byday_str(s).unwrap_err();
+
+        assert_eq!(err(",").kind, InvalidByDayErrorKind::Empty);
+        assert_eq!(err("MO,,TU").index, 1);
+        assert_eq!(err("0MO").kind, InvalidByDayErrorKind::InvalidOrdinal);
+        assert_eq!(err("54MO").kind, InvalidByDayErrorKind::InvalidOrdinal);
+        assert_eq!(err("+MO").kind, InvalidByDayErrorKind::InvalidOrdinal);
+        assert_eq!(err("001MO").kind, InvalidByDayErrorKind::InvalidOrdinal);
+        assert_eq!(err("1XX").kind, InvalidByDayErrorKind::InvalidWeekday);
+        assert_eq!(err("M").kind, InvalidByDayErrorKind::InvalidWeekday);
+        assert_eq!(err("1MÖ").kind, InvalidByDayErrorKind::InvalidWeekday);
+    }
 }
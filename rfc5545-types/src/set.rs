@@ -4,10 +4,47 @@
 //! All extensible enums are `#[non_exhaustive]` — callers that need to handle unknown
 //! values should wrap them with a discriminated union (e.g. `Token<T, S>`).
 
-use strum::{Display, EnumString};
+use strum::{Display, EnumString, IntoStaticStr, VariantArray};
+
+calendar_types::impl_token_parse!(
+    Method,
+    Encoding,
+    TimeTransparency,
+    EventStatus,
+    TodoStatus,
+    JournalStatus,
+    ClassValue,
+    CalendarUserType,
+    ParticipationRole,
+    ParticipationStatus,
+    FreeBusyType,
+    RelationshipType,
+    AlarmAction,
+    TriggerRelation,
+    ValueType,
+    DisplayType,
+    FeatureType,
+    ResourceType,
+    ParticipantType,
+    ProximityValue,
+    Status,
+);
 
 /// An iTIP method (RFC 5546 §1.4).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum Method {
@@ -53,6 +90,51 @@ impl Percent {
             _ => None,
         }
     }
+
+    /// Returns `self + rhs`, clamped to [`Percent::MAX`].
+    #[inline(always)]
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        match self.0 + rhs.0 {
+            sum @ 0..=100 => Self(sum),
+            _ => Self::MAX,
+        }
+    }
+
+    /// Returns `self - rhs`, clamped to [`Percent::MIN`].
+    #[inline(always)]
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl std::ops::Add for Percent {
+    type Output = Self;
+
+    /// Adds two percentages, saturating at 100.
+    fn add(self, rhs: Self) -> Self::Output {
+        self.saturating_add(rhs)
+    }
+}
+
+impl std::ops::AddAssign for Percent {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.saturating_add(rhs);
+    }
+}
+
+impl std::ops::Sub for Percent {
+    type Output = Self;
+
+    /// Subtracts two percentages, saturating at 0.
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.saturating_sub(rhs)
+    }
+}
+
+impl std::ops::SubAssign for Percent {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self.saturating_sub(rhs);
+    }
 }
 
 /// A priority value in the range `0..=9` (RFC 5545 §3.8.1.9).
@@ -82,20 +164,18 @@ pub enum PriorityClass {
     High,
 }
 
+/// Priorities are ordered by importance, so `A1` is the greatest value and `C3` the least
+/// defined value. The undefined priority `Zero` is less than every defined priority, so sorting
+/// in descending order lists the most important values first and undefined values last.
+impl Ord for Priority {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
 impl PartialOrd for Priority {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        if matches!(self, Self::Zero) || matches!(other, Self::Zero) {
-            None
-        } else {
-            let a = (*self) as u8;
-            let b = (*other) as u8;
-
-            match a.cmp(&b) {
-                std::cmp::Ordering::Less => Some(std::cmp::Ordering::Greater),
-                std::cmp::Ordering::Equal => Some(std::cmp::Ordering::Equal),
-                std::cmp::Ordering::Greater => Some(std::cmp::Ordering::Less),
-            }
-        }
+        Some(self.cmp(other))
     }
 }
 
@@ -115,6 +195,14 @@ impl Priority {
         matches!(self.into_class(), Some(PriorityClass::High))
     }
 
+    /// Returns the importance of this priority, from 0 for `Zero` (undefined) through 9 for `A1`.
+    const fn rank(self) -> u8 {
+        match self {
+            Self::Zero => 0,
+            _ => 10 - self as u8,
+        }
+    }
+
     /// Returns the [`PriorityClass`] of this priority, or `None` for `Zero` (undefined).
     pub const fn into_class(self) -> Option<PriorityClass> {
         match self {
@@ -146,7 +234,9 @@ pub struct Gregorian;
 // ============================================================================
 
 /// The possible values of the ENCODING parameter (RFC 5545 §3.2.7).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum Encoding {
@@ -163,7 +253,19 @@ pub enum Encoding {
 // ============================================================================
 
 /// The value of the TRANSP property (RFC 5545 §3.8.2.7).
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum TimeTransparency {
@@ -181,7 +283,9 @@ pub enum TimeTransparency {
 // ============================================================================
 
 /// The status of a VEVENT component (RFC 5545 §3.8.1.11).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum EventStatus {
@@ -194,7 +298,9 @@ pub enum EventStatus {
 }
 
 /// The status of a VTODO component (RFC 5545 §3.8.1.11).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum TodoStatus {
@@ -209,7 +315,9 @@ pub enum TodoStatus {
 }
 
 /// The status of a VJOURNAL component (RFC 5545 §3.8.1.11).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum JournalStatus {
@@ -226,7 +334,19 @@ pub enum JournalStatus {
 // ============================================================================
 
 /// The value of the CLASS property (RFC 5545 §3.8.1.3).
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum ClassValue {
@@ -244,7 +364,19 @@ pub enum ClassValue {
 // ============================================================================
 
 /// The CUTYPE parameter value (RFC 5545 §3.2.3).
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum CalendarUserType {
@@ -262,7 +394,19 @@ pub enum CalendarUserType {
 }
 
 /// The ROLE parameter value (RFC 5545 §3.2.16).
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum ParticipationRole {
@@ -278,7 +422,19 @@ pub enum ParticipationRole {
 }
 
 /// The PARTSTAT parameter value (RFC 5545 §3.2.12).
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    EnumString,
+    Display,
+    IntoStaticStr,
+    VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum ParticipationStatus {
@@ -300,7 +456,9 @@ pub enum ParticipationStatus {
 }
 
 /// The FBTYPE parameter value (RFC 5545 §3.2.9).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum FreeBusyType {
@@ -315,7 +473,9 @@ pub enum FreeBusyType {
 }
 
 /// The RELTYPE parameter value (RFC 5545 §3.2.15).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum RelationshipType {
@@ -331,7 +491,9 @@ pub enum RelationshipType {
 }
 
 /// The ACTION property value for alarms (RFC 5545 §3.8.6.1).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum AlarmAction {
@@ -344,7 +506,9 @@ pub enum AlarmAction {
 }
 
 /// The RELATED parameter value for TRIGGER (RFC 5545 §3.8.6.3).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum TriggerRelation {
@@ -355,7 +519,9 @@ pub enum TriggerRelation {
 }
 
 /// The VALUE parameter value (RFC 5545 §3.2.20).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum ValueType {
@@ -398,7 +564,9 @@ pub struct ThisAndFuture;
 // ============================================================================
 
 /// The DISPLAY parameter value (RFC 7986 §6.1).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum DisplayType {
@@ -413,7 +581,9 @@ pub enum DisplayType {
 }
 
 /// The FEATURE parameter value (RFC 7986 §6.3).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum FeatureType {
@@ -438,7 +608,9 @@ pub enum FeatureType {
 // ============================================================================
 
 /// The RESOURCE-TYPE property value (RFC 9073 §6.3).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum ResourceType {
@@ -453,7 +625,9 @@ pub enum ResourceType {
 }
 
 /// The PARTICIPANT-TYPE property value (RFC 9073 §6.2).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum ParticipantType {
@@ -484,7 +658,9 @@ pub enum ParticipantType {
 // ============================================================================
 
 /// A proximity value (RFC 9074 §8.1).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum ProximityValue {
@@ -503,7 +679,9 @@ pub enum ProximityValue {
 // ============================================================================
 
 /// A unified status value covering events, todos, and journals (RFC 5545 §3.8.1.11).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display, IntoStaticStr, VariantArray,
+)]
 #[non_exhaustive]
 #[strum(ascii_case_insensitive)]
 pub enum Status {
@@ -580,11 +758,13 @@ mod tests {
     use super::*;
 
     #[test]
-    #[allow(clippy::neg_cmp_op_on_partial_ord)]
-    fn priority_partial_ord() {
-        assert_eq!(Priority::Zero.partial_cmp(&Priority::Zero), None);
-        assert_eq!(Priority::Zero.partial_cmp(&Priority::A1), None);
-        assert_eq!(Priority::A1.partial_cmp(&Priority::Zero), None);
+    fn priority_ord() {
+        assert_eq!(
+            Priority::Zero.cmp(&Priority::Zero),
+            std::cmp::Ordering::Equal
+        );
+        assert!(Priority::Zero < Priority::A1);
+        assert!(Priority::Zero < Priority::C3);
 
         assert!(Priority::A1 > Priority::A2);
         assert!(Priority::A2 > Priority::A3);
@@ -594,9 +774,42 @@ mod tests {
         assert!(Priority::B1 > Priority::C1);
         assert!(Priority::A1 > Priority::C1);
 
-        assert!(!(Priority::Zero > Priority::C2));
-        assert!(!(Priority::Zero < Priority::C2));
         assert!(Priority::Zero != Priority::C2);
+
+        let mut priorities = [
+            Priority::B2,
+            Priority::Zero,
+            Priority::C3,
+            Priority::A1,
+            Priority::B1,
+        ];
+        priorities.sort_by(|a, b| b.cmp(a));
+        assert_eq!(
+            priorities,
+            [
+                Priority::A1,
+                Priority::B1,
+                Priority::B2,
+                Priority::C3,
+                Priority::Zero
+            ]
+        );
+    }
+
+    #[test]
+    fn percent_saturating_arithmetic() {
+        let percent = |value| Percent::new(value).unwrap();
+
+        let mut progress = percent(40);
+        progress += percent(50);
+        assert_eq!(progress, percent(90));
+        progress += percent(50);
+        assert_eq!(progress, Percent::MAX);
+        assert_eq!(percent(100) + percent(100), Percent::MAX);
+
+        progress -= percent(30);
+        assert_eq!(progress, percent(70));
+        assert_eq!(percent(10) - percent(20), Percent::MIN);
     }
 
     #[test]
@@ -1,13 +1,46 @@
 //! Basic time types.
+//!
+//! The [`Display`](std::fmt::Display) and [`FromStr`] implementations of [`UtcOffset`],
+//! [`Period`], [`RDate`], and [`TriggerValue`] use the text forms of RFC 5545 property values,
+//! such as `+0530` and `19970101T180000Z/PT5H30M`, so that these values can be written and read
+//! without the parser of an iCalendar library. Like those parsers, [`FromStr`] accepts durations
+//! with both week and day components.
+
+use std::{fmt, str::FromStr};
 
 use calendar_types::{
-    duration::{Duration, SignedDuration},
+    duration::{Duration, ExactDuration, NominalDuration, SignedDuration},
     primitive::Sign,
-    time::{Date, DateTime, Hour, Minute, NonLeapSecond, Utc},
+    time::{
+        Date, DateTime, Day, Hour, Local, Minute, Month, NonLeapSecond, Second, Time, Utc, Year,
+    },
 };
+use thiserror::Error;
 
 pub use calendar_types::time::TimeFormat;
 
+/// An error arising from parsing the RFC 5545 text form of a time value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[non_exhaustive]
+pub enum ParseTimeValueError {
+    /// The text is not a valid UTC-OFFSET value (RFC 5545 §3.3.14).
+    #[error("invalid UTC offset")]
+    UtcOffset,
+    /// The text is not a valid DATE value (RFC 5545 §3.3.4).
+    #[error("invalid date")]
+    Date,
+    /// The text is not a valid DATE-TIME value (RFC 5545 §3.3.5), or is not in UTC where UTC is
+    /// required.
+    #[error("invalid date-time")]
+    DateTime,
+    /// The text is not a valid DURATION value (RFC 5545 §3.3.6).
+    #[error("invalid duration")]
+    Duration,
+    /// The text is not a valid PERIOD value (RFC 5545 §3.3.9), since it has no `/`.
+    #[error("invalid period")]
+    Period,
+}
+
 /// Either a full datetime or a date-only value.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DateTimeOrDate<M = TimeFormat> {
@@ -54,23 +87,107 @@ pub struct UtcOffset {
     pub second: NonLeapSecond,
 }
 
-impl std::fmt::Display for UtcOffset {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl UtcOffset {
+    /// Returns the signed length of this offset in seconds.
+    pub fn as_seconds(&self) -> i32 {
+        let seconds = self.hour as i32 * 3600 + self.minute as i32 * 60 + self.second as i32;
+        seconds * self.sign as i32
+    }
+
+    /// Returns this offset as a [`SignedDuration`], which is the difference from a UTC time to
+    /// the corresponding local time.
+    pub fn as_signed_duration(&self) -> SignedDuration {
+        SignedDuration {
+            sign: self.sign,
+            duration: Duration::Exact(ExactDuration {
+                hours: self.hour as u32,
+                minutes: self.minute as u32,
+                seconds: self.second as u32,
+                frac: None,
+            }),
+        }
+    }
+
+    /// Converts a local date-time at this offset to UTC, returning `None` if the result is out of
+    /// range.
+    pub fn local_to_utc(&self, local: DateTime<Local>) -> Option<DateTime<Utc>> {
+        let mut duration = self.as_signed_duration();
+        duration.sign = match duration.sign {
+            Sign::Pos => Sign::Neg,
+            Sign::Neg => Sign::Pos,
+        };
+
+        let utc = local.checked_add_signed(duration)?;
+        Some(DateTime {
+            date: utc.date,
+            time: utc.time,
+            marker: Utc,
+        })
+    }
+
+    /// Converts a UTC date-time to the local date-time at this offset, returning `None` if the
+    /// result is out of range.
+    pub fn utc_to_local(&self, utc: DateTime<Utc>) -> Option<DateTime<Local>> {
+        let local = utc.checked_add_signed(self.as_signed_duration())?;
+        Some(DateTime {
+            date: local.date,
+            time: local.time,
+            marker: Local,
+        })
+    }
+}
+
+impl fmt::Display for UtcOffset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{}{:02}:{:02}",
+            "{}{:02}{:02}",
             self.sign.as_char(),
             self.hour as u8,
             self.minute as u8
         )?;
         let sec = self.second as u8;
         if sec != 0 {
-            write!(f, ":{sec:02}")?;
+            write!(f, "{sec:02}")?;
         }
         Ok(())
     }
 }
 
+impl FromStr for UtcOffset {
+    type Err = ParseTimeValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = ParseTimeValueError::UtcOffset;
+        let (sign, digits) = match s.split_at_checked(1) {
+            Some(("+", digits)) => (Sign::Pos, digits),
+            Some(("-", digits)) => (Sign::Neg, digits),
+            _ => return Err(error),
+        };
+        if !matches!(digits.len(), 4 | 6) {
+            return Err(error);
+        }
+
+        let offset = Self {
+            sign,
+            hour: Hour::new(number(&digits[..2]).ok_or(error)?).map_err(|_| error)?,
+            minute: Minute::new(number(&digits[2..4]).ok_or(error)?).map_err(|_| error)?,
+            second: match digits.get(4..) {
+                Some("") => NonLeapSecond::S00,
+                Some(second) => {
+                    NonLeapSecond::new(number(second).ok_or(error)?).map_err(|_| error)?
+                }
+                None => return Err(error),
+            },
+        };
+        // negative zero offsets are not permitted (RFC 5545 §3.3.14)
+        match offset.sign == Sign::Neg && offset.as_seconds() == 0 {
+            true => Err(error),
+            false => Ok(offset),
+        }
+    }
+}
+
 // ============================================================================
 // Period
 // ============================================================================
@@ -94,6 +211,39 @@ pub enum Period<M = TimeFormat> {
     },
 }
 
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Explicit { start, end } => {
+                write!(f, "{}/{}", BasicDateTime(start), BasicDateTime(end))
+            }
+            Self::Start { start, duration } => write!(f, "{}/{duration}", BasicDateTime(start)),
+        }
+    }
+}
+
+impl FromStr for Period {
+    type Err = ParseTimeValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s.split_once('/').ok_or(ParseTimeValueError::Period)?;
+        let start = parse_date_time(start)?;
+        if end.starts_with(['+', '-', 'P']) {
+            let duration = parse_duration(end)?;
+            match duration.sign {
+                Sign::Pos => Ok(Self::Start {
+                    start,
+                    duration: duration.duration,
+                }),
+                Sign::Neg => Err(ParseTimeValueError::Duration),
+            }
+        } else {
+            let end = parse_date_time(end)?;
+            Ok(Self::Explicit { start, end })
+        }
+    }
+}
+
 // ============================================================================
 // RDate / ExDate sequences
 // ============================================================================
@@ -106,6 +256,30 @@ pub enum RDate<M = TimeFormat> {
     Period(Period<M>),
 }
 
+impl fmt::Display for RDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DateTime(date_time) => BasicDateTime(date_time).fmt(f),
+            Self::Date(date) => BasicDate(date).fmt(f),
+            Self::Period(period) => period.fmt(f),
+        }
+    }
+}
+
+impl FromStr for RDate {
+    type Err = ParseTimeValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains('/') {
+            s.parse().map(Self::Period)
+        } else if s.contains('T') {
+            parse_date_time(s).map(Self::DateTime)
+        } else {
+            parse_date(s).map(Self::Date)
+        }
+    }
+}
+
 /// A homogeneous sequence of RDATE values.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RDateSeq<M = TimeFormat> {
@@ -133,3 +307,254 @@ pub enum TriggerValue {
     /// An absolute UTC datetime.
     DateTime(DateTime<Utc>),
 }
+
+impl fmt::Display for TriggerValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duration(duration) => duration.fmt(f),
+            Self::DateTime(date_time) => BasicDateTime(&DateTime {
+                date: date_time.date,
+                time: date_time.time,
+                marker: TimeFormat::Utc,
+            })
+            .fmt(f),
+        }
+    }
+}
+
+impl FromStr for TriggerValue {
+    type Err = ParseTimeValueError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with(['+', '-', 'P']) {
+            return parse_duration(s).map(Self::Duration);
+        }
+
+        let date_time = parse_date_time(s)?;
+        match date_time.marker {
+            TimeFormat::Utc => Ok(Self::DateTime(DateTime {
+                date: date_time.date,
+                time: date_time.time,
+                marker: Utc,
+            })),
+            TimeFormat::Local => Err(ParseTimeValueError::DateTime),
+        }
+    }
+}
+
+// ============================================================================
+// Text form helpers
+// ============================================================================
+
+/// Formats a date in the basic format of RFC 5545, as in `19970714`.
+struct BasicDate<'a>(&'a Date);
+
+impl fmt::Display for BasicDate<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let date = self.0;
+        write!(f, "{}{}{}", date.year(), date.month(), date.day())
+    }
+}
+
+/// Formats a date-time in the basic format of RFC 5545, as in `19970714T133000Z`.
+struct BasicDateTime<'a>(&'a DateTime<TimeFormat>);
+
+impl fmt::Display for BasicDateTime<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let DateTime { date, time, marker } = self.0;
+        write!(
+            f,
+            "{}T{}{}{}",
+            BasicDate(date),
+            time.hour(),
+            time.minute(),
+            time.second()
+        )?;
+        match marker {
+            TimeFormat::Utc => f.write_str("Z"),
+            TimeFormat::Local => Ok(()),
+        }
+    }
+}
+
+/// Parses a string of ASCII digits, which [`str::parse`] would also accept with a sign.
+fn number<T: FromStr>(digits: &str) -> Option<T> {
+    match !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        true => digits.parse().ok(),
+        false => None,
+    }
+}
+
+fn parse_date(s: &str) -> Result<Date, ParseTimeValueError> {
+    let error = ParseTimeValueError::Date;
+    if s.len() != 8 {
+        return Err(error);
+    }
+
+    let year = Year::new(number(&s[..4]).ok_or(error)?).map_err(|_| error)?;
+    let month = Month::new(number(&s[4..6]).ok_or(error)?).map_err(|_| error)?;
+    let day = Day::new(number(&s[6..]).ok_or(error)?).map_err(|_| error)?;
+    Date::new(year, month, day).map_err(|_| error)
+}
+
+fn parse_date_time(s: &str) -> Result<DateTime<TimeFormat>, ParseTimeValueError> {
+    let error = ParseTimeValueError::DateTime;
+    let (date, time) = s.split_once('T').ok_or(error)?;
+    let (time, marker) = match time.strip_suffix('Z') {
+        Some(time) => (time, TimeFormat::Utc),
+        None => (time, TimeFormat::Local),
+    };
+    if time.len() != 6 {
+        return Err(error);
+    }
+
+    let time = Time::new(
+        Hour::new(number(&time[..2]).ok_or(error)?).map_err(|_| error)?,
+        Minute::new(number(&time[2..4]).ok_or(error)?).map_err(|_| error)?,
+        Second::new(number(&time[4..]).ok_or(error)?).map_err(|_| error)?,
+        None,
+    )
+    .map_err(|_| error)?;
+    Ok(DateTime {
+        date: parse_date(date).map_err(|_| error)?,
+        time,
+        marker,
+    })
+}
+
+fn parse_duration(s: &str) -> Result<SignedDuration, ParseTimeValueError> {
+    let error = ParseTimeValueError::Duration;
+    let (sign, s) = match s.split_at_checked(1) {
+        Some(("+", rest)) => (Sign::Pos, rest),
+        Some(("-", rest)) => (Sign::Neg, rest),
+        _ => (Sign::Pos, s),
+    };
+    let s = s.strip_prefix('P').ok_or(error)?;
+    let (date, time) = s.split_once('T').unwrap_or((s, ""));
+
+    // each component is a number followed by its designator, in the order of `designators`
+    let components = |mut s: &str, designators: &[char]| {
+        let mut values = vec![None; designators.len()];
+        for (value, designator) in values.iter_mut().zip(designators) {
+            if let Some((digits, rest)) = s.split_once(*designator) {
+                *value = Some(number::<u32>(digits).ok_or(error)?);
+                s = rest;
+            }
+        }
+        match s.is_empty() {
+            true => Ok(values),
+            false => Err(error),
+        }
+    };
+    let date = components(date, &['W', 'D'])?;
+    let exact = match s.contains('T') {
+        true => match components(time, &['H', 'M', 'S'])?[..] {
+            [None, None, None] | [Some(_), None, Some(_)] => return Err(error),
+            [hours, minutes, seconds] => Some(ExactDuration {
+                hours: hours.unwrap_or(0),
+                minutes: minutes.unwrap_or(0),
+                seconds: seconds.unwrap_or(0),
+                frac: None,
+            }),
+            _ => unreachable!(),
+        },
+        false => None,
+    };
+
+    let duration = match (&date[..], exact) {
+        (&[None, None], Some(exact)) => Duration::Exact(exact),
+        (&[None, None], None) => return Err(error),
+        (&[weeks, days], exact) => Duration::Nominal(NominalDuration {
+            weeks: weeks.unwrap_or(0),
+            days: days.unwrap_or(0),
+            exact,
+        }),
+        _ => unreachable!(),
+    };
+    Ok(SignedDuration { sign, duration })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip<T>(s: &str) -> String
+    where
+        T: FromStr<Err = ParseTimeValueError> + fmt::Display,
+    {
+        s.parse::<T>().unwrap().to_string()
+    }
+
+    #[test]
+    fn utc_offset_text() {
+        assert_eq!(round_trip::<UtcOffset>("+0530"), "+0530");
+        assert_eq!(round_trip::<UtcOffset>("-083015"), "-083015");
+        assert_eq!(round_trip::<UtcOffset>("+000000"), "+0000");
+        assert!("-0000".parse::<UtcOffset>().is_err());
+        assert!("+05:30".parse::<UtcOffset>().is_err());
+        assert!("0530".parse::<UtcOffset>().is_err());
+    }
+
+    #[test]
+    fn period_and_rdate_text() {
+        for s in [
+            "19970101T180000Z/19970102T070000Z",
+            "19970101T180000Z/PT5H30M",
+            "19970101T180000/P1W",
+        ] {
+            assert_eq!(round_trip::<Period>(s), s);
+            assert_eq!(round_trip::<RDate>(s), s);
+        }
+        assert_eq!(round_trip::<RDate>("19970714"), "19970714");
+        assert_eq!(round_trip::<RDate>("19970714T123000"), "19970714T123000");
+        assert!(matches!("19970714".parse(), Ok(RDate::Date(_))));
+
+        assert_eq!(
+            "19970101T180000Z".parse::<Period>(),
+            Err(ParseTimeValueError::Period)
+        );
+        assert_eq!(
+            "19970101T180000Z/-PT1H".parse::<Period>(),
+            Err(ParseTimeValueError::Duration)
+        );
+        assert_eq!("19970230".parse::<RDate>(), Err(ParseTimeValueError::Date));
+        assert_eq!(
+            "19970714T1230".parse::<RDate>(),
+            Err(ParseTimeValueError::DateTime)
+        );
+    }
+
+    #[test]
+    fn trigger_text() {
+        assert_eq!(round_trip::<TriggerValue>("-PT15M"), "-PT15M");
+        assert_eq!(round_trip::<TriggerValue>("+P1DT2H"), "P1DT2H");
+        assert_eq!(round_trip::<TriggerValue>("PT0S"), "PT0S");
+        assert_eq!(
+            round_trip::<TriggerValue>("19980101T050000Z"),
+            "19980101T050000Z"
+        );
+        assert!(matches!(
+            "P1W2D".parse(),
+            Ok(TriggerValue::Duration(SignedDuration {
+                sign: Sign::Pos,
+                duration: Duration::Nominal(NominalDuration {
+                    weeks: 1,
+                    days: 2,
+                    exact: None
+                }),
+            }))
+        ));
+
+        for s in [
+            "19980101T050000",
+            "P",
+            "PT",
+            "PT1H2S",
+            "P1S",
+            "PT1M2H",
+            "-P-1D",
+        ] {
+            assert!(s.parse::<TriggerValue>().is_err(), "{s}");
+        }
+    }
+}
@@ -0,0 +1,399 @@
+//! Time zone transitions derived from VTIMEZONE rules.
+//!
+//! A VTIMEZONE component (RFC 5545 §3.6.5) and a JSCalendar `TimeZone` object (RFC 8984 §4.7.2)
+//! both describe a time zone by observances: rules which give the onsets of a UTC offset by a
+//! start date-time together with recurrence rules and additional dates. [`vtimezone_to_transitions`]
+//! expands a set of such [`Observance`]s into the chronological list of [`Transition`]s between
+//! their offsets, from which the offset at any instant or local time can be determined.
+//!
+//! Only the recurrence rules used by time zone definitions in practice are supported: YEARLY
+//! rules whose BYDAY and BYMONTHDAY parts select days within the months of their BYMONTH part,
+//! such as `FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU`. Other rules produce an
+//! [`UnsupportedRule`](TransitionError::UnsupportedRule) error.
+
+use calendar_types::{
+    primitive::Sign,
+    set::Token,
+    time::{Date, DateTime, Day, Local, Month, TimeFormat, Utc, Year},
+};
+use thiserror::Error;
+
+use crate::{
+    rrule::{FreqByRules, MonthDay, MonthDaySetIndex, RRule, Rscale, Termination, WeekdayNum},
+    time::{DateTimeOrDate, UtcOffset},
+};
+
+/// An observance of a time zone, such as a STANDARD or DAYLIGHT component of a VTIMEZONE.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observance<'a> {
+    /// The first onset of the observance, in terms of the offset before it.
+    pub start: DateTime<Local>,
+    /// The UTC offset before each onset.
+    pub offset_from: UtcOffset,
+    /// The UTC offset after each onset.
+    pub offset_to: UtcOffset,
+    /// The recurrence rules generating further onsets from `start`.
+    pub rrules: Vec<&'a RRule>,
+    /// Additional onsets of the observance.
+    pub rdates: Vec<DateTime<Local>>,
+    /// Onsets which are excluded from those generated by `rrules`.
+    pub exdates: Vec<DateTime<Local>>,
+}
+
+/// A change of UTC offset between the observances of a time zone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    /// The instant of the transition.
+    pub utc: DateTime<Utc>,
+    /// The local time of the transition, in terms of the offset before it.
+    pub local: DateTime<Local>,
+    /// The UTC offset before the transition.
+    pub offset_from: UtcOffset,
+    /// The UTC offset after the transition.
+    pub offset_to: UtcOffset,
+    /// The index of the observance which begins at the transition.
+    pub observance: usize,
+}
+
+/// An error arising from the expansion of time zone observances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum TransitionError {
+    /// A recurrence rule of the observance at the given index cannot be evaluated.
+    #[error("the recurrence rule of observance {0} is not supported")]
+    UnsupportedRule(usize),
+    /// The instant of a transition lies outside the range of representable dates.
+    #[error("the transition is out of range")]
+    OutOfRange,
+}
+
+/// Returns the transitions between `observances` whose local times fall in or before the year
+/// `horizon`, sorted by their instants. If several observances begin at the same instant, only
+/// the first of them is kept.
+pub fn vtimezone_to_transitions(
+    observances: &[Observance<'_>],
+    horizon: Year,
+) -> Result<Vec<Transition>, TransitionError> {
+    let mut transitions = Vec::new();
+    for (index, observance) in observances.iter().enumerate() {
+        let onsets =
+            onsets(observance, horizon).map_err(|()| TransitionError::UnsupportedRule(index))?;
+
+        for local in onsets {
+            transitions.push(Transition {
+                utc: observance
+                    .offset_from
+                    .local_to_utc(local)
+                    .ok_or(TransitionError::OutOfRange)?,
+                local,
+                offset_from: observance.offset_from,
+                offset_to: observance.offset_to,
+                observance: index,
+            });
+        }
+    }
+
+    transitions.sort_by_key(|transition| transition.utc);
+    transitions.dedup_by_key(|transition| transition.utc);
+    Ok(transitions)
+}
+
+/// Returns the onsets of `observance` whose years are at most `horizon`, or `Err(())` if one of
+/// its recurrence rules is not supported.
+fn onsets(observance: &Observance<'_>, horizon: Year) -> Result<Vec<DateTime<Local>>, ()> {
+    let mut onsets = vec![observance.start];
+    for rrule in &observance.rrules {
+        expand(rrule, observance, horizon, &mut onsets)?;
+    }
+
+    onsets.retain(|onset| !observance.exdates.contains(onset));
+    onsets.extend_from_slice(&observance.rdates);
+    onsets.retain(|onset| onset.date.year() <= horizon);
+    onsets.sort();
+    onsets.dedup();
+    Ok(onsets)
+}
+
+/// Appends the occurrences of `rrule` after the start of `observance` in years up to `horizon` to
+/// `onsets`, or returns `Err(())` if `rrule` is not supported.
+fn expand(
+    rrule: &RRule,
+    observance: &Observance<'_>,
+    horizon: Year,
+    onsets: &mut Vec<DateTime<Local>>,
+) -> Result<(), ()> {
+    let FreqByRules::Yearly(yearly) = &rrule.freq else {
+        return Err(());
+    };
+
+    let core = &rrule.core_by_rules;
+    let unsupported = yearly.by_year_day.is_some()
+        || yearly.by_week_no.is_some()
+        || core.by_second.is_some()
+        || core.by_minute.is_some()
+        || core.by_hour.is_some()
+        || core.by_set_pos.is_some()
+        || (core.by_day.is_some() && core.by_month.is_none())
+        || rrule
+            .rscale
+            .as_ref()
+            .is_some_and(|rscale| *rscale != Token::Known(Rscale::Gregorian));
+
+    if unsupported {
+        return Err(());
+    }
+
+    let start = observance.start;
+    let interval = rrule.interval.unwrap_or_default().get().get();
+    let ends = |onset: DateTime<Local>| match rrule.termination {
+        Some(Termination::Until(DateTimeOrDate::Date(date))) => onset.date > date,
+        Some(Termination::Until(DateTimeOrDate::DateTime(until))) => match until.marker {
+            TimeFormat::Local => onset > with_marker(until, Local),
+            TimeFormat::Utc => observance
+                .offset_from
+                .local_to_utc(onset)
+                .is_some_and(|onset| onset > with_marker(until, Utc)),
+        },
+        _ => false,
+    };
+
+    // the start is always the first occurrence, and counts towards the COUNT part
+    let mut count = 1;
+    let mut year = start.date.year().get() as u64;
+    while year <= horizon.get() as u64 {
+        let Ok(current) = Year::new(year as u16) else {
+            break;
+        };
+
+        for date in dates_in_year(rrule, start.date, current) {
+            let onset = DateTime {
+                date,
+                time: start.time,
+                marker: Local,
+            };
+
+            if onset <= start {
+                continue;
+            }
+
+            let exhausted = match rrule.termination {
+                Some(Termination::Count(limit)) => count >= limit,
+                _ => false,
+            };
+
+            if exhausted || ends(onset) {
+                return Ok(());
+            }
+
+            onsets.push(onset);
+            count += 1;
+        }
+
+        year += interval;
+    }
+
+    Ok(())
+}
+
+/// Returns the dates selected by the BYMONTH, BYDAY, and BYMONTHDAY parts of the YEARLY rule
+/// `rrule` in `year`, in ascending order. Parts which are absent default to the month and day of
+/// `start`.
+fn dates_in_year(rrule: &RRule, start: Date, year: Year) -> Vec<Date> {
+    let core = &rrule.core_by_rules;
+    let by_month_day = match &rrule.freq {
+        FreqByRules::Yearly(yearly) => yearly.by_month_day,
+        _ => None,
+    };
+
+    let months: Vec<Month> = match core.by_month {
+        Some(months) => Month::iter().filter(|&month| months.get(month)).collect(),
+        None => vec![start.month()],
+    };
+
+    let mut dates = Vec::new();
+    for month in months {
+        let days = month.days_in(year);
+        let in_month = |day: u8| {
+            let date = Date::new(year, month, Day::new(day).ok()?).ok()?;
+            Some(date)
+        };
+
+        let mut candidates: Vec<Date> = match &core.by_day {
+            Some(by_day) => by_day
+                .iter()
+                .flat_map(|weekday_num| weekdays_in_month(weekday_num, year, month))
+                .collect(),
+            None if by_month_day.is_some() => (1..=days).filter_map(in_month).collect(),
+            None => in_month(start.day() as u8).into_iter().collect(),
+        };
+
+        if let Some(set) = by_month_day {
+            candidates.retain(|date| {
+                let day = date.day() as u8;
+                let positive = MonthDay::from_repr(day).map(|day| (Sign::Pos, day));
+                let negative = MonthDay::from_repr(days - day + 1).map(|day| (Sign::Neg, day));
+                positive
+                    .into_iter()
+                    .chain(negative)
+                    .any(|(sign, day)| set.get(MonthDaySetIndex::from_signed_month_day(sign, day)))
+            });
+        }
+
+        dates.extend(candidates);
+    }
+
+    dates.sort();
+    dates.dedup();
+    dates
+}
+
+/// Returns the dates in `month` of `year` selected by `weekday_num`.
+fn weekdays_in_month(weekday_num: WeekdayNum, year: Year, month: Month) -> Vec<Date> {
+    let first = Date::new(year, month, Day::D01).expect("every month has a first day");
+    let skip = (weekday_num.weekday as i64 - first.weekday() as i64).rem_euclid(7);
+
+    let all: Vec<Date> = (0..5)
+        .filter_map(|week| first.checked_add_days(skip + 7 * week))
+        .filter(|date| date.month() == month)
+        .collect();
+
+    match weekday_num.ordinal {
+        None => all,
+        Some((sign, week)) => {
+            let n = week as usize;
+            let index = match sign {
+                Sign::Pos => n.checked_sub(1),
+                Sign::Neg => all.len().checked_sub(n),
+            };
+
+            index
+                .and_then(|index| all.get(index))
+                .copied()
+                .into_iter()
+                .collect()
+        }
+    }
+}
+
+fn with_marker<M, N>(dt: DateTime<M>, marker: N) -> DateTime<N> {
+    DateTime {
+        date: dt.date,
+        time: dt.time,
+        marker,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use calendar_types::time::{Hour, IsoWeek, Minute, Second, Time, Weekday};
+
+    use crate::rrule::{MonthSet, YearlyByRules, weekday_num_set::WeekdayNumSet};
+
+    fn local(y: u16, m: u8, d: u8) -> DateTime<Local> {
+        DateTime {
+            date: Date::new(
+                Year::new(y).unwrap(),
+                Month::new(m).unwrap(),
+                Day::new(d).unwrap(),
+            )
+            .unwrap(),
+            time: Time::new(Hour::H02, Minute::M00, Second::S00, None).unwrap(),
+            marker: Local,
+        }
+    }
+
+    fn offset(hours: u8) -> UtcOffset {
+        UtcOffset {
+            sign: Sign::Neg,
+            hour: Hour::new(hours).unwrap(),
+            minute: Minute::M00,
+            second: Default::default(),
+        }
+    }
+
+    /// `FREQ=YEARLY;BYMONTH=3;BYDAY=2SU`, the onset of daylight time in the United States.
+    fn second_sunday_of_march() -> RRule {
+        let mut months = MonthSet::default();
+        months.set(Month::Mar);
+        let mut days = WeekdayNumSet::default();
+        days.insert(WeekdayNum {
+            ordinal: Some((Sign::Pos, IsoWeek::W2)),
+            weekday: Weekday::Sunday,
+        });
+
+        let mut rrule = RRule {
+            freq: FreqByRules::Yearly(YearlyByRules::default()),
+            core_by_rules: Default::default(),
+            interval: None,
+            termination: None,
+            week_start: None,
+            rscale: None,
+            skip: None,
+            extras: Vec::new(),
+        };
+        rrule.core_by_rules.by_month = Some(months);
+        rrule.core_by_rules.by_day = Some(days);
+        rrule
+    }
+
+    fn daylight(rrule: &RRule) -> Observance<'_> {
+        Observance {
+            start: local(2007, 3, 11),
+            offset_from: offset(5),
+            offset_to: offset(4),
+            rrules: vec![rrule],
+            rdates: Vec::new(),
+            exdates: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn expands_yearly_rules() {
+        let rrule = second_sunday_of_march();
+        let transitions =
+            vtimezone_to_transitions(&[daylight(&rrule)], Year::new(2010).unwrap()).unwrap();
+
+        let onsets: Vec<_> = transitions.iter().map(|t| t.local).collect();
+        assert_eq!(
+            onsets,
+            [
+                local(2007, 3, 11),
+                local(2008, 3, 9),
+                local(2009, 3, 8),
+                local(2010, 3, 14)
+            ]
+        );
+        assert_eq!(transitions[0].utc.time.hour(), Hour::H07);
+        assert!(transitions.iter().all(|t| t.observance == 0));
+    }
+
+    #[test]
+    fn applies_count_rdates_and_exdates() {
+        let mut rrule = second_sunday_of_march();
+        rrule.termination = Some(Termination::Count(3));
+        let mut observance = daylight(&rrule);
+        observance.rdates.push(local(2012, 4, 1));
+        observance.exdates.push(local(2008, 3, 9));
+
+        let transitions =
+            vtimezone_to_transitions(&[observance], Year::new(2012).unwrap()).unwrap();
+        let onsets: Vec<_> = transitions.iter().map(|t| t.local).collect();
+        assert_eq!(
+            onsets,
+            [local(2007, 3, 11), local(2009, 3, 8), local(2012, 4, 1)]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_rules() {
+        let mut rrule = second_sunday_of_march();
+        rrule.freq = FreqByRules::Weekly;
+        let supported = second_sunday_of_march();
+
+        let observances = [daylight(&supported), daylight(&rrule)];
+        assert_eq!(
+            vtimezone_to_transitions(&observances, Year::new(2010).unwrap()),
+            Err(TransitionError::UnsupportedRule(1))
+        );
+    }
+}
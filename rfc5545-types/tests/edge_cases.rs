@@ -402,3 +402,87 @@ fn hour_set_full_iteration() {
         assert!(set.get(h));
     }
 }
+
+// ── Set operations and iterators ─────────────────────────────────────
+
+#[test]
+fn bitset_iteration_is_ascending() {
+    let set: SecondSet = [Second::S60, Second::S0, Second::S30].into_iter().collect();
+    assert_eq!(set.len(), 3);
+    assert_eq!(set.iter().len(), 3);
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        [Second::S0, Second::S30, Second::S60]
+    );
+
+    let full: HourSet = Hour::iter().collect();
+    assert_eq!(full.len(), 24);
+    assert!(full.iter().eq(Hour::iter()));
+
+    let months: MonthSet = [Month::Dec, Month::Jan].into_iter().collect();
+    assert_eq!(months.into_iter().collect::<Vec<_>>(), [Month::Jan, Month::Dec]);
+    assert_eq!(MonthSet::default().iter().next(), None);
+}
+
+#[test]
+fn bitset_set_operations() {
+    let a: MinuteSet = [Minute::M0, Minute::M15, Minute::M30].into_iter().collect();
+    let b: MinuteSet = [Minute::M30, Minute::M45].into_iter().collect();
+
+    let minutes = |set: MinuteSet| set.iter().collect::<Vec<_>>();
+    assert_eq!(
+        minutes(a.union(&b)),
+        [Minute::M0, Minute::M15, Minute::M30, Minute::M45]
+    );
+    assert_eq!(minutes(a.intersection(&b)), [Minute::M30]);
+    assert_eq!(minutes(a.difference(&b)), [Minute::M0, Minute::M15]);
+    assert!(b.difference(&a.union(&b)).is_empty());
+    assert!(a.contains(Minute::M15) && !b.contains(Minute::M15));
+}
+
+#[test]
+fn signed_bitset_indices_round_trip() {
+    let days: MonthDaySet = [(Sign::Neg, MonthDay::D1), (Sign::Pos, MonthDay::D31)]
+        .into_iter()
+        .map(|(sign, day)| MonthDaySetIndex::from_signed_month_day(sign, day))
+        .collect();
+    assert_eq!(
+        days
+            .iter()
+            .map(MonthDaySetIndex::to_signed_month_day)
+            .collect::<Vec<_>>(),
+        [(Sign::Pos, MonthDay::D31), (Sign::Neg, MonthDay::D1)]
+    );
+
+    let weeks: WeekNoSet = [(Sign::Neg, IsoWeek::W53), (Sign::Pos, IsoWeek::W1)]
+        .into_iter()
+        .map(|(sign, week)| WeekNoSetIndex::from_signed_week(sign, week))
+        .collect();
+    assert_eq!(
+        weeks
+            .iter()
+            .map(WeekNoSetIndex::to_signed_week)
+            .collect::<Vec<_>>(),
+        [(Sign::Pos, IsoWeek::W1), (Sign::Neg, IsoWeek::W53)]
+    );
+}
+
+#[test]
+fn weekday_num_set_operations() {
+    let a = WeekdayNumSet::from_byday_str("MO,TU,1FR").unwrap();
+    let b = WeekdayNumSet::from_byday_str("TU,-1SU").unwrap();
+
+    assert_eq!(a.union(&b).to_byday_string(), "MO,TU,-1SU,1FR");
+    assert_eq!(a.intersection(&b).to_byday_string(), "TU");
+    assert_eq!(a.difference(&b).to_byday_string(), "MO,1FR");
+
+    let large: WeekdayNumSet = (1..=40)
+        .map(|week| WeekdayNum {
+            ordinal: Some((Sign::Pos, IsoWeek::from_index(week).unwrap())),
+            weekday: Weekday::Monday,
+        })
+        .collect();
+    assert_eq!(large.len(), 40);
+    assert_eq!(large.clone().into_iter().count(), 40);
+    assert_eq!(large.intersection(&a).len(), 0);
+}